@@ -579,6 +579,41 @@ impl<K: Hash + Eq, V> FrameCache<K, V> {
     unsafe { Some((key.assume_init(), val.assume_init())) }
   }
 
+  /// Removes and returns a key-value pair that has not been hit in the current
+  /// frame, the pairs hit in the current frame are kept. Returns `None` if all
+  /// pairs are hit in the current frame.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use ribir_algo::FrameCache;
+  /// let mut cache = FrameCache::new();
+  ///
+  /// cache.put(1, "a");
+  /// cache.put(2, "b");
+  /// assert_eq!(cache.pop_unused(), None);
+  ///
+  /// cache.end_frame("example");
+  /// cache.get(&1);
+  /// assert_eq!(cache.pop_unused(), Some((2, "b")));
+  /// assert_eq!(cache.pop_unused(), None);
+  /// assert_eq!(cache.len(), 1);
+  /// ```
+  pub fn pop_unused(&mut self) -> Option<(K, V)> {
+    // The entries after the `tail` sigil are not hit in the current frame.
+    let node_ptr = unsafe { (*self.tail).next };
+    if node_ptr.is_null() {
+      return None;
+    }
+
+    let old_key = KeyRef { k: unsafe { &(*(*node_ptr).key.as_ptr()) } };
+    self.map.remove(&old_key).unwrap();
+    self.detach(node_ptr);
+    let node = unsafe { *Box::from_raw(node_ptr) };
+    let LruEntry { key, val, .. } = node;
+    unsafe { Some((key.assume_init(), val.assume_init())) }
+  }
+
   /// Marks the key as the most recently used one.
   ///
   /// # Example
@@ -1771,4 +1806,27 @@ mod tests {
     cache.end_frame("");
     cache.end_frame("");
   }
+
+  #[test]
+  fn pop_unused_keep_current_frame() {
+    let mut cache = FrameCache::new();
+    for i in 0..4 {
+      cache.put(i, i);
+    }
+    cache.end_frame("");
+    cache.get(&1);
+    cache.put(4, 4);
+
+    let mut unused = std::iter::from_fn(|| cache.pop_unused())
+      .map(|(k, _)| k)
+      .collect::<Vec<_>>();
+    unused.sort();
+    assert_eq!(unused, [0, 2, 3]);
+    assert!(cache.contains(&1));
+    assert!(cache.contains(&4));
+
+    // the pairs hit in the current frame are kept by the next frame.
+    cache.end_frame("");
+    assert_eq!(cache.len(), 2);
+  }
 }
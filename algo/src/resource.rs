@@ -28,6 +28,18 @@ impl<T: Sized> Resource<T> {
     let ptr: triomphe::Arc<dyn Any> = unsafe { triomphe::Arc::from_raw(ptr) };
     Resource(ptr)
   }
+
+  /// The same as [`Resource::into_any`], but the resource can be shared across
+  /// threads.
+  #[inline]
+  pub fn into_any_send(self) -> Resource<dyn Any + Send + Sync>
+  where
+    T: Sized + Any + Send + Sync,
+  {
+    let ptr = triomphe::Arc::into_raw(self.0) as *const (dyn Any + Send + Sync);
+    let ptr: triomphe::Arc<dyn Any + Send + Sync> = unsafe { triomphe::Arc::from_raw(ptr) };
+    Resource(ptr)
+  }
 }
 
 impl<T: ?Sized> Resource<T> {
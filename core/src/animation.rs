@@ -14,6 +14,10 @@ mod stagger;
 pub use stagger::Stagger;
 mod keyframes;
 pub use keyframes::*;
+mod spring;
+pub use spring::Spring;
+mod time_scale;
+pub use time_scale::AnimationTimeScale;
 
 ///  Trait to describe how to control the animation.
 pub trait Animation {
@@ -13,16 +13,27 @@ where
   #[declare(strict)]
   pub state: S,
   pub from: S::Value,
+  /// If true, the animation runs at the normal speed regardless of the global
+  /// [`AnimationTimeScale`] and the reduced motion preference. Use it only for
+  /// the animation that is essential to convey information.
+  #[declare(default)]
+  pub ignore_time_scale: bool,
   #[declare(skip)]
   running_info: Option<AnimateInfo<S::Value>>,
   #[declare(skip, default = BuildCtx::get().window().id())]
   window_id: WindowId,
 }
 
+/// The time span to sample the velocity of an interrupted animation.
+const VELOCITY_SAMPLE: Duration = Duration::from_millis(1);
+
 pub(crate) struct AnimateInfo<V> {
   from: V,
   to: V,
-  start_at: Instant,
+  /// The elapsed time of the animation timeline, it's scaled by the time
+  /// scale.
+  elapsed: Duration,
+  last_at: Instant,
   last_progress: AnimateProgress,
   // Determines if lerp value in current frame.
   already_lerp: bool,
@@ -47,13 +58,36 @@ where
 
     let new_to = this.state.get();
 
-    if let Some(AnimateInfo { from, to, last_progress, start_at, .. }) = &mut this.running_info {
-      *from = this
-        .state
-        .calc_lerp_value(from, to, last_progress.value());
+    if let Some(AnimateInfo { from, to, last_progress, elapsed, last_at, .. }) =
+      &mut this.running_info
+    {
+      let rate = last_progress.value();
+      let current = this.state.calc_lerp_value(from, to, rate);
+      // Carry the velocity over to the new run, it's sampled from the
+      // transition and mapped to the progress of the new run.
+      let velocity = if let AnimateProgress::Between(_) = last_progress {
+        let next = match this
+          .transition
+          .rate_of_change(*elapsed + VELOCITY_SAMPLE)
+        {
+          AnimateProgress::Between(p) => p,
+          AnimateProgress::Dismissed => 0.,
+          AnimateProgress::Finish => 1.,
+        };
+        let next = this.state.calc_lerp_value(from, to, next);
+        this
+          .state
+          .calc_inverse_lerp(&current, &new_to, &next)
+          .map_or(0., |p| p / VELOCITY_SAMPLE.as_secs_f32())
+      } else {
+        0.
+      };
+      this.transition.carry_velocity(velocity);
+      *from = current;
       *to = new_to;
       *last_progress = AnimateProgress::Between(0.);
-      *start_at = Instant::now();
+      *elapsed = Duration::ZERO;
+      *last_at = FrameClock::now();
     } else {
       drop(animate_ref);
 
@@ -106,10 +140,12 @@ where
         })
         .unsubscribe_when_dropped();
 
+      this.transition.carry_velocity(0.);
       this.running_info = Some(AnimateInfo {
         from: this.from.clone(),
         to: new_to,
-        start_at: Instant::now(),
+        elapsed: Duration::ZERO,
+        last_at: FrameClock::now(),
         last_progress: AnimateProgress::Dismissed,
         _tick_msg_guard: Some(Box::new((tick_handle, state_handle))),
         already_lerp: false,
@@ -138,15 +174,47 @@ where
   S: AnimateState + 'static,
 {
   pub fn is_running(&self) -> bool { self.running_info.is_some() }
+}
+
+impl<S> Animate<KeyFrames<S>>
+where
+  S: AnimateStateSetter + 'static,
+  S::Value: Lerp,
+{
+  /// Create an animation that plays the `frames` of the `state` by the
+  /// `transition`, it starts from the current value of the `state`. Use a
+  /// [`RepeatTransition`] as the transition to repeat or reverse it.
+  ///
+  /// It must be called when building a widget, and the animation is not
+  /// started until you run it.
+  pub fn keyframes(
+    state: S, frames: impl IntoIterator<Item = KeyFrame<S::Value>>,
+    transition: impl Transition + 'static,
+  ) -> Stateful<Self> {
+    let from = state.get();
+    Animate::declarer()
+      .transition(Box::new(transition))
+      .from(from)
+      .state(KeyFrames::new(state, frames.into_iter().collect()))
+      .finish()
+      .as_stateful()
+      .clone_writer()
+  }
+}
 
+impl<S> Animate<S>
+where
+  S: AnimateState + 'static,
+{
   /// Advance the animation to the given time, you must start the animation
-  /// before calling this method, the `at` relative to the start time.
+  /// before calling this method. The time passed since the last advance is
+  /// scaled by the global [`AnimationTimeScale`].
   ///
   /// ## Panics
   ///
   /// Panics if the animation is not running.
   fn advance_to(&mut self, at: Instant) -> AnimateProgress {
-    let AnimateInfo { from, to, start_at, last_progress, already_lerp, .. } = self
+    let AnimateInfo { from, to, elapsed, last_at, last_progress, already_lerp, .. } = self
       .running_info
       .as_mut()
       .expect("This animation is not running.");
@@ -155,8 +223,22 @@ where
       return *last_progress;
     }
 
-    let elapsed = at - *start_at;
-    let progress = self.transition.rate_of_change(elapsed);
+    let scale = if self.ignore_time_scale {
+      1.
+    } else {
+      AppCtx::animation_time_scale()
+        .read()
+        .effective_scale()
+    };
+    let progress = if scale == 0. {
+      AnimateProgress::Finish
+    } else {
+      *elapsed += at
+        .saturating_duration_since(*last_at)
+        .mul_f32(scale);
+      self.transition.rate_of_change(*elapsed)
+    };
+    *last_at = at;
 
     let v = match progress {
       AnimateProgress::Between(rate) => self.state.calc_lerp_value(from, to, rate),
@@ -187,6 +269,11 @@ where
 
 #[cfg(test)]
 mod tests {
+  use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+  };
+
   use super::*;
   use crate::{reset_test_env, test_helper::TestWindow};
 
@@ -235,4 +322,142 @@ mod tests {
     wnd.draw_frame();
     assert_eq!(*c_state.read(), 1);
   }
+
+  #[test]
+  fn animate_respect_time_scale() {
+    reset_test_env!();
+
+    let wnd = TestWindow::new(fn_widget! { @Void {} });
+    wnd.set_flags(wnd.flags() | WindowFlags::ANIMATIONS);
+
+    let advance = |ignore_time_scale: bool, dur: Duration| {
+      let state = Stateful::new(0.);
+      let animate = Stateful::new(Animate {
+        transition: EasingTransition {
+          easing: easing::LINEAR,
+          duration: Duration::from_millis(100),
+        }
+        .box_it(),
+        state: state.clone_writer(),
+        from: 0.,
+        ignore_time_scale,
+        running_info: None,
+        window_id: wnd.id(),
+      });
+      *state.write() = 100.;
+      animate.run();
+
+      let mut animate = animate.write();
+      let now = FrameClock::now();
+      animate.running_info.as_mut().unwrap().last_at = now;
+      animate.advance_to(now + dur);
+      let v = *state.read();
+      v
+    };
+
+    assert_eq!(advance(false, Duration::from_millis(50)), 50.);
+
+    AppCtx::animation_time_scale().write().scale = 0.5;
+    assert_eq!(advance(false, Duration::from_millis(50)), 25.);
+    assert_eq!(advance(true, Duration::from_millis(50)), 50.);
+
+    AppCtx::animation_time_scale()
+      .write()
+      .reduced_motion = true;
+    assert_eq!(advance(false, Duration::from_millis(10)), 100.);
+    assert_eq!(advance(true, Duration::from_millis(50)), 50.);
+  }
+
+  #[test]
+  fn spring_carry_velocity() {
+    reset_test_env!();
+
+    let wnd = TestWindow::new(fn_widget! { @Void {} });
+    wnd.set_flags(wnd.flags() | WindowFlags::ANIMATIONS);
+
+    let state = Stateful::new(0.);
+    let animate = Stateful::new(Animate {
+      transition: Spring::default().box_it(),
+      state: state.clone_writer(),
+      from: 0.,
+      ignore_time_scale: true,
+      running_info: None,
+      window_id: wnd.id(),
+    });
+    let advance = |dur: Duration| {
+      let mut animate = animate.write();
+      let info = animate.running_info.as_mut().unwrap();
+      info.already_lerp = false;
+      let at = info.last_at + dur;
+      animate.advance_to(at);
+    };
+
+    *state.write() = 100.;
+    animate.run();
+    advance(Duration::from_millis(50));
+    let moving = *state.read();
+    assert!(moving > 0.);
+
+    // Interrupted by a backward target, the value keeps going forward for a
+    // while because of the velocity.
+    *state.write() = 0.;
+    animate.run();
+    advance(Duration::from_millis(5));
+    assert!(*state.read() > moving);
+
+    let settle = animate.read().transition.duration();
+    advance(settle);
+    assert_eq!(*state.read(), 0.);
+  }
+
+  #[test]
+  fn tick_frames_by_manual_clock() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! { @Void {} });
+    wnd.use_manual_clock();
+
+    let state = Stateful::new(0.);
+    let animate = Stateful::new(Animate {
+      transition: EasingTransition { easing: easing::LINEAR, duration: Duration::from_millis(100) }
+        .box_it(),
+      state: state.clone_writer(),
+      from: 0.,
+      ignore_time_scale: false,
+      running_info: None,
+      window_id: wnd.id(),
+    });
+    let frames = Rc::new(RefCell::new(vec![]));
+    let c_frames = frames.clone();
+    let c_state = state.clone_reader();
+    let _h = wnd
+      .frame_ticker
+      .clone()
+      .subscribe(move |msg| {
+        if let FrameMsg::LayoutReady(_) = msg {
+          c_frames.borrow_mut().push(*c_state.read());
+        }
+      })
+      .unsubscribe_when_dropped();
+
+    let fired = Rc::new(Cell::new(false));
+    let c_fired = fired.clone();
+    let _t = observable::timer((), Duration::from_millis(60), AppCtx::scheduler())
+      .subscribe(move |_| c_fired.set(true));
+
+    *state.write() = 100.;
+    animate.run();
+    wnd.tick(Duration::from_millis(25));
+    wnd.tick(Duration::from_millis(25));
+    assert!(!fired.get());
+    wnd.tick(Duration::from_millis(25));
+    assert!(fired.get());
+    wnd.tick(Duration::from_millis(25));
+
+    assert_eq!(&*frames.borrow(), &[25., 50., 75., 100.]);
+    // The animation finishes in this frame, and stops after it.
+    wnd.tick(Duration::from_millis(25));
+    wnd.run_frame_tasks();
+    assert!(!animate.is_running());
+  }
 }
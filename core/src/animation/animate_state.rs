@@ -21,6 +21,13 @@ pub trait AnimateStateSetter {
 pub trait AnimateState: AnimateStateSetter {
   fn calc_lerp_value(&mut self, from: &Self::Value, to: &Self::Value, rate: f32) -> Self::Value;
 
+  /// The inverse of `calc_lerp_value`, see [`Lerp::inverse_lerp`].
+  fn calc_inverse_lerp(
+    &mut self, _from: &Self::Value, _to: &Self::Value, _value: &Self::Value,
+  ) -> Option<f32> {
+    None
+  }
+
   /// Use an animate to transition the state after it modified.
   fn transition(self, transition: impl Transition + 'static) -> Stateful<Animate<Self>>
   where
@@ -101,6 +108,12 @@ where
   fn calc_lerp_value(&mut self, from: &Self::Value, to: &Self::Value, rate: f32) -> Self::Value {
     from.lerp(to, rate)
   }
+
+  fn calc_inverse_lerp(
+    &mut self, from: &Self::Value, to: &Self::Value, value: &Self::Value,
+  ) -> Option<f32> {
+    from.inverse_lerp(to, value)
+  }
 }
 
 impl<S, F> AnimateStateSetter for LerpFnState<S, F>
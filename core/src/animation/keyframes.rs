@@ -36,6 +36,21 @@
 //! };
 //! ```
 //!
+//! Every keyframe can specify an easing after a `;`, it's applied to the
+//! segment that ends at the keyframe, so every property can have its own easing
+//! when you animate multiple keyframes states together.
+//!
+//! ```
+//! use ribir::prelude::*;
+//!
+//! let value = State::value(0.);
+//! let _frames = keyframes! {
+//!   state: value,
+//!   50% => 100.; easing::EASE_IN,
+//!   100% => 0.; easing::EASE_OUT,
+//! };
+//! ```
+//!
 //! Alternatively, you can utilize it to create an animation and control it.
 //!
 //! ```
@@ -64,6 +79,26 @@
 //!   }
 //! };
 //! ```
+//!
+//! `Animate::keyframes` is a shortcut to create it, and a
+//! [`Timeline`](super::Timeline) can repeat or reverse the animation.
+//!
+//! ```
+//! use ribir::prelude::*;
+//!
+//! let _w = fn_widget! {
+//!   let mut rect = @SizedBox { size: Size::new(100., 100.) };
+//!   let opacity = part_writer!(&mut rect.opacity);
+//!   let animate = Animate::keyframes(
+//!     opacity,
+//!     [KeyFrame::new(0.5, 0.2).with_easing(easing::EASE_IN), KeyFrame::new(1., 1.)],
+//!     EasingTransition { duration: Duration::from_millis(500), easing: easing::LINEAR }
+//!       .timeline(3., PlayDirection::Alternate),
+//!   );
+//!
+//!   @ $rect { on_tap: move |_| animate.run() }
+//! };
+//! ```
 #[derive(Debug)]
 pub struct KeyFrames<S: AnimateStateSetter> {
   /// The state for the keyframes.
@@ -72,10 +107,33 @@ pub struct KeyFrames<S: AnimateStateSetter> {
   frames: Box<[KeyFrame<S::Value>]>,
 }
 
-#[derive(Debug)]
 pub struct KeyFrame<S> {
   pub rate: f32,
   pub state_value: S,
+  /// The easing of the segment that ends at this keyframe, the segment changes
+  /// linearly if it's `None`.
+  pub easing: Option<Box<dyn Easing>>,
+}
+
+impl<S> KeyFrame<S> {
+  /// Create a keyframe at the `rate` of the animation with a linear segment.
+  pub fn new(rate: f32, state_value: S) -> Self { Self { rate, state_value, easing: None } }
+
+  /// Set the easing of the segment that ends at this keyframe.
+  pub fn with_easing(mut self, easing: impl Easing + 'static) -> Self {
+    self.easing = Some(Box::new(easing));
+    self
+  }
+}
+
+impl<S: std::fmt::Debug> std::fmt::Debug for KeyFrame<S> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("KeyFrame")
+      .field("rate", &self.rate)
+      .field("state_value", &self.state_value)
+      .field("easing", &self.easing.is_some())
+      .finish()
+  }
 }
 
 impl<S: AnimateStateSetter> KeyFrames<S> {
@@ -112,33 +170,77 @@ impl<S: AnimateStateSetter> KeyFrames<S> {
     S::Value: Lerp,
   {
     let Self { state, frames } = self;
-    LerpFnState::new(state, move |from, to, rate| {
-      let idx = frames
-        .binary_search_by(|f| f.rate.total_cmp(&rate))
-        .unwrap_or_else(|idx| idx);
-
-      if idx == 0 {
-        let rate = if rate > 0. { rate / frames[0].rate } else { 1. };
-        from.lerp(&frames[0].state_value, rate)
-      } else if idx == frames.len() {
-        let pre_rate = frames[idx - 1].rate;
-        if pre_rate == 1. {
-          to.clone()
-        } else {
-          let rate = (rate - pre_rate) / (1. - pre_rate);
-          frames[idx - 1].state_value.lerp(to, rate)
-        }
-      } else {
-        let f2 = &frames[idx];
-        let f1 = &frames[idx - 1];
-        if f2.rate == f1.rate {
-          f2.state_value.clone()
-        } else {
-          let rate = (rate - f1.rate) / (f2.rate - f1.rate);
-          f1.state_value.lerp(&f2.state_value, rate)
-        }
-      }
-    })
+    LerpFnState::new(state, move |from, to, rate| lerp_frames(&frames, from, to, rate))
+  }
+}
+
+impl<S> AnimateStateSetter for KeyFrames<S>
+where
+  S: AnimateStateSetter,
+{
+  type C = S::C;
+  type Value = S::Value;
+
+  #[inline]
+  fn get(&self) -> Self::Value { self.state.get() }
+
+  #[inline]
+  fn set(&self, v: Self::Value) { self.state.set(v) }
+
+  #[inline]
+  fn revert_value(&self, v: Self::Value) { self.state.revert_value(v) }
+
+  #[inline]
+  fn clone_setter(&self) -> Self::C { self.state.clone_setter() }
+
+  #[inline]
+  fn animate_state_modifies(&self) -> BoxOp<'static, ModifyScope, Infallible> {
+    self.state.animate_state_modifies()
+  }
+}
+
+impl<S> AnimateState for KeyFrames<S>
+where
+  S: AnimateStateSetter,
+  S::Value: Lerp,
+{
+  fn calc_lerp_value(&mut self, from: &S::Value, to: &S::Value, rate: f32) -> S::Value {
+    lerp_frames(&self.frames, from, to, rate)
+  }
+}
+
+fn lerp_frames<V: Lerp + Clone>(frames: &[KeyFrame<V>], from: &V, to: &V, rate: f32) -> V {
+  let ease = |frame: &KeyFrame<V>, rate: f32| {
+    frame
+      .easing
+      .as_ref()
+      .map_or(rate, |e| e.easing(rate))
+  };
+  let idx = frames
+    .binary_search_by(|f| f.rate.total_cmp(&rate))
+    .unwrap_or_else(|idx| idx);
+
+  if idx == 0 {
+    let rate = if rate > 0. { rate / frames[0].rate } else { 1. };
+    from.lerp(&frames[0].state_value, ease(&frames[0], rate))
+  } else if idx == frames.len() {
+    let pre_rate = frames[idx - 1].rate;
+    if pre_rate == 1. {
+      to.clone()
+    } else {
+      let rate = (rate - pre_rate) / (1. - pre_rate);
+      frames[idx - 1].state_value.lerp(to, rate)
+    }
+  } else {
+    let f2 = &frames[idx];
+    let f1 = &frames[idx - 1];
+    if f2.rate == f1.rate {
+      f2.state_value.clone()
+    } else {
+      let rate = (rate - f1.rate) / (f2.rate - f1.rate);
+      f1.state_value
+        .lerp(&f2.state_value, ease(f2, rate))
+    }
   }
 }
 
@@ -148,7 +250,8 @@ impl<S: AnimateStateSetter> KeyFrames<S> {
 /// * A state to use for the keyframes.
 /// * A list of pairs of `rate` and `state_value` to specify the keyframes. The
 ///   `rate` is a float, where 0 represents the start, and 1 indicates the
-///   finish, or a percentage suffixed by `%`.
+///   finish, or a percentage suffixed by `%`. An easing can follow the
+///   `state_value` after a `;` to ease the segment that ends at the keyframe.
 ///
 /// # Examples
 ///
@@ -160,7 +263,7 @@ impl<S: AnimateStateSetter> KeyFrames<S> {
 ///     state: value,
 ///     // accepts value
 ///     0.1 => 20.0,
-///     0.5 => 60.0,
+///     0.5 => 60.0; easing::EASE_IN,
 ///     90% => 90.0
 /// };
 /// ```
@@ -172,12 +275,38 @@ macro_rules! keyframes {
   (state: $state:expr, frames: [ $($f:expr),*] $(,)?) => {
     $crate::animation::KeyFrames::new($state, vec![ $($f),*]).into_lerp_fn_state()
   };
+  (
+    state: $state:expr, frames: [ $($f:expr),* ],
+    $l: literal% => $v:expr; $e:expr $(, $($rest:tt)*)?
+  ) => {
+    $crate::keyframes!(
+      state: $state,
+      frames: [
+        $($f,)*
+        $crate::animation::KeyFrame::new($l as f32 / 100., $v).with_easing($e)
+      ]
+      $(, $($rest)*)?
+    )
+  };
   (state: $state:expr, frames: [ $($f:expr),* ], $l: literal% => $v:expr $(, $($rest:tt)*)?) => {
     $crate::keyframes!(
       state: $state,
       frames: [
         $($f,)*
-        KeyFrame { rate: $l as f32 / 100., state_value: $v }
+        $crate::animation::KeyFrame::new($l as f32 / 100., $v)
+      ]
+      $(, $($rest)*)?
+    )
+  };
+  (
+    state: $state:expr, frames: [$($f:expr),*],
+    $rate: expr => $v:expr; $e:expr $(, $($rest:tt)*)?
+  ) => {
+    $crate::keyframes!(
+      state: $state,
+      frames: [
+        $($f,)*
+        $crate::animation::KeyFrame::new($rate, $v).with_easing($e)
       ]
       $(, $($rest)*)?
     )
@@ -187,7 +316,7 @@ macro_rules! keyframes {
       state: $state,
       frames: [
         $($f,)*
-        KeyFrame { rate: $rate, state_value: $v }
+        $crate::animation::KeyFrame::new($rate, $v)
       ]
       $(, $($rest)*)?
     )
@@ -196,9 +325,13 @@ macro_rules! keyframes {
     $crate::keyframes!(state: $state, frames: [], $($rest)*)
   };
 }
+use std::convert::Infallible;
+
 pub use keyframes;
+use rxrust::ops::box_it::BoxOp;
 
-use super::{AnimateStateSetter, Lerp, LerpFnState};
+use super::{AnimateState, AnimateStateSetter, Easing, Lerp, LerpFnState};
+use crate::prelude::ModifyScope;
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -225,4 +358,23 @@ mod tests {
     let p100 = keyframes.calc_lerp_value(&0., &1., 1.);
     assert_eq!(p100, 1.);
   }
+
+  #[test]
+  fn keyframe_easing() {
+    struct Square;
+    impl Easing for Square {
+      fn easing(&self, time_rate: f32) -> f32 { time_rate * time_rate }
+    }
+
+    let state = State::value(0.);
+    let mut keyframes = keyframes! {
+      state: state,
+      50% => 1.; Square,
+      100% => 2.,
+    };
+
+    assert_eq!(keyframes.calc_lerp_value(&0., &2., 0.25), 0.25);
+    assert_eq!(keyframes.calc_lerp_value(&0., &2., 0.5), 1.);
+    assert_eq!(keyframes.calc_lerp_value(&0., &2., 0.75), 1.5);
+  }
 }
@@ -5,6 +5,13 @@ use crate::prelude::*;
 /// * `factor`: the percent of the distance between two value to advance.s
 pub trait Lerp {
   fn lerp(&self, to: &Self, factor: f32) -> Self;
+
+  /// The inverse of `lerp`, calc the factor of the `value` from `self` to `to`.
+  /// A value not on the line is projected onto it. Return `None` if the type
+  /// does not support it.
+  ///
+  /// It's used to carry the velocity over when an animation is interrupted.
+  fn inverse_lerp(&self, _to: &Self, _value: &Self) -> Option<f32> { None }
 }
 
 macro_rules! impl_lerp_for_integer {
@@ -15,6 +22,10 @@ macro_rules! impl_lerp_for_integer {
         fn lerp(&self, to: &Self, factor: f32) -> Self{
         (*self as f32 * (1. - factor)   +  *to as f32 * factor) as $ty
         }
+
+        fn inverse_lerp(&self, to: &Self, value: &Self) -> Option<f32> {
+          (*self as f32).inverse_lerp(&(*to as f32), &(*value as f32))
+        }
       }
     )*
   }
@@ -24,10 +35,20 @@ impl_lerp_for_integer! { i8, i16, i32, i64, isize, u8, u16, u32, u64, usize }
 
 impl Lerp for f32 {
   fn lerp(&self, to: &Self, factor: f32) -> Self { factor.mul_add(to - self, *self) }
+
+  fn inverse_lerp(&self, to: &Self, value: &Self) -> Option<f32> {
+    let dist = to - self;
+    (dist != 0.).then(|| (value - self) / dist)
+  }
 }
 
 impl Lerp for f64 {
   fn lerp(&self, to: &Self, factor: f32) -> Self { (factor as f64).mul_add(to - self, *self) }
+
+  fn inverse_lerp(&self, to: &Self, value: &Self) -> Option<f32> {
+    let dist = to - self;
+    (dist != 0.).then(|| ((value - self) / dist) as f32)
+  }
 }
 
 impl Lerp for bool {
@@ -76,13 +97,20 @@ impl_lerp_for_tuple! {T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5, T6, 6, T7, 7,T8,
 }
 
 macro_rules! impl_lerp_for_copy_geom {
-  ($($ty: ident), *) => {
+  ($($ty: ident: $to_vector: expr), *) => {
     $(
       impl Lerp for $ty {
         #[inline]
         fn lerp(&self, to: &Self, factor: f32) -> Self{
           $ty::lerp(*self, *to, factor)
         }
+
+        fn inverse_lerp(&self, to: &Self, value: &Self) -> Option<f32> {
+          let from: Vector = $to_vector(*self);
+          let dist = $to_vector(*to) - from;
+          let len = dist.square_length();
+          (len != 0.).then(|| ($to_vector(*value) - from).dot(dist) / len)
+        }
       }
     )*
   }
@@ -114,7 +142,7 @@ macro_rules! impl_lerp_for_device_geom {
   }
 }
 
-impl_lerp_for_copy_geom! { Point, Size, Vector }
+impl_lerp_for_copy_geom! { Point: Point::to_vector, Size: Size::to_vector, Vector: Vector::from }
 impl_lerp_for_geom! { Rect, Angle, Box2D }
 impl_lerp_for_device_geom! { DeviceRect, DevicePoint, DeviceSize, DeviceVector }
 
@@ -186,6 +214,16 @@ mod tests {
     assert!(eq(Lerp::lerp(&10., &0., 0.2), 8.));
   }
 
+  #[test]
+  fn inverse_lerp() {
+    assert_eq!(10f32.inverse_lerp(&0., &8.), Some(0.2));
+    assert_eq!(5f32.inverse_lerp(&5., &8.), None);
+    assert_eq!(
+      Point::new(0., 0.).inverse_lerp(&Point::new(10., 0.), &Point::new(5., 3.)),
+      Some(0.5)
+    );
+  }
+
   #[test]
   fn lerp_point() {
     let eq = |p1: Point, p2: Point| {
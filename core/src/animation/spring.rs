@@ -0,0 +1,236 @@
+use std::cell::Cell;
+
+use crate::prelude::*;
+
+/// The displacement from the target that the spring is treated as at rest.
+const REST_THRESHOLD: f32 = 0.001;
+/// A spring that takes longer than it to settle is stopped at it.
+const MAX_SETTLE: Duration = Duration::from_secs(60);
+
+/// A physics based transition that simulates a damped spring pulling the value
+/// from the start to the target.
+///
+/// The spring has no fixed duration, it finishes when it settles down. It may
+/// overshoot the target if it's underdamped. When the animation is interrupted
+/// by a new target, the spring starts the new run with the velocity it had, so
+/// the motion keeps continuous.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+///
+/// let _bouncy = Spring::new(300., 10., 1.);
+/// let _smooth = Spring::default().box_it();
+/// ```
+#[derive(Clone, Debug)]
+pub struct Spring {
+  stiffness: f32,
+  damping: f32,
+  mass: f32,
+  /// The initial velocity of the run, in progress per second.
+  velocity: Cell<f32>,
+}
+
+impl Spring {
+  /// Create a spring by its `stiffness`, `damping` and `mass`.
+  ///
+  /// A stiffer spring moves faster, the damping slows it down and reduces the
+  /// bounces, and a heavier object moves slower and bounces longer.
+  ///
+  /// # Panics
+  ///
+  /// Panics if any of the parameters is not positive, a spring without damping
+  /// never settles down.
+  pub fn new(stiffness: f32, damping: f32, mass: f32) -> Self {
+    assert!(
+      stiffness > 0. && damping > 0. && mass > 0.,
+      "The stiffness, damping and mass of a spring must be positive."
+    );
+    Self { stiffness, damping, mass, velocity: Cell::new(0.) }
+  }
+
+  /// Start the spring with the initial `velocity`, in progress per second.
+  pub fn with_velocity(self, velocity: f32) -> Self {
+    self.carry_velocity(velocity);
+    self
+  }
+
+  /// The stiffness of the spring.
+  pub fn stiffness(&self) -> f32 { self.stiffness }
+
+  /// The damping of the spring.
+  pub fn damping(&self) -> f32 { self.damping }
+
+  /// The mass of the object attached to the spring.
+  pub fn mass(&self) -> f32 { self.mass }
+
+  /// The natural frequency and the damping ratio of the spring.
+  fn frequency_and_ratio(&self) -> (f32, f32) {
+    let Self { stiffness, damping, mass, .. } = self;
+    let w0 = (stiffness / mass).sqrt();
+    let zeta = damping / (2. * (stiffness * mass).sqrt());
+    (w0, zeta)
+  }
+
+  /// The progress and the velocity of the spring at `t` seconds from the
+  /// start.
+  fn state_at(&self, t: f32) -> (f32, f32) {
+    let v0 = self.velocity.get();
+    let (w0, zeta) = self.frequency_and_ratio();
+    // The displacement from the target, it starts from -1.
+    let (x, v) = if (zeta - 1.).abs() < 1e-4 {
+      let b = v0 - w0;
+      let envelope = (-w0 * t).exp();
+      let x = envelope * (-1. + b * t);
+      (x, -w0 * x + envelope * b)
+    } else if zeta < 1. {
+      let wd = w0 * (1. - zeta * zeta).sqrt();
+      let b = (v0 - zeta * w0) / wd;
+      let envelope = (-zeta * w0 * t).exp();
+      let (sin, cos) = (wd * t).sin_cos();
+      let x = envelope * (-cos + b * sin);
+      let v = -zeta * w0 * x + envelope * (wd * sin + b * wd * cos);
+      (x, v)
+    } else {
+      let d = w0 * (zeta * zeta - 1.).sqrt();
+      let (r1, r2) = (-zeta * w0 + d, -zeta * w0 - d);
+      let c2 = (v0 + r1) / (r2 - r1);
+      let c1 = -1. - c2;
+      let (e1, e2) = ((r1 * t).exp(), (r2 * t).exp());
+      (c1 * e1 + c2 * e2, c1 * r1 * e1 + c2 * r2 * e2)
+    };
+    (1. + x, v)
+  }
+
+  /// The time that the envelope of the displacement decays below the
+  /// `REST_THRESHOLD`, t = ln(A / ε) / λ, where `A` is the amplitude and `λ`
+  /// is the slowest decay rate of the spring.
+  fn settle_duration(&self) -> Duration {
+    let v0 = self.velocity.get();
+    let (w0, zeta) = self.frequency_and_ratio();
+    let settle_at = |amplitude: f32, rate: f32| (amplitude / REST_THRESHOLD).ln().max(0.) / rate;
+    let secs = if (zeta - 1.).abs() < 1e-4 {
+      // The envelope is (1 + |b|t)·e^(-ω₀t), the linear factor is refined by the
+      // time it settles at.
+      let b = (v0 - w0).abs();
+      let t = settle_at(1., w0);
+      settle_at(1. + b * t, w0)
+    } else if zeta < 1. {
+      let wd = w0 * (1. - zeta * zeta).sqrt();
+      let b = (v0 - zeta * w0) / wd;
+      settle_at((1. + b * b).sqrt(), zeta * w0)
+    } else {
+      let d = w0 * (zeta * zeta - 1.).sqrt();
+      let (r1, r2) = (-zeta * w0 + d, -zeta * w0 - d);
+      let c2 = (v0 + r1) / (r2 - r1);
+      let c1 = -1. - c2;
+      settle_at(c1.abs() + c2.abs(), -r1)
+    };
+    Duration::try_from_secs_f32(secs)
+      .unwrap_or(MAX_SETTLE)
+      .min(MAX_SETTLE)
+  }
+}
+
+impl Default for Spring {
+  fn default() -> Self { Self::new(170., 26., 1.) }
+}
+
+impl Transition for Spring {
+  fn rate_of_change(&self, dur: Duration) -> AnimateProgress {
+    if dur >= self.settle_duration() {
+      return AnimateProgress::Finish;
+    }
+    AnimateProgress::Between(self.state_at(dur.as_secs_f32()).0)
+  }
+
+  fn duration(&self) -> Duration { self.settle_duration() }
+
+  fn carry_velocity(&self, velocity: f32) { self.velocity.set(velocity); }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn progress(spring: &impl Transition, ms: u64) -> f32 {
+    match spring.rate_of_change(Duration::from_millis(ms)) {
+      AnimateProgress::Between(p) => p,
+      AnimateProgress::Dismissed => 0.,
+      AnimateProgress::Finish => 1.,
+    }
+  }
+
+  #[test]
+  fn spring_settle() {
+    for spring in
+      [Spring::new(170., 5., 1.), Spring::new(100., 20., 1.), Spring::new(100., 40., 1.)]
+    {
+      assert!(progress(&spring, 0).abs() < 1e-6);
+      let dur = spring.duration();
+      assert!(dur > Duration::ZERO && dur < Duration::from_secs(10));
+      assert!((progress(&spring, dur.as_millis() as u64 - 1) - 1.).abs() < 0.01);
+      assert_eq!(spring.rate_of_change(dur), AnimateProgress::Finish);
+    }
+  }
+
+  #[test]
+  fn underdamped_overshoot() {
+    let spring = Spring::new(300., 5., 1.);
+    let max = (0..spring.duration().as_millis() as u64)
+      .map(|ms| progress(&spring, ms))
+      .fold(0., f32::max);
+    assert!(max > 1.);
+
+    let spring = Spring::new(100., 40., 1.);
+    let max = (0..spring.duration().as_millis() as u64)
+      .map(|ms| progress(&spring, ms))
+      .fold(0., f32::max);
+    assert!(max <= 1.);
+  }
+
+  #[test]
+  fn initial_velocity() {
+    let still = Spring::default();
+    let moving = Spring::default().with_velocity(10.);
+    assert!(progress(&moving, 10) > progress(&still, 10));
+
+    let (_, v) = moving.state_at(0.);
+    assert!((v - 10.).abs() < 0.001);
+  }
+
+  #[test]
+  fn wrapped_carry_velocity() {
+    let moving = Spring::default().with_velocity(10.);
+
+    let delayed = Spring::default().delay(Duration::from_millis(100));
+    delayed.carry_velocity(10.);
+    assert_eq!(progress(&delayed, 110), progress(&moving, 10));
+
+    let repeated = Spring::default().repeat(2.);
+    repeated.carry_velocity(10.);
+    assert_eq!(progress(&repeated, 10), progress(&moving, 10));
+  }
+
+  #[test]
+  fn settle_by_the_envelope() {
+    for spring in [
+      Spring::new(170., 5., 1.),
+      Spring::new(100., 20., 1.),
+      Spring::new(100., 40., 1.),
+      Spring::default().with_velocity(10.),
+    ] {
+      let dur = spring.duration().as_millis() as u64;
+      // It rests after the settle duration, but not long before it.
+      let resting =
+        |from: u64| (from..from + 200).all(|ms| (progress(&spring, ms) - 1.).abs() < 0.01);
+      assert!(resting(dur.saturating_sub(10)));
+      assert!(!resting(dur / 2));
+    }
+  }
+
+  #[test]
+  #[should_panic(expected = "must be positive")]
+  fn no_damping_panic() { Spring::new(100., 0., 1.); }
+}
@@ -138,7 +138,7 @@ impl<T: Transition + 'static> Animation for Stateful<Stagger<T>> {
     }
     {
       let mut this = self.write();
-      this.next_to_run = Some(AnimationCursor { prev_at: Instant::now(), index: 0 });
+      this.next_to_run = Some(AnimationCursor { prev_at: FrameClock::now(), index: 0 });
       this.run_times += 1;
     }
 
@@ -172,7 +172,10 @@ impl<T: Transition + 'static> Stateful<Stagger<T>> {
     let mut this = self.write();
     if let Some(step) = this.next_to_run.take() {
       if let Some((delay, next)) = this.animations.get(step.index) {
-        let at = step.prev_at + *delay;
+        let delay = AppCtx::animation_time_scale()
+          .read()
+          .real_duration(*delay);
+        let at = step.prev_at + delay;
         let next = next.box_clone();
         this.next_to_run = Some(AnimationCursor { prev_at: at, index: step.index + 1 });
 
@@ -182,7 +185,10 @@ impl<T: Transition + 'static> Stateful<Stagger<T>> {
         drop(this);
 
         let this = self.clone_writer();
-        let h = observable::timer_at((), at, AppCtx::scheduler()).subscribe(move |_| {
+        // Not use `timer_at`, it measures by the system time rather than the
+        // `FrameClock`.
+        let delay = at.saturating_duration_since(FrameClock::now());
+        let h = observable::timer((), delay, AppCtx::scheduler()).subscribe(move |_| {
           next.run();
           this.trigger_next();
         });
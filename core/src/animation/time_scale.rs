@@ -0,0 +1,36 @@
+use crate::prelude::*;
+
+/// The global time scale of all animations in the application, you can get it
+/// by `AppCtx::animation_time_scale()`.
+///
+/// All the transitions, keyframes and staggers respect it. For example, a
+/// `scale` of `0.5` makes all animations run at half speed, which is useful
+/// for debugging. A `scale` of `0.` or a `reduced_motion` preference makes
+/// every animation jump to its final value directly.
+///
+/// An animation can opt out by its `ignore_time_scale` field.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnimationTimeScale {
+  /// The speed multiplier of the animations, `1.` means the normal speed.
+  pub scale: f32,
+  /// Whether the user prefers reduced motion. The application shell
+  /// initializes it from the operating system setting if it is supported.
+  pub reduced_motion: bool,
+}
+
+impl AnimationTimeScale {
+  /// Return the speed multiplier that should be applied to the animations, it
+  /// is `0.` if the user prefers reduced motion.
+  pub fn effective_scale(&self) -> f32 { if self.reduced_motion { 0. } else { self.scale.max(0.) } }
+
+  /// Convert a duration of the animation timeline to the real duration that
+  /// it takes.
+  pub fn real_duration(&self, dur: Duration) -> Duration {
+    let scale = self.effective_scale();
+    if scale == 0. { Duration::ZERO } else { dur.div_f32(scale) }
+  }
+}
+
+impl Default for AnimationTimeScale {
+  fn default() -> Self { Self { scale: 1., reduced_motion: false } }
+}
@@ -16,11 +16,53 @@ pub struct DelayTransition<T> {
   pub transition: T,
 }
 
-/// Transition will apply with repeat times.
+/// Transition will apply with repeat times, and every round can play forward
+/// or backward by the `direction`.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+///
+/// let _alternate = EasingTransition { duration: Duration::from_millis(200), easing: easing::EASE }
+///   .repeat(3.)
+///   .with_direction(PlayDirection::Alternate);
+/// ```
 #[derive(Clone)]
 pub struct RepeatTransition<T> {
+  /// The count of the rounds, can be fractional. Use `f32::INFINITY` to play
+  /// forever.
   pub repeat: f32,
   pub transition: T,
+  pub direction: PlayDirection,
+}
+
+/// A timeline plays a transition several rounds, and every round can play
+/// forward or backward by its direction. It's the [`RepeatTransition`] built
+/// by [`Transition::timeline`].
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+///
+/// let _timeline = EasingTransition { duration: Duration::from_millis(200), easing: easing::EASE }
+///   .timeline(f32::INFINITY, PlayDirection::Alternate);
+/// ```
+pub type Timeline<T> = RepeatTransition<T>;
+
+/// The direction to play each round of a [`Timeline`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlayDirection {
+  /// Every round plays forward.
+  #[default]
+  Normal,
+  /// Every round plays backward.
+  Reverse,
+  /// The first round plays forward, then the direction alternates every round.
+  Alternate,
+  /// The first round plays backward, then the direction alternates every round.
+  AlternateReverse,
 }
 
 /// Transition is a trait to help you calc the rate of change over time.
@@ -31,12 +73,26 @@ pub trait Transition {
   /// Return the duration of the animation from start to finish.
   fn duration(&self) -> Duration;
 
+  /// Set the velocity, in progress per second, that the next run starts with.
+  /// The animation calls it before every run, with the velocity it had if it
+  /// was interrupted, or zero. Most transitions ignore it, a [`Spring`] uses it
+  /// to keep the motion continuous.
+  fn carry_velocity(&self, _velocity: f32) {}
+
   /// Transition will apply with repeat times
   fn repeat(self, repeat: f32) -> RepeatTransition<Self>
   where
     Self: Sized,
   {
-    RepeatTransition { repeat, transition: self }
+    RepeatTransition { repeat, transition: self, direction: PlayDirection::Normal }
+  }
+
+  /// Transition will play `repeat` rounds in the `direction`, see [`Timeline`].
+  fn timeline(self, repeat: f32, direction: PlayDirection) -> Timeline<Self>
+  where
+    Self: Sized,
+  {
+    self.repeat(repeat).with_direction(direction)
   }
 
   /// Transition will apply after the delay duration
@@ -73,6 +129,27 @@ impl<T: Transition> Transition for DelayTransition<T> {
   }
 
   fn duration(&self) -> Duration { self.delay + self.transition.duration() }
+
+  fn carry_velocity(&self, velocity: f32) { self.transition.carry_velocity(velocity) }
+}
+
+impl<T> RepeatTransition<T> {
+  /// Set the direction to play each round.
+  pub fn with_direction(mut self, direction: PlayDirection) -> Self {
+    self.direction = direction;
+    self
+  }
+}
+
+impl PlayDirection {
+  fn is_reverse(&self, round: u64) -> bool {
+    match self {
+      PlayDirection::Normal => false,
+      PlayDirection::Reverse => true,
+      PlayDirection::Alternate => round & 1 == 1,
+      PlayDirection::AlternateReverse => round & 1 == 0,
+    }
+  }
 }
 
 impl<T: Transition> Transition for RepeatTransition<T> {
@@ -83,9 +160,13 @@ impl<T: Transition> Transition for RepeatTransition<T> {
     if rounds > repeat {
       return AnimateProgress::Finish;
     }
+    let mut time_rate = rounds.fract();
+    if self.direction.is_reverse(rounds as u64) {
+      time_rate = 1. - time_rate;
+    }
     let rate = match self
       .transition
-      .rate_of_change(duration.mul_f32(rounds.fract()))
+      .rate_of_change(duration.mul_f32(time_rate))
     {
       AnimateProgress::Dismissed => 0.,
       AnimateProgress::Finish => 1.,
@@ -95,22 +176,27 @@ impl<T: Transition> Transition for RepeatTransition<T> {
   }
 
   fn duration(&self) -> Duration {
-    let duration = self.transition.duration();
-    let repeat = self.repeat;
-    Duration::from_secs_f32(duration.as_secs_f32() * repeat)
+    let secs = self.transition.duration().as_secs_f64() * self.repeat as f64;
+    Duration::try_from_secs_f64(secs).unwrap_or(Duration::MAX)
   }
+
+  fn carry_velocity(&self, velocity: f32) { self.transition.carry_velocity(velocity) }
 }
 
 impl Transition for Box<dyn Transition> {
   fn rate_of_change(&self, dur: Duration) -> AnimateProgress { (**self).rate_of_change(dur) }
 
   fn duration(&self) -> Duration { (**self).duration() }
+
+  fn carry_velocity(&self, velocity: f32) { (**self).carry_velocity(velocity) }
 }
 
 impl<T: Transition> Transition for Sc<T> {
   fn rate_of_change(&self, dur: Duration) -> AnimateProgress { (**self).rate_of_change(dur) }
 
   fn duration(&self) -> Duration { (**self).duration() }
+
+  fn carry_velocity(&self, velocity: f32) { (**self).carry_velocity(velocity) }
 }
 
 impl<E: Easing> Transition for EasingTransition<E> {
@@ -125,3 +211,56 @@ impl<E: Easing> Transition for EasingTransition<E> {
 
   fn duration(&self) -> Duration { self.duration }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn rate(t: &impl Transition, millis: u64) -> f32 {
+    t.rate_of_change(Duration::from_millis(millis))
+      .value()
+  }
+
+  #[test]
+  fn repeat_direction() {
+    let linear = EasingTransition { duration: Duration::from_millis(100), easing: easing::LINEAR };
+
+    let normal = linear.clone().repeat(2.);
+    assert_eq!(rate(&normal, 25), 0.25);
+    assert_eq!(rate(&normal, 125), 0.25);
+    assert!(
+      normal
+        .rate_of_change(Duration::from_millis(201))
+        .is_finish()
+    );
+    assert_eq!(normal.duration(), Duration::from_millis(200));
+
+    let reverse = linear
+      .clone()
+      .repeat(2.)
+      .with_direction(PlayDirection::Reverse);
+    assert_eq!(rate(&reverse, 25), 0.75);
+    assert_eq!(rate(&reverse, 125), 0.75);
+
+    let alternate = linear
+      .clone()
+      .repeat(2.)
+      .with_direction(PlayDirection::Alternate);
+    assert_eq!(rate(&alternate, 25), 0.25);
+    assert_eq!(rate(&alternate, 125), 0.75);
+
+    let alternate_reverse = linear
+      .repeat(2.)
+      .with_direction(PlayDirection::AlternateReverse);
+    assert_eq!(rate(&alternate_reverse, 25), 0.75);
+    assert_eq!(rate(&alternate_reverse, 125), 0.25);
+  }
+
+  #[test]
+  fn infinite_timeline() {
+    let linear = EasingTransition { duration: Duration::from_millis(100), easing: easing::LINEAR };
+    let forever = linear.timeline(f32::INFINITY, PlayDirection::Alternate);
+    assert_eq!(forever.duration(), Duration::MAX);
+    assert_eq!(rate(&forever, 100_025), 0.25);
+  }
+}
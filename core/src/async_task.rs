@@ -0,0 +1,170 @@
+//! Run async tasks that can be cancelled, and bind their results to widgets.
+//!
+//! The tasks are spawned by [`AppCtx::spawn_local`], so they are polled and
+//! deliver their results on the UI thread. Use a channel or
+//! `TokioToRibirFuture` to wait for the work done on the other threads.
+use futures::{
+  Future,
+  future::{AbortHandle, abortable},
+};
+
+use crate::prelude::*;
+
+/// The handle of a task spawned by [`AppCtx::spawn_cancellable`].
+///
+/// Dropping the handle doesn't cancel the task, call [`CancelHandle::cancel`]
+/// explicitly.
+#[derive(Clone)]
+pub struct CancelHandle(AbortHandle);
+
+/// The state of an async task.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AsyncValue<T, E> {
+  Pending,
+  Ready(T),
+  Error(E),
+}
+
+/// An async task that exposes its state by a [`Stateful`], and is cancelled
+/// when the widget built by [`AsyncTask::build`] is disposed.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// async fn load_greeting() -> Result<CowArc<str>, String> { Ok("Hello!".into()) }
+///
+/// let _w = fn_widget! {
+///   AsyncTask::new(load_greeting()).build(|v| match v.clone() {
+///     AsyncValue::Pending => text! { text: "Loading..." }.into_widget(),
+///     AsyncValue::Ready(greeting) => text! { text: greeting }.into_widget(),
+///     AsyncValue::Error(err) => text! { text: err }.into_widget(),
+///   })
+/// };
+/// ```
+pub struct AsyncTask<T, E> {
+  value: Stateful<AsyncValue<T, E>>,
+  handle: CancelHandle,
+}
+
+impl AppCtx {
+  /// Spawn a future on the UI thread, and return a handle to cancel it.
+  pub fn spawn_cancellable<Fut>(future: Fut) -> CancelHandle
+  where
+    Fut: Future<Output = ()> + 'static,
+  {
+    let (future, handle) = abortable(future);
+    let _ = AppCtx::spawn_local(async move {
+      let _ = future.await;
+    });
+    CancelHandle(handle)
+  }
+}
+
+impl CancelHandle {
+  /// Cancel the task, it will not be polled anymore.
+  pub fn cancel(&self) { self.0.abort() }
+
+  pub fn is_cancelled(&self) -> bool { self.0.is_aborted() }
+}
+
+impl<T, E> AsyncValue<T, E> {
+  pub fn is_pending(&self) -> bool { matches!(self, AsyncValue::Pending) }
+
+  pub fn ready(&self) -> Option<&T> { if let AsyncValue::Ready(v) = self { Some(v) } else { None } }
+
+  pub fn error(&self) -> Option<&E> { if let AsyncValue::Error(e) = self { Some(e) } else { None } }
+}
+
+impl<T, E> From<Result<T, E>> for AsyncValue<T, E> {
+  fn from(value: Result<T, E>) -> Self {
+    match value {
+      Ok(v) => AsyncValue::Ready(v),
+      Err(e) => AsyncValue::Error(e),
+    }
+  }
+}
+
+impl<T: 'static, E: 'static> AsyncTask<T, E> {
+  /// Spawn the `future` at once, its state is pending until it's done.
+  pub fn new<Fut>(future: Fut) -> Self
+  where
+    Fut: Future<Output = Result<T, E>> + 'static,
+  {
+    let value = Stateful::new(AsyncValue::Pending);
+    let writer = value.clone_writer();
+    let handle = AppCtx::spawn_cancellable(async move {
+      *writer.write() = future.await.into();
+    });
+    Self { value, handle }
+  }
+
+  /// The state of the task, watch it or use it in a `pipe!` to respond to the
+  /// completion of the task.
+  pub fn value(&self) -> &Stateful<AsyncValue<T, E>> { &self.value }
+
+  pub fn handle(&self) -> &CancelHandle { &self.handle }
+
+  /// Build a widget by the state of the task, the widget is rebuilt when the
+  /// state changed, and the task is cancelled when the widget is disposed.
+  pub fn build(
+    self, mut f: impl FnMut(&AsyncValue<T, E>) -> Widget<'static> + 'static,
+  ) -> Widget<'static> {
+    let Self { value, handle } = self;
+    (fn_widget! {
+      let w = FatObj::new(pipe!($value;).map(move |_| f(&$value)).into_widget());
+      @ $w { on_disposed: move |_| handle.cancel() }
+    })
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use futures::channel::oneshot;
+
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[test]
+  fn build_by_task_state() {
+    reset_test_env!();
+
+    let (sender, receiver) = oneshot::channel::<Result<i32, ()>>();
+    let (states, w_states) = split_value(vec![]);
+    let mut task = Some(AsyncTask::new(async move { receiver.await.unwrap() }));
+    let mut wnd = TestWindow::new(fn_widget! {
+      task.take().unwrap().build(move |v| {
+        $w_states.write().push(v.clone());
+        Void.into_widget()
+      })
+    });
+    wnd.draw_frame();
+    assert_eq!(*states.read(), [AsyncValue::Pending]);
+
+    sender.send(Ok(1)).unwrap();
+    wnd.draw_frame();
+    assert_eq!(*states.read(), [AsyncValue::Pending, AsyncValue::Ready(1)]);
+  }
+
+  #[test]
+  fn cancel_task() {
+    reset_test_env!();
+
+    let (sender, receiver) = oneshot::channel::<()>();
+    let done = Stateful::new(false);
+    let c_done = done.clone_writer();
+    let handle = AppCtx::spawn_cancellable(async move {
+      let _ = receiver.await;
+      *c_done.write() = true;
+    });
+    AppCtx::run_until_stalled();
+
+    handle.cancel();
+    let _ = sender.send(());
+    AppCtx::run_until_stalled();
+    assert!(handle.is_cancelled());
+    assert!(!*done.read());
+  }
+}
@@ -9,7 +9,7 @@ pub mod key;
 mod painting_style;
 use std::ops::DerefMut;
 
-pub use key::{Key, KeyWidget};
+pub use key::{Key, KeyWidget, Keyed};
 pub use painting_style::*;
 pub mod image_widget;
 pub mod keep_alive;
@@ -73,13 +73,19 @@ mod text_style;
 pub use text_style::*;
 mod smooth_layout;
 pub use smooth_layout::*;
+mod layout_builder;
+pub use layout_builder::*;
 
 mod track_widget_id;
 pub use track_widget_id::*;
 mod text;
 pub use text::*;
+mod rich_text;
+pub use rich_text::*;
 mod tooltips;
 pub use tooltips::*;
+mod semantics;
+pub use semantics::*;
 mod providers;
 pub use providers::*;
 mod border;
@@ -88,6 +94,16 @@ mod radius;
 pub use radius::*;
 mod background;
 pub use background::*;
+mod cache_layer;
+pub use cache_layer::*;
+mod blur;
+pub use blur::*;
+mod vibrancy;
+pub use vibrancy::*;
+mod shader_box;
+pub use shader_box::*;
+mod draggable;
+pub use draggable::*;
 
 use crate::prelude::*;
 
@@ -148,6 +164,7 @@ pub struct FatObj<T> {
   keep_alive: Option<State<KeepAlive>>,
   keep_alive_unsubscribe_handle: Option<Box<dyn Any>>,
   tooltips: Option<State<Tooltips>>,
+  semantics: Option<State<Semantics>>,
   clip_boundary: Option<State<ClipBoundary>>,
   providers: Option<SmallVec<[Provider; 1]>>,
 }
@@ -197,6 +214,7 @@ impl<T> FatObj<T> {
       visibility: self.visibility,
       opacity: self.opacity,
       tooltips: self.tooltips,
+      semantics: self.semantics,
       clip_boundary: self.clip_boundary,
       keep_alive: self.keep_alive,
       keep_alive_unsubscribe_handle: self.keep_alive_unsubscribe_handle,
@@ -232,6 +250,7 @@ impl<T> FatObj<T> {
       && self.opacity.is_none()
       && self.keep_alive.is_none()
       && self.tooltips.is_none()
+      && self.semantics.is_none()
       && self.clip_boundary.is_none()
   }
 
@@ -476,6 +495,14 @@ impl<T> FatObj<T> {
       .get_or_insert_with(|| State::value(<_>::default()))
   }
 
+  /// Returns the `State<Semantics>` widget from the FatObj. If it doesn't
+  /// exist, a new one is created.
+  pub fn get_semantics_widget(&mut self) -> &State<Semantics> {
+    self
+      .semantics
+      .get_or_insert_with(|| State::value(<_>::default()))
+  }
+
   /// Returns the `State<ClipBoundary>` widget from the FatObj. If it doesn't
   /// exist, a new one is created.
   pub fn get_clip_boundary_widget(&mut self) -> &State<ClipBoundary> {
@@ -516,6 +543,127 @@ impl<T> FatObj<T> {
     on_mixin!(self, on_disposed, f)
   }
 
+  /// Attaches an event handler that runs when the window is resized, the new
+  /// size can be read from `e.window().size()`.
+  pub fn on_window_resized(mut self, f: impl FnMut(&mut LifecycleEvent) + 'static) -> Self {
+    on_mixin!(self, on_window_resized, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when the accessibility
+  /// subsystem requests it to perform an action.
+  pub fn on_accessibility_action(
+    mut self, f: impl FnMut(&mut AccessibilityActionEvent) + 'static,
+  ) -> Self {
+    on_mixin!(self, on_accessibility_action, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when a drag starts from
+  /// it, the handler fills the payload of the drag by `e.data`.
+  pub fn on_drag_start(mut self, f: impl FnMut(&mut DragEvent) + 'static) -> Self {
+    on_mixin!(self, on_drag_start, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when the drag started
+  /// from it ends, `e.dropped` tells whether it's dropped on a drop target.
+  pub fn on_drag_end(mut self, f: impl FnMut(&mut DragEvent) + 'static) -> Self {
+    on_mixin!(self, on_drag_end, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when a drag enters it,
+  /// the widget becomes a drop target.
+  pub fn on_drag_enter(mut self, f: impl FnMut(&mut DragEvent) + 'static) -> Self {
+    on_mixin!(self, on_drag_enter, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when a drag moves over
+  /// it, the widget becomes a drop target.
+  pub fn on_drag_over(mut self, f: impl FnMut(&mut DragEvent) + 'static) -> Self {
+    on_mixin!(self, on_drag_over, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when a drag leaves it,
+  /// the widget becomes a drop target.
+  pub fn on_drag_leave(mut self, f: impl FnMut(&mut DragEvent) + 'static) -> Self {
+    on_mixin!(self, on_drag_leave, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when a drag is dropped
+  /// on it, the widget becomes a drop target.
+  pub fn on_drop(mut self, f: impl FnMut(&mut DragEvent) + 'static) -> Self {
+    on_mixin!(self, on_drop, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when a file is dragged
+  /// from the operating system over it.
+  pub fn on_file_hover(mut self, f: impl FnMut(&mut FileDropEvent) + 'static) -> Self {
+    on_mixin!(self, on_file_hover, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when the drag of the
+  /// files from the operating system leaves the window or is canceled, it's
+  /// triggered for every hovered file.
+  pub fn on_file_hover_cancel(mut self, f: impl FnMut(&mut FileDropEvent) + 'static) -> Self {
+    on_mixin!(self, on_file_hover_cancel, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when a file is dropped
+  /// from the operating system on it.
+  pub fn on_file_drop(mut self, f: impl FnMut(&mut FileDropEvent) + 'static) -> Self {
+    on_mixin!(self, on_file_drop, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when a finger starts
+  /// touching it.
+  pub fn on_touch_start(mut self, f: impl FnMut(&mut TouchEvent) + 'static) -> Self {
+    on_mixin!(self, on_touch_start, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when a finger that
+  /// started touching it moves, `e.touches` has all the fingers on the screen.
+  pub fn on_touch_move(mut self, f: impl FnMut(&mut TouchEvent) + 'static) -> Self {
+    on_mixin!(self, on_touch_move, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when a finger that
+  /// started touching it is lifted.
+  pub fn on_touch_end(mut self, f: impl FnMut(&mut TouchEvent) + 'static) -> Self {
+    on_mixin!(self, on_touch_end, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when the touch of a
+  /// finger that started touching it is canceled by the system.
+  pub fn on_touch_cancel(mut self, f: impl FnMut(&mut TouchEvent) + 'static) -> Self {
+    on_mixin!(self, on_touch_cancel, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered by the pan gesture, a
+  /// pointer pressed and moved. Like the other gestures, it's recognized on the
+  /// nearest widget listening to it from the pressed one, and the tap is not
+  /// fired after it.
+  pub fn on_pan(mut self, f: impl FnMut(&mut PanEvent) + 'static) -> Self {
+    on_mixin!(self, on_pan, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered by the pinch gesture,
+  /// two pointers pressed and moved closer or apart.
+  pub fn on_pinch(mut self, f: impl FnMut(&mut PinchEvent) + 'static) -> Self {
+    on_mixin!(self, on_pinch, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered by the long press
+  /// gesture, a pointer pressed and held still, see
+  /// [`Window::set_long_press_duration`].
+  pub fn on_long_press(mut self, f: impl FnMut(&mut LongPressEvent) + 'static) -> Self {
+    on_mixin!(self, on_long_press, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when the context menu
+  /// is requested on it, by pressing the right mouse button or the long press
+  /// of a finger.
+  pub fn on_context_menu(mut self, f: impl FnMut(&mut PointerEvent) + 'static) -> Self {
+    on_mixin!(self, on_context_menu, f)
+  }
+
   /// Attaches a handler to the widget that is triggered when a pointer down
   /// occurs.
   pub fn on_pointer_down(mut self, f: impl FnMut(&mut PointerEvent) + 'static) -> Self {
@@ -814,6 +962,34 @@ impl<T> FatObj<T> {
     )
   }
 
+  /// Initializes whether the widget holds sensitive content, such as a
+  /// password.
+  ///
+  /// The framework refuses to copy the content of a sensitive widget to the
+  /// clipboard, switches the IME to password mode when it's focused, and
+  /// redacts it from the painted frame if the window has the
+  /// `WindowFlags::REDACT_SENSITIVE` flag.
+  pub fn sensitive<const M: usize>(self, v: impl DeclareInto<bool, M>) -> Self {
+    self.declare_builtin_init(
+      v,
+      |this| this.get_mix_builtin_widget().mix_flags(),
+      |m, v| m.set_sensitive(v),
+    )
+  }
+
+  /// Initializes whether the widget can be focused by the pointer hovering
+  /// over it, it works with the [`FocusFollowsMouse`] policy of the window.
+  ///
+  /// `false` keeps the widget from being focused by hovering. `true` allows
+  /// it, which is required if the policy is [`FocusFollowsMouse::AllowList`].
+  pub fn hover_focus<const M: usize>(self, v: impl DeclareInto<bool, M>) -> Self {
+    self.declare_builtin_init(
+      v,
+      |this| this.get_mix_builtin_widget().mix_flags(),
+      |m, v| m.set_hover_focus(v),
+    )
+  }
+
   /// Initializes how its child should be scale to fit its box.
   pub fn box_fit<const M: usize>(self, v: impl DeclareInto<BoxFit, M>) -> Self {
     self.declare_builtin_init(v, Self::get_fitted_box_widget, |m, v| m.box_fit = v)
@@ -854,6 +1030,11 @@ impl<T> FatObj<T> {
     self.declare_builtin_init(v, Self::get_text_style_widget, |m, v| m.text_style.overflow = v)
   }
 
+  /// Initializes the max lines of the text of this widget.
+  pub fn text_max_lines<const M: usize>(self, v: impl DeclareInto<Option<usize>, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_text_style_widget, |m, v| m.text_style.max_lines = v)
+  }
+
   /// Initializes the background of the widget.
   pub fn background<const M: usize>(self, v: impl DeclareInto<Brush, M>) -> Self {
     self.declare_builtin_init(v, Self::get_background_widget, |m, v| m.background = v)
@@ -904,6 +1085,31 @@ impl<T> FatObj<T> {
     self.declare_builtin_init(v, Self::get_transform_widget, |m, v| m.transform = v)
   }
 
+  /// Initializes the point of the widget that its transformation is around.
+  pub fn transform_origin<const M: usize>(self, v: impl DeclareInto<TransformOrigin, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_transform_widget, |m, v| m.transform_origin = v)
+  }
+
+  /// Initializes the rotation of the widget.
+  pub fn rotate<const M: usize>(self, v: impl DeclareInto<Angle, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_transform_widget, |m, v| m.rotate = v)
+  }
+
+  /// Initializes the uniform scale of the widget.
+  pub fn scale<const M: usize>(self, v: impl DeclareInto<f32, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_transform_widget, |m, v| m.scale = v)
+  }
+
+  /// Initializes the horizontal translation of the widget.
+  pub fn translate_x<const M: usize>(self, v: impl DeclareInto<f32, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_transform_widget, |m, v| m.translate_x = v)
+  }
+
+  /// Initializes the vertical translation of the widget.
+  pub fn translate_y<const M: usize>(self, v: impl DeclareInto<f32, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_transform_widget, |m, v| m.translate_y = v)
+  }
+
   /// Initializes how the widget should be aligned horizontally.
   pub fn h_align<const M: usize>(self, v: impl DeclareInto<HAlign, M>) -> Self {
     self.declare_builtin_init(v, Self::get_h_align_widget, |m, v| m.h_align = v)
@@ -934,16 +1140,57 @@ impl<T> FatObj<T> {
     self.declare_builtin_init(v, Self::get_visibility_widget, |m, v| m.visible = v)
   }
 
+  /// Initializes whether the widget keeps its space when it's hidden.
+  pub fn maintain_size<const M: usize>(self, v: impl DeclareInto<bool, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_visibility_widget, |m, v| m.maintain_size = v)
+  }
+
+  /// Initializes whether the widget can be hit and focused when it's hidden
+  /// but keeps its space.
+  pub fn maintain_interactivity<const M: usize>(self, v: impl DeclareInto<bool, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_visibility_widget, |m, v| m.maintain_interactivity = v)
+  }
+
   /// Initializes the opacity of the widget.
   pub fn opacity<const M: usize>(self, v: impl DeclareInto<f32, M>) -> Self {
     self.declare_builtin_init(v, Self::get_opacity_widget, |m, v| m.opacity = v)
   }
 
+  /// Initializes the opacity threshold below which the widget and its
+  /// descendants are transparent to the pointer events.
+  pub fn opacity_hit_threshold<const M: usize>(self, v: impl DeclareInto<f32, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_opacity_widget, |m, v| m.opacity_hit_threshold = v)
+  }
+
+  /// Initializes whether the widget and its descendants are composited as a
+  /// whole with the opacity.
+  pub fn opacity_group<const M: usize>(self, v: impl DeclareInto<bool, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_opacity_widget, |m, v| m.opacity_group = v)
+  }
+
   /// Initializes the tooltips of the widget.
   pub fn tooltips<const M: usize>(self, v: impl DeclareInto<CowArc<str>, M>) -> Self {
     self.declare_builtin_init(v, Self::get_tooltips_widget, |m, v| m.tooltips = v)
   }
 
+  /// Initializes the label that describes the widget in the accessibility
+  /// tree.
+  pub fn semantics_label<const M: usize>(self, v: impl DeclareInto<CowArc<str>, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_semantics_widget, |m, v| m.semantics_label = Some(v))
+  }
+
+  /// Initializes the role of the widget in the accessibility tree.
+  pub fn semantics_role<const M: usize>(self, v: impl DeclareInto<SemanticsRole, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_semantics_widget, |m, v| m.semantics_role = Some(v))
+  }
+
+  /// Initializes whether the semantics of the descendants are merged into the
+  /// widget, so they are presented as a single node in the accessibility
+  /// tree.
+  pub fn merge_semantics<const M: usize>(self, v: impl DeclareInto<bool, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_semantics_widget, |m, v| m.merge_semantics = v)
+  }
+
   /// Initializes the clip_boundary of the widget.
   pub fn clip_boundary<const M: usize>(self, v: impl DeclareInto<bool, M>) -> Self {
     self.declare_builtin_init(v, Self::get_clip_boundary_widget, |m, v| m.clip_boundary = v)
@@ -1090,6 +1337,7 @@ impl<'a> FatObj<Widget<'a>> {
           class,
           constrained_box,
           tooltips,
+          semantics,
           margin,
           cursor,
           mix_builtin,
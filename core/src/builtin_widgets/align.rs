@@ -21,6 +21,11 @@ pub enum Align {
   /// [`HAlign::Stretch`]! if direction is horizontal and [`VAlign::Stretch`]!
   /// if direction is vertical.
   Stretch,
+  /// The children are aligned by their first text baselines, and the children
+  /// without a baseline are aligned by their bottom edges. Only the cross axis
+  /// of a horizontal flex layout supports it, it's the same as `Start` in
+  /// others.
+  Baseline,
 }
 
 /// A enum that describe how widget align to its box in x-axis.
@@ -154,7 +159,7 @@ impl From<VAlign> for Align {
 impl From<Align> for HAlign {
   fn from(h: Align) -> Self {
     match h {
-      Align::Start => HAlign::Left,
+      Align::Start | Align::Baseline => HAlign::Left,
       Align::Center => HAlign::Center,
       Align::End => HAlign::Right,
       Align::Stretch => HAlign::Stretch,
@@ -165,7 +170,7 @@ impl From<Align> for HAlign {
 impl From<Align> for VAlign {
   fn from(h: Align) -> Self {
     match h {
-      Align::Start => VAlign::Top,
+      Align::Start | Align::Baseline => VAlign::Top,
       Align::Center => VAlign::Center,
       Align::End => VAlign::Bottom,
       Align::Stretch => VAlign::Stretch,
@@ -0,0 +1,106 @@
+use crate::{prelude::*, wrap_render::*};
+
+/// A widget that blurs its child by a gaussian blur, or blurs the content
+/// painted behind it with `backdrop`.
+///
+/// The child is painted to an offscreen texture, then the texture is blurred
+/// and composited. The blurred child spreads out of its box by about three
+/// times the `blur_radius`.
+///
+/// With `backdrop`, the child isn't blurred but painted on the blurred content
+/// behind the widget box, that's the frosted glass style panel. Clip the
+/// widget if the panel isn't a rectangle, the blurred area follows the clip.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let _frosted_glass = fn_widget! {
+///   @Blur {
+///     blur_radius: 12.,
+///     backdrop: true,
+///     @Container {
+///       size: Size::new(200., 100.),
+///       background: Color::WHITE.with_alpha(0.3),
+///     }
+///   }
+/// };
+/// ```
+#[derive(Declare, Clone)]
+pub struct Blur {
+  /// The standard deviation of the gaussian blur, in logical pixels.
+  pub blur_radius: f32,
+  /// Blur the content painted behind the widget box instead of its child.
+  #[declare(default)]
+  pub backdrop: bool,
+}
+
+impl_compose_child_for_wrap_render!(Blur, DirtyPhase::Paint);
+
+impl WrapRender for Blur {
+  fn perform_layout(&self, clamp: BoxClamp, host: &dyn Render, ctx: &mut LayoutCtx) -> Size {
+    host.perform_layout(clamp, ctx)
+  }
+
+  fn paint(&self, host: &dyn Render, ctx: &mut PaintingCtx) {
+    if self.blur_radius > 0. {
+      if self.backdrop {
+        let size = ctx.box_size().unwrap();
+        ctx
+          .box_painter()
+          .backdrop_blur(&Rect::from_size(size), self.blur_radius);
+      } else {
+        ctx.start_blur_layer(self.blur_radius);
+      }
+    }
+    host.paint(ctx)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  fn blur_commands(backdrop: bool) -> Vec<PaintCommand> {
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @Blur {
+          blur_radius: 4.,
+          backdrop,
+          @MockBox { size: Size::new(20., 20.), background: Color::RED }
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+    wnd.take_last_frame().unwrap().commands
+  }
+
+  #[test]
+  fn blur_child() {
+    reset_test_env!();
+
+    let commands = blur_commands(false);
+    let [PaintCommand::Blur { radius, bounds, cmds, .. }] = &commands[..] else {
+      panic!("expect a blur command, but got {commands:?}");
+    };
+    assert_eq!(*radius, 4.);
+    assert_eq!(*bounds, Rect::from_size(Size::new(20., 20.)));
+    assert_eq!(cmds.len(), 1);
+  }
+
+  #[test]
+  fn blur_backdrop() {
+    reset_test_env!();
+
+    let commands = blur_commands(true);
+    let [PaintCommand::BackdropBlur { radius, bounds, .. }, PaintCommand::Path(_)] = &commands[..]
+    else {
+      panic!("expect a backdrop blur before the child, but got {commands:?}");
+    };
+    assert_eq!(*radius, 4.);
+    assert_eq!(*bounds, Rect::from_size(Size::new(20., 20.)));
+  }
+}
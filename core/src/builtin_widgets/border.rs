@@ -754,4 +754,20 @@ mod tests {
       "all_borders"
     );
   }
+
+  #[test]
+  fn border_paint_snapshot() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(mock_multi! {
+      @ { border_100_50_box(10., 10., 10., 10., Some(Radius::all(5.))) }
+      @ { border_100_50_box(1., 2., 3., 4., None) }
+    });
+    wnd.draw_frame();
+
+    // The commands are relative to the widget box, so the second box is not
+    // affected by its position.
+    assert_paint_snapshot!(wnd.paint_commands_by_path(&[0, 0, 0]), "border_radius_paint");
+    assert_paint_snapshot!(wnd.paint_commands_by_path(&[0, 1, 0]), "border_paint");
+  }
 }
@@ -0,0 +1,184 @@
+use std::{cell::RefCell, rc::Rc};
+
+use ribir_algo::Resource;
+
+use crate::{prelude::*, wrap_render::*};
+
+/// A widget that paints its child and the descendants as a layer, and reuses
+/// the layer in the later frames while none of them changes.
+///
+/// The layer is drawn as a bundle of commands, the backend rasterizes it into
+/// an offscreen texture once and re-blits the texture in the later frames. So
+/// it saves both the painting of the subtree and the drawing of the backend.
+///
+/// It's opt-in, use it for a static but complex subtree, such as a chart. Don't
+/// use it for a subtree that changes every frame, the layer costs the texture
+/// memory and is repainted every time.
+///
+/// The layer is dropped when any widget in it is marked dirty or relayout.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let _chart = fn_widget! {
+///   @CacheLayer {
+///     @Stack {
+///       @Text { text: "A static chart with many paths." }
+///     }
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct CacheLayer {
+  #[declare(skip)]
+  layer: Rc<LayerCache>,
+}
+
+/// The bounds and the commands of a layer.
+type Layer = (Rect, Resource<Box<[PaintCommand]>>);
+
+/// The layer painted before.
+#[derive(Default)]
+pub(crate) struct LayerCache(RefCell<Option<Layer>>);
+
+/// Drop the layer when its host performs layout, the descendants may be
+/// moved.
+struct LayerRender(Rc<LayerCache>);
+
+impl<'c> ComposeChild<'c> for CacheLayer {
+  type Child = Widget<'c>;
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    let layer = this.read().layer.clone();
+    let render = Stateful::new(LayerRender(layer.clone()));
+    WrapRender::combine_child(render, child, DirtyPhase::Paint)
+      .attach_data(Box::new(Queryable(layer)))
+  }
+}
+
+impl WrapRender for LayerRender {
+  fn perform_layout(&self, clamp: BoxClamp, host: &dyn Render, ctx: &mut LayoutCtx) -> Size {
+    self.0.invalidate();
+    host.perform_layout(clamp, ctx)
+  }
+}
+
+impl LayerCache {
+  /// Return the bounds and the commands of the layer if it's still valid.
+  pub(crate) fn get(&self) -> Option<Layer> { self.0.borrow().clone() }
+
+  pub(crate) fn set(&self, bounds: Rect, commands: Resource<Box<[PaintCommand]>>) {
+    *self.0.borrow_mut() = Some((bounds, commands));
+  }
+
+  /// Drop the layer, return if there was one.
+  pub(crate) fn invalidate(&self) -> bool { self.0.borrow_mut().take().is_some() }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::cell::Cell;
+
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  struct PaintCnt(Rc<Cell<usize>>);
+
+  impl Render for PaintCnt {
+    fn perform_layout(&self, clamp: BoxClamp, _: &mut LayoutCtx) -> Size { clamp.max }
+
+    fn paint(&self, ctx: &mut PaintingCtx) {
+      self.0.set(self.0.get() + 1);
+      let size = ctx.box_size().unwrap();
+      ctx
+        .painter()
+        .rect(&Rect::from_size(size))
+        .set_fill_brush(Color::RED)
+        .fill();
+    }
+  }
+
+  fn bundle_count(wnd: &mut TestWindow) -> usize {
+    wnd
+      .take_last_frame()
+      .unwrap()
+      .commands
+      .iter()
+      .filter(|c| matches!(c, PaintCommand::Bundle { .. }))
+      .count()
+  }
+
+  #[test]
+  fn reuse_clean_layer() {
+    reset_test_env!();
+
+    let cnt = Rc::new(Cell::new(0));
+    let c_cnt = cnt.clone();
+    let (color, w_color) = split_value(Color::RED);
+    let (size, w_size) = split_value(Size::new(50., 50.));
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let cnt = c_cnt.clone();
+        @MockStack {
+          @MockBox {
+            size: Size::new(100., 100.),
+            background: pipe!(*$color),
+          }
+          @CacheLayer {
+            @MockBox {
+              size: pipe!(*$size),
+              @ { PaintCnt(cnt.clone()) }
+            }
+          }
+        }
+      },
+      Size::new(200., 200.),
+    );
+    wnd.draw_frame();
+    assert_eq!(cnt.get(), 1);
+    assert_eq!(bundle_count(&mut wnd), 1);
+
+    // The layer is clean, even if the window is repainted.
+    *w_color.write() = Color::BLUE;
+    wnd.draw_frame();
+    assert_eq!(cnt.get(), 1);
+    assert_eq!(bundle_count(&mut wnd), 1);
+
+    // The layer is dropped after its descendant relayout.
+    *w_size.write() = Size::new(60., 60.);
+    wnd.draw_frame();
+    assert_eq!(cnt.get(), 2);
+    assert_eq!(bundle_count(&mut wnd), 1);
+  }
+
+  #[test]
+  fn repaint_dirty_layer() {
+    reset_test_env!();
+
+    let cnt = Rc::new(Cell::new(0));
+    let c_cnt = cnt.clone();
+    let (opacity, w_opacity) = split_value(1.);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let cnt = c_cnt.clone();
+        @CacheLayer {
+          @MockBox {
+            size: Size::new(50., 50.),
+            @ {
+              let w = FatObj::new(PaintCnt(cnt.clone()));
+              @ $w { opacity: pipe!(*$opacity) }
+            }
+          }
+        }
+      },
+      Size::new(200., 200.),
+    );
+    wnd.draw_frame();
+    assert_eq!(cnt.get(), 1);
+
+    *w_opacity.write() = 0.5;
+    wnd.draw_frame();
+    assert_eq!(cnt.get(), 2);
+  }
+}
@@ -21,6 +21,20 @@ impl WrapRender for ConstrainedBox {
     host.perform_layout(BoxClamp { min, max }, ctx)
   }
 
+  fn min_intrinsic_width(&self, host: &dyn Render, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    let BoxClamp { min, max } = self.clamp;
+    host
+      .min_intrinsic_width(height.clamp(min.height, max.height), ctx)
+      .clamp(min.width, max.width)
+  }
+
+  fn min_intrinsic_height(&self, host: &dyn Render, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    let BoxClamp { min, max } = self.clamp;
+    host
+      .min_intrinsic_height(width.clamp(min.width, max.width), ctx)
+      .clamp(min.height, max.height)
+  }
+
   fn only_sized_by_parent(&self, host: &dyn Render) -> bool {
     self.clamp.min == self.clamp.max || host.only_sized_by_parent()
   }
@@ -16,6 +16,12 @@ impl Render for Container {
 
   #[inline]
   fn only_sized_by_parent(&self) -> bool { true }
+
+  #[inline]
+  fn min_intrinsic_width(&self, _: f32, _: &mut LayoutCtx) -> f32 { self.size.width }
+
+  #[inline]
+  fn min_intrinsic_height(&self, _: f32, _: &mut LayoutCtx) -> f32 { self.size.height }
 }
 
 #[cfg(test)]
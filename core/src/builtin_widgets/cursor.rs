@@ -1,7 +1,11 @@
 use crate::prelude::*;
 
 /// `Cursor` is an attribute to assign an `cursor` to a widget.
-
+///
+/// The cursor of the window is set to it when the mouse enters the widget, and
+/// restored when the mouse leaves. So a widget without a cursor shows the
+/// cursor of its nearest ancestor that declares one, and it follows the change
+/// of the `cursor` while the mouse is over the widget.
 #[derive(Default, Debug)]
 pub struct Cursor {
   pub cursor: CursorIcon,
@@ -18,6 +22,14 @@ impl<'c> ComposeChild<'c> for Cursor {
   fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
     fn_widget! {
       let save_cursor: Stateful<Option<CursorIcon>> = Stateful::new(None);
+      let wnd = BuildCtx::get().window();
+      let u = watch!($this.get_cursor())
+        .distinct_until_changed()
+        .subscribe(move |cursor| {
+          if $save_cursor.is_some() {
+            wnd.set_cursor(cursor);
+          }
+        });
       let child = FatObj::new(child);
       @ $child {
         on_pointer_enter: move |e: &mut PointerEvent| {
@@ -35,6 +47,7 @@ impl<'c> ComposeChild<'c> for Cursor {
           }
         },
         on_disposed: move |e| {
+          u.unsubscribe();
           if let Some(cursor) = $save_cursor.write().take() {
             e.window().set_cursor(cursor);
           }
@@ -120,4 +133,32 @@ mod tests {
     wnd.run_frame_tasks();
     assert_eq!(wnd.get_cursor(), CursorIcon::Help);
   }
+
+  #[test]
+  fn follow_cursor_change() {
+    reset_test_env!();
+
+    let (cursor, w_cursor) = split_value(CursorIcon::Pointer);
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        cursor: pipe!(*$cursor),
+      }
+    });
+    wnd.draw_frame();
+    assert_eq!(wnd.get_cursor(), CursorIcon::Default);
+
+    // Not hovered, the cursor of the window isn't changed.
+    *w_cursor.write() = CursorIcon::Wait;
+    wnd.draw_frame();
+    assert_eq!(wnd.get_cursor(), CursorIcon::Default);
+
+    wnd.process_input(TestInput::CursorMove(Point::new(1., 1.)));
+    wnd.draw_frame();
+    assert_eq!(wnd.get_cursor(), CursorIcon::Wait);
+
+    *w_cursor.write() = CursorIcon::Text;
+    wnd.draw_frame();
+    assert_eq!(wnd.get_cursor(), CursorIcon::Text);
+  }
 }
@@ -0,0 +1,78 @@
+use crate::prelude::*;
+
+/// A widget that starts a drag from its child when the pointer is pressed on
+/// it and moves over the `threshold` distance.
+///
+/// Fill the payload of the drag in `on_drag_start`, and the widgets under the
+/// pointer that listen to `on_drag_enter`, `on_drag_over`, `on_drag_leave` or
+/// `on_drop` are the drop targets.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @Row {
+///     @Draggable {
+///       drag_image: GenWidget::new(|| @Text { text: "Moving" }.into_widget()),
+///       on_drag_start: move |e| e.data.insert("Hello".to_string()),
+///       @Text { text: "Drag me" }
+///     }
+///     @Container {
+///       size: Size::new(100., 100.),
+///       on_drop: move |e| if let Some(txt) = e.data.get::<String>() {
+///         println!("dropped {txt}");
+///       },
+///     }
+///   }
+/// };
+/// ```
+#[derive(Declare, Clone)]
+pub struct Draggable {
+  /// The distance in logical pixels that the pointer moves after pressed to
+  /// start the drag.
+  #[declare(default = 4.)]
+  pub threshold: f32,
+  /// The widget follows the pointer above all the other widgets during the
+  /// drag, it keeps the offset to the pointer as the child when pressed.
+  #[declare(default)]
+  pub drag_image: Option<GenWidget>,
+}
+
+impl<'c> ComposeChild<'c> for Draggable {
+  type Child = Widget<'c>;
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    (fn_widget! {
+      // The global position and the local position that the pointer pressed.
+      let pressed: Stateful<Option<(Point, Point)>> = Stateful::new(None);
+      let grab: Stateful<Option<GrabPointer>> = Stateful::new(None);
+      let child = FatObj::new(child);
+      @ $child {
+        on_pointer_down: move |e| if e.mouse_buttons() == MouseButtons::PRIMARY {
+          *$pressed.write() = Some((e.global_pos(), e.position()));
+        },
+        on_pointer_move: move |e| {
+          let Some((from, offset)) = *$pressed else { return };
+          let wnd = e.window();
+          if e.mouse_buttons().is_empty() {
+            // The pointer is released out of the widget.
+            $pressed.write().take();
+          } else if !wnd.is_dragging() && (e.global_pos() - from).length() > $this.threshold {
+            let source = e.current_target();
+            *$grab.write() = GrabPointer::grab(source, &wnd);
+            let image = $this.drag_image.clone().map(|image| (image, offset.to_vector()));
+            wnd.start_drag(source, image);
+          }
+        },
+        on_pointer_up: move |_| {
+          $pressed.write().take();
+          $grab.write().take();
+        },
+      }
+      .into_widget()
+      .try_unwrap_state_and_attach(this)
+    })
+    .into_widget()
+  }
+}
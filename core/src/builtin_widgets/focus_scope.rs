@@ -12,6 +12,13 @@ pub struct FocusScope {
   /// If false, then the host widget can be focused.
   #[declare(default = true)]
   pub skip_host: bool,
+
+  /// If true, the tab navigation can not move the focus out of the scope, it
+  /// wraps around in the scope instead, and moves the focus into the scope
+  /// from outside. That's what a modal dialog wants. When there are multiple
+  /// trap scopes, the last one in the tree wins.
+  #[declare(default)]
+  pub trap: bool,
 }
 
 impl<'c> ComposeChild<'c> for FocusScope {
@@ -147,6 +154,50 @@ mod tests {
     }
   }
 
+  #[test]
+  fn tab_trap_scope() {
+    reset_test_env!();
+
+    let size = Size::zero();
+    let widget = fn_widget! {
+      @MockMulti {
+        @MockBox { size, tab_index: 0i16, auto_focus: true }
+        @FocusScope {
+          trap: true,
+          @MockMulti {
+            @MockBox { size, tab_index: 0i16, }
+            @MockBox { size, tab_index: 0i16, }
+          }
+        }
+        @MockBox { size, tab_index: 0i16 }
+      }
+    };
+
+    let wnd = TestWindow::new(widget);
+    let mut focus_mgr = wnd.focus_mgr.borrow_mut();
+    let tree = wnd.tree();
+    focus_mgr.refresh_focus(tree);
+
+    let id0 = tree.content_root().first_child(tree).unwrap();
+    let scope = id0.next_sibling(tree).unwrap();
+    let scope_id1 = scope.first_child(tree).unwrap();
+    let scope_id2 = scope_id1.next_sibling(tree).unwrap();
+    assert_eq!(focus_mgr.focusing(), Some(id0));
+
+    // the focus moves into the trap scope and wraps around in it.
+    focus_mgr.focus_next_widget(tree);
+    assert_eq!(focus_mgr.focusing(), Some(scope_id1));
+    focus_mgr.focus_next_widget(tree);
+    assert_eq!(focus_mgr.focusing(), Some(scope_id2));
+    focus_mgr.focus_next_widget(tree);
+    assert_eq!(focus_mgr.focusing(), Some(scope_id1));
+
+    focus_mgr.focus_prev_widget(tree);
+    assert_eq!(focus_mgr.focusing(), Some(scope_id2));
+    focus_mgr.focus_prev_widget(tree);
+    assert_eq!(focus_mgr.focusing(), Some(scope_id1));
+  }
+
   #[test]
   fn focus_scope() {
     reset_test_env!();
@@ -6,6 +6,10 @@ impl Render for Resource<PixelImage> {
     clamp.clamp(size)
   }
 
+  fn min_intrinsic_width(&self, _: f32, _: &mut LayoutCtx) -> f32 { self.width() as f32 }
+
+  fn min_intrinsic_height(&self, _: f32, _: &mut LayoutCtx) -> f32 { self.height() as f32 }
+
   fn paint(&self, ctx: &mut PaintingCtx) {
     let size = ctx.box_size().unwrap();
     let box_rect = Rect::from_size(size);
@@ -94,6 +94,38 @@ pub struct KeyWidget<V: 'static> {
   has_successor: bool,
 }
 
+/// A child generated by a multi-child pipe and identified by its key.
+///
+/// When the pipe regenerates, the widget of a key that was also generated last
+/// time is moved to its new position instead of being disposed and built
+/// again, only the widgets of the new keys are built. So a large dynamic list
+/// only pays for the items that are really inserted.
+///
+/// ```rust
+/// use ribir_core::{prelude::*, test_helper::*};
+///
+/// let list = Stateful::new(vec![1, 2, 3]);
+/// let _w = fn_widget! {
+///   @MockMulti {
+///     @ {
+///       pipe!($list.clone()).map(move |list| {
+///         list.into_iter().map(move |i| {
+///           Keyed::new(i, @MockBox { size: Size::new(i as f32, 10.) })
+///         })
+///       })
+///     }
+///   }
+/// };
+/// ```
+pub struct Keyed<W> {
+  pub key: Key,
+  pub widget: W,
+}
+
+impl<W> Keyed<W> {
+  pub fn new(key: impl Into<Key>, widget: W) -> Self { Self { key: key.into(), widget } }
+}
+
 /// A trait for `keyWidget` that use to record information of the previous and
 /// next key widget.
 pub(crate) trait AnyKey: Any {
@@ -0,0 +1,98 @@
+use std::cell::{Cell, RefCell};
+
+use crate::prelude::*;
+
+type ClampBuilder = Box<dyn FnMut(BoxClamp) -> Widget<'static>>;
+
+/// A widget builds its child by the clamp it receives from its parent during
+/// layout, so the structure of the child can depend on the available space.
+///
+/// The child is rebuilt only when the clamp changes, and it's built and laid
+/// out in the same layout pass, so there is no frame with a stale child.
+///
+/// # Example
+///
+/// Show the items in a row if there is enough width, otherwise in a column.
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @LayoutBuilder::new(|clamp| {
+///     let items = || ["Home", "Search", "Settings"].map(|t| @Text { text: t });
+///     if clamp.max.width >= 400. {
+///       @Row { @{ items() } }.into_widget()
+///     } else {
+///       @Column { @{ items() } }.into_widget()
+///     }
+///   })
+/// };
+/// ```
+pub struct LayoutBuilder {
+  builder: RefCell<ClampBuilder>,
+  clamp: Cell<Option<BoxClamp>>,
+}
+
+impl LayoutBuilder {
+  pub fn new(builder: impl FnMut(BoxClamp) -> Widget<'static> + 'static) -> Self {
+    Self { builder: RefCell::new(Box::new(builder)), clamp: Cell::new(None) }
+  }
+}
+
+impl Render for LayoutBuilder {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let child = if self.clamp.get() != Some(clamp) {
+      self.clamp.set(Some(clamp));
+      let child = (self.builder.borrow_mut())(clamp);
+      Some(ctx.rebuild_child(child))
+    } else {
+      ctx.single_child()
+    };
+
+    child.map_or(clamp.min, |child| ctx.perform_child_layout(child, clamp))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::rc::Rc;
+
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[test]
+  fn rebuild_by_clamp() {
+    reset_test_env!();
+
+    let (width, w_width) = split_value(100.);
+    let builds = Rc::new(Cell::new(0));
+    let c_builds = builds.clone();
+    let mut wnd = TestWindow::new(fn_widget! {
+      let c_builds = c_builds.clone();
+      @MockBox {
+        size: pipe!(Size::new(*$width, 100.)),
+        @LayoutBuilder::new(move |clamp| {
+          c_builds.set(c_builds.get() + 1);
+          let height = if clamp.max.width > 150. { 20. } else { 40. };
+          @MockBox { size: Size::new(10., height) }.into_widget()
+        })
+      }
+    });
+    wnd.draw_frame();
+    assert_eq!(builds.get(), 1);
+    wnd.assert_root_size(Size::new(100., 100.));
+    let child = wnd.layout_info_by_path(&[0, 0, 0]).unwrap();
+    assert_eq!(child.size, Some(Size::new(10., 40.)));
+
+    *w_width.write() = 200.;
+    wnd.draw_frame();
+    assert_eq!(builds.get(), 2);
+    let child = wnd.layout_info_by_path(&[0, 0, 0]).unwrap();
+    assert_eq!(child.size, Some(Size::new(10., 20.)));
+
+    // Relayout with the same clamp not rebuild the child.
+    *w_width.write() = 200.;
+    wnd.draw_frame();
+    assert_eq!(builds.get(), 2);
+  }
+}
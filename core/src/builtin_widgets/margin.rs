@@ -56,6 +56,22 @@ impl Render for Margin {
 
     size + thickness
   }
+
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    let EdgeInsets { top, right, bottom, left } = self.margin;
+    let width = ctx
+      .single_child()
+      .map_or(0., |c| ctx.min_intrinsic_width_of(c, (height - top - bottom).max(0.)));
+    width + left + right
+  }
+
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    let EdgeInsets { top, right, bottom, left } = self.margin;
+    let height = ctx
+      .single_child()
+      .map_or(0., |c| ctx.min_intrinsic_height_of(c, (width - left - right).max(0.)));
+    height + top + bottom
+  }
 }
 
 impl Margin {
@@ -1,6 +1,10 @@
-use std::{cell::RefCell, convert::Infallible};
+use std::{
+  cell::{Cell, RefCell},
+  convert::Infallible,
+};
 
 use rxrust::prelude::*;
+use smallvec::SmallVec;
 
 use self::focus_mgr::FocusType;
 use crate::prelude::*;
@@ -30,13 +34,52 @@ bitflags! {
      custom events"]
     const Customs = 1 << 6;
 
+    #[doc="Window resized listener flag, hint the widget is listening to the \
+     resize of the window"]
+    const WindowResized = 1 << 7;
+    #[doc="Accessibility action listener flag, hint the widget is listening to \
+     the actions requested by the accessibility subsystem"]
+    const Accessibility = 1 << 8;
+    #[doc="Drag source listener flag, hint the widget is listening to the \
+     start and the end of the drags from it"]
+    const DragSource = 1 << 9;
+    #[doc="Drop target listener flag, hint the widget is a drop target that \
+     listening to the drags over it"]
+    const DropTarget = 1 << 10;
+    #[doc="File drop listener flag, hint the widget is listening to the files \
+     dragged from the operating system"]
+    const FileDrop = 1 << 11;
+    #[doc="Touch listener flag, hint the widget is listening to the fingers \
+     touching the screen"]
+    const Touch = 1 << 12;
+    #[doc="Pan gesture listener flag, hint the widget is listening to the pan"]
+    const Pan = 1 << 13;
+    #[doc="Pinch gesture listener flag, hint the widget is listening to the pinch"]
+    const Pinch = 1 << 14;
+    #[doc="Long press gesture listener flag, hint the widget is listening to the \
+     long press"]
+    const LongPress = 1 << 15;
+    #[doc="Context menu listener flag, hint the widget is listening to the \
+     request of the context menu"]
+    const ContextMenu = 1 << 22;
+
     const AllListeners = Self::Lifecycle.bits()
       | Self::Pointer.bits()
       | Self::Wheel.bits()
       | Self::KeyBoard.bits()
       | Self::Focus.bits()
       | Self::FocusInOut.bits()
-      | Self::Customs.bits();
+      | Self::Customs.bits()
+      | Self::WindowResized.bits()
+      | Self::Accessibility.bits()
+      | Self::DragSource.bits()
+      | Self::DropTarget.bits()
+      | Self::FileDrop.bits()
+      | Self::Touch.bits()
+      | Self::Pan.bits()
+      | Self::Pinch.bits()
+      | Self::LongPress.bits()
+      | Self::ContextMenu.bits();
     // listener end
 
     #[doc="Indicates whether this widget is tracing its focus status."]
@@ -52,6 +95,14 @@ bitflags! {
     #[doc="Indicates whether the pointer is pressed on this widget."]
     const PointerPressed = 1 << 21;
 
+    #[doc="Indicates whether this widget allows being focused by the pointer \
+     hovering over it."]
+    const HoverFocus = 1 << 44;
+    #[doc="Indicates whether this widget denies being focused by the pointer \
+     hovering over it."]
+    const NoHoverFocus = 1 << 45;
+    #[doc="Indicates whether this widget holds sensitive content, like a password."]
+    const Sensitive = 1 << 46;
     #[doc="Indicates whether this widget has auto-focus functionality."]
     const AutoFocus = 1 << 47;
     // The last 16 bits keep for tab index
@@ -59,10 +110,39 @@ bitflags! {
 }
 
 pub type EventSubject = MutRefItemSubject<'static, Event, Infallible>;
+type PrioritySubjects = SmallVec<[(ListenerPriority, EventSubject); 1]>;
+
+/// The priority of the event listeners. The listeners of a higher priority on
+/// a widget are called before the lower ones, and the listeners of the same
+/// priority are called in the order they are added.
+///
+/// A composed widget can add its internal listeners with
+/// `ListenerPriority::INTERNAL` to guarantee they run before the listeners of
+/// the users, and stop the propagation of the event to the user listeners if
+/// needed.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Default, Hash)]
+pub struct ListenerPriority(pub i32);
+
+impl ListenerPriority {
+  /// The priority of the internal listeners of a composed widget.
+  pub const INTERNAL: Self = Self(100);
+  /// The default priority of the listeners.
+  pub const NORMAL: Self = Self(0);
+  /// The priority of the listeners that should run after the others.
+  pub const LOW: Self = Self(-100);
+}
 
 pub struct MixBuiltin {
   flags: State<MixFlags>,
+  /// The subject of the listeners of the normal priority.
   subject: EventSubject,
+  /// The subjects of the listeners of other priorities, sorted by the priority
+  /// from high to low.
+  prior_subjects: Sc<RefCell<PrioritySubjects>>,
+  /// The priority of the listeners being added.
+  priority: Cell<ListenerPriority>,
+  /// Whether the listeners being added only are called once.
+  once: Cell<bool>,
 }
 
 impl Declare for MixBuiltin {
@@ -86,6 +166,7 @@ macro_rules! impl_event_callback {
     let _ = $this
       .subject()
       .filter_map(event_map_filter!($event_name, $event_ty))
+      .take($this.take_count())
       .subscribe($handler);
 
     $this
@@ -131,6 +212,34 @@ impl MixFlags {
     }
   }
 
+  /// Indicates whether this widget is marked as holding sensitive content.
+  ///
+  /// The content of a sensitive widget and its descendants is not allowed to be
+  /// copied to the clipboard, the IME works in password mode when they are
+  /// focused, and their content is redacted from the painted frame if the
+  /// window sets the `WindowFlags::REDACT_SENSITIVE` flag.
+  pub fn is_sensitive(&self) -> bool { self.contains(MixFlags::Sensitive) }
+
+  pub fn set_sensitive(&mut self, v: bool) { self.set(MixFlags::Sensitive, v); }
+
+  /// Indicates whether this widget allows (`Some(true)`) or denies
+  /// (`Some(false)`) being focused by the pointer hovering over it, `None`
+  /// means it follows the [`FocusFollowsMouse`] policy of the window.
+  pub fn hover_focus(&self) -> Option<bool> {
+    if self.contains(MixFlags::HoverFocus) {
+      Some(true)
+    } else if self.contains(MixFlags::NoHoverFocus) {
+      Some(false)
+    } else {
+      None
+    }
+  }
+
+  pub fn set_hover_focus(&mut self, v: bool) {
+    self.set(MixFlags::HoverFocus, v);
+    self.set(MixFlags::NoHoverFocus, !v);
+  }
+
   pub fn tab_index(&self) -> Option<i16> {
     self
       .contains(MixFlags::Focus)
@@ -147,12 +256,52 @@ impl MixFlags {
 impl MixBuiltin {
   pub fn mix_flags(&self) -> &State<MixFlags> { &self.flags }
 
-  pub fn dispatch(&self, event: &mut Event) { self.subject.clone().next(event) }
+  /// Dispatch the event to the listeners by the order of their priorities.
+  pub fn dispatch(&self, event: &mut Event) {
+    let mut listeners = SmallVec::new();
+    self.collect_listeners(&mut listeners);
+    listeners
+      .into_iter()
+      .for_each(|(_, mut s)| s.next(&mut *event));
+  }
+
+  /// Add the listeners in `f` with the `priority`, the listeners of a higher
+  /// priority are called before the lower ones when an event is dispatched to
+  /// this widget, even if they are added in another `MixBuiltin` of the same
+  /// widget.
+  ///
+  /// ```
+  /// use ribir_core::prelude::*;
+  ///
+  /// let mut obj = FatObj::new(Void);
+  /// obj
+  ///   .get_mix_builtin_widget()
+  ///   .with_priority(ListenerPriority::INTERNAL, |mix| {
+  ///     mix.on_tap(|e| e.stop_propagation());
+  ///   });
+  /// ```
+  pub fn with_priority(&self, priority: ListenerPriority, f: impl FnOnce(&Self)) -> &Self {
+    let old = self.priority.replace(priority);
+    f(self);
+    self.priority.set(old);
+    self
+  }
+
+  /// Add the listeners in `f` that are unsubscribed after their first call.
+  pub fn once(&self, f: impl FnOnce(&Self)) -> &Self {
+    let old = self.once.replace(true);
+    f(self);
+    self.once.set(old);
+    self
+  }
 
   /// Listen to all events
   pub fn on_event(&self, handler: impl FnMut(&mut Event) + 'static) -> &Self {
     self.silent_mark(MixFlags::AllListeners);
-    let _ = self.subject().subscribe(handler);
+    let _ = self
+      .subject()
+      .take(self.take_count())
+      .subscribe(handler);
     self
   }
 
@@ -162,7 +311,7 @@ impl MixBuiltin {
       .subject()
       .filter_map(event_map_filter!(Mounted, LifecycleEvent))
       .take(1)
-      .subscribe(life_fn_once_to_fn_mut(handler));
+      .subscribe(fn_once_to_fn_mut(handler));
 
     self
   }
@@ -177,11 +326,95 @@ impl MixBuiltin {
       .subject()
       .filter_map(event_map_filter!(Disposed, LifecycleEvent))
       .take(1)
-      .subscribe(life_fn_once_to_fn_mut(handler));
+      .subscribe(fn_once_to_fn_mut(handler));
 
     self
   }
 
+  pub fn on_window_resized(&self, handler: impl FnMut(&mut LifecycleEvent) + 'static) -> &Self {
+    impl_event_callback!(self, WindowResized, WindowResized, LifecycleEvent, handler)
+  }
+
+  pub fn on_accessibility_action(
+    &self, handler: impl FnMut(&mut AccessibilityActionEvent) + 'static,
+  ) -> &Self {
+    impl_event_callback!(
+      self,
+      Accessibility,
+      AccessibilityAction,
+      AccessibilityActionEvent,
+      handler
+    )
+  }
+
+  pub fn on_drag_start(&self, handler: impl FnMut(&mut DragEvent) + 'static) -> &Self {
+    impl_event_callback!(self, DragSource, DragStart, DragEvent, handler)
+  }
+
+  pub fn on_drag_end(&self, handler: impl FnMut(&mut DragEvent) + 'static) -> &Self {
+    impl_event_callback!(self, DragSource, DragEnd, DragEvent, handler)
+  }
+
+  pub fn on_drag_enter(&self, handler: impl FnMut(&mut DragEvent) + 'static) -> &Self {
+    impl_event_callback!(self, DropTarget, DragEnter, DragEvent, handler)
+  }
+
+  pub fn on_drag_over(&self, handler: impl FnMut(&mut DragEvent) + 'static) -> &Self {
+    impl_event_callback!(self, DropTarget, DragOver, DragEvent, handler)
+  }
+
+  pub fn on_drag_leave(&self, handler: impl FnMut(&mut DragEvent) + 'static) -> &Self {
+    impl_event_callback!(self, DropTarget, DragLeave, DragEvent, handler)
+  }
+
+  pub fn on_drop(&self, handler: impl FnMut(&mut DragEvent) + 'static) -> &Self {
+    impl_event_callback!(self, DropTarget, Drop, DragEvent, handler)
+  }
+
+  pub fn on_file_hover(&self, handler: impl FnMut(&mut FileDropEvent) + 'static) -> &Self {
+    impl_event_callback!(self, FileDrop, FileHover, FileDropEvent, handler)
+  }
+
+  pub fn on_file_hover_cancel(&self, handler: impl FnMut(&mut FileDropEvent) + 'static) -> &Self {
+    impl_event_callback!(self, FileDrop, FileHoverCancel, FileDropEvent, handler)
+  }
+
+  pub fn on_file_drop(&self, handler: impl FnMut(&mut FileDropEvent) + 'static) -> &Self {
+    impl_event_callback!(self, FileDrop, FileDrop, FileDropEvent, handler)
+  }
+
+  pub fn on_touch_start(&self, handler: impl FnMut(&mut TouchEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Touch, TouchStart, TouchEvent, handler)
+  }
+
+  pub fn on_touch_move(&self, handler: impl FnMut(&mut TouchEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Touch, TouchMove, TouchEvent, handler)
+  }
+
+  pub fn on_touch_end(&self, handler: impl FnMut(&mut TouchEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Touch, TouchEnd, TouchEvent, handler)
+  }
+
+  pub fn on_touch_cancel(&self, handler: impl FnMut(&mut TouchEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Touch, TouchCancel, TouchEvent, handler)
+  }
+
+  pub fn on_pan(&self, handler: impl FnMut(&mut PanEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Pan, Pan, PanEvent, handler)
+  }
+
+  pub fn on_pinch(&self, handler: impl FnMut(&mut PinchEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Pinch, Pinch, PinchEvent, handler)
+  }
+
+  pub fn on_long_press(&self, handler: impl FnMut(&mut LongPressEvent) + 'static) -> &Self {
+    impl_event_callback!(self, LongPress, LongPress, LongPressEvent, handler)
+  }
+
+  pub fn on_context_menu(&self, handler: impl FnMut(&mut PointerEvent) + 'static) -> &Self {
+    impl_event_callback!(self, ContextMenu, ContextMenu, PointerEvent, handler)
+  }
+
   pub fn on_pointer_down(&self, handler: impl FnMut(&mut PointerEvent) + 'static) -> &Self {
     impl_event_callback!(self, Pointer, PointerDown, PointerEvent, handler)
   }
@@ -270,6 +503,7 @@ impl MixBuiltin {
     self
       .subject()
       .filter_map(x_times_tap_map_filter(times, dur, capture))
+      .take(self.take_count())
       .subscribe(handler);
     self
   }
@@ -378,7 +612,49 @@ impl MixBuiltin {
     }
   }
 
-  fn subject(&self) -> EventSubject { self.subject.clone() }
+  /// The subject of the priority of the listeners being added.
+  fn subject(&self) -> EventSubject {
+    let priority = self.priority.get();
+    if priority == ListenerPriority::NORMAL {
+      return self.subject.clone();
+    }
+
+    let mut subjects = self.prior_subjects.borrow_mut();
+    match subjects.binary_search_by(|(p, _)| priority.cmp(p)) {
+      Ok(idx) => subjects[idx].1.clone(),
+      Err(idx) => {
+        let subject = EventSubject::default();
+        subjects.insert(idx, (priority, subject.clone()));
+        subject
+      }
+    }
+  }
+
+  fn take_count(&self) -> usize { if self.once.get() { 1 } else { usize::MAX } }
+
+  /// Collect the subjects of the listeners with their priorities, sorted from
+  /// high to low.
+  fn collect_listeners(&self, out: &mut SmallVec<[(ListenerPriority, EventSubject); 2]>) {
+    let subjects = self.prior_subjects.borrow();
+    let split = subjects.partition_point(|(p, _)| *p > ListenerPriority::NORMAL);
+    out.extend(subjects[..split].iter().cloned());
+    out.push((ListenerPriority::NORMAL, self.subject.clone()));
+    out.extend(subjects[split..].iter().cloned());
+  }
+
+  /// Return the listeners of the `mixes` of a widget that listen to the
+  /// `flags`, sorted by their priorities from high to low. The listeners of the
+  /// same priority keep the order of the `mixes`.
+  pub(crate) fn prioritized_listeners<'a>(
+    mixes: impl Iterator<Item = QueryRef<'a, MixBuiltin>>, flags: MixFlags,
+  ) -> SmallVec<[EventSubject; 2]> {
+    let mut listeners = SmallVec::new();
+    for m in mixes.filter(|m| m.contain_flag(flags)) {
+      m.collect_listeners(&mut listeners);
+    }
+    listeners.sort_by_key(|(priority, _)| std::cmp::Reverse(*priority));
+    listeners.into_iter().map(|(_, s)| s).collect()
+  }
 
   pub(crate) fn contain_flag(&self, t: MixFlags) -> bool { self.flags.read().contains(t) }
 
@@ -389,9 +665,80 @@ impl MixBuiltin {
   }
 }
 
-fn life_fn_once_to_fn_mut(
-  handler: impl FnOnce(&mut LifecycleEvent),
-) -> impl FnMut(&mut LifecycleEvent) {
+macro_rules! impl_once_callbacks {
+  ($($on: ident, $once: ident, $ty: ty;)*) => {
+    impl MixBuiltin {
+      $(
+        #[doc = concat!(
+          "Like [`MixBuiltin::", stringify!($on), "`], but the handler is only called once."
+        )]
+        pub fn $once(&self, handler: impl FnOnce(&mut $ty) + 'static) -> &Self {
+          self.once(|this| {
+            this.$on(fn_once_to_fn_mut(handler));
+          })
+        }
+      )*
+    }
+  };
+}
+
+impl_once_callbacks!(
+  on_event, on_event_once, Event;
+  on_performed_layout, on_performed_layout_once, LifecycleEvent;
+  on_window_resized, on_window_resized_once, LifecycleEvent;
+  on_accessibility_action, on_accessibility_action_once, AccessibilityActionEvent;
+  on_drag_start, on_drag_start_once, DragEvent;
+  on_drag_end, on_drag_end_once, DragEvent;
+  on_drag_enter, on_drag_enter_once, DragEvent;
+  on_drag_over, on_drag_over_once, DragEvent;
+  on_drag_leave, on_drag_leave_once, DragEvent;
+  on_drop, on_drop_once, DragEvent;
+  on_file_hover, on_file_hover_once, FileDropEvent;
+  on_file_hover_cancel, on_file_hover_cancel_once, FileDropEvent;
+  on_file_drop, on_file_drop_once, FileDropEvent;
+  on_touch_start, on_touch_start_once, TouchEvent;
+  on_touch_move, on_touch_move_once, TouchEvent;
+  on_touch_end, on_touch_end_once, TouchEvent;
+  on_touch_cancel, on_touch_cancel_once, TouchEvent;
+  on_pan, on_pan_once, PanEvent;
+  on_pinch, on_pinch_once, PinchEvent;
+  on_long_press, on_long_press_once, LongPressEvent;
+  on_context_menu, on_context_menu_once, PointerEvent;
+  on_pointer_down, on_pointer_down_once, PointerEvent;
+  on_pointer_down_capture, on_pointer_down_capture_once, PointerEvent;
+  on_pointer_up, on_pointer_up_once, PointerEvent;
+  on_pointer_up_capture, on_pointer_up_capture_once, PointerEvent;
+  on_pointer_move, on_pointer_move_once, PointerEvent;
+  on_pointer_move_capture, on_pointer_move_capture_once, PointerEvent;
+  on_pointer_cancel, on_pointer_cancel_once, PointerEvent;
+  on_pointer_enter, on_pointer_enter_once, PointerEvent;
+  on_pointer_leave, on_pointer_leave_once, PointerEvent;
+  on_tap, on_tap_once, PointerEvent;
+  on_tap_capture, on_tap_capture_once, PointerEvent;
+  on_double_tap, on_double_tap_once, PointerEvent;
+  on_double_tap_capture, on_double_tap_capture_once, PointerEvent;
+  on_triple_tap, on_triple_tap_once, PointerEvent;
+  on_triple_tap_capture, on_triple_tap_capture_once, PointerEvent;
+  on_wheel, on_wheel_once, WheelEvent;
+  on_wheel_capture, on_wheel_capture_once, WheelEvent;
+  on_ime_pre_edit, on_ime_pre_edit_once, ImePreEditEvent;
+  on_ime_pre_edit_capture, on_ime_pre_edit_capture_once, ImePreEditEvent;
+  on_chars, on_chars_once, CharsEvent;
+  on_chars_capture, on_chars_capture_once, CharsEvent;
+  on_key_down, on_key_down_once, KeyboardEvent;
+  on_key_down_capture, on_key_down_capture_once, KeyboardEvent;
+  on_key_up, on_key_up_once, KeyboardEvent;
+  on_key_up_capture, on_key_up_capture_once, KeyboardEvent;
+  on_focus, on_focus_once, FocusEvent;
+  on_blur, on_blur_once, FocusEvent;
+  on_focus_in, on_focus_in_once, FocusEvent;
+  on_focus_in_capture, on_focus_in_capture_once, FocusEvent;
+  on_focus_out, on_focus_out_once, FocusEvent;
+  on_focus_out_capture, on_focus_out_capture_once, FocusEvent;
+  on_custom_event, on_custom_event_once, RawCustomEvent;
+);
+
+fn fn_once_to_fn_mut<E: ?Sized>(handler: impl FnOnce(&mut E)) -> impl FnMut(&mut E) {
   let mut handler = Some(handler);
   move |e| {
     if let Some(h) = handler.take() {
@@ -445,10 +792,16 @@ impl<'c> ComposeChild<'c> for MixBuiltin {
     if mix.contain_flag(MixFlags::Focus) {
       child = callbacks_for_focus_node(child);
     }
-    if !mix.subject.is_empty() {
-      let subject = mix.subject.clone();
+    let mut listeners = SmallVec::new();
+    mix.collect_listeners(&mut listeners);
+    listeners.retain(|(_, s)| !s.is_empty());
+    if !listeners.is_empty() {
       mix.on_disposed(move |_| {
-        let _ = AppCtx::spawn_local(async move { subject.unsubscribe() });
+        let _ = AppCtx::spawn_local(async move {
+          listeners
+            .into_iter()
+            .for_each(|(_, s)| s.unsubscribe())
+        });
       });
     }
     drop(mix);
@@ -472,7 +825,7 @@ fn x_times_tap_map_filter(
       Event::TapCapture(e) if capture => e,
       _ => return None,
     };
-    let now = Instant::now();
+    let now = FrameClock::now();
     match &mut type_info {
       Some(info) if info.pointer_id == e.id => {
         if info.stamps.len() + 1 == x {
@@ -501,21 +854,35 @@ fn x_times_tap_map_filter(
 
 impl Default for MixBuiltin {
   fn default() -> Self {
-    Self { flags: State::value(MixFlags::default()), subject: Default::default() }
+    Self {
+      flags: State::value(MixFlags::default()),
+      subject: Default::default(),
+      prior_subjects: Default::default(),
+      priority: Cell::new(ListenerPriority::NORMAL),
+      once: Cell::new(false),
+    }
   }
 }
 
 impl Clone for MixBuiltin {
   fn clone(&self) -> Self {
     let flags = self.flags.clone_writer();
-    Self { flags, subject: self.subject.clone() }
+    Self {
+      flags,
+      subject: self.subject.clone(),
+      prior_subjects: self.prior_subjects.clone(),
+      priority: Cell::new(ListenerPriority::NORMAL),
+      once: Cell::new(false),
+    }
   }
 }
 
 #[cfg(test)]
 mod tests {
+  use winit::event::{DeviceId, ElementState, MouseButton, WindowEvent};
+
   use super::*;
-  use crate::{reset_test_env, test_helper::*};
+  use crate::{reset_test_env, test_helper::*, window::WindowFlags};
 
   #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
   #[test]
@@ -544,4 +911,78 @@ mod tests {
     wnd.draw_frame();
     assert_eq!(*outer_layout.read(), 2);
   }
+
+  #[test]
+  fn listener_priority_and_once() {
+    reset_test_env!();
+
+    let (order, w_order) = split_value(vec![]);
+    let w = fn_widget! {
+      let mut inner = FatObj::new(MockBox { size: Size::new(100., 100.) });
+      let mix = inner.get_mix_builtin_widget();
+      mix.with_priority(ListenerPriority::LOW, |mix| {
+        mix.on_tap(move |_| $w_order.write().push("low"));
+      });
+      mix.with_priority(ListenerPriority::INTERNAL, |mix| {
+        mix.on_tap_once(move |_| $w_order.write().push("internal"));
+      });
+
+      FatObj::new(inner.into_widget()).on_tap(move |_| $w_order.write().push("normal"))
+    };
+
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+    let tap = |wnd: &mut TestWindow| {
+      let device_id = unsafe { DeviceId::dummy() };
+      #[allow(deprecated)]
+      wnd.processes_native_event(WindowEvent::CursorMoved {
+        device_id,
+        position: (50f64, 50f64).into(),
+      });
+      wnd.process_mouse_input(device_id, ElementState::Pressed, MouseButton::Left);
+      wnd.process_mouse_input(device_id, ElementState::Released, MouseButton::Left);
+      wnd.run_frame_tasks();
+    };
+
+    tap(&mut wnd);
+    assert_eq!(*order.read(), ["internal", "normal", "low"]);
+
+    tap(&mut wnd);
+    assert_eq!(*order.read(), ["internal", "normal", "low", "normal", "low"]);
+  }
+
+  #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+  #[test]
+  fn redact_sensitive() {
+    reset_test_env!();
+
+    let w = fn_widget! {
+      @MockBox {
+        size: Size::new(10., 10.),
+        sensitive: true,
+        background: Color::RED,
+      }
+    };
+    let mut wnd = TestWindow::new(w);
+    let colors = |wnd: &mut TestWindow| {
+      wnd.draw_frame();
+      let frame = wnd.take_last_frame().unwrap();
+      frame
+        .commands
+        .into_iter()
+        .filter_map(|cmd| match cmd {
+          PaintCommand::Path(PathCommand {
+            action: PaintPathAction::Paint { brush: CommandBrush::Color(c), .. },
+            ..
+          }) => Some(c),
+          _ => None,
+        })
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(colors(&mut wnd), vec![Color::RED]);
+
+    let flags = wnd.flags() | WindowFlags::REDACT_SENSITIVE;
+    wnd.set_flags(flags);
+    assert_eq!(colors(&mut wnd), vec![Color::BLACK]);
+  }
 }
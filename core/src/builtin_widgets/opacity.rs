@@ -3,6 +3,15 @@ use crate::{prelude::*, wrap_render::*};
 #[derive(Clone)]
 pub struct Opacity {
   pub opacity: f32,
+  /// The widget and its descendants are transparent to the pointer events
+  /// when the `opacity` is below this threshold, like a faded out ghost. The
+  /// default is `0.`, which means the widget can always be hit.
+  pub opacity_hit_threshold: f32,
+  /// Composite the widget and its descendants as a whole with the `opacity`,
+  /// instead of applying it to every primitive. So the overlapping descendants
+  /// don't show through each other. It paints the subtree to an offscreen
+  /// texture and costs more, the default is `false`.
+  pub opacity_group: bool,
 }
 
 impl Declare for Opacity {
@@ -13,7 +22,7 @@ impl Declare for Opacity {
 
 impl Default for Opacity {
   #[inline]
-  fn default() -> Self { Self { opacity: 1.0 } }
+  fn default() -> Self { Self { opacity: 1.0, opacity_hit_threshold: 0., opacity_group: false } }
 }
 
 impl_compose_child_for_wrap_render!(Opacity, DirtyPhase::Paint);
@@ -26,7 +35,68 @@ impl WrapRender for Opacity {
   fn paint(&self, host: &dyn Render, ctx: &mut PaintingCtx) {
     ctx.painter().apply_alpha(self.opacity);
     if self.opacity > 0. {
+      if self.opacity_group && self.opacity < 1. {
+        ctx.start_layer(None);
+      }
       host.paint(ctx)
     }
   }
+
+  fn hit_test(&self, host: &dyn Render, ctx: &mut HitTestCtx, pos: Point) -> HitTest {
+    if self.opacity < self.opacity_hit_threshold {
+      HitTest { hit: false, can_hit_child: false }
+    } else {
+      host.hit_test(ctx, pos)
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  fn opacity_commands(group: bool) -> Vec<PaintCommand> {
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockStack {
+          opacity: 0.5,
+          opacity_group: group,
+          @MockBox { size: Size::new(20., 20.), background: Color::RED }
+          @MockBox { size: Size::new(10., 10.), background: Color::BLUE }
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+    wnd.take_last_frame().unwrap().commands
+  }
+
+  #[test]
+  fn group_opacity() {
+    reset_test_env!();
+
+    let alpha = |cmd: &PaintCommand| match cmd {
+      PaintCommand::Path(PathCommand {
+        action: PaintPathAction::Paint { brush: CommandBrush::Color(c), .. },
+        ..
+      }) => c.alpha,
+      _ => unreachable!(),
+    };
+
+    // Every primitive is painted with the opacity.
+    let commands = opacity_commands(false);
+    assert_eq!(commands.len(), 2);
+    let half = Color::RED.apply_alpha(0.5).alpha;
+    assert!(commands.iter().all(|c| alpha(c) == half));
+
+    // The subtree is painted opaque and composited as a whole.
+    let commands = opacity_commands(true);
+    let [PaintCommand::Bundle { opacity, cmds, .. }] = &commands[..] else {
+      panic!("Expect a bundle, but got {commands:?}");
+    };
+    assert_eq!(*opacity, 0.5);
+    assert_eq!(cmds.len(), 2);
+    assert!(cmds.iter().all(|c| alpha(c) == 255));
+  }
 }
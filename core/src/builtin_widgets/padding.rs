@@ -55,10 +55,26 @@ impl WrapRender for Padding {
 
     let ts = host
       .get_transform()
-      .map_or(padding_matrix, |h| padding_matrix.then(&h));
+      .map_or(padding_matrix, |h| h.then(&padding_matrix));
 
     Some(ts)
   }
+
+  fn baseline_offset(&self, host: &dyn Render, ctx: &mut LayoutCtx) -> Option<f32> {
+    host
+      .baseline_offset(ctx)
+      .map(|b| b + self.padding.top)
+  }
+
+  fn min_intrinsic_width(&self, host: &dyn Render, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    let EdgeInsets { top, right, bottom, left } = self.padding;
+    host.min_intrinsic_width((height - top - bottom).max(0.), ctx) + left + right
+  }
+
+  fn min_intrinsic_height(&self, host: &dyn Render, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    let EdgeInsets { top, right, bottom, left } = self.padding;
+    host.min_intrinsic_height((width - left - right).max(0.), ctx) + top + bottom
+  }
 }
 
 impl Padding {
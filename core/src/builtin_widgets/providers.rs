@@ -698,7 +698,27 @@ impl Render for ProvidersRender {
 
   fn only_sized_by_parent(&self) -> bool { self.render.only_sized_by_parent() }
 
+  fn debug_name(&self) -> &'static str { self.render.debug_name() }
+
   fn get_transform(&self) -> Option<Transform> { self.render.get_transform() }
+
+  fn baseline_offset(&self, ctx: &mut LayoutCtx) -> Option<f32> { self.render.baseline_offset(ctx) }
+
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    let Self { render, providers } = self;
+    providers.setup_providers(ctx.as_mut());
+    let width = render.min_intrinsic_width(height, ctx);
+    providers.restore_providers(ctx.as_mut());
+    width
+  }
+
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    let Self { render, providers } = self;
+    providers.setup_providers(ctx.as_mut());
+    let height = render.min_intrinsic_height(width, ctx);
+    providers.restore_providers(ctx.as_mut());
+    height
+  }
 }
 
 pub(crate) struct Setup {
@@ -54,4 +54,12 @@ impl WrapRender for RadiusWidget {
   }
 }
 
-impl_compose_child_for_wrap_render!(RadiusWidget, DirtyPhase::Paint);
+impl<'c> ComposeChild<'c> for RadiusWidget {
+  type Child = Widget<'c>;
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    // The wrapper is not a node of the tree, attach its state to the host so
+    // the framework can query the shape of the widget, like the focus ring.
+    let child = child.attach_data(Box::new(this.clone_boxed_reader()));
+    WrapRender::combine_child(this, child, DirtyPhase::Paint)
+  }
+}
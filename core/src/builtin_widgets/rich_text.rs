@@ -0,0 +1,474 @@
+use std::{cell::RefCell, ops::Range, rc::Rc};
+
+use font_db::GlyphBaseline;
+use typography::PlaceLineDirection;
+
+use crate::prelude::*;
+
+type SpanTapHandler = Rc<RefCell<dyn FnMut(&mut PointerEvent)>>;
+
+/// The text widget display a paragraph composed of spans, every span has its
+/// own style and tap handler, and a span can be a widget placed inline, such
+/// as an icon or an emoji image.
+///
+/// The style of a span not specified is inherited from the `TextStyle` of the
+/// context.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let dot = || Container { size: Size::new(8., 8.) }.into_widget();
+/// let spans = vec![
+///   TextSpan::text("Read the "),
+///   TextSpan::text("terms")
+///     .with_foreground(Color::BLUE)
+///     .with_decoration(TextDecoration::UNDERLINE)
+///     .on_tap(|_| println!("Show the terms.")),
+///   TextSpan::widget(dot),
+///   TextSpan::text("Agree").with_font_size(20.),
+/// ];
+/// let _w = fn_widget! {
+///   @RichText { spans: spans.clone() }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct RichText {
+  pub spans: Vec<TextSpan>,
+  #[declare(default = TextAlign::Start)]
+  pub text_align: TextAlign,
+}
+
+/// A span of the [`RichText`].
+#[derive(Clone)]
+pub struct TextSpan {
+  content: SpanContent,
+  pub style: SpanStyle,
+  on_tap: Option<SpanTapHandler>,
+}
+
+#[derive(Clone)]
+enum SpanContent {
+  Text(CowArc<str>),
+  Widget(GenWidget),
+}
+
+/// The style of a [`TextSpan`], the fields not specified are inherited from
+/// the `TextStyle` of the context.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SpanStyle {
+  pub font_size: Option<f32>,
+  pub font_face: Option<FontFace>,
+  pub letter_space: Option<f32>,
+  /// The brush to paint the text of the span.
+  pub foreground: Option<Brush>,
+  /// The decorations to paint near the text of the span.
+  pub decoration: TextDecoration,
+  /// The brush to paint the decorations, the foreground of the span is used
+  /// if it's not specified.
+  pub decoration_color: Option<Brush>,
+}
+
+impl TextSpan {
+  /// Create a span of the `text`.
+  pub fn text(text: impl Into<CowArc<str>>) -> Self {
+    Self { content: SpanContent::Text(text.into()), style: <_>::default(), on_tap: None }
+  }
+
+  /// Create a span that places the widget inline, the bottom of the widget is
+  /// aligned with the bottom of the text in the line.
+  pub fn widget(widget: impl Into<GenWidget>) -> Self {
+    Self { content: SpanContent::Widget(widget.into()), style: <_>::default(), on_tap: None }
+  }
+
+  pub fn with_style(mut self, style: SpanStyle) -> Self {
+    self.style = style;
+    self
+  }
+
+  pub fn with_font_size(mut self, font_size: f32) -> Self {
+    self.style.font_size = Some(font_size);
+    self
+  }
+
+  pub fn with_font_face(mut self, font_face: FontFace) -> Self {
+    self.style.font_face = Some(font_face);
+    self
+  }
+
+  pub fn with_letter_space(mut self, letter_space: f32) -> Self {
+    self.style.letter_space = Some(letter_space);
+    self
+  }
+
+  pub fn with_foreground(mut self, brush: impl Into<Brush>) -> Self {
+    self.style.foreground = Some(brush.into());
+    self
+  }
+
+  pub fn with_decoration(mut self, decoration: TextDecoration) -> Self {
+    self.style.decoration = decoration;
+    self
+  }
+
+  pub fn with_decoration_color(mut self, brush: impl Into<Brush>) -> Self {
+    self.style.decoration_color = Some(brush.into());
+    self
+  }
+
+  /// Call the handler when the span is tapped.
+  pub fn on_tap(mut self, f: impl FnMut(&mut PointerEvent) + 'static) -> Self {
+    self.on_tap = Some(Rc::new(RefCell::new(f)));
+    self
+  }
+}
+
+impl SpanStyle {
+  fn text_style(&self, base: &TextStyle) -> TextStyle {
+    TextStyle {
+      font_size: self.font_size.unwrap_or(base.font_size),
+      font_face: self
+        .font_face
+        .clone()
+        .unwrap_or_else(|| base.font_face.clone()),
+      letter_space: self.letter_space.unwrap_or(base.letter_space),
+      ..base.clone()
+    }
+  }
+}
+
+impl Compose for RichText {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    (fn_widget! {
+      pipe!($this;).map(move |_| $this.paragraph())
+    })
+    .into_widget()
+  }
+}
+
+impl RichText {
+  fn paragraph(&self) -> Widget<'static> {
+    let mut text = String::new();
+    let mut spans = Vec::with_capacity(self.spans.len());
+    let mut children = vec![];
+    for span in &self.spans {
+      let start = text.len();
+      let is_widget = match &span.content {
+        SpanContent::Text(t) => {
+          text.push_str(t);
+          false
+        }
+        SpanContent::Widget(w) => {
+          text.push('\u{FFFC}');
+          children.push(w.gen_widget());
+          true
+        }
+      };
+      spans.push(RichSpan {
+        range: start..text.len(),
+        style: span.style.clone(),
+        is_widget,
+        on_tap: span.on_tap.clone(),
+      });
+    }
+
+    let tappable = spans.iter().any(|s| s.on_tap.is_some());
+    let spans: Rc<[RichSpan]> = spans.into();
+    let glyphs = Rc::new(RefCell::new(None));
+    let paragraph = Paragraph {
+      text: text.into(),
+      spans: spans.clone(),
+      text_align: self.text_align,
+      styles: <_>::default(),
+      glyphs: glyphs.clone(),
+    };
+
+    let mut paragraph = FatObj::new(MultiPair::new(paragraph, children));
+    if tappable {
+      paragraph = paragraph.on_tap(move |e| {
+        let Some(glyphs) = &*glyphs.borrow() else { return };
+        let pos = e.position();
+        let (row, col) = glyphs.nearest_glyph(pos.x, pos.y);
+        if !glyphs.glyph_rect(row, col).contains(pos) {
+          return;
+        }
+        let cluster = glyphs.position_to_cluster(row, col);
+        let handler = spans
+          .iter()
+          .find(|s| s.range.contains(&cluster))
+          .and_then(|s| s.on_tap.clone());
+        if let Some(handler) = handler {
+          (handler.borrow_mut())(e);
+        }
+      });
+    }
+    paragraph.into_widget()
+  }
+}
+
+struct RichSpan {
+  range: Range<usize>,
+  style: SpanStyle,
+  is_widget: bool,
+  on_tap: Option<SpanTapHandler>,
+}
+
+/// The render of the `RichText`, its children are the widgets of the spans.
+#[derive(MultiChild)]
+struct Paragraph {
+  text: Substr,
+  spans: Rc<[RichSpan]>,
+  text_align: TextAlign,
+  /// The text styles of the spans resolved in the last layout.
+  styles: RefCell<Vec<TextStyle>>,
+  glyphs: Rc<RefCell<Option<VisualGlyphs>>>,
+}
+
+impl Paragraph {
+  fn span_index(&self, cluster: usize) -> Option<usize> {
+    self
+      .spans
+      .iter()
+      .position(|s| s.range.contains(&cluster))
+  }
+}
+
+impl Render for Paragraph {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let style = Provider::of::<TextStyle>(ctx).unwrap().clone();
+    let styles: Vec<_> = self
+      .spans
+      .iter()
+      .map(|s| s.style.text_style(&style))
+      .collect();
+
+    let (ctx, children) = ctx.split_children();
+    let child_clamp = BoxClamp { min: ZERO_SIZE, max: clamp.max };
+    let sizes: Vec<_> = children
+      .map(|c| ctx.perform_child_layout(c, child_clamp))
+      .collect();
+
+    let mut sizes_iter = sizes.iter();
+    let runs: Vec<_> = self
+      .spans
+      .iter()
+      .zip(styles.iter())
+      .map(|(span, style)| {
+        if span.is_widget {
+          let size = sizes_iter.next().copied().unwrap_or_default();
+          RichRun::Placeholder(&span.range, size)
+        } else {
+          RichRun::Text(&span.range, style)
+        }
+      })
+      .collect();
+    let glyphs = AppCtx::typography_store()
+      .borrow_mut()
+      .typography_rich(
+        self.text.clone(),
+        &runs,
+        &style,
+        clamp.max,
+        self.text_align,
+        GlyphBaseline::Middle,
+        PlaceLineDirection::TopToBottom,
+      );
+
+    let (ctx, children) = ctx.split_children();
+    let placeholders = self.spans.iter().filter(|s| s.is_widget);
+    for (child, span) in children.zip(placeholders) {
+      let (row, col) = glyphs.position_by_cluster(span.range.start);
+      ctx.update_position(child, glyphs.glyph_rect(row, col).origin);
+    }
+
+    let size = glyphs.visual_rect().size;
+    *self.styles.borrow_mut() = styles;
+    *self.glyphs.borrow_mut() = Some(glyphs);
+
+    clamp.clamp(size)
+  }
+
+  #[inline]
+  fn only_sized_by_parent(&self) -> bool { false }
+
+  fn baseline_offset(&self, _: &mut LayoutCtx) -> Option<f32> {
+    self
+      .glyphs
+      .borrow()
+      .as_ref()
+      .and_then(|g| g.first_baseline())
+  }
+
+  fn paint(&self, ctx: &mut PaintingCtx) {
+    let box_rect = Rect::from_size(ctx.box_size().unwrap());
+    let style = Provider::of::<PaintingStyle>(ctx).map(|p| p.clone());
+    let glyphs = self.glyphs.borrow();
+    let Some(glyphs) = glyphs.as_ref() else { return };
+    let styles = self.styles.borrow();
+    let painter = ctx.painter();
+    let Some(paint_rect) = painter.intersection_paint_bounds(&box_rect) else { return };
+    let Some(iter) = glyphs.glyphs_in_bounds(&paint_rect) else { return };
+
+    if let Some(PaintingStyle::Stroke(options)) = style {
+      painter
+        .set_style(PathStyle::Stroke)
+        .set_strokes(options);
+    } else {
+      painter.set_style(PathStyle::Fill);
+    }
+
+    let font_db = AppCtx::font_db().clone();
+    let font_db = font_db.borrow();
+    let origin = glyphs.visual_rect().origin;
+    let mut painter = painter.save_guard();
+    painter.translate(origin.x, origin.y);
+    for g in iter {
+      let Some(idx) = self.span_index(g.cluster as usize) else { continue };
+      let span = &self.spans[idx];
+      if span.is_widget {
+        continue;
+      }
+      let font_size = styles[idx].font_size;
+      let mut painter = painter.save_guard();
+      if let Some(brush) = span.style.foreground.clone() {
+        painter
+          .set_fill_brush(brush.clone())
+          .set_stroke_brush(brush);
+      }
+      painter.draw_glyph(&g, font_size, &font_db);
+
+      let decoration = span.style.decoration;
+      if decoration.is_empty() || decoration == TextDecoration::NONE {
+        continue;
+      }
+      if let Some(brush) = span.style.decoration_color.clone() {
+        painter.set_fill_brush(brush);
+      }
+      let bounds = g.bounds();
+      let thickness = (font_size / 14.).max(1.);
+      let mut line = |y: f32| {
+        let rect = Rect::new(Point::new(bounds.min_x(), y), Size::new(bounds.width(), thickness));
+        painter
+          .set_style(PathStyle::Fill)
+          .rect(&rect)
+          .fill();
+      };
+      // The alphabetic baseline of a glyph is one em below its top.
+      let top = bounds.min_y();
+      if decoration.contains(TextDecoration::UNDERLINE) {
+        line(top + font_size + thickness);
+      }
+      if decoration.contains(TextDecoration::OVERLINE) {
+        line(top);
+      }
+      if decoration.contains(TextDecoration::THROUGHLINE) {
+        line(top + font_size * 0.65);
+      }
+    }
+  }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+  use std::{cell::Cell, rc::Rc};
+
+  use ribir::{core::test_helper::*, material as ribir_material, prelude::*};
+  use ribir_dev_helper::*;
+  use winit::event::{DeviceId, ElementState, MouseButton, WindowEvent};
+
+  fn text_style() -> TextStyle { TextStyle { font_size: 16., line_height: 20., ..<_>::default() } }
+
+  #[test]
+  fn inline_widget() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! {
+      let mock_box = || MockBox { size: Size::new(10., 30.) }.into_widget();
+      @RichText {
+        text_style: text_style(),
+        spans: vec![
+          TextSpan::text("ab"),
+          TextSpan::widget(mock_box),
+          TextSpan::text("cd").with_font_size(32.),
+        ],
+      }
+    });
+    wnd.draw_frame();
+
+    // The line grows to hold the large span, and keeps the leading.
+    let paragraph = wnd.layout_info_by_path(&[0]).unwrap();
+    assert_eq!(paragraph.size.unwrap().height, 36.);
+    // The widget is placed after the text, and its bottom is aligned with the
+    // bottom of the large text.
+    let widget = wnd.layout_info_by_path(&[0, 0]).unwrap();
+    assert_eq!(widget.size, Some(Size::new(10., 30.)));
+    assert!(widget.pos.x > 0.);
+    assert_eq!(widget.pos.y, 4.);
+  }
+
+  #[test]
+  fn tap_span() {
+    reset_test_env!();
+
+    let taps = Rc::new(Cell::new(0));
+    let c_taps = taps.clone();
+    let mut wnd = TestWindow::new(fn_widget! {
+      let c_taps = c_taps.clone();
+      @RichText {
+        text_style: text_style(),
+        spans: vec![
+          TextSpan::text("aaaa"),
+          TextSpan::text("bbbb").on_tap(move |_| c_taps.set(c_taps.get() + 1)),
+        ],
+      }
+    });
+    wnd.draw_frame();
+
+    let width = wnd
+      .layout_info_by_path(&[0])
+      .unwrap()
+      .size
+      .unwrap()
+      .width as f64;
+    let tap = |wnd: &mut TestWindow, x: f64| {
+      let device_id = unsafe { DeviceId::dummy() };
+      #[allow(deprecated)]
+      wnd.processes_native_event(WindowEvent::CursorMoved { device_id, position: (x, 5.).into() });
+      wnd.process_mouse_input(device_id, ElementState::Pressed, MouseButton::Left);
+      wnd.process_mouse_input(device_id, ElementState::Released, MouseButton::Left);
+      wnd.draw_frame();
+    };
+
+    tap(&mut wnd, width * 0.75);
+    assert_eq!(taps.get(), 1);
+    tap(&mut wnd, width * 0.25);
+    assert_eq!(taps.get(), 1);
+  }
+
+  widget_image_tests!(
+    styled_spans,
+    WidgetTester::new(fn_widget! {
+      let dot = || {
+        @Container { size: Size::new(12., 12.), background: Color::YELLOW }.into_widget()
+      };
+      @RichText {
+        text_style: text_style(),
+        spans: vec![
+          TextSpan::text("Hello "),
+          TextSpan::text("Ribir")
+            .with_font_size(28.)
+            .with_foreground(Color::RED)
+            .with_decoration(TextDecoration::UNDERLINE),
+          TextSpan::widget(dot),
+          TextSpan::text(" rich ").with_decoration(TextDecoration::THROUGHLINE),
+          TextSpan::text("text!")
+            .with_foreground(Color::BLUE)
+            .with_decoration(TextDecoration::OVERLINE)
+            .with_decoration_color(Color::GREEN),
+        ],
+      }
+    })
+    .with_wnd_size(Size::new(240., 48.))
+  );
+}
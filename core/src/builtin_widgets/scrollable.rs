@@ -54,6 +54,19 @@ impl<'c> ComposeChild<'c> for ScrollableWidget {
           this.scrollable
         },
         on_wheel: move |e| $this.write().scroll(-e.delta_x, -e.delta_y),
+        on_accessibility_action: move |e| {
+          let forward = match e.action {
+            AccessibilityAction::ScrollForward => true,
+            AccessibilityAction::ScrollBackward => false,
+            _ => return,
+          };
+          let mut this = $this.write();
+          if this.scroll_page(forward) {
+            e.stop_propagation();
+          } else {
+            this.forget_modifies();
+          }
+        },
       };
 
       let child = FatObj::new(child);
@@ -74,10 +87,24 @@ impl<'c> ComposeChild<'c> for ScrollableWidget {
       $this.write().view_id = Some($view.track_id());
 
       @ $view {
-        on_performed_layout: move |_| {
+        on_performed_layout: move |e| {
           let view_size = $view.size.get();
-          if $this.page != view_size {
-            $this.write().set_page(view_size);
+          let page = $this.page;
+          if page != view_size {
+            let mut this = $this.write();
+            this.set_page(view_size);
+            // Keep the focused widget visible when the view shrinks, for
+            // example, the on-screen keyboard covers a part of the view.
+            if view_size.width < page.width || view_size.height < page.height {
+              let wnd = e.window();
+              let view_id = e.current_target();
+              if let Some(focus) = wnd
+                .focusing()
+                .filter(|f| *f != view_id && view_id.ancestor_of(*f, wnd.tree()))
+              {
+                this.visible_widget(focus, Anchor::default(), &wnd);
+              }
+            }
           }
         },
         providers: [Provider::value_of_writer(this.clone_boxed_writer(), None)],
@@ -170,6 +197,20 @@ impl ScrollableWidget {
     self.jump_to(new);
   }
 
+  /// Scroll the content forward or backward by a page, along the vertical
+  /// direction if it's scrollable, otherwise along the horizontal direction.
+  /// Return if the scroll position changed.
+  pub fn scroll_page(&mut self, forward: bool) -> bool {
+    let sign = if forward { 1. } else { -1. };
+    let old = self.scroll_pos;
+    if self.is_y_scrollable() {
+      self.scroll(0., sign * self.page.height);
+    } else if self.is_x_scrollable() {
+      self.scroll(sign * self.page.width, 0.);
+    }
+    old != self.scroll_pos
+  }
+
   pub fn jump_to(&mut self, top_left: Point) {
     let max = self.max_scrollable();
     self.scroll_pos = top_left.clamp(Point::zero(), max.to_vector().to_point());
@@ -318,6 +359,69 @@ mod tests {
     test_assert(Scrollable::Both, 100., 100., 0., 0.);
   }
 
+  #[test]
+  fn accessibility_page_scroll() {
+    reset_test_env!();
+
+    let (content, w_content) = split_value(None);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(1000., 1000.),
+          scrollable: Scrollable::Y,
+          on_mounted: move |e| *$w_content.write() = Some(e.current_target()),
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+
+    let id = content.read().unwrap();
+    wnd.dispatch_accessibility_action(id, AccessibilityAction::ScrollForward);
+    wnd.draw_frame();
+    assert_eq!(wnd.layout_info_by_path(&[0, 0]).unwrap().pos, Point::new(0., -100.));
+
+    wnd.dispatch_accessibility_action(id, AccessibilityAction::ScrollBackward);
+    wnd.draw_frame();
+    assert_eq!(wnd.layout_info_by_path(&[0, 0]).unwrap().pos, Point::zero());
+  }
+
+  #[test]
+  fn keep_focus_visible_when_shrink() {
+    reset_test_env!();
+
+    let (focus, w_focus) = split_value(None);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(100., 400.),
+          scrollable: Scrollable::Y,
+          @MockBox {
+            size: Size::new(100., 20.),
+            anchor: Anchor::top(80.),
+            tab_index: 0i16,
+            on_mounted: move |e| *$w_focus.write() = Some(e.current_target()),
+          }
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+    let focus = focus.read().unwrap();
+    wnd
+      .focus_mgr
+      .borrow_mut()
+      .focus(focus, wnd.tree());
+    assert_eq!(wnd.layout_info_by_path(&[0, 0]).unwrap().pos, Point::zero());
+
+    wnd
+      .shell_wnd()
+      .borrow_mut()
+      .request_resize(Size::new(100., 50.));
+    wnd.draw_frame();
+    assert_eq!(wnd.layout_info_by_path(&[0, 0]).unwrap().pos, Point::new(0., -50.));
+  }
+
   #[derive(SingleChild, Declare, Clone)]
   pub struct FixedBox {
     pub size: Size,
@@ -0,0 +1,285 @@
+use crate::prelude::*;
+
+/// The role of a widget in the accessibility tree, it tells the assistive
+/// technologies what the widget is and how to interact with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SemanticsRole {
+  /// A widget without a specific role, it only groups its descendants.
+  #[default]
+  Generic,
+  Button,
+  CheckBox,
+  Radio,
+  Switch,
+  Slider,
+  ProgressIndicator,
+  TextInput,
+  Text,
+  Heading,
+  Image,
+  Link,
+  List,
+  ListItem,
+  Tab,
+  TabList,
+  Menu,
+  MenuItem,
+  Dialog,
+  ScrollView,
+}
+
+/// A builtin widget describes the semantics of its child for the
+/// accessibility tree.
+///
+/// A widget declares any of the `semantics_label`, `semantics_role` or
+/// `merge_semantics` fields becomes a [`SemanticsNode`] in the tree built by
+/// [`Window::semantics_tree`].
+///
+/// The content of the `sensitive` widgets is excluded from the tree: their
+/// nodes keep the role and the bounds, but have no label and no children, and
+/// their labels are not merged into their ancestors.
+///
+/// # Example
+///
+/// The icon and the text of the button are merged into a single node, so the
+/// screen reader reads it as one button.
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @Row {
+///     semantics_role: SemanticsRole::Button,
+///     merge_semantics: true,
+///     on_tap: move |_| println!("Saved!"),
+///     @Icon { semantics_label: "Save", @ { svgs::ADD } }
+///     @Text { text: "Save the document" }
+///   }
+/// };
+/// ```
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Semantics {
+  /// The text that describes the widget to the user.
+  pub semantics_label: Option<CowArc<str>>,
+  /// The role of the widget, the node is a [`SemanticsRole::Generic`] if it's
+  /// `None`.
+  pub semantics_role: Option<SemanticsRole>,
+  /// If `true`, the semantics of the descendants are merged into this widget,
+  /// their labels are appended to the label of this widget, and they don't
+  /// create their own nodes.
+  pub merge_semantics: bool,
+}
+
+/// A node of the accessibility tree, built from the [`Semantics`] of the
+/// widgets.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SemanticsNode {
+  /// The widget that the node is built from.
+  pub id: WidgetId,
+  pub role: SemanticsRole,
+  pub label: Option<CowArc<str>>,
+  /// The bounds of the widget in the window coordinate.
+  pub rect: Rect,
+  pub children: Vec<SemanticsNode>,
+}
+
+impl Declare for Semantics {
+  type Builder = FatObj<()>;
+  #[inline]
+  fn declarer() -> Self::Builder { FatObj::new(()) }
+}
+
+impl<'c> ComposeChild<'c> for Semantics {
+  type Child = Widget<'c>;
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    child.try_unwrap_state_and_attach(this)
+  }
+}
+
+impl Semantics {
+  /// Returns the semantics declared on the widget `id`, combined from all the
+  /// `Semantics` attached to it, the outermost one takes precedence.
+  fn of(id: WidgetId, tree: &WidgetTree) -> Option<Semantics> {
+    id.query_all_iter::<Semantics>(tree)
+      .map(|s| s.clone())
+      .reduce(|outer, inner| Semantics {
+        semantics_label: outer.semantics_label.or(inner.semantics_label),
+        semantics_role: outer.semantics_role.or(inner.semantics_role),
+        merge_semantics: outer.merge_semantics || inner.merge_semantics,
+      })
+  }
+}
+
+impl SemanticsNode {
+  /// Build the accessibility tree of the subtree of `root`, the root always
+  /// creates a node.
+  pub(crate) fn build(root: WidgetId, tree: &WidgetTree) -> SemanticsNode {
+    let semantics = Semantics::of(root, tree).unwrap_or_default();
+    let mut node = SemanticsNode::new(root, &semantics, tree);
+    if root.is_sensitive(tree) {
+      // Nothing of the sensitive content is exposed.
+      node.label = None;
+    } else if semantics.merge_semantics {
+      merge_labels(root, tree, &mut node.label);
+    } else {
+      collect_children(root, tree, &mut node.children);
+    }
+    node
+  }
+
+  fn new(id: WidgetId, semantics: &Semantics, tree: &WidgetTree) -> Self {
+    let size = tree.store.layout_box_size(id).unwrap_or_default();
+    let rect = Rect::new(tree.map_to_global(Point::zero(), id), size);
+    SemanticsNode {
+      id,
+      role: semantics.semantics_role.unwrap_or_default(),
+      label: semantics.semantics_label.clone(),
+      rect,
+      children: vec![],
+    }
+  }
+}
+
+/// Collect the nodes of the descendants of `id` to `nodes`, the widgets without
+/// semantics are skipped and their descendants are collected to their closest
+/// ancestor node.
+fn collect_children(id: WidgetId, tree: &WidgetTree, nodes: &mut Vec<SemanticsNode>) {
+  for c in id.children(tree) {
+    if Semantics::of(c, tree).is_some() {
+      nodes.push(SemanticsNode::build(c, tree));
+    } else {
+      collect_children(c, tree, nodes);
+    }
+  }
+}
+
+/// Append the labels of the descendants of `id` to `label` in the tree order.
+fn merge_labels(id: WidgetId, tree: &WidgetTree, label: &mut Option<CowArc<str>>) {
+  for c in id.descendants(tree).skip(1) {
+    if c.is_sensitive(tree) {
+      continue;
+    }
+    let Some(child) = Semantics::of(c, tree).and_then(|s| s.semantics_label) else { continue };
+    *label = Some(match label.take() {
+      Some(label) => format!("{} {}", &*label, &*child).into(),
+      None => child,
+    });
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[test]
+  fn semantics_tree() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockMulti {
+        @MockBox {
+          size: Size::new(10., 10.),
+          semantics_role: SemanticsRole::Button,
+          semantics_label: "OK",
+        }
+        @MockMulti {
+          @MockBox {
+            size: Size::new(20., 10.),
+            semantics_label: "Cancel",
+          }
+        }
+      }
+    });
+    wnd.draw_frame();
+
+    let root = wnd.semantics_tree();
+    let nodes: Vec<_> = root
+      .children
+      .iter()
+      .map(|n| (n.role, n.label.as_deref(), n.rect))
+      .collect();
+    assert_eq!(
+      nodes,
+      vec![
+        (SemanticsRole::Button, Some("OK"), Rect::new(Point::zero(), Size::new(10., 10.))),
+        (
+          SemanticsRole::Generic,
+          Some("Cancel"),
+          Rect::new(Point::new(10., 0.), Size::new(20., 10.))
+        ),
+      ]
+    );
+  }
+
+  #[test]
+  fn merge_descendants() {
+    reset_test_env!();
+
+    let (label, w_label) = split_value(CowArc::<str>::from("Save"));
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockMulti {
+        semantics_role: SemanticsRole::Button,
+        merge_semantics: true,
+        @MockBox { size: Size::new(10., 10.), semantics_label: pipe!($label.clone()) }
+        @MockBox {
+          size: Size::new(10., 10.),
+          semantics_role: SemanticsRole::Text,
+          semantics_label: "the document",
+        }
+      }
+    });
+    wnd.draw_frame();
+
+    let button = |wnd: &TestWindow| {
+      let root = wnd.semantics_tree();
+      assert_eq!(root.children.len(), 1);
+      let button = root.children[0].clone();
+      assert!(button.children.is_empty());
+      (button.role, button.label)
+    };
+    assert_eq!(button(&wnd), (SemanticsRole::Button, Some("Save the document".into())));
+
+    *w_label.write() = "Open".into();
+    wnd.draw_frame();
+    assert_eq!(button(&wnd), (SemanticsRole::Button, Some("Open the document".into())));
+  }
+
+  #[test]
+  fn exclude_sensitive() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockMulti {
+        @MockMulti {
+          semantics_role: SemanticsRole::TextInput,
+          semantics_label: "Password",
+          sensitive: true,
+          @MockBox { size: Size::new(10., 10.), semantics_label: "secret" }
+        }
+        @MockMulti {
+          semantics_role: SemanticsRole::Button,
+          merge_semantics: true,
+          @MockBox { size: Size::new(10., 10.), semantics_label: "Show" }
+          @MockBox {
+            size: Size::new(10., 10.),
+            sensitive: true,
+            semantics_label: "secret",
+          }
+        }
+      }
+    });
+    wnd.draw_frame();
+
+    let root = wnd.semantics_tree();
+    let nodes: Vec<_> = root
+      .children
+      .iter()
+      .map(|n| (n.role, n.label.as_deref(), n.children.len()))
+      .collect();
+    assert_eq!(
+      nodes,
+      vec![(SemanticsRole::TextInput, None, 0), (SemanticsRole::Button, Some("Show"), 0)]
+    );
+  }
+}
@@ -0,0 +1,86 @@
+use crate::prelude::*;
+
+/// A widget with a fixed size that is painted by a custom WGSL fragment
+/// shader.
+///
+/// The `shader` must define the fragment entry `fs_main`, it takes the
+/// `VertexOutput` provided by the backend, see [`Painter::draw_shader`]. The
+/// `uniforms` are the bytes of the uniform it declares at
+/// `@group(0) @binding(0)`, so the shader can be animated by changing them.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// const GRADIENT: &str = r#"
+/// struct Params { color: vec4<f32> }
+/// @group(0) @binding(0) var<uniform> params: Params;
+///
+/// @fragment
+/// fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+///   return vec4(params.color.rgb * input.uv.x, params.color.a);
+/// }
+/// "#;
+///
+/// let _gradient = fn_widget! {
+///   @ShaderBox {
+///     size: Size::new(200., 100.),
+///     shader: GRADIENT.to_string(),
+///     uniforms: [1f32, 0., 0., 1.].iter().flat_map(|v| v.to_ne_bytes()).collect::<Vec<_>>(),
+///   }
+/// };
+/// ```
+#[derive(Declare, Clone)]
+pub struct ShaderBox {
+  pub size: Size,
+  /// The WGSL source of the fragment shader.
+  pub shader: Resource<String>,
+  /// The bytes of the uniform of the shader.
+  #[declare(default)]
+  pub uniforms: Vec<u8>,
+}
+
+impl Render for ShaderBox {
+  #[inline]
+  fn perform_layout(&self, clamp: BoxClamp, _: &mut LayoutCtx) -> Size { clamp.clamp(self.size) }
+
+  fn paint(&self, ctx: &mut PaintingCtx) {
+    let size = ctx.box_size().unwrap();
+    let uniforms = self.uniforms.clone().into_boxed_slice();
+    ctx
+      .painter()
+      .draw_shader(&Rect::from_size(size), self.shader.clone(), uniforms);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[test]
+  fn paint_shader() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @ShaderBox {
+          size: Size::new(20., 10.),
+          shader: "@fragment fn fs_main() {}".to_string(),
+          uniforms: vec![0; 8],
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+
+    let commands = wnd.take_last_frame().unwrap().commands;
+    let [PaintCommand::Shader { bounds, source, uniforms, .. }] = &commands[..] else {
+      panic!("expect a shader command, but got {commands:?}");
+    };
+    assert_eq!(*bounds, Rect::from_size(Size::new(20., 10.)));
+    assert_eq!(source.as_str(), "@fragment fn fs_main() {}");
+    assert_eq!(uniforms.len(), 8);
+  }
+}
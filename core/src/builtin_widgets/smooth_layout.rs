@@ -41,6 +41,10 @@
 //! move in from the right, positioned 10 percent from the right edge of the
 //! window. If no `init_value` is provided, the first layout will not be
 //! animated.
+//!
+//! Unlike the widgets above, [`AnimatedLayout`] doesn't relayout its child
+//! during the transition, it plays a FLIP animation that only transforms the
+//! child from its previous rect to the new one.
 use crate::{prelude::*, window::WindowFlags, wrap_render::*};
 
 /// This widget enables smooth position transitions for its declare child
@@ -67,6 +71,14 @@ pub struct SmoothWidth(Stateful<SmoothImpl<Measure, f32>>);
 /// between layout. See the [module-level documentation](self) for more.
 pub struct SmoothHeight(Stateful<SmoothImpl<Measure, f32>>);
 
+/// This widget plays a FLIP-style animation for its declare child when the
+/// layout changed. It records the global rect of the child, and paints the
+/// child transformed from the previous rect to the new one, without relayout.
+/// So the list reorders and the flex reflows animate smoothly.
+///
+/// Its `init_value` is the global rect that the first layout transitions from.
+pub struct AnimatedLayout(Stateful<SmoothImpl<Rect, Rect>>);
+
 /// Creates a function widget that utilizes `SmoothPos` as its root widget.
 #[macro_export]
 macro_rules! smooth_pos {
@@ -103,6 +115,13 @@ macro_rules! smooth_height {
   ($($t: tt)*) => { fn_widget! { @SmoothHeight { $($t)* } } };
 }
 
+/// Creates a function widget that utilizes `AnimatedLayout` as its root
+/// widget.
+#[macro_export]
+macro_rules! animated_layout {
+  ($($t: tt)*) => { fn_widget! { @AnimatedLayout { $($t)* } } };
+}
+
 smooth_pos_widget_impl!(SmoothPos);
 smooth_pos_widget_impl!(SmoothY);
 smooth_pos_widget_impl!(SmoothX);
@@ -116,6 +135,7 @@ impl_smooth_layout_declare!(SmoothX, HAnchor);
 impl_smooth_layout_declare!(SmoothSize, Size<Measure>);
 impl_smooth_layout_declare!(SmoothWidth, Measure);
 impl_smooth_layout_declare!(SmoothHeight, Measure);
+impl_smooth_layout_declare!(AnimatedLayout, Rect);
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum SmoothValue<I, T> {
@@ -268,6 +288,47 @@ macro_rules! smooth_pos_widget_impl {
   };
 }
 
+impl WrapRender for AnimatedLayout {
+  // The rect is detected in the painting phase, because the widget may be moved
+  // by its parent without performing its own layout.
+  fn paint(&self, host: &dyn Render, ctx: &mut PaintingCtx) {
+    if !ctx
+      .window()
+      .flags()
+      .contains(WindowFlags::ANIMATIONS)
+    {
+      return host.paint(ctx);
+    }
+
+    let pos = ctx.map_to_global(Point::zero());
+    let size = ctx.box_size().unwrap_or_default();
+    let rect = Rect::new(pos, size);
+    let value = self.0.read().value;
+    let prev = match value {
+      SmoothValue::Value(v) => v,
+      SmoothValue::Init(v) => v.unwrap_or(rect),
+    };
+    if prev != rect {
+      // Paint the child at the previous rect, it transitions to the new one.
+      let offset = prev.origin - pos;
+      let painter = ctx.painter();
+      painter.translate(offset.x, offset.y);
+      if !size.is_empty() {
+        painter.scale(prev.width() / size.width, prev.height() / size.height);
+      }
+    }
+    if prev != rect || matches!(value, SmoothValue::Init(_)) {
+      // The value is the target of the running animation out of the frame, so
+      // retarget it in the next frame if the rect changed.
+      let smooth = AnimatedLayout(self.0.clone_writer());
+      AppCtx::once_next_frame(move |_| smooth.set_rect(rect));
+    }
+    host.paint(ctx);
+  }
+}
+
+impl_compose_child!(AnimatedLayout, DirtyPhase::Paint);
+
 macro_rules! impl_compose_child {
   ($name:ty, $dirty:expr) => {
     impl<'c> ComposeChild<'c> for $name {
@@ -545,6 +606,19 @@ impl SmoothHeight {
   }
 }
 
+impl AnimatedLayout {
+  fn set_rect(&self, rect: Rect) {
+    let value = self.0.read().value;
+    match value {
+      SmoothValue::Value(v) if v == rect => {}
+      // The initial value doesn't transition, so switch to it first and
+      // transition to the `rect` in the next frame.
+      SmoothValue::Init(Some(v)) => self.0.write().value = SmoothValue::Value(v),
+      _ => self.0.write().value = SmoothValue::Value(rect),
+    }
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use ribir::{
@@ -772,4 +846,50 @@ mod tests {
       "smooth_height"
     );
   }
+
+  #[test]
+  fn animated_layout_flip() {
+    reset_test_env!();
+
+    let (width, w_width) = split_value(10.);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockMulti {
+          @MockBox { size: pipe!(Size::new(*$width, 10.)) }
+          @AnimatedLayout {
+            transition: TEST_TRANS,
+            @MockBox { size: Size::new(10., 10.), background: Color::RED }
+          }
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.use_manual_clock();
+    let red_x = |wnd: &mut TestWindow| {
+      let frame = wnd.take_last_frame().unwrap();
+      frame
+        .commands
+        .iter()
+        .find_map(|c| match c {
+          PaintCommand::Path(PathCommand { paint_bounds, .. }) => Some(paint_bounds.min_x()),
+          _ => None,
+        })
+        .unwrap()
+    };
+    wnd.draw_frame();
+    assert_eq!(red_x(&mut wnd), 10.);
+
+    *w_width.write() = 30.;
+    wnd.draw_frame();
+    // Still painted at the previous position, the animation begins next frame.
+    assert_eq!(red_x(&mut wnd), 10.);
+    wnd.draw_frame();
+    assert_eq!(red_x(&mut wnd), 10.);
+
+    wnd.tick(Duration::from_millis(100));
+    assert_eq!(red_x(&mut wnd), 20.);
+
+    wnd.tick(Duration::from_millis(100));
+    assert_eq!(red_x(&mut wnd), 30.);
+  }
 }
@@ -4,6 +4,12 @@ impl Render for Svg {
   #[inline]
   fn perform_layout(&self, clamp: BoxClamp, _: &mut LayoutCtx) -> Size { clamp.clamp(self.size()) }
 
+  #[inline]
+  fn min_intrinsic_width(&self, _: f32, _: &mut LayoutCtx) -> f32 { self.size().width }
+
+  #[inline]
+  fn min_intrinsic_height(&self, _: f32, _: &mut LayoutCtx) -> f32 { self.size().height }
+
   fn paint(&self, ctx: &mut PaintingCtx) {
     let size = ctx.box_size().unwrap();
     let painter = ctx.painter();
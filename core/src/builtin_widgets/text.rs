@@ -71,6 +71,37 @@ impl Render for Text {
   #[inline]
   fn only_sized_by_parent(&self) -> bool { false }
 
+  fn baseline_offset(&self, _: &mut LayoutCtx) -> Option<f32> {
+    self.glyphs().and_then(|g| g.first_baseline())
+  }
+
+  /// The width of the widest word if the text wraps, otherwise the width it's
+  /// laid out in no space.
+  fn min_intrinsic_width(&self, _: f32, ctx: &mut LayoutCtx) -> f32 {
+    let style = Provider::of::<TextStyle>(ctx).unwrap();
+    let width = |text: Substr, bounds: Size| {
+      text_glyph(text, &style, self.text_align, bounds)
+        .visual_rect()
+        .width()
+    };
+    if style.overflow != TextOverflow::AutoWrap && style.max_lines.is_none() {
+      return width(self.text.substr(..), Size::new(0., f32::INFINITY));
+    }
+    self
+      .text
+      .split_whitespace()
+      .map(|word| width(word.to_owned().into(), INFINITY_SIZE))
+      .fold(0., f32::max)
+  }
+
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    let style = Provider::of::<TextStyle>(ctx).unwrap();
+    let bounds = Size::new(width, f32::INFINITY);
+    text_glyph(self.text.substr(..), &style, self.text_align, bounds)
+      .visual_rect()
+      .height()
+  }
+
   fn paint(&self, ctx: &mut PaintingCtx) {
     let box_rect = Rect::from_size(ctx.box_size().unwrap());
     if ctx
@@ -95,6 +126,10 @@ impl Text {
   pub fn glyphs(&self) -> Option<Ref<VisualGlyphs>> {
     Ref::filter_map(self.glyphs.borrow(), |v| v.as_ref()).ok()
   }
+
+  /// Return if the text is truncated by the bounds or the max lines in the
+  /// last layout.
+  pub fn is_truncated(&self) -> bool { self.glyphs().is_some_and(|g| g.is_truncated()) }
 }
 
 macro_rules! define_text_with_theme_style {
@@ -147,6 +182,59 @@ mod tests {
     LayoutCase::default().with_size(Size::new(50., 45.))
   );
 
+  #[test]
+  fn ellipsis_truncated() {
+    reset_test_env!();
+
+    let (truncated, w_truncated) = split_value(false);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let text = @Text {
+          text: "Hello ribir!",
+          text_overflow: TextOverflow::Ellipsis,
+        };
+        @MockBox {
+          size: Size::new(40., 20.),
+          @ $text {
+            on_performed_layout: move |_| *$w_truncated.write() = $text.is_truncated(),
+          }
+        }
+      },
+      WND_SIZE,
+    );
+    wnd.draw_frame();
+    assert!(*truncated.read());
+  }
+
+  #[test]
+  fn max_lines() {
+    reset_test_env!();
+
+    let (rows, w_rows) = split_value(0);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let text = @Text {
+          text: "Hello ribir! Hello ribir! Hello ribir!",
+          text_max_lines: Some(2),
+          text_overflow: TextOverflow::Ellipsis,
+        };
+        @MockBox {
+          size: Size::new(60., 200.),
+          @ $text {
+            on_performed_layout: move |_| {
+              let truncated = $text.is_truncated();
+              assert!(truncated);
+              *$w_rows.write() = $text.glyphs().unwrap().glyph_row_count();
+            },
+          }
+        }
+      },
+      WND_SIZE,
+    );
+    wnd.draw_frame();
+    assert_eq!(*rows.read(), 2);
+  }
+
   widget_image_tests!(
     default_text,
     WidgetTester::new(fn_widget! {
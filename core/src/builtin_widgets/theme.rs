@@ -20,9 +20,18 @@ mod compose_decorators;
 pub use compose_decorators::*;
 mod custom_styles;
 pub use custom_styles::*;
+#[cfg(feature = "theme-tokens")]
+mod theme_tokens;
 pub use ribir_painter::*;
+#[cfg(feature = "theme-tokens")]
+pub use theme_tokens::*;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "theme-tokens",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(rename_all = "lowercase")
+)]
 pub enum Brightness {
   Dark,
   Light,
@@ -237,7 +246,14 @@ fn typography_theme() -> TypographyTheme {
     };
     let overflow = TextOverflow::Overflow;
     TextTheme {
-      text: TextStyle { line_height, font_size, letter_space, font_face, overflow },
+      text: TextStyle {
+        line_height,
+        font_size,
+        letter_space,
+        font_face,
+        overflow,
+        max_lines: None,
+      },
       decoration: TextDecorationStyle {
         decoration: TextDecoration::NONE,
         decoration_color: Color::BLACK.with_alpha(0.87).into(),
@@ -306,4 +322,30 @@ mod tests {
 
     assert_eq!(*watcher.read(), [Brightness::Light, Brightness::Dark, Brightness::Light]);
   }
+
+  #[test]
+  fn switch_theme_at_runtime() {
+    reset_test_env!();
+
+    let (watcher, writer) = split_value(vec![]);
+    let mut wnd = TestWindow::new(fn_widget! {
+      $writer.write().push(Palette::of(BuildCtx::get()).brightness);
+      @Void {}
+    });
+    wnd.draw_frame();
+
+    let mut theme = Theme::default();
+    theme.palette.brightness = Brightness::Dark;
+    let surface = theme.palette.surface();
+    AppCtx::set_app_theme(theme);
+    wnd.draw_frame();
+
+    assert_eq!(*watcher.read(), [Brightness::Light, Brightness::Dark]);
+    let shell = wnd.shell_wnd().borrow();
+    let shell = shell
+      .as_any()
+      .downcast_ref::<TestShellWindow>()
+      .unwrap();
+    assert_eq!(shell.surface_color, surface);
+  }
 }
@@ -0,0 +1,311 @@
+use std::{
+  collections::BTreeMap,
+  path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as _};
+
+use super::*;
+
+/// How often the theme file is checked for modifications in the debug build.
+#[cfg(debug_assertions)]
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The design tokens of a theme, loaded from a JSON file, enabled by the
+/// `theme-tokens` feature.
+///
+/// Every token is optional, only the tokens in the file override the theme
+/// they apply to. The colors are hex strings like `#6750A4` or `#6750A4FF`, and
+/// the text styles are named as the fields of [`TypographyTheme`].
+///
+/// ```json
+/// {
+///   "brightness": "dark",
+///   "palette": { "primary": "#6750A4", "error": "#B3261E" },
+///   "typography": {
+///     "body_medium": { "font_size": 15, "line_height": 22, "font_families": ["Lato"] }
+///   },
+///   "font_files": ["./fonts/Lato-Regular.ttf"]
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeTokens {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub brightness: Option<Brightness>,
+  pub palette: PaletteTokens,
+  pub typography: BTreeMap<String, TextTokens>,
+  pub font_files: Vec<String>,
+}
+
+/// The key colors of the [`Palette`].
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PaletteTokens {
+  #[serde(skip_serializing_if = "Option::is_none", with = "hex_color")]
+  pub primary: Option<Color>,
+  #[serde(skip_serializing_if = "Option::is_none", with = "hex_color")]
+  pub secondary: Option<Color>,
+  #[serde(skip_serializing_if = "Option::is_none", with = "hex_color")]
+  pub tertiary: Option<Color>,
+  #[serde(skip_serializing_if = "Option::is_none", with = "hex_color")]
+  pub neutral: Option<Color>,
+  #[serde(skip_serializing_if = "Option::is_none", with = "hex_color")]
+  pub neutral_variant: Option<Color>,
+  #[serde(skip_serializing_if = "Option::is_none", with = "hex_color")]
+  pub error: Option<Color>,
+  #[serde(skip_serializing_if = "Option::is_none", with = "hex_color")]
+  pub warning: Option<Color>,
+  #[serde(skip_serializing_if = "Option::is_none", with = "hex_color")]
+  pub success: Option<Color>,
+}
+
+/// The tokens of a text style in the [`TypographyTheme`].
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TextTokens {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub font_size: Option<f32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub line_height: Option<f32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub letter_space: Option<f32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub font_weight: Option<u16>,
+  /// The font families by priority, the generic families are `serif`,
+  /// `sans-serif`, `cursive`, `fantasy` and `monospace`.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub font_families: Option<Vec<String>>,
+}
+
+/// The errors that may occur when loading the theme tokens.
+#[derive(Debug)]
+pub enum ThemeTokensError {
+  /// The file can't be read.
+  Io(std::io::Error),
+  /// The content is not valid theme tokens.
+  Parse(serde_json::Error),
+}
+
+impl ThemeTokens {
+  /// Parse the theme tokens from the JSON text.
+  pub fn from_json(json: &str) -> Result<Self, ThemeTokensError> {
+    serde_json::from_str(json).map_err(ThemeTokensError::Parse)
+  }
+
+  /// Load the theme tokens from the JSON file at `path`.
+  pub fn load(path: impl AsRef<Path>) -> Result<Self, ThemeTokensError> {
+    let json = std::fs::read_to_string(path).map_err(ThemeTokensError::Io)?;
+    Self::from_json(&json)
+  }
+
+  /// Serialize the theme tokens to a pretty JSON text.
+  pub fn to_json(&self) -> String { serde_json::to_string_pretty(self).unwrap() }
+
+  /// Override the `theme` with the tokens.
+  pub fn apply(&self, theme: &mut Theme) {
+    let Self { brightness, palette, typography, font_files } = self;
+    if let Some(brightness) = brightness {
+      theme.palette.brightness = *brightness;
+    }
+    palette.apply(&mut theme.palette);
+    for (name, tokens) in typography {
+      match text_theme_mut(&mut theme.typography_theme, name) {
+        Some(text) => tokens.apply(&mut text.text),
+        None => log::warn!("Unknown text style `{name}` in the theme tokens."),
+      }
+    }
+    for file in font_files {
+      if !theme.font_files.contains(file) {
+        theme.font_files.push(file.clone());
+      }
+    }
+  }
+}
+
+impl PaletteTokens {
+  fn apply(&self, palette: &mut Palette) {
+    let colors = [
+      (self.primary, &mut palette.primary),
+      (self.secondary, &mut palette.secondary),
+      (self.tertiary, &mut palette.tertiary),
+      (self.neutral, &mut palette.neutral),
+      (self.neutral_variant, &mut palette.neutral_variant),
+      (self.error, &mut palette.error),
+      (self.warning, &mut palette.warning),
+      (self.success, &mut palette.success),
+    ];
+    for (token, color) in colors {
+      if let Some(token) = token {
+        *color = token;
+      }
+    }
+  }
+}
+
+impl TextTokens {
+  fn apply(&self, style: &mut TextStyle) {
+    if let Some(font_size) = self.font_size {
+      style.font_size = font_size;
+    }
+    if let Some(line_height) = self.line_height {
+      style.line_height = line_height;
+    }
+    if let Some(letter_space) = self.letter_space {
+      style.letter_space = letter_space;
+    }
+    if let Some(weight) = self.font_weight {
+      style.font_face.weight = FontWeight(weight);
+    }
+    if let Some(families) = &self.font_families {
+      style.font_face.families = families
+        .iter()
+        .map(|name| match name.as_str() {
+          "serif" => FontFamily::Serif,
+          "sans-serif" => FontFamily::SansSerif,
+          "cursive" => FontFamily::Cursive,
+          "fantasy" => FontFamily::Fantasy,
+          "monospace" => FontFamily::Monospace,
+          _ => FontFamily::Name(name.clone().into()),
+        })
+        .collect();
+    }
+  }
+}
+
+fn text_theme_mut<'a>(
+  typography: &'a mut TypographyTheme, name: &str,
+) -> Option<&'a mut TextTheme> {
+  let text = match name {
+    "display_large" => &mut typography.display_large,
+    "display_medium" => &mut typography.display_medium,
+    "display_small" => &mut typography.display_small,
+    "headline_large" => &mut typography.headline_large,
+    "headline_medium" => &mut typography.headline_medium,
+    "headline_small" => &mut typography.headline_small,
+    "title_large" => &mut typography.title_large,
+    "title_medium" => &mut typography.title_medium,
+    "title_small" => &mut typography.title_small,
+    "label_large" => &mut typography.label_large,
+    "label_medium" => &mut typography.label_medium,
+    "label_small" => &mut typography.label_small,
+    "body_large" => &mut typography.body_large,
+    "body_medium" => &mut typography.body_medium,
+    "body_small" => &mut typography.body_small,
+    _ => return None,
+  };
+  Some(text)
+}
+
+impl AppCtx {
+  /// Override the application theme with the theme tokens of the JSON file at
+  /// `path`, see [`ThemeTokens`]. Call it after the application theme is set.
+  ///
+  /// In the debug build, the file is watched and applied again every time it's
+  /// modified, so the colors and the text styles can be tweaked without
+  /// recompiling.
+  pub fn load_theme_file(path: impl Into<PathBuf>) -> Result<(), ThemeTokensError> {
+    let path = path.into();
+    ThemeTokens::load(&path)?.apply(&mut AppCtx::app_theme().write());
+
+    #[cfg(debug_assertions)]
+    {
+      let modified = |p: &Path| {
+        std::fs::metadata(p)
+          .and_then(|m| m.modified())
+          .ok()
+      };
+      let mut last = modified(&path);
+      observable::interval(WATCH_INTERVAL, AppCtx::scheduler()).subscribe(move |_| {
+        let now = modified(&path);
+        if now != last {
+          last = now;
+          match ThemeTokens::load(&path) {
+            Ok(tokens) => tokens.apply(&mut AppCtx::app_theme().write()),
+            Err(e) => log::warn!("Failed to reload the theme file {}: {e}", path.display()),
+          }
+        }
+      });
+    }
+    Ok(())
+  }
+}
+
+impl std::fmt::Display for ThemeTokensError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ThemeTokensError::Io(e) => write!(f, "failed to read the theme tokens: {e}"),
+      ThemeTokensError::Parse(e) => write!(f, "invalid theme tokens: {e}"),
+    }
+  }
+}
+
+impl std::error::Error for ThemeTokensError {}
+
+/// Serialize the colors as the hex strings like `#RRGGBBAA`.
+mod hex_color {
+  use super::*;
+
+  pub fn serialize<S: Serializer>(color: &Option<Color>, s: S) -> Result<S::Ok, S::Error> {
+    match color {
+      Some(Color { red, green, blue, alpha }) => {
+        s.serialize_str(&format!("#{red:02X}{green:02X}{blue:02X}{alpha:02X}"))
+      }
+      None => s.serialize_none(),
+    }
+  }
+
+  pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Color>, D::Error> {
+    let Some(hex) = Option::<String>::deserialize(d)? else { return Ok(None) };
+    parse(&hex)
+      .map(Some)
+      .ok_or_else(|| D::Error::custom(format!("invalid color `{hex}`, expect `#RRGGBB[AA]`")))
+  }
+
+  fn parse(hex: &str) -> Option<Color> {
+    let digits = hex
+      .strip_prefix('#')
+      .filter(|d| d.bytes().all(|b| b.is_ascii_hexdigit()))?;
+    let value = u32::from_str_radix(digits, 16).ok()?;
+    match digits.len() {
+      6 => Some(Color::from_u32(value << 8 | 0xFF)),
+      8 => Some(Color::from_u32(value)),
+      _ => None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn apply_tokens() {
+    let tokens = ThemeTokens::from_json(
+      r##"{
+        "brightness": "dark",
+        "palette": { "primary": "#112233", "error": "#44556680" },
+        "typography": {
+          "body_medium": { "font_size": 15, "font_weight": 700, "font_families": ["Lato", "serif"] }
+        }
+      }"##,
+    )
+    .unwrap();
+
+    let mut theme = Theme::default();
+    let secondary = theme.palette.secondary;
+    tokens.apply(&mut theme);
+    assert_eq!(theme.palette.brightness, Brightness::Dark);
+    assert_eq!(theme.palette.primary, Color::from_u32(0x112233FF));
+    assert_eq!(theme.palette.error, Color::from_u32(0x44556680));
+    assert_eq!(theme.palette.secondary, secondary);
+
+    let body = &theme.typography_theme.body_medium.text;
+    assert_eq!(body.font_size, 15.);
+    assert_eq!(body.font_face.weight, FontWeight::BOLD);
+    assert_eq!(&*body.font_face.families, &[FontFamily::Name("Lato".into()), FontFamily::Serif]);
+
+    assert_eq!(ThemeTokens::from_json(&tokens.to_json()).unwrap(), tokens);
+    assert!(ThemeTokens::from_json(r#"{ "palette": { "primary": "red" } }"#).is_err());
+  }
+}
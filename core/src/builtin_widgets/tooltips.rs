@@ -1,4 +1,7 @@
-use std::cell::RefCell;
+use std::{
+  cell::{Cell, RefCell},
+  rc::Rc,
+};
 
 use crate::prelude::*;
 
@@ -8,6 +11,11 @@ class_names! {
 }
 /// Add attributes of tooltips to Widget Declarer.
 ///
+/// The tooltips show near the pointer after it hovers over the widget for
+/// [`Window::tooltips_delay`], or above the widget after it's focused by the
+/// keyboard for the same delay. They hide when the pointer leaves, the focus
+/// leaves or the pointer presses the widget.
+///
 /// ### Example:
 /// ```no_run
 /// use ribir::prelude::*;
@@ -25,6 +33,10 @@ pub struct Tooltips {
   overlay: RefCell<Option<Overlay>>,
 }
 
+/// The distance from the pointer to the tooltips, so the cursor doesn't cover
+/// them.
+const POINTER_OFFSET: f32 = 16.;
+
 impl Declare for Tooltips {
   type Builder = FatObj<()>;
   fn declarer() -> Self::Builder { FatObj::new(()) }
@@ -34,6 +46,9 @@ impl Tooltips {
   fn tooltips(&self) -> &CowArc<str> { &self.tooltips }
 
   pub fn show(&self, wnd: Sc<Window>) {
+    if self.tooltips.is_empty() {
+      return;
+    }
     if let Some(overlay) = self.overlay.borrow().clone() {
       if !overlay.is_showing() {
         overlay.show(wnd);
@@ -55,46 +70,74 @@ impl<'c> ComposeChild<'c> for Tooltips {
   fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
     fn_widget! {
       let mut child = FatObj::new(child);
+      // The global position to show the tooltips at, they show above the child
+      // if it's `None`.
+      let at: Rc<Cell<Option<Point>>> = <_>::default();
+      let at2 = at.clone();
       *$this.overlay.borrow_mut() = Some(Overlay::new(
         move || {
-          let w = @Text {
+          let mut w = @Text {
             text: pipe!($this.tooltips().clone()),
             class: TOOLTIPS,
           };
 
-          @ $w {
-            global_anchor_x: pipe!(
+          if let Some(pos) = at2.get() {
+            w = w.anchor(Anchor::from_point(pos + Vector::new(0., POINTER_OFFSET)));
+          } else {
+            w = w.global_anchor_x(pipe!(
               GlobalAnchorX::center_align_to(
                 $child.track_id(), 0.
               ).always_follow()
-            ),
-            global_anchor_y: pipe!(
+            ))
+            .global_anchor_y(pipe!(
               GlobalAnchorY::bottom_align_to(
                 $child.track_id(), $child.layout_size().height
               ).always_follow()
-            ),
-          }.into_widget()
+            ));
+          }
+          w.into_widget()
         },  OverlayStyle {
           auto_close_policy: AutoClosePolicy::NOT_AUTO_CLOSE,
           mask: None,
         }
       ));
 
+      // The tooltips are suppressed after the pointer presses the widget, until
+      // both the pointer and the focus leave it.
+      let suppressed = Stateful::new(false);
+      let pointer = Rc::new(Cell::new(Point::zero()));
+      let pointer2 = pointer.clone();
       let wnd = BuildCtx::get().window();
-      let u = watch!($child.is_hover())
-        .delay(Duration::from_millis(50), AppCtx::scheduler())
+      let u = watch!(!*$suppressed && ($child.is_hover() || $child.has_focus()))
         .distinct_until_changed()
-        .subscribe(move |_| {
-          if $child.is_hover() {
-            $this.show(wnd.clone());
-          } else {
+        .subscribe(move |active| {
+          if !active {
             $this.hidden();
+            return;
           }
+          let (pointer, at, wnd) = (pointer.clone(), at.clone(), wnd.clone());
+          observable::timer((), wnd.tooltips_delay(), AppCtx::scheduler())
+            .subscribe(move |_| {
+              let hover = $child.is_hover();
+              if !*$suppressed && (hover || $child.has_focus()) {
+                at.set(hover.then(|| pointer.get()));
+                $this.show(wnd.clone());
+              }
+            });
         });
+      let u2 = watch!($child.is_hover() || $child.has_focus())
+        .filter(|active| !active)
+        .subscribe(move |_| *$suppressed.write() = false);
 
       @ $child {
+        on_pointer_move: move |e| pointer2.set(e.global_pos()),
+        on_pointer_down: move |_| {
+          *$suppressed.write() = true;
+          $this.hidden();
+        },
         on_disposed: move|_| {
           u.unsubscribe();
+          u2.unsubscribe();
           $this.hidden();
         },
       }
@@ -102,3 +145,130 @@ impl<'c> ComposeChild<'c> for Tooltips {
     .into_widget()
   }
 }
+
+/// Shows the full text of a `Text` in the tooltips when it's hovered and
+/// truncated by the `TextOverflow::Ellipsis`.
+///
+/// The truncation is known after the text is laid out, so the tooltips of the
+/// `Text` are updated after every layout, and override the tooltips declared
+/// on it.
+///
+/// ### Example:
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let w = fn_widget! {
+///   @AutoTooltip {
+///     @Text {
+///       text: "A long text that may be truncated by its parent",
+///       text_overflow: TextOverflow::Ellipsis,
+///     }
+///   }
+/// };
+/// App::run(w);
+/// ```
+#[derive(Declare)]
+pub struct AutoTooltip;
+
+impl ChildOfCompose for FatObj<State<Text>> {}
+
+impl ComposeChild<'static> for AutoTooltip {
+  type Child = FatObj<State<Text>>;
+  fn compose_child(_: impl StateWriter<Value = Self>, mut child: Self::Child) -> Widget<'static> {
+    let text = child.clone_reader();
+    let tooltips = child.get_tooltips_widget().clone_writer();
+    child
+      .on_performed_layout(move |_| {
+        let text = text.read();
+        let full = if text.is_truncated() { text.text.clone() } else { CowArc::default() };
+        if tooltips.read().tooltips != full {
+          if full.is_empty() {
+            tooltips.read().hidden();
+          }
+          tooltips.write().tooltips = full;
+        }
+      })
+      .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use winit::{
+    event::{DeviceId, ElementState, MouseButton, WindowEvent},
+    keyboard::{KeyCode, KeyLocation, PhysicalKey},
+  };
+
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  fn tooltips_pos(wnd: &TestWindow) -> Option<Point> {
+    let tree = wnd.tree();
+    let overlay = tree.root().children(tree).nth(1)?;
+    tree
+      .store
+      .layout_info(overlay)
+      .map(|info| info.pos)
+  }
+
+  fn move_to(wnd: &mut TestWindow, x: f64, y: f64) {
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::CursorMoved {
+      device_id: unsafe { DeviceId::dummy() },
+      position: (x, y).into(),
+    });
+    wnd.draw_frame();
+  }
+
+  #[test]
+  fn show_by_hover_and_focus() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(100., 50.),
+          tab_index: 0_i16,
+          tooltips: "tips",
+        }
+      },
+      Size::new(200., 200.),
+    );
+    wnd.set_tooltips_delay(Duration::from_millis(100));
+    wnd.draw_frame();
+
+    // Show near the pointer after the delay.
+    move_to(&mut wnd, 10., 10.);
+    assert_eq!(tooltips_pos(&wnd), None);
+    wnd.tick(Duration::from_millis(100));
+    wnd.draw_frame();
+    assert_eq!(tooltips_pos(&wnd), Some(Point::new(10., 10. + POINTER_OFFSET)));
+
+    // Hide when the pointer leaves.
+    move_to(&mut wnd, 150., 150.);
+    assert_eq!(tooltips_pos(&wnd), None);
+
+    // Show above the widget when it's focused by the keyboard.
+    wnd.processes_keyboard_event(
+      PhysicalKey::Code(KeyCode::Tab),
+      VirtualKey::Named(NamedKey::Tab),
+      false,
+      KeyLocation::Standard,
+      ElementState::Pressed,
+    );
+    wnd.draw_frame();
+    wnd.tick(Duration::from_millis(100));
+    wnd.draw_frame();
+    assert!(tooltips_pos(&wnd).is_some_and(|pos| pos.y < 0.));
+
+    // Hide when the pointer presses, and not show again.
+    move_to(&mut wnd, 10., 10.);
+    let device_id = unsafe { DeviceId::dummy() };
+    wnd.process_mouse_input(device_id, ElementState::Pressed, MouseButton::Left);
+    wnd.draw_frame();
+    assert_eq!(tooltips_pos(&wnd), None);
+    wnd.tick(Duration::from_millis(100));
+    wnd.draw_frame();
+    assert_eq!(tooltips_pos(&wnd), None);
+  }
+}
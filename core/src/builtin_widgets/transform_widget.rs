@@ -1,8 +1,108 @@
+use std::cell::Cell;
+
 use crate::{prelude::*, wrap_render::*};
 
-#[derive(Clone, Default)]
+/// The transformation of a widget, the `transform` matrix is applied first,
+/// then the `scale`, the `rotate` and the translation, all of them around the
+/// `transform_origin` of the widget.
+///
+/// The origin is resolved by the size of the widget after it's laid out, so
+/// painting, hit testing and mapping the points of the widget use the same
+/// matrix.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @Container {
+///     size: Size::new(100., 50.),
+///     transform_origin: TransformOrigin::Center,
+///     rotate: Angle::degrees(45.),
+///     scale: 1.5,
+///   }
+/// };
+/// ```
+#[derive(Clone)]
 pub struct TransformWidget {
   pub transform: Transform,
+  pub transform_origin: TransformOrigin,
+  pub rotate: Angle,
+  /// The uniform scale of the widget.
+  pub scale: f32,
+  pub translate_x: f32,
+  pub translate_y: f32,
+  size: Cell<Size>,
+}
+
+/// The point of a widget that its transformation is around.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TransformOrigin {
+  #[default]
+  TopLeft,
+  TopRight,
+  BottomLeft,
+  BottomRight,
+  Center,
+  /// The fractions of the width and height of the widget, `Fraction(0.5,
+  /// 0.5)` is the center.
+  Fraction(f32, f32),
+  /// The point in pixels from the top left of the widget.
+  Point(Point),
+}
+
+impl TransformOrigin {
+  /// The point of the origin in a widget of the `size`.
+  pub fn resolve(&self, size: Size) -> Point {
+    let (x, y) = match *self {
+      TransformOrigin::TopLeft => (0., 0.),
+      TransformOrigin::TopRight => (1., 0.),
+      TransformOrigin::BottomLeft => (0., 1.),
+      TransformOrigin::BottomRight => (1., 1.),
+      TransformOrigin::Center => (0.5, 0.5),
+      TransformOrigin::Fraction(x, y) => (x, y),
+      TransformOrigin::Point(p) => return p,
+    };
+    Point::new(size.width * x, size.height * y)
+  }
+}
+
+impl Lerp for TransformOrigin {
+  fn lerp(&self, to: &Self, factor: f32) -> Self {
+    match (*self, *to) {
+      (TransformOrigin::Point(from), TransformOrigin::Point(to)) => {
+        TransformOrigin::Point(Lerp::lerp(&from, &to, factor))
+      }
+      (TransformOrigin::Point(_), to) | (_, to @ TransformOrigin::Point(_)) => {
+        if factor < 1. {
+          *self
+        } else {
+          to
+        }
+      }
+      (from, to) => {
+        let from = from.resolve(Size::new(1., 1.));
+        let to = to.resolve(Size::new(1., 1.));
+        let p = Lerp::lerp(&from, &to, factor);
+        TransformOrigin::Fraction(p.x, p.y)
+      }
+    }
+  }
+}
+
+impl Default for TransformWidget {
+  fn default() -> Self {
+    Self {
+      transform: Transform::identity(),
+      transform_origin: TransformOrigin::default(),
+      rotate: Angle::zero(),
+      scale: 1.,
+      translate_x: 0.,
+      translate_y: 0.,
+      size: Cell::default(),
+    }
+  }
 }
 
 impl Declare for TransformWidget {
@@ -16,16 +116,18 @@ impl_compose_child_for_wrap_render!(TransformWidget, DirtyPhase::Paint);
 impl WrapRender for TransformWidget {
   #[inline]
   fn perform_layout(&self, clamp: BoxClamp, host: &dyn Render, ctx: &mut LayoutCtx) -> Size {
-    host.perform_layout(clamp, ctx)
+    let size = host.perform_layout(clamp, ctx);
+    self.size.set(size);
+    size
   }
 
   fn paint(&self, host: &dyn Render, ctx: &mut PaintingCtx) {
-    ctx.painter().apply_transform(&self.transform);
+    ctx.painter().apply_transform(&self.matrix());
     host.paint(ctx)
   }
 
   fn hit_test(&self, host: &dyn Render, ctx: &mut HitTestCtx, pos: Point) -> HitTest {
-    if let Some(t) = self.transform.inverse() {
+    if let Some(t) = self.matrix().inverse() {
       let lt = ctx.box_pos().unwrap();
       let pos = (pos - lt).to_point();
       let pos = t.transform_point(pos) + lt.to_vector();
@@ -36,17 +138,30 @@ impl WrapRender for TransformWidget {
   }
 
   fn get_transform(&self, host: &dyn Render) -> Option<Transform> {
-    if let Some(t) = host.get_transform() {
-      Some(self.transform.then(&t))
-    } else {
-      Some(self.transform)
-    }
+    // The transform of the host is applied to the content first, since it's
+    // painted inside this transform.
+    let matrix = self.matrix();
+    let ts = host
+      .get_transform()
+      .map_or(matrix, |h| h.then(&matrix));
+    Some(ts)
   }
 }
 
 impl TransformWidget {
   #[inline]
-  pub fn new(transform: Transform) -> Self { Self { transform } }
+  pub fn new(transform: Transform) -> Self { Self { transform, ..Default::default() } }
+
+  /// The matrix composed by all the fields, around the origin resolved by the
+  /// size of the last layout.
+  pub fn matrix(&self) -> Transform {
+    let origin = self.transform_origin.resolve(self.size.get());
+    Transform::translation(-origin.x, -origin.y)
+      .then(&self.transform)
+      .then_scale(self.scale, self.scale)
+      .then_rotate(self.rotate)
+      .then_translate(Vector::new(origin.x + self.translate_x, origin.y + self.translate_y))
+  }
 }
 
 #[cfg(test)]
@@ -54,7 +169,7 @@ mod tests {
   use ribir_dev_helper::*;
 
   use super::*;
-  use crate::test_helper::*;
+  use crate::{reset_test_env, test_helper::*};
 
   widget_layout_test!(
     smoke,
@@ -68,4 +183,50 @@ mod tests {
     }),
     LayoutCase::default().with_size((100., 100.).into())
   );
+
+  #[test]
+  fn resolve_origin() {
+    let size = Size::new(100., 50.);
+    assert_eq!(TransformOrigin::Center.resolve(size), Point::new(50., 25.));
+    assert_eq!(TransformOrigin::BottomRight.resolve(size), Point::new(100., 50.));
+    assert_eq!(TransformOrigin::Fraction(0.2, 0.4).resolve(size), Point::new(20., 20.));
+
+    let w = TransformWidget {
+      transform_origin: TransformOrigin::Center,
+      rotate: Angle::degrees(180.),
+      translate_x: 10.,
+      ..Default::default()
+    };
+    w.size.set(size);
+    let p = w.matrix().transform_point(Point::zero());
+    assert!((p - Point::new(110., 50.)).length() < 1e-4);
+  }
+
+  #[test]
+  fn hit_scaled_around_center() {
+    reset_test_env!();
+
+    let (taps, w_taps) = split_value(0);
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        transform_origin: TransformOrigin::Center,
+        scale: 0.5,
+        on_tap: move |_| *$w_taps.write() += 1,
+      }
+    });
+    wnd.draw_frame();
+
+    let mut tap = |pos: Point| {
+      UiEvent::tap_at(pos)
+        .into_iter()
+        .for_each(|e| wnd.inject_event(e));
+      wnd.draw_frame();
+    };
+    // The box is scaled to the center, its corner is not hit.
+    tap(Point::new(10., 10.));
+    assert_eq!(*taps.read(), 0);
+    tap(Point::new(30., 30.));
+    assert_eq!(*taps.read(), 1);
+  }
 }
@@ -0,0 +1,144 @@
+use crate::{prelude::*, wrap_render::*};
+
+/// A widget that marks its box as a region where the platform backdrop of the
+/// window shows through, like the macOS vibrancy or the Windows acrylic/mica
+/// material.
+///
+/// It only takes effect when the window has the `WindowFlags::BACKDROP` flag,
+/// then the window is cleared to transparent, and only the area out of the
+/// `Vibrancy` widgets is filled with the surface color. The child is painted
+/// over the backdrop, usually with a translucent background to tint it.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let _sidebar = fn_widget! {
+///   @Vibrancy {
+///     @Container {
+///       size: Size::new(240., 600.),
+///       background: Color::WHITE.with_alpha(0.2),
+///     }
+///   }
+/// };
+/// ```
+#[derive(Declare, Clone)]
+pub struct Vibrancy;
+
+impl_compose_child_for_wrap_render!(Vibrancy, DirtyPhase::Paint);
+
+impl WrapRender for Vibrancy {
+  fn perform_layout(&self, clamp: BoxClamp, host: &dyn Render, ctx: &mut LayoutCtx) -> Size {
+    host.perform_layout(clamp, ctx)
+  }
+
+  fn paint(&self, host: &dyn Render, ctx: &mut PaintingCtx) {
+    let size = ctx.box_size().unwrap();
+    let mut transform = *ctx.box_painter().transform();
+    if let Some(layer) = ctx.layer_transform() {
+      transform = transform.then(layer);
+    }
+    let visible = ctx
+      .box_painter()
+      .intersection_paint_bounds(&Rect::from_size(size));
+    if let Some(rect) = visible {
+      ctx
+        .window()
+        .add_vibrancy_region(transform.outer_transformed_rect(&rect));
+    }
+    host.paint(ctx)
+  }
+}
+
+/// Return the commands that fill the `viewport` with the `surface` color, but
+/// leave the `regions` transparent.
+pub(crate) fn backdrop_surface(
+  viewport: Rect, regions: &[Rect], surface: Color,
+) -> Vec<PaintCommand> {
+  let mut painter = Painter::new(viewport);
+  painter.set_fill_brush(surface);
+  for rect in subtract_rects(viewport, regions) {
+    painter.rect(&rect);
+  }
+  painter.fill();
+  painter.take_commands(0)
+}
+
+/// Subtract the `holes` from the `rect`, return the rects that don't overlap
+/// each other.
+fn subtract_rects(rect: Rect, holes: &[Rect]) -> Vec<Rect> {
+  let mut rects = vec![rect];
+  for hole in holes {
+    let mut remain = Vec::with_capacity(rects.len());
+    for rect in rects {
+      let Some(inter) = rect.intersection(hole) else {
+        remain.push(rect);
+        continue;
+      };
+      let (min, max) = (rect.min(), rect.max());
+      let (i_min, i_max) = (inter.min(), inter.max());
+      let pieces = [
+        Box2D::new(min, Point::new(max.x, i_min.y)),
+        Box2D::new(Point::new(min.x, i_max.y), max),
+        Box2D::new(Point::new(min.x, i_min.y), Point::new(i_min.x, i_max.y)),
+        Box2D::new(Point::new(i_max.x, i_min.y), Point::new(max.x, i_max.y)),
+      ];
+      remain.extend(
+        pieces
+          .iter()
+          .filter(|b| !b.is_empty())
+          .map(Box2D::to_rect),
+      );
+    }
+    rects = remain;
+  }
+  rects
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*, window::WindowFlags};
+
+  #[test]
+  fn subtract_hole() {
+    let rect = Rect::from_size(Size::new(100., 100.));
+    let hole = Rect::new(Point::new(20., 30.), Size::new(40., 50.));
+    let rects = subtract_rects(rect, &[hole]);
+    assert_eq!(rects.len(), 4);
+    let area: f32 = rects.iter().map(|r| r.area()).sum();
+    assert_eq!(area, 100. * 100. - 40. * 50.);
+    assert!(rects.iter().all(|r| !r.intersects(&hole)));
+
+    assert_eq!(subtract_rects(rect, &[rect]), vec![]);
+    let outside = Rect::new(Point::new(200., 0.), Size::new(10., 10.));
+    assert_eq!(subtract_rects(rect, &[outside]), vec![rect]);
+  }
+
+  #[test]
+  fn vibrancy_backdrop() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockStack {
+          @MockBox { size: Size::new(100., 100.) }
+          @Vibrancy {
+            @MockBox { size: Size::new(20., 20.) }
+          }
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.set_flags(wnd.flags() | WindowFlags::BACKDROP);
+    wnd.draw_frame();
+
+    let frame = wnd.take_last_frame().unwrap();
+    assert_eq!(frame.surface, Color::TRANSPARENT);
+    let Some(PaintCommand::Path(fill)) = frame.commands.first() else {
+      panic!("expect the surface fill first, but got {:?}", frame.commands);
+    };
+    assert_eq!(fill.paint_bounds, Rect::from_size(Size::new(100., 100.)));
+  }
+}
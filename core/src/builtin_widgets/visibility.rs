@@ -1,8 +1,32 @@
 use crate::{prelude::*, wrap_render::*};
 
-#[derive(Default)]
+/// Decide whether the widget is shown.
+///
+/// A hidden widget is collapsed by default, it takes no space, and it isn't
+/// painted, hit or focused. With `maintain_size`, it's still laid out and
+/// keeps its space, and with `maintain_interactivity` too, it can still be
+/// hit and focused.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @Container {
+///     size: Size::new(100., 100.),
+///     visible: false,
+///     maintain_size: true,
+///   }
+/// };
+/// ```
 pub struct Visibility {
   pub visible: bool,
+  /// If true, the hidden widget keeps its space.
+  pub maintain_size: bool,
+  /// If true, the hidden widget can be hit and focused, it only works with
+  /// `maintain_size`.
+  pub maintain_interactivity: bool,
 }
 
 impl Declare for Visibility {
@@ -16,10 +40,10 @@ impl<'c> ComposeChild<'c> for Visibility {
   fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
     fn_widget! {
       @FocusScope {
-        skip_descendants: pipe!(!$this.get_visible()),
-        skip_host: pipe!(!$this.get_visible()),
+        skip_descendants: pipe!(!$this.is_interactive()),
+        skip_host: pipe!(!$this.is_interactive()),
         @VisibilityRender {
-          display: pipe!($this.get_visible()),
+          display: pipe!($this.display()),
           @ { child }
         }
       }
@@ -28,9 +52,21 @@ impl<'c> ComposeChild<'c> for Visibility {
   }
 }
 
+/// How a widget is displayed by its visibility.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Display {
+  #[default]
+  Show,
+  /// Laid out and hit, but not painted.
+  Invisible,
+  /// Laid out, but not painted or hit.
+  KeepSize,
+  Collapse,
+}
+
 #[derive(Declare, Clone)]
 struct VisibilityRender {
-  display: bool,
+  display: Display,
 }
 
 impl_compose_child_for_wrap_render!(VisibilityRender, DirtyPhase::Layout);
@@ -38,11 +74,11 @@ impl_compose_child_for_wrap_render!(VisibilityRender, DirtyPhase::Layout);
 impl WrapRender for VisibilityRender {
   #[inline]
   fn perform_layout(&self, clamp: BoxClamp, host: &dyn Render, ctx: &mut LayoutCtx) -> Size {
-    if self.display { host.perform_layout(clamp, ctx) } else { clamp.min }
+    if self.display == Display::Collapse { clamp.min } else { host.perform_layout(clamp, ctx) }
   }
 
   fn paint(&self, host: &dyn Render, ctx: &mut PaintingCtx) {
-    if self.display {
+    if self.display == Display::Show {
       host.paint(ctx)
     } else {
       ctx.painter().apply_alpha(0.);
@@ -50,20 +86,82 @@ impl WrapRender for VisibilityRender {
   }
 
   fn hit_test(&self, host: &dyn Render, ctx: &mut HitTestCtx, pos: Point) -> HitTest {
-    if self.display {
-      host.hit_test(ctx, pos)
-    } else {
-      HitTest { hit: false, can_hit_child: false }
+    match self.display {
+      Display::Show | Display::Invisible => host.hit_test(ctx, pos),
+      Display::KeepSize | Display::Collapse => HitTest { hit: false, can_hit_child: false },
     }
   }
 }
 
+impl Default for Visibility {
+  fn default() -> Self {
+    Self { visible: true, maintain_size: false, maintain_interactivity: false }
+  }
+}
+
 impl Visibility {
   #[inline]
-  pub fn new(visible: bool) -> Self { Self { visible } }
+  pub fn new(visible: bool) -> Self { Self { visible, ..Default::default() } }
+
+  fn display(&self) -> Display {
+    match self {
+      Self { visible: true, .. } => Display::Show,
+      Self { maintain_size: true, maintain_interactivity: true, .. } => Display::Invisible,
+      Self { maintain_size: true, .. } => Display::KeepSize,
+      _ => Display::Collapse,
+    }
+  }
 
-  #[inline]
-  fn get_visible(&self) -> bool { self.visible }
+  fn is_interactive(&self) -> bool { matches!(self.display(), Display::Show | Display::Invisible) }
+}
+
+/// A widget lays out its child but neither paints nor hits it when it's
+/// `offstage`, and the child takes no space.
+///
+/// It keeps the child ready to show, for example, to measure it before
+/// showing it, or to keep its state while it's not displayed.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @Offstage {
+///     offstage: true,
+///     @Container { size: Size::new(100., 100.) }
+///   }
+/// };
+/// ```
+#[derive(Declare, Clone)]
+pub struct Offstage {
+  #[declare(default = true)]
+  pub offstage: bool,
+}
+
+impl_compose_child_for_wrap_render!(Offstage, DirtyPhase::Layout);
+
+impl WrapRender for Offstage {
+  fn perform_layout(&self, clamp: BoxClamp, host: &dyn Render, ctx: &mut LayoutCtx) -> Size {
+    let size = host.perform_layout(clamp, ctx);
+    if self.offstage { clamp.min } else { size }
+  }
+
+  fn paint(&self, host: &dyn Render, ctx: &mut PaintingCtx) {
+    if self.offstage {
+      ctx.painter().apply_alpha(0.);
+    } else {
+      host.paint(ctx)
+    }
+  }
+
+  fn hit_test(&self, host: &dyn Render, ctx: &mut HitTestCtx, pos: Point) -> HitTest {
+    if self.offstage {
+      HitTest { hit: false, can_hit_child: false }
+    } else {
+      host.hit_test(ctx, pos)
+    }
+  }
 }
 
 #[cfg(test)]
@@ -100,4 +198,42 @@ mod tests {
     wnd.draw_frame();
     assert_eq!(*hit.read(), 1);
   }
+
+  #[test]
+  fn hidden_keep_size() {
+    reset_test_env!();
+
+    let root_size = |maintain_size: bool| {
+      let mut wnd = TestWindow::new(fn_widget! {
+        @MockBox { size: Size::splat(100.), visible: false, maintain_size }
+      });
+      wnd.draw_frame();
+      wnd
+        .layout_info_by_path(&[0])
+        .unwrap()
+        .size
+        .unwrap()
+    };
+    assert_eq!(root_size(false), Size::zero());
+    assert_eq!(root_size(true), Size::splat(100.));
+  }
+
+  #[test]
+  fn offstage_layout_child() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! {
+      @Offstage {
+        @MockBox {
+          size: Size::splat(100.),
+          @MockBox { size: Size::splat(30.) }
+        }
+      }
+    });
+    wnd.draw_frame();
+    wnd.assert_root_size(Size::zero());
+    // The child is still laid out.
+    let child = wnd.layout_info_by_path(&[0, 0]).unwrap();
+    assert_eq!(child.size, Some(Size::splat(30.)));
+  }
 }
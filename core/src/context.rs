@@ -11,4 +11,6 @@ pub mod app_ctx;
 pub use app_ctx::tokio_async::*;
 pub use app_ctx::*;
 mod build_variant;
+mod deferred_work;
 pub use build_variant::*;
+pub use deferred_work::DeferPriority;
@@ -16,7 +16,9 @@ use rxrust::{
   subject::Subject,
 };
 
+use super::deferred_work::DeferredWorks;
 use crate::{
+  animation::AnimationTimeScale,
   builtin_widgets::Theme,
   clipboard::{Clipboard, MockClipboard},
   local_sender::LocalSender,
@@ -42,6 +44,7 @@ pub trait RuntimeWaker {
 /// already hold by others.
 pub struct AppCtx {
   app_theme: Stateful<Theme>,
+  animation_time_scale: Stateful<AnimationTimeScale>,
   windows: RefCell<ahash::HashMap<WindowId, Sc<Window>>>,
   font_db: Sc<RefCell<FontDB>>,
   typography_store: RefCell<TypographyStore>,
@@ -50,6 +53,9 @@ pub struct AppCtx {
   scheduler: FuturesLocalScheduler,
   executor: RefCell<LocalPool>,
   frame_ticks: Subject<'static, Instant, Infallible>,
+  pub(super) deferred: RefCell<DeferredWorks>,
+  #[cfg(feature = "i18n")]
+  localization: Stateful<crate::i18n::Localization>,
 
   #[cfg(feature = "tokio-async")]
   tokio_runtime: tokio::runtime::Runtime,
@@ -74,6 +80,22 @@ impl AppCtx {
   #[track_caller]
   pub fn app_theme() -> &'static Stateful<Theme> { &Self::shared().app_theme }
 
+  /// Get the global time scale of all animations, modify it to speed up, slow
+  /// down or disable the animations of the application.
+  #[track_caller]
+  pub fn animation_time_scale() -> &'static Stateful<AnimationTimeScale> {
+    &Self::shared().animation_time_scale
+  }
+
+  /// Get the localization of the application, load the Fluent resources and
+  /// switch the active locale by writing it. The texts translated by
+  /// [`tr!`](crate::tr) follow its modifies.
+  #[cfg(feature = "i18n")]
+  #[track_caller]
+  pub fn localization() -> &'static Stateful<crate::i18n::Localization> {
+    &Self::shared().localization
+  }
+
   pub fn new_window(shell_wnd: Box<dyn ShellWindow>, content: GenWidget) -> Sc<Window> {
     let wnd = Window::new(shell_wnd);
     let id = wnd.id();
@@ -168,11 +190,13 @@ impl AppCtx {
     count
   }
 
-  /// Set the theme of the application
+  /// Set the theme of the application, it can be called at runtime to switch
+  /// the theme, for example, to toggle the dark and light mode.
   ///
-  /// # Safety
-  /// This should be only called before application startup. The behavior is
-  /// undefined if you call it in a running application.
+  /// Every window regenerates its content with the new theme in the next
+  /// frame, so the styles, classes and the window background all follow the
+  /// new theme. Note that the states created inside the content are recreated
+  /// too, keep the states that should survive the switch outside.
   #[track_caller]
   pub fn set_app_theme(theme: Theme) { *Self::shared().app_theme.write() = theme; }
 
@@ -469,6 +493,7 @@ impl Default for AppCtx {
     AppCtx {
       font_db,
       app_theme,
+      animation_time_scale: Stateful::new(AnimationTimeScale::default()),
       typography_store,
       clipboard: RefCell::new(Box::new(MockClipboard {})),
       executor: RefCell::new(executor),
@@ -476,6 +501,9 @@ impl Default for AppCtx {
       runtime_waker: RefCell::new(Box::new(MockWaker)),
       windows: RefCell::new(ahash::HashMap::default()),
       frame_ticks: <_>::default(),
+      deferred: <_>::default(),
+      #[cfg(feature = "i18n")]
+      localization: <_>::default(),
 
       #[cfg(feature = "tokio-async")]
       tokio_runtime: tokio::runtime::Builder::new_multi_thread()
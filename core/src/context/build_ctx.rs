@@ -125,6 +125,20 @@ impl BuildCtx {
     BuildCtx::set(ctx);
   }
 
+  /// Build the `widget` with the providers of `provider_ctx`, it's used to
+  /// build widgets out of the build phase, such as during the layout.
+  pub(crate) fn build_with_providers(
+    widget: Widget<'_>, tree: NonNull<WidgetTree>, provider_ctx: &mut ProviderCtx,
+  ) -> WidgetId {
+    let providers = std::mem::take(provider_ctx);
+    let ctx = BuildCtx { tree, children: <_>::default(), provider_ctx: providers };
+    let _guard = BuildCtx::init(ctx);
+    let ctx = BuildCtx::get_mut();
+    let id = ctx.build(widget);
+    *provider_ctx = std::mem::take(&mut ctx.provider_ctx);
+    id
+  }
+
   pub(crate) fn set(ctx: BuildCtx) { unsafe { CTX = Some(LocalSender::new(ctx)) } }
 
   pub(crate) fn clear() {
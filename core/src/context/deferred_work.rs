@@ -0,0 +1,114 @@
+use std::collections::VecDeque;
+
+use crate::prelude::*;
+
+/// The priority of a work deferred by [`AppCtx::defer`], the works of a
+/// higher priority run first, and the works of the same priority run in the
+/// order they are deferred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum DeferPriority {
+  Low,
+  #[default]
+  Normal,
+  High,
+}
+
+/// The default time budget of a frame, the frame time of 60 FPS.
+const DEFAULT_FRAME_BUDGET: Duration = Duration::from_micros(16_667);
+
+pub(crate) struct DeferredWorks {
+  queues: [VecDeque<Box<dyn FnOnce()>>; 3],
+  budget: Duration,
+}
+
+impl Default for DeferredWorks {
+  fn default() -> Self { Self { queues: <_>::default(), budget: DEFAULT_FRAME_BUDGET } }
+}
+
+impl DeferredWorks {
+  fn pop(&mut self) -> Option<Box<dyn FnOnce()>> {
+    self
+      .queues
+      .iter_mut()
+      .rev()
+      .find_map(VecDeque::pop_front)
+  }
+}
+
+impl AppCtx {
+  /// Defer the `work` to the idle time of the frames. The deferred works run
+  /// one by one at the end of a frame only when the frame has remaining time
+  /// budget, the rest wait for the next frames.
+  ///
+  /// It's useful to split heavy work into small pieces that run incrementally
+  /// without causing frame drops, such as pre-building the items of a
+  /// virtualized list or decoding images.
+  pub fn defer(work: impl FnOnce() + 'static, priority: DeferPriority) {
+    Self::shared().deferred.borrow_mut().queues[priority as usize].push_back(Box::new(work));
+  }
+
+  /// Whether there are deferred works waiting to run.
+  pub fn has_deferred_work() -> bool {
+    Self::shared()
+      .deferred
+      .borrow()
+      .queues
+      .iter()
+      .any(|q| !q.is_empty())
+  }
+
+  /// The time budget of a frame, the deferred works run only if the time spent
+  /// by the frame doesn't exceed it.
+  pub fn frame_budget() -> Duration { Self::shared().deferred.borrow().budget }
+
+  /// Sets the time budget of a frame, it's the frame time of 60 FPS by
+  /// default.
+  pub fn set_frame_budget(budget: Duration) {
+    Self::shared().deferred.borrow_mut().budget = budget;
+  }
+
+  /// Run the deferred works until there is no work or the `deadline` is
+  /// reached, return the count of the works that ran.
+  pub fn run_deferred_works(deadline: Instant) -> usize {
+    let mut count = 0;
+    while Instant::now() < deadline {
+      // Not hold the borrow when running the work, it may defer other works.
+      let Some(work) = Self::shared().deferred.borrow_mut().pop() else { break };
+      work();
+      count += 1;
+    }
+    count
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[test]
+  fn run_by_priority_in_budget() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! { @Void {} });
+    let (order, w_order) = split_value(vec![]);
+    for (name, priority) in [
+      ("low", DeferPriority::Low),
+      ("normal", DeferPriority::Normal),
+      ("high", DeferPriority::High),
+    ] {
+      let w_order = w_order.clone_writer();
+      AppCtx::defer(move || w_order.write().push(name), priority);
+    }
+
+    AppCtx::set_frame_budget(Duration::ZERO);
+    wnd.draw_frame();
+    assert!(order.read().is_empty());
+    assert!(AppCtx::has_deferred_work());
+
+    AppCtx::set_frame_budget(Duration::from_secs(1));
+    wnd.draw_frame();
+    assert_eq!(*order.read(), ["high", "normal", "low"]);
+    assert!(!AppCtx::has_deferred_work());
+  }
+}
@@ -1,10 +1,13 @@
+use std::ptr::NonNull;
+
 use ribir_geom::{Point, Size};
+use smallvec::SmallVec;
 
 use super::{WidgetCtx, WidgetCtxImpl};
 use crate::{
-  prelude::ProviderCtx,
-  widget::{BoxClamp, WidgetTree},
-  widget_tree::WidgetId,
+  prelude::{BuildCtx, ProviderCtx},
+  widget::{BoxClamp, Widget, WidgetTree},
+  widget_tree::{WidgetId, widget_id::RenderQueryable},
   window::DelayEvent,
 };
 
@@ -50,9 +53,7 @@ impl<'a> LayoutCtx<'a> {
     debug_assert!(clamp.min.is_finite());
     let size = id.assert_get(tree2).perform_layout(clamp, self);
     debug_assert!(size.is_finite());
-    let info = self.tree.store.layout_info_or_default(id);
-    info.clamp = clamp;
-    info.size = Some(size);
+    self.tree.store.record_layout(id, clamp, size);
 
     self
       .window()
@@ -98,6 +99,39 @@ impl<'a> LayoutCtx<'a> {
       .map(|info| info.pos)
   }
 
+  /// Return the distance from the top of the `child` to its first text
+  /// baseline, or `None` if it has no baseline. The `child` should be laid out
+  /// before.
+  pub fn baseline_of(&mut self, child: WidgetId) -> Option<f32> {
+    self.query_child(child, |r, ctx| r.baseline_offset(ctx))
+  }
+
+  /// Return the smallest width the `child` can be laid out in without
+  /// overflowing when its height is `height`, see
+  /// [`Render::min_intrinsic_width`].
+  pub fn min_intrinsic_width_of(&mut self, child: WidgetId, height: f32) -> f32 {
+    self.query_child(child, |r, ctx| r.min_intrinsic_width(height, ctx))
+  }
+
+  /// Return the smallest height the `child` can be laid out in without
+  /// overflowing when its width is `width`, see
+  /// [`Render::min_intrinsic_height`].
+  pub fn min_intrinsic_height_of(&mut self, child: WidgetId, width: f32) -> f32 {
+    self.query_child(child, |r, ctx| r.min_intrinsic_height(width, ctx))
+  }
+
+  fn query_child<T>(
+    &mut self, child: WidgetId, f: impl FnOnce(&dyn RenderQueryable, &mut Self) -> T,
+  ) -> T {
+    // Safety: the `tree` just use to get the widget of `child`, and `tree2` not
+    // drop or modify it during the query.
+    let tree2 = unsafe { &*(self.tree as *mut WidgetTree) };
+    let id = std::mem::replace(&mut self.id, child);
+    let value = f(child.assert_get(tree2), self);
+    self.id = id;
+    value
+  }
+
   /// Adjust the size of the layout widget. Use this method to directly modify
   /// the size of a widget. In most cases, it is unnecessary to call this
   /// method; using clamp to constrain the child size is typically sufficient.
@@ -147,6 +181,28 @@ impl<'a> LayoutCtx<'a> {
     self.tree.store.force_layout(child).is_some()
   }
 
+  /// Replace the children of the current widget with a new built `child`,
+  /// and return its id.
+  ///
+  /// The children of a widget are only allowed to be replaced by itself
+  /// before they are laid out, so the new child can be laid out in the same
+  /// layout pass.
+  pub(crate) fn rebuild_child(&mut self, child: Widget<'static>) -> WidgetId {
+    let id = self.id;
+    let tree_ptr = NonNull::from(&mut *self.tree);
+    let new = BuildCtx::build_with_providers(child, tree_ptr, &mut self.provider_ctx);
+
+    let tree = &mut *self.tree;
+    let old: SmallVec<[WidgetId; 1]> = id.children(tree).collect();
+    old
+      .into_iter()
+      .for_each(|c| c.dispose_subtree(tree));
+    id.append(new, tree);
+    new.on_mounted_subtree(tree);
+
+    new
+  }
+
   fn get_calculated_size(&self, child: WidgetId, clamp: BoxClamp) -> Option<Size> {
     let info = self.tree.store.layout_info(child)?;
     if info.clamp == clamp { info.size } else { None }
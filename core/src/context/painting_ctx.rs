@@ -1,8 +1,11 @@
+use std::rc::Rc;
+
+use ribir_algo::Resource;
 use ribir_geom::Transform;
 
 use super::WidgetCtxImpl;
 use crate::{
-  prelude::{Painter, ProviderCtx, WidgetId},
+  prelude::{LayerCache, Painter, ProviderCtx, WidgetId},
   widget::WidgetTree,
 };
 
@@ -18,6 +21,26 @@ pub struct PaintingCtx<'a> {
   /// text requires translation, but the background should not.
   /// The `Background` should utilize a `box_painter` that applies this matrix.
   box_offset: Transform,
+  /// The layers are painting, from the outermost to the innermost.
+  layers: Vec<PaintingLayer>,
+}
+
+struct PaintingLayer {
+  /// The widget that starts the layer.
+  id: WidgetId,
+  /// The count of the commands before the layer.
+  start: usize,
+  /// The transform from the axis of the layer to the axis of the painter.
+  to_painter: Transform,
+  effect: LayerEffect,
+}
+
+/// How the layer is drawn after its subtree is painted.
+enum LayerEffect {
+  /// Draw as a bundle, and keep it in the cache if there is one.
+  Bundle(Option<Rc<LayerCache>>),
+  /// Draw as a bundle blurred by the radius.
+  Blur(f32),
 }
 
 impl<'a> WidgetCtxImpl for PaintingCtx<'a> {
@@ -37,7 +60,7 @@ impl<'a> PaintingCtx<'a> {
     };
 
     let box_offset = Transform::identity();
-    Self { id, tree, painter, provider_ctx, box_offset }
+    Self { id, tree, painter, provider_ctx, box_offset, layers: vec![] }
   }
 
   /// Called by the framework when the painting widget is finished.
@@ -50,6 +73,71 @@ impl<'a> PaintingCtx<'a> {
     self.id = id;
   }
 
+  /// Paint the current widget and its descendants in a layer, the layer is
+  /// drawn as a bundle with the opacity of the painter after the subtree is
+  /// painted. So the subtree is composited as a whole, and the backend can
+  /// cache it.
+  ///
+  /// The painter paints in the axis of the layer with full opacity after it.
+  pub(crate) fn start_layer(&mut self, cache: Option<Rc<LayerCache>>) {
+    self.push_layer(LayerEffect::Bundle(cache));
+  }
+
+  /// Paint the current widget and its descendants in a layer like
+  /// [`PaintingCtx::start_layer`], but the layer is blurred by a gaussian blur
+  /// with the standard deviation `radius` when it's drawn.
+  pub(crate) fn start_blur_layer(&mut self, radius: f32) {
+    self.push_layer(LayerEffect::Blur(radius));
+  }
+
+  fn push_layer(&mut self, effect: LayerEffect) {
+    let mut to_painter = *self.painter.transform();
+    if let Some(outer) = self.layers.last() {
+      to_painter = to_painter.then(&outer.to_painter);
+    }
+    self.painter.save_layer();
+    let start = self.painter.command_count();
+    self
+      .layers
+      .push(PaintingLayer { id: self.id, start, to_painter, effect });
+  }
+
+  /// Return if the widget started a layer.
+  pub(crate) fn has_layer(&self, id: WidgetId) -> bool {
+    self.layers.last().is_some_and(|l| l.id == id)
+  }
+
+  /// The transform from the axis of the current layer to the axis of the
+  /// painter, `None` if not in a layer.
+  pub(crate) fn layer_transform(&self) -> Option<&Transform> {
+    self.layers.last().map(|l| &l.to_painter)
+  }
+
+  /// Finish the layers started by the widget, and draw them as bundles.
+  pub(crate) fn finish_layers(&mut self, id: WidgetId) {
+    while self.has_layer(id) {
+      let PaintingLayer { start, effect, .. } = self.layers.pop().unwrap();
+      let painter = &mut *self.painter;
+      painter.restore();
+      let bounds = painter.commands_bounds(start);
+      let cmds = painter.take_commands(start);
+      if let Some(bounds) = bounds {
+        let cmds = Resource::new(cmds.into_boxed_slice());
+        match effect {
+          LayerEffect::Bundle(cache) => {
+            if let Some(cache) = cache {
+              cache.set(bounds, cmds.clone());
+            }
+            painter.draw_bundle_commands(bounds, cmds);
+          }
+          LayerEffect::Blur(radius) => {
+            painter.draw_blur_commands(bounds, radius, cmds);
+          }
+        }
+      }
+    }
+  }
+
   /// Apply a transformation apply only for the content but should not effect
   /// the box widget painter.
   pub fn content_only_transform_apply(&mut self, matrix: &Transform) {
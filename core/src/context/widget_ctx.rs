@@ -53,16 +53,17 @@ pub trait WidgetCtx {
   fn widget_box_rect(&self, wid: WidgetId) -> Option<Rect>;
   /// Return the position of the widget that `wid` references.
   fn widget_box_pos(&self, wid: WidgetId) -> Option<Point>;
-  /// Translates the global window coordinate pos to widget coordinates.
+  /// Translates the widget pos to the global window coordinates.
   fn map_to_global(&self, pos: Point) -> Point;
-  /// Translates the global screen coordinate pos to widget coordinates.
+  /// Translates the global window coordinate pos to widget coordinates.
   fn map_from_global(&self, pos: Point) -> Point;
   /// Translates the widget pos to the coordinate system of `parent`.
   fn map_to_parent(&self, pos: Point) -> Point;
   /// Translates the widget pos from the coordinate system of parent to this
   /// widget system.
   fn map_from_parent(&self, pos: Point) -> Point;
-  /// Translates the widget pos to the coordinate system of `w`.
+  /// Translates the widget pos to the coordinate system of `w`, `w` is usually
+  /// an ancestor of the widget, but any widget in the same window works.
   fn map_to(&self, pos: Point, w: WidgetId) -> Point;
   /// Translates the widget pos from the coordinate system of `w` to this widget
   /// system.
@@ -89,6 +90,11 @@ pub trait WidgetCtx {
   // Query a write reference to the `T` if a writer of `T` is shared within the
   // widget `w`.
   fn query_write_of_widget<T: 'static>(&self, w: WidgetId) -> Option<WriteRef<T>>;
+  /// Return if the widget or any of its ancestors is marked as holding
+  /// sensitive content, see [`MixFlags::is_sensitive`].
+  ///
+  /// [`MixFlags::is_sensitive`]: crate::prelude::MixFlags::is_sensitive
+  fn is_sensitive(&self) -> bool;
   /// Retrieve the window associated with this context.
   fn window(&self) -> Sc<Window>;
 }
@@ -178,15 +184,9 @@ impl<T: WidgetCtxImpl> WidgetCtx for T {
 
   fn map_from_parent(&self, pos: Point) -> Point { self.tree().map_from_parent(self.id(), pos) }
 
-  fn map_to(&self, pos: Point, w: WidgetId) -> Point {
-    let global = self.map_to_global(pos);
-    self.tree().map_from_global(global, w)
-  }
+  fn map_to(&self, pos: Point, w: WidgetId) -> Point { self.tree().map_to(pos, self.id(), w) }
 
-  fn map_from(&self, pos: Point, w: WidgetId) -> Point {
-    let global = self.tree().map_to_global(pos, w);
-    self.map_from_global(global)
-  }
+  fn map_from(&self, pos: Point, w: WidgetId) -> Point { self.tree().map_to(pos, w, self.id()) }
 
   fn query_all_iter<Q: 'static>(&self) -> impl DoubleEndedIterator<Item = QueryRef<Q>> {
     self.id().query_all_iter(self.tree())
@@ -207,6 +207,8 @@ impl<T: WidgetCtxImpl> WidgetCtx for T {
     w.query_write(self.tree())
   }
 
+  fn is_sensitive(&self) -> bool { self.id().is_sensitive(self.tree()) }
+
   fn window(&self) -> Sc<Window> { self.tree().window() }
 }
 
@@ -307,6 +309,31 @@ mod tests {
     assert_eq!(w_ctx.map_to(to_pos, child), Point::new(40., 40.));
   }
 
+  #[test]
+  fn map_between_siblings() {
+    reset_test_env!();
+
+    let w = fn_widget! {
+      @MockMulti {
+        @MockBox { size: Size::new(20., 20.) }
+        @MockBox {
+          size: Size::new(40., 40.),
+          transform: Transform::scale(0.5, 0.5),
+        }
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+
+    let first = wnd.widget_id_by_path(&[0, 0]);
+    let second = wnd.widget_id_by_path(&[0, 1]);
+    // The second box is placed after the first one and scaled by half.
+    assert_eq!(wnd.map_to(Point::new(10., 10.), second, first), Point::new(25., 5.));
+    assert_eq!(wnd.map_to(Point::new(30., 10.), first, second), Point::new(20., 20.));
+    let rect = Rect::new(Point::new(20., 0.), Size::new(20., 20.));
+    assert_eq!(wnd.global_box_rect(second), Some(rect));
+  }
+
   fn get_single_child_by_depth(id: WidgetId, tree: &WidgetTree, mut depth: u32) -> WidgetId {
     let mut child = id;
     while depth > 0 {
@@ -2,7 +2,11 @@ use std::convert::Infallible;
 
 use rxrust::ops::box_it::BoxOp;
 
-use crate::{pipe::Pipe, prelude::BoxPipe, state::ModifyScope};
+use crate::{
+  pipe::Pipe,
+  prelude::BoxPipe,
+  state::{ModifyScope, TwoWay},
+};
 
 /// Trait used to create a widget declarer that can interact with the `BuildCtx`
 /// to create a widget.
@@ -34,6 +38,8 @@ pub trait DeclareInto<V, const M: usize> {
 pub enum DeclareInit<V> {
   Value(V),
   Pipe(BoxPipe<V>),
+  /// The value is bound to a state in two ways, see [`TwoWay`].
+  Bind(TwoWay<V>),
 }
 
 pub type ValueStream<V> = BoxOp<'static, (ModifyScope, V), Infallible>;
@@ -46,6 +52,26 @@ impl<V: 'static> DeclareInit<V> {
         let (v, pipe) = v.into_pipe().unzip(ModifyScope::DATA, None);
         (v, Some(pipe))
       }
+      Self::Bind(b) => {
+        let (v, from_state, _) = b.unzip();
+        (v, Some(from_state))
+      }
+    }
+  }
+
+  /// Like [`DeclareInit::unzip`], but also return the callback to write the
+  /// value back if the value is bound in two ways.
+  #[allow(clippy::type_complexity)]
+  pub fn unzip_bind(self) -> (V, Option<ValueStream<V>>, Option<Box<dyn Fn(&V)>>) {
+    match self {
+      Self::Bind(b) => {
+        let (v, from_state, to_state) = b.unzip();
+        (v, Some(from_state), Some(to_state))
+      }
+      other => {
+        let (v, pipe) = other.unzip();
+        (v, pipe, None)
+      }
     }
   }
 
@@ -56,6 +82,7 @@ impl<V: 'static> DeclareInit<V> {
     match self {
       Self::Value(v) => DeclareInit::Value(f(v)),
       Self::Pipe(v) => v.into_pipe().map(f).declare_into(),
+      Self::Bind(b) => DeclareInit::Bind(b.map(f)),
     }
   }
 }
@@ -70,6 +97,11 @@ impl<V> DeclareFrom<DeclareInit<V>, 0> for DeclareInit<V> {
   fn declare_from(value: DeclareInit<V>) -> Self { value }
 }
 
+impl<V> DeclareFrom<TwoWay<V>, 0> for DeclareInit<V> {
+  #[inline]
+  fn declare_from(value: TwoWay<V>) -> Self { Self::Bind(value) }
+}
+
 impl<V, U: From<V>> DeclareFrom<V, 1> for DeclareInit<U> {
   #[inline]
   fn declare_from(value: V) -> Self { Self::Value(value.into()) }
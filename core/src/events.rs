@@ -4,13 +4,13 @@ use self::dispatcher::DispatchInfo;
 use crate::{
   builtin_widgets::MixFlags,
   context::{WidgetCtx, WidgetCtxImpl},
-  prelude::ProviderCtx,
+  prelude::{ProviderCtx, Vector},
   query::QueryHandle,
   widget_tree::{WidgetId, WidgetTree},
 };
 
 pub(crate) mod dispatcher;
-pub use dispatcher::GrabPointer;
+pub use dispatcher::{FocusFollowsMouse, GrabPointer, WheelConfig};
 pub mod custom_event;
 pub use custom_event::*;
 mod pointers;
@@ -27,6 +27,18 @@ mod ime_pre_edit;
 pub use ime_pre_edit::*;
 mod lifecycle;
 pub use lifecycle::*;
+mod accessibility;
+pub use accessibility::*;
+mod drag_drop;
+pub use drag_drop::*;
+mod file_drop;
+pub use file_drop::*;
+mod touch;
+pub use touch::*;
+pub(crate) mod gesture;
+pub use gesture::{GesturePhase, LongPressEvent, MOUSE_SLOP, PanEvent, PinchEvent, TOUCH_SLOP};
+mod ui_event;
+pub use ui_event::*;
 
 pub(crate) mod focus_mgr;
 mod listener_impl_helper;
@@ -106,6 +118,12 @@ impl CommonEvent {
   #[inline]
   pub fn position(&self) -> Point { self.map_from_global(self.global_pos()) }
 
+  /// The relative motion of the pointer since the last pointer move. When the
+  /// pointer is locked, it's the only meaningful motion information because
+  /// the cursor stays still.
+  #[inline]
+  pub fn movement(&self) -> Vector { self.pick_info(DispatchInfo::movement) }
+
   /// The buttons being depressed (if any) in current state.
   #[inline]
   pub fn mouse_buttons(&self) -> MouseButtons { self.pick_info(DispatchInfo::mouse_buttons) }
@@ -124,6 +142,9 @@ pub enum Event {
   PerformedLayout(LifecycleEvent),
   /// Event fired when the widget is disposed. This event is fired only once.
   Disposed(LifecycleEvent),
+  /// Event fired when the window is resized, every widget listening to it
+  /// receives it, and the root is relayout with the new window size.
+  WindowResized(LifecycleEvent),
   PointerDown(PointerEvent),
   PointerDownCapture(PointerEvent),
   PointerUp(PointerEvent),
@@ -136,8 +157,50 @@ pub enum Event {
   PointerLeave(PointerEvent),
   Tap(PointerEvent),
   TapCapture(PointerEvent),
+  /// Event fired when the context menu is requested on the widget, by pressing
+  /// the right mouse button or the long press of a finger.
+  ContextMenu(PointerEvent),
   ImePreEdit(ImePreEditEvent),
   ImePreEditCapture(ImePreEditEvent),
+  /// Event fired when the accessibility subsystem requests a widget to perform
+  /// an action.
+  AccessibilityAction(AccessibilityActionEvent),
+  /// Event fired on the drag source when a drag starts from it, the handler
+  /// fills the payload of the drag.
+  DragStart(DragEvent),
+  /// Event fired on the drag source when the drag ends.
+  DragEnd(DragEvent),
+  /// Event fired on the drop target when a drag enters it.
+  DragEnter(DragEvent),
+  /// Event fired on the drop target when a drag moves over it.
+  DragOver(DragEvent),
+  /// Event fired on the drop target when a drag leaves it.
+  DragLeave(DragEvent),
+  /// Event fired on the drop target when a drag is dropped on it.
+  Drop(DragEvent),
+  /// Event fired when a file is dragged from the operating system over the
+  /// window.
+  FileHover(FileDropEvent),
+  /// Event fired for every hovered file when the drag of the files leaves the
+  /// window or is canceled.
+  FileHoverCancel(FileDropEvent),
+  /// Event fired when a file is dropped from the operating system into the
+  /// window.
+  FileDrop(FileDropEvent),
+  /// Event fired when a finger starts touching the screen.
+  TouchStart(TouchEvent),
+  /// Event fired when a finger moves on the screen.
+  TouchMove(TouchEvent),
+  /// Event fired when a finger is lifted from the screen.
+  TouchEnd(TouchEvent),
+  /// Event fired when the touch of a finger is canceled by the system.
+  TouchCancel(TouchEvent),
+  /// Event fired on the widget recognized the pan gesture.
+  Pan(PanEvent),
+  /// Event fired on the widget recognized the pinch gesture.
+  Pinch(PinchEvent),
+  /// Event fired on the widget recognized the long press gesture.
+  LongPress(LongPressEvent),
   /// Firing the wheel event when the user rotates a wheel button on a pointing
   /// device (typically a mouse).
   Wheel(WheelEvent),
@@ -189,6 +252,7 @@ impl std::ops::Deref for Event {
       Event::Mounted(e)
       | Event::PerformedLayout(e)
       | Event::Disposed(e)
+      | Event::WindowResized(e)
       | Event::Focus(e)
       | Event::Blur(e)
       | Event::FocusIn(e)
@@ -207,8 +271,21 @@ impl std::ops::Deref for Event {
       | Event::PointerLeave(e)
       | Event::Tap(e)
       | Event::TapCapture(e) => e,
+      Event::ContextMenu(e) => e,
       Event::ImePreEdit(e) | Event::ImePreEditCapture(e) => e,
       Event::Wheel(e) | Event::WheelCapture(e) => e,
+      Event::AccessibilityAction(e) => e,
+      Event::DragStart(e)
+      | Event::DragEnd(e)
+      | Event::DragEnter(e)
+      | Event::DragOver(e)
+      | Event::DragLeave(e)
+      | Event::Drop(e) => e,
+      Event::FileHover(e) | Event::FileHoverCancel(e) | Event::FileDrop(e) => e,
+      Event::TouchStart(e) | Event::TouchMove(e) | Event::TouchEnd(e) | Event::TouchCancel(e) => e,
+      Event::Pan(e) => e,
+      Event::Pinch(e) => e,
+      Event::LongPress(e) => e,
       Event::Chars(e) | Event::CharsCapture(e) => e,
       Event::KeyDown(e) | Event::KeyDownCapture(e) | Event::KeyUp(e) | Event::KeyUpCapture(e) => e,
       Event::CustomEvent(e) => e,
@@ -222,6 +299,7 @@ impl std::ops::DerefMut for Event {
       Event::Mounted(e)
       | Event::PerformedLayout(e)
       | Event::Disposed(e)
+      | Event::WindowResized(e)
       | Event::Focus(e)
       | Event::Blur(e)
       | Event::FocusIn(e)
@@ -240,8 +318,21 @@ impl std::ops::DerefMut for Event {
       | Event::PointerLeave(e)
       | Event::Tap(e)
       | Event::TapCapture(e) => e,
+      Event::ContextMenu(e) => e,
       Event::ImePreEdit(e) | Event::ImePreEditCapture(e) => e,
       Event::Wheel(e) | Event::WheelCapture(e) => e,
+      Event::AccessibilityAction(e) => e,
+      Event::DragStart(e)
+      | Event::DragEnd(e)
+      | Event::DragEnter(e)
+      | Event::DragOver(e)
+      | Event::DragLeave(e)
+      | Event::Drop(e) => e,
+      Event::FileHover(e) | Event::FileHoverCancel(e) | Event::FileDrop(e) => e,
+      Event::TouchStart(e) | Event::TouchMove(e) | Event::TouchEnd(e) | Event::TouchCancel(e) => e,
+      Event::Pan(e) => e,
+      Event::Pinch(e) => e,
+      Event::LongPress(e) => e,
       Event::Chars(e) | Event::CharsCapture(e) => e,
       Event::KeyDown(e) | Event::KeyDownCapture(e) | Event::KeyUp(e) | Event::KeyUpCapture(e) => e,
       Event::CustomEvent(e) => e,
@@ -253,6 +344,7 @@ impl Event {
   pub(crate) fn flags(&self) -> MixFlags {
     match self {
       Event::Mounted(_) | Event::PerformedLayout(_) | Event::Disposed(_) => MixFlags::Lifecycle,
+      Event::WindowResized(_) => MixFlags::WindowResized,
       Event::PointerDown(_)
       | Event::PointerDownCapture(_)
       | Event::PointerUp(_)
@@ -266,6 +358,19 @@ impl Event {
       | Event::Tap(_)
       | Event::TapCapture(_) => MixFlags::Pointer,
       Event::Wheel(_) | Event::WheelCapture(_) => MixFlags::Wheel,
+      Event::AccessibilityAction(_) => MixFlags::Accessibility,
+      Event::DragStart(_) | Event::DragEnd(_) => MixFlags::DragSource,
+      Event::DragEnter(_) | Event::DragOver(_) | Event::DragLeave(_) | Event::Drop(_) => {
+        MixFlags::DropTarget
+      }
+      Event::FileHover(_) | Event::FileHoverCancel(_) | Event::FileDrop(_) => MixFlags::FileDrop,
+      Event::TouchStart(_) | Event::TouchMove(_) | Event::TouchEnd(_) | Event::TouchCancel(_) => {
+        MixFlags::Touch
+      }
+      Event::ContextMenu(_) => MixFlags::ContextMenu,
+      Event::Pan(_) => MixFlags::Pan,
+      Event::Pinch(_) => MixFlags::Pinch,
+      Event::LongPress(_) => MixFlags::LongPress,
       Event::ImePreEdit(_)
       | Event::ImePreEditCapture(_)
       | Event::Chars(_)
@@ -0,0 +1,90 @@
+use crate::{impl_common_event_deref, prelude::*};
+
+/// The action that the accessibility subsystem requests a widget to perform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessibilityAction {
+  /// Perform the default action of the widget, it is the same as a tap if no
+  /// widget handles it.
+  Default,
+  /// Increase the value of the widget, like a slider.
+  Increment,
+  /// Decrease the value of the widget, like a slider.
+  Decrement,
+  /// Scroll the content forward by a page.
+  ScrollForward,
+  /// Scroll the content backward by a page.
+  ScrollBackward,
+}
+
+/// The event fired when the accessibility subsystem requests a widget to
+/// perform an action. It bubbles from the target widget to its ancestors, a
+/// widget that handles the action should stop its propagation.
+#[derive(Debug)]
+pub struct AccessibilityActionEvent {
+  pub action: AccessibilityAction,
+  pub common: CommonEvent,
+}
+
+impl_common_event_deref!(AccessibilityActionEvent);
+
+impl AccessibilityActionEvent {
+  #[inline]
+  pub fn new(action: AccessibilityAction, id: WidgetId, wnd: &Window) -> Self {
+    Self { action, common: CommonEvent::new(id, wnd.tree) }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[test]
+  fn bubble_action() {
+    reset_test_env!();
+
+    let (actions, w_actions) = split_value(vec![]);
+    let (inner, w_inner) = split_value(None);
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockBox {
+        size: INFINITY_SIZE,
+        on_accessibility_action: move |e| $w_actions.write().push(e.action),
+        @MockBox {
+          size: Size::new(10., 10.),
+          on_mounted: move |e| *$w_inner.write() = Some(e.current_target()),
+          on_accessibility_action: move |e| if e.action == AccessibilityAction::Increment {
+            e.stop_propagation();
+          },
+        }
+      }
+    });
+    wnd.draw_frame();
+
+    let id = inner.read().unwrap();
+    wnd.dispatch_accessibility_action(id, AccessibilityAction::Increment);
+    wnd.dispatch_accessibility_action(id, AccessibilityAction::ScrollForward);
+    wnd.run_frame_tasks();
+    assert_eq!(*actions.read(), vec![AccessibilityAction::ScrollForward]);
+  }
+
+  #[test]
+  fn default_action_fallback_to_tap() {
+    reset_test_env!();
+
+    let (taps, w_taps) = split_value(0);
+    let (inner, w_inner) = split_value(None);
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockBox {
+        size: Size::new(10., 10.),
+        on_mounted: move |e| *$w_inner.write() = Some(e.current_target()),
+        on_tap: move |_| *$w_taps.write() += 1,
+      }
+    });
+    wnd.draw_frame();
+
+    let id = inner.read().unwrap();
+    wnd.dispatch_accessibility_action(id, AccessibilityAction::Default);
+    wnd.run_frame_tasks();
+    assert_eq!(*taps.read(), 1);
+  }
+}
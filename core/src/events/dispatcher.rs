@@ -1,10 +1,17 @@
-use std::cell::RefCell;
+use std::{
+  cell::{Cell, RefCell},
+  path::PathBuf,
+};
 
-use winit::event::{DeviceId, ElementState, MouseButton, MouseScrollDelta, WindowEvent};
+use rxrust::prelude::*;
+use winit::event::{
+  DeviceId, ElementState, MouseButton, MouseScrollDelta, Touch, TouchPhase, WindowEvent,
+};
 
+use super::gesture::GestureRecognizer;
 use crate::{
   prelude::*,
-  window::{DelayEvent, WindowId},
+  window::{DelayEvent, DragKind, FileDropKind, PointerKind, TouchKind, WindowFlags, WindowId},
 };
 
 /// Grabs the pointer input.
@@ -37,7 +44,99 @@ pub(crate) struct Dispatcher {
   pub(crate) info: DispatchInfo,
   pub(crate) entered_widgets: Vec<WidgetId>,
   grab_mouse_wid: Sc<RefCell<Option<WidgetId>>>,
+  pointer_lock_wid: Cell<Option<WidgetId>>,
   pointer_down_wid: Option<WidgetId>,
+  focus_follows_mouse: FocusFollowsMouse,
+  /// The widget waiting to be focused by hovering and the timer of it.
+  hover_focus: Option<(WidgetId, TaskHandle<NormalReturn<()>>)>,
+  wheel_config: WheelConfig,
+  /// The timer of the next momentum wheel event.
+  wheel_momentum: Option<TaskHandle<NormalReturn<()>>>,
+  drag: Option<DragSession>,
+  /// The files dragged from the operating system over the window.
+  hovered_files: Vec<PathBuf>,
+  /// The fingers touching the screen, in the order they started.
+  touches: Vec<ActiveTouch>,
+  pub(crate) gesture: GestureRecognizer,
+}
+
+/// A finger touching the screen.
+#[derive(Debug, Clone)]
+pub(crate) struct ActiveTouch {
+  pub(crate) point: TouchPoint,
+  /// The relative motion of the finger of the last move.
+  pub(crate) movement: Vector,
+  /// Whether it's the first finger of the touches on the screen.
+  pub(crate) is_primary: bool,
+  /// The widget that the finger started touching.
+  down_wid: Option<WidgetId>,
+}
+
+/// The drag in progress, see [`Window::start_drag`].
+struct DragSession {
+  source: WidgetId,
+  data: DragData,
+  /// The drop target under the pointer.
+  target: Option<WidgetId>,
+  /// The overlay of the drag image, the position of the image and its offset
+  /// to the pointer.
+  image: Option<(Overlay, Stateful<Point>, Vector)>,
+}
+
+/// The policy that decides whether the pointer hovering over a focusable
+/// widget focuses it, see [`Window::set_focus_follows_mouse`].
+///
+/// A widget can allow or deny it by the `hover_focus` builtin field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FocusFollowsMouse {
+  /// The focus is not changed by hovering.
+  #[default]
+  Off,
+  /// Hovering over a focusable widget for the duration focuses it, unless it
+  /// denies by `hover_focus: false`.
+  All(Duration),
+  /// Hovering over a focusable widget for the duration focuses it, only if it
+  /// allows by `hover_focus: true`.
+  AllowList(Duration),
+}
+
+/// How the wheel deltas from the platform are normalized before being
+/// dispatched to the `on_wheel` listeners, see [`Window::set_wheel_config`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WheelConfig {
+  /// The factor multiplied to the wheel deltas.
+  pub multiplier: f32,
+  /// Invert the direction of the wheel deltas, the content follows the
+  /// fingers like on a touch screen.
+  pub natural_scrolling: bool,
+  /// The logical pixels of a line, for the wheels that only deliver line
+  /// deltas.
+  pub line_height: f32,
+  /// The decay of the velocity per step of the momentum synthesized for the
+  /// line deltas, in the range `(0, 1)`. A line delta is split into the
+  /// decaying wheel events, so the discrete wheels scroll smoothly, the total
+  /// delta is not changed. `None` dispatches a line delta at once.
+  pub momentum: Option<f32>,
+}
+
+/// The interval of the wheel events synthesized by the momentum.
+const MOMENTUM_INTERVAL: Duration = Duration::from_millis(16);
+/// The momentum stops when its delta is less than it, the remaining delta is
+/// dispatched at once.
+const MOMENTUM_MIN_DELTA: f32 = 0.5;
+
+impl Default for WheelConfig {
+  fn default() -> Self {
+    Self { multiplier: 1., natural_scrolling: false, line_height: 16., momentum: None }
+  }
+}
+
+impl WheelConfig {
+  /// Apply the multiplier and the direction to a delta in logical pixels.
+  pub fn normalize(&self, delta_x: f32, delta_y: f32) -> (f32, f32) {
+    let factor = if self.natural_scrolling { -self.multiplier } else { self.multiplier };
+    (delta_x * factor, delta_y * factor)
+  }
 }
 
 impl Dispatcher {
@@ -47,7 +146,34 @@ impl Dispatcher {
       info: <_>::default(),
       entered_widgets: vec![],
       grab_mouse_wid: Sc::new(RefCell::new(None)),
+      pointer_lock_wid: Cell::new(None),
       pointer_down_wid: None,
+      focus_follows_mouse: FocusFollowsMouse::Off,
+      hover_focus: None,
+      wheel_config: WheelConfig::default(),
+      wheel_momentum: None,
+      drag: None,
+      hovered_files: vec![],
+      touches: vec![],
+      gesture: GestureRecognizer::new(),
+    }
+  }
+
+  pub(crate) fn focus_follows_mouse(&self) -> FocusFollowsMouse { self.focus_follows_mouse }
+
+  pub(crate) fn set_focus_follows_mouse(&mut self, policy: FocusFollowsMouse) {
+    self.focus_follows_mouse = policy;
+    if policy == FocusFollowsMouse::Off {
+      self.cancel_hover_focus();
+    }
+  }
+
+  pub(crate) fn wheel_config(&self) -> WheelConfig { self.wheel_config }
+
+  pub(crate) fn set_wheel_config(&mut self, config: WheelConfig) {
+    self.wheel_config = config;
+    if config.momentum.is_none() {
+      self.stop_wheel_momentum();
     }
   }
 
@@ -60,6 +186,151 @@ impl Dispatcher {
     }
   }
 
+  /// Lock the pointer to the widget, return false if the pointer is already
+  /// locked by another widget.
+  pub(crate) fn lock_pointer(&self, wid: WidgetId) -> bool {
+    match self.pointer_lock_target() {
+      Some(locked) => locked == wid,
+      None => {
+        self.pointer_lock_wid.set(Some(wid));
+        self
+          .window()
+          .shell_wnd()
+          .borrow_mut()
+          .set_pointer_lock(true);
+        true
+      }
+    }
+  }
+
+  pub(crate) fn release_pointer_lock(&self) {
+    if self.pointer_lock_wid.take().is_some() {
+      self
+        .window()
+        .shell_wnd()
+        .borrow_mut()
+        .set_pointer_lock(false);
+    }
+  }
+
+  /// The widget that locked the pointer, the lock is released if the widget
+  /// is already disposed.
+  pub(crate) fn pointer_lock_target(&self) -> Option<WidgetId> {
+    let wid = self.pointer_lock_wid.get()?;
+    if wid.is_dropped(self.window().tree()) {
+      self.release_pointer_lock();
+      None
+    } else {
+      Some(wid)
+    }
+  }
+
+  pub(crate) fn start_drag(
+    &mut self, source: WidgetId, image: Option<(GenWidget, Vector)>,
+  ) -> bool {
+    if self.drag.is_some() {
+      return false;
+    }
+    let wnd = self.window();
+    let data = DragData::default();
+    let e = DelayEvent::Drag {
+      kind: DragKind::Start,
+      id: source,
+      source,
+      data: data.clone(),
+      dropped: false,
+    };
+    wnd.add_delay_event(e);
+
+    let image = image.map(|(image, offset)| {
+      let pos = Stateful::new(self.info.cursor_pos - offset);
+      let pos2 = pos.clone_watcher();
+      let overlay = Overlay::new(
+        move || {
+          let image = FatObj::new(image.gen_widget());
+          let pos = pos2.clone_watcher();
+          (fn_widget! {
+            @IgnorePointer {
+              @ $image { anchor: pipe!(Anchor::from_point(*$pos)) }
+            }
+          })
+          .into_widget()
+        },
+        OverlayStyle { auto_close_policy: AutoClosePolicy::NOT_AUTO_CLOSE, mask: None },
+      );
+      overlay.show(wnd.clone());
+      (overlay, pos, offset)
+    });
+    // The drop target is found by the next pointer move, it can't hit test here
+    // because it's usually called in an event handler.
+    self.drag = Some(DragSession { source, data, target: None, image });
+    true
+  }
+
+  pub(crate) fn is_dragging(&self) -> bool { self.drag.is_some() }
+
+  /// End the drag in progress, drop it on the target under the pointer if
+  /// `drop` is true.
+  pub(crate) fn end_drag(&mut self, drop: bool) {
+    let Some(DragSession { source, data, target, image }) = self.drag.take() else {
+      return;
+    };
+    let wnd = self.window();
+    let dropped = drop && target.is_some();
+    if let Some(id) = target {
+      let kind = if dropped { DragKind::Drop } else { DragKind::Leave };
+      wnd.add_delay_event(DelayEvent::Drag { kind, id, source, data: data.clone(), dropped });
+    }
+    let e = DelayEvent::Drag { kind: DragKind::End, id: source, source, data, dropped };
+    wnd.add_delay_event(e);
+    if let Some((overlay, ..)) = image {
+      overlay.close();
+    }
+  }
+
+  /// Move the drag to the pointer position, dispatch the events to the drop
+  /// targets the pointer enters, leaves or moves over.
+  fn drag_move(&mut self) {
+    if self.drag.is_none() {
+      return;
+    }
+    let wnd = self.window();
+    let tree = wnd.tree();
+    let target = self.hit_widget().and_then(|hit| {
+      hit.ancestors(tree).find(|id| {
+        id.query_all_iter::<MixBuiltin>(tree)
+          .any(|m| m.contain_flag(MixFlags::DropTarget))
+      })
+    });
+
+    let cursor_pos = self.info.cursor_pos;
+    let drag = self.drag.as_mut().unwrap();
+    if let Some((_, pos, offset)) = &drag.image {
+      *pos.write() = cursor_pos - *offset;
+    }
+    let DragSession { source, data, .. } = drag;
+    let event =
+      |kind, id| DelayEvent::Drag { kind, id, source: *source, data: data.clone(), dropped: false };
+    if drag.target != target {
+      if let Some(old) = drag.target {
+        wnd.add_delay_event(event(DragKind::Leave, old));
+      }
+      if let Some(new) = target {
+        wnd.add_delay_event(event(DragKind::Enter, new));
+      }
+    }
+    if let Some(target) = target {
+      wnd.add_delay_event(event(DragKind::Over, target));
+    }
+    drag.target = target;
+  }
+
+  fn capture_wid(&self) -> Option<WidgetId> {
+    self
+      .pointer_lock_target()
+      .or_else(|| *self.grab_mouse_wid.borrow())
+  }
+
   fn window(&self) -> Sc<Window> {
     AppCtx::get_window(self.wnd_id).expect("The window of the `Dispatcher` already dropped")
   }
@@ -70,6 +341,8 @@ pub(crate) struct DispatchInfo {
   mouse_button: (Option<DeviceId>, MouseButtons),
   /// The current global position (relative to window) of mouse
   cursor_pos: Point,
+  /// The relative motion of the mouse of the last move.
+  movement: Vector,
   /// The current state of the keyboard modifiers
   modifiers: ModifiersState,
 }
@@ -77,21 +350,69 @@ pub(crate) struct DispatchInfo {
 impl Dispatcher {
   pub fn dispatch(&mut self, event: WindowEvent, wnd_factor: f64) {
     match event {
-      WindowEvent::ModifiersChanged(s) => self.info.modifiers = s.state(),
+      WindowEvent::ModifiersChanged(s) => self.dispatch_modifiers(s.state()),
       WindowEvent::CursorMoved { position, .. } => {
         let pos = position.to_logical::<f32>(wnd_factor);
         self.cursor_move_to(Point::new(pos.x, pos.y))
       }
       WindowEvent::CursorLeft { .. } => self.on_cursor_left(),
       WindowEvent::MouseWheel { delta, .. } => self.dispatch_wheel(delta, wnd_factor),
+      WindowEvent::HoveredFile(path) => {
+        self.hovered_files.push(path.clone());
+        self.dispatch_file(FileDropKind::Hover, path);
+      }
+      WindowEvent::HoveredFileCancelled => {
+        for path in std::mem::take(&mut self.hovered_files) {
+          self.dispatch_file(FileDropKind::HoverCancel, path);
+        }
+      }
+      WindowEvent::DroppedFile(path) => {
+        self.hovered_files.retain(|p| p != &path);
+        self.dispatch_file(FileDropKind::Drop, path);
+      }
+      WindowEvent::Touch(touch) => self.dispatch_touch(touch, wnd_factor),
       _ => log::info!("not processed event {:?}", event),
     }
   }
 
+  pub fn dispatch_modifiers(&mut self, modifiers: ModifiersState) {
+    self.info.modifiers = modifiers;
+  }
+
   pub fn dispatch_keyboard_input(
     &mut self, physical_key: PhysicalKey, key: VirtualKey, is_repeat: bool, location: KeyLocation,
     state: ElementState,
   ) {
+    if cfg!(debug_assertions)
+      && physical_key == PhysicalKey::Code(KeyCode::KeyI)
+      && state == ElementState::Pressed
+      && self.info.modifiers == ModifiersState::CONTROL | ModifiersState::SHIFT
+    {
+      // The `Ctrl+Shift+I` is reserved to toggle the inspector in the debug build.
+      let wnd = self.window();
+      wnd.toggle_inspector();
+      if wnd.flags().contains(WindowFlags::INSPECTOR) {
+        wnd.inspect(self.hit_widget());
+      }
+      return;
+    }
+    if key == VirtualKey::Named(NamedKey::Escape)
+      && state == ElementState::Pressed
+      && self.pointer_lock_target().is_some()
+    {
+      // The `Escape` key is reserved to exit the pointer lock.
+      self.release_pointer_lock();
+      return;
+    }
+    if key == VirtualKey::Named(NamedKey::Escape)
+      && state == ElementState::Pressed
+      && self.drag.is_some()
+    {
+      // The `Escape` key is reserved to cancel the drag.
+      self.end_drag(false);
+      return;
+    }
+
     let wnd = self.window();
     if let Some(id) = wnd.focusing() {
       let e = DelayEvent::KeyBoard { key, state, physical_key, is_repeat, location, id };
@@ -118,7 +439,11 @@ impl Dispatcher {
   }
 
   fn cursor_press_down(&mut self, hit: Option<WidgetId>) {
-    let grab_pointer = *self.grab_mouse_wid.borrow();
+    let pos = self.info.cursor_pos;
+    self
+      .gesture
+      .pointer_down(PointerId::MOUSE, pos, hit, &self.window());
+    let grab_pointer = self.capture_wid();
     if let Some(grab_pointer) = grab_pointer {
       self
         .window()
@@ -136,13 +461,14 @@ impl Dispatcher {
 
   fn cursor_press_up(&mut self, hit: Option<WidgetId>) {
     let wnd = self.window();
-    let grab_pointer = *self.grab_mouse_wid.borrow();
+    let gestured = self.gesture.pointer_up(PointerId::MOUSE, &wnd);
+    let grab_pointer = self.capture_wid();
     if let Some(grab_pointer) = grab_pointer {
       wnd.add_delay_event(DelayEvent::GrabPointerUp(grab_pointer));
     } else {
       if let Some(hit) = hit {
         wnd.add_delay_event(DelayEvent::PointerUp(hit));
-        if let Some(wid) = self.pointer_down_wid {
+        if let Some(wid) = self.pointer_down_wid.filter(|_| !gestured) {
           if let Some(p) = wid.lowest_common_ancestor(hit, wnd.tree()) {
             wnd.add_delay_event(DelayEvent::Tap(p));
           }
@@ -150,10 +476,24 @@ impl Dispatcher {
       }
       self.pointer_down_wid = None;
     }
+    self.end_drag(true);
   }
 
   pub fn cursor_move_to(&mut self, position: Point) {
+    // The cursor is hidden and stays still when the pointer is locked, the
+    // motion is delivered by `dispatch_pointer_motion`.
+    if self.pointer_lock_target().is_some() {
+      return;
+    }
+    self.info.movement = position - self.info.cursor_pos;
     self.info.cursor_pos = position;
+    let wnd = self.window();
+    if wnd.flags().contains(WindowFlags::INSPECTOR) {
+      wnd.inspect(self.hit_widget());
+    }
+    self
+      .gesture
+      .pointer_move(PointerId::MOUSE, position, &self.window());
     let grab_pointer = *self.grab_mouse_wid.borrow();
     if let Some(grab_pointer) = grab_pointer {
       self
@@ -168,10 +508,22 @@ impl Dispatcher {
           .add_delay_event(DelayEvent::PointerMove(hit));
       }
     }
+    self.drag_move();
+  }
+
+  /// Dispatch the relative motion of the pointer to the widget that locked the
+  /// pointer, do nothing if the pointer is not locked.
+  pub fn dispatch_pointer_motion(&mut self, delta: Vector) {
+    if let Some(wid) = self.pointer_lock_target() {
+      self.info.movement = delta;
+      self
+        .window()
+        .add_delay_event(DelayEvent::GrabPointerMove(wid));
+    }
   }
 
   pub fn on_cursor_left(&mut self) {
-    if self.grab_mouse_wid.borrow().is_none() {
+    if self.capture_wid().is_none() {
       self.info.cursor_pos = Point::new(-1., -1.);
       self.pointer_enter_leave_dispatch(self.hit_widget());
     }
@@ -188,22 +540,13 @@ impl Dispatcher {
           // only the first button press emit event.
           if self.info.mouse_button.1 == button.into() {
             let hit = self.hit_widget();
-            let wnd = self.window();
-            let tree = wnd.tree();
-
-            let nearest_focus = hit.and_then(|wid| {
-              wid.ancestors(tree).find(|id| {
-                id.query_all_iter::<MixBuiltin>(tree)
-                  .any(|m| m.contain_flag(MixFlags::Focus))
-              })
-            });
-            if let Some(focus_id) = nearest_focus {
-              wnd.focus_mgr.borrow_mut().focus(focus_id, tree);
-            } else {
-              wnd.focus_mgr.borrow_mut().blur(tree);
-            }
-
+            self.focus_nearest(hit);
             self.cursor_press_down(hit);
+            if let (MouseButton::Right, Some(hit)) = (button, hit) {
+              self
+                .window()
+                .add_delay_event(DelayEvent::ContextMenu(hit));
+            }
           }
         }
         ElementState::Released => {
@@ -220,22 +563,215 @@ impl Dispatcher {
     }
   }
 
+  /// Focus the nearest focusable ancestor of the pressed widget, or blur the
+  /// focus if there isn't one.
+  fn focus_nearest(&self, hit: Option<WidgetId>) {
+    let wnd = self.window();
+    let tree = wnd.tree();
+
+    // The focus moved by the pointer doesn't show the focus ring.
+    wnd
+      .focus_mgr
+      .borrow_mut()
+      .set_focus_visible(false);
+    let nearest_focus = hit.and_then(|wid| {
+      wid.ancestors(tree).find(|id| {
+        id.query_all_iter::<MixBuiltin>(tree)
+          .any(|m| m.contain_flag(MixFlags::Focus))
+      })
+    });
+    if let Some(focus_id) = nearest_focus {
+      wnd.focus_mgr.borrow_mut().focus(focus_id, tree);
+    } else {
+      wnd.focus_mgr.borrow_mut().blur(tree);
+    }
+  }
+
+  /// Dispatch the touch of a finger as the pointer events of its own pointer,
+  /// and the touch events to the widget it started touching.
+  ///
+  /// The fingers don't hover, so they don't fire the enter and leave events.
+  /// Only the first finger focuses the widget and fires the tap.
+  pub fn dispatch_touch(&mut self, touch: Touch, wnd_factor: f64) {
+    let Touch { phase, location, force, id, .. } = touch;
+    let pos = location.to_logical::<f32>(wnd_factor);
+    let point = TouchPoint {
+      id: PointerId::from_touch(id),
+      global_pos: Point::new(pos.x, pos.y),
+      pressure: force.map_or(0.5, |f| f.normalized() as f32),
+    };
+
+    let wnd = self.window();
+    let capture = self.capture_wid();
+    let hit = self.hit_widget_at(point.global_pos);
+    let idx = self
+      .touches
+      .iter()
+      .position(|t| t.point.id == point.id);
+    let touch = match (phase, idx) {
+      (TouchPhase::Started, None) => {
+        self
+          .gesture
+          .pointer_down(point.id, point.global_pos, hit, &wnd);
+        let is_primary = self.touches.is_empty();
+        if is_primary {
+          self.focus_nearest(hit);
+        }
+        let touch = ActiveTouch { point, movement: Vector::zero(), is_primary, down_wid: hit };
+        self.touches.push(touch.clone());
+        touch
+      }
+      (TouchPhase::Moved, Some(idx)) => {
+        self
+          .gesture
+          .pointer_move(point.id, point.global_pos, &wnd);
+        let touch = &mut self.touches[idx];
+        touch.movement = point.global_pos - touch.point.global_pos;
+        touch.point = point;
+        touch.clone()
+      }
+      (TouchPhase::Ended | TouchPhase::Cancelled, Some(idx)) => {
+        let mut touch = self.touches.remove(idx);
+        touch.movement = point.global_pos - touch.point.global_pos;
+        touch.point = point;
+        touch
+      }
+      _ => return,
+    };
+
+    let (pointer, touch_kind) = match phase {
+      TouchPhase::Started => (PointerKind::Down, TouchKind::Start),
+      TouchPhase::Moved => (PointerKind::Move, TouchKind::Move),
+      TouchPhase::Ended => (PointerKind::Up, TouchKind::End),
+      TouchPhase::Cancelled => (PointerKind::Cancel, TouchKind::Cancel),
+    };
+    let gestured = matches!(phase, TouchPhase::Ended | TouchPhase::Cancelled)
+      && self.gesture.pointer_up(point.id, &wnd);
+    let tap = (phase == TouchPhase::Ended && touch.is_primary && capture.is_none() && !gestured)
+      .then(|| {
+        let (down, up) = (touch.down_wid?, hit?);
+        down.lowest_common_ancestor(up, wnd.tree())
+      })
+      .flatten();
+    let target = touch
+      .down_wid
+      .unwrap_or_else(|| wnd.tree().root());
+    let touches = self.touches.iter().map(|t| t.point).collect();
+    let (point, grab) = (touch.point, capture.is_some());
+    if let Some(id) = capture.or(hit) {
+      wnd.add_delay_event(DelayEvent::TouchPointer {
+        kind: pointer,
+        id,
+        grab,
+        touch: touch.clone(),
+      });
+    }
+    if let Some(id) = tap {
+      wnd.add_delay_event(DelayEvent::TouchPointer { kind: PointerKind::Tap, id, grab, touch });
+    }
+    wnd.add_delay_event(DelayEvent::Touch { kind: touch_kind, id: target, touch: point, touches });
+  }
+
+  /// The long press timer of the gestures is up, the long press of a finger
+  /// opens the context menu of the widget it pressed.
+  pub(crate) fn long_press_timeout(&mut self) {
+    let wnd = self.window();
+    if self.gesture.long_press_timeout(&wnd) {
+      if let Some(touch) = self.touches.first() {
+        if let Some(id) = touch.down_wid {
+          let touch = touch.clone();
+          let e =
+            DelayEvent::TouchPointer { kind: PointerKind::ContextMenu, id, grab: false, touch };
+          wnd.add_delay_event(e);
+        }
+      }
+    }
+  }
+
+  /// Dispatch the file event to the widget under the pointer, the pointer
+  /// position may not update during the drag on some platforms, so it's the
+  /// last known position. The root receives it if the pointer is out of the
+  /// window.
+  fn dispatch_file(&mut self, kind: FileDropKind, path: PathBuf) {
+    let wnd = self.window();
+    let id = self
+      .hit_widget()
+      .unwrap_or_else(|| wnd.tree().root());
+    wnd.add_delay_event(DelayEvent::FileDrop { kind, id, path });
+  }
+
   pub fn dispatch_wheel(&mut self, delta: MouseScrollDelta, wnd_factor: f64) {
-    if let Some(wid) = self.hit_widget() {
-      let (delta_x, delta_y) = match delta {
-        MouseScrollDelta::LineDelta(x, y) => (x * 16., y * 16.),
-        MouseScrollDelta::PixelDelta(delta) => {
-          let winit::dpi::LogicalPosition { x, y } = delta.to_logical(wnd_factor);
-          (x, y)
+    match delta {
+      MouseScrollDelta::LineDelta(x, y) => {
+        let line_height = self.wheel_config.line_height;
+        match self.wheel_config.momentum {
+          Some(decay) => {
+            self.stop_wheel_momentum();
+            let (x, y) = self
+              .wheel_config
+              .normalize(x * line_height, y * line_height);
+            let decay = decay.clamp(0., 0.99);
+            let (x, y) = (x * (1. - decay), y * (1. - decay));
+            self.emit_wheel(x, y);
+            self.schedule_wheel_momentum(x * decay, y * decay, decay);
+          }
+          None => self.dispatch_wheel_delta(x * line_height, y * line_height),
         }
-      };
+      }
+      MouseScrollDelta::PixelDelta(delta) => {
+        let winit::dpi::LogicalPosition { x, y } = delta.to_logical(wnd_factor);
+        self.dispatch_wheel_delta(x, y);
+      }
+    }
+  }
 
+  /// Dispatch the wheel delta in logical pixels to the widget under the
+  /// cursor, it's normalized by the [`WheelConfig`] of the window.
+  pub fn dispatch_wheel_delta(&mut self, delta_x: f32, delta_y: f32) {
+    self.stop_wheel_momentum();
+    let (delta_x, delta_y) = self.wheel_config.normalize(delta_x, delta_y);
+    self.emit_wheel(delta_x, delta_y);
+  }
+
+  fn emit_wheel(&self, delta_x: f32, delta_y: f32) {
+    if let Some(wid) = self.hit_widget() {
       self
         .window()
         .add_delay_event(DelayEvent::Wheel { id: wid, delta_x, delta_y });
     }
   }
 
+  /// Dispatch the decaying deltas of the momentum one by one, until the delta
+  /// is small enough to dispatch all the remaining at once.
+  fn schedule_wheel_momentum(&mut self, delta_x: f32, delta_y: f32, decay: f32) {
+    if delta_x.abs() < MOMENTUM_MIN_DELTA && delta_y.abs() < MOMENTUM_MIN_DELTA {
+      if delta_x != 0. || delta_y != 0. {
+        self.emit_wheel(delta_x / (1. - decay), delta_y / (1. - decay));
+      }
+      return;
+    }
+
+    let wnd_id = self.wnd_id;
+    let delta = (delta_x, delta_y);
+    let handle = observable::timer(delta, MOMENTUM_INTERVAL, AppCtx::scheduler()).subscribe(
+      move |(delta_x, delta_y): (f32, f32)| {
+        if let Some(wnd) = AppCtx::get_window(wnd_id) {
+          let mut dispatcher = wnd.dispatcher.borrow_mut();
+          dispatcher.wheel_momentum = None;
+          dispatcher.emit_wheel(delta_x, delta_y);
+          dispatcher.schedule_wheel_momentum(delta_x * decay, delta_y * decay, decay);
+        }
+      },
+    );
+    self.wheel_momentum = Some(handle);
+  }
+
+  fn stop_wheel_momentum(&mut self) {
+    if let Some(handle) = self.wheel_momentum.take() {
+      handle.unsubscribe();
+    }
+  }
+
   fn pointer_enter_leave_dispatch(&mut self, new_hit: Option<WidgetId>) {
     let wnd = self.window();
     let tree = wnd.tree();
@@ -257,9 +793,62 @@ impl Dispatcher {
     }
 
     self.entered_widgets = new_hit.map_or(vec![], |wid| wid.ancestors(tree).collect::<Vec<_>>());
+    self.schedule_hover_focus(new_hit, &wnd);
   }
 
-  fn hit_widget(&self) -> Option<WidgetId> {
+  /// Start a timer to focus the nearest focusable widget of the `hit` if the
+  /// `FocusFollowsMouse` policy allows it.
+  fn schedule_hover_focus(&mut self, hit: Option<WidgetId>, wnd: &Window) {
+    let (delay, allow_list) = match self.focus_follows_mouse {
+      FocusFollowsMouse::Off => return,
+      FocusFollowsMouse::All(delay) => (delay, false),
+      FocusFollowsMouse::AllowList(delay) => (delay, true),
+    };
+
+    let tree = wnd.tree();
+    let target = hit
+      .and_then(|wid| {
+        wid.ancestors(tree).find_map(|id| {
+          let flags = id
+            .query_all_iter::<MixBuiltin>(tree)
+            .fold(MixFlags::empty(), |acc, m| acc | *m.mix_flags().read());
+          flags
+            .contains(MixFlags::Focus)
+            .then(|| (id, flags.hover_focus()))
+        })
+      })
+      .filter(|(_, hover_focus)| hover_focus.unwrap_or(!allow_list))
+      .map(|(id, _)| id)
+      .filter(|id| wnd.focusing() != Some(*id));
+
+    if self.hover_focus.as_ref().map(|(id, _)| *id) == target {
+      return;
+    }
+    self.cancel_hover_focus();
+    if let Some(target) = target {
+      let wnd_id = self.wnd_id;
+      let handle = observable::timer(target, delay, AppCtx::scheduler()).subscribe(move |id| {
+        if let Some(wnd) = AppCtx::get_window(wnd_id) {
+          wnd.dispatcher.borrow_mut().hover_focus = None;
+          let tree = wnd.tree();
+          if !id.is_dropped(tree) {
+            wnd.focus_mgr.borrow_mut().focus(id, tree);
+          }
+        }
+      });
+      self.hover_focus = Some((target, handle));
+    }
+  }
+
+  fn cancel_hover_focus(&mut self) {
+    if let Some((_, handle)) = self.hover_focus.take() {
+      handle.unsubscribe();
+    }
+  }
+
+  fn hit_widget(&self) -> Option<WidgetId> { self.hit_widget_at(self.info.cursor_pos) }
+
+  fn hit_widget_at(&self, pos: Point) -> Option<WidgetId> {
     fn deepest_test(ctx: &mut HitTestCtx, pos: &mut Point) -> Option<WidgetId> {
       // Safety: The widget tree remains read-only throughout the entire hit testing
       // process.
@@ -274,7 +863,12 @@ impl Dispatcher {
           hit_target = Some(id);
         }
 
-        if hit || can_hit_child {
+        // A widget with a non-invertible transform, like scaling to zero, has
+        // nothing visible in it to hit.
+        let invertible = r
+          .get_transform()
+          .is_none_or(|t| t.inverse().is_some());
+        if (hit || can_hit_child) && invertible {
           if let Some(c) = id.last_child(tree) {
             *pos = ctx.map_from_parent(*pos);
             ctx.set_id(c);
@@ -289,7 +883,7 @@ impl Dispatcher {
     }
 
     let mut ctx = HitTestCtx::new(self.window().tree);
-    let mut pos = self.info.cursor_pos;
+    let mut pos = pos;
     let mut hit_target = deepest_test(&mut ctx, &mut pos);
 
     let (ctx, tree) = ctx.split_tree();
@@ -323,6 +917,9 @@ impl DispatchInfo {
 
   #[inline]
   pub fn mouse_buttons(&self) -> MouseButtons { self.mouse_button.1 }
+
+  #[inline]
+  pub fn movement(&self) -> Vector { self.movement }
 }
 
 #[cfg(test)]
@@ -651,6 +1248,71 @@ mod tests {
     assert_eq!(*click_path.read(), 1);
   }
 
+  #[test]
+  fn pointer_lock() {
+    reset_test_env!();
+
+    let (movements, w_movements) = split_value(vec![]);
+    let w = fn_widget! {
+      @MockMulti {
+        @MockBox {
+          size: Size::new(100., 100.),
+          on_pointer_down: move |e| assert!(e.request_pointer_lock()),
+          on_pointer_move: move |e| $w_movements.write().push(e.movement()),
+        }
+        @MockBox { size: Size::new(100., 100.) }
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(400., 400.));
+    wnd.draw_frame();
+    let is_locked = |wnd: &TestWindow| {
+      wnd
+        .shell_wnd()
+        .borrow()
+        .as_any()
+        .downcast_ref::<TestShellWindow>()
+        .unwrap()
+        .pointer_locked
+    };
+
+    let device_id = unsafe { DeviceId::dummy() };
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::CursorMoved {
+      device_id,
+      position: (50f64, 50f64).into(),
+    });
+    wnd.process_mouse_input(device_id, ElementState::Pressed, MouseButton::Left);
+    wnd.process_mouse_input(device_id, ElementState::Released, MouseButton::Left);
+    wnd.run_frame_tasks();
+    assert!(is_locked(&wnd));
+    assert!(wnd.pointer_lock_target().is_some());
+    let before = movements.read().len();
+
+    // The absolute cursor moving is ignored when the pointer is locked.
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::CursorMoved {
+      device_id,
+      position: (150f64, 50f64).into(),
+    });
+    wnd.process_mouse_motion(Vector::new(3., 4.));
+    wnd.run_frame_tasks();
+    assert_eq!(movements.read()[before..], [Vector::new(3., 4.)]);
+
+    wnd.processes_keyboard_event(
+      PhysicalKey::Code(KeyCode::Escape),
+      VirtualKey::Named(NamedKey::Escape),
+      false,
+      KeyLocation::Standard,
+      ElementState::Pressed,
+    );
+    assert!(!is_locked(&wnd));
+    assert!(wnd.pointer_lock_target().is_none());
+
+    wnd.process_mouse_motion(Vector::new(3., 4.));
+    wnd.run_frame_tasks();
+    assert_eq!(movements.read().len(), before + 1);
+  }
+
   #[test]
   fn focus_change_by_event() {
     reset_test_env!();
@@ -790,6 +1452,60 @@ mod tests {
     assert_eq!(dispatcher.hit_widget(), *expect_hit.read());
   }
 
+  #[test]
+  fn transform_with_padding_hit() {
+    reset_test_env!();
+    let (expect_hit, w_hit) = split_value(None);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(100., 100.),
+          padding: EdgeInsets::only_left(20.),
+          transform: Transform::scale(2., 2.),
+          @MockBox {
+            size: Size::new(10., 10.),
+            on_mounted: move |ctx| *$w_hit.write() = Some(ctx.id),
+          }
+        }
+      },
+      Size::new(500., 500.),
+    );
+    wnd.draw_frame();
+    let mut dispatcher = wnd.dispatcher.borrow_mut();
+    // The padding is scaled with the content.
+    dispatcher.info.cursor_pos = Point::new(50., 10.);
+    assert!(expect_hit.read().is_some());
+    assert_eq!(dispatcher.hit_widget(), *expect_hit.read());
+  }
+
+  #[test]
+  fn transform_in_overlay_hit() {
+    reset_test_env!();
+    let (expect_hit, w_hit) = split_value(None);
+    let overlay = Overlay::new(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(20., 20.),
+          transform: Transform::scale(2., 2.),
+          on_mounted: move |ctx| *$w_hit.write() = Some(ctx.id),
+        }
+      },
+      OverlayStyle { auto_close_policy: AutoClosePolicy::NOT_AUTO_CLOSE, mask: None },
+    );
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! { @MockBox { size: Size::new(500., 500.) } },
+      Size::new(500., 500.),
+    );
+    wnd.draw_frame();
+    overlay.show_at(Point::new(50., 50.), wnd.0.clone());
+    wnd.draw_frame();
+
+    let mut dispatcher = wnd.dispatcher.borrow_mut();
+    dispatcher.info.cursor_pos = Point::new(85., 85.);
+    assert!(expect_hit.read().is_some());
+    assert_eq!(dispatcher.hit_widget(), *expect_hit.read());
+  }
+
   #[test]
   fn fix_over_container_hit() {
     reset_test_env!();
@@ -810,4 +1526,110 @@ mod tests {
 
     assert_ne!(w.unwrap(), wnd.tree().root());
   }
+
+  #[test]
+  fn focus_follows_mouse() {
+    reset_test_env!();
+
+    let ids = Stateful::new(vec![]);
+    let c_ids = ids.clone_writer();
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockMulti {
+          @MockBox {
+            size: Size::new(100., 100.),
+            tab_index: 0_i16,
+            on_mounted: move |e| $c_ids.write().push(e.current_target()),
+          }
+          @MockBox {
+            size: Size::new(100., 100.),
+            tab_index: 0_i16,
+            hover_focus: false,
+            on_mounted: move |e| $c_ids.write().push(e.current_target()),
+          }
+          @MockBox {
+            size: Size::new(100., 100.),
+            tab_index: 0_i16,
+            hover_focus: true,
+            on_mounted: move |e| $c_ids.write().push(e.current_target()),
+          }
+        }
+      },
+      Size::new(300., 100.),
+    );
+    wnd.use_manual_clock();
+    wnd.draw_frame();
+    let [normal, _, allow] = ids.read()[..] else { panic!("three widgets expected") };
+    let delay = Duration::from_millis(100);
+    let hover = |wnd: &mut TestWindow, x: f32| {
+      wnd
+        .dispatcher
+        .borrow_mut()
+        .cursor_move_to(Point::new(x, 50.));
+      wnd.tick(Duration::ZERO);
+    };
+
+    // The policy is off by default.
+    hover(&mut wnd, 50.);
+    wnd.tick(delay);
+    assert_eq!(wnd.focusing(), None);
+
+    wnd.set_focus_follows_mouse(FocusFollowsMouse::All(delay));
+    hover(&mut wnd, 250.);
+    hover(&mut wnd, 50.);
+    wnd.tick(delay / 2);
+    assert_eq!(wnd.focusing(), None);
+    wnd.tick(delay);
+    assert_eq!(wnd.focusing(), Some(normal));
+
+    // Leaving before the delay cancels it, and the widget denied is not focused.
+    hover(&mut wnd, 250.);
+    wnd.tick(delay / 2);
+    hover(&mut wnd, 150.);
+    wnd.tick(delay * 2);
+    assert_eq!(wnd.focusing(), Some(normal));
+
+    wnd.set_focus_follows_mouse(FocusFollowsMouse::AllowList(delay));
+    hover(&mut wnd, 50.);
+    hover(&mut wnd, 250.);
+    wnd.tick(delay * 2);
+    assert_eq!(wnd.focusing(), Some(allow));
+    hover(&mut wnd, 50.);
+    wnd.tick(delay * 2);
+    assert_eq!(wnd.focusing(), Some(allow));
+  }
+
+  #[test]
+  fn transparent_to_pointer_below_threshold() {
+    reset_test_env!();
+
+    let (opacity, w_opacity) = split_value(1.);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(100., 100.),
+          opacity: pipe!(*$opacity),
+          opacity_hit_threshold: 0.5,
+          @MockBox { size: Size::new(50., 50.) }
+        }
+      },
+      Size::new(200., 200.),
+    );
+    wnd.draw_frame();
+    let root = wnd.tree().root();
+    let hit = |wnd: &TestWindow| {
+      let mut dispatcher = wnd.dispatcher.borrow_mut();
+      dispatcher.info.cursor_pos = Point::new(10., 10.);
+      dispatcher.hit_widget()
+    };
+    assert_ne!(hit(&wnd), Some(root));
+
+    *w_opacity.write() = 0.4;
+    wnd.draw_frame();
+    assert_eq!(hit(&wnd), Some(root));
+
+    *w_opacity.write() = 0.5;
+    wnd.draw_frame();
+    assert_ne!(hit(&wnd), Some(root));
+  }
 }
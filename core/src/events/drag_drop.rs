@@ -0,0 +1,146 @@
+use std::{
+  any::{Any, TypeId},
+  cell::RefCell,
+  rc::Rc,
+};
+
+use ahash::HashMap;
+
+use crate::{impl_common_event_deref, prelude::*};
+
+/// The payload of a drag, a map from the type to the value. The drag source
+/// fills it in `on_drag_start`, and the drop targets take the types they
+/// accept from it.
+///
+/// It's a shared reference, the clones are the same payload.
+#[derive(Clone, Default)]
+pub struct DragData(Rc<RefCell<HashMap<TypeId, Rc<dyn Any>>>>);
+
+/// The event of a drag, the `DragStart` and `DragEnd` are fired on the drag
+/// source, the others are fired on the drop target under the pointer.
+#[derive(Debug)]
+pub struct DragEvent {
+  /// The payload of the drag.
+  pub data: DragData,
+  /// The widget that the drag starts from.
+  pub source: WidgetId,
+  /// Whether the drag is dropped on a drop target, only meaningful in
+  /// `DragEnd`.
+  pub dropped: bool,
+  pub common: CommonEvent,
+}
+
+impl_common_event_deref!(DragEvent);
+
+impl DragData {
+  /// Insert a value to the payload, replace the value of the same type.
+  pub fn insert<T: Any>(&self, value: T) {
+    self
+      .0
+      .borrow_mut()
+      .insert(TypeId::of::<T>(), Rc::new(value));
+  }
+
+  /// Return the value of the type `T` in the payload.
+  pub fn get<T: Any>(&self) -> Option<Rc<T>> {
+    let v = self.0.borrow().get(&TypeId::of::<T>()).cloned()?;
+    v.downcast().ok()
+  }
+
+  /// Whether the payload has a value of the type `T`.
+  pub fn contains<T: Any>(&self) -> bool { self.0.borrow().contains_key(&TypeId::of::<T>()) }
+
+  /// Whether the payload is empty.
+  pub fn is_empty(&self) -> bool { self.0.borrow().is_empty() }
+}
+
+impl std::fmt::Debug for DragData {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("DragData")
+      .field("len", &self.0.borrow().len())
+      .finish()
+  }
+}
+
+impl DragEvent {
+  #[inline]
+  pub fn new(data: DragData, source: WidgetId, target: WidgetId, wnd: &Window) -> Self {
+    Self { data, source, dropped: false, common: CommonEvent::new(target, wnd.tree) }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use winit::event::{DeviceId, ElementState, MouseButton, WindowEvent};
+
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[test]
+  fn payload_by_type() {
+    let data = DragData::default();
+    assert!(data.is_empty());
+    data.insert(1u32);
+    data.insert("text");
+    assert_eq!(data.get::<u32>().as_deref(), Some(&1));
+    assert_eq!(data.get::<&str>().as_deref(), Some(&"text"));
+    assert!(!data.contains::<i32>());
+  }
+
+  #[test]
+  fn drag_to_target() {
+    reset_test_env!();
+
+    let (log, w_log) = split_value(vec![]);
+    let w_log2 = w_log.clone_writer();
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockMulti {
+        @Draggable {
+          drag_image: GenWidget::new(|| {
+            @MockBox { size: Size::new(5., 5.), background: Color::RED }.into_widget()
+          }),
+          on_drag_start: move |e| e.data.insert(7u32),
+          on_drag_end: move |e| $w_log.write().push(format!("end {}", e.dropped)),
+          @MockBox { size: Size::new(20., 20.) }
+        }
+        @MockBox {
+          size: Size::new(20., 20.),
+          on_drag_enter: move |_| $w_log2.write().push("enter".to_string()),
+          on_drag_leave: move |_| $w_log2.write().push("leave".to_string()),
+          on_drop: move |e| $w_log2.write().push(format!("drop {:?}", e.data.get::<u32>())),
+        }
+      }
+    });
+    wnd.draw_frame();
+
+    let device_id = unsafe { DeviceId::dummy() };
+    let move_to = |wnd: &mut TestWindow, x: f32| {
+      #[allow(deprecated)]
+      wnd.processes_native_event(WindowEvent::CursorMoved {
+        device_id,
+        position: (x as f64, 10.).into(),
+      });
+      wnd.run_frame_tasks();
+    };
+
+    move_to(&mut wnd, 5.);
+    wnd.process_mouse_input(device_id, ElementState::Pressed, MouseButton::Left);
+    wnd.run_frame_tasks();
+    // Not start the drag until the pointer moves over the threshold.
+    move_to(&mut wnd, 7.);
+    assert!(!wnd.is_dragging());
+    move_to(&mut wnd, 15.);
+    assert!(wnd.is_dragging());
+    // The drag image is painted.
+    wnd.draw_frame();
+    assert_eq!(wnd.take_last_frame().unwrap().commands.len(), 1);
+    move_to(&mut wnd, 30.);
+    move_to(&mut wnd, 45.);
+    move_to(&mut wnd, 35.);
+    wnd.process_mouse_input(device_id, ElementState::Released, MouseButton::Left);
+    wnd.run_frame_tasks();
+
+    assert!(!wnd.is_dragging());
+    assert_eq!(*log.read(), ["enter", "leave", "enter", "drop Some(7)", "end true"]);
+  }
+}
@@ -0,0 +1,72 @@
+use std::path::PathBuf;
+
+use crate::{impl_common_event_deref, prelude::*};
+
+/// The event of a file dragged from the operating system into the window, it
+/// bubbles from the widget under the pointer.
+///
+/// Every file of the drag fires its own event, and the position of the event
+/// is the last known position of the pointer in the window. If the pointer is
+/// out of the window, it bubbles from the root.
+#[derive(Debug)]
+pub struct FileDropEvent {
+  /// The path of the file.
+  pub path: PathBuf,
+  pub common: CommonEvent,
+}
+
+impl_common_event_deref!(FileDropEvent);
+
+impl FileDropEvent {
+  #[inline]
+  pub fn new(path: PathBuf, id: WidgetId, wnd: &Window) -> Self {
+    Self { path, common: CommonEvent::new(id, wnd.tree) }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use winit::event::WindowEvent;
+
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[test]
+  fn hover_and_drop_files() {
+    reset_test_env!();
+
+    let (log, w_log) = split_value(vec![]);
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        on_file_hover: move |e| $w_log.write().push(format!("hover {}", e.path.display())),
+        on_file_hover_cancel: move |e| {
+          $w_log.write().push(format!("cancel {}", e.path.display()))
+        },
+        on_file_drop: move |e| $w_log.write().push(format!("drop {}", e.path.display())),
+        @MockBox { size: Size::new(10., 10.) }
+      }
+    });
+    wnd.draw_frame();
+
+    #[allow(deprecated)]
+    let native = |e| {
+      wnd.processes_native_event(e);
+      wnd.run_frame_tasks();
+    };
+    native(WindowEvent::CursorMoved {
+      device_id: unsafe { winit::event::DeviceId::dummy() },
+      position: (5., 5.).into(),
+    });
+    native(WindowEvent::HoveredFile("a.txt".into()));
+    native(WindowEvent::HoveredFile("b.txt".into()));
+    native(WindowEvent::HoveredFileCancelled);
+    native(WindowEvent::HoveredFile("a.txt".into()));
+    native(WindowEvent::DroppedFile("a.txt".into()));
+
+    assert_eq!(
+      *log.read(),
+      ["hover a.txt", "hover b.txt", "cancel a.txt", "cancel b.txt", "hover a.txt", "drop a.txt"]
+    );
+  }
+}
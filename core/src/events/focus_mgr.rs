@@ -18,6 +18,9 @@ pub(crate) struct FocusManager {
   arena: Arena<FocusNodeInfo>,
   root: NodeId,
   wnd_id: WindowId,
+  /// Whether the focus was moved by the keyboard last time, the focus ring is
+  /// only painted in this case.
+  focus_visible: bool,
 }
 
 pub struct FocusHandle {
@@ -92,6 +95,7 @@ impl FocusManager {
       node_ids: ahash::HashMap::default(),
       arena,
       root,
+      focus_visible: false,
     }
   }
 
@@ -195,11 +199,23 @@ impl FocusManager {
     let mut scope_id = node_id
       .and_then(|id| self.scope_id(id))
       .or(Some(self.root));
+    if let Some(trap) = self.trap_scope() {
+      // Move into the trap scope if the focus is out of it or on its host.
+      let inside =
+        node_id.is_some_and(|id| id != trap && id.ancestors(&self.arena).any(|n| n == trap));
+      if !inside {
+        node_id = None;
+        scope_id = Some(trap);
+      }
+    }
     loop {
       scope_id?;
       let next = self.focus_step_in_scope(scope_id.unwrap(), node_id, backward);
       if let Some(id) = next {
         return self.get(id).and_then(|n| n.wid);
+      } else if self.is_trap_scope(scope_id.unwrap()) {
+        // Wrap around in the trap scope, or it has nothing to focus.
+        node_id.take()?;
       } else {
         node_id = scope_id;
         scope_id = self.scope_id(node_id.unwrap());
@@ -207,6 +223,23 @@ impl FocusManager {
     }
   }
 
+  /// The last trap scope in the tree, the tab navigation is contained in it.
+  fn trap_scope(&self) -> Option<NodeId> {
+    self
+      .root
+      .descendants(&self.arena)
+      .filter(|id| self.is_trap_scope(*id))
+      .last()
+  }
+
+  fn is_trap_scope(&self, node_id: NodeId) -> bool {
+    let node = self.assert_get(node_id);
+    node.has_focus_scope() && {
+      let scope = self.scope_property(node.wid);
+      scope.trap && !scope.skip_descendants
+    }
+  }
+
   fn collect_tab_index_in_scope(
     &self, scope_id: NodeId, backward: bool,
   ) -> Vec<(i16, NodeId, FocusType)> {
@@ -471,11 +504,13 @@ impl FocusManager {
 
 impl FocusManager {
   pub fn focus_next_widget(&mut self, tree: &WidgetTree) {
+    self.set_focus_visible(true);
     self.focus_move_circle(false);
     self.refresh_focus(tree);
   }
 
   pub fn focus_prev_widget(&mut self, tree: &WidgetTree) {
+    self.set_focus_visible(true);
     self.focus_move_circle(true);
     self.refresh_focus(tree);
   }
@@ -495,6 +530,18 @@ impl FocusManager {
   /// return the focusing widget.
   pub fn focusing(&self) -> Option<WidgetId> { self.focusing }
 
+  /// Return if the focus was moved by the keyboard last time.
+  pub(crate) fn focus_visible(&self) -> bool { self.focus_visible }
+
+  /// The focus is moved by the keyboard or the pointer, the focus ring is
+  /// shown only for the keyboard.
+  pub(crate) fn set_focus_visible(&mut self, visible: bool) {
+    if self.focus_visible != visible {
+      self.focus_visible = visible;
+      self.window().request_redraw();
+    }
+  }
+
   pub fn refresh_focus(&mut self, tree: &WidgetTree) {
     let new_focus = self.next_focus(tree);
     if self.focus_widgets.first() != new_focus.as_ref() {
@@ -541,6 +588,10 @@ impl FocusManager {
 
     self.focus_widgets = node.map_or(vec![], |wid| wid.ancestors(tree).collect::<Vec<_>>());
     self.focusing = node;
+    if self.focus_visible {
+      // The focus ring follows the focus.
+      wnd.request_redraw();
+    }
     old
   }
 }
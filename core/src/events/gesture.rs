@@ -0,0 +1,559 @@
+//! The gestures recognized from the pointer events.
+//!
+//! A gesture is recognized on the nearest widget listening to it, from the
+//! widget the first pointer pressed on up to the root, so a descendant takes
+//! precedence over its ancestors. Only one gesture is recognized at a time,
+//! until all the pointers are released:
+//!
+//! - The long press is recognized when the only pointer stays within the slop,
+//!   [`TOUCH_SLOP`] for a finger and [`MOUSE_SLOP`] for the mouse, for the
+//!   duration of [`Window::set_long_press_duration`]. The long press of a
+//!   finger also fires the context menu event.
+//! - The pan is recognized when the only pointer moves out of the slop before
+//!   the long press.
+//! - The pinch is recognized when a second pointer is pressed before the long
+//!   press, it ends the pan in progress.
+//!
+//! The pointer events are still fired during the gestures, but the tap is not
+//! fired if a gesture is recognized.
+
+use std::collections::VecDeque;
+
+use rxrust::prelude::*;
+
+use crate::{impl_common_event_deref, prelude::*, window::DelayEvent};
+
+/// The distance a finger moves in before it's considered as a pan, the same
+/// as the 8dp touch slop of Android. It's in logical pixels, so it's the same
+/// physical distance on the screens of any density.
+pub const TOUCH_SLOP: f32 = 8.;
+/// The distance the mouse moves in before it's considered as a pan, it's
+/// smaller than the [`TOUCH_SLOP`] because the mouse is more precise than a
+/// finger.
+pub const MOUSE_SLOP: f32 = 4.;
+/// The time range of the pointer moves to calculate the velocity of the pan.
+const VELOCITY_WINDOW: Duration = Duration::from_millis(100);
+
+/// The phase of a continuous gesture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GesturePhase {
+  /// The gesture is recognized.
+  Start,
+  /// The pointers of the gesture moved.
+  Update,
+  /// The pointers of the gesture are released.
+  End,
+}
+
+/// The event of the pan, a pointer pressed and moved.
+#[derive(Debug)]
+pub struct PanEvent {
+  pub phase: GesturePhase,
+  /// The pointer that pans.
+  pub pointer: PointerId,
+  /// The movement of the pointer since the last pan event.
+  pub delta: Vector,
+  /// The movement of the pointer since it's pressed.
+  pub translation: Vector,
+  /// The velocity of the pointer in logical pixels per second, measured from
+  /// its recent moves.
+  pub velocity: Vector,
+  pub common: CommonEvent,
+  global_pos: Point,
+}
+
+/// The event of the pinch, two pointers pressed and moved closer or apart.
+#[derive(Debug)]
+pub struct PinchEvent {
+  pub phase: GesturePhase,
+  /// The distance between the pointers relative to the start of the pinch,
+  /// it's greater than 1 if they moved apart.
+  pub scale: f32,
+  pub common: CommonEvent,
+  global_center: Point,
+}
+
+/// The event of the long press, a pointer pressed and held still.
+#[derive(Debug)]
+pub struct LongPressEvent {
+  /// The pointer that is held.
+  pub pointer: PointerId,
+  pub common: CommonEvent,
+  global_pos: Point,
+}
+
+impl_common_event_deref!(PanEvent);
+impl_common_event_deref!(PinchEvent);
+impl_common_event_deref!(LongPressEvent);
+
+impl PanEvent {
+  /// The X, Y coordinate of the pointer in global (window) coordinates.
+  #[inline]
+  pub fn global_pos(&self) -> Point { self.global_pos }
+
+  /// The X, Y coordinate of the pointer in current target widget.
+  #[inline]
+  pub fn position(&self) -> Point { self.map_from_global(self.global_pos) }
+}
+
+impl PinchEvent {
+  /// The center of the pointers in global (window) coordinates.
+  #[inline]
+  pub fn global_center(&self) -> Point { self.global_center }
+
+  /// The center of the pointers in current target widget.
+  #[inline]
+  pub fn center(&self) -> Point { self.map_from_global(self.global_center) }
+}
+
+impl LongPressEvent {
+  /// The X, Y coordinate of the pointer in global (window) coordinates.
+  #[inline]
+  pub fn global_pos(&self) -> Point { self.global_pos }
+
+  /// The X, Y coordinate of the pointer in current target widget.
+  #[inline]
+  pub fn position(&self) -> Point { self.map_from_global(self.global_pos) }
+}
+
+/// The gesture recognized, it's converted to the event when it's emitted.
+#[derive(Debug, Clone)]
+pub(crate) enum Gesture {
+  Pan {
+    phase: GesturePhase,
+    pointer: PointerId,
+    delta: Vector,
+    translation: Vector,
+    velocity: Vector,
+    pos: Point,
+  },
+  Pinch {
+    phase: GesturePhase,
+    scale: f32,
+    center: Point,
+  },
+  LongPress {
+    pointer: PointerId,
+    pos: Point,
+  },
+}
+
+impl Gesture {
+  pub(crate) fn into_event(self, id: WidgetId, wnd: &Window) -> Event {
+    let common = CommonEvent::new(id, wnd.tree);
+    match self {
+      Gesture::Pan { phase, pointer, delta, translation, velocity, pos } => Event::Pan(PanEvent {
+        phase,
+        pointer,
+        delta,
+        translation,
+        velocity,
+        common,
+        global_pos: pos,
+      }),
+      Gesture::Pinch { phase, scale, center } => {
+        Event::Pinch(PinchEvent { phase, scale, common, global_center: center })
+      }
+      Gesture::LongPress { pointer, pos } => {
+        Event::LongPress(LongPressEvent { pointer, common, global_pos: pos })
+      }
+    }
+  }
+}
+
+enum Recognized {
+  Pan {
+    translation: Vector,
+    samples: VecDeque<(Instant, Point)>,
+  },
+  Pinch {
+    start_span: f32,
+  },
+  LongPress,
+  /// The gesture is ended, nothing is recognized until all the pointers are
+  /// released.
+  Done,
+}
+
+/// Recognize the gestures from the pressed pointers, see the module
+/// documentation for the rules.
+pub(crate) struct GestureRecognizer {
+  long_press_duration: Duration,
+  /// The pressed pointers and their positions, in the order they're pressed.
+  pointers: Vec<(PointerId, Point)>,
+  /// The position the first pointer pressed at.
+  origin: Point,
+  pan_wid: Option<WidgetId>,
+  pinch_wid: Option<WidgetId>,
+  long_press_wid: Option<WidgetId>,
+  long_press: Option<TaskHandle<NormalReturn<()>>>,
+  recognized: Option<Recognized>,
+}
+
+impl GestureRecognizer {
+  pub(crate) fn new() -> Self {
+    Self {
+      long_press_duration: Duration::from_millis(500),
+      pointers: vec![],
+      origin: Point::zero(),
+      pan_wid: None,
+      pinch_wid: None,
+      long_press_wid: None,
+      long_press: None,
+      recognized: None,
+    }
+  }
+
+  pub(crate) fn long_press_duration(&self) -> Duration { self.long_press_duration }
+
+  pub(crate) fn set_long_press_duration(&mut self, duration: Duration) {
+    self.long_press_duration = duration;
+  }
+
+  pub(crate) fn pointer_down(
+    &mut self, pointer: PointerId, pos: Point, hit: Option<WidgetId>, wnd: &Window,
+  ) {
+    if self.pointers.is_empty() {
+      self.start_session(pointer, pos, hit, wnd);
+    } else if self.pointers.iter().any(|(id, _)| *id == pointer) {
+      return;
+    }
+    self.pointers.push((pointer, pos));
+
+    if self.pointers.len() == 2 && matches!(self.recognized, None | Some(Recognized::Pan { .. })) {
+      self.cancel_long_press();
+      if let Some(wid) = self.pinch_wid {
+        self.end_pan(wnd);
+        let start_span = self.span();
+        self.recognized = Some(Recognized::Pinch { start_span });
+        let center = self.centroid();
+        let gesture = Gesture::Pinch { phase: GesturePhase::Start, scale: 1., center };
+        wnd.add_delay_event(DelayEvent::Gesture { id: wid, gesture });
+      }
+    }
+  }
+
+  pub(crate) fn pointer_move(&mut self, pointer: PointerId, pos: Point, wnd: &Window) {
+    let Some(idx) = self
+      .pointers
+      .iter()
+      .position(|(id, _)| *id == pointer)
+    else {
+      return;
+    };
+    let last = std::mem::replace(&mut self.pointers[idx].1, pos);
+
+    match &mut self.recognized {
+      None if self.pointers.len() == 1 && (pos - self.origin).length() > slop(pointer) => {
+        self.cancel_long_press();
+        if let Some(wid) = self.pan_wid {
+          let translation = pos - self.origin;
+          let samples =
+            VecDeque::from([(FrameClock::now(), self.origin), (FrameClock::now(), pos)]);
+          self.recognized = Some(Recognized::Pan { translation, samples });
+          let gesture = Gesture::Pan {
+            phase: GesturePhase::Start,
+            pointer,
+            delta: translation,
+            translation,
+            velocity: Vector::zero(),
+            pos,
+          };
+          wnd.add_delay_event(DelayEvent::Gesture { id: wid, gesture });
+        }
+      }
+      Some(Recognized::Pan { translation, samples }) if idx == 0 => {
+        let delta = pos - last;
+        *translation += delta;
+        let now = FrameClock::now();
+        samples.push_back((now, pos));
+        while samples.len() > 2 && now - samples[0].0 > VELOCITY_WINDOW {
+          samples.pop_front();
+        }
+        let gesture = Gesture::Pan {
+          phase: GesturePhase::Update,
+          pointer,
+          delta,
+          translation: *translation,
+          velocity: velocity(samples),
+          pos,
+        };
+        let id = self.pan_wid.unwrap();
+        wnd.add_delay_event(DelayEvent::Gesture { id, gesture });
+      }
+      Some(Recognized::Pinch { start_span }) if idx < 2 => {
+        let start_span = *start_span;
+        let scale = if start_span > 0. { self.span() / start_span } else { 1. };
+        let gesture =
+          Gesture::Pinch { phase: GesturePhase::Update, scale, center: self.centroid() };
+        let id = self.pinch_wid.unwrap();
+        wnd.add_delay_event(DelayEvent::Gesture { id, gesture });
+      }
+      _ => {}
+    }
+  }
+
+  /// Release the pointer, return whether a gesture is recognized since the
+  /// first pointer pressed, the tap should not be fired if so.
+  pub(crate) fn pointer_up(&mut self, pointer: PointerId, wnd: &Window) -> bool {
+    let Some(idx) = self
+      .pointers
+      .iter()
+      .position(|(id, _)| *id == pointer)
+    else {
+      return false;
+    };
+
+    match self.recognized {
+      Some(Recognized::Pan { .. }) if idx == 0 => self.end_pan(wnd),
+      Some(Recognized::Pinch { start_span }) if idx < 2 => {
+        let scale = if start_span > 0. { self.span() / start_span } else { 1. };
+        let gesture = Gesture::Pinch { phase: GesturePhase::End, scale, center: self.centroid() };
+        let id = self.pinch_wid.unwrap();
+        wnd.add_delay_event(DelayEvent::Gesture { id, gesture });
+        self.recognized = Some(Recognized::Done);
+      }
+      _ => {}
+    }
+    self.pointers.remove(idx);
+    let recognized = self.recognized.is_some();
+    if self.pointers.is_empty() {
+      self.cancel_long_press();
+      self.recognized = None;
+    }
+    recognized
+  }
+
+  fn start_session(&mut self, pointer: PointerId, pos: Point, hit: Option<WidgetId>, wnd: &Window) {
+    self.cancel_long_press();
+    self.recognized = None;
+    self.origin = pos;
+    let tree = wnd.tree();
+    let nearest = |flag| {
+      hit.and_then(|hit| {
+        hit.ancestors(tree).find(|id| {
+          id.query_all_iter::<MixBuiltin>(tree)
+            .any(|m| m.contain_flag(flag))
+        })
+      })
+    };
+    self.pan_wid = nearest(MixFlags::Pan);
+    self.pinch_wid = nearest(MixFlags::Pinch);
+    self.long_press_wid = nearest(MixFlags::LongPress);
+    let context_menu = pointer != PointerId::MOUSE && nearest(MixFlags::ContextMenu).is_some();
+    if self.long_press_wid.is_some() || context_menu {
+      let wnd_id = wnd.id();
+      let handle =
+        observable::timer((), self.long_press_duration, AppCtx::scheduler()).subscribe(move |_| {
+          if let Some(wnd) = AppCtx::get_window(wnd_id) {
+            wnd.dispatcher.borrow_mut().long_press_timeout();
+          }
+        });
+      self.long_press = Some(handle);
+    }
+  }
+
+  /// Recognize the long press when its timer is up, return whether it's
+  /// recognized.
+  pub(crate) fn long_press_timeout(&mut self, wnd: &Window) -> bool {
+    self.long_press = None;
+    let [(pointer, pos)] = self.pointers[..] else {
+      return false;
+    };
+    if self.recognized.is_some() {
+      return false;
+    }
+    self.recognized = Some(Recognized::LongPress);
+    if let Some(id) = self
+      .long_press_wid
+      .filter(|id| !id.is_dropped(wnd.tree()))
+    {
+      let gesture = Gesture::LongPress { pointer, pos };
+      wnd.add_delay_event(DelayEvent::Gesture { id, gesture });
+    }
+    true
+  }
+
+  fn end_pan(&mut self, wnd: &Window) {
+    if let Some(Recognized::Pan { translation, samples }) = self.recognized.take() {
+      let gesture = Gesture::Pan {
+        phase: GesturePhase::End,
+        pointer: self.pointers[0].0,
+        delta: Vector::zero(),
+        translation,
+        velocity: velocity(&samples),
+        pos: self.pointers[0].1,
+      };
+      let id = self.pan_wid.unwrap();
+      wnd.add_delay_event(DelayEvent::Gesture { id, gesture });
+      self.recognized = Some(Recognized::Done);
+    }
+  }
+
+  fn cancel_long_press(&mut self) {
+    if let Some(handle) = self.long_press.take() {
+      handle.unsubscribe();
+    }
+  }
+
+  /// The center of the first two pointers.
+  fn centroid(&self) -> Point {
+    let mut pointers = self.pointers.iter().take(2).map(|(_, p)| *p);
+    let first = pointers.next().unwrap_or_default();
+    pointers
+      .next()
+      .map_or(first, |second| first.lerp(second, 0.5))
+  }
+
+  /// The distance between the first two pointers.
+  fn span(&self) -> f32 {
+    match &self.pointers[..] {
+      [(_, a), (_, b), ..] => (*a - *b).length(),
+      _ => 0.,
+    }
+  }
+}
+
+fn slop(pointer: PointerId) -> f32 {
+  if pointer == PointerId::MOUSE { MOUSE_SLOP } else { TOUCH_SLOP }
+}
+
+fn velocity(samples: &VecDeque<(Instant, Point)>) -> Vector {
+  let (Some((t0, p0)), Some((t1, p1))) = (samples.front(), samples.back()) else {
+    return Vector::zero();
+  };
+  let secs = (*t1 - *t0).as_secs_f32();
+  if secs > 0. { (*p1 - *p0) / secs } else { Vector::zero() }
+}
+
+#[cfg(test)]
+mod tests {
+  use winit::event::{DeviceId, Touch, TouchPhase, WindowEvent};
+
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  fn touch(wnd: &mut TestWindow, id: u64, phase: TouchPhase, x: f64, y: f64) {
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::Touch(Touch {
+      device_id: unsafe { DeviceId::dummy() },
+      phase,
+      location: (x, y).into(),
+      force: None,
+      id,
+    }));
+    wnd.tick(Duration::from_millis(10));
+  }
+
+  #[test]
+  fn pan_and_pinch() {
+    reset_test_env!();
+
+    let (log, w_log) = split_value(vec![]);
+    let w_log2 = w_log.clone_writer();
+    let w_log3 = w_log.clone_writer();
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        on_pinch: move |e| $w_log.write().push(format!("pinch {:?} {}", e.phase, e.scale)),
+        on_tap: move |_| $w_log2.write().push("tap".to_string()),
+        @MockBox {
+          size: Size::new(100., 100.),
+          on_pan: move |e| {
+            let v = if e.velocity.x > 0. { "+" } else { "0" };
+            $w_log3.write().push(format!("pan {:?} {:?} {v}", e.phase, e.translation.to_tuple()));
+          },
+        }
+      }
+    });
+    wnd.draw_frame();
+
+    // Moving in the slop is not a pan.
+    touch(&mut wnd, 1, TouchPhase::Started, 10., 10.);
+    touch(&mut wnd, 1, TouchPhase::Moved, 15., 10.);
+    touch(&mut wnd, 1, TouchPhase::Ended, 15., 10.);
+    assert_eq!(*log.read(), ["tap"]);
+
+    touch(&mut wnd, 1, TouchPhase::Started, 10., 10.);
+    touch(&mut wnd, 1, TouchPhase::Moved, 20., 10.);
+    touch(&mut wnd, 1, TouchPhase::Moved, 30., 10.);
+    // The second finger ends the pan and starts the pinch on the ancestor.
+    touch(&mut wnd, 2, TouchPhase::Started, 50., 10.);
+    touch(&mut wnd, 2, TouchPhase::Moved, 70., 10.);
+    touch(&mut wnd, 2, TouchPhase::Ended, 70., 10.);
+    touch(&mut wnd, 1, TouchPhase::Moved, 40., 10.);
+    touch(&mut wnd, 1, TouchPhase::Ended, 40., 10.);
+
+    assert_eq!(
+      log.read()[1..],
+      [
+        "pan Start (10.0, 0.0) 0",
+        "pan Update (20.0, 0.0) +",
+        "pan End (20.0, 0.0) +",
+        "pinch Start 1",
+        "pinch Update 2",
+        "pinch End 2",
+      ]
+    );
+  }
+
+  #[test]
+  fn slop_by_pointer() {
+    reset_test_env!();
+
+    let (log, w_log) = split_value(vec![]);
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        on_pan: move |e| $w_log.write().push(format!("pan {:?}", e.phase)),
+      }
+    });
+    wnd.draw_frame();
+
+    // A finger moving 6 pixels is still in the slop.
+    touch(&mut wnd, 1, TouchPhase::Started, 10., 10.);
+    touch(&mut wnd, 1, TouchPhase::Moved, 16., 10.);
+    touch(&mut wnd, 1, TouchPhase::Ended, 16., 10.);
+    assert!(log.read().is_empty());
+
+    // But the mouse is more precise, it pans.
+    wnd.process_input(TestInput::CursorMove(Point::new(10., 10.)));
+    wnd.process_input(TestInput::MousePress(MouseButton::Left));
+    wnd.process_input(TestInput::CursorMove(Point::new(16., 10.)));
+    wnd.process_input(TestInput::MouseRelease(MouseButton::Left));
+    wnd.draw_frame();
+    assert_eq!(*log.read(), ["pan Start", "pan End"]);
+  }
+
+  #[test]
+  fn long_press() {
+    reset_test_env!();
+
+    let (log, w_log) = split_value(vec![]);
+    let w_log2 = w_log.clone_writer();
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        on_long_press: move |e| $w_log.write().push(format!("long press {:?}", e.position())),
+        on_pan: move |e| $w_log2.write().push(format!("pan {:?}", e.phase)),
+      }
+    });
+    wnd.set_long_press_duration(Duration::from_millis(100));
+    wnd.draw_frame();
+
+    // Moving out of the slop cancels the long press.
+    touch(&mut wnd, 1, TouchPhase::Started, 10., 10.);
+    touch(&mut wnd, 1, TouchPhase::Moved, 30., 10.);
+    wnd.tick(Duration::from_millis(200));
+    touch(&mut wnd, 1, TouchPhase::Ended, 30., 10.);
+    assert_eq!(*log.read(), ["pan Start", "pan End"]);
+
+    // No pan after the long press.
+    touch(&mut wnd, 1, TouchPhase::Started, 10., 10.);
+    touch(&mut wnd, 1, TouchPhase::Moved, 12., 10.);
+    wnd.tick(Duration::from_millis(100));
+    touch(&mut wnd, 1, TouchPhase::Moved, 40., 10.);
+    touch(&mut wnd, 1, TouchPhase::Ended, 40., 10.);
+    assert_eq!(log.read()[2..], ["long press (12.0, 10.0)"]);
+  }
+}
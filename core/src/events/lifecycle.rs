@@ -1,6 +1,7 @@
 use super::*;
 
-/// The event fired when the widget is mounted, performed layout or disposed.
+/// The event fired when the widget is mounted, performed layout, disposed or
+/// the window is resized.
 pub type LifecycleEvent = CommonEvent;
 
 #[cfg(test)]
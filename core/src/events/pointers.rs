@@ -1,7 +1,13 @@
+use ribir_geom::{Point, Vector};
+
 use super::CommonEvent;
-use crate::impl_common_event_deref;
+use crate::{context::WidgetCtx, impl_common_event_deref, widget_tree::WidgetId};
 mod from_mouse;
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+mod from_touch;
+
+/// The identifier of a pointer. The mouse is always the same pointer, and
+/// every finger touching the screen is a different pointer until it's lifted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct PointerId(usize);
 
 /// The pointer is a hardware-agnostic device that can target a specific set of
@@ -47,6 +53,9 @@ pub struct PointerEvent {
   pub is_primary: bool,
 
   pub common: CommonEvent,
+  global_pos: Point,
+  movement: Vector,
+  buttons: MouseButtons,
 }
 
 bitflags! {
@@ -75,13 +84,61 @@ pub enum PointerType {
   Touch,
 }
 
+impl PointerEvent {
+  /// The X, Y coordinate of the pointer in global (window) coordinates.
+  ///
+  /// Every pointer has its own position, so it's the position of this event's
+  /// pointer, not the mouse cursor.
+  #[inline]
+  pub fn global_pos(&self) -> Point { self.global_pos }
+
+  /// The X, Y coordinate of the pointer in current target widget.
+  #[inline]
+  pub fn position(&self) -> Point { self.map_from_global(self.global_pos) }
+
+  /// The X, Y coordinate of the pointer in the widget `w`, `w` can be any
+  /// widget of the window, not only the ancestors of the target.
+  pub fn position_in(&self, w: WidgetId) -> Point {
+    self.window().map_from_global(self.global_pos, w)
+  }
+
+  /// The relative motion of the pointer since its last move.
+  #[inline]
+  pub fn movement(&self) -> Vector { self.movement }
+
+  /// The buttons being depressed (if any) of the pointer. A touch is
+  /// considered as the primary button pressed.
+  #[inline]
+  pub fn mouse_buttons(&self) -> MouseButtons { self.buttons }
+
+  /// The button number that was pressed (if applicable) when the event was
+  /// fired.
+  #[inline]
+  pub fn button_num(&self) -> u32 { self.buttons.bits().count_ones() }
+
+  /// Lock the pointer to the current target widget. The cursor is hidden and
+  /// the widget receives all the pointer events with the relative motion in
+  /// `movement()`, until the lock is released by pressing `Escape`, calling
+  /// `exit_pointer_lock`, or the widget is disposed.
+  ///
+  /// Return false if the pointer is already locked by another widget.
+  pub fn request_pointer_lock(&self) -> bool {
+    self
+      .window()
+      .request_pointer_lock(self.current_target())
+  }
+
+  /// Release the pointer lock, if any.
+  pub fn exit_pointer_lock(&self) { self.window().exit_pointer_lock() }
+}
+
 impl_common_event_deref!(PointerEvent);
 #[cfg(test)]
 mod tests {
 
   use winit::{
     dpi::LogicalPosition,
-    event::{DeviceId, ElementState, MouseButton, WindowEvent},
+    event::{DeviceId, ElementState, MouseButton, Touch, TouchPhase, WindowEvent},
   };
 
   use crate::{prelude::*, reset_test_env, test_helper::*};
@@ -137,4 +194,45 @@ mod tests {
     assert_eq!(*tap.read(), 2);
     assert!(*focused.read());
   }
+
+  #[test]
+  fn context_menu() {
+    reset_test_env!();
+
+    let (log, w_log) = split_value(vec![]);
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        on_context_menu: move |e| {
+          $w_log.write().push(format!("{:?} {:?}", e.point_type, e.position()))
+        },
+      }
+    });
+    wnd.set_long_press_duration(Duration::from_millis(100));
+    wnd.draw_frame();
+
+    let device_id = unsafe { DeviceId::dummy() };
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::CursorMoved { device_id, position: (10., 10.).into() });
+    wnd.process_mouse_input(device_id, ElementState::Pressed, MouseButton::Right);
+    wnd.process_mouse_input(device_id, ElementState::Released, MouseButton::Right);
+    wnd.draw_frame();
+
+    #[allow(deprecated)]
+    let touch = |wnd: &mut TestWindow, phase| {
+      wnd.processes_native_event(WindowEvent::Touch(Touch {
+        device_id,
+        phase,
+        location: (20., 20.).into(),
+        force: None,
+        id: 0,
+      }));
+      wnd.tick(Duration::ZERO);
+    };
+    touch(&mut wnd, TouchPhase::Started);
+    wnd.tick(Duration::from_millis(100));
+    touch(&mut wnd, TouchPhase::Ended);
+
+    assert_eq!(*log.read(), ["Mouse (10.0, 10.0)", "Touch (20.0, 20.0)"]);
+  }
 }
@@ -3,17 +3,21 @@ use winit::event::MouseButton;
 use super::PointerId;
 use crate::prelude::*;
 
+impl PointerId {
+  /// The pointer of the mouse.
+  pub(crate) const MOUSE: PointerId = PointerId(0);
+
+  /// Return if it's the pointer of the mouse, otherwise it's a finger.
+  pub fn is_mouse(&self) -> bool { *self == Self::MOUSE }
+}
+
 impl PointerEvent {
   pub(crate) fn from_mouse(target: WidgetId, wnd: &Window) -> Self {
-    let no_button = wnd
-      .dispatcher
-      .borrow()
-      .info
-      .mouse_buttons()
-      .is_empty();
+    let dispatcher = wnd.dispatcher.borrow();
+    let buttons = dispatcher.info.mouse_buttons();
+    let no_button = buttons.is_empty();
     PointerEvent {
-      // todo: we need to trace the pressed pointer, how to generate pointer id, by device + button?
-      id: PointerId(0),
+      id: PointerId::MOUSE,
       width: 1.0,
       height: 1.0,
       pressure: if no_button { 0. } else { 0.5 },
@@ -23,6 +27,9 @@ impl PointerEvent {
       point_type: PointerType::Mouse,
       is_primary: true,
       common: CommonEvent::new(target, wnd.tree),
+      global_pos: dispatcher.info.global_pos(),
+      movement: dispatcher.info.movement(),
+      buttons,
     }
   }
 }
@@ -0,0 +1,31 @@
+use super::PointerId;
+use crate::{events::dispatcher::ActiveTouch, prelude::*};
+
+impl PointerId {
+  /// The pointer of a finger, the `id` is unique among the fingers touching
+  /// the screen.
+  pub(crate) fn from_touch(id: u64) -> Self { PointerId(id as usize + 1) }
+}
+
+impl PointerEvent {
+  pub(crate) fn from_touch(
+    touch: &ActiveTouch, pressed: bool, target: WidgetId, wnd: &Window,
+  ) -> Self {
+    let TouchPoint { id, global_pos, pressure } = touch.point;
+    PointerEvent {
+      id,
+      width: 1.0,
+      height: 1.0,
+      pressure: if pressed { pressure } else { 0. },
+      tilt_x: 90.,
+      tilt_y: 90.,
+      twist: 0.,
+      point_type: PointerType::Touch,
+      is_primary: touch.is_primary,
+      common: CommonEvent::new(target, wnd.tree),
+      global_pos,
+      movement: touch.movement,
+      buttons: if pressed { MouseButtons::PRIMARY } else { MouseButtons::empty() },
+    }
+  }
+}
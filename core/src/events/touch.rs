@@ -0,0 +1,154 @@
+use crate::{impl_common_event_deref, prelude::*};
+
+/// A finger touching the screen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TouchPoint {
+  /// The pointer of the finger, it's the same as the `id` of the pointer
+  /// events fired by the finger.
+  pub id: PointerId,
+  /// The position of the finger in global (window) coordinates.
+  pub global_pos: Point,
+  /// The normalized pressure of the finger in the range of 0 to 1, it's 0.5
+  /// if the device doesn't report it.
+  pub pressure: f32,
+}
+
+/// The event of a finger touching the screen, it bubbles from the widget that
+/// the finger started touching, even if the finger has moved out of it.
+///
+/// Every finger also fires the pointer events, the touch event carries all the
+/// fingers on the screen, so the multiple finger gestures like the pinch and
+/// the pan can be recognized from it.
+#[derive(Debug)]
+pub struct TouchEvent {
+  /// The finger that changed and fired this event.
+  pub touch: TouchPoint,
+  /// All the fingers touching the screen. The finger of the `TouchEnd` and the
+  /// `TouchCancel` is already removed from it.
+  pub touches: Vec<TouchPoint>,
+  pub common: CommonEvent,
+}
+
+impl_common_event_deref!(TouchEvent);
+
+impl TouchEvent {
+  #[inline]
+  pub fn new(touch: TouchPoint, touches: Vec<TouchPoint>, id: WidgetId, wnd: &Window) -> Self {
+    Self { touch, touches, common: CommonEvent::new(id, wnd.tree) }
+  }
+
+  /// The center of all the fingers in the current target widget, the pan moves
+  /// it. Return `None` if no finger is touching.
+  pub fn centroid(&self) -> Option<Point> {
+    let center = global_centroid(&self.touches)?;
+    Some(self.map_from_global(center))
+  }
+
+  /// The average distance from the fingers to their center, the pinch changes
+  /// it, so the ratio of two spans is the scale of the pinch. It's 0 if less
+  /// than two fingers are touching.
+  pub fn span(&self) -> f32 {
+    let Some(center) = global_centroid(&self.touches) else {
+      return 0.;
+    };
+    let sum: f32 = self
+      .touches
+      .iter()
+      .map(|t| (t.global_pos - center).length())
+      .sum();
+    sum / self.touches.len() as f32
+  }
+}
+
+fn global_centroid(touches: &[TouchPoint]) -> Option<Point> {
+  if touches.is_empty() {
+    return None;
+  }
+  let sum = touches
+    .iter()
+    .fold(Vector::zero(), |acc, t| acc + t.global_pos.to_vector());
+  Some((sum / touches.len() as f32).to_point())
+}
+
+#[cfg(test)]
+mod tests {
+  use winit::event::{DeviceId, Touch, TouchPhase, WindowEvent};
+
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  fn touch(wnd: &TestWindow, id: u64, phase: TouchPhase, x: f64, y: f64) {
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::Touch(Touch {
+      device_id: unsafe { DeviceId::dummy() },
+      phase,
+      location: (x, y).into(),
+      force: None,
+      id,
+    }));
+    wnd.run_frame_tasks();
+  }
+
+  #[test]
+  fn touch_as_pointers() {
+    reset_test_env!();
+
+    let (log, w_log) = split_value(vec![]);
+    let w_log2 = w_log.clone_writer();
+    let w_log3 = w_log.clone_writer();
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        on_pointer_down: move |e| {
+          let id = e.id;
+          $w_log.write().push(format!("down {id:?} {} {:?}", e.is_primary, e.position()));
+        },
+        on_pointer_up: move |e| $w_log2.write().push(format!("up {:?}", e.id)),
+        on_tap: move |e| $w_log3.write().push(format!("tap {:?}", e.position())),
+      }
+    });
+    wnd.draw_frame();
+
+    touch(&wnd, 1, TouchPhase::Started, 10., 10.);
+    touch(&wnd, 2, TouchPhase::Started, 50., 50.);
+    touch(&wnd, 2, TouchPhase::Ended, 50., 50.);
+    touch(&wnd, 1, TouchPhase::Ended, 20., 20.);
+
+    assert_eq!(
+      *log.read(),
+      [
+        "down PointerId(2) true (10.0, 10.0)",
+        "down PointerId(3) false (50.0, 50.0)",
+        "up PointerId(3)",
+        "up PointerId(2)",
+        "tap (20.0, 20.0)",
+      ]
+    );
+  }
+
+  #[test]
+  fn pinch_by_touches() {
+    reset_test_env!();
+
+    let (spans, w_spans) = split_value(vec![]);
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        on_touch_move: move |e| $w_spans.write().push((e.span(), e.centroid())),
+        on_touch_end: move |e| assert_eq!(e.touches.len(), 1),
+      }
+    });
+    wnd.draw_frame();
+
+    touch(&wnd, 1, TouchPhase::Started, 40., 50.);
+    touch(&wnd, 2, TouchPhase::Started, 60., 50.);
+    touch(&wnd, 1, TouchPhase::Moved, 30., 50.);
+    touch(&wnd, 2, TouchPhase::Moved, 70., 50.);
+    touch(&wnd, 2, TouchPhase::Ended, 70., 50.);
+
+    assert_eq!(
+      *spans.read(),
+      [(15., Some(Point::new(45., 50.))), (20., Some(Point::new(50., 50.)))]
+    );
+  }
+}
@@ -0,0 +1,62 @@
+use winit::event::MouseButton;
+
+use crate::prelude::*;
+
+/// An input event of the framework that can be injected into a window by
+/// [`Window::inject_event`].
+///
+/// It's decoupled from the events of the shell, so the integration tests and
+/// the automation tools can drive the UI as a user does. The positions and
+/// the deltas are in logical pixels.
+#[derive(Debug, Clone)]
+pub enum UiEvent {
+  /// The pointer moves to the position relative to the window.
+  PointerMove(Point),
+  /// The pointer leaves the window.
+  PointerLeave,
+  /// The buttons of the pointer are pressed.
+  PointerDown(MouseButtons),
+  /// The buttons of the pointer are released.
+  PointerUp(MouseButtons),
+  /// The wheel scrolls over the widget under the pointer.
+  Wheel { delta_x: f32, delta_y: f32 },
+  /// The modifier keys pressed changed.
+  Modifiers(ModifiersState),
+  /// A key is pressed.
+  KeyDown { key: VirtualKey, physical_key: PhysicalKey },
+  /// A key is released.
+  KeyUp { key: VirtualKey, physical_key: PhysicalKey },
+  /// The characters are received, like from the keyboard or the IME.
+  Chars(String),
+  /// The pre-edit of the IME changed.
+  ImePreEdit(ImePreEdit),
+}
+
+impl UiEvent {
+  /// The events of a tap of the primary button at the `pos`.
+  pub fn tap_at(pos: Point) -> [UiEvent; 3] {
+    [
+      UiEvent::PointerMove(pos),
+      UiEvent::PointerDown(MouseButtons::PRIMARY),
+      UiEvent::PointerUp(MouseButtons::PRIMARY),
+    ]
+  }
+
+  /// The events of pressing and releasing a key.
+  pub fn key_press(key: VirtualKey, physical_key: PhysicalKey) -> [UiEvent; 2] {
+    [UiEvent::KeyDown { key: key.clone(), physical_key }, UiEvent::KeyUp { key, physical_key }]
+  }
+}
+
+impl MouseButtons {
+  pub(crate) fn to_mouse_buttons(self) -> impl Iterator<Item = MouseButton> {
+    self.iter().filter_map(|btn| match btn {
+      MouseButtons::PRIMARY => Some(MouseButton::Left),
+      MouseButtons::SECONDARY => Some(MouseButton::Right),
+      MouseButtons::AUXILIARY => Some(MouseButton::Middle),
+      MouseButtons::FOURTH => Some(MouseButton::Back),
+      MouseButtons::FIFTH => Some(MouseButton::Forward),
+      _ => None,
+    })
+  }
+}
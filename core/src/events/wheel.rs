@@ -72,4 +72,69 @@ mod tests {
     assert_eq!(*source_receive_for_capture.borrow(), (1., 1.));
     assert_eq!(*event_order.borrow(), ["capture", "bubble"]);
   }
+
+  fn wheel_log() -> (TestWindow, Stateful<Vec<f32>>) {
+    let deltas = Stateful::new(vec![]);
+    let c_deltas = deltas.clone_writer();
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(100., 100.),
+          on_wheel: move |e| $c_deltas.write().push(e.delta_y),
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+    (wnd, deltas)
+  }
+
+  fn line_wheel(wnd: &TestWindow, lines: f32) {
+    let device_id = unsafe { DeviceId::dummy() };
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::MouseWheel {
+      device_id,
+      delta: MouseScrollDelta::LineDelta(0., lines),
+      phase: TouchPhase::Moved,
+    });
+  }
+
+  #[test]
+  fn normalize_delta() {
+    reset_test_env!();
+
+    let (wnd, deltas) = wheel_log();
+    line_wheel(&wnd, 1.);
+    wnd.run_frame_tasks();
+    assert_eq!(*deltas.read(), [16.]);
+
+    wnd.set_wheel_config(WheelConfig {
+      multiplier: 2.,
+      natural_scrolling: true,
+      line_height: 10.,
+      ..Default::default()
+    });
+    line_wheel(&wnd, 1.);
+    wnd.inject_event(UiEvent::Wheel { delta_x: 0., delta_y: 3. });
+    wnd.run_frame_tasks();
+    assert_eq!(*deltas.read(), [16., -20., -6.]);
+  }
+
+  #[test]
+  fn line_wheel_momentum() {
+    reset_test_env!();
+
+    let (mut wnd, deltas) = wheel_log();
+    wnd.use_manual_clock();
+    wnd.set_wheel_config(WheelConfig { momentum: Some(0.5), ..Default::default() });
+    line_wheel(&wnd, 1.);
+    wnd.run_frame_tasks();
+    assert_eq!(*deltas.read(), [8.]);
+
+    for _ in 0..10 {
+      wnd.tick(Duration::from_millis(16));
+    }
+    assert_eq!(*deltas.read(), [8., 4., 2., 1., 0.5, 0.5]);
+    assert_eq!(deltas.read().iter().sum::<f32>(), 16.);
+  }
 }
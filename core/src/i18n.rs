@@ -0,0 +1,316 @@
+//! Localize the text of the application by the [Fluent](https://projectfluent.org)
+//! resources.
+//!
+//! The `.ftl` resources are loaded per locale into the [`Localization`] of the
+//! application, see [`AppCtx::localization`]. The [`tr!`](crate::tr) macro
+//! translates a message as a pipe, so the text follows when the active locale
+//! is switched.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use ribir::prelude::*;
+//!
+//! {
+//!   let mut l10n = AppCtx::localization().write();
+//!   l10n
+//!     .add_ftl("en-US", "hello = Hello, { $name }!")
+//!     .unwrap();
+//!   l10n
+//!     .add_ftl("zh-CN", "hello = 你好，{ $name }！")
+//!     .unwrap();
+//! }
+//!
+//! let _w = fn_widget! {
+//!   @Text {
+//!     text: tr!("hello", name = "Ribir"),
+//!     on_tap: |_| AppCtx::localization().write().set_locale("zh-CN".parse().unwrap()),
+//!   }
+//! };
+//! ```
+
+use std::path::Path;
+
+pub use fluent_bundle::{FluentArgs, FluentValue};
+use fluent_bundle::{FluentBundle, FluentResource};
+pub use unic_langid::LanguageIdentifier;
+
+use crate::prelude::*;
+
+/// The Fluent bundles of the application and the active locale to translate
+/// the messages.
+///
+/// A message missing in the active locale is looked up in the fallback locale,
+/// and the key itself is used if neither has it.
+pub struct Localization {
+  locale: LanguageIdentifier,
+  fallback: LanguageIdentifier,
+  bundles: ahash::HashMap<LanguageIdentifier, FluentBundle<FluentResource>>,
+}
+
+/// The errors that may occur when loading the Fluent resources.
+#[derive(Debug)]
+pub enum LocalizationError {
+  /// The locale is not a valid language identifier.
+  InvalidLocale(String),
+  /// The resource has syntax errors, the messages without errors are still
+  /// loaded.
+  Syntax(Vec<String>),
+  /// The resource file can't be read.
+  Io(std::io::Error),
+}
+
+impl Localization {
+  /// Create a localization with the `locale` active, it's also the fallback
+  /// locale.
+  pub fn new(locale: LanguageIdentifier) -> Self {
+    Self { fallback: locale.clone(), locale, bundles: <_>::default() }
+  }
+
+  /// The active locale.
+  pub fn locale(&self) -> &LanguageIdentifier { &self.locale }
+
+  /// Switch the active locale, the texts translated by [`tr!`](crate::tr)
+  /// follow it.
+  pub fn set_locale(&mut self, locale: LanguageIdentifier) { self.locale = locale; }
+
+  /// The locale to look up the messages missing in the active locale.
+  pub fn fallback(&self) -> &LanguageIdentifier { &self.fallback }
+
+  /// Set the locale to look up the messages missing in the active locale.
+  pub fn set_fallback(&mut self, locale: LanguageIdentifier) { self.fallback = locale; }
+
+  /// All the locales that have any resource loaded.
+  pub fn locales(&self) -> impl Iterator<Item = &LanguageIdentifier> { self.bundles.keys() }
+
+  /// Load the Fluent `source` into the bundle of the `locale`, a message
+  /// already defined in the bundle is overridden.
+  pub fn add_ftl(&mut self, locale: &str, source: &str) -> Result<(), LocalizationError> {
+    let locale: LanguageIdentifier = locale
+      .parse()
+      .map_err(|_| LocalizationError::InvalidLocale(locale.to_string()))?;
+    let (res, errors) = match FluentResource::try_new(source.to_string()) {
+      Ok(res) => (res, vec![]),
+      Err((res, errors)) => (res, errors.iter().map(|e| e.to_string()).collect()),
+    };
+
+    let bundle = self
+      .bundles
+      .entry(locale)
+      .or_insert_with_key(|locale| {
+        let mut bundle = FluentBundle::new(vec![locale.clone()]);
+        // The isolation marks are for the bidirectional text of the web, the text
+        // shaping of the framework doesn't need them.
+        bundle.set_use_isolating(false);
+        bundle
+      });
+    bundle.add_resource_overriding(res);
+
+    if errors.is_empty() { Ok(()) } else { Err(LocalizationError::Syntax(errors)) }
+  }
+
+  /// Load the Fluent file at `path` into the bundle of the `locale`, see
+  /// [`Localization::add_ftl`].
+  pub fn load_ftl_file(
+    &mut self, locale: &str, path: impl AsRef<Path>,
+  ) -> Result<(), LocalizationError> {
+    let source = std::fs::read_to_string(path).map_err(LocalizationError::Io)?;
+    self.add_ftl(locale, &source)
+  }
+
+  /// Translate the message `key` in the active locale, or in the fallback
+  /// locale if it's missing. Return `None` if neither has it.
+  pub fn format(&self, key: &str, args: Option<&FluentArgs>) -> Option<String> {
+    [&self.locale, &self.fallback]
+      .into_iter()
+      .filter_map(|locale| self.bundles.get(locale))
+      .find_map(|bundle| {
+        let pattern = bundle.get_message(key)?.value()?;
+        let mut errors = vec![];
+        let text = bundle.format_pattern(pattern, args, &mut errors);
+        if !errors.is_empty() {
+          log::warn!("Failed to format the message `{key}`: {errors:?}");
+        }
+        Some(text.into_owned())
+      })
+  }
+
+  /// Translate the message `key` like [`Localization::format`], but return
+  /// the key itself if the message is missing.
+  pub fn tr(&self, key: &str, args: Option<&FluentArgs>) -> String {
+    self.format(key, args).unwrap_or_else(|| {
+      log::warn!("The message `{key}` is missing in the locale `{}`.", self.locale);
+      key.to_string()
+    })
+  }
+
+  /// Format the `value` with `fraction` digits after the decimal point, by the
+  /// decimal and the grouping separators of the active locale.
+  pub fn format_number(&self, value: f64, fraction: usize) -> String {
+    if !value.is_finite() {
+      return value.to_string();
+    }
+    let (decimal, group) = number_separators(&self.locale);
+    let text = format!("{:.*}", fraction, value.abs());
+    let (int, frac) = text.split_at(text.find('.').unwrap_or(text.len()));
+
+    let mut out = String::with_capacity(text.len() * 2);
+    if value.is_sign_negative()
+      && text
+        .bytes()
+        .any(|b| b.is_ascii_digit() && b != b'0')
+    {
+      out.push('-');
+    }
+    for (i, c) in int.chars().enumerate() {
+      if i > 0 && (int.len() - i) % 3 == 0 {
+        out.push(group);
+      }
+      out.push(c);
+    }
+    if let Some(frac) = frac.strip_prefix('.') {
+      out.push(decimal);
+      out.push_str(frac);
+    }
+    out
+  }
+
+  /// Format the date in the numeric style of the active locale, like
+  /// `1/29/2025` for `en-US` and `29.01.2025` for `de`. The `month` and the
+  /// `day` start from 1.
+  pub fn format_date(&self, year: i32, month: u32, day: u32) -> String {
+    let region = self.locale.region.as_ref().map(|r| r.as_str());
+    match (self.locale.language.as_str(), region) {
+      ("en", None | Some("US")) => format!("{month}/{day}/{year}"),
+      ("en" | "fr" | "es" | "it" | "pt" | "el" | "vi", _) => {
+        format!("{day:02}/{month:02}/{year}")
+      }
+      ("de" | "ru" | "uk" | "pl" | "cs" | "fi" | "nb" | "no" | "tr", _) => {
+        format!("{day:02}.{month:02}.{year}")
+      }
+      ("nl", _) => format!("{day:02}-{month:02}-{year}"),
+      ("zh", _) => format!("{year}/{month}/{day}"),
+      ("ja", _) => format!("{year}/{month:02}/{day:02}"),
+      ("ko", _) => format!("{year}. {month}. {day}."),
+      _ => format!("{year}-{month:02}-{day:02}"),
+    }
+  }
+}
+
+impl Default for Localization {
+  fn default() -> Self { Self::new(unic_langid::langid!("en-US")) }
+}
+
+impl std::fmt::Display for LocalizationError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      LocalizationError::InvalidLocale(locale) => write!(f, "invalid locale `{locale}`"),
+      LocalizationError::Syntax(errors) => {
+        write!(f, "the Fluent resource has syntax errors: {}", errors.join("; "))
+      }
+      LocalizationError::Io(e) => write!(f, "failed to read the Fluent resource: {e}"),
+    }
+  }
+}
+
+impl std::error::Error for LocalizationError {}
+
+/// The decimal and the grouping separators of the numbers in the `locale`.
+fn number_separators(locale: &LanguageIdentifier) -> (char, char) {
+  match locale.language.as_str() {
+    "de" | "es" | "it" | "nl" | "pt" | "id" | "tr" | "da" | "el" | "vi" => (',', '.'),
+    "fr" => (',', '\u{202F}'),
+    "ru" | "uk" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "no" | "hu" | "bg" => (',', '\u{A0}'),
+    _ => ('.', ','),
+  }
+}
+
+/// Translate the message `key` with the `args` as a pipe, it's translated
+/// again when the localization of the application is modified. Use the
+/// [`tr!`](crate::tr) macro instead of calling it directly.
+pub fn tr_pipe(
+  key: impl Into<CowArc<str>>, args: FluentArgs<'static>,
+) -> impl Pipe<Value = String> {
+  let key = key.into();
+  let l10n = AppCtx::localization();
+  let modifies = observable::of(ModifyScope::DATA)
+    .merge(l10n.modifies())
+    .skip(1)
+    .box_it();
+  MapPipe::new(ModifiesPipe::new(modifies), move |_: ModifyScope| l10n.read().tr(&key, Some(&args)))
+}
+
+/// Translate a message of the application localization as a pipe, the text
+/// follows when the active locale is switched. The arguments of the message
+/// follow the key as `name = value`.
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @Text { text: tr!("unread-emails", count = 3) }
+/// };
+/// ```
+#[macro_export]
+macro_rules! tr {
+  ($key:expr $(, $name:ident = $value:expr)* $(,)?) => {{
+    #[allow(unused_mut)]
+    let mut args = $crate::i18n::FluentArgs::new();
+    $(args.set(stringify!($name), $value);)*
+    $crate::i18n::tr_pipe($key, args)
+  }};
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[test]
+  fn switch_locale() {
+    reset_test_env!();
+
+    {
+      let mut l10n = AppCtx::localization().write();
+      l10n
+        .add_ftl("en-US", "hello = Hello, { $name }!")
+        .unwrap();
+      l10n
+        .add_ftl("zh-CN", "hello = 你好，{ $name }！")
+        .unwrap();
+    }
+
+    let init: DeclareInit<CowArc<str>> = tr!("hello", name = "Ribir").declare_into();
+    let (text, stream) = init.unzip();
+    assert_eq!(&*text, "Hello, Ribir!");
+
+    let text = Stateful::new(text);
+    let c_text = text.clone_writer();
+    stream
+      .unwrap()
+      .subscribe(move |(_, v)| *c_text.write() = v);
+    AppCtx::localization()
+      .write()
+      .set_locale("zh-CN".parse().unwrap());
+    AppCtx::run_until_stalled();
+    assert_eq!(&**text.read(), "你好，Ribir！");
+
+    let l10n = AppCtx::localization().read();
+    assert_eq!(l10n.tr("missing", None), "missing");
+  }
+
+  #[test]
+  fn format_by_locale() {
+    let mut l10n = Localization::default();
+    assert_eq!(l10n.format_number(-1234567.891, 2), "-1,234,567.89");
+    assert_eq!(l10n.format_number(999., 0), "999");
+    assert_eq!(l10n.format_date(2025, 1, 29), "1/29/2025");
+
+    l10n.set_locale("de-DE".parse().unwrap());
+    assert_eq!(l10n.format_number(1234.5, 1), "1.234,5");
+    assert_eq!(l10n.format_date(2025, 1, 29), "29.01.2025");
+
+    assert!(matches!(l10n.add_ftl("!", ""), Err(LocalizationError::InvalidLocale(_))));
+    assert!(matches!(l10n.add_ftl("de", "hello = {"), Err(LocalizationError::Syntax(_))));
+  }
+}
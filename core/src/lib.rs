@@ -4,12 +4,15 @@
 extern crate bitflags;
 
 pub mod animation;
+pub mod async_task;
 pub mod builtin_widgets;
 pub mod clipboard;
 mod context;
 pub mod data_widget;
 pub mod declare;
 pub mod events;
+#[cfg(feature = "i18n")]
+pub mod i18n;
 pub mod local_sender;
 pub mod pipe;
 pub(crate) mod render_helper;
@@ -47,9 +50,12 @@ pub mod prelude {
   pub use rxrust::prelude::*;
   pub use smallvec;
 
+  #[cfg(feature = "i18n")]
+  pub use super::i18n::{FluentArgs, LanguageIdentifier, Localization, LocalizationError};
   pub use super::{
     Measure,
     animation::*,
+    async_task::*,
     builtin_widgets::*,
     class_names,
     context::*,
@@ -57,16 +63,16 @@ pub mod prelude {
     events::*,
     multi_class,
     overlay::{AutoClosePolicy, Overlay, OverlayStyle},
-    pipe::{BoxPipe, FinalChain, MapPipe, ModifiesPipe, Pipe},
+    pipe::{BoxPipe, FinalChain, MapPipe, ModifiesPipe, OpPipe, Pipe},
     providers,
     query::*,
     state::*,
     style_class,
-    ticker::{Duration, Instant},
+    ticker::{Duration, FrameClock, Instant},
     widget::*,
     widget_children::*,
-    widget_tree::{BoxClamp, DirtyPhase, LayoutInfo, TrackId, WidgetId},
-    window::Window,
+    widget_tree::{BoxClamp, DirtyPhase, LayoutInfo, TrackId, TreeStats, WidgetId},
+    window::{FocusRingStyle, PresentMode, Window, WindowConfig},
   };
   pub use crate::{timer, *};
 }
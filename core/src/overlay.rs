@@ -63,6 +63,10 @@ struct ShowingInfo {
   generator: GenWidget,
 }
 
+impl PartialEq for Overlay {
+  fn eq(&self, other: &Self) -> bool { Sc::ptr_eq(&self.0, &other.0) }
+}
+
 impl Overlay {
   /// Create overlay from a function widget that may call many times.
   pub fn new(gen: impl Into<GenWidget>, style: OverlayStyle) -> Self {
@@ -80,13 +84,7 @@ impl Overlay {
   /// Return the overlay that the `ctx` belongs to if it is within an overlay.
   pub fn of(ctx: &impl WidgetCtx) -> Option<Self> {
     let wnd = ctx.window();
-    let tree = wnd.tree();
-    let overlays = tree
-      .root()
-      .query_ref::<ShowingOverlays>(tree)
-      .unwrap();
-
-    overlays.showing_of(ctx)
+    wnd.tree().overlays.showing_of(ctx)
   }
 
   /// Get the auto close policy of the overlay.
@@ -95,7 +93,8 @@ impl Overlay {
   /// Get the mask of the the background of the overlay used.  
   pub fn mask(&self) -> Option<Brush> { self.0.borrow().mask.clone() }
 
-  /// Show the overlay.
+  /// Show the overlay. It's built before the next frame, so it can be shown
+  /// in an event handler.
   pub fn show(&self, wnd: Sc<Window>) {
     if self.is_showing() {
       return;
@@ -177,9 +176,7 @@ impl Overlay {
     if let Some(showing) = showing {
       let ShowingInfo { wnd_id, .. } = showing;
       if let Some(wnd) = AppCtx::get_window(wnd_id) {
-        let _guard = BuildCtx::init_for(wnd.tree().root(), wnd.tree);
-        let showing_overlays = Provider::of::<ShowingOverlays>(BuildCtx::get()).unwrap();
-        showing_overlays.remove(self);
+        wnd.tree().overlays.remove(self);
 
         if let Some(wid) = track_id.and_then(|track_id| track_id.get()) {
           AppCtx::once_next_frame(move |_| {
@@ -197,6 +194,7 @@ impl Overlay {
     let background = self.mask();
     let close_policy = self.auto_close_policy();
     let inner = self.0.clone();
+    let this = self.clone();
     let gen = fn_widget! {
       let w = content.gen_widget().into_widget();
       let mut w = if background.is_some() || close_policy.contains(AutoClosePolicy::TAP_OUTSIDE) {
@@ -205,11 +203,10 @@ impl Overlay {
           container = container.background(background);
         }
         if close_policy.contains(AutoClosePolicy::TAP_OUTSIDE) {
+          let this = this.clone();
           container = container.on_tap(move |e| {
             if e.target() == e.current_target() {
-              if let Some(overlay) = Overlay::of(&**e) {
-                overlay.close();
-              }
+              this.close();
             }
           })
         }
@@ -218,11 +215,10 @@ impl Overlay {
         FatObj::new(w)
       };
       if close_policy.contains(AutoClosePolicy::ESC) {
+        let this = this.clone();
         w = w.on_key_down(move |e| {
           if *e.key() == VirtualKey::Named(NamedKey::Escape) {
-            if let Some(overlay) = Overlay::of(&**e) {
-              overlay.close();
-            }
+            this.close();
           }
         });
       }
@@ -231,18 +227,27 @@ impl Overlay {
       w
     };
 
-    let _guard = BuildCtx::init_for(wnd.tree().root(), wnd.tree);
+    let gen: GenWidget = gen.into();
+    self.0.borrow_mut().showing = Some(ShowingInfo { generator: gen.clone(), wnd_id: wnd.id() });
 
-    let wid = BuildCtx::get_mut().build(gen());
-    let tree = wnd.tree_mut();
-    tree.root().append(wid, tree);
-    wid.on_mounted_subtree(tree);
-    tree.dirty_marker().mark(wid, DirtyPhase::Layout);
+    // The overlay may be shown in an event handler, when the providers of the
+    // ancestors are in use, so build it out of the event dispatching.
+    let this = self.clone();
+    let _ = AppCtx::spawn_local(async move {
+      // Skip it if it's closed or already built before.
+      if !this.is_showing() || this.showing_root().is_some() {
+        return;
+      }
+      let _guard = BuildCtx::init_for(wnd.tree().root(), wnd.tree);
 
-    self.0.borrow_mut().showing = Some(ShowingInfo { generator: gen.into(), wnd_id: wnd.id() });
+      let wid = BuildCtx::get_mut().build(gen.gen_widget());
+      let tree = wnd.tree_mut();
+      tree.root().append(wid, tree);
+      wid.on_mounted_subtree(tree);
+      tree.dirty_marker().mark(wid, DirtyPhase::Layout);
 
-    let showing_overlays = Provider::of::<ShowingOverlays>(BuildCtx::get()).unwrap();
-    showing_overlays.add(self.clone());
+      tree.overlays.add(this);
+    });
   }
 
   fn showing_root(&self) -> Option<WidgetId> {
@@ -256,7 +261,8 @@ impl Overlay {
   }
 }
 
-pub(crate) struct ShowingOverlays(RefCell<Vec<Overlay>>);
+#[derive(Clone, Default)]
+pub(crate) struct ShowingOverlays(Sc<RefCell<Vec<Overlay>>>);
 
 impl ShowingOverlays {
   pub(crate) fn rebuild(&self) {
@@ -285,10 +291,7 @@ impl ShowingOverlays {
 
   fn remove(&self, overlay: &Overlay) {
     assert!(overlay.showing_root().is_none());
-    self
-      .0
-      .borrow_mut()
-      .retain(|o| !Sc::ptr_eq(&o.0, &overlay.0))
+    self.0.borrow_mut().retain(|o| o != overlay)
   }
 
   fn showing_of(&self, ctx: &impl WidgetCtx) -> Option<Overlay> {
@@ -300,14 +303,12 @@ impl ShowingOverlays {
   }
 }
 
-impl Default for ShowingOverlays {
-  fn default() -> Self { Self(RefCell::new(vec![])) }
-}
-
 #[cfg(test)]
 mod tests {
   use std::{cell::RefCell, rc::Rc};
 
+  use winit::event::{DeviceId, ElementState, MouseButton, WindowEvent};
+
   use crate::{
     overlay::{AutoClosePolicy, OverlayStyle},
     prelude::*,
@@ -362,4 +363,49 @@ mod tests {
     assert_eq!(*r_log.borrow(), &["mounted", "disposed"]);
     assert_eq!(wnd.tree().count(root), 3);
   }
+
+  #[test]
+  fn show_and_close_in_event() {
+    reset_test_env!();
+
+    let overlay = Overlay::new(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(20., 20.),
+          on_tap: move |e| Overlay::of(&**e).unwrap().close(),
+        }
+      },
+      OverlayStyle { auto_close_policy: AutoClosePolicy::TAP_OUTSIDE, mask: None },
+    );
+    let o = overlay.clone();
+    let mut wnd = TestWindow::new(fn_widget! {
+      let o = o.clone();
+      @MockBox {
+        size: Size::new(100., 100.),
+        on_tap: move |e| o.show(e.window()),
+      }
+    });
+    wnd.draw_frame();
+
+    let tap = |wnd: &mut TestWindow, x: f64, y: f64| {
+      let device_id = unsafe { DeviceId::dummy() };
+      #[allow(deprecated)]
+      wnd.processes_native_event(WindowEvent::CursorMoved { device_id, position: (x, y).into() });
+      wnd.process_mouse_input(device_id, ElementState::Pressed, MouseButton::Left);
+      wnd.process_mouse_input(device_id, ElementState::Released, MouseButton::Left);
+      wnd.draw_frame();
+    };
+
+    tap(&mut wnd, 50., 50.);
+    assert!(overlay.is_showing());
+    // Closed by the content of the overlay.
+    tap(&mut wnd, 10., 10.);
+    assert!(!overlay.is_showing());
+
+    tap(&mut wnd, 50., 50.);
+    assert!(overlay.is_showing());
+    // Closed by tapping outside.
+    tap(&mut wnd, 50., 50.);
+    assert!(!overlay.is_showing());
+  }
 }
@@ -1,12 +1,12 @@
 use std::{
-  cell::{Cell, UnsafeCell},
+  cell::{Cell, RefCell, UnsafeCell},
   convert::Infallible,
   ops::RangeInclusive,
   ptr::NonNull,
 };
 
 use ribir_algo::Sc;
-use rxrust::ops::box_it::BoxOp;
+use rxrust::ops::{box_it::BoxOp, throttle::ThrottleEdge};
 use smallvec::SmallVec;
 use widget_id::RenderQueryable;
 
@@ -57,6 +57,63 @@ pub trait Pipe: 'static {
   fn box_unzip(
     self: Box<Self>, scope: ModifyScope, priority: Option<PipeWidgetBuildInit>,
   ) -> (Self::Value, ValueStream<Self::Value>);
+
+  /// Only emit the changed values that satisfy the predicate `f`, the initial
+  /// value is always kept.
+  fn filter<F>(self, f: F) -> OpPipe<Self::Value, Self>
+  where
+    Self: Sized,
+    Self::Value: 'static,
+    F: FnMut(&Self::Value) -> bool + 'static,
+  {
+    let f = RefCell::new(f);
+    OpPipe::new(self, move |_, s| s.filter(move |(_, v)| f.borrow_mut()(v)).box_it())
+  }
+
+  /// Only emit the value when it's not equal to the last one, include the
+  /// initial value.
+  fn distinct_until_changed(self) -> OpPipe<Self::Value, Self>
+  where
+    Self: Sized,
+    Self::Value: PartialEq + Clone + 'static,
+  {
+    OpPipe::new(self, |init, s| {
+      let last = RefCell::new(init.clone());
+      s.filter(move |(_, v)| {
+        let mut last = last.borrow_mut();
+        let changed = *v != *last;
+        if changed {
+          *last = v.clone();
+        }
+        changed
+      })
+      .box_it()
+    })
+  }
+
+  /// Only emit the latest value after the pipe stops changing for `duration`.
+  /// It's useful to delay the expensive work until the user stops typing,
+  /// such as the search as you type.
+  fn debounce(self, duration: Duration) -> OpPipe<Self::Value, Self>
+  where
+    Self: Sized,
+    Self::Value: 'static,
+  {
+    OpPipe::new(self, move |_, s| s.debounce(duration, AppCtx::scheduler()).box_it())
+  }
+
+  /// Emit the value at most once every `duration`, both the first and the
+  /// latest value of a period are emitted.
+  fn throttle(self, duration: Duration) -> OpPipe<Self::Value, Self>
+  where
+    Self: Sized,
+    Self::Value: Clone + 'static,
+  {
+    OpPipe::new(self, move |_, s| {
+      s.throttle_time(duration, ThrottleEdge::all(), AppCtx::scheduler())
+        .box_it()
+    })
+  }
 }
 
 /// A trait object type for `Pipe`, help to store a concrete `Pipe`
@@ -81,6 +138,16 @@ pub struct FinalChain<V, S, F> {
   _marker: PhantomData<V>,
 }
 
+/// A pipe that applies an operator to the value stream of its source before the
+/// stream is bound to the pipe widget, so the operator can emit the values
+/// asynchronously, unlike [`Pipe::value_chain`].
+pub struct OpPipe<V, S> {
+  source: S,
+  op: StreamOp<V>,
+}
+
+type StreamOp<V> = Box<dyn FnOnce(&V, ValueStream<V>) -> ValueStream<V>>;
+
 impl<V: 'static> BoxPipe<V> {
   #[inline]
   pub fn value(v: V) -> Self { Self(Box::new(ValuePipe(v))) }
@@ -236,6 +303,154 @@ pub(crate) trait InnerPipe: Pipe + Sized {
     widgets
   }
 
+  /// Build the children of a multi-child pipe identified by keys. When the pipe
+  /// regenerates, the widgets of the keys that were generated last time are
+  /// moved to their new positions, only the widgets of the new keys are built.
+  fn build_keyed_multi<W, const M: usize>(self) -> Vec<Widget<'static>>
+  where
+    Self::Value: IntoIterator<Item = Keyed<W>>,
+    W: IntoWidget<'static, M>,
+  {
+    let node = PipeNode::empty_node();
+    let mut init = PipeWidgetBuildInit::new(node.clone());
+    let (m, modifies) = self.unzip(ModifyScope::FRAMEWORK, Some(init.clone()));
+    let mut keys = vec![];
+    let mut widgets = m
+      .into_iter()
+      .map(|Keyed { key, widget }| {
+        keys.push(key);
+        widget.into_widget()
+      })
+      .collect::<Vec<_>>();
+    if widgets.is_empty() {
+      widgets.push(Void.into_widget());
+    }
+
+    let pipe_node = node.clone();
+    let first = widgets.remove(0).on_build(move |id| {
+      pipe_node.init(id, GenRange::Multi(vec![id]));
+      let tree_ptr = BuildCtx::get().tree_ptr();
+      init.set_tree(tree_ptr);
+
+      let c_pipe_node = pipe_node.clone();
+
+      let u = modifies.subscribe(move |(_, m)| {
+        let old = match &pipe_node.dyn_info().gen_range {
+          GenRange::Multi(m) => m.clone(),
+          _ => unreachable!(),
+        };
+        let host = old[0];
+
+        let old_node = pipe_node.take_data();
+        let without_ctx = BuildCtx::try_get().is_none();
+        if without_ctx {
+          BuildCtx::set_for(host, unsafe { NonNull::new_unchecked(tree_ptr) });
+        }
+
+        // The widgets of the last generation that can be reused by their keys, if
+        // the list is empty last time, the `Void` placeholder has no key.
+        let mut reusable = ahash::HashMap::default();
+        for (key, id) in keys.drain(..).zip(old.iter().copied()) {
+          reusable.entry(key).or_insert(id);
+        }
+
+        let ctx = BuildCtx::get_mut();
+        let mut new = vec![];
+        let mut built = ahash::HashSet::default();
+        for Keyed { key, widget } in m {
+          let id = match reusable.remove(&key) {
+            Some(id) => id,
+            None => {
+              let id = ctx.build(widget.into_widget());
+              built.insert(id);
+              id
+            }
+          };
+          keys.push(key);
+          new.push(id);
+        }
+        if new.is_empty() {
+          let id = ctx.build(Void.into_widget());
+          built.insert(id);
+          new.push(id);
+        }
+
+        let tree = ctx.tree_mut();
+        for (idx, id) in new.iter().enumerate() {
+          // The host is still wrapped by this pipe node, its position is updated after
+          // the pipe node moves to the new host.
+          if *id != host && set_pos_of_multi(*id, idx, tree) && idx > 0 && built.contains(id) {
+            id.attach_data(Box::new(pipe_node.clone()), tree);
+          }
+        }
+
+        if new[0] == host {
+          pipe_node.replace_data(old_node);
+        } else {
+          pipe_node.transplant_to_new(old_node, new[0], tree);
+          if let Some(idx) = new.iter().position(|id| *id == host) {
+            if set_pos_of_multi(host, idx, tree) {
+              host.attach_data(Box::new(pipe_node.clone()), tree);
+            }
+          }
+        }
+
+        query_outside_infos(new[0], &pipe_node, tree)
+          .for_each(|node| node.dyn_info_mut().multi_replace(&old, &new));
+
+        // Move the widgets to their new positions, the reused widgets keep their
+        // subtree.
+        if new[0] != host {
+          host.insert_before(new[0], tree);
+        }
+        new
+          .windows(2)
+          .for_each(|w| w[0].insert_after(w[1], tree));
+
+        let kept: ahash::HashSet<_> = new.iter().copied().collect();
+        old
+          .iter()
+          .filter(|id| !kept.contains(id))
+          .for_each(|id| id.dispose_subtree(tree));
+        built.iter().for_each(|w| {
+          w.on_mounted_subtree(tree);
+          tree.dirty_marker().mark(*w, DirtyPhase::Layout);
+        });
+        if old != new {
+          if let Some(p) = new[0].parent(tree) {
+            tree.dirty_marker().mark(p, DirtyPhase::Layout);
+          }
+        }
+
+        if without_ctx {
+          BuildCtx::clear();
+        }
+      });
+
+      c_pipe_node.attach_subscription(u);
+    });
+
+    let mut children = vec![first];
+    for (idx, w) in widgets.into_iter().enumerate() {
+      let pipe_node = node.clone();
+      let w = w.on_build(move |id| {
+        match &mut pipe_node.dyn_info_mut().gen_range {
+          GenRange::Multi(m) => m.push(id),
+          _ => unreachable!(),
+        };
+
+        let tree = BuildCtx::get_mut().tree_mut();
+        if set_pos_of_multi(id, idx + 1, tree) {
+          id.attach_data(Box::new(pipe_node), tree);
+        }
+      });
+
+      children.push(w);
+    }
+
+    children
+  }
+
   fn into_parent_widget<const M: usize>(self) -> Widget<'static>
   where
     Self: Sized,
@@ -343,19 +558,10 @@ impl Pipe for ModifiesPipe {
     let stream = self
       .0
       .filter(move |s| s.contains(scope))
-      .map(|s| (s, s));
-
-    let stream = if let Some(init) = init {
-      let source = stream
-        .sample(AppCtx::frame_ticks().clone())
-        .priority(init.clone());
-
-      PipeWidgetContextOp { source, init }.box_it()
-    } else {
-      stream.box_it()
-    };
+      .map(|s| (s, s))
+      .box_it();
 
-    (ModifyScope::empty(), stream)
+    (ModifyScope::empty(), bind_pipe_widget(stream, init))
   }
 
   #[inline]
@@ -432,6 +638,52 @@ where
   }
 }
 
+impl<V, S: Pipe<Value = V>> OpPipe<V, S> {
+  /// Create a pipe that applies the `op` to the value stream of the `source`,
+  /// the `op` also receives the initial value of the pipe.
+  pub fn new(source: S, op: impl FnOnce(&V, ValueStream<V>) -> ValueStream<V> + 'static) -> Self {
+    Self { source, op: Box::new(op) }
+  }
+}
+
+impl<V: 'static, S: Pipe<Value = V>> Pipe for OpPipe<V, S> {
+  type Value = V;
+
+  fn unzip(
+    self, scope: ModifyScope, init: Option<PipeWidgetBuildInit>,
+  ) -> (Self::Value, ValueStream<Self::Value>) {
+    let Self { source, op } = self;
+    // The source is not bound to the pipe widget, the stream is bound after the
+    // operator is applied.
+    let (v, stream) = source.unzip(scope, None);
+    let stream = op(&v, stream);
+    (v, bind_pipe_widget(stream, init))
+  }
+
+  #[inline]
+  fn box_unzip(
+    self: Box<Self>, scope: ModifyScope, init: Option<PipeWidgetBuildInit>,
+  ) -> (Self::Value, ValueStream<Self::Value>) {
+    (*self).unzip(scope, init)
+  }
+}
+
+/// Bind the stream to the pipe widget if `init` is provided, the values are
+/// sampled by the frame ticks and emitted in the build context of the widget.
+fn bind_pipe_widget<V: 'static>(
+  stream: ValueStream<V>, init: Option<PipeWidgetBuildInit>,
+) -> ValueStream<V> {
+  if let Some(init) = init {
+    let source = stream
+      .sample(AppCtx::frame_ticks().clone())
+      .priority(init.clone());
+
+    PipeWidgetContextOp { source, init }.box_it()
+  } else {
+    stream
+  }
+}
+
 /// A pipe that never changes, help to construct a pipe from a value.
 struct ValuePipe<V>(V);
 
@@ -471,6 +723,14 @@ where
   fn into_widget_strict(self) -> Widget<'static> { self.build_single() }
 }
 
+impl<V, S, const M: usize> IntoWidgetStrict<'static, M> for OpPipe<V, S>
+where
+  Self: InnerPipe<Value = V>,
+  V: IntoWidget<'static, M>,
+{
+  fn into_widget_strict(self) -> Widget<'static> { self.build_single() }
+}
+
 impl<const M: usize, V> IntoWidgetStrict<'static, M> for Box<dyn Pipe<Value = V>>
 where
   V: IntoWidget<'static, M>,
@@ -494,6 +754,14 @@ where
   fn into_widget(self) -> Widget<'static> { option_into_widget(self) }
 }
 
+impl<V, S, const M: usize> IntoWidget<'static, M> for OpPipe<Option<V>, S>
+where
+  Self: InnerPipe<Value = Option<V>>,
+  V: IntoWidget<'static, M>,
+{
+  fn into_widget(self) -> Widget<'static> { option_into_widget(self) }
+}
+
 impl<const M: usize, V> IntoWidget<'static, M> for Box<dyn Pipe<Value = Option<V>>>
 where
   V: IntoWidget<'static, M>,
@@ -866,6 +1134,26 @@ impl Render for PipeNode {
   }
 
   fn get_transform(&self) -> Option<Transform> { self.as_ref().data.get_transform() }
+
+  fn debug_name(&self) -> &'static str { self.as_ref().data.debug_name() }
+
+  fn baseline_offset(&self, ctx: &mut LayoutCtx) -> Option<f32> {
+    self.as_ref().data.baseline_offset(ctx)
+  }
+
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    self
+      .as_ref()
+      .data
+      .min_intrinsic_width(height, ctx)
+  }
+
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    self
+      .as_ref()
+      .data
+      .min_intrinsic_height(width, ctx)
+  }
 }
 
 #[derive(Clone)]
@@ -1010,6 +1298,142 @@ mod tests {
     assert_eq!(ids[1], new_ids[1]);
   }
 
+  #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+  #[test]
+  fn distinct_pipe_first_change() {
+    reset_test_env!();
+
+    let (builds, w_builds) = split_value(0);
+    let (v, w_v) = split_value(0);
+    let mut wnd = TestWindow::new(fn_widget! {
+      distinct_pipe!(*$v > 0).map(move |_| {
+        *$w_builds.write() += 1;
+        Void
+      })
+    });
+    wnd.draw_frame();
+    assert_eq!(*builds.read(), 1);
+
+    *w_v.write() = 1;
+    wnd.draw_frame();
+    assert_eq!(*builds.read(), 2);
+
+    *w_v.write() = 2;
+    wnd.draw_frame();
+    assert_eq!(*builds.read(), 2);
+  }
+
+  #[test]
+  fn distinct_and_filter_pipe() {
+    reset_test_env!();
+
+    let (builds, w_builds) = split_value(vec![]);
+    let (v, w_v) = split_value(0);
+    let mut wnd = TestWindow::new(fn_widget! {
+      pipe!(*$v)
+        .distinct_until_changed()
+        .filter(|v| v % 2 == 0)
+        .map(move |v| {
+          $w_builds.write().push(v);
+          Void
+        })
+    });
+    wnd.draw_frame();
+    assert_eq!(*builds.read(), vec![0]);
+
+    // The same value as the initial one.
+    *w_v.write() = 0;
+    wnd.draw_frame();
+    *w_v.write() = 1;
+    wnd.draw_frame();
+    *w_v.write() = 2;
+    wnd.draw_frame();
+    assert_eq!(*builds.read(), vec![0, 2]);
+  }
+
+  #[test]
+  fn debounce_pipe() {
+    reset_test_env!();
+
+    let (builds, w_builds) = split_value(vec![]);
+    let (v, w_v) = split_value(0);
+    let mut wnd = TestWindow::new(fn_widget! {
+      pipe!(*$v)
+        .debounce(Duration::from_millis(100))
+        .map(move |v| {
+          $w_builds.write().push(v);
+          Void
+        })
+    });
+    wnd.draw_frame();
+
+    *w_v.write() = 1;
+    wnd.tick(Duration::from_millis(60));
+    *w_v.write() = 2;
+    wnd.tick(Duration::from_millis(60));
+    assert_eq!(*builds.read(), vec![0]);
+
+    wnd.tick(Duration::from_millis(60));
+    wnd.draw_frame();
+    assert_eq!(*builds.read(), vec![0, 2]);
+  }
+
+  #[test]
+  fn throttle_pipe() {
+    reset_test_env!();
+
+    let (builds, w_builds) = split_value(vec![]);
+    let (v, w_v) = split_value(0);
+    let mut wnd = TestWindow::new(fn_widget! {
+      pipe!(*$v)
+        .throttle(Duration::from_millis(100))
+        .map(move |v| {
+          $w_builds.write().push(v);
+          Void
+        })
+    });
+    wnd.draw_frame();
+
+    // The first value of the period is emitted at once.
+    *w_v.write() = 1;
+    wnd.tick(Duration::from_millis(30));
+    *w_v.write() = 2;
+    wnd.tick(Duration::from_millis(30));
+    *w_v.write() = 3;
+    wnd.tick(Duration::from_millis(30));
+    assert_eq!(*builds.read(), vec![0, 1]);
+
+    // The latest value is emitted when the period ends.
+    wnd.tick(Duration::from_millis(30));
+    wnd.draw_frame();
+    assert_eq!(*builds.read(), vec![0, 1, 3]);
+  }
+
+  #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+  #[test]
+  fn distinct_pipe_not_copy() {
+    reset_test_env!();
+
+    let (builds, w_builds) = split_value(0);
+    let (v, w_v) = split_value(0);
+    let mut wnd = TestWindow::new(fn_widget! {
+      distinct_pipe!((*$v / 2).to_string()).map(move |_| {
+        *$w_builds.write() += 1;
+        Void
+      })
+    });
+    wnd.draw_frame();
+    assert_eq!(*builds.read(), 1);
+
+    *w_v.write() = 1;
+    wnd.draw_frame();
+    assert_eq!(*builds.read(), 1);
+
+    *w_v.write() = 2;
+    wnd.draw_frame();
+    assert_eq!(*builds.read(), 2);
+  }
+
   #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
   #[test]
   fn expr_widget_with_declare_child() {
@@ -1127,6 +1551,61 @@ mod tests {
     assert_eq!(*c_drop_cnt.read(), 7);
   }
 
+  #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+  #[test]
+  fn keyed_multi_pipe_reuse_widgets() {
+    reset_test_env!();
+
+    let (v, w_v) = split_value(vec![1, 2, 3]);
+    let (new_cnt, w_new_cnt) = split_value(0);
+    let (drop_cnt, w_drop_cnt) = split_value(0);
+    let w = fn_widget! {
+      @MockMulti {
+        @ {
+          pipe!($v.clone()).map(move |v| {
+            v.into_iter().map(move |i| {
+              Keyed::new(i, @MockBox {
+                size: Size::new(i as f32, 1.),
+                on_mounted: move |_| *$w_new_cnt.write() += 1,
+                on_disposed: move |_| *$w_drop_cnt.write() += 1
+              })
+            })
+          })
+        }
+      }
+    };
+
+    let mut wnd = TestWindow::new(w);
+    wnd.draw_frame();
+    assert_eq!(*new_cnt.read(), 3);
+    wnd.assert_root_size(Size::new(6., 1.));
+
+    w_v.write().insert(1, 4);
+    wnd.draw_frame();
+    assert_eq!(*new_cnt.read(), 4);
+    assert_eq!(*drop_cnt.read(), 0);
+    assert_eq!(wnd.layout_info_by_path(&[0, 1]).unwrap().size, Some(Size::new(4., 1.)));
+
+    // Move the first one to the end and remove the middle ones.
+    *w_v.write() = vec![3, 1];
+    wnd.draw_frame();
+    assert_eq!(*new_cnt.read(), 4);
+    assert_eq!(*drop_cnt.read(), 2);
+    assert_eq!(wnd.layout_info_by_path(&[0, 0]).unwrap().size, Some(Size::new(3., 1.)));
+    let info = wnd.layout_info_by_path(&[0, 1]).unwrap();
+    assert_eq!(info.size, Some(Size::new(1., 1.)));
+    assert_eq!(info.pos, Point::new(3., 0.));
+
+    w_v.write().clear();
+    wnd.draw_frame();
+    assert_eq!(*drop_cnt.read(), 4);
+
+    *w_v.write() = vec![5];
+    wnd.draw_frame();
+    assert_eq!(*new_cnt.read(), 5);
+    wnd.assert_root_size(Size::new(5., 1.));
+  }
+
   #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
   #[test]
   fn pipe_widget_in_pipe() {
@@ -39,6 +39,9 @@ where
   #[inline]
   fn paint(&self, ctx: &mut PaintingCtx) { self.proxy().paint(ctx) }
 
+  #[inline]
+  fn debug_name(&self) -> &'static str { self.proxy().debug_name() }
+
   #[inline]
   fn only_sized_by_parent(&self) -> bool { self.proxy().only_sized_by_parent() }
 
@@ -49,6 +52,21 @@ where
 
   #[inline]
   fn get_transform(&self) -> Option<Transform> { self.proxy().get_transform() }
+
+  #[inline]
+  fn baseline_offset(&self, ctx: &mut LayoutCtx) -> Option<f32> {
+    self.proxy().baseline_offset(ctx)
+  }
+
+  #[inline]
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    self.proxy().min_intrinsic_width(height, ctx)
+  }
+
+  #[inline]
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    self.proxy().min_intrinsic_height(width, ctx)
+  }
 }
 
 impl<R: Render> RenderProxy for RefCell<R> {
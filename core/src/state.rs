@@ -1,12 +1,20 @@
+mod lens;
 mod map_state;
+#[cfg(all(feature = "persistence", not(target_arch = "wasm32")))]
+mod persistent;
 mod prior_op;
 mod splitted_state;
 mod stateful;
+mod transaction;
+mod two_way;
 mod watcher;
 use std::{cell::UnsafeCell, convert::Infallible, mem::MaybeUninit, ops::DerefMut};
 pub mod state_cell;
 
+pub use lens::*;
 pub use map_state::*;
+#[cfg(all(feature = "persistence", not(target_arch = "wasm32")))]
+pub use persistent::*;
 pub use prior_op::*;
 use ribir_algo::Sc;
 use rxrust::ops::box_it::{BoxOp, CloneableBoxOp};
@@ -14,6 +22,8 @@ pub use splitted_state::*;
 pub use state_cell::*;
 use state_cell::{StateCell, ValueMutRef};
 pub use stateful::*;
+pub use transaction::*;
+pub use two_way::*;
 pub use watcher::*;
 
 use crate::{prelude::*, render_helper::RenderProxy};
@@ -174,6 +184,25 @@ pub trait StateWriter: StateWatcher {
     let origin = self.clone_writer();
     MapWriter { origin, part_map }
   }
+
+  /// Return a lens writer that focuses on a part of this writer by applying a
+  /// function to the contained value, see [`LensWriter`].
+  ///
+  /// Like `map_writer(...)`, writing through the lens notifies this writer.
+  /// But the lens has its own notifier, which only notifies when the part it
+  /// focuses on is changed, so it's suitable to pass a part of a large model
+  /// to a child widget without notifying the child by the other parts.
+  ///
+  /// The same as `map_writer(...)`, the `part_map` function should not modify
+  /// the data, but return a mutable reference to a part of it.
+  fn lens_writer<V, M>(&self, part_map: M) -> LensWriter<Self, M>
+  where
+    V: PartialEq + Clone + 'static,
+    M: Fn(&mut Self::Value) -> PartMut<V> + Clone + 'static,
+    Self: Sized + 'static,
+  {
+    LensWriter::new(self.map_writer(part_map))
+  }
 }
 
 pub struct WriteRef<'a, V: ?Sized> {
@@ -415,15 +444,13 @@ impl<'a, W: ?Sized> Drop for WriteRef<'a, W> {
     if batched_modifies.get().is_empty() && !modify_scope.is_empty() {
       batched_modifies.set(*modify_scope);
 
-      let info = info.clone();
-      let _ = AppCtx::spawn_local(async move {
-        let scope = info
-          .batched_modifies
-          .replace(ModifyScope::empty());
-        info.notifier.next(scope);
-      });
+      let Some(info) = Transaction::hold(info.clone()) else { return };
+      let _ = AppCtx::spawn_local(async move { Transaction::notify(&info) });
     } else {
       batched_modifies.set(*modify_scope | batched_modifies.get());
+      // The notification scheduled before a running transaction is taken over
+      // by the transaction.
+      Transaction::hold(info.clone());
     }
   }
 }
@@ -0,0 +1,184 @@
+use std::rc::Rc;
+
+use super::*;
+
+/// A writer focuses on a part of the origin writer, created by
+/// [`StateWriter::lens_writer`].
+///
+/// It shares the data with the origin writer, and writing through it notifies
+/// the origin writer as usual. But unlike [`MapWriter`], it has its own
+/// notifier, which notifies only when the part it focuses on is changed, so
+/// the watchers of the lens are not notified by the modifies of the other
+/// parts of the origin.
+///
+/// The lens follows the origin writer as long as any writer of the lens is
+/// alive.
+pub struct LensWriter<O, M> {
+  writer: MapWriter<O, M>,
+  info: Sc<WriterInfo>,
+  _guard: Rc<SubscriptionGuard<BoxSubscription<'static>>>,
+}
+
+impl<O, M> Drop for LensWriter<O, M> {
+  fn drop(&mut self) { self.info.dec_writer() }
+}
+
+impl<V, O, M> LensWriter<O, M>
+where
+  V: PartialEq + Clone + 'static,
+  O: StateWriter,
+  M: Fn(&mut O::Value) -> PartMut<V> + Clone + 'static,
+  MapWriter<O, M>: StateWriter<Value = V>,
+{
+  pub(super) fn new(writer: MapWriter<O, M>) -> Self {
+    let info = Sc::new(WriterInfo::new());
+    let reader = writer.clone_reader();
+    let mut last = reader.read().clone();
+    let c_info = info.clone();
+    let guard = writer
+      .origin
+      .raw_modifies()
+      .subscribe(move |scope: ModifyScope| {
+        let changed = *reader.read() != last;
+        if changed {
+          last = reader.read().clone();
+        }
+        // A modify that only effects the framework is always passed through.
+        if changed || !scope.contains(ModifyScope::DATA) {
+          c_info.notifier.next(scope);
+        }
+      })
+      .unsubscribe_when_dropped();
+
+    Self { writer, info, _guard: Rc::new(guard) }
+  }
+}
+
+impl<V: ?Sized, O, M> StateReader for LensWriter<O, M>
+where
+  Self: 'static,
+  O: StateWriter,
+  M: Fn(&mut O::Value) -> PartMut<V> + Clone,
+{
+  type Value = V;
+  type Reader = MapReader<O::Reader, WriterMapReaderFn<M>>;
+
+  #[inline]
+  fn read(&self) -> ReadRef<'_, Self::Value> { self.writer.read() }
+
+  #[inline]
+  fn clone_boxed_reader(&self) -> Box<dyn StateReader<Value = Self::Value>> {
+    Box::new(self.clone_reader())
+  }
+
+  #[inline]
+  fn clone_reader(&self) -> Self::Reader { self.writer.clone_reader() }
+}
+
+impl<V: ?Sized, O, M> StateWatcher for LensWriter<O, M>
+where
+  Self: 'static,
+  O: StateWriter,
+  M: Fn(&mut O::Value) -> PartMut<V> + Clone,
+{
+  type Watcher = Watcher<Self::Reader>;
+
+  #[inline]
+  fn clone_boxed_watcher(&self) -> Box<dyn StateWatcher<Value = Self::Value>> {
+    Box::new(self.clone_watcher())
+  }
+
+  #[inline]
+  fn raw_modifies(&self) -> CloneableBoxOp<'static, ModifyScope, Infallible> {
+    self.info.notifier.raw_modifies()
+  }
+
+  #[inline]
+  fn clone_watcher(&self) -> Watcher<Self::Reader> {
+    Watcher::new(self.clone_reader(), self.raw_modifies())
+  }
+}
+
+impl<V: ?Sized, O, M> StateWriter for LensWriter<O, M>
+where
+  Self: 'static,
+  O: StateWriter,
+  M: Fn(&mut O::Value) -> PartMut<V> + Clone,
+{
+  fn into_reader(self) -> Result<Self::Reader, Self> {
+    if self.info.writer_count.get() == 1 { Ok(self.clone_reader()) } else { Err(self) }
+  }
+
+  #[inline]
+  fn write(&self) -> WriteRef<'_, Self::Value> { self.writer.write() }
+
+  #[inline]
+  fn silent(&self) -> WriteRef<'_, Self::Value> { self.writer.silent() }
+
+  #[inline]
+  fn shallow(&self) -> WriteRef<'_, Self::Value> { self.writer.shallow() }
+
+  #[inline]
+  fn clone_boxed_writer(&self) -> Box<dyn StateWriter<Value = Self::Value>> {
+    Box::new(self.clone_writer())
+  }
+
+  fn clone_writer(&self) -> Self {
+    self.info.inc_writer();
+    LensWriter {
+      writer: self.writer.clone_writer(),
+      info: self.info.clone(),
+      _guard: self._guard.clone(),
+    }
+  }
+}
+
+impl<'w, O, M> IntoWidgetStrict<'w, RENDER> for LensWriter<O, M>
+where
+  Self: StateWriter<Value: Render + Sized>,
+{
+  fn into_widget_strict(self) -> Widget<'w> { WriterRender(self).into_widget() }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[derive(Default)]
+  struct Model {
+    name: String,
+    count: i32,
+  }
+
+  #[test]
+  fn lens_only_notify_its_part() {
+    reset_test_env!();
+
+    let model = Stateful::new(Model::default());
+    let count = model.lens_writer(|m| PartMut::new(&mut m.count));
+    let (notified, w_notified) = split_value(0);
+    let _u = count
+      .modifies()
+      .subscribe(move |_| *w_notified.write() += 1);
+
+    model.write().name = "lens".into();
+    AppCtx::run_until_stalled();
+    assert_eq!(*notified.read(), 0);
+
+    model.write().count = 1;
+    AppCtx::run_until_stalled();
+    assert_eq!(*notified.read(), 1);
+
+    // Write through the lens notifies both the lens and the origin.
+    let (origin_notified, w_origin) = split_value(0);
+    let _u2 = model
+      .modifies()
+      .subscribe(move |_| *w_origin.write() += 1);
+    *count.write() = 2;
+    AppCtx::run_until_stalled();
+    assert_eq!(*notified.read(), 2);
+    assert_eq!(*origin_notified.read(), 1);
+    assert_eq!(model.read().count, 2);
+  }
+}
@@ -0,0 +1,197 @@
+use std::{
+  io,
+  ops::Deref,
+  path::{Path, PathBuf},
+  sync::Mutex,
+};
+
+use log::warn;
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::prelude::*;
+
+/// How long the state waits after the last modification before saving.
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(300);
+
+static STORAGE_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// A state that survives the restarts of the application, enabled by the
+/// `persistence` feature.
+///
+/// The initial value is loaded from the storage by the key, or the `default`
+/// if nothing is saved. The value is saved as a JSON file every time the
+/// state stops changing for a while, and once more when the `PersistentState`
+/// is dropped. The files are saved in the directory returned by
+/// [`persistent_dir`], named by the key with the characters other than the
+/// ASCII letters, digits, `-` and `_` percent-encoded, so any key stays in the
+/// directory.
+///
+/// It derefs to the [`Stateful`] that holds the value, so use it like a
+/// `Stateful`. The modifications by the writers cloned from it are only saved
+/// while the `PersistentState` is alive.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir_core::prelude::*;
+///
+/// let dark_mode = PersistentState::new("dark_mode", false);
+/// let _w = fn_widget! {
+///   @Void { on_tap: move |_| *$dark_mode.write() ^= true }
+/// };
+/// ```
+pub struct PersistentState<V: Serialize + 'static> {
+  state: Stateful<V>,
+  path: PathBuf,
+  _save: SubscriptionGuard<BoxSubscription<'static>>,
+}
+
+impl<V: Serialize + DeserializeOwned + 'static> PersistentState<V> {
+  pub fn new(key: &str, default: V) -> Self {
+    let path = persistent_dir().join(format!("{}.json", file_stem(key)));
+    let init = std::fs::read(&path)
+      .ok()
+      .and_then(|data| {
+        serde_json::from_slice(&data)
+          .map_err(|err| warn!("Failed to load the persistent state `{key}`: {err}"))
+          .ok()
+      })
+      .unwrap_or(default);
+    let state = Stateful::new(init);
+
+    let (reader, c_path) = (state.clone_reader(), path.clone());
+    let save = state
+      .modifies()
+      .debounce(SAVE_DEBOUNCE, AppCtx::scheduler())
+      .subscribe(move |_| save_value(&c_path, &*reader.read()));
+    let save = BoxSubscription::new(save).unsubscribe_when_dropped();
+
+    Self { state, path, _save: save }
+  }
+}
+
+impl<V: Serialize + 'static> PersistentState<V> {
+  /// Save the value at once, rather than waiting for the debounced saving.
+  pub fn save(&self) -> io::Result<()> { write_value(&self.path, &*self.state.read()) }
+}
+
+/// Sets the directory the [`PersistentState`]s are saved in.
+pub fn set_persistent_dir(dir: impl Into<PathBuf>) {
+  *STORAGE_DIR.lock().unwrap() = Some(dir.into());
+}
+
+/// Return the directory the [`PersistentState`]s are saved in. By default,
+/// it's the directory named by the executable under the data directory of the
+/// platform.
+pub fn persistent_dir() -> PathBuf {
+  if let Some(dir) = STORAGE_DIR.lock().unwrap().as_ref() {
+    return dir.clone();
+  }
+  let app = std::env::current_exe()
+    .ok()
+    .and_then(|exe| {
+      exe
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+    })
+    .unwrap_or_else(|| "ribir".into());
+  dirs::data_dir()
+    .unwrap_or_else(std::env::temp_dir)
+    .join(app)
+}
+
+impl<V: Serialize + 'static> Deref for PersistentState<V> {
+  type Target = Stateful<V>;
+
+  fn deref(&self) -> &Self::Target { &self.state }
+}
+
+impl<V: Serialize + 'static> Drop for PersistentState<V> {
+  fn drop(&mut self) { save_value(&self.path, &*self.state.read()); }
+}
+
+/// Percent-encode the key to a flat file name, so the separators and `..` in
+/// it can't escape the directory.
+fn file_stem(key: &str) -> String {
+  let mut stem = String::with_capacity(key.len());
+  for b in key.bytes() {
+    if b.is_ascii_alphanumeric() || b == b'-' || b == b'_' {
+      stem.push(b as char);
+    } else {
+      stem.push_str(&format!("%{b:02X}"));
+    }
+  }
+  stem
+}
+
+fn save_value<V: Serialize>(path: &Path, value: &V) {
+  if let Err(err) = write_value(path, value) {
+    warn!("Failed to save the persistent state to {path:?}: {err}");
+  }
+}
+
+fn write_value<V: Serialize>(path: &Path, value: &V) -> io::Result<()> {
+  if let Some(dir) = path.parent() {
+    std::fs::create_dir_all(dir)?;
+  }
+  let data = serde_json::to_vec(value)?;
+  std::fs::write(path, data)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[test]
+  fn load_and_save() {
+    reset_test_env!();
+
+    let dir = std::env::temp_dir().join("ribir_persistent_state_test");
+    let _ = std::fs::remove_dir_all(&dir);
+    set_persistent_dir(&dir);
+
+    let mut wnd = TestWindow::new(fn_widget! { @Void {} });
+    let count = PersistentState::new("count", 1);
+    assert_eq!(*count.read(), 1);
+
+    *count.write() = 2;
+    wnd.tick(Duration::from_millis(100));
+    assert!(!dir.join("count.json").exists());
+
+    *count.write() = 3;
+    wnd.tick(Duration::from_millis(400));
+    assert_eq!(std::fs::read_to_string(dir.join("count.json")).unwrap(), "3");
+
+    // The change not saved yet is saved when dropped.
+    *count.write() = 4;
+    drop(count);
+    assert_eq!(*PersistentState::new("count", 1).read(), 4);
+  }
+
+  #[test]
+  fn flat_file_name() {
+    assert_eq!(file_stem("dark_mode-2"), "dark_mode-2");
+    assert_eq!(file_stem("../a/b\\c"), "%2E%2E%2Fa%2Fb%5Cc");
+    assert_eq!(file_stem("设"), "%E8%AE%BE");
+  }
+
+  #[test]
+  fn stop_saving_when_dropped() {
+    reset_test_env!();
+
+    let dir = std::env::temp_dir().join("ribir_persistent_state_drop_test");
+    let _ = std::fs::remove_dir_all(&dir);
+    set_persistent_dir(&dir);
+
+    let mut wnd = TestWindow::new(fn_widget! { @Void {} });
+    let count = PersistentState::new("count", 1);
+    let writer = count.clone_writer();
+    drop(count);
+    assert_eq!(std::fs::read_to_string(dir.join("count.json")).unwrap(), "1");
+
+    *writer.write() = 2;
+    wnd.tick(Duration::from_millis(400));
+    assert_eq!(std::fs::read_to_string(dir.join("count.json")).unwrap(), "1");
+  }
+}
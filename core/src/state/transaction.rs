@@ -0,0 +1,166 @@
+use std::{cell::RefCell, marker::PhantomData};
+
+use super::*;
+
+thread_local! {
+  static TRANSACTION: RefCell<Option<TransactionInfo>> = const { RefCell::new(None) };
+}
+
+#[derive(Default)]
+struct TransactionInfo {
+  depth: usize,
+  pending: Vec<Sc<WriterInfo>>,
+}
+
+/// A guard that holds the notifications of all the states modified during its
+/// lifetime, and notifies them together when it's dropped.
+///
+/// The modifies of a state are always batched to one notification, but the
+/// states notify separately. Modify several states in a transaction to let
+/// their watchers respond to them at once, for example, a widget depends on
+/// both of them only updates one time.
+///
+/// Transactions can be nested, the notifications are sent when the outermost
+/// one is dropped.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+///
+/// let width = Stateful::new(1.);
+/// let height = Stateful::new(1.);
+///
+/// Transaction::run(|| {
+///   *width.write() = 100.;
+///   *height.write() = 50.;
+/// });
+/// ```
+pub struct Transaction {
+  _not_send: PhantomData<*const ()>,
+}
+
+impl Transaction {
+  /// Begin a transaction, it ends when the returned guard is dropped.
+  pub fn begin() -> Self {
+    TRANSACTION.with(|t| {
+      t.borrow_mut()
+        .get_or_insert_with(TransactionInfo::default)
+        .depth += 1
+    });
+    Self { _not_send: PhantomData }
+  }
+
+  /// Run `f` in a transaction.
+  pub fn run<R>(f: impl FnOnce() -> R) -> R {
+    let _guard = Self::begin();
+    f()
+  }
+
+  /// Hold the notification of the `info` if a transaction is running, return
+  /// the `info` back if not.
+  pub(super) fn hold(info: Sc<WriterInfo>) -> Option<Sc<WriterInfo>> {
+    TRANSACTION.with(|t| match t.borrow_mut().as_mut() {
+      Some(t) => {
+        if !t.pending.iter().any(|p| Sc::ptr_eq(p, &info)) {
+          t.pending.push(info);
+        }
+        None
+      }
+      None => Some(info),
+    })
+  }
+
+  /// Send the batched notification of the `info`, unless a running transaction
+  /// holds it.
+  pub(super) fn notify(info: &Sc<WriterInfo>) {
+    let held = TRANSACTION.with(|t| {
+      t.borrow()
+        .as_ref()
+        .is_some_and(|t| t.pending.iter().any(|p| Sc::ptr_eq(p, info)))
+    });
+    if !held {
+      let scope = info
+        .batched_modifies
+        .replace(ModifyScope::empty());
+      // The notification may be sent by the transaction already.
+      if !scope.is_empty() {
+        info.notifier.next(scope);
+      }
+    }
+  }
+}
+
+impl Drop for Transaction {
+  fn drop(&mut self) {
+    let pending = TRANSACTION.with(|t| {
+      let mut t = t.borrow_mut();
+      let info = t.as_mut().unwrap();
+      info.depth -= 1;
+      if info.depth == 0 { t.take().map(|info| info.pending) } else { None }
+    });
+
+    if let Some(pending) = pending.filter(|p| !p.is_empty()) {
+      let _ = AppCtx::spawn_local(async move {
+        for info in pending {
+          Transaction::notify(&info);
+        }
+      });
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[test]
+  fn notify_together() {
+    reset_test_env!();
+
+    let a = Stateful::new(0);
+    let b = Stateful::new(0);
+    let (notified, w_notified) = split_value(vec![]);
+    let w2 = w_notified.clone_writer();
+    let _u1 = a
+      .modifies()
+      .subscribe(move |_| w_notified.write().push("a"));
+    let _u2 = b
+      .modifies()
+      .subscribe(move |_| w2.write().push("b"));
+
+    {
+      let _t = Transaction::begin();
+      *a.write() = 1;
+      Transaction::run(|| *b.write() = 1);
+      *a.write() = 2;
+      AppCtx::run_until_stalled();
+      assert!(notified.read().is_empty());
+    }
+    AppCtx::run_until_stalled();
+    assert_eq!(*notified.read(), ["a", "b"]);
+  }
+
+  #[test]
+  fn take_over_pending_notification() {
+    reset_test_env!();
+
+    let a = Stateful::new(0);
+    let (notified, w_notified) = split_value(vec![]);
+    let _u = a
+      .modifies()
+      .subscribe(move |_| w_notified.write().push(0));
+
+    // The notification is pending before the transaction begins.
+    *a.write() = 1;
+    {
+      let _t = Transaction::begin();
+      *a.write() = 2;
+      AppCtx::run_until_stalled();
+      assert!(notified.read().is_empty());
+    }
+    AppCtx::run_until_stalled();
+    assert_eq!(notified.read().len(), 1);
+  }
+}
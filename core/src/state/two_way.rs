@@ -0,0 +1,145 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::prelude::*;
+
+/// A two-way binding between a field of a declared widget and a state, create
+/// it by the `bind!` macro.
+///
+/// The widget field is initialized by the value of the state, every time the
+/// state is modified the field follows it, and every time the widget modifies
+/// the field the value is written back to the state. The binding remembers the
+/// last synced value, so a value is never sent back to where it comes from.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+///
+/// #[derive(Declare)]
+/// struct Counter {
+///   count: i32,
+/// }
+///
+/// impl Render for Counter {
+///   fn perform_layout(&self, clamp: BoxClamp, _: &mut LayoutCtx) -> Size { clamp.min }
+/// }
+///
+/// struct Form {
+///   count: i32,
+/// }
+///
+/// let form = Stateful::new(Form { count: 1 });
+/// let _w = fn_widget! {
+///   @Counter { count: bind!(&mut form.count) }
+/// };
+/// ```
+pub struct TwoWay<V> {
+  init: V,
+  from_state: ValueStream<V>,
+  to_state: WriteBack<V>,
+}
+
+/// The callback of a [`TwoWay`] to write a value back to the state.
+pub type WriteBack<V> = Box<dyn Fn(&V)>;
+
+impl<V: PartialEq + Clone + 'static> TwoWay<V> {
+  pub fn new(state: impl StateWriter<Value = V>) -> Self {
+    let init = state.read().clone();
+    let last = Rc::new(RefCell::new(init.clone()));
+
+    let reader = state.clone_reader();
+    let last2 = last.clone();
+    let from_state = state
+      .modifies()
+      .filter_map(move |scope| {
+        let v = reader.read().clone();
+        let mut last = last2.borrow_mut();
+        (*last != v).then(|| {
+          *last = v.clone();
+          (scope, v)
+        })
+      })
+      .box_it();
+
+    let to_state = Box::new(move |v: &V| {
+      if *last.borrow() != *v {
+        *last.borrow_mut() = v.clone();
+        *state.write() = v.clone();
+      }
+    });
+
+    Self { init, from_state, to_state }
+  }
+}
+
+impl<V: 'static> TwoWay<V> {
+  /// Split the binding to the init value, the stream of the values come from
+  /// the state, and the callback to write a value back to the state.
+  pub fn unzip(self) -> (V, ValueStream<V>, WriteBack<V>) {
+    (self.init, self.from_state, self.to_state)
+  }
+
+  /// Convert the values come from the state, the binding becomes one-way
+  /// because the converted value can't be written back.
+  pub fn map<U: 'static>(self, f: impl Fn(V) -> U + 'static) -> TwoWay<U> {
+    let Self { init, from_state, .. } = self;
+    let init = f(init);
+    let from_state = from_state.map(move |(s, v)| (s, f(v))).box_it();
+    TwoWay { init, from_state, to_state: Box::new(|_| {}) }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[derive(Declare)]
+  struct Input {
+    text: String,
+  }
+
+  impl Render for Input {
+    fn perform_layout(&self, clamp: BoxClamp, _: &mut LayoutCtx) -> Size { clamp.min }
+  }
+
+  struct Form {
+    name: String,
+  }
+
+  #[test]
+  fn two_way_bind_field() {
+    reset_test_env!();
+
+    let form = Stateful::new(Form { name: "init".into() });
+    let form_modified = Stateful::new(0);
+    let c_modified = form_modified.clone_writer();
+    let _u = form
+      .modifies()
+      .subscribe(move |_| *c_modified.write() += 1);
+
+    let input_slot: Rc<RefCell<Option<Box<dyn StateWriter<Value = Input>>>>> = <_>::default();
+    let c_slot = input_slot.clone();
+    let c_form = form.clone_writer();
+    let mut wnd = TestWindow::new(fn_widget! {
+      let input = @Input { text: bind!(&mut c_form.name) };
+      *c_slot.borrow_mut() = Some(input.clone_boxed_writer());
+      input
+    });
+    wnd.draw_frame();
+    let input = input_slot.borrow_mut().take().unwrap();
+    assert_eq!(input.read().text, "init");
+
+    // The external writes update the widget.
+    form.write().name = "from state".into();
+    wnd.draw_frame();
+    assert_eq!(input.read().text, "from state");
+    assert_eq!(*form_modified.read(), 1);
+
+    // The widget edits are written back, but not sent back to the widget again.
+    input.write().text = "from widget".into();
+    wnd.draw_frame();
+    assert_eq!(form.read().name, "from widget");
+    assert_eq!(*form_modified.read(), 2);
+  }
+}
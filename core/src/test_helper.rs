@@ -8,15 +8,19 @@ pub use wasm_bindgen_test::wasm_bindgen_test;
 #[cfg(target_family = "wasm")]
 wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
 
+use winit::event::{DeviceId, ElementState, MouseScrollDelta, Touch};
+pub use winit::event::{MouseButton, TouchPhase};
+
 pub use crate::timer::Timer;
 use crate::{
   prelude::*,
-  window::{ShellWindow, WindowFlags, WindowId},
+  window::{ImePurpose, ShellWindow, WindowFlags, WindowId},
 };
 
 pub struct Frame {
   pub commands: Vec<PaintCommand>,
   pub viewport: Rect,
+  pub damage: Vec<Rect>,
   pub surface: Color,
 }
 
@@ -25,6 +29,38 @@ pub fn split_value<T: 'static>(v: T) -> (Watcher<Reader<T>>, Stateful<T>) {
   (src.clone_watcher(), src.clone_writer())
 }
 
+/// An input simulated by [`TestWindow::process_input`] as if it comes from the
+/// platform. The positions are in logical pixels of the window.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TestInput {
+  /// Move the mouse cursor to the position.
+  CursorMove(Point),
+  /// The mouse cursor leaves the window.
+  CursorLeave,
+  MousePress(MouseButton),
+  MouseRelease(MouseButton),
+  /// Scroll the mouse wheel by the delta.
+  Wheel(Vector),
+  /// A finger `id` touches the window at the position.
+  Touch {
+    id: u64,
+    phase: TouchPhase,
+    pos: Point,
+  },
+  /// The pressed keyboard modifiers changed.
+  Modifiers(ModifiersState),
+  KeyPress {
+    key: VirtualKey,
+    physical_key: PhysicalKey,
+  },
+  KeyRelease {
+    key: VirtualKey,
+    physical_key: PhysicalKey,
+  },
+  /// Receive the characters typed by the keyboard.
+  Chars(String),
+}
+
 /// The Window assists in writing unit tests; animations are disabled by
 /// default.
 #[derive(Clone)]
@@ -35,6 +71,7 @@ macro_rules! reset_test_env {
   () => {
     let _ = $crate::prelude::NEW_TIMER_FN.set($crate::timer::Timer::new_timer_future);
     let _guard = $crate::prelude::AppCtx::new_lock_scope();
+    $crate::prelude::FrameClock::use_system();
   };
 }
 
@@ -69,6 +106,38 @@ impl TestWindow {
   /// [0, 1, 2] the first node at the root level (must be 0), then down to its
   /// second child, then down to third child.
   pub fn layout_info_by_path(&self, path: &[usize]) -> Option<LayoutInfo> {
+    let node = self.widget_id_by_path(path);
+    self.0.tree().store.layout_info(node).cloned()
+  }
+
+  /// Capture the paint commands emitted by the `paint` of the widget at the
+  /// `path`, see [`TestWindow::layout_info_by_path`] for the path. Its
+  /// descendants are not painted.
+  ///
+  /// The commands are relative to the box of the widget, so they don't change
+  /// with the position of the widget.
+  pub fn paint_commands_by_path(&self, path: &[usize]) -> Vec<PaintCommand> {
+    let tree = self.0.tree();
+    let id = self.widget_id_by_path(path);
+    let origin = self.map_to_global(Point::zero(), id);
+    let viewport = self
+      .0
+      .painter
+      .borrow()
+      .viewport()
+      .translate(-origin.to_vector());
+
+    let mut painter = Painter::new(viewport);
+    let mut ctx = PaintingCtx::new(id, tree, &mut painter);
+    id.assert_get(tree).paint(&mut ctx);
+    ctx.finish();
+    let commands = painter.finish().to_vec();
+    commands
+  }
+
+  /// The id of the widget at the `path`, see
+  /// [`TestWindow::layout_info_by_path`] for the path.
+  pub fn widget_id_by_path(&self, path: &[usize]) -> WidgetId {
     let tree = self.0.tree();
     let mut node = tree.root();
     for (level, idx) in path[..].iter().enumerate() {
@@ -76,7 +145,7 @@ impl TestWindow {
         panic!("node no exist: {:?}", &path[0..level]);
       });
     }
-    tree.store.layout_info(node).cloned()
+    node
   }
 
   pub fn take_last_frame(&mut self) -> Option<Frame> {
@@ -104,9 +173,123 @@ impl TestWindow {
     AppCtx::run_until_stalled();
     self.run_frame_tasks();
 
-    AppCtx::frame_ticks().clone().next(Instant::now());
+    AppCtx::frame_ticks()
+      .clone()
+      .next(FrameClock::now());
     self.0.draw_frame();
   }
+
+  /// Drive the window by a manual clock, the animations of the window are
+  /// enabled too. After that, the time of the animations and timers only
+  /// advances by [`TestWindow::tick`], so every intermediate frame can be
+  /// checked reproducibly.
+  ///
+  /// Call it before the animations start, otherwise they have started with the
+  /// system time.
+  pub fn use_manual_clock(&self) {
+    FrameClock::use_manual();
+    self.set_flags(self.flags() | WindowFlags::ANIMATIONS);
+  }
+
+  /// Advance the manual clock by `dur` and draw a frame. It switches to the
+  /// manual clock by [`TestWindow::use_manual_clock`] if it's not in use.
+  #[track_caller]
+  pub fn tick(&mut self, dur: Duration) {
+    if !FrameClock::is_manual() {
+      self.use_manual_clock();
+    }
+    // Run the pending tasks first, so the timers scheduled before are started
+    // at the current time.
+    AppCtx::run_until_stalled();
+    FrameClock::advance(dur);
+    self.draw_frame();
+  }
+
+  /// Dispatch the input to the window, the events it fires are delivered to
+  /// the widgets when the next frame is drawn.
+  pub fn process_input(&self, input: TestInput) {
+    let device_id = unsafe { DeviceId::dummy() };
+    let ratio = self.device_pixel_ratio() as f64;
+    let mut dispatcher = self.dispatcher.borrow_mut();
+    match input {
+      TestInput::CursorMove(pos) => dispatcher.cursor_move_to(pos),
+      TestInput::CursorLeave => dispatcher.on_cursor_left(),
+      TestInput::MousePress(button) => {
+        dispatcher.dispatch_mouse_input(device_id, ElementState::Pressed, button)
+      }
+      TestInput::MouseRelease(button) => {
+        dispatcher.dispatch_mouse_input(device_id, ElementState::Released, button)
+      }
+      TestInput::Wheel(delta) => {
+        let delta = (delta.x as f64 * ratio, delta.y as f64 * ratio).into();
+        dispatcher.dispatch_wheel(MouseScrollDelta::PixelDelta(delta), ratio)
+      }
+      TestInput::Touch { id, phase, pos } => {
+        let location = (pos.x as f64 * ratio, pos.y as f64 * ratio).into();
+        let touch = Touch { device_id, phase, location, force: None, id };
+        dispatcher.dispatch_touch(touch, ratio)
+      }
+      TestInput::Modifiers(state) => {
+        dispatcher.dispatch(winit::event::WindowEvent::ModifiersChanged(state.into()), ratio)
+      }
+      TestInput::KeyPress { key, physical_key } => dispatcher.dispatch_keyboard_input(
+        physical_key,
+        key,
+        false,
+        KeyLocation::Standard,
+        ElementState::Pressed,
+      ),
+      TestInput::KeyRelease { key, physical_key } => dispatcher.dispatch_keyboard_input(
+        physical_key,
+        key,
+        false,
+        KeyLocation::Standard,
+        ElementState::Released,
+      ),
+      TestInput::Chars(chars) => dispatcher.dispatch_receive_chars(chars),
+    }
+  }
+
+  /// Play a sequence of inputs, each with its time since the sequence starts.
+  ///
+  /// The window is driven by the manual clock, see
+  /// [`TestWindow::use_manual_clock`]. Before each input, the clock advances to
+  /// its time and a frame is drawn, so the timers and animations between the
+  /// inputs run reproducibly. A frame is drawn after the last input too.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the times of the inputs are not in ascending order.
+  #[track_caller]
+  pub fn simulate_inputs(&mut self, inputs: impl IntoIterator<Item = (Duration, TestInput)>) {
+    let mut elapsed = Duration::ZERO;
+    for (at, input) in inputs {
+      assert!(at >= elapsed, "The inputs must be in ascending order of their times.");
+      self.tick(at - elapsed);
+      elapsed = at;
+      self.process_input(input);
+    }
+    self.tick(Duration::ZERO);
+  }
+
+  /// Draw the window and return the frame, even if nothing changed since the
+  /// last frame. Render it to an image to compare with a golden image, see
+  /// the `assert_frame_eq_image!` of `ribir_dev_helper`.
+  #[track_caller]
+  pub fn draw_and_take_frame(&mut self) -> Frame {
+    self.draw_frame();
+    if let Some(frame) = self.take_last_frame() {
+      return frame;
+    }
+    let tree = self.tree();
+    tree
+      .dirty_marker()
+      .mark(tree.root(), DirtyPhase::Paint);
+    self.draw_frame();
+    self
+      .take_last_frame()
+      .expect("The window has no content to draw.")
+  }
 }
 
 impl std::ops::Deref for TestWindow {
@@ -118,9 +301,19 @@ impl std::ops::Deref for TestWindow {
 pub struct TestShellWindow {
   pub size: Size,
   pub cursor: CursorIcon,
+  pub ime_purpose: ImePurpose,
   pub id: WindowId,
   pub surface_color: Color,
   pub last_frame: Option<Frame>,
+  pub pointer_locked: bool,
+  pub title: String,
+  pub min_size: Option<Size>,
+  pub max_size: Option<Size>,
+  pub resizable: bool,
+  pub decorations: bool,
+  pub fullscreen: bool,
+  pub device_pixel_ratio: f32,
+  pub viewport_insets: EdgeInsets,
 }
 
 impl ShellWindow for TestShellWindow {
@@ -135,13 +328,15 @@ impl ShellWindow for TestShellWindow {
     self.last_frame = None;
   }
 
-  fn set_min_size(&mut self, _: Size) {}
+  fn set_min_size(&mut self, size: Size) { self.min_size = Some(size); }
+
+  fn set_max_size(&mut self, size: Size) { self.max_size = Some(size); }
 
   fn set_cursor(&mut self, cursor: CursorIcon) { self.cursor = cursor; }
 
   fn cursor(&self) -> CursorIcon { self.cursor }
 
-  fn set_title(&mut self, _: &str) {}
+  fn set_title(&mut self, title: &str) { self.title = title.to_string(); }
 
   fn set_icon(&mut self, _: &PixelImage) {}
 
@@ -151,13 +346,19 @@ impl ShellWindow for TestShellWindow {
 
   fn is_visible(&self) -> Option<bool> { Some(true) }
 
-  fn set_resizable(&mut self, _: bool) {}
+  fn set_resizable(&mut self, resizable: bool) { self.resizable = resizable; }
 
-  fn is_resizable(&self) -> bool { true }
+  fn is_resizable(&self) -> bool { self.resizable }
 
   fn focus_window(&mut self) {}
 
-  fn set_decorations(&mut self, _: bool) {}
+  fn set_decorations(&mut self, decorations: bool) { self.decorations = decorations; }
+
+  fn is_fullscreen(&self) -> bool { self.fullscreen }
+
+  fn set_fullscreen(&mut self, fullscreen: bool) { self.fullscreen = fullscreen; }
+
+  fn set_pointer_lock(&mut self, lock: bool) { self.pointer_locked = lock; }
 
   fn is_minimized(&self) -> bool { false }
 
@@ -165,22 +366,30 @@ impl ShellWindow for TestShellWindow {
 
   fn set_ime_allowed(&mut self, _: bool) {}
 
+  fn set_ime_purpose(&mut self, purpose: ImePurpose) { self.ime_purpose = purpose; }
+
   fn as_any(&self) -> &dyn Any { self }
 
   fn as_any_mut(&mut self) -> &mut dyn Any { self }
 
   fn begin_frame(&mut self, surface: Color) { self.surface_color = surface; }
 
-  fn draw_commands(&mut self, viewport: Rect, commands: &[PaintCommand]) {
-    self.last_frame =
-      Some(Frame { commands: commands.to_owned(), viewport, surface: self.surface_color });
+  fn draw_commands(&mut self, viewport: Rect, damage: &[Rect], commands: &[PaintCommand]) {
+    self.last_frame = Some(Frame {
+      commands: commands.to_owned(),
+      viewport,
+      damage: damage.to_owned(),
+      surface: self.surface_color,
+    });
   }
 
   fn end_frame(&mut self) {}
 
   fn id(&self) -> WindowId { self.id }
 
-  fn device_pixel_ratio(&self) -> f32 { 1. }
+  fn device_pixel_ratio(&self) -> f32 { self.device_pixel_ratio }
+
+  fn viewport_insets(&self) -> EdgeInsets { self.viewport_insets }
 }
 
 impl TestShellWindow {
@@ -190,9 +399,19 @@ impl TestShellWindow {
     TestShellWindow {
       size,
       cursor: CursorIcon::Default,
+      ime_purpose: ImePurpose::Normal,
       id: ID.fetch_add(1, Ordering::Relaxed).into(),
       last_frame: None,
       surface_color: Color::WHITE,
+      pointer_locked: false,
+      title: String::new(),
+      min_size: None,
+      max_size: None,
+      resizable: true,
+      decorations: true,
+      fullscreen: false,
+      device_pixel_ratio: 1.,
+      viewport_insets: EdgeInsets::ZERO,
     }
   }
 }
@@ -414,3 +633,51 @@ impl WidgetTester {
 impl Default for LayoutCase {
   fn default() -> Self { Self { path: &[0], x: None, y: None, width: None, height: None } }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn simulate_timed_inputs() {
+    reset_test_env!();
+
+    let (log, w_log) = split_value(vec![]);
+    let w_log2 = w_log.clone_writer();
+    let w_log3 = w_log.clone_writer();
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        tab_index: 0i16,
+        on_tap: move |_| $w_log.write().push("tap".to_string()),
+        on_long_press: move |_| $w_log2.write().push("long press".to_string()),
+        on_chars: move |e| $w_log3.write().push(e.chars.to_string()),
+      }
+    });
+    wnd.set_long_press_duration(Duration::from_millis(100));
+    wnd.draw_frame();
+
+    let ms = Duration::from_millis;
+    wnd.simulate_inputs([
+      (ms(0), TestInput::CursorMove(Point::new(10., 10.))),
+      (ms(0), TestInput::MousePress(MouseButton::Left)),
+      (ms(50), TestInput::MouseRelease(MouseButton::Left)),
+      (ms(100), TestInput::MousePress(MouseButton::Left)),
+      (ms(250), TestInput::MouseRelease(MouseButton::Left)),
+      (ms(300), TestInput::Chars("a".into())),
+    ]);
+    assert_eq!(*log.read(), ["tap", "long press", "a"]);
+  }
+
+  #[test]
+  fn capture_unchanged_frame() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! { @MockBox { size: Size::new(10., 10.) } });
+    wnd.draw_frame();
+    assert!(wnd.take_last_frame().is_some());
+
+    let frame = wnd.draw_and_take_frame();
+    assert_eq!(frame.viewport, Rect::from_size(Size::new(1024., 1024.)));
+  }
+}
@@ -1,6 +1,6 @@
-use std::convert::Infallible;
 #[cfg(not(target_family = "wasm"))]
 pub use std::time::{Duration, Instant};
+use std::{cell::Cell, convert::Infallible};
 
 use rxrust::prelude::Subject;
 #[cfg(target_family = "wasm")]
@@ -36,3 +36,46 @@ pub enum FrameMsg {
   /// receive this message.
   Finish(Instant),
 }
+
+thread_local! {
+  static MANUAL_NOW: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// The clock that drives the frames, animations and timers of the framework.
+///
+/// It follows the system time by default. A test can switch it to a manual
+/// clock, then the time only advances by [`FrameClock::advance`], so the
+/// animations and timers run deterministically. See `TestWindow::tick`.
+pub struct FrameClock;
+
+impl FrameClock {
+  /// Return the current time of the framework.
+  pub fn now() -> Instant {
+    MANUAL_NOW
+      .with(|now| now.get())
+      .unwrap_or_else(Instant::now)
+  }
+
+  /// Switch to the manual clock, it stops at the current time until it's
+  /// advanced. Does nothing if the manual clock is already in use.
+  pub fn use_manual() {
+    MANUAL_NOW.with(|now| {
+      if now.get().is_none() {
+        now.set(Some(Instant::now()));
+      }
+    });
+  }
+
+  /// Switch back to the system time.
+  pub fn use_system() { MANUAL_NOW.with(|now| now.set(None)); }
+
+  /// Whether the manual clock is in use.
+  pub fn is_manual() -> bool { MANUAL_NOW.with(|now| now.get().is_some()) }
+
+  /// Advance the manual clock by `dur`, switch to the manual clock first if
+  /// it's not in use.
+  pub fn advance(dur: Duration) {
+    Self::use_manual();
+    MANUAL_NOW.with(|now| now.set(now.get().map(|t| t + dur)));
+  }
+}
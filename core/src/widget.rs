@@ -71,6 +71,54 @@ pub trait Render: 'static {
 
   /// Return a transform to map the coordinate to parent coordinate.
   fn get_transform(&self) -> Option<Transform> { None }
+
+  /// The type name of the widget, it's used by the debug tools like the
+  /// inspector of the window. The widgets wrapping another widget should
+  /// return the name of the wrapped one.
+  fn debug_name(&self) -> &'static str { std::any::type_name::<Self>() }
+
+  /// Return the distance from the top of the widget to its first text
+  /// baseline after it's laid out, or `None` if it has no baseline. The parent
+  /// can query it by [`LayoutCtx::baseline_of`] to align its children by their
+  /// baselines.
+  ///
+  /// By default, it's the first baseline of its children.
+  fn baseline_offset(&self, ctx: &mut LayoutCtx) -> Option<f32> {
+    let (ctx, mut children) = ctx.split_children();
+    children.find_map(|c| {
+      let y = ctx.position(c)?.y;
+      ctx.baseline_of(c).map(|b| b + y)
+    })
+  }
+
+  /// Return the smallest width the widget can be laid out in without
+  /// overflowing its content, when its height is `height`. The `height` may be
+  /// infinite if it's unbounded.
+  ///
+  /// It measures the content without laying out the widget, the parent can
+  /// query it by [`LayoutCtx::min_intrinsic_width_of`] before deciding the
+  /// constraints of its children.
+  ///
+  /// By default, it's the largest one of its children.
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    let (ctx, children) = ctx.split_children();
+    children
+      .map(|c| ctx.min_intrinsic_width_of(c, height))
+      .fold(0., f32::max)
+  }
+
+  /// Return the smallest height the widget can be laid out in without
+  /// overflowing its content, when its width is `width`. The `width` may be
+  /// infinite if it's unbounded.
+  ///
+  /// The parent can query it by [`LayoutCtx::min_intrinsic_height_of`]. By
+  /// default, it's the largest one of its children.
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    let (ctx, children) = ctx.split_children();
+    children
+      .map(|c| ctx.min_intrinsic_height_of(c, width))
+      .fold(0., f32::max)
+  }
 }
 
 /// The common type of all widget can convert to.
@@ -67,6 +67,17 @@ where
   fn into_child_multi(self) -> impl Iterator<Item = Widget<'w>> { self.build_multi().into_iter() }
 }
 
+impl<'w, const M: usize, C, W> IntoChildMulti<'w, 3, M> for C
+where
+  C: InnerPipe,
+  C::Value: IntoIterator<Item = Keyed<W>>,
+  W: IntoWidget<'static, M>,
+{
+  fn into_child_multi(self) -> impl Iterator<Item = Widget<'w>> {
+    self.build_keyed_multi().into_iter()
+  }
+}
+
 impl<T> MultiChild for T
 where
   T: StateReader<Value: MultiChild> + IntoWidget<'static, RENDER>,
@@ -1,4 +1,4 @@
-use std::{cell::RefCell, cmp::Reverse, mem::MaybeUninit};
+use std::{cell::RefCell, cmp::Reverse, mem::MaybeUninit, rc::Rc};
 
 pub mod widget_id;
 use indextree::Arena;
@@ -6,6 +6,8 @@ use widget_id::RenderQueryable;
 pub use widget_id::{TrackId, WidgetId};
 mod layout_info;
 pub use layout_info::*;
+mod damage;
+pub(crate) use damage::DamageTracker;
 
 use self::widget::widget_id::new_node;
 use crate::{overlay::ShowingOverlays, prelude::*, render_helper::PureRender, window::WindowId};
@@ -32,6 +34,31 @@ pub(crate) struct WidgetTree {
   pub(crate) store: LayoutStore,
   pub(crate) dirty_set: DirtySet,
   pub(crate) dummy_id: WidgetId,
+  pub(crate) damage: RefCell<DamageTracker>,
+  /// The overlays showing in the top layer, they are above the root content.
+  pub(crate) overlays: ShowingOverlays,
+}
+
+/// The statistics of the widgets stored in the tree of a window.
+///
+/// The arena of the tree recycles the slots of the removed widgets through a
+/// free list, and every slot carries a generation stamp, so a `WidgetId` of a
+/// removed widget never points to the widget that reuses its slot. The slots
+/// only grow up to the peak count of the widgets alive at the same time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TreeStats {
+  /// The count of the widgets alive in the tree.
+  pub live: usize,
+  /// The count of the slots of the arena, including the free slots waiting to
+  /// be reused.
+  pub slots: usize,
+  /// The count of the layout information stored for the widgets.
+  pub layout_infos: usize,
+}
+
+impl TreeStats {
+  /// The count of the free slots that the new widgets will reuse.
+  pub fn free_slots(&self) -> usize { self.slots - self.live }
 }
 
 /// A tool that help you to mark a widget as dirty
@@ -40,22 +67,25 @@ pub(crate) struct DirtyMarker(DirtySet);
 
 type TreeArena = Arena<Box<dyn RenderQueryable>>;
 
+/// The spare capacity of the tree is only shrunk when it can hold more widgets
+/// than this.
+const SHRINK_THRESHOLD: usize = 256;
+
 impl WidgetTree {
   pub fn init(&mut self, wnd: &Window, content: GenWidget) -> WidgetId {
     self.root.0.remove_subtree(&mut self.arena);
     let _guard = BuildCtx::init(BuildCtx::empty(wnd.tree));
 
     let theme = AppCtx::app_theme().clone_writer();
+    let overlays = self.overlays.clone();
     let child = move || {
-      let overlays = Provider::of::<ShowingOverlays>(BuildCtx::get()).unwrap();
       overlays.rebuild();
       Root
         .with_child(content.gen_widget())
         .into_widget()
     };
 
-    let (mut providers, child) = Theme::preprocess_before_compose(theme, child.into());
-    providers.push(Provider::new(ShowingOverlays::default()));
+    let (providers, child) = Theme::preprocess_before_compose(theme, child.into());
 
     let root = Providers::new(providers).with_child(child);
     let root = BuildCtx::get_mut().build(root);
@@ -115,8 +145,32 @@ impl WidgetTree {
 
   pub(crate) fn is_dirty(&self) -> bool { !self.dirty_set.borrow().is_empty() }
 
+  /// The count of the widgets marked dirty.
+  pub(crate) fn dirty_count(&self) -> usize { self.dirty_set.borrow().len() }
+
   pub(crate) fn count(&self, wid: WidgetId) -> usize { wid.descendants(self).count() }
 
+  pub(crate) fn stats(&self) -> TreeStats {
+    TreeStats {
+      live: self.arena.live_count(),
+      slots: self.arena.len(),
+      layout_infos: self.store.len(),
+    }
+  }
+
+  /// Shrink the spare capacity of the arena and the layout store to fit, when
+  /// it is more than four times of what is used. The slots of the removed
+  /// widgets are kept in the free list of the arena and are not moved.
+  pub(crate) fn shrink_to_fit(&mut self) {
+    let live = self.arena.live_count();
+    if self.store.capacity() > 4 * live.max(SHRINK_THRESHOLD) {
+      self.store.shrink_to_fit();
+    }
+    if self.arena.capacity() > 4 * self.arena.len().max(SHRINK_THRESHOLD) {
+      self.arena.shrink_to_fit();
+    }
+  }
+
   pub(crate) fn window(&self) -> Sc<Window> {
     AppCtx::get_window(self.wnd_id).expect("Must initialize the widget tree before use it.")
   }
@@ -152,8 +206,25 @@ impl WidgetTree {
 
     let mut needs_layout = vec![];
 
+    let mut damage = self.damage.borrow_mut();
     for (id, dirty) in self.dirty_set.borrow_mut().drain() {
-      if id.is_dropped(self) || dirty == DirtyPhase::Paint {
+      if id.is_dropped(self) {
+        continue;
+      }
+      if id == self.root {
+        damage.mark_full();
+      }
+      // The cached layers containing the widget are outdated.
+      for p in id.ancestors(self) {
+        if p
+          .query_ref::<Rc<LayerCache>>(self)
+          .is_some_and(|layer| layer.invalidate())
+        {
+          damage.mark_dirty(p);
+        }
+      }
+      if dirty == DirtyPhase::Paint {
+        damage.mark_dirty(id);
         continue;
       }
 
@@ -168,25 +239,29 @@ impl WidgetTree {
       }
 
       let mut relayout_root = id;
-      // All ancestors of this render widget should relayout until the one which only
-      // sized by parent.
+      // All ancestors of this render widget should relayout until a relayout
+      // boundary, the one which only sized by parent or was laid out with a tight
+      // clamp. The size of a boundary can't change, so its parent needn't relayout.
       for p in id.0.ancestors(&self.arena).skip(1).map(WidgetId) {
         // The first one may be a pipe that is newly generated. Otherwise, if there
         // isn't layout information, it indicates that the ancestor marked for relayout
         // already.
-        if self.store.layout_box_size(p).is_none() {
+        let Some(info) = self
+          .store
+          .get_mut(&p)
+          .filter(|info| info.size.is_some())
+        else {
           break;
-        }
+        };
 
         relayout_root = p;
-        if let Some(info) = self.store.get_mut(&p) {
-          info.size.take();
-        }
-
-        if p.assert_get(self).only_sized_by_parent() {
+        info.size.take();
+        if info.clamp.is_tight() || p.assert_get(self).only_sized_by_parent() {
           break;
         }
       }
+      // The widgets may be moved in the relayout root.
+      damage.mark_dirty(relayout_root);
       needs_layout.push(relayout_root);
     }
 
@@ -206,11 +281,13 @@ impl WidgetTree {
       self.root = new_root;
     }
 
+    self.damage.get_mut().mark_removed(id);
     id.0.detach(&mut self.arena);
   }
 
   pub(crate) fn remove_subtree(&mut self, id: WidgetId) {
     assert_ne!(id, self.root(), "You should detach the root widget before remove it.");
+    self.damage.get_mut().mark_removed(id);
 
     id.0.descendants(&self.arena).for_each(|id| {
       self.store.remove(WidgetId(id));
@@ -226,9 +303,7 @@ impl WidgetTree {
       let outs_ptr = outs.as_mut_ptr();
       for (idx, wid) in ids.iter().enumerate() {
         let tree = &mut *(self as *mut Self);
-        let cur = wid
-          .get_node_mut(tree)
-          .expect("Invalid widget id.");
+        let cur = wid.assert_get_node_mut(tree);
 
         *(*outs_ptr).get_unchecked_mut(idx) = cur;
       }
@@ -244,7 +319,16 @@ impl WidgetTree {
     let dummy_id = new_node(&mut arena, Box::new(PureRender(Void)));
     dummy_id.0.remove(&mut arena);
 
-    Self { root, dummy_id, wnd_id, arena, store: <_>::default(), dirty_set: <_>::default() }
+    Self {
+      root,
+      dummy_id,
+      wnd_id,
+      arena,
+      store: <_>::default(),
+      dirty_set: <_>::default(),
+      damage: <_>::default(),
+      overlays: <_>::default(),
+    }
   }
 }
 
@@ -391,6 +475,55 @@ mod tests {
     wnd.draw_frame();
   }
 
+  #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+  #[test]
+  fn recycle_removed_slots() {
+    reset_test_env!();
+
+    let (cnt, w_cnt) = split_value(10);
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockMulti {
+        @ {
+          pipe!(*$cnt).map(move |cnt| {
+            (0..cnt).map(|_| MockBox { size: Size::new(1., 1.) })
+          })
+        }
+      }
+    });
+    wnd.draw_frame();
+    // The placeholder of the empty pipe is created before the children are
+    // removed, so the first round takes one more slot for it.
+    *w_cnt.write() = 0;
+    wnd.draw_frame();
+    *w_cnt.write() = 10;
+    wnd.draw_frame();
+    let stats = wnd.tree_stats();
+
+    for _ in 0..3 {
+      *w_cnt.write() = 0;
+      wnd.draw_frame();
+      let removed = wnd.tree_stats();
+      assert_eq!(removed.live, stats.live - 9);
+      assert_eq!(removed.slots, stats.slots);
+
+      *w_cnt.write() = 10;
+      wnd.draw_frame();
+      assert_eq!(wnd.tree_stats(), stats);
+    }
+  }
+
+  #[test]
+  #[should_panic(expected = "used after it was removed")]
+  fn use_removed_widget() {
+    reset_test_env!();
+
+    let wnd = TestWindow::new(fn_widget! { MockBox { size: Size::zero() } });
+    let tree = wnd.tree_mut();
+    let id = tree.alloc_node(Box::new(PureRender(Void)));
+    id.0.remove(&mut tree.arena);
+    id.assert_get(tree);
+  }
+
   #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
   #[test]
   fn drop_info_clear() {
@@ -533,4 +666,42 @@ mod tests {
     assert_eq!(*layout_cnt.read(), 1);
     assert_eq!(c_paint_cnt.read().paint_cnt.get(), 2);
   }
+
+  #[test]
+  fn damage_of_changed_widgets() {
+    reset_test_env!();
+
+    let (color, w_color) = split_value(Color::RED);
+    let (show, w_show) = split_value(true);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockMulti {
+          @MockBox { size: Size::new(10., 10.), background: Color::BLUE }
+          @MockBox { size: Size::new(20., 10.), background: pipe!(*$color) }
+          @MockBox {
+            size: Size::new(30., 10.),
+            @ {
+              pipe!(*$show).map(move |show| {
+                show.then(|| @MockBox { size: Size::new(30., 10.), background: Color::GREEN })
+              })
+            }
+          }
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+    let frame = wnd.take_last_frame().unwrap();
+    assert_eq!(frame.damage, vec![Rect::from_size(Size::new(100., 100.))]);
+
+    *w_color.write() = Color::YELLOW;
+    wnd.draw_frame();
+    let frame = wnd.take_last_frame().unwrap();
+    assert_eq!(frame.damage, vec![Rect::new(Point::new(10., 0.), Size::new(20., 10.))]);
+
+    *w_show.write() = false;
+    wnd.draw_frame();
+    let frame = wnd.take_last_frame().unwrap();
+    assert_eq!(frame.damage, vec![Rect::new(Point::new(30., 0.), Size::new(30., 10.))]);
+  }
 }
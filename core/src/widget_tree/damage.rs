@@ -0,0 +1,145 @@
+use super::WidgetId;
+use crate::prelude::{Color, Rect};
+
+/// The max count of the rects in a damage region, more rects are merged.
+const MAX_DAMAGE_RECTS: usize = 4;
+
+/// Tracks the area of the window that changed since the last frame, so only
+/// it needs to be repainted.
+///
+/// The damage comes from the widgets marked dirty and the widgets removed, the
+/// area of a widget is the bounds that it and its descendants painted, both in
+/// the last frame and in the current frame.
+#[derive(Default)]
+pub(crate) struct DamageTracker {
+  /// The bounds painted by every widget and its descendants in the last frame.
+  painted: ahash::HashMap<WidgetId, Rect>,
+  /// The bounds painted by every widget and its descendants in this frame.
+  painting: ahash::HashMap<WidgetId, Rect>,
+  dirty: ahash::HashSet<WidgetId>,
+  region: DamageRegion,
+  full: bool,
+  surface: Option<Color>,
+}
+
+/// A region made of a few rects that don't overlap each other.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub(crate) struct DamageRegion(Vec<Rect>);
+
+impl DamageTracker {
+  /// The widget changed, the area it painted in the last frame and in this
+  /// frame both need to be repainted.
+  pub(crate) fn mark_dirty(&mut self, id: WidgetId) { self.dirty.insert(id); }
+
+  /// The widget is removed from the tree, the area it painted in the last frame
+  /// needs to be repainted.
+  pub(crate) fn mark_removed(&mut self, id: WidgetId) {
+    if let Some(rect) = self.painted.get(&id) {
+      self.region.add(*rect);
+    }
+  }
+
+  /// The `rect` of the window needs to be repainted in this frame.
+  pub(crate) fn mark_area(&mut self, rect: Rect) { self.region.add(rect); }
+
+  /// Repaint the whole window in this frame.
+  pub(crate) fn mark_full(&mut self) { self.full = true; }
+
+  /// The whole window needs to be repainted if the surface color changed.
+  pub(crate) fn set_surface(&mut self, surface: Color) {
+    if self.surface.replace(surface) != Some(surface) {
+      self.full = true;
+    }
+  }
+
+  /// Replace the bounds painted in this frame, so a paint out of the frame can
+  /// restore them after it finishes.
+  pub(crate) fn replace_painting(
+    &mut self, painting: ahash::HashMap<WidgetId, Rect>,
+  ) -> ahash::HashMap<WidgetId, Rect> {
+    std::mem::replace(&mut self.painting, painting)
+  }
+
+  /// Record the bounds painted by the widget and its descendants in this frame.
+  pub(crate) fn record_painted(&mut self, id: WidgetId, bounds: Rect) {
+    self.painting.insert(id, bounds);
+  }
+
+  /// Finish the frame and return the area changed in the `viewport`.
+  pub(crate) fn end_frame(&mut self, viewport: Rect) -> Vec<Rect> {
+    let Self { painted, painting, dirty, region, full, .. } = self;
+    for id in dirty.drain() {
+      if let Some(rect) = painted.get(&id) {
+        region.add(*rect);
+      }
+      if let Some(rect) = painting.get(&id) {
+        region.add(*rect);
+      }
+    }
+    std::mem::swap(painted, painting);
+    painting.clear();
+
+    let region = std::mem::take(region);
+    if std::mem::take(full) {
+      vec![viewport]
+    } else {
+      region
+        .0
+        .into_iter()
+        .filter_map(|rect| rect.intersection(&viewport))
+        .collect()
+    }
+  }
+}
+
+impl DamageRegion {
+  fn add(&mut self, mut rect: Rect) {
+    if rect.is_empty() {
+      return;
+    }
+    // Merge the overlapping rects, so the same pixel is never painted twice.
+    while let Some(idx) = self.0.iter().position(|r| r.intersects(&rect)) {
+      rect = rect.union(&self.0.swap_remove(idx));
+    }
+    if self.0.len() < MAX_DAMAGE_RECTS {
+      self.0.push(rect);
+    } else {
+      // Merge with the rect that grows the least area.
+      let grow = |r: &Rect| r.union(&rect).area() - r.area();
+      let idx = (0..self.0.len())
+        .min_by(|a, b| grow(&self.0[*a]).total_cmp(&grow(&self.0[*b])))
+        .unwrap();
+      let merged = rect.union(&self.0.swap_remove(idx));
+      self.add(merged);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::prelude::{Point, Size};
+
+  fn rect(x: f32, y: f32, w: f32, h: f32) -> Rect { Rect::new(Point::new(x, y), Size::new(w, h)) }
+
+  #[test]
+  fn merge_overlap_rects() {
+    let mut region = DamageRegion::default();
+    region.add(rect(0., 0., 10., 10.));
+    region.add(rect(20., 0., 10., 10.));
+    assert_eq!(region.0.len(), 2);
+
+    region.add(rect(5., 0., 20., 5.));
+    assert_eq!(region, DamageRegion(vec![rect(0., 0., 30., 10.)]));
+  }
+
+  #[test]
+  fn limit_rects_count() {
+    let mut region = DamageRegion::default();
+    for i in 0..MAX_DAMAGE_RECTS + 1 {
+      region.add(rect(i as f32 * 100., 0., 10., 10.));
+    }
+    assert_eq!(region.0.len(), MAX_DAMAGE_RECTS);
+    assert!(region.0.contains(&rect(300., 0., 110., 10.)));
+  }
+}
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use ribir_geom::ZERO_SIZE;
 
 use super::{Lerp, WidgetId, WidgetTree};
-use crate::prelude::{INFINITY_SIZE, Point, Size};
+use crate::prelude::{INFINITY_SIZE, Point, Size, Transform};
 
 /// boundary limit of the render object's layout
 #[derive(Debug, Clone, PartialEq, Copy, Lerp)]
@@ -106,6 +106,8 @@ pub struct LayoutInfo {
 #[derive(Default)]
 pub(crate) struct LayoutStore {
   data: HashMap<WidgetId, LayoutInfo, ahash::RandomState>,
+  /// The total count of the layouts performed by the widgets of the tree.
+  performed_count: usize,
 }
 
 impl LayoutStore {
@@ -114,6 +116,15 @@ impl LayoutStore {
 
   pub(crate) fn remove(&mut self, id: WidgetId) -> Option<LayoutInfo> { self.data.remove(&id) }
 
+  /// Clear the layout results of all the objects, so they're laid out again
+  /// with their last clamps.
+  pub(crate) fn reset_sizes(&mut self) {
+    self
+      .data
+      .values_mut()
+      .for_each(|info| info.size = None);
+  }
+
   pub(crate) fn layout_box_size(&self, id: WidgetId) -> Option<Size> {
     self.layout_info(id).and_then(|info| info.size)
   }
@@ -124,6 +135,16 @@ impl LayoutStore {
 
   pub(crate) fn layout_info(&self, id: WidgetId) -> Option<&LayoutInfo> { self.data.get(&id) }
 
+  /// Record the layout result of `id` performed with `clamp`.
+  pub(crate) fn record_layout(&mut self, id: WidgetId, clamp: BoxClamp, size: Size) {
+    let info = self.layout_info_or_default(id);
+    info.clamp = clamp;
+    info.size = Some(size);
+    self.performed_count += 1;
+  }
+
+  pub(crate) fn performed_count(&self) -> usize { self.performed_count }
+
   /// return a mutable reference of the layout info  of `id`, if it's not exist
   /// insert a default value before return
   pub(crate) fn layout_info_or_default(&mut self, id: WidgetId) -> &mut LayoutInfo {
@@ -166,6 +187,21 @@ impl WidgetTree {
       .fold(pos, |pos, p| self.map_to_parent(p, pos))
   }
 
+  /// The transform that maps the coordinate of the `widget` to the window.
+  pub(crate) fn global_transform(&self, widget: WidgetId) -> Transform {
+    widget
+      .ancestors(self)
+      .fold(Transform::identity(), |ts, p| {
+        self.store.layout_box_pos(p).map_or(ts, |offset| {
+          let ts = p
+            .assert_get(self)
+            .get_transform()
+            .map_or(ts, |t| ts.then(&t));
+          ts.then_translate(offset.to_vector())
+        })
+      })
+  }
+
   pub(crate) fn map_from_global(&self, pos: Point, widget: WidgetId) -> Point {
     let stack = widget.ancestors(self).collect::<Vec<_>>();
     stack
@@ -173,9 +209,37 @@ impl WidgetTree {
       .rev()
       .fold(pos, |pos, p| self.map_from_parent(*p, pos))
   }
+
+  /// Map the `pos` in the coordinate of `from` to the coordinate of `to`. If
+  /// one is an ancestor of the other, only the widgets between them are
+  /// walked, otherwise the `pos` is mapped through the window coordinate.
+  pub(crate) fn map_to(&self, pos: Point, from: WidgetId, to: WidgetId) -> Point {
+    if to.ancestor_of(from, self) {
+      from
+        .ancestors(self)
+        .take_while(|p| *p != to)
+        .fold(pos, |pos, p| self.map_to_parent(p, pos))
+    } else if from.ancestor_of(to, self) {
+      let stack = to
+        .ancestors(self)
+        .take_while(|p| *p != from)
+        .collect::<Vec<_>>();
+      stack
+        .iter()
+        .rev()
+        .fold(pos, |pos, p| self.map_from_parent(*p, pos))
+    } else {
+      self.map_from_global(self.map_to_global(pos, from), to)
+    }
+  }
 }
 
 impl BoxClamp {
+  /// Return if the clamp only allows one size, a widget laid out with a tight
+  /// clamp can't change its size until its parent passes another clamp.
+  #[inline]
+  pub fn is_tight(&self) -> bool { self.min == self.max }
+
   #[inline]
   pub fn clamp(self, size: Size) -> Size { size.clamp(self.min, self.max) }
 
@@ -6,7 +6,7 @@ use smallvec::{SmallVec, smallvec};
 use super::*;
 use crate::{
   data_widget::{AnonymousAttacher, DataAttacher},
-  window::DelayEvent,
+  window::{DelayEvent, WindowFlags},
 };
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Debug, Hash)]
@@ -70,6 +70,14 @@ impl WidgetId {
     tree.arena.get_mut(self.0).map(|n| n.get_mut())
   }
 
+  #[track_caller]
+  pub(crate) fn assert_get_node_mut(self, tree: &mut WidgetTree) -> &mut Box<dyn RenderQueryable> {
+    if self.is_dropped(tree) {
+      self.panic_not_exist(tree)
+    }
+    self.get_node_mut(tree).unwrap()
+  }
+
   /// detect if the widget of this id point to is dropped.
   pub(crate) fn is_dropped(self, tree: &WidgetTree) -> bool { self.0.is_removed(&tree.arena) }
 
@@ -145,7 +153,6 @@ impl WidgetId {
     self.node_feature(tree, |node| node.previous_sibling())
   }
 
-  #[allow(unused)]
   pub(crate) fn ancestor_of(self, other: WidgetId, tree: &WidgetTree) -> bool {
     other.ancestors(tree).any(|p| self == p)
   }
@@ -248,10 +255,23 @@ impl WidgetId {
       .map(WidgetId)
   }
 
+  #[track_caller]
   pub(crate) fn assert_get<'a, 'b>(self, tree: &'a WidgetTree) -> &'a (dyn RenderQueryable + 'b) {
     self
       .get(tree)
-      .expect("Widget not exists in the `tree`")
+      .unwrap_or_else(|| self.panic_not_exist(tree))
+  }
+
+  /// Panic with the reason why the widget can't be found, use a removed widget
+  /// id is a misuse even if its slot is reused by another widget.
+  #[track_caller]
+  fn panic_not_exist(self, tree: &WidgetTree) -> ! {
+    // The ids are 1-based, the slot of a removed widget is kept in the arena.
+    if usize::from(self.0) <= tree.arena.len() {
+      panic!("The widget {self:?} is used after it was removed from the tree.")
+    } else {
+      panic!("Widget {self:?} not exists in the `tree`.")
+    }
   }
 
   /// We assume the `f` wrap the widget into a new widget, and keep the old
@@ -260,7 +280,7 @@ impl WidgetId {
     self, tree: &mut WidgetTree,
     f: impl FnOnce(Box<dyn RenderQueryable>) -> Box<dyn RenderQueryable>,
   ) {
-    let node = self.get_node_mut(tree).unwrap();
+    let node = self.assert_get_node_mut(tree);
     unsafe {
       let data = Box::from_raw(&mut **node as *mut _);
       let copied = std::mem::replace(node, f(data));
@@ -276,34 +296,109 @@ impl WidgetId {
     self.wrap_node(tree, |render| Box::new(AnonymousAttacher::new(render, Box::new(data))));
   }
 
+  /// Return if this widget or any of its ancestors is marked as sensitive.
+  pub(crate) fn is_sensitive(self, tree: &WidgetTree) -> bool {
+    self
+      .ancestors(tree)
+      .any(|id| id.has_sensitive_flag(tree))
+  }
+
+  fn has_sensitive_flag(self, tree: &WidgetTree) -> bool {
+    self
+      .query_all_iter::<MixBuiltin>(tree)
+      .any(|m| m.contain_flag(MixFlags::Sensitive))
+  }
+
   pub(crate) fn paint_subtree(self, tree: &WidgetTree, painter: &mut Painter) {
+    let redact = tree
+      .window()
+      .flags()
+      .contains(WindowFlags::REDACT_SENSITIVE);
     let mut ctx = PaintingCtx::new(self, tree, painter);
-    let mut painting = vec![];
+    // The painting widgets and the bounds painted by them so far.
+    let mut painting: Vec<(WidgetId, Option<Rect>)> = vec![];
+    let mut counted = ctx.painter().command_count();
+    // Add the bounds of the commands not counted yet to the painting widget.
+    let mut count_commands =
+      |ctx: &mut PaintingCtx, painting: &mut Vec<(WidgetId, Option<Rect>)>| {
+        // The commands in a layer are in the axis of the layer.
+        let layer = ctx.layer_transform().copied();
+        let painter = ctx.painter();
+        if let Some((_, acc)) = painting.last_mut() {
+          if let Some(mut bounds) = painter.commands_bounds(counted) {
+            if let Some(layer) = layer {
+              bounds = layer.outer_transformed_rect(&bounds);
+            }
+            *acc = Some(acc.map_or(bounds, |acc| acc.union(&bounds)));
+          }
+        }
+        counted = painter.command_count();
+      };
     loop {
       let id = ctx.id();
       if ctx.painter().alpha() != 0. {
         if let Some(layout_box) = ctx.box_rect() {
-          painting.push(id);
+          painting.push((id, None));
           let render = id.assert_get(tree);
           ctx
             .painter()
             .save()
             .translate(layout_box.min_x(), layout_box.min_y());
-          render.paint(&mut ctx);
-
-          if let Some(c) = id.first_child(tree) {
-            ctx.switch_to(c);
-            continue;
+          // The redacted content is never cached.
+          let cache = if redact { None } else { id.query_ref::<Rc<LayerCache>>(tree) };
+          // The painting may start from a descendant of a sensitive widget,
+          // so the root of the subtree also checks its ancestors.
+          let sensitive =
+            if id == self { id.is_sensitive(tree) } else { id.has_sensitive_flag(tree) };
+          if redact && sensitive {
+            // Paint a placeholder instead of the sensitive content and its
+            // descendants.
+            ctx
+              .painter()
+              .rect(&Rect::from_size(layout_box.size))
+              .set_fill_brush(Color::BLACK)
+              .fill();
+          } else if let Some((bounds, cmds)) = cache.as_ref().and_then(|l| l.get()) {
+            // The subtree is clean, reuse its layer.
+            ctx.painter().draw_bundle_commands(bounds, cmds);
+          } else {
+            if let Some(cache) = cache {
+              ctx.start_layer(Some(cache.clone()));
+            }
+            render.paint(&mut ctx);
+
+            if let Some(c) = id.first_child(tree) {
+              count_commands(&mut ctx, &mut painting);
+              ctx.switch_to(c);
+              continue;
+            }
           }
         };
       }
 
-      while let Some(painting) = painting.pop() {
+      while let Some((wid, _)) = painting.last().copied() {
+        if ctx.has_layer(wid) {
+          count_commands(&mut ctx, &mut painting);
+          ctx.finish_layers(wid);
+        }
         ctx.painter().restore();
-        ctx.switch_to(painting);
+        ctx.switch_to(wid);
         ctx.finish();
+        count_commands(&mut ctx, &mut painting);
+
+        let (_, bounds) = painting.pop().unwrap();
+        if let Some(bounds) = bounds {
+          // Borrow the damage only here, the painting may mark the damage too.
+          tree
+            .damage
+            .borrow_mut()
+            .record_painted(wid, bounds);
+          if let Some((_, acc)) = painting.last_mut() {
+            *acc = Some(acc.map_or(bounds, |acc| acc.union(&bounds)));
+          }
+        }
 
-        if let Some(sibling) = painting.next_sibling(tree) {
+        if let Some(sibling) = wid.next_sibling(tree) {
           ctx.switch_to(sibling);
           break;
         }
@@ -1,7 +1,9 @@
 use std::{
+  borrow::Cow,
   cell::{Cell, RefCell},
   collections::VecDeque,
   convert::Infallible,
+  path::PathBuf,
   ptr::NonNull,
 };
 
@@ -10,12 +12,23 @@ use ribir_algo::Sc;
 use smallvec::SmallVec;
 use widget_id::TrackId;
 use winit::event::{DeviceId, ElementState, MouseButton, WindowEvent};
-pub use winit::window::CursorIcon;
+pub use winit::window::{CursorIcon, ImePurpose};
+
+mod capture;
+mod config;
+mod focus_ring;
+mod inspector;
+mod perf_hud;
+pub use config::WindowConfig;
+pub use focus_ring::FocusRingStyle;
+use perf_hud::FrameTimingsRecord;
+pub use perf_hud::{BackendTimings, FrameTimings};
 
 use crate::{
   events::{
-    dispatcher::Dispatcher,
+    dispatcher::{ActiveTouch, Dispatcher},
     focus_mgr::{FocusManager, FocusType},
+    gesture::Gesture,
   },
   prelude::*,
   ticker::{FrameMsg, FrameTicker},
@@ -35,6 +48,15 @@ pub struct Window {
   pub(crate) frame_ticker: FrameTicker,
   pub(crate) focus_mgr: RefCell<FocusManager>,
   pub(crate) running_animates: Sc<Cell<u32>>,
+  /// Whether a frame is requested by `Window::request_redraw` and not drawn
+  /// yet.
+  redraw_requested: Cell<bool>,
+  present_mode: Cell<PresentMode>,
+  max_fps: Cell<Option<u32>>,
+  /// The time the last frame was drawn, to hold the frames back by `max_fps`.
+  last_draw: Cell<Option<Instant>>,
+  /// The area the focus ring painted in the last frame.
+  focus_ring_bounds: Cell<Option<Rect>>,
   pre_edit: RefCell<Option<String>>,
   /// This vector store the task to emit events. When perform layout, dispatch
   /// event and so on, some part of window may be already mutable borrowed and
@@ -57,6 +79,14 @@ pub struct Window {
   pub(crate) delay_drop_widgets: RefCell<Vec<(Option<WidgetId>, TrackId)>>,
 
   flags: Cell<WindowFlags>,
+  last_frame: RefCell<Option<PaintRecord>>,
+  /// The areas of the `Vibrancy` widgets painted in the current frame.
+  vibrancy_regions: RefCell<Vec<Rect>>,
+  viewport_insets: Stateful<EdgeInsets>,
+  tooltips_delay: Cell<Duration>,
+  /// The widget highlighted by the inspector.
+  inspecting: Cell<Option<WidgetId>>,
+  frame_timings: RefCell<FrameTimingsRecord>,
 }
 
 bitflags! {
@@ -67,6 +97,31 @@ bitflags! {
     activate all animations; if this flag is not marked, all animations\
     will not run."]
     const ANIMATIONS = 1 << 0;
+    #[doc="If this flag is marked, the content of the sensitive widgets will \
+    be redacted from the painted frame, so it will not leak to any \
+    screenshot or capture."]
+    const REDACT_SENSITIVE = 1 << 1;
+    #[doc="If this flag is marked, the paint commands of the last drawn frame \
+    are recorded, you can get them by `Window::last_frame`."]
+    const RECORD_FRAME = 1 << 2;
+    #[doc="If this flag is marked, the window is cleared to transparent and \
+    only the area out of the `Vibrancy` widgets is filled with the surface \
+    color, so the platform backdrop of the window shows through them."]
+    const BACKDROP = 1 << 3;
+    #[doc="If this flag is marked, the window highlights the widget under the \
+    cursor and shows its id, type, layout rect and clamp over the frame, and \
+    logs its ancestor chain when it changes. In the debug build, it's toggled \
+    by pressing `Ctrl+Shift+I`."]
+    const INSPECTOR = 1 << 4;
+    #[doc="If this flag is marked, the window records the timings of the \
+    drawn frames and paints a graph of the frame times and the timings of the \
+    last frame at its top right corner, see `Window::frame_timings`."]
+    const PERFORMANCE_HUD = 1 << 5;
+    #[doc="If this flag is marked, the window draws a frame every vsync even \
+    if nothing changed, for games or live previews that update every frame. \
+    Without it, the window only draws when the widgets are dirty, an \
+    animation is running or a redraw is requested."]
+    const CONTINUOUS_RENDER = 1 << 6;
     const DEFAULT = Self::ANIMATIONS.bits();
   }
 }
@@ -74,16 +129,36 @@ bitflags! {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
 pub struct WindowId(u64);
 
+/// How the frames of a window are presented to the screen. The backend falls
+/// back to `Fifo` if its surface doesn't support the mode.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PresentMode {
+  /// Present the frames at the vsync, the frames wait in a queue if they are
+  /// produced faster. It never tears and saves the most power.
+  #[default]
+  Fifo,
+  /// Present the frames at the vsync, but a waiting frame is replaced by the
+  /// newer one. It never tears and has a lower latency than `Fifo`.
+  Mailbox,
+  /// Present the frames at once, it has the lowest latency but may tear.
+  Immediate,
+}
+
+/// The font size of the text painted by the debug overlays.
+const OVERLAY_FONT_SIZE: f32 = 12.;
+
 pub trait ShellWindow {
   fn id(&self) -> WindowId;
   fn inner_size(&self) -> Size;
   fn outer_size(&self) -> Size;
   fn set_ime_cursor_area(&mut self, rect: &Rect);
   fn set_ime_allowed(&mut self, allowed: bool);
+  fn set_ime_purpose(&mut self, purpose: ImePurpose);
 
   fn request_resize(&mut self, size: Size);
   fn on_resize(&mut self, size: Size);
   fn set_min_size(&mut self, size: Size);
+  fn set_max_size(&mut self, size: Size);
   fn cursor(&self) -> CursorIcon;
   fn set_cursor(&mut self, cursor: CursorIcon);
   fn set_title(&mut self, str: &str);
@@ -96,15 +171,47 @@ pub trait ShellWindow {
   fn set_minimized(&mut self, minimized: bool);
   fn focus_window(&mut self);
   fn set_decorations(&mut self, decorations: bool);
+  fn is_fullscreen(&self) -> bool;
+  /// Make the window borderless fullscreen on its current monitor when
+  /// `fullscreen` is true, restore it otherwise.
+  fn set_fullscreen(&mut self, fullscreen: bool);
+  /// Hide the cursor and keep it still when `lock` is true, restore it
+  /// otherwise.
+  fn set_pointer_lock(&mut self, lock: bool);
+  /// Set how the frames are presented to the screen, the shell window ignores
+  /// it if not supported.
+  fn set_present_mode(&mut self, _mode: PresentMode) {}
+  /// The insets of the window area covered by the system UI, such as the
+  /// system bars, the display cutouts and the on-screen keyboard, in logical
+  /// pixels.
+  fn viewport_insets(&self) -> EdgeInsets { EdgeInsets::ZERO }
   fn as_any(&self) -> &dyn Any;
   fn as_any_mut(&mut self) -> &mut dyn Any;
   /// The device pixel ratio of Window interface returns the ratio of the
   /// resolution in physical pixels to the logic pixels for the current display
   /// device.
   fn device_pixel_ratio(&self) -> f32;
+  /// Called after the device pixel ratio changed, the shell window resizes its
+  /// surface to the new physical size and releases the caches rendered at the
+  /// old ratio.
+  fn on_device_pixel_ratio_changed(&mut self) {}
   fn begin_frame(&mut self, surface_color: Color);
-  fn draw_commands(&mut self, viewport: Rect, commands: &[PaintCommand]);
+  /// Draw the commands of the frame. The `damage` is the area changed since
+  /// the last frame, so only it needs to be redrawn if the shell keeps the
+  /// content of the last frame.
+  fn draw_commands(&mut self, viewport: Rect, damage: &[Rect], commands: &[PaintCommand]);
   fn end_frame(&mut self);
+  /// The time the backend spent on the last frame it finished drawing, `None`
+  /// if it doesn't measure it.
+  fn backend_timings(&self) -> Option<BackendTimings> { None }
+  /// Render the commands in the `viewport` to an image in device pixels out of
+  /// the frame, the area not painted is filled with the `surface` color.
+  /// Returns `None` if the shell window can't render to an image.
+  fn render_image(
+    &mut self, _viewport: Rect, _commands: &[PaintCommand], _surface: Color,
+  ) -> Option<PixelImage> {
+    None
+  }
 }
 
 impl Window {
@@ -150,6 +257,135 @@ impl Window {
       .dispatch_mouse_input(device_id, state, button);
   }
 
+  /// Inject a framework input event into this window, it's dispatched as if
+  /// the user did it. Like the events from the shell, the listeners are called
+  /// when the frame tasks run, see [`Window::run_frame_tasks`].
+  ///
+  /// ```
+  /// use ribir_core::{prelude::*, test_helper::*};
+  ///
+  /// let mut wnd = TestWindow::new(fn_widget! { @MockBox { size: Size::new(100., 100.) } });
+  /// wnd.draw_frame();
+  /// UiEvent::tap_at(Point::new(50., 50.))
+  ///   .into_iter()
+  ///   .for_each(|e| wnd.inject_event(e));
+  /// wnd.run_frame_tasks();
+  /// ```
+  pub fn inject_event(&self, event: UiEvent) {
+    let mut dispatcher = self.dispatcher.borrow_mut();
+    match event {
+      UiEvent::PointerMove(pos) => dispatcher.cursor_move_to(pos),
+      UiEvent::PointerLeave => dispatcher.on_cursor_left(),
+      UiEvent::PointerDown(btns) | UiEvent::PointerUp(btns) => {
+        let state = if matches!(event, UiEvent::PointerDown(_)) {
+          ElementState::Pressed
+        } else {
+          ElementState::Released
+        };
+        // Safety: the dummy device only identifies the injected pointer, it's never
+        // passed to the shell.
+        let device_id = unsafe { DeviceId::dummy() };
+        for btn in btns.to_mouse_buttons() {
+          dispatcher.dispatch_mouse_input(device_id, state, btn);
+        }
+      }
+      UiEvent::Wheel { delta_x, delta_y } => dispatcher.dispatch_wheel_delta(delta_x, delta_y),
+      UiEvent::Modifiers(modifiers) => dispatcher.dispatch_modifiers(modifiers),
+      UiEvent::KeyDown { key, physical_key } => dispatcher.dispatch_keyboard_input(
+        physical_key,
+        key,
+        false,
+        KeyLocation::Standard,
+        ElementState::Pressed,
+      ),
+      UiEvent::KeyUp { key, physical_key } => dispatcher.dispatch_keyboard_input(
+        physical_key,
+        key,
+        false,
+        KeyLocation::Standard,
+        ElementState::Released,
+      ),
+      UiEvent::Chars(chars) => dispatcher.dispatch_receive_chars(chars),
+      UiEvent::ImePreEdit(pre_edit) => dispatcher.dispatch_ime_pre_edit(pre_edit),
+    }
+  }
+
+  /// Dispatch the relative motion of the mouse in logical pixels, it's only
+  /// delivered to the widget that locked the pointer.
+  pub fn process_mouse_motion(&self, delta: Vector) {
+    self
+      .dispatcher
+      .borrow_mut()
+      .dispatch_pointer_motion(delta);
+  }
+
+  /// Lock the pointer to the widget, see
+  /// [`PointerEvent::request_pointer_lock`].
+  pub fn request_pointer_lock(&self, wid: WidgetId) -> bool {
+    self.dispatcher.borrow().lock_pointer(wid)
+  }
+
+  /// Release the pointer lock, if any.
+  pub fn exit_pointer_lock(&self) { self.dispatcher.borrow().release_pointer_lock() }
+
+  /// The widget that locked the pointer.
+  pub fn pointer_lock_target(&self) -> Option<WidgetId> {
+    self.dispatcher.borrow().pointer_lock_target()
+  }
+
+  /// Set the policy of whether hovering over a focusable widget focuses it,
+  /// see [`FocusFollowsMouse`].
+  pub fn set_focus_follows_mouse(&self, policy: FocusFollowsMouse) {
+    self
+      .dispatcher
+      .borrow_mut()
+      .set_focus_follows_mouse(policy);
+  }
+
+  /// The policy of whether hovering over a focusable widget focuses it.
+  pub fn focus_follows_mouse(&self) -> FocusFollowsMouse {
+    self.dispatcher.borrow().focus_follows_mouse()
+  }
+
+  /// Set how the wheel deltas are normalized before being dispatched to the
+  /// `on_wheel` listeners of this window, see [`WheelConfig`].
+  pub fn set_wheel_config(&self, config: WheelConfig) {
+    self
+      .dispatcher
+      .borrow_mut()
+      .set_wheel_config(config);
+  }
+
+  /// The config of how the wheel deltas are normalized.
+  pub fn wheel_config(&self) -> WheelConfig { self.dispatcher.borrow().wheel_config() }
+
+  /// Set how long a pointer is held still to be recognized as a long press,
+  /// the default is 500 milliseconds.
+  pub fn set_long_press_duration(&self, duration: Duration) {
+    self
+      .dispatcher
+      .borrow_mut()
+      .gesture
+      .set_long_press_duration(duration);
+  }
+
+  /// How long a pointer is held still to be recognized as a long press.
+  pub fn long_press_duration(&self) -> Duration {
+    self
+      .dispatcher
+      .borrow()
+      .gesture
+      .long_press_duration()
+  }
+
+  /// Set how long the pointer hovers or the keyboard focuses on a widget
+  /// before its tooltips show, the default is 500 milliseconds.
+  pub fn set_tooltips_delay(&self, delay: Duration) { self.tooltips_delay.set(delay) }
+
+  /// How long the pointer hovers or the keyboard focuses on a widget before its
+  /// tooltips show.
+  pub fn tooltips_delay(&self) -> Duration { self.tooltips_delay.get() }
+
   /// Request switch the focus to next widget.
   pub fn request_next_focus(&self) {
     self
@@ -218,13 +454,19 @@ impl Window {
   /// Draw an image what current render tree represent.
   #[track_caller]
   pub fn draw_frame(&self) -> bool {
+    // Hold the frame back until the interval of the max fps passed.
+    if self.next_frame_at().is_some() {
+      return false;
+    }
+    let frame_start = Instant::now();
     AppCtx::run_until_stalled();
     let mut ticker = self.frame_ticker.clone();
-    ticker.next(FrameMsg::NewFrame(Instant::now()));
+    ticker.next(FrameMsg::NewFrame(FrameClock::now()));
     self.run_frame_tasks();
 
     self.update_painter_viewport();
     let draw = self.need_draw() && !self.size().is_empty();
+    self.redraw_requested.set(false);
     if draw {
       let root = self.tree().root();
 
@@ -232,24 +474,71 @@ impl Window {
         let _guard = BuildCtx::init_for(root, self.tree);
         Palette::of(BuildCtx::get()).surface()
       };
-      self.shell_wnd.borrow_mut().begin_frame(surface);
-
-      ticker.next(FrameMsg::BeforeLayout(Instant::now()));
+      let backdrop = self.flags().contains(WindowFlags::BACKDROP);
+      let clear = if backdrop { Color::TRANSPARENT } else { surface };
+      self.shell_wnd.borrow_mut().begin_frame(clear);
+      self
+        .tree()
+        .damage
+        .borrow_mut()
+        .set_surface(surface);
+
+      let dirty_widgets = self.tree().dirty_count();
+      ticker.next(FrameMsg::BeforeLayout(FrameClock::now()));
+      let layout_start = Instant::now();
       self.layout();
+      let layout = layout_start.elapsed();
 
+      let paint_start = Instant::now();
       self.tree().draw();
       self.draw_delay_drop_widgets();
+      self.tree_mut().shrink_to_fit();
+      self.paint_focus_ring();
+      let paint = paint_start.elapsed();
+      if self.flags().contains(WindowFlags::INSPECTOR) {
+        self.paint_inspector();
+      }
+      if self
+        .flags()
+        .contains(WindowFlags::PERFORMANCE_HUD)
+      {
+        self.paint_perf_hud();
+      }
 
       let mut shell = self.shell_wnd.borrow_mut();
       let inner_size = shell.inner_size();
       let mut painter = self.painter.borrow_mut();
-      shell.draw_commands(Rect::from_size(inner_size), &painter.finish());
+      let commands = painter.finish();
+      let viewport = Rect::from_size(inner_size);
+      let regions = self.vibrancy_regions.take();
+      let commands: Cow<[PaintCommand]> = if backdrop {
+        let mut all = backdrop_surface(viewport, &regions, surface);
+        all.extend_from_slice(&commands);
+        Cow::Owned(all)
+      } else {
+        Cow::Borrowed(&*commands)
+      };
+      if self.flags().contains(WindowFlags::RECORD_FRAME) {
+        let record = PaintRecord::new(viewport, commands.to_vec().into_boxed_slice());
+        *self.last_frame.borrow_mut() = Some(record);
+      }
+      let damage = self
+        .tree()
+        .damage
+        .borrow_mut()
+        .end_frame(viewport);
+      shell.draw_commands(viewport, &damage, &commands);
 
       shell.end_frame();
+      let backend = shell.backend_timings();
+      self.last_draw.set(Some(FrameClock::now()));
+      let frame = frame_start.elapsed();
+      self.record_frame_timings(FrameTimings { frame, layout, paint, backend, dirty_widgets });
     }
 
+    AppCtx::run_deferred_works(frame_start + AppCtx::frame_budget());
     AppCtx::end_frame();
-    ticker.next(FrameMsg::Finish(Instant::now()));
+    ticker.next(FrameMsg::Finish(FrameClock::now()));
     ticker.retain();
 
     draw
@@ -269,7 +558,7 @@ impl Window {
       }
 
       if !tree.is_dirty() {
-        let ready = FrameMsg::LayoutReady(Instant::now());
+        let ready = FrameMsg::LayoutReady(FrameClock::now());
         self.frame_ticker.clone().next(ready);
         self.run_frame_tasks();
       }
@@ -290,10 +579,74 @@ impl Window {
       let mut painter = self.painter.borrow_mut();
       painter.set_viewport(Rect::from_size(size));
       painter.reset();
+      self.add_delay_event(DelayEvent::WindowResized);
+    }
+  }
+
+  pub fn need_draw(&self) -> bool {
+    self.tree().is_dirty()
+      || self.running_animates.get() > 0
+      || self.redraw_requested.get()
+      || self
+        .flags()
+        .contains(WindowFlags::CONTINUOUS_RENDER)
+  }
+
+  /// Request the window to draw the next frame even if nothing changed, for
+  /// example, the content painted by a custom painter depends on an outside
+  /// source. Only one frame is drawn, set the
+  /// [`WindowFlags::CONTINUOUS_RENDER`] flag to draw every frame.
+  pub fn request_redraw(&self) { self.redraw_requested.set(true); }
+
+  /// The present mode of the frames, see [`Window::set_present_mode`].
+  pub fn present_mode(&self) -> PresentMode { self.present_mode.get() }
+
+  /// Set how the frames are presented to the screen, it can be switched at
+  /// any time. The default is [`PresentMode::Fifo`].
+  ///
+  /// A power-sensitive app keeps the default, a latency-sensitive one may use
+  /// `Mailbox` or `Immediate` to reduce the frames waiting in the queue.
+  pub fn set_present_mode(&self, mode: PresentMode) {
+    if self.present_mode.replace(mode) != mode {
+      self.shell_wnd.borrow_mut().set_present_mode(mode);
     }
   }
 
-  pub fn need_draw(&self) -> bool { self.tree().is_dirty() || self.running_animates.get() > 0 }
+  /// The most frames drawn per second, see [`Window::set_max_fps`].
+  pub fn max_fps(&self) -> Option<u32> { self.max_fps.get() }
+
+  /// Limit the frames drawn per second, `None` for no limit, which is the
+  /// default. A frame requested within the interval after the last one is held
+  /// back until the interval passed, so the app can throttle the continuous
+  /// rendering and the animations to save power.
+  pub fn set_max_fps(&self, fps: Option<u32>) { self.max_fps.set(fps.filter(|fps| *fps > 0)); }
+
+  /// The time the next frame can be drawn if it's held back by the max fps,
+  /// `None` if it can be drawn now.
+  pub fn next_frame_at(&self) -> Option<Instant> {
+    let fps = self.max_fps.get()?;
+    let at = self.last_draw.get()? + Duration::from_secs_f64(1. / fps as f64);
+    (at > FrameClock::now()).then_some(at)
+  }
+
+  /// Call `f` at the start of every frame with the time elapsed since the
+  /// previous frame, the first call receives zero.
+  ///
+  /// It's the place to step a custom animation loop, the frames are only
+  /// drawn on demand, so request the next frame by [`Window::request_redraw`]
+  /// in `f` or set the [`WindowFlags::CONTINUOUS_RENDER`] flag to keep it
+  /// running. Unsubscribe the returned subscription to remove the callback.
+  pub fn on_frame(&self, mut f: impl FnMut(Duration) + 'static) -> BoxSubscription<'static> {
+    let mut last: Option<Instant> = None;
+    let subscription = self.frame_tick_stream().subscribe(move |msg| {
+      if let FrameMsg::NewFrame(now) = msg {
+        let elapsed = last.map_or(Duration::ZERO, |last| now.duration_since(last));
+        last = Some(now);
+        f(elapsed);
+      }
+    });
+    BoxSubscription::new(subscription)
+  }
 
   pub fn new(shell_wnd: Box<dyn ShellWindow>) -> Sc<Self> {
     let wnd_id = shell_wnd.id();
@@ -301,6 +654,7 @@ impl Window {
     let tree = Box::new(WidgetTree::new(wnd_id));
     let dispatcher = RefCell::new(Dispatcher::new(wnd_id));
     let size = shell_wnd.inner_size();
+    let viewport_insets = Stateful::new(shell_wnd.viewport_insets());
     let painter = Painter::new(Rect::from_size(size));
     let window = Self {
       tree: NonNull::new(Box::into_raw(tree)).unwrap(),
@@ -310,12 +664,23 @@ impl Window {
       delay_emitter: <_>::default(),
       frame_ticker: FrameTicker::default(),
       running_animates: <_>::default(),
+      redraw_requested: Cell::new(false),
+      present_mode: Cell::new(PresentMode::Fifo),
+      max_fps: Cell::new(None),
+      last_draw: Cell::new(None),
+      focus_ring_bounds: Cell::new(None),
       frame_pool: <_>::default(),
       priority_task_queue: PriorityTaskQueue::default(),
       shell_wnd: RefCell::new(shell_wnd),
       delay_drop_widgets: <_>::default(),
       flags: Cell::new(WindowFlags::DEFAULT),
+      last_frame: RefCell::new(None),
+      vibrancy_regions: <_>::default(),
+      viewport_insets,
       pre_edit: <_>::default(),
+      tooltips_delay: Cell::new(Duration::from_millis(500)),
+      inspecting: Cell::new(None),
+      frame_timings: <_>::default(),
     };
 
     Sc::new(window)
@@ -339,12 +704,90 @@ impl Window {
 
   pub fn flags(&self) -> WindowFlags { self.flags.get() }
 
-  pub fn set_flags(&self, flags: WindowFlags) { self.flags.set(flags) }
+  pub fn set_flags(&self, flags: WindowFlags) {
+    let old = self.flags.replace(flags);
+    let changed = old.symmetric_difference(flags);
+    if changed.intersects(WindowFlags::BACKDROP | WindowFlags::INSPECTOR) {
+      self.tree().damage.borrow_mut().mark_full();
+    }
+    if changed.contains(WindowFlags::INSPECTOR) {
+      self.inspecting.set(None);
+    }
+    if changed
+      .intersects(WindowFlags::REDACT_SENSITIVE | WindowFlags::BACKDROP | WindowFlags::INSPECTOR)
+    {
+      // Repaint the whole window to apply the redaction, the backdrop or the
+      // inspector.
+      let tree = self.tree();
+      tree
+        .dirty_marker()
+        .mark(tree.root(), DirtyPhase::Paint);
+    }
+    if !flags.contains(WindowFlags::RECORD_FRAME) {
+      self.last_frame.borrow_mut().take();
+    }
+  }
+
+  /// Mark the `rect` in the window as a region where the platform backdrop
+  /// shows through in the current frame.
+  pub(crate) fn add_vibrancy_region(&self, rect: Rect) {
+    self.vibrancy_regions.borrow_mut().push(rect);
+  }
+
+  /// Return the paint record of the last drawn frame, it's only recorded when
+  /// the window sets the `WindowFlags::RECORD_FRAME` flag.
+  pub fn last_frame(&self) -> Option<PaintRecord> { self.last_frame.borrow().clone() }
+
+  /// The insets of the viewport covered by the system UI, such as the system
+  /// bars, the display cutouts and the on-screen keyboard, in logical pixels.
+  /// It's reactive, so the widgets can follow the changes, the `Scaffold` and
+  /// the `SafeArea` of the widgets library are examples.
+  pub fn viewport_insets(&self) -> Watcher<Reader<EdgeInsets>> {
+    self.viewport_insets.clone_watcher()
+  }
+
+  /// Update the viewport insets, the shell updates them by
+  /// [`Window::processes_viewport_insets_changed`].
+  pub fn set_viewport_insets(&self, insets: EdgeInsets) {
+    if *self.viewport_insets.read() != insets {
+      *self.viewport_insets.write() = insets;
+    }
+  }
 
   pub fn bubble_custom_event<E: 'static>(&self, from: WidgetId, e: E) {
     self.add_delay_event(DelayEvent::BubbleCustomEvent { from, data: Box::new(e) as Box<dyn Any> });
   }
 
+  /// Request the widget `id` to perform an accessibility action, the action is
+  /// dispatched as an [`AccessibilityActionEvent`] that bubbles from the
+  /// widget. If no widget handles the [`AccessibilityAction::Default`] action,
+  /// it is treated as a tap on the widget.
+  pub fn dispatch_accessibility_action(&self, id: WidgetId, action: AccessibilityAction) {
+    self.add_delay_event(DelayEvent::AccessibilityAction { id, action });
+  }
+
+  /// Start a drag from the `source` widget, the `DragStart` event is fired on
+  /// the source to fill the payload. The drag follows the pointer until the
+  /// pointer is released, or it's canceled by the `Escape` key.
+  ///
+  /// The `image` widget follows the pointer above all the other widgets during
+  /// the drag, its position is the pointer position minus the offset.
+  ///
+  /// Return `false` if another drag is in progress. Usually, use the
+  /// [`Draggable`] widget instead of calling it directly.
+  pub fn start_drag(&self, source: WidgetId, image: Option<(GenWidget, Vector)>) -> bool {
+    self
+      .dispatcher
+      .borrow_mut()
+      .start_drag(source, image)
+  }
+
+  /// Whether a drag is in progress in the window.
+  pub fn is_dragging(&self) -> bool { self.dispatcher.borrow().is_dragging() }
+
+  /// Cancel the drag in progress, the drag ends without a drop.
+  pub fn cancel_drag(&self) { self.dispatcher.borrow_mut().end_drag(false); }
+
   pub(crate) fn add_focus_node(&self, wid: WidgetId, auto_focus: bool, focus_type: FocusType) {
     self
       .focus_mgr
@@ -430,6 +873,21 @@ impl Window {
           let mut e = Event::PerformedLayout(LifecycleEvent::new(id, self.tree));
           self.emit(id, &mut e);
         }
+        DelayEvent::WindowResized => {
+          let tree = self.tree();
+          let listeners = tree
+            .root()
+            .descendants(tree)
+            .filter(|id| {
+              id.query_all_iter::<MixBuiltin>(tree)
+                .any(|m| m.contain_flag(MixFlags::WindowResized))
+            })
+            .collect::<Vec<_>>();
+          for id in listeners {
+            let mut e = Event::WindowResized(LifecycleEvent::new(id, self.tree));
+            self.emit(id, &mut e);
+          }
+        }
         DelayEvent::Disposed { id, parent } => {
           id.descendants(self.tree())
             .collect::<Vec<_>>()
@@ -567,6 +1025,10 @@ impl Window {
           let event = PointerEvent::from_mouse(wid, self);
           self.bottom_up_emit(&mut Event::Tap(event), None);
         }
+        DelayEvent::ContextMenu(wid) => {
+          let mut e = Event::ContextMenu(PointerEvent::from_mouse(wid, self));
+          self.bottom_up_emit(&mut e, None);
+        }
         DelayEvent::ImePreEdit { wid, pre_edit } => {
           let root = self.tree().root();
           let ime_event = ImePreEditEvent::new(pre_edit.clone(), root, self);
@@ -586,6 +1048,83 @@ impl Window {
           let mut e = Event::PointerUp(PointerEvent::from_mouse(wid, self));
           self.emit(wid, &mut e);
         }
+        DelayEvent::AccessibilityAction { id, action } => {
+          let mut e = Event::AccessibilityAction(AccessibilityActionEvent::new(action, id, self));
+          self.bottom_up_emit(&mut e, None);
+          if action == AccessibilityAction::Default && e.is_propagation() {
+            self.add_delay_event(DelayEvent::Tap(id));
+          }
+        }
+        DelayEvent::Drag { kind, id, source, data, dropped } => {
+          if !id.is_dropped(self.tree()) {
+            let mut e = DragEvent::new(data, source, id, self);
+            e.dropped = dropped;
+            let mut e = match kind {
+              DragKind::Start => Event::DragStart(e),
+              DragKind::End => Event::DragEnd(e),
+              DragKind::Enter => Event::DragEnter(e),
+              DragKind::Over => Event::DragOver(e),
+              DragKind::Leave => Event::DragLeave(e),
+              DragKind::Drop => Event::Drop(e),
+            };
+            self.emit(id, &mut e);
+          }
+        }
+        DelayEvent::FileDrop { kind, id, path } => {
+          let e = FileDropEvent::new(path, id, self);
+          let mut e = match kind {
+            FileDropKind::Hover => Event::FileHover(e),
+            FileDropKind::HoverCancel => Event::FileHoverCancel(e),
+            FileDropKind::Drop => Event::FileDrop(e),
+          };
+          self.bottom_up_emit(&mut e, None);
+        }
+        DelayEvent::TouchPointer { kind, id, grab, touch } => {
+          if !id.is_dropped(self.tree()) {
+            let pressed = matches!(kind, PointerKind::Down | PointerKind::Move);
+            let event = |target| PointerEvent::from_touch(&touch, pressed, target, self);
+            type ToEvent = fn(PointerEvent) -> Event;
+            let (capture, bubble): (Option<ToEvent>, ToEvent) = match kind {
+              PointerKind::Down => (Some(Event::PointerDownCapture), Event::PointerDown),
+              PointerKind::Move => (Some(Event::PointerMoveCapture), Event::PointerMove),
+              PointerKind::Up => (Some(Event::PointerUpCapture), Event::PointerUp),
+              PointerKind::Cancel => (Some(Event::PointerCancelCapture), Event::PointerCancel),
+              PointerKind::Tap => (Some(Event::TapCapture), Event::Tap),
+              PointerKind::ContextMenu => (None, Event::ContextMenu),
+            };
+            if grab {
+              self.emit(id, &mut bubble(event(id)));
+            } else {
+              if let Some(capture) = capture {
+                self.top_down_emit(&mut capture(event(self.tree().root())), id);
+              }
+              self.bottom_up_emit(&mut bubble(event(id)), None);
+              if kind == PointerKind::Down {
+                self
+                  .focus_mgr
+                  .borrow_mut()
+                  .refresh_focus(self.tree());
+              }
+            }
+          }
+        }
+        DelayEvent::Touch { kind, id, touch, touches } => {
+          if !id.is_dropped(self.tree()) {
+            let e = TouchEvent::new(touch, touches, id, self);
+            let mut e = match kind {
+              TouchKind::Start => Event::TouchStart(e),
+              TouchKind::Move => Event::TouchMove(e),
+              TouchKind::End => Event::TouchEnd(e),
+              TouchKind::Cancel => Event::TouchCancel(e),
+            };
+            self.bottom_up_emit(&mut e, None);
+          }
+        }
+        DelayEvent::Gesture { id, gesture } => {
+          if !id.is_dropped(self.tree()) {
+            self.emit(id, &mut gesture.into_event(id, self));
+          }
+        }
         DelayEvent::BubbleCustomEvent { from: id, data } => {
           let mut e = Event::CustomEvent(new_custom_event(CommonEvent::new(id, self.tree), data));
           self.bottom_up_emit(&mut e, None);
@@ -595,12 +1134,10 @@ impl Window {
   }
 
   fn emit(&self, id: WidgetId, e: &mut Event) {
-    id.query_all_iter::<MixBuiltin>(self.tree())
-      .for_each(|m| {
-        if m.contain_flag(e.flags()) {
-          m.dispatch(e);
-        }
-      })
+    let mixes = id.query_all_iter::<MixBuiltin>(self.tree());
+    MixBuiltin::prioritized_listeners(mixes, e.flags())
+      .into_iter()
+      .for_each(|mut s| s.next(&mut *e))
   }
 
   fn top_down_emit(&self, e: &mut Event, bottom: WidgetId) {
@@ -613,12 +1150,11 @@ impl Window {
     let mut buffer = SmallVec::new();
     path.iter().rev().all(|id| {
       e.capture_to_child(*id, &mut buffer);
-      id.query_all_iter::<MixBuiltin>(tree)
-        .rev()
-        .all(|m| {
-          if m.contain_flag(e.flags()) {
-            m.dispatch(e);
-          }
+      let mixes = id.query_all_iter::<MixBuiltin>(tree).rev();
+      MixBuiltin::prioritized_listeners(mixes, e.flags())
+        .into_iter()
+        .all(|mut s| {
+          s.next(&mut *e);
           e.is_propagation()
         })
     });
@@ -635,12 +1171,13 @@ impl Window {
       .take_while(|id| Some(*id) != up)
       .all(|id| {
         e.bubble_to_parent(id);
-        id.query_all_iter::<MixBuiltin>(tree).all(|m| {
-          if m.contain_flag(e.flags()) {
-            m.dispatch(e);
-          }
-          e.is_propagation()
-        })
+        let mixes = id.query_all_iter::<MixBuiltin>(tree);
+        MixBuiltin::prioritized_listeners(mixes, e.flags())
+          .into_iter()
+          .all(|mut s| {
+            s.next(&mut *e);
+            e.is_propagation()
+          })
       });
   }
 
@@ -670,10 +1207,66 @@ impl Window {
     self.tree().map_from_global(point, id)
   }
 
+  /// Translates the `point` in the coordinate of the widget `from` to the
+  /// coordinate of the widget `to`, the layout and the transforms of the
+  /// widgets between them are both counted.
+  ///
+  /// It's useful to position an overlay or a custom-painted decoration
+  /// relative to another widget, track the id of that widget by
+  /// [`TrackWidgetId::track_id`].
+  pub fn map_to(&self, point: Point, from: WidgetId, to: WidgetId) -> Point {
+    self.tree().map_to(point, from, to)
+  }
+
+  /// The rect of the widget `id` in the global window coordinates, or `None`
+  /// if the widget isn't laid out yet.
+  ///
+  /// The rect is the bounds of the widget box if the widget or its ancestors
+  /// are transformed.
+  pub fn global_box_rect(&self, id: WidgetId) -> Option<Rect> {
+    let tree = self.tree();
+    let size = tree.layout_info(id)?.size?;
+    let rect = Rect::from_size(size);
+    Some(
+      tree
+        .global_transform(id)
+        .outer_transformed_rect(&rect),
+    )
+  }
+
+  /// Build the accessibility tree of the window from the [`Semantics`] of the
+  /// widgets, the root node is the root of the window.
+  pub fn semantics_tree(&self) -> SemanticsNode {
+    let tree = self.tree();
+    SemanticsNode::build(tree.root(), tree)
+  }
+
+  /// Shape the `text` to paint over the frame by the debug overlays, like the
+  /// inspector.
+  pub(crate) fn overlay_glyphs(&self, text: String) -> VisualGlyphs {
+    let tree = self.tree();
+    let mut style = {
+      let _guard = BuildCtx::init_for(tree.root(), self.tree);
+      Provider::of::<TextStyle>(BuildCtx::get())
+        .unwrap()
+        .clone()
+    };
+    style.font_size = OVERLAY_FONT_SIZE;
+    style.line_height = OVERLAY_FONT_SIZE * 1.5;
+    text_glyph(text.into(), &style, TextAlign::Start, INFINITY_SIZE)
+  }
+
   pub fn widget_size(&self, id: WidgetId) -> Option<Size> { self.tree().store.layout_box_size(id) }
 
   pub fn widget_pos(&self, id: WidgetId) -> Option<Point> { self.tree().store.layout_box_pos(id) }
 
+  /// Return the total count of the layouts performed by the widgets of this
+  /// window. Compare it between frames to know how many widgets were laid out.
+  pub fn performed_layout_count(&self) -> usize { self.tree().store.performed_count() }
+
+  /// Return the statistics of the widgets stored in the tree of this window.
+  pub fn tree_stats(&self) -> TreeStats { self.tree().stats() }
+
   pub(crate) fn tree(&self) -> &WidgetTree {
     // Safety: Please refer to the comments in `WidgetTree::tree_mut` for more
     // information.
@@ -701,6 +1294,30 @@ impl Window {
   /// device.
   pub fn device_pixel_ratio(&self) -> f32 { self.shell_wnd.borrow().device_pixel_ratio() }
 
+  /// Notify the window that its device pixel ratio changed, for example, it's
+  /// moved to a monitor of a different DPI. The whole window is laid out and
+  /// painted again at the new ratio.
+  pub fn processes_device_pixel_ratio_changed(&self) {
+    self
+      .shell_wnd
+      .borrow_mut()
+      .on_device_pixel_ratio_changed();
+    let tree = self.tree_mut();
+    tree.store.reset_sizes();
+    tree.damage.borrow_mut().mark_full();
+    tree
+      .dirty_marker()
+      .mark(tree.root(), DirtyPhase::Layout);
+  }
+
+  /// Notify the window that its viewport insets may be changed, for example,
+  /// it's resized or rotated, or the on-screen keyboard shows or hides. The
+  /// insets are read from the shell window.
+  pub fn processes_viewport_insets_changed(&self) {
+    let insets = self.shell_wnd.borrow().viewport_insets();
+    self.set_viewport_insets(insets);
+  }
+
   pub fn set_title(&self, title: &str) -> &Self {
     self.shell_wnd.borrow_mut().set_title(title);
     self
@@ -738,6 +1355,17 @@ impl Window {
     self
   }
 
+  /// Sets the purpose of the IME input, so the IME can adjust its behavior.
+  /// For example, use `ImePurpose::Password` to disable the IME reconversion
+  /// and candidate history for sensitive content.
+  pub fn set_ime_purpose(&self, purpose: ImePurpose) -> &Self {
+    self
+      .shell_wnd
+      .borrow_mut()
+      .set_ime_purpose(purpose);
+    self
+  }
+
   pub fn is_visible(&self) -> Option<bool> { self.shell_wnd.borrow().is_visible() }
 
   pub fn set_visible(&self, visible: bool) -> &Self {
@@ -754,6 +1382,42 @@ impl Window {
     self
   }
 
+  pub fn set_max_size(&self, size: Size) -> &Self {
+    self.shell_wnd.borrow_mut().set_max_size(size);
+    self
+  }
+
+  pub fn is_resizable(&self) -> bool { self.shell_wnd.borrow().is_resizable() }
+
+  pub fn set_resizable(&self, resizable: bool) -> &Self {
+    self
+      .shell_wnd
+      .borrow_mut()
+      .set_resizable(resizable);
+    self
+  }
+
+  /// Sets whether the window has a border, a title bar, etc.
+  pub fn set_decorations(&self, decorations: bool) -> &Self {
+    self
+      .shell_wnd
+      .borrow_mut()
+      .set_decorations(decorations);
+    self
+  }
+
+  pub fn is_fullscreen(&self) -> bool { self.shell_wnd.borrow().is_fullscreen() }
+
+  /// Make the window borderless fullscreen on its current monitor when
+  /// `fullscreen` is true, restore it otherwise.
+  pub fn set_fullscreen(&self, fullscreen: bool) -> &Self {
+    self
+      .shell_wnd
+      .borrow_mut()
+      .set_fullscreen(fullscreen);
+    self
+  }
+
   pub fn is_pre_editing(&self) -> bool { self.pre_edit.borrow().is_some() }
 
   pub fn force_exit_pre_edit(&self) {
@@ -802,12 +1466,52 @@ impl Drop for Window {
   fn drop(&mut self) { let _release_tree = unsafe { Box::from_raw(self.tree.as_ptr()) }; }
 }
 
+/// The kind of the drag event in `DelayEvent::Drag`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DragKind {
+  Start,
+  End,
+  Enter,
+  Over,
+  Leave,
+  Drop,
+}
+
+/// The kind of the file event in `DelayEvent::FileDrop`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FileDropKind {
+  Hover,
+  HoverCancel,
+  Drop,
+}
+
+/// The kind of the pointer event in `DelayEvent::TouchPointer`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PointerKind {
+  Down,
+  Move,
+  Up,
+  Cancel,
+  Tap,
+  ContextMenu,
+}
+
+/// The kind of the touch event in `DelayEvent::Touch`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TouchKind {
+  Start,
+  Move,
+  End,
+  Cancel,
+}
+
 /// Event that delay to emit, emit it when the window is not busy(nobody borrow
 /// parts of the window).
 #[derive(Debug)]
 pub(crate) enum DelayEvent {
   Mounted(WidgetId),
   PerformedLayout(WidgetId),
+  WindowResized,
   Disposed {
     parent: Option<WidgetId>,
     id: WidgetId,
@@ -854,6 +1558,7 @@ pub(crate) enum DelayEvent {
     up: Option<WidgetId>,
   },
   Tap(WidgetId),
+  ContextMenu(WidgetId),
   ImePreEdit {
     wid: WidgetId,
     pre_edit: ImePreEdit,
@@ -861,6 +1566,40 @@ pub(crate) enum DelayEvent {
   GrabPointerDown(WidgetId),
   GrabPointerMove(WidgetId),
   GrabPointerUp(WidgetId),
+  AccessibilityAction {
+    id: WidgetId,
+    action: AccessibilityAction,
+  },
+  Drag {
+    kind: DragKind,
+    id: WidgetId,
+    source: WidgetId,
+    data: DragData,
+    dropped: bool,
+  },
+  FileDrop {
+    kind: FileDropKind,
+    id: WidgetId,
+    path: PathBuf,
+  },
+  /// The pointer event of a finger, it's only emitted to the widget if `grab`
+  /// is true.
+  TouchPointer {
+    kind: PointerKind,
+    id: WidgetId,
+    grab: bool,
+    touch: ActiveTouch,
+  },
+  Touch {
+    kind: TouchKind,
+    id: WidgetId,
+    touch: TouchPoint,
+    touches: Vec<TouchPoint>,
+  },
+  Gesture {
+    id: WidgetId,
+    gesture: Gesture,
+  },
   BubbleCustomEvent {
     from: WidgetId,
     data: Box<dyn Any>,
@@ -899,6 +1638,126 @@ mod tests {
     wnd.assert_root_size(new_size);
   }
 
+  #[test]
+  fn relayout_after_device_pixel_ratio_changed() {
+    reset_test_env!();
+
+    let (layouts, w_layouts) = split_value(0);
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        @MockBox {
+          size: Size::new(50., 50.),
+          on_performed_layout: move |_| *$w_layouts.write() += 1,
+        }
+      }
+    });
+    wnd.draw_frame();
+    assert_eq!(*layouts.read(), 1);
+
+    wnd
+      .shell_wnd()
+      .borrow_mut()
+      .as_any_mut()
+      .downcast_mut::<TestShellWindow>()
+      .unwrap()
+      .device_pixel_ratio = 2.;
+    wnd.processes_device_pixel_ratio_changed();
+    wnd.draw_frame();
+    assert_eq!(*layouts.read(), 2);
+    assert_eq!(wnd.device_pixel_ratio(), 2.);
+  }
+
+  #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+  #[test]
+  fn window_resized_event() {
+    reset_test_env!();
+
+    let (sizes, w_sizes) = split_value(vec![]);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: INFINITY_SIZE,
+          @MockBox {
+            size: Size::zero(),
+            on_window_resized: move |e| $w_sizes.write().push(e.window().size()),
+          }
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+    assert!(sizes.read().is_empty());
+
+    let new_size = Size::new(200., 150.);
+    wnd.request_resize(new_size);
+    wnd.draw_frame();
+    assert_eq!(*sizes.read(), vec![new_size]);
+    wnd.assert_root_size(new_size);
+  }
+
+  #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+  #[test]
+  fn record_last_frame() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! { @Container { size: Size::new(50., 50.), background: Color::RED } },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+    assert!(wnd.last_frame().is_none());
+
+    wnd.set_flags(wnd.flags() | WindowFlags::RECORD_FRAME);
+    wnd
+      .tree()
+      .dirty_marker()
+      .mark(wnd.tree().root(), DirtyPhase::Paint);
+    wnd.draw_frame();
+    let record = wnd.last_frame().unwrap();
+    assert_eq!(record.viewport, Rect::from_size(Size::new(100., 100.)));
+    assert!(!record.commands.is_empty());
+
+    let bytes = record.to_bytes().unwrap();
+    let replay = PaintRecord::from_bytes(&bytes).unwrap();
+    assert_eq!(replay.commands.len(), record.commands.len());
+  }
+
+  #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+  #[test]
+  fn inject_ui_events() {
+    reset_test_env!();
+
+    let (taps, w_taps) = split_value(0);
+    let (chars, w_chars) = split_value(String::new());
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        auto_focus: true,
+        on_tap: move |_| *$w_taps.write() += 1,
+        on_chars: move |e| $w_chars.write().push_str(&e.chars),
+      }
+    });
+    wnd.draw_frame();
+
+    UiEvent::tap_at(Point::new(50., 50.))
+      .into_iter()
+      .for_each(|e| wnd.inject_event(e));
+    wnd.run_frame_tasks();
+    assert_eq!(*taps.read(), 1);
+
+    wnd.inject_event(UiEvent::Chars("hi".into()));
+    wnd.run_frame_tasks();
+    assert_eq!(&*chars.read(), "hi");
+
+    // Tapping outside misses the box and blurs it.
+    UiEvent::tap_at(Point::new(150., 150.))
+      .into_iter()
+      .for_each(|e| wnd.inject_event(e));
+    wnd.run_frame_tasks();
+    assert_eq!(*taps.read(), 1);
+  }
+
   #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
   #[test]
   fn fire_tasks_before_new_window() {
@@ -918,4 +1777,49 @@ mod tests {
 
     wnd.draw_frame();
   }
+
+  #[test]
+  fn request_redraw_and_on_frame() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! { @MockBox { size: Size::new(10., 10.) } });
+    wnd.draw_frame();
+    assert!(!wnd.need_draw());
+
+    wnd.request_redraw();
+    assert!(wnd.need_draw());
+    wnd.draw_frame();
+    assert!(!wnd.need_draw());
+
+    wnd.set_flags(wnd.flags() | WindowFlags::CONTINUOUS_RENDER);
+    assert!(wnd.need_draw());
+
+    let (frames, w_frames) = split_value(vec![]);
+    let u = wnd.on_frame(move |elapsed| w_frames.write().push(elapsed));
+    let frame = Duration::from_millis(16);
+    wnd.tick(frame);
+    wnd.tick(frame);
+    u.unsubscribe();
+    wnd.tick(frame);
+    assert_eq!(*frames.read(), [Duration::ZERO, frame]);
+  }
+
+  #[test]
+  fn max_fps() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! { @MockBox { size: Size::new(10., 10.) } });
+    wnd.set_max_fps(Some(10));
+    wnd.tick(Duration::ZERO);
+
+    wnd.request_redraw();
+    wnd.tick(Duration::from_millis(50));
+    // Held back in the interval of 100ms.
+    assert!(wnd.next_frame_at().is_some());
+    assert!(wnd.need_draw());
+
+    wnd.tick(Duration::from_millis(50));
+    assert!(!wnd.need_draw());
+    assert!(wnd.next_frame_at().is_some());
+  }
 }
@@ -0,0 +1,147 @@
+use crate::prelude::*;
+
+impl Window {
+  /// Render the widget `id` and its descendants to an image in device pixels,
+  /// cropped to the bounds they paint. It's useful to export a widget as an
+  /// image or to make a thumbnail of it.
+  ///
+  /// The widget is painted by its last layout, without the opacity of its
+  /// ancestors, and the area not painted is transparent. Returns `None` if the
+  /// widget paints nothing or the shell window can't render to an image.
+  pub fn capture_widget(&self, id: WidgetId) -> Option<PixelImage> {
+    let (bounds, commands) = self.paint_widget_offscreen(id)?;
+    self
+      .shell_wnd
+      .borrow_mut()
+      .render_image(bounds, &commands, Color::TRANSPARENT)
+  }
+
+  /// Render the content of the window to an image in device pixels, the same
+  /// as the frame presented but without the debug overlays. Returns `None` if
+  /// the shell window can't render to an image.
+  pub fn capture_frame(&self) -> Option<PixelImage> {
+    let tree = self.tree();
+    let surface = {
+      let _guard = BuildCtx::init_for(tree.root(), self.tree);
+      Palette::of(BuildCtx::get()).surface()
+    };
+    let viewport = Rect::from_size(self.size());
+    let commands = self
+      .paint_widget_offscreen(tree.root())
+      .map(|(_, commands)| commands)
+      .unwrap_or_default();
+    self
+      .shell_wnd
+      .borrow_mut()
+      .render_image(viewport, &commands, surface)
+  }
+
+  /// Paint the subtree of `id` in the window coordinate out of the frame,
+  /// return the bounds it painted and the paint commands.
+  pub(crate) fn paint_widget_offscreen(&self, id: WidgetId) -> Option<(Rect, Vec<PaintCommand>)> {
+    let tree = self.tree();
+    tree.store.layout_box_size(id)?;
+
+    // Don't clip the part of the widget out of the window.
+    let viewport = Rect::new(Point::splat(f32::MIN / 4.), Size::splat(f32::MAX / 2.));
+    let mut painter = Painter::new(viewport);
+    if let Some(p) = id.parent(tree) {
+      painter.set_transform(tree.global_transform(p));
+    }
+
+    // The offscreen paint is not a part of the frame, keep the damage of the
+    // frame unchanged.
+    let painting = tree
+      .damage
+      .borrow_mut()
+      .replace_painting(<_>::default());
+    id.paint_subtree(tree, &mut painter);
+    tree
+      .damage
+      .borrow_mut()
+      .replace_painting(painting);
+
+    let bounds = painter.commands_bounds(0)?;
+    let commands = painter.finish().to_vec();
+    Some((bounds, commands))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*, window::WindowFlags};
+
+  #[test]
+  fn paint_widget_offscreen() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockMulti {
+          @MockBox { size: Size::new(10., 10.) }
+          @MockBox {
+            size: Size::new(20., 20.),
+            transform: Transform::scale(2., 2.),
+            @Container {
+              size: Size::new(5., 5.),
+              background: Color::RED,
+            }
+          }
+        }
+      },
+      Size::new(30., 30.),
+    );
+    wnd.draw_frame();
+
+    let tree = wnd.tree();
+    let multi = tree.root().first_child(tree).unwrap();
+    let scaled = multi.last_child(tree).unwrap();
+    let (bounds, commands) = wnd.paint_widget_offscreen(scaled).unwrap();
+    // The widget is scaled and partly out of the window.
+    assert_eq!(bounds, Rect::new(Point::new(10., 0.), Size::new(10., 10.)));
+    assert_eq!(commands.len(), 1);
+
+    let first = multi.first_child(tree).unwrap();
+    assert!(wnd.paint_widget_offscreen(first).is_none());
+    // No shell renderer in the test window.
+    assert!(wnd.capture_widget(scaled).is_none());
+  }
+
+  #[test]
+  fn capture_redacts_sensitive_descendant() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(20., 20.),
+          sensitive: true,
+          @Container {
+            size: Size::new(10., 10.),
+            background: Color::RED,
+          }
+        }
+      },
+      Size::new(30., 30.),
+    );
+    wnd.set_flags(wnd.flags() | WindowFlags::REDACT_SENSITIVE);
+    wnd.draw_frame();
+
+    let tree = wnd.tree();
+    let child = tree.root().first_child(tree).unwrap();
+    let (_, commands) = wnd.paint_widget_offscreen(child).unwrap();
+    let colors = commands
+      .into_iter()
+      .filter_map(|cmd| match cmd {
+        PaintCommand::Path(PathCommand {
+          action: PaintPathAction::Paint { brush: CommandBrush::Color(c), .. },
+          ..
+        }) => Some(c),
+        _ => None,
+      })
+      .collect::<Vec<_>>();
+    // The child of the sensitive widget is captured as a placeholder.
+    assert_eq!(colors, vec![Color::BLACK]);
+  }
+}
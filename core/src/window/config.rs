@@ -0,0 +1,180 @@
+use crate::prelude::*;
+
+/// The attributes of a window that can be changed at runtime. A field of
+/// `None` leaves the attribute of the window untouched.
+///
+/// Apply it once by [`Window::apply_config`], or bind a state of it by
+/// [`Window::bind_config`] to keep the window following the state.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+///
+/// let config = WindowConfig::default()
+///   .with_title("Editor")
+///   .with_min_size(Size::new(320., 240.))
+///   .with_resizable(true);
+/// assert_eq!(config.title.as_deref(), Some("Editor"));
+/// ```
+#[derive(Clone, Default, PartialEq)]
+pub struct WindowConfig {
+  pub title: Option<String>,
+  pub icon: Option<Resource<PixelImage>>,
+  pub min_size: Option<Size>,
+  pub max_size: Option<Size>,
+  pub resizable: Option<bool>,
+  pub decorations: Option<bool>,
+  pub fullscreen: Option<bool>,
+}
+
+impl WindowConfig {
+  pub fn with_title(mut self, title: impl Into<String>) -> Self {
+    self.title = Some(title.into());
+    self
+  }
+
+  pub fn with_icon(mut self, icon: Resource<PixelImage>) -> Self {
+    self.icon = Some(icon);
+    self
+  }
+
+  /// Sets the minimum size of the window client area.
+  pub fn with_min_size(mut self, size: Size) -> Self {
+    self.min_size = Some(size);
+    self
+  }
+
+  /// Sets the maximum size of the window client area.
+  pub fn with_max_size(mut self, size: Size) -> Self {
+    self.max_size = Some(size);
+    self
+  }
+
+  pub fn with_resizable(mut self, resizable: bool) -> Self {
+    self.resizable = Some(resizable);
+    self
+  }
+
+  /// Sets whether the window has a border, a title bar, etc.
+  pub fn with_decorations(mut self, decorations: bool) -> Self {
+    self.decorations = Some(decorations);
+    self
+  }
+
+  /// Sets whether the window is borderless fullscreen on its monitor.
+  pub fn with_fullscreen(mut self, fullscreen: bool) -> Self {
+    self.fullscreen = Some(fullscreen);
+    self
+  }
+}
+
+impl Window {
+  /// Apply the attributes set in the `config` to the window.
+  pub fn apply_config(&self, config: &WindowConfig) { self.apply_config_diff(None, config); }
+
+  /// Keep the window following the `config` state. The config is applied at
+  /// once, and after every modification of the state only the attributes
+  /// that changed are applied again, so the user can still change the others,
+  /// for example, resize the window.
+  ///
+  /// Unsubscribe the returned subscription to end the binding, it does
+  /// nothing after the window is closed.
+  pub fn bind_config(
+    &self, config: impl StateWatcher<Value = WindowConfig>,
+  ) -> BoxSubscription<'static> {
+    let mut last = config.read().clone();
+    self.apply_config(&last);
+
+    let wnd_id = self.id();
+    let reader = config.clone_reader();
+    config.modifies().subscribe(move |_| {
+      let Some(wnd) = AppCtx::get_window(wnd_id) else { return };
+      let config = reader.read().clone();
+      wnd.apply_config_diff(Some(&last), &config);
+      last = config;
+    })
+  }
+
+  fn apply_config_diff(&self, last: Option<&WindowConfig>, config: &WindowConfig) {
+    macro_rules! changed {
+      ($field:ident) => {
+        config
+          .$field
+          .as_ref()
+          .filter(|v| last.map_or(true, |last| last.$field.as_ref() != Some(*v)))
+      };
+    }
+
+    if let Some(title) = changed!(title) {
+      self.set_title(title);
+    }
+    if let Some(icon) = changed!(icon) {
+      self.set_icon(icon);
+    }
+    if let Some(size) = changed!(min_size) {
+      self.set_min_size(*size);
+    }
+    if let Some(size) = changed!(max_size) {
+      self.set_max_size(*size);
+    }
+    if let Some(resizable) = changed!(resizable) {
+      self.set_resizable(*resizable);
+    }
+    if let Some(decorations) = changed!(decorations) {
+      self.set_decorations(*decorations);
+    }
+    if let Some(fullscreen) = changed!(fullscreen) {
+      self.set_fullscreen(*fullscreen);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  fn shell(wnd: &TestWindow) -> std::cell::Ref<'_, TestShellWindow> {
+    std::cell::Ref::map(wnd.shell_wnd().borrow(), |shell| {
+      shell
+        .as_any()
+        .downcast_ref::<TestShellWindow>()
+        .unwrap()
+    })
+  }
+
+  #[test]
+  fn follow_config_state() {
+    reset_test_env!();
+
+    let wnd = TestWindow::new(fn_widget! { @Void {} });
+    let config = Stateful::new(
+      WindowConfig::default()
+        .with_title("first")
+        .with_min_size(Size::new(100., 100.)),
+    );
+    let _u = wnd.bind_config(config.clone_watcher());
+    assert_eq!(shell(&wnd).title, "first");
+    assert_eq!(shell(&wnd).min_size, Some(Size::new(100., 100.)));
+    assert!(!wnd.is_fullscreen());
+
+    {
+      let mut config = config.write();
+      config.title = Some("second".into());
+      config.fullscreen = Some(true);
+      config.resizable = Some(false);
+    }
+    AppCtx::run_until_stalled();
+    assert_eq!(shell(&wnd).title, "second");
+    assert!(wnd.is_fullscreen());
+    assert!(!wnd.is_resizable());
+
+    // Only the changed attributes are applied again.
+    wnd.set_title("by user");
+    config.write().max_size = Some(Size::new(800., 600.));
+    AppCtx::run_until_stalled();
+    assert_eq!(shell(&wnd).title, "by user");
+    assert_eq!(shell(&wnd).max_size, Some(Size::new(800., 600.)));
+  }
+}
@@ -0,0 +1,160 @@
+use crate::prelude::*;
+
+/// The style of the focus ring that the framework paints around the focused
+/// widget when the focus is moved by the keyboard.
+///
+/// The ring follows the radius of the nearest ancestor decorated by a radius,
+/// including the focused widget itself. A theme customizes it by
+/// `CustomStyles`.
+///
+/// # Example
+///
+/// ```
+/// use ribir::prelude::*;
+///
+/// let mut theme = Theme::default();
+/// theme
+///   .custom_styles
+///   .set_custom_style(FocusRingStyle { color: Color::from_u32(0x1A73E8FF), width: 3., offset: 2. });
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct FocusRingStyle {
+  /// The color of the ring.
+  pub color: Color,
+  /// The line width of the ring.
+  pub width: f32,
+  /// The gap between the ring and the box of the focused widget, a negative
+  /// value draws the ring inside the box.
+  pub offset: f32,
+}
+
+impl CustomStyle for FocusRingStyle {
+  fn default_style(ctx: &impl AsRef<ProviderCtx>) -> Self {
+    FocusRingStyle { color: Palette::of(ctx).primary(), width: 2., offset: 2. }
+  }
+}
+
+/// The ring around the focused widget to paint.
+struct FocusRing {
+  /// The center line of the stroke.
+  rect: Rect,
+  radius: Radius,
+  style: FocusRingStyle,
+}
+
+impl FocusRing {
+  /// The area the ring covers.
+  fn bounds(&self) -> Rect {
+    let half = self.style.width / 2.;
+    self.rect.inflate(half, half).round_out()
+  }
+}
+
+impl Window {
+  /// Return if the focus ring is showing, the focus is moved by the keyboard
+  /// and a widget is focused.
+  pub fn is_focus_visible(&self) -> bool {
+    let focus_mgr = self.focus_mgr.borrow();
+    focus_mgr.focus_visible() && focus_mgr.focusing().is_some()
+  }
+
+  /// Paint the focus ring around the focused widget over the frame.
+  pub(crate) fn paint_focus_ring(&self) {
+    let ring = self.focus_ring();
+    let bounds = ring.as_ref().map(FocusRing::bounds);
+    let last = self.focus_ring_bounds.replace(bounds);
+    if last != bounds {
+      // The ring is not painted by any widget, damage the area it moved from
+      // and to.
+      let mut damage = self.tree().damage.borrow_mut();
+      last
+        .into_iter()
+        .chain(bounds)
+        .for_each(|rect| damage.mark_area(rect));
+    }
+
+    let Some(FocusRing { rect, radius, style }) = ring else { return };
+    let mut painter = self.painter.borrow_mut();
+    painter.save();
+    painter.set_transform(Transform::identity());
+    painter
+      .set_stroke_brush(style.color)
+      .set_line_width(style.width)
+      .rect_round(&rect, &radius)
+      .stroke();
+    painter.restore();
+  }
+
+  fn focus_ring(&self) -> Option<FocusRing> {
+    if !self.is_focus_visible() {
+      return None;
+    }
+    let id = self.focus_mgr.borrow().focusing()?;
+    let tree = self.tree();
+    let size = tree.store.layout_box_size(id)?;
+    let rect = Rect::new(tree.map_to_global(Point::zero(), id), size);
+
+    let style = {
+      let _guard = BuildCtx::init_for(id, self.tree);
+      FocusRingStyle::of(BuildCtx::get())
+    };
+    let radius = id
+      .ancestors(tree)
+      .find_map(|p| {
+        p.query_ref::<RadiusWidget>(tree)
+          .map(|r| r.radius)
+      })
+      .unwrap_or_default();
+
+    let inflate = style.offset + style.width / 2.;
+    let grow = |r: f32| if r > 0. { (r + inflate).max(0.) } else { 0. };
+    let Radius { top_left, top_right, bottom_left, bottom_right } = radius;
+    let radius =
+      Radius::new(grow(top_left), grow(top_right), grow(bottom_left), grow(bottom_right));
+    Some(FocusRing { rect: rect.inflate(inflate, inflate), radius, style })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[test]
+  fn only_visible_by_keyboard() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockMulti {
+        @MockBox { size: Size::new(10., 10.), tab_index: 0_i16 }
+        @MockBox {
+          size: Size::new(20., 10.),
+          tab_index: 0_i16,
+          radius: Radius::all(4.),
+        }
+      }
+    });
+    wnd.draw_frame();
+    assert!(!wnd.is_focus_visible());
+
+    wnd.request_next_focus();
+    wnd.draw_frame();
+    assert!(wnd.is_focus_visible());
+    let ring = wnd.focus_ring().unwrap();
+    assert_eq!(ring.rect, Rect::new(Point::new(-3., -3.), Size::new(16., 16.)));
+    assert_eq!(ring.radius, Radius::default());
+
+    wnd.request_next_focus();
+    wnd.draw_frame();
+    let ring = wnd.focus_ring().unwrap();
+    assert_eq!(ring.rect, Rect::new(Point::new(7., -3.), Size::new(26., 16.)));
+    assert_eq!(ring.radius, Radius::all(7.));
+
+    // Clicking a widget focuses it but hides the ring.
+    wnd.process_input(TestInput::CursorMove(Point::new(5., 5.)));
+    wnd.process_input(TestInput::MousePress(MouseButton::Left));
+    wnd.draw_frame();
+    assert!(!wnd.is_focus_visible());
+    assert!(wnd.focus_ring().is_none());
+  }
+}
@@ -0,0 +1,186 @@
+use std::fmt::Write;
+
+use crate::{prelude::*, widget_tree::WidgetTree, window::WindowFlags};
+
+/// The color to highlight the inspected widget.
+const HIGHLIGHT: Color = Color::from_u32(0x4285F4FF);
+/// The padding between the information of the inspected widget and its box.
+const INFO_PADDING: f32 = 4.;
+
+impl Window {
+  /// Show the inspector if it's hidden, otherwise hide it, see
+  /// [`WindowFlags::INSPECTOR`].
+  ///
+  /// In the debug build, it's also toggled by pressing `Ctrl+Shift+I`.
+  pub fn toggle_inspector(&self) { self.set_flags(self.flags() ^ WindowFlags::INSPECTOR); }
+
+  /// The widget under the cursor that the inspector highlights.
+  pub fn inspecting(&self) -> Option<WidgetId> {
+    self
+      .inspecting
+      .get()
+      .filter(|id| !id.is_dropped(self.tree()))
+  }
+
+  /// Inspect the widget `id`, and log its ancestor chain if it's a different
+  /// one from the last inspected widget.
+  pub(crate) fn inspect(&self, id: Option<WidgetId>) {
+    if self.inspecting.replace(id) == id {
+      return;
+    }
+    if let Some(id) = id {
+      log::info!("[inspector]: {}", self.ancestor_chain(id));
+    }
+    let tree = self.tree();
+    tree
+      .dirty_marker()
+      .mark(tree.root(), DirtyPhase::Paint);
+  }
+
+  /// The widget `id` and its ancestors from the innermost to the root, like
+  /// `MockBox#3 < MockMulti#2 < Root#1`.
+  pub(crate) fn ancestor_chain(&self, id: WidgetId) -> String {
+    let tree = self.tree();
+    let mut chain = String::new();
+    for (i, p) in id.ancestors(tree).enumerate() {
+      if i > 0 {
+        chain.push_str(" < ");
+      }
+      let _ = write!(chain, "{}#{}", widget_name(p, tree), p.0);
+    }
+    chain
+  }
+
+  /// The information of the widget `id` shows by the inspector.
+  pub(crate) fn inspect_info(&self, id: WidgetId) -> String {
+    let tree = self.tree();
+    let mut info = format!("{}#{}", widget_name(id, tree), id.0);
+    if let Some(LayoutInfo { clamp, size: Some(size), pos }) = tree.layout_info(id) {
+      let _ = write!(
+        info,
+        "\npos: ({}, {}) size: {} x {}\nclamp: {} x {} - {} x {}",
+        pos.x,
+        pos.y,
+        size.width,
+        size.height,
+        clamp.min.width,
+        clamp.min.height,
+        clamp.max.width,
+        clamp.max.height
+      );
+    }
+    info
+  }
+
+  /// Paint the highlight and the information of the inspected widget over the
+  /// frame.
+  pub(crate) fn paint_inspector(&self) {
+    let Some(id) = self.inspecting() else { return };
+    let tree = self.tree();
+    let Some(size) = tree.store.layout_box_size(id) else { return };
+    let rect = Rect::new(tree.map_to_global(Point::zero(), id), size);
+
+    let glyphs = self.overlay_glyphs(self.inspect_info(id));
+    let text_rect = glyphs.visual_rect();
+
+    // Place the information below the widget, or above it if there is no enough
+    // space, and keep it in the window.
+    let viewport = self.size();
+    let info_size = text_rect.size + Size::splat(INFO_PADDING * 2.);
+    let mut y = rect.max_y();
+    if y + info_size.height > viewport.height {
+      y = rect.min_y() - info_size.height;
+    }
+    let x = rect
+      .min_x()
+      .min(viewport.width - info_size.width)
+      .max(0.);
+    let info_rect = Rect::new(Point::new(x, y.max(0.)), info_size);
+
+    let mut painter = self.painter.borrow_mut();
+    painter.save();
+    painter.set_transform(Transform::identity());
+    painter
+      .set_fill_brush(HIGHLIGHT.with_alpha(0.2))
+      .rect(&rect)
+      .fill();
+    painter
+      .set_stroke_brush(HIGHLIGHT)
+      .set_line_width(1.)
+      .rect(&rect)
+      .stroke();
+    painter
+      .set_fill_brush(Color::BLACK.with_alpha(0.8))
+      .rect(&info_rect)
+      .fill();
+    painter
+      .translate(info_rect.min_x() + INFO_PADDING, info_rect.min_y() + INFO_PADDING)
+      .set_fill_brush(Color::WHITE);
+    paint_text(&mut painter, &glyphs, PaintingStyle::Fill, text_rect);
+    painter.restore();
+
+    // The overlay is not painted by any widget, so the whole window is damaged.
+    tree.damage.borrow_mut().mark_full();
+  }
+}
+
+/// The type name of the widget without the module paths, like `MockBox`.
+fn widget_name(id: WidgetId, tree: &WidgetTree) -> String {
+  let name = id.assert_get(tree).debug_name();
+  let mut short = String::with_capacity(name.len());
+  let mut segment_start = 0;
+  for (i, c) in name.char_indices() {
+    if c.is_alphanumeric() || c == '_' {
+      continue;
+    }
+    if c == ':' {
+      segment_start = i + 1;
+    } else {
+      short.push_str(&name[segment_start..i]);
+      short.push(c);
+      segment_start = i + c.len_utf8();
+    }
+  }
+  short.push_str(&name[segment_start..]);
+  short
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[test]
+  fn inspect_hovered_widget() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockMulti {
+        @MockBox { size: Size::new(10., 10.) }
+        @MockBox { size: Size::new(20., 10.) }
+      }
+    });
+    wnd.draw_frame();
+    wnd.set_flags(wnd.flags() | WindowFlags::INSPECTOR);
+    wnd
+      .dispatcher
+      .borrow_mut()
+      .cursor_move_to(Point::new(15., 5.));
+    wnd.draw_frame();
+
+    let id = wnd.inspecting().unwrap();
+    assert_eq!(
+      wnd.inspect_info(id),
+      format!("MockBox#{}\npos: (10, 0) size: 20 x 10\nclamp: 0 x 0 - 1024 x 1024", id.0)
+    );
+    let parent = id.parent(wnd.tree()).unwrap();
+    let root = wnd.tree().root();
+    assert_eq!(
+      wnd.ancestor_chain(id),
+      format!("MockBox#{} < MockMulti#{} < Root#{}", id.0, parent.0, root.0)
+    );
+
+    wnd.toggle_inspector();
+    assert_eq!(wnd.inspecting(), None);
+  }
+}
@@ -0,0 +1,187 @@
+use std::collections::VecDeque;
+
+use crate::{prelude::*, window::WindowFlags};
+
+/// The count of the recent frames the performance HUD keeps.
+const HUD_FRAMES: usize = 120;
+/// The frame time of 60 FPS, the budget line of the graph.
+const FRAME_BUDGET: Duration = Duration::from_micros(16_667);
+const HUD_WIDTH: f32 = HUD_FRAMES as f32 * 2.;
+const GRAPH_HEIGHT: f32 = 48.;
+const HUD_PADDING: f32 = 4.;
+
+/// The time spent by the phases of a drawn frame, see
+/// [`WindowFlags::PERFORMANCE_HUD`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameTimings {
+  /// The whole time spent by the [`Window::draw_frame`].
+  pub frame: Duration,
+  /// The time spent on laying out the widgets, including the rebuilding of the
+  /// widgets and the frame tasks run between the layouts.
+  pub layout: Duration,
+  /// The time spent on painting the widgets to the paint commands.
+  pub paint: Duration,
+  /// The time the backend spent on drawing the frame, `None` if the shell
+  /// window doesn't report it.
+  pub backend: Option<BackendTimings>,
+  /// The count of the widgets marked dirty when the frame began.
+  pub dirty_widgets: usize,
+}
+
+/// The time the backend spent on drawing a frame, reported by the
+/// [`ShellWindow::backend_timings`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BackendTimings {
+  /// The time spent on converting the paint commands to the GPU primitives,
+  /// mostly on tessellating the paths.
+  pub tessellation: Duration,
+  /// The time spent on submitting the draw calls to the GPU.
+  pub submit: Duration,
+}
+
+#[derive(Default)]
+pub(crate) struct FrameTimingsRecord(VecDeque<FrameTimings>);
+
+impl FrameTimingsRecord {
+  fn push(&mut self, timings: FrameTimings) {
+    if self.0.len() == HUD_FRAMES {
+      self.0.pop_front();
+    }
+    self.0.push_back(timings);
+  }
+}
+
+impl Window {
+  /// The timings of the recent drawn frames from the oldest to the newest. They
+  /// are only recorded when the window has the
+  /// [`WindowFlags::PERFORMANCE_HUD`] flag.
+  pub fn frame_timings(&self) -> Vec<FrameTimings> {
+    self
+      .frame_timings
+      .borrow()
+      .0
+      .iter()
+      .copied()
+      .collect()
+  }
+
+  pub(crate) fn record_frame_timings(&self, timings: FrameTimings) {
+    if self
+      .flags()
+      .contains(WindowFlags::PERFORMANCE_HUD)
+    {
+      self.frame_timings.borrow_mut().push(timings);
+    } else {
+      self.frame_timings.borrow_mut().0.clear();
+    }
+  }
+
+  /// Paint the graph of the frame times and the timings of the last frame at
+  /// the top right corner of the window.
+  pub(crate) fn paint_perf_hud(&self) {
+    let record = self.frame_timings.borrow();
+    let Some(last) = record.0.back() else { return };
+
+    let ms = |d: Duration| d.as_secs_f32() * 1000.;
+    let mut info = format!(
+      "frame {:.1}ms  dirty {}\nlayout {:.1}  paint {:.1}",
+      ms(last.frame),
+      last.dirty_widgets,
+      ms(last.layout),
+      ms(last.paint)
+    );
+    if let Some(BackendTimings { tessellation, submit }) = last.backend {
+      info.push_str(&format!("  tess {:.1}  submit {:.1}", ms(tessellation), ms(submit)));
+    }
+    let glyphs = self.overlay_glyphs(info);
+    let text_rect = glyphs.visual_rect();
+
+    let hud_size = Size::new(
+      HUD_WIDTH.max(text_rect.width()) + HUD_PADDING * 2.,
+      GRAPH_HEIGHT + text_rect.height() + HUD_PADDING * 3.,
+    );
+    let hud_rect = Rect::new(Point::new(self.size().width - hud_size.width, 0.), hud_size);
+
+    let mut painter = self.painter.borrow_mut();
+    painter.save();
+    painter.set_transform(Transform::identity());
+    painter
+      .set_fill_brush(Color::BLACK.with_alpha(0.8))
+      .rect(&hud_rect)
+      .fill();
+
+    // The bars of the frame times, the full height is twice the budget.
+    let graph = Rect::new(
+      hud_rect.origin + Vector::splat(HUD_PADDING),
+      Size::new(hud_size.width - HUD_PADDING * 2., GRAPH_HEIGHT),
+    );
+    let bar_width = graph.width() / HUD_FRAMES as f32;
+    let offset = HUD_FRAMES - record.0.len();
+    for (i, timings) in record.0.iter().enumerate() {
+      let ratio = (timings.frame.as_secs_f32() / (FRAME_BUDGET.as_secs_f32() * 2.)).min(1.);
+      let height = GRAPH_HEIGHT * ratio;
+      let x = graph.min_x() + (offset + i) as f32 * bar_width;
+      let bar = Rect::new(Point::new(x, graph.max_y() - height), Size::new(bar_width, height));
+      let color = if timings.frame <= FRAME_BUDGET {
+        Color::GREEN
+      } else if timings.frame <= FRAME_BUDGET * 2 {
+        Color::YELLOW
+      } else {
+        Color::RED
+      };
+      painter.set_fill_brush(color).rect(&bar).fill();
+    }
+    let budget_y = graph.min_y() + GRAPH_HEIGHT / 2.;
+    painter
+      .set_stroke_brush(Color::WHITE.with_alpha(0.6))
+      .set_line_width(1.)
+      .begin_path(Point::new(graph.min_x(), budget_y))
+      .line_to(Point::new(graph.max_x(), budget_y))
+      .end_path(false)
+      .stroke();
+
+    painter
+      .translate(graph.min_x(), graph.max_y() + HUD_PADDING)
+      .set_fill_brush(Color::WHITE);
+    paint_text(&mut painter, &glyphs, PaintingStyle::Fill, text_rect);
+    painter.restore();
+
+    self
+      .tree()
+      .damage
+      .borrow_mut()
+      .mark_area(hud_rect);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[test]
+  fn record_timings_only_with_hud() {
+    reset_test_env!();
+
+    let (size, w_size) = split_value(Size::new(10., 10.));
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockBox { size: pipe!(*$size) }
+    });
+    wnd.draw_frame();
+    assert!(wnd.frame_timings().is_empty());
+
+    wnd.set_flags(wnd.flags() | WindowFlags::PERFORMANCE_HUD);
+    *w_size.write() = Size::new(20., 20.);
+    wnd.draw_frame();
+    let timings = wnd.frame_timings();
+    assert_eq!(timings.len(), 1);
+    assert!(timings[0].dirty_widgets > 0);
+    assert!(timings[0].frame >= timings[0].layout + timings[0].paint);
+    assert_eq!(timings[0].backend, None);
+
+    wnd.set_flags(wnd.flags() - WindowFlags::PERFORMANCE_HUD);
+    *w_size.write() = Size::new(30., 30.);
+    wnd.draw_frame();
+    assert!(wnd.frame_timings().is_empty());
+  }
+}
@@ -31,6 +31,18 @@ pub trait WrapRender {
 
   fn get_transform(&self, host: &dyn Render) -> Option<Transform> { host.get_transform() }
 
+  fn baseline_offset(&self, host: &dyn Render, ctx: &mut LayoutCtx) -> Option<f32> {
+    host.baseline_offset(ctx)
+  }
+
+  fn min_intrinsic_width(&self, host: &dyn Render, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    host.min_intrinsic_width(height, ctx)
+  }
+
+  fn min_intrinsic_height(&self, host: &dyn Render, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    host.min_intrinsic_height(width, ctx)
+  }
+
   fn combine_child(
     this: impl StateWriter<Value = Self>, mut child: Widget, dirty: DirtyPhase,
   ) -> Widget
@@ -105,7 +117,27 @@ impl Render for RenderPair {
 
   fn dirty_phase(&self) -> DirtyPhase { self.host.dirty_phase() }
 
+  fn debug_name(&self) -> &'static str { self.host.debug_name() }
+
   fn get_transform(&self) -> Option<Transform> { self.wrapper.get_transform(self.host.as_render()) }
+
+  fn baseline_offset(&self, ctx: &mut LayoutCtx) -> Option<f32> {
+    self
+      .wrapper
+      .baseline_offset(self.host.as_render(), ctx)
+  }
+
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    self
+      .wrapper
+      .min_intrinsic_width(self.host.as_render(), height, ctx)
+  }
+
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    self
+      .wrapper
+      .min_intrinsic_height(self.host.as_render(), width, ctx)
+  }
 }
 
 impl<R> WrapRender for R
@@ -130,6 +162,18 @@ where
   fn get_transform(&self, host: &dyn Render) -> Option<Transform> {
     self.read().get_transform(host)
   }
+
+  fn baseline_offset(&self, host: &dyn Render, ctx: &mut LayoutCtx) -> Option<f32> {
+    self.read().baseline_offset(host, ctx)
+  }
+
+  fn min_intrinsic_width(&self, host: &dyn Render, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    self.read().min_intrinsic_width(host, height, ctx)
+  }
+
+  fn min_intrinsic_height(&self, host: &dyn Render, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    self.read().min_intrinsic_height(host, width, ctx)
+  }
 }
 
 #[macro_export]
@@ -175,27 +175,14 @@ pub fn assert_texture_eq_png(test_img: PixelImage, ref_path: &std::path::Path) {
   ImageTest::new(test_img, ref_path).test();
 }
 
-/// Render painter by wgpu backend, and return the image.
+/// Render painter by wgpu backend, and return the image. It falls back to the
+/// software backend if no GPU adapter is found.
 pub fn wgpu_render_commands(
   commands: &[ribir_painter::PaintCommand], viewport: ribir_geom::DeviceRect,
   surface: ribir_painter::Color,
 ) -> PixelImage {
   use futures::executor::block_on;
-  use ribir_geom::{DeviceRect, DeviceSize};
-  use ribir_gpu::{GPUBackend, GPUBackendImpl, Texture, WgpuImpl};
-  use ribir_painter::PainterBackend;
-
-  let draw_img = |backend: &mut GPUBackend<WgpuImpl>| {
-    let rect = DeviceRect::from_size(DeviceSize::new(viewport.max_x() + 2, viewport.max_y() + 2));
-    let mut texture = backend
-      .get_impl_mut()
-      .new_texture(rect.size, ColorFormat::Rgba8);
-    backend.begin_frame(surface);
-    backend.draw_commands(rect, commands, &Transform::identity(), &mut texture);
-    let img = texture.copy_as_image(&rect, backend.get_impl_mut());
-    backend.end_frame();
-    block_on(img).unwrap()
-  };
+  use ribir_gpu::{GPUBackend, SoftImpl, WgpuImpl};
 
   #[cfg(not(target_family = "wasm"))]
   {
@@ -207,20 +194,51 @@ pub fn wgpu_render_commands(
     static WGPU_IMPL: Mutex<Option<WgpuImpl>> = Mutex::new(None);
 
     let mut container = WGPU_IMPL.lock().unwrap();
-    let wgpu_impl = container
-      .take()
-      .unwrap_or_else(|| block_on(ribir_gpu::WgpuImpl::headless()));
-    let mut backend = GPUBackend::new(wgpu_impl);
-    let img = draw_img(&mut backend);
-    *container = Some(backend.into_impl());
-
-    img
+    let wgpu_impl = match container.take() {
+      Some(wgpu_impl) => Ok(wgpu_impl),
+      None => block_on(WgpuImpl::headless()),
+    };
+    match wgpu_impl {
+      Ok(wgpu_impl) => {
+        let mut backend = GPUBackend::new(wgpu_impl);
+        let img = render_commands(&mut backend, commands, viewport, surface);
+        *container = Some(backend.into_impl());
+        img
+      }
+      Err(_) => {
+        let mut backend = GPUBackend::new(SoftImpl::new());
+        render_commands(&mut backend, commands, viewport, surface)
+      }
+    }
   }
 
   #[cfg(target_family = "wasm")]
-  {
-    let wgpu_impl = block_on(ribir_gpu::WgpuImpl::headless());
-    let mut backend = GPUBackend::new(wgpu_impl);
-    draw_img(&mut backend)
+  match block_on(WgpuImpl::headless()) {
+    Ok(wgpu_impl) => render_commands(&mut GPUBackend::new(wgpu_impl), commands, viewport, surface),
+    Err(_) => render_commands(&mut GPUBackend::new(SoftImpl::new()), commands, viewport, surface),
   }
 }
+
+fn render_commands<Impl>(
+  backend: &mut ribir_gpu::GPUBackend<Impl>, commands: &[ribir_painter::PaintCommand],
+  viewport: ribir_geom::DeviceRect, surface: ribir_painter::Color,
+) -> PixelImage
+where
+  Impl: ribir_gpu::GPUBackendImpl,
+  Impl::Texture: ribir_gpu::Texture<Host = Impl>,
+{
+  use futures::executor::block_on;
+  use ribir_geom::{DeviceRect, DeviceSize};
+  use ribir_gpu::Texture;
+  use ribir_painter::PainterBackend;
+
+  let rect = DeviceRect::from_size(DeviceSize::new(viewport.max_x() + 2, viewport.max_y() + 2));
+  let mut texture = backend
+    .get_impl_mut()
+    .new_texture(rect.size, ColorFormat::Rgba8);
+  backend.begin_frame(surface);
+  backend.draw_commands(rect, commands, &Transform::identity(), &mut texture);
+  let img = texture.copy_as_image(&rect, backend.get_impl_mut());
+  backend.end_frame();
+  block_on(img).unwrap()
+}
@@ -1,4 +1,6 @@
 mod image_test;
 pub use image_test::*;
+mod paint_snapshot;
+pub use paint_snapshot::*;
 mod unit_test_describe;
 mod widget_test;
@@ -0,0 +1,217 @@
+use std::fmt::Write;
+
+use ribir_geom::{Rect, Transform};
+use ribir_painter::{
+  Color, CommandBrush, PaintCommand, PaintPath, PaintPathAction, PaintingStyle, PathCommand,
+};
+
+/// This macro checks the paint commands against the snapshot file named
+/// `{name}.snap`, it is stored in the `test_cases` folder at the workspace's
+/// root, relative to the test source path.
+///
+/// The snapshot is a readable text of the commands, use
+/// [`paint_commands_snapshot`] to see what it looks like. Unlike the image
+/// test, it needs neither the GPU nor a rasterizer.
+///
+/// To run the test and overwrite the snapshot file, you can use
+/// `RIBIR_IMG_TEST=overwrite`, the same as the image test.
+#[macro_export]
+macro_rules! assert_paint_snapshot {
+  ($commands:expr, $name:expr) => {
+    let snap_path = $crate::test_case_name!($name, "snap");
+    $crate::assert_snapshot_eq(&$crate::paint_commands_snapshot(&$commands), &snap_path);
+  };
+}
+
+/// Convert the paint commands to a readable text, one command per line and
+/// the commands of a bundle are indented.
+///
+/// The numbers are rounded to two decimal places and the path is summarized
+/// by its bounds and the count of its segments, so the snapshot is stable and
+/// its diff is easy to read.
+pub fn paint_commands_snapshot(commands: &[PaintCommand]) -> String {
+  let mut snapshot = String::new();
+  write_commands(&mut snapshot, commands, 0);
+  snapshot
+}
+
+/// Compare the `snapshot` with the content of the file at `ref_path`.
+///
+/// If they are different, the actual snapshot is saved next to the file with
+/// an `_actual` suffix.
+#[track_caller]
+pub fn assert_snapshot_eq(snapshot: &str, ref_path: &std::path::Path) {
+  let overwrite = std::ffi::OsStr::new("overwrite");
+  if std::env::var_os("RIBIR_IMG_TEST").is_some_and(|var| var == overwrite) {
+    std::fs::create_dir_all(ref_path.parent().unwrap()).unwrap();
+    std::fs::write(ref_path, snapshot).unwrap();
+    return;
+  }
+
+  let expected = std::fs::read_to_string(ref_path).unwrap_or_else(|_| {
+    panic!(
+      "Snapshot {ref_path:?} not found, run the test with `RIBIR_IMG_TEST=overwrite` to create it."
+    )
+  });
+  if expected != snapshot {
+    let stem = ref_path.file_stem().unwrap().to_str().unwrap();
+    let actual_path = ref_path.with_file_name(format!("{stem}_actual.snap"));
+    std::fs::write(&actual_path, snapshot).unwrap();
+
+    let mut diff = String::new();
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = snapshot.lines();
+    loop {
+      match (expected_lines.next(), actual_lines.next()) {
+        (None, None) => break,
+        (e, a) if e == a => {}
+        (e, a) => {
+          if let Some(e) = e {
+            let _ = writeln!(diff, "- {e}");
+          }
+          if let Some(a) = a {
+            let _ = writeln!(diff, "+ {a}");
+          }
+        }
+      }
+    }
+    panic!(
+      "Snapshot test failed, the actual snapshot has been saved next to the expected one.
+      Expected snapshot location: {ref_path:?}
+      Actual snapshot location: {actual_path:?}
+{diff}"
+    );
+  }
+}
+
+fn write_commands(out: &mut String, commands: &[PaintCommand], depth: usize) {
+  for cmd in commands {
+    let _ = write!(out, "{:indent$}", "", indent = depth * 2);
+    match cmd {
+      PaintCommand::Path(PathCommand { path, transform, action, .. }) => {
+        match action {
+          PaintPathAction::Paint { brush, painting_style } => {
+            match painting_style {
+              PaintingStyle::Fill => out.push_str("fill"),
+              PaintingStyle::Stroke(opts) => {
+                let _ = write!(out, "stroke({})", num(opts.width));
+              }
+            }
+            let _ = write!(out, " {}", brush_text(brush));
+          }
+          PaintPathAction::Clip => out.push_str("clip"),
+        }
+        let _ = write!(out, " {}{}", path_text(path), transform_text(transform));
+      }
+      PaintCommand::PopClip => out.push_str("pop clip"),
+      PaintCommand::Bundle { transform, opacity, bounds, cmds } => {
+        let _ = write!(
+          out,
+          "bundle opacity({}) bounds({}){}",
+          num(*opacity),
+          rect_text(bounds),
+          transform_text(transform)
+        );
+        out.push('\n');
+        write_commands(out, cmds, depth + 1);
+        continue;
+      }
+      PaintCommand::Blur { transform, opacity, bounds, radius, cmds } => {
+        let _ = write!(
+          out,
+          "blur({}) opacity({}) bounds({}){}",
+          num(*radius),
+          num(*opacity),
+          rect_text(bounds),
+          transform_text(transform)
+        );
+        out.push('\n');
+        write_commands(out, cmds, depth + 1);
+        continue;
+      }
+      PaintCommand::BackdropBlur { transform, opacity, bounds, radius } => {
+        let _ = write!(
+          out,
+          "backdrop blur({}) opacity({}) bounds({}){}",
+          num(*radius),
+          num(*opacity),
+          rect_text(bounds),
+          transform_text(transform)
+        );
+      }
+      PaintCommand::Shader { transform, opacity, bounds, source, uniforms } => {
+        let _ = write!(
+          out,
+          "shader({} chars, {} bytes) opacity({}) bounds({}){}",
+          source.len(),
+          uniforms.len(),
+          num(*opacity),
+          rect_text(bounds),
+          transform_text(transform)
+        );
+      }
+      PaintCommand::ExternalTexture { transform, opacity, bounds, texture } => {
+        let size = texture.size();
+        let _ = write!(
+          out,
+          "external texture({}x{}) opacity({}) bounds({}){}",
+          size.width,
+          size.height,
+          num(*opacity),
+          rect_text(bounds),
+          transform_text(transform)
+        );
+      }
+    }
+    out.push('\n');
+  }
+}
+
+fn brush_text(brush: &CommandBrush) -> String {
+  match brush {
+    CommandBrush::Color(c) => color_text(c),
+    CommandBrush::Image { img, opacity } => {
+      format!("image({}x{}, opacity {})", img.width(), img.height(), num(*opacity))
+    }
+    CommandBrush::Radial(r) => format!("radial({} stops)", r.stops.len()),
+    CommandBrush::Linear(l) => format!("linear({} stops)", l.stops.len()),
+  }
+}
+
+fn color_text(c: &Color) -> String {
+  format!("#{:02x}{:02x}{:02x}{:02x}", c.red, c.green, c.blue, c.alpha)
+}
+
+fn path_text(path: &PaintPath) -> String {
+  format!("path({}, {} segments)", rect_text(&path.bounds(None)), path.segments().count())
+}
+
+fn transform_text(t: &Transform) -> String {
+  if t.m11 == 1. && t.m12 == 0. && t.m21 == 0. && t.m22 == 1. {
+    if t.m31 == 0. && t.m32 == 0. {
+      String::new()
+    } else {
+      format!(" translate({}, {})", num(t.m31), num(t.m32))
+    }
+  } else {
+    let [m11, m12, m21, m22, m31, m32] = t.to_array().map(num);
+    format!(" matrix({m11}, {m12}, {m21}, {m22}, {m31}, {m32})")
+  }
+}
+
+fn rect_text(rect: &Rect) -> String {
+  format!(
+    "{}, {}, {}x{}",
+    num(rect.origin.x),
+    num(rect.origin.y),
+    num(rect.size.width),
+    num(rect.size.height)
+  )
+}
+
+fn num(v: f32) -> String {
+  let v = (v * 100.).round() / 100.;
+  // Avoid the negative zero.
+  let v = if v == 0. { 0. } else { v };
+  format!("{v}")
+}
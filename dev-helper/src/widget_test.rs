@@ -81,7 +81,7 @@ macro_rules! assert_widget_eq_image {
 
     let mut wnd = $widget_tester.create_wnd();
 
-    let Frame { commands, viewport, surface } = wnd.take_last_frame().unwrap();
+    let Frame { commands, viewport, surface, .. } = wnd.take_last_frame().unwrap();
     let viewport = viewport.to_i32().cast_unit();
     let img = $crate::wgpu_render_commands(&commands, viewport, surface);
 
@@ -92,3 +92,37 @@ macro_rules! assert_widget_eq_image {
     img_test.test();
   };
 }
+
+/// Capture the current frame of a `TestWindow` and check if it renders the
+/// same as the golden image `{name}.png` with the wgpu backend. The image file
+/// is in the `test_cases` folder at the workspace's root, relative to the test
+/// source path. The default tolerance of the difference can be changed by
+/// `comparison = ...`.
+///
+/// It works with [`TestWindow::simulate_inputs`] to check the UI after a
+/// sequence of the user inputs, for example:
+///
+/// ```ignore
+/// let mut wnd = TestWindow::new_with_size(counter, Size::new(320., 240.));
+/// wnd.simulate_inputs([
+///   (Duration::ZERO, TestInput::CursorMove(Point::new(160., 120.))),
+///   (Duration::ZERO, TestInput::MousePress(MouseButton::Left)),
+///   (Duration::from_millis(50), TestInput::MouseRelease(MouseButton::Left)),
+/// ]);
+/// assert_frame_eq_image!(wnd, "counter_tapped", comparison = 0.001);
+/// ```
+///
+/// Run the test with `RIBIR_IMG_TEST=overwrite` to overwrite the image file.
+#[cfg(not(target_arch = "wasm32"))]
+#[macro_export]
+macro_rules! assert_frame_eq_image {
+  ($wnd:expr, $name:expr $(, comparison = $comparison:expr)? $(,)?) => {{
+    let img_path = $crate::test_case_name!($name, "png");
+    let frame = $wnd.draw_and_take_frame();
+    let viewport = frame.viewport.to_i32().cast_unit();
+    let img = $crate::wgpu_render_commands(&frame.commands, viewport, frame.surface);
+    $crate::ImageTest::new(img, &img_path)
+      $(.with_comparison($comparison))?
+      .test();
+  }};
+}
@@ -35,4 +35,25 @@ mod tests {
       .with_wnd_size(Size::new(320., 240.))
       .with_comparison(0.001)
   );
+
+  #[test]
+  fn tap_counter() {
+    let _scope = AppCtx::new_lock_scope();
+    AppCtx::set_app_theme(ribir_material::purple::light());
+
+    let mut wnd = TestWindow::new_with_size(counter, Size::new(320., 240.));
+    let center = Point::new(160., 120.);
+    let ms = Duration::from_millis;
+    wnd.simulate_inputs([
+      (ms(0), TestInput::CursorMove(center)),
+      (ms(0), TestInput::MousePress(MouseButton::Left)),
+      (ms(50), TestInput::MouseRelease(MouseButton::Left)),
+      (ms(200), TestInput::MousePress(MouseButton::Left)),
+      (ms(250), TestInput::MouseRelease(MouseButton::Left)),
+      (ms(300), TestInput::CursorLeave),
+    ]);
+    // Wait for the ripple to finish.
+    wnd.tick(Duration::from_secs(2));
+    assert_frame_eq_image!(wnd, "tap_counter", comparison = 0.001);
+  }
 }
@@ -7,3 +7,31 @@ pub enum Error {
   /// The image is too large to good for the atlas store.
   LargeImageAvoid,
 }
+
+/// The errors that may occur when initializing the GPU renderer.
+#[derive(Debug)]
+pub enum RenderInitError {
+  /// No GPU adapter is found, not even a software fallback adapter.
+  NoAdapter,
+  /// The surface can't be created or its formats are not supported.
+  SurfaceIncompatible(String),
+  /// The device can't be created with the limits or the features that the
+  /// renderer requires.
+  DeviceLimitsTooLow(String),
+}
+
+impl std::fmt::Display for RenderInitError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      RenderInitError::NoAdapter => write!(f, "no suitable GPU adapter found on the system"),
+      RenderInitError::SurfaceIncompatible(reason) => {
+        write!(f, "the surface is incompatible: {reason}")
+      }
+      RenderInitError::DeviceLimitsTooLow(reason) => {
+        write!(f, "the device limits are too low: {reason}")
+      }
+    }
+  }
+}
+
+impl std::error::Error for RenderInitError {}
@@ -1,18 +1,23 @@
 use std::error::Error;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
 
 use guillotiere::euclid::Vector2D;
 use ribir_geom::{
-  DeviceRect, DeviceSize, Point, Transform, rect_corners, transform_to_device_rect,
+  DeviceRect, DeviceSize, Point, Rect, Transform, rect_corners, transform_to_device_rect,
 };
 use ribir_painter::{
-  Color, CommandBrush, PaintCommand, PaintPath, PaintPathAction, PainterBackend, PaintingStyle,
-  PathCommand, PixelImage, Vertex, VertexBuffers, image::ColorFormat,
+  Color, CommandBrush, LineJoin, PaintCommand, PaintPath, PaintPathAction, PainterBackend,
+  PaintingStyle, Path, PathCommand, PixelImage, RoundRect, StrokeOptions, Vertex, VertexBuffers,
+  blur_spread, image::ColorFormat,
 };
+#[cfg(target_arch = "wasm32")]
+use web_time::{Duration, Instant};
 
 use crate::{
   ColorAttr, GPUBackendImpl, GradientStopPrimitive, ImagePrimIndex, ImgPrimitive,
   LinearGradientPrimIndex, LinearGradientPrimitive, MaskLayer, RadialGradientPrimIndex,
-  RadialGradientPrimitive,
+  RadialGradientPrimitive, RoundRectPrimIndex, RoundRectPrimitive,
 };
 
 mod atlas;
@@ -32,13 +37,37 @@ pub struct GPUBackend<Impl: GPUBackendImpl> {
   linear_gradient_prims: Vec<LinearGradientPrimitive>,
   linear_gradient_stops: Vec<GradientStopPrimitive>,
   linear_gradient_vertices_buffer: VertexBuffers<LinearGradientPrimIndex>,
+  round_rect_prims: Vec<RoundRectPrimitive>,
+  round_rect_vertices_buffer: VertexBuffers<RoundRectPrimIndex>,
   current_phase: CurrentPhase,
   tex_ids_map: TextureIdxMap,
   viewport: DeviceRect,
   mask_layers: Vec<MaskLayer>,
   clip_layer_stack: Vec<ClipLayer>,
   skip_clip_cnt: usize,
+  /// The depth of the offscreen slices in drawing, the backdrop isn't
+  /// available in them.
+  offscreen_depth: usize,
   surface_color: Option<Color>,
+  /// The timings of the frame in drawing.
+  drawing_timings: DrawTimings,
+  last_timings: DrawTimings,
+  /// The count of the draw calls submitted in the current frame, the
+  /// consecutive commands of the same brush kind share one draw call.
+  draw_calls: usize,
+}
+
+/// The time the backend spent on drawing a frame on the CPU side.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct DrawTimings {
+  /// The time spent on converting the paint commands to the GPU primitives,
+  /// mostly on tessellating the paths and caching their masks. The rounded
+  /// rectangles filled or bordered by a color skip the tessellation, they're
+  /// drawn by their signed distance fields.
+  pub tessellation: Duration,
+  /// The time spent on uploading the primitives and submitting the draw calls
+  /// to the GPU.
+  pub submit: Duration,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -48,6 +77,7 @@ enum CurrentPhase {
   Img,
   RadialGradient,
   LinearGradient,
+  RoundRect,
 }
 
 struct ClipLayer {
@@ -91,6 +121,8 @@ where
   type Texture = Impl::Texture;
 
   fn begin_frame(&mut self, surface: Color) {
+    self.drawing_timings = DrawTimings::default();
+    self.draw_calls = 0;
     self.surface_color = Some(surface);
     self.gpu_impl.begin_frame();
   }
@@ -103,19 +135,55 @@ where
     self.viewport = viewport;
     self.begin_draw_phase();
     let output_size = output.size();
+    // Only the viewport is redrawn, so only clear it with the surface color and
+    // keep the rest of the output.
+    let partial_surface = self
+      .surface_color
+      .filter(|_| !viewport.contains_rect(&DeviceRect::from_size(output_size)));
+    if let Some(surface) = partial_surface {
+      self.surface_color = None;
+      if surface.alpha < u8::MAX {
+        // The fill blends with the old content, a translucent surface needs the
+        // viewport to be cleared first.
+        output.clear_areas(&[viewport], &mut self.gpu_impl);
+      }
+      let rect = viewport.to_f32().cast_unit();
+      let clear = PaintCommand::Path(PathCommand {
+        path: PaintPath::Own(Path::rect(&rect)),
+        paint_bounds: rect,
+        transform: Transform::identity(),
+        action: PaintPathAction::Paint {
+          brush: CommandBrush::Color(surface),
+          painting_style: PaintingStyle::Fill,
+        },
+      });
+      self.draw_command(&clear, &Transform::identity(), output_size, output);
+    }
+    let start = Instant::now();
     for cmd in commands {
       self.draw_command(cmd, global_matrix, output_size, output);
     }
+    self.drawing_timings.tessellation += start.elapsed();
+
+    let start = Instant::now();
     self.draw_triangles(output);
     self.end_draw_phase();
+    self.drawing_timings.submit += start.elapsed();
+    if partial_surface.is_some() {
+      // Every viewport of this frame needs to be cleared.
+      self.surface_color = partial_surface;
+    }
 
     assert_eq!(self.clip_layer_stack.len(), clips);
   }
 
   fn end_frame(&mut self) {
+    let start = Instant::now();
     self.mask_layers.clear();
     self.tex_mgr.end_frame();
     self.gpu_impl.end_frame();
+    self.drawing_timings.submit += start.elapsed();
+    self.last_timings = std::mem::take(&mut self.drawing_timings);
   }
 }
 
@@ -132,6 +200,7 @@ where
       mask_layers: vec![],
       clip_layer_stack: vec![],
       skip_clip_cnt: 0,
+      offscreen_depth: 0,
       color_vertices_buffer: VertexBuffers::with_capacity(256, 512),
       img_vertices_buffer: VertexBuffers::with_capacity(256, 512),
       radial_gradient_vertices_buffer: VertexBuffers::with_capacity(256, 512),
@@ -140,13 +209,35 @@ where
       linear_gradient_vertices_buffer: VertexBuffers::with_capacity(256, 512),
       linear_gradient_stops: vec![],
       linear_gradient_prims: vec![],
+      round_rect_prims: vec![],
+      round_rect_vertices_buffer: VertexBuffers::with_capacity(256, 512),
       img_prims: vec![],
       current_phase: CurrentPhase::None,
       viewport: DeviceRect::zero(),
       surface_color: Some(Color::WHITE),
+      drawing_timings: DrawTimings::default(),
+      last_timings: DrawTimings::default(),
+      draw_calls: 0,
     }
   }
 
+  /// The timings of the last frame finished drawing.
+  pub fn last_frame_timings(&self) -> DrawTimings { self.last_timings }
+
+  /// Limit the size the alpha atlas can grow to. The atlas caches the masks of
+  /// the paths like the glyphs, and evicts the least recently used masks when
+  /// it's full. By default, it can grow to the texture size limit of the GPU.
+  pub fn set_alpha_atlas_max_size(&mut self, size: DeviceSize) {
+    self
+      .tex_mgr
+      .set_alpha_atlas_max_size(size, &self.gpu_impl);
+  }
+
+  /// Release the caches rendered at the old device scale, call it after the
+  /// scale factor of the window changed, so the glyphs and the paths are not
+  /// drawn from the masks rendered at the old scale and look blurry.
+  pub fn clear_scaled_caches(&mut self) { self.tex_mgr.clear_scaled_caches(); }
+
   #[inline]
   pub fn get_impl(&self) -> &Impl { &self.gpu_impl }
 
@@ -179,11 +270,22 @@ where
           return;
         };
 
+        let matrix = transform.then(global_matrix);
+        if let PaintPathAction::Paint { brush: CommandBrush::Color(color), painting_style } = action
+        {
+          if let Some(prim) = round_rect_primitive(path, painting_style, &matrix, *color) {
+            if !self.can_batch_round_rect() {
+              self.new_draw_phase(output);
+            }
+            self.draw_round_rect(prim, &viewport, output_tex_size);
+            return;
+          }
+        }
+
         if !self.can_batch_path_command(cmd) {
           self.new_draw_phase(output);
         }
 
-        let matrix = transform.then(global_matrix);
         let (rect, mask_head) = match action {
           PaintPathAction::Clip => {
             self.new_mask_layer(&viewport, &matrix, path, &PaintingStyle::Fill)
@@ -272,7 +374,7 @@ where
         let this = self as *mut Self;
         let (cache_scale, slice) = self.tex_mgr.store_commands(
           cache_size.to_i32().cast_unit(),
-          cmds.clone().into_any(),
+          cmds.clone().into_any_send(),
           scale,
           &mut self.gpu_impl,
           |slice, tex, _| {
@@ -281,56 +383,191 @@ where
             // within the frame, and no modifications will be made to the slice
             // that has already been allocated.
             let this = unsafe { &mut *this };
-
-            // Initiate a new drawing phase to ensure a clean state for rendering in a new
-            // texture.
-            this.new_draw_phase(output);
-
-            // store the viewport
-            let viewport = self.viewport;
-            // Overwrite the viewport to the slice bounds.
-            self
-              .clip_layer_stack
-              .push(ClipLayer { viewport, mask_head: -1 });
-
             let matrix = Transform::translation(bounds.origin.x, bounds.origin.y)
               .then_scale(scale, scale)
               .then_translate(slice.origin.to_f32().cast_unit().to_vector());
-            this.draw_commands(*slice, cmds, &matrix, tex);
-
-            // restore the clip layer and viewport
-            self.clip_layer_stack.pop();
-            this.viewport = viewport;
-            this.begin_draw_phase();
+            this.draw_commands_to_slice(slice, cmds, &matrix, tex, output);
           },
         );
 
-        let mut points: [_; 4] = rect_corners(&bounds.to_f32().cast_unit());
+        self.draw_cached_slice(
+          slice,
+          bounds,
+          cache_scale,
+          &matrix,
+          *opacity,
+          output_tex_size,
+          output,
+        );
+      }
+      PaintCommand::Blur { transform, opacity, bounds, radius, cmds } => {
+        let matrix = transform.then(global_matrix);
+        let spread = blur_spread(*radius);
+        let bounds = bounds.inflate(spread, spread);
+        let scale = self.tex_mgr.cache_scale(&bounds.size, &matrix);
+        let size = (bounds.size * scale).ceil().to_i32().cast_unit();
+
+        // The blurred result is not cached, the commands are drawn to a new slice
+        // and blurred every frame.
+        let slice = self
+          .tex_mgr
+          .store_frame_target(size, &mut self.gpu_impl);
+        let this = self as *mut Self;
+        let tex = self.tex_mgr.texture_mut(slice.tex_id);
+        tex.clear_areas(&[slice.rect], &mut self.gpu_impl);
+        let slice_matrix = Transform::translation(-bounds.origin.x, -bounds.origin.y)
+          .then_scale(scale, scale)
+          .then_translate(slice.rect.origin.to_f32().cast_unit().to_vector());
+        // SAFETY: The same as the bundle, the texture always exists within the frame.
+        unsafe { &mut *this }.draw_commands_to_slice(&slice.rect, cmds, &slice_matrix, tex, output);
+        let tex = self.tex_mgr.texture_mut(slice.tex_id);
+        self
+          .gpu_impl
+          .blur_texture(tex, &slice.rect, radius * scale);
+
+        self.draw_cached_slice(slice, &bounds, scale, &matrix, *opacity, output_tex_size, output);
+      }
+      PaintCommand::Shader { transform, opacity, bounds, source, uniforms } => {
+        let matrix = transform.then(global_matrix);
+        let scale = self.tex_mgr.cache_scale(&bounds.size, &matrix);
+        let size = (bounds.size * scale).ceil().to_i32().cast_unit();
+
+        // The shader may animate by its uniforms, so it's painted every frame.
+        let slice = self
+          .tex_mgr
+          .store_frame_target(size, &mut self.gpu_impl);
+        let tex = self.tex_mgr.texture_mut(slice.tex_id);
+        self
+          .gpu_impl
+          .draw_shader(tex, &slice.rect, source, uniforms);
+
+        self.draw_cached_slice(slice, bounds, scale, &matrix, *opacity, output_tex_size, output);
+      }
+      PaintCommand::ExternalTexture { transform, opacity, bounds, texture } => {
+        if bounds.is_empty() {
+          return;
+        }
+        // The frame is drawn from its own texture, not copied to the atlas.
+        let Some(slice) = self
+          .tex_mgr
+          .store_external(texture, &mut self.gpu_impl)
+        else {
+          return;
+        };
+        let matrix = transform.then(global_matrix);
+        let mut points: [_; 4] = rect_corners(bounds);
         for p in points.iter_mut() {
           *p = matrix.transform_point(*p);
         }
-
+        // Scale the frame to fill the bounds.
+        let frame_size = slice.rect.size.to_f32();
         let view_to_slice = matrix
-          // point back to the bundle commands axis.
           .inverse()
           .unwrap()
-          // align to the zero point, draw image slice is start from zero.
           .then_translate(Vector2D::new(-bounds.origin.x, -bounds.origin.y))
-          // scale to the cache size.
-          .then_scale(cache_scale, cache_scale);
+          .then_scale(frame_size.width / bounds.width(), frame_size.height / bounds.height());
 
         if !self.can_batch_img_path() {
           self.new_draw_phase(output);
         }
-        let mask_head = self
-          .clip_layer_stack
-          .last()
-          .map_or(-1, |l| l.mask_head);
+        let mask_head = self.current_clip_mask_index();
+        self.draw_img_slice(slice, &view_to_slice, mask_head, *opacity, output_tex_size, points);
+      }
+      PaintCommand::BackdropBlur { transform, opacity, bounds, radius } => {
+        if self.skip_clip_cnt > 0 || self.offscreen_depth > 0 {
+          return;
+        }
+        let matrix = transform.then(global_matrix);
+        let device_bounds = transform_to_device_rect(bounds, &matrix);
+        let Some(rect) = self.viewport().intersection(&device_bounds) else {
+          return;
+        };
+        let Transform { m11, m12, m21, m22, .. } = matrix;
+        let sigma = radius * (m11.abs() + m12.abs()).max(m21.abs() + m22.abs());
+        let spread = blur_spread(sigma).ceil() as i32;
+        let Some(src) = rect
+          .inflate(spread, spread)
+          .intersection(&DeviceRect::from_size(output_tex_size))
+        else {
+          return;
+        };
+
+        // Draw the commands before, so the backdrop is ready in the output.
+        self.new_draw_phase(output);
+        let slice = self
+          .tex_mgr
+          .store_frame_target(src.size, &mut self.gpu_impl);
+        let tex = self.tex_mgr.texture_mut(slice.tex_id);
+        self
+          .gpu_impl
+          .copy_texture_from_texture(tex, slice.rect.origin, output, &src);
+        self
+          .gpu_impl
+          .blur_texture(tex, &slice.rect, sigma);
+
+        let src_origin = src.origin.to_f32();
+        let view_to_slice = Transform::translation(-src_origin.x, -src_origin.y);
+        let points = rect_corners(&rect.to_f32().cast_unit());
+        let mask_head = self.current_clip_mask_index();
         self.draw_img_slice(slice, &view_to_slice, mask_head, *opacity, output_tex_size, points);
       }
     }
   }
 
+  /// Draw the `cmds` to the `slice` of the `tex` in the texture manager.
+  fn draw_commands_to_slice(
+    &mut self, slice: &DeviceRect, cmds: &[PaintCommand], matrix: &Transform,
+    tex: &mut Impl::Texture, output: &mut Impl::Texture,
+  ) {
+    // Initiate a new drawing phase to ensure a clean state for rendering in a new
+    // texture.
+    self.new_draw_phase(output);
+
+    // store the viewport
+    let viewport = self.viewport;
+    // Overwrite the viewport to the slice bounds.
+    self
+      .clip_layer_stack
+      .push(ClipLayer { viewport, mask_head: -1 });
+
+    self.offscreen_depth += 1;
+    self.draw_commands(*slice, cmds, matrix, tex);
+    self.offscreen_depth -= 1;
+
+    // restore the clip layer and viewport
+    self.clip_layer_stack.pop();
+    self.viewport = viewport;
+    self.begin_draw_phase();
+  }
+
+  /// Draw the `slice` that caches the content of the `bounds` scaled by the
+  /// `cache_scale` to the output.
+  #[allow(clippy::too_many_arguments)]
+  fn draw_cached_slice(
+    &mut self, slice: TextureSlice, bounds: &Rect, cache_scale: f32, matrix: &Transform,
+    opacity: f32, output_tex_size: DeviceSize, output: &mut Impl::Texture,
+  ) {
+    let mut points: [_; 4] = rect_corners(bounds);
+    for p in points.iter_mut() {
+      *p = matrix.transform_point(*p);
+    }
+
+    let view_to_slice = matrix
+      // point back to the bundle commands axis.
+      .inverse()
+      .unwrap()
+      // align to the zero point, draw image slice is start from zero.
+      .then_translate(Vector2D::new(-bounds.origin.x, -bounds.origin.y))
+      // scale to the cache size.
+      .then_scale(cache_scale, cache_scale);
+
+    if !self.can_batch_img_path() {
+      self.new_draw_phase(output);
+    }
+    let mask_head = self.current_clip_mask_index();
+    self.draw_img_slice(slice, &view_to_slice, mask_head, opacity, output_tex_size, points);
+  }
+
   fn can_batch_img_path(&self) -> bool {
     let limits = self.gpu_impl.limits();
     self.current_phase == CurrentPhase::None
@@ -405,6 +642,9 @@ where
       .indices
       .clear();
     self.linear_gradient_stops.clear();
+    self.round_rect_prims.clear();
+    self.round_rect_vertices_buffer.vertices.clear();
+    self.round_rect_vertices_buffer.indices.clear();
   }
 
   fn draw_img_slice(
@@ -428,6 +668,27 @@ where
     self.current_phase = CurrentPhase::Img;
   }
 
+  fn can_batch_round_rect(&self) -> bool {
+    let limits = self.gpu_impl.limits();
+    self.current_phase == CurrentPhase::None
+      || (self.current_phase == CurrentPhase::RoundRect
+        && self.round_rect_prims.len() < limits.max_round_rect_primitives)
+  }
+
+  /// Draw the rounded rectangle in the `view` of the output, it's clipped by
+  /// the current clip layer.
+  fn draw_round_rect(
+    &mut self, mut prim: RoundRectPrimitive, view: &DeviceRect, output_tex_size: DeviceSize,
+  ) {
+    prim.mask_head = self.current_clip_mask_index();
+    let prim_idx = self.round_rect_prims.len() as u32;
+    self.round_rect_prims.push(prim);
+    let rect = rect_corners(&view.to_f32().cast_unit());
+    let buffer = &mut self.round_rect_vertices_buffer;
+    add_rect_vertices(rect, output_tex_size, RoundRectPrimIndex(prim_idx), buffer);
+    self.current_phase = CurrentPhase::RoundRect;
+  }
+
   fn can_batch_path_command(&self, cmd: &PathCommand) -> bool {
     let limits = self.gpu_impl.limits();
     let tex_used = self.tex_ids_map.len();
@@ -520,18 +781,21 @@ where
     match self.current_phase {
       CurrentPhase::None => {
         if color.is_some() {
+          self.draw_calls += 1;
           gpu_impl.draw_color_triangles(output, 0..0, color.take())
         }
       }
       CurrentPhase::Color if !self.color_vertices_buffer.indices.is_empty() => {
         gpu_impl.load_color_vertices(&self.color_vertices_buffer);
         let rg = 0..self.color_vertices_buffer.indices.len() as u32;
+        self.draw_calls += 1;
         gpu_impl.draw_color_triangles(output, rg, color.take())
       }
       CurrentPhase::Img if !self.img_vertices_buffer.indices.is_empty() => {
         gpu_impl.load_img_primitives(&self.img_prims);
         gpu_impl.load_img_vertices(&self.img_vertices_buffer);
         let rg = 0..self.img_vertices_buffer.indices.len() as u32;
+        self.draw_calls += 1;
         gpu_impl.draw_img_triangles(output, rg, color.take())
       }
       CurrentPhase::RadialGradient
@@ -544,6 +808,7 @@ where
         gpu_impl.load_radial_gradient_stops(&self.radial_gradient_stops);
         gpu_impl.load_radial_gradient_vertices(&self.radial_gradient_vertices_buffer);
         let rg = 0..self.radial_gradient_vertices_buffer.indices.len() as u32;
+        self.draw_calls += 1;
         gpu_impl.draw_radial_gradient_triangles(output, rg, color.take())
       }
       CurrentPhase::LinearGradient
@@ -556,8 +821,16 @@ where
         gpu_impl.load_linear_gradient_stops(&self.linear_gradient_stops);
         gpu_impl.load_linear_gradient_vertices(&self.linear_gradient_vertices_buffer);
         let rg = 0..self.linear_gradient_vertices_buffer.indices.len() as u32;
+        self.draw_calls += 1;
         gpu_impl.draw_linear_gradient_triangles(output, rg, color.take())
       }
+      CurrentPhase::RoundRect if !self.round_rect_vertices_buffer.indices.is_empty() => {
+        gpu_impl.load_round_rect_primitives(&self.round_rect_prims);
+        gpu_impl.load_round_rect_vertices(&self.round_rect_vertices_buffer);
+        let rg = 0..self.round_rect_vertices_buffer.indices.len() as u32;
+        self.draw_calls += 1;
+        gpu_impl.draw_round_rect_triangles(output, rg, color.take())
+      }
       _ => {}
     }
   }
@@ -582,6 +855,50 @@ impl TextureIdxMap {
   fn len(&self) -> usize { self.textures.len() }
 }
 
+/// The primitive to draw the `path` by its signed distance field, if it's a
+/// rounded rectangle that keeps its shape in the texture.
+fn round_rect_primitive(
+  path: &Path, style: &PaintingStyle, matrix: &Transform, color: Color,
+) -> Option<RoundRectPrimitive> {
+  let RoundRect { rect, radius } = *path.round_rect()?;
+  // Only the translation and the uniform scale keep the corners circular.
+  let Transform { m11, m12, m21, m22, .. } = *matrix;
+  if m12 != 0. || m21 != 0. || m11 <= 0. || m11 != m22 || rect.is_empty() {
+    return None;
+  }
+
+  // The radius is limited by the size like the tessellated path.
+  let max = rect.width().min(rect.height()) / 2.;
+  let radius = [radius.top_left, radius.top_right, radius.bottom_right, radius.bottom_left]
+    .map(|r| r.clamp(0., max));
+  let half = match style {
+    PaintingStyle::Fill => 0.,
+    PaintingStyle::Stroke(StrokeOptions { width, line_join, miter_limit, .. }) => {
+      // A sharp corner of the stroke is only kept by the miter join.
+      let miter = matches!(line_join, LineJoin::Miter | LineJoin::MiterClip)
+        && *miter_limit >= std::f32::consts::SQRT_2;
+      if *width <= 0. || (!miter && radius.contains(&0.)) {
+        return None;
+      }
+      width / 2.
+    }
+  };
+
+  // The stroke is centered on the path, its outer bounds grow half of the width.
+  let rect = rect.inflate(half, half);
+  let min = matrix.transform_point(rect.min());
+  let max = matrix.transform_point(rect.max());
+  let radius = radius.map(|r| if r > 0. { (r + half) * m11 } else { 0. });
+  Some(RoundRectPrimitive {
+    rect: [min.x, min.y, max.x, max.y],
+    radius,
+    color: color.into_u32(),
+    border_width: half * 2. * m11,
+    mask_head: -1,
+    _dummy: 0,
+  })
+}
+
 pub fn vertices_coord(pos: Point, tex_size: DeviceSize) -> [f32; 2] {
   [pos.x / tex_size.width as f32, pos.y / tex_size.height as f32]
 }
@@ -813,4 +1130,55 @@ mod tests {
     painter
   }
   painter_backend_eq_image_test!(draw_bundle_svg, comparison = 0.001);
+
+  fn count_draw_calls(mut painter: Painter) -> usize {
+    let mut backend = GPUBackend::new(crate::SoftImpl::new());
+    let rect = DeviceRect::from_size(DeviceSize::new(200, 200));
+    let mut texture = backend
+      .get_impl_mut()
+      .new_texture(rect.size, ColorFormat::Rgba8);
+    backend.begin_frame(Color::TRANSPARENT);
+    let commands = painter.finish().to_vec();
+    backend.draw_commands(rect, &commands, &Transform::identity(), &mut texture);
+    let draw_calls = backend.draw_calls;
+    backend.end_frame();
+    draw_calls
+  }
+
+  #[test]
+  fn batch_draw_calls_by_brush() {
+    let leaves = Resource::new(PixelImage::from_png(include_bytes!("../imgs/leaves.png")));
+    let other =
+      Resource::new(PixelImage::from_png(include_bytes!("../../examples/attachments/3DDD-1.png")));
+    let triangle = |painter: &mut Painter, i: usize| {
+      let x = (i % 10) as f32 * 20.;
+      let y = (i / 10) as f32 * 20.;
+      painter
+        .begin_path(Point::new(x, y))
+        .line_to(Point::new(x + 20., y))
+        .line_to(Point::new(x, y + 20.))
+        .end_path(true);
+    };
+
+    // The images of different textures are drawn by one call.
+    let mut images = painter(Size::new(200., 200.));
+    for i in 0..20 {
+      triangle(&mut images, i);
+      let img = if i % 2 == 0 { leaves.clone() } else { other.clone() };
+      images.set_fill_brush(img).fill();
+    }
+    assert_eq!(count_draw_calls(images), 1);
+
+    // The consecutive commands of the same brush kind share a draw call.
+    let mut mixed = painter(Size::new(200., 200.));
+    for i in 0..20 {
+      triangle(&mut mixed, i);
+      if i < 10 {
+        mixed.set_fill_brush(Color::RED).fill();
+      } else {
+        mixed.set_fill_brush(leaves.clone()).fill();
+      }
+    }
+    assert_eq!(count_draw_calls(mixed), 2);
+  }
 }
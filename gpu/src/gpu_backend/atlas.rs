@@ -36,6 +36,9 @@ pub(crate) struct Atlas<K, T: Texture> {
   extras: Slab<T>,
   /// All allocations in the current frame and not cached.
   islands: ahash::HashSet<AtlasDist>,
+  /// The areas of the cached allocations evicted in the current frame to make
+  /// room for new allocations.
+  evicted: Vec<DeviceRect>,
 }
 
 impl<K, T: Texture> Atlas<K, T>
@@ -53,6 +56,7 @@ where
       cache: FrameCache::new(),
       extras: Slab::default(),
       islands: <_>::default(),
+      evicted: vec![],
     }
   }
 
@@ -131,6 +135,10 @@ where
       }
     }
 
+    if alloc.is_none() && !size.greater_than(self.config.max_size).any() {
+      alloc = self.evict_to_allocate(size);
+    }
+
     let dist = if let Some(alloc) = alloc {
       AtlasDist::Atlas(alloc)
     } else {
@@ -143,6 +151,33 @@ where
     dist
   }
 
+  /// Evict the cached allocations not used in the current frame until there
+  /// is enough space to allocate `size`.
+  fn evict_to_allocate(&mut self, size: DeviceSize) -> Option<Allocation> {
+    while let Some((_, h)) = self.cache.pop_unused() {
+      match h.dist {
+        AtlasDist::Atlas(alloc) => {
+          self
+            .evicted
+            .push(alloc.rectangle.to_rect().cast_unit());
+          self.atlas_allocator.deallocate(alloc.id);
+          let alloc = self.atlas_allocator.allocate(size.cast_unit());
+          if alloc.is_some() {
+            return alloc;
+          }
+        }
+        AtlasDist::Extra(id) => {
+          self.extras.remove(id);
+        }
+      }
+    }
+    None
+  }
+
+  /// Take the areas evicted in the current frame, the content of them is
+  /// stale and may need to be cleared before reuse.
+  pub fn take_evicted(&mut self) -> Vec<DeviceRect> { std::mem::take(&mut self.evicted) }
+
   /// Get a mut reference of a texture that `id` point to. The `id` get from
   /// `AtlasHandle::tex_id`
   pub fn get_texture_mut(&mut self, id: usize) -> &mut T {
@@ -160,6 +195,11 @@ where
   /// The max size of the atlas can be.
   pub fn max_size(&self) -> DeviceSize { self.config.max_size }
 
+  /// Set the max size the atlas can grow to. The atlas not shrinks if it's
+  /// already larger than the size, but it evicts the least recently used
+  /// allocations instead of growing.
+  pub fn set_max_size(&mut self, size: DeviceSize) { self.config.max_size = size; }
+
   pub fn is_good_size_to_alloc(&self, size: DeviceSize) -> bool {
     (!size.greater_than(self.config.max_size).any())
       && size.area() <= self.config.max_size.area() / 4
@@ -167,7 +207,24 @@ where
 
   pub(crate) fn end_frame(&mut self) { self.end_frame_with(|_| {}) }
 
+  /// Release all the cached allocations at once, the `on_deallocate` is called
+  /// with the area of every allocation released from the atlas texture.
+  pub(crate) fn clear_cache_with(&mut self, mut on_deallocate: impl FnMut(DeviceRect)) {
+    while let Some((_, h)) = self.cache.pop_lru() {
+      match h.dist {
+        AtlasDist::Atlas(alloc) => {
+          on_deallocate(alloc.rectangle.to_rect().cast_unit());
+          self.atlas_allocator.deallocate(alloc.id);
+        }
+        AtlasDist::Extra(id) => {
+          self.extras.remove(id);
+        }
+      }
+    }
+  }
+
   pub(crate) fn end_frame_with(&mut self, mut on_deallocate: impl FnMut(DeviceRect)) {
+    self.evicted.clear();
     self
       .cache
       .end_frame(self.config.label)
@@ -254,18 +311,19 @@ mod tests {
 
   #[test]
   fn resource_hit() {
-    let mut gpu = block_on(WgpuImpl::headless());
+    let mut gpu = block_on(WgpuImpl::headless()).unwrap();
     let size = gpu.limits().texture_size;
-    let mut atlas = Atlas::<Resource<dyn Any>, WgpuTexture>::new(
+    let mut atlas = Atlas::<Resource<dyn Any + Send + Sync>, WgpuTexture>::new(
       AtlasConfig::new("", size),
       ColorFormat::Rgba8,
       &mut gpu,
     );
     let resource = Resource::new(1);
-    let h1 = atlas.get_or_cache(resource.clone().into_any(), 1., size, &mut gpu, |_, _, _| {});
-    let h2 = atlas.get_or_cache(resource.clone().into_any(), 0.8, size, &mut gpu, |_, _, _| {});
-    let h3 = atlas.get_or_cache(resource.clone().into_any(), 2., size, &mut gpu, |_, _, _| {});
-    let h4 = atlas.get_or_cache(resource.clone().into_any(), 1., size, &mut gpu, |_, _, _| {});
+    let h1 = atlas.get_or_cache(resource.clone().into_any_send(), 1., size, &mut gpu, |_, _, _| {});
+    let h2 =
+      atlas.get_or_cache(resource.clone().into_any_send(), 0.8, size, &mut gpu, |_, _, _| {});
+    let h3 = atlas.get_or_cache(resource.clone().into_any_send(), 2., size, &mut gpu, |_, _, _| {});
+    let h4 = atlas.get_or_cache(resource.clone().into_any_send(), 1., size, &mut gpu, |_, _, _| {});
 
     assert_eq!(h1, h2);
     assert_ne!(h2, h3);
@@ -274,8 +332,8 @@ mod tests {
 
   #[test]
   fn atlas_grow_to_alloc() {
-    let mut gpu_impl = block_on(WgpuImpl::headless());
-    let mut atlas = Atlas::<Resource<dyn Any>, WgpuTexture>::new(
+    let mut gpu_impl = block_on(WgpuImpl::headless()).unwrap();
+    let mut atlas = Atlas::<Resource<dyn Any + Send + Sync>, WgpuTexture>::new(
       AtlasConfig::new("", DeviceSize::new(4096, 4096)),
       ColorFormat::Alpha8,
       &mut gpu_impl,
@@ -283,7 +341,7 @@ mod tests {
 
     let size = DeviceSize::new(atlas.config.min_size.width + 1, 16);
     let dist = atlas.allocate(size, &mut gpu_impl);
-    atlas.cache(Resource::new(1).into_any(), 1., dist);
+    atlas.cache(Resource::new(1).into_any_send(), 1., dist);
 
     gpu_impl.end_frame();
     assert_eq!(dist.tex_id(), 0);
@@ -291,15 +349,15 @@ mod tests {
 
   #[test]
   fn resource_clear() {
-    let mut wgpu = block_on(WgpuImpl::headless());
+    let mut wgpu = block_on(WgpuImpl::headless()).unwrap();
     let size = wgpu.limits().texture_size;
-    let mut atlas = Atlas::<Resource<dyn Any>, WgpuTexture>::new(
+    let mut atlas = Atlas::<Resource<dyn Any + Send + Sync>, WgpuTexture>::new(
       AtlasConfig::new("", size),
       ColorFormat::Rgba8,
       &mut wgpu,
     );
     let dist = atlas.allocate(DeviceSize::new(32, 32), &mut wgpu);
-    atlas.cache(Resource::new(1).into_any(), 1., dist);
+    atlas.cache(Resource::new(1).into_any_send(), 1., dist);
     atlas.allocate(size, &mut wgpu);
     atlas.end_frame();
     atlas.end_frame();
@@ -309,15 +367,43 @@ mod tests {
     assert!(atlas.atlas_allocator.is_empty());
   }
 
+  #[test]
+  fn clear_cache() {
+    let mut wgpu = block_on(WgpuImpl::headless()).unwrap();
+    let size = DeviceSize::new(64, 64);
+    let config = AtlasConfig { label: "", min_size: size, max_size: size };
+    let mut atlas = Atlas::<Resource<dyn Any + Send + Sync>, WgpuTexture>::new(
+      config,
+      ColorFormat::Alpha8,
+      &mut wgpu,
+    );
+    let key = Resource::new(1).into_any_send();
+    let dist = atlas.allocate(DeviceSize::new(32, 32), &mut wgpu);
+    atlas.cache(key.clone(), 1., dist);
+    let extra = Resource::new(2).into_any_send();
+    let dist = atlas.allocate(DeviceSize::new(128, 128), &mut wgpu);
+    atlas.cache(extra.clone(), 1., dist);
+
+    let mut released = vec![];
+    atlas.clear_cache_with(|rect| released.push(rect));
+    assert_eq!(released, vec![DeviceRect::from_size(DeviceSize::new(32, 32))]);
+    assert!(atlas.get(&key, 1.).is_none());
+    assert!(atlas.get(&extra, 1.).is_none());
+    assert!(atlas.atlas_allocator.is_empty());
+    assert!(atlas.extras.is_empty());
+
+    wgpu.end_frame();
+  }
+
   #[test]
   fn fix_scale_path_cache_miss() {
-    let mut wgpu = block_on(WgpuImpl::headless());
-    let mut atlas = Atlas::<Resource<dyn Any>, WgpuTexture>::new(
+    let mut wgpu = block_on(WgpuImpl::headless()).unwrap();
+    let mut atlas = Atlas::<Resource<dyn Any + Send + Sync>, WgpuTexture>::new(
       AtlasConfig::new("", DeviceSize::new(4096, 4096)),
       ColorFormat::Rgba8,
       &mut wgpu,
     );
-    let key = Resource::new(1).into_any();
+    let key = Resource::new(1).into_any_send();
     let dist = atlas.allocate(DeviceSize::new(32, 32), &mut wgpu);
     atlas.cache(key.clone(), 1., dist);
     let dist = atlas.allocate(DeviceSize::new(512, 512), &mut wgpu);
@@ -340,10 +426,44 @@ mod tests {
     assert_eq!(alloc_count, 1);
   }
 
+  #[test]
+  fn evict_unused_when_full() {
+    let mut wgpu = block_on(WgpuImpl::headless()).unwrap();
+    let size = DeviceSize::new(64, 64);
+    let config = AtlasConfig { label: "", min_size: size, max_size: size };
+    let mut atlas = Atlas::<Resource<dyn Any + Send + Sync>, WgpuTexture>::new(
+      config,
+      ColorFormat::Rgba8,
+      &mut wgpu,
+    );
+    let a = Resource::new(1).into_any_send();
+    let b = Resource::new(2).into_any_send();
+
+    let dist = atlas.allocate(size, &mut wgpu);
+    atlas.cache(a.clone(), 1., dist);
+    atlas.end_frame();
+
+    // `a` is not used in this frame, so it's evicted to make room.
+    let dist = atlas.allocate(size, &mut wgpu);
+    assert_eq!(dist.tex_id(), 0);
+    assert!(atlas.get(&a, 1.).is_none());
+    assert_eq!(atlas.take_evicted(), vec![DeviceRect::from_size(size)]);
+    atlas.cache(b.clone(), 1., dist);
+    atlas.end_frame();
+
+    // `b` is used in this frame, so the allocation falls back to an extra texture.
+    assert!(atlas.get(&b, 1.).is_some());
+    let dist = atlas.allocate(size, &mut wgpu);
+    assert_eq!(dist.tex_id(), 1);
+    assert!(atlas.take_evicted().is_empty());
+
+    wgpu.end_frame();
+  }
+
   #[test]
   fn fix_atlas_expand_overlap() {
-    let mut wgpu = block_on(WgpuImpl::headless());
-    let mut atlas = Atlas::<Resource<dyn Any>, WgpuTexture>::new(
+    let mut wgpu = block_on(WgpuImpl::headless()).unwrap();
+    let mut atlas = Atlas::<Resource<dyn Any + Send + Sync>, WgpuTexture>::new(
       AtlasConfig::new("", DeviceSize::new(4096, 4096)),
       ColorFormat::Alpha8,
       &mut wgpu,
@@ -2,11 +2,13 @@ use std::{any::Any, cmp::Ordering, hash::Hash, ops::Range};
 
 use guillotiere::euclid::SideOffsets2D;
 use rayon::{prelude::ParallelIterator, slice::ParallelSlice};
-use ribir_algo::Resource;
-use ribir_geom::{DeviceRect, DeviceSize, Size, Transform, transform_to_device_rect};
+use ribir_algo::{FrameCache, Resource};
+use ribir_geom::{
+  DeviceRect, DeviceSize, Point, Rect, Size, Transform, Vector, transform_to_device_rect,
+};
 use ribir_painter::{
-  PaintPath, PaintingStyle, Path, PixelImage, StrokeOptions, Vertex, VertexBuffers,
-  image::ColorFormat,
+  ExternalFrame, ExternalTexture, PaintPath, PaintingStyle, Path, PixelImage, StrokeOptions,
+  Vertex, VertexBuffers, WeakExternalTexture, image::ColorFormat,
 };
 
 use super::{
@@ -16,33 +18,64 @@ use super::{
 use crate::GPUBackendImpl;
 const TOLERANCE: f32 = 0.1_f32;
 const PAR_CHUNKS_SIZE: usize = 64;
+/// A shared path larger than this size in device pixels, such as the glyph of
+/// a display-size text, is not cached in the alpha atlas. It's rendered from
+/// its cached tessellation every frame to save the atlas space.
+const PATH_RENDER_THRESHOLD: i32 = 200;
+/// The number of the subpixel positions in a pixel that a cached path mask
+/// can be rendered at, in each axis.
+const SUBPIXEL_STEPS: u8 = 4;
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Copy)]
 pub(super) enum TextureID {
   Alpha(usize),
   Rgba(usize),
   Bundle(usize),
+  /// The texture of an external texture, by its id.
+  External(usize),
 }
 
 #[derive(PartialEq, Clone)]
 enum PathKey {
-  Fill(Resource<dyn Any>),
-  Stroke { resource: Resource<dyn Any>, options: StrokeOptions },
+  Fill(Resource<dyn Any + Send + Sync>),
+  Stroke { resource: Resource<dyn Any + Send + Sync>, options: StrokeOptions },
 }
 
+/// The subpixel offset of a path mask in the alpha atlas, as the steps of
+/// `1 / SUBPIXEL_STEPS` pixel in the x and y axis.
+type SubpixelBins = [u8; 2];
+
 pub(super) struct TexturesMgr<T: Texture> {
-  alpha_atlas: Atlas<PathKey, T>,
-  rgba_atlas: Atlas<Resource<dyn Any>, T>,
+  alpha_atlas: Atlas<(PathKey, SubpixelBins), T>,
+  rgba_atlas: Atlas<Resource<dyn Any + Send + Sync>, T>,
   /// Similar to the `rgba_atlas`, this is used to allocate the target texture
   /// for drawing commands.
   ///
   /// We keep it separate from `rgba_atlas` because the backend may not permit a
   /// texture to be used both as a target and as a sampled resource in the same
   /// draw call.
-  target_atlas: Atlas<Resource<dyn Any>, T>,
+  target_atlas: Atlas<Resource<dyn Any + Send + Sync>, T>,
+  /// The tessellation of the large shared paths, in the path's coordinate.
+  path_tessellations: FrameCache<PathKey, PathTessellation>,
   tess_task: Vec<TessTask>,
   tess_task_buffer: VertexBuffers<()>,
   need_clear_areas: Vec<DeviceRect>,
+  /// The textures of the external textures, they're not in any atlas and kept
+  /// until the handle is dropped.
+  external_textures: ahash::HashMap<usize, ExternalSlot<T>>,
+}
+
+struct ExternalSlot<T> {
+  handle: WeakExternalTexture,
+  texture: T,
+  /// Whether the texture is imported from the frame, it's not written by the
+  /// backend.
+  imported: bool,
+}
+
+struct PathTessellation {
+  scale: f32,
+  buffer: Resource<VertexBuffers<()>>,
 }
 
 struct TessTask {
@@ -52,6 +85,8 @@ struct TessTask {
   // transform to construct vertex
   transform: Transform,
   clip_rect: Option<DeviceRect>,
+  // the cached tessellation of the path, use it instead of tessellating again.
+  tessellation: Option<Resource<VertexBuffers<()>>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -66,6 +101,13 @@ macro_rules! id_to_texture_mut {
       TextureID::Alpha(id) => $mgr.alpha_atlas.get_texture_mut(id),
       TextureID::Rgba(id) => $mgr.rgba_atlas.get_texture_mut(id),
       TextureID::Bundle(id) => $mgr.target_atlas.get_texture_mut(id),
+      TextureID::External(id) => {
+        &mut $mgr
+          .external_textures
+          .get_mut(&id)
+          .unwrap()
+          .texture
+      }
     }
   };
 }
@@ -76,6 +118,7 @@ macro_rules! id_to_texture {
       TextureID::Alpha(id) => $mgr.alpha_atlas.get_texture(id),
       TextureID::Rgba(id) => $mgr.rgba_atlas.get_texture(id),
       TextureID::Bundle(id) => $mgr.target_atlas.get_texture(id),
+      TextureID::External(id) => &$mgr.external_textures[&id].texture,
     }
   };
 }
@@ -104,9 +147,11 @@ where
         ColorFormat::Rgba8,
         gpu_impl,
       ),
+      path_tessellations: FrameCache::new(),
       tess_task: <_>::default(),
       tess_task_buffer: <_>::default(),
       need_clear_areas: vec![],
+      external_textures: <_>::default(),
     }
   }
 
@@ -119,22 +164,33 @@ where
     let path_bounds = path.bounds(style.line_width());
     match path {
       PaintPath::Share(p) => {
-        let resource = p.clone().into_any();
-        let cache_scale: f32 = self.cache_scale(&path_bounds.size, matrix);
+        let resource = p.clone().into_any_send();
         let key = match style {
           PaintingStyle::Fill => PathKey::Fill(resource),
           PaintingStyle::Stroke(options) => PathKey::Stroke { resource, options: options.clone() },
         };
 
+        let paint_bounds = transform_to_device_rect(&path_bounds, matrix);
+        if paint_bounds.width().max(paint_bounds.height()) > PATH_RENDER_THRESHOLD {
+          let tessellation = self.path_tessellation(key, p, style, matrix);
+          return self.store_frame_path(path, style, matrix, viewport, Some(tessellation), gpu);
+        }
+
+        let cache_scale: f32 = self.cache_scale(&path_bounds.size, matrix);
+        let bins = subpixel_bins(&path_bounds.origin, cache_scale, matrix);
+        let subpixel = Vector::new(bins[0] as f32, bins[1] as f32) / SUBPIXEL_STEPS as f32;
+        let key = (key, bins);
+
         let (slice, scale) = if let Some(h) = self.alpha_atlas.get(&key, cache_scale).copied() {
           let mask_slice = self.alpha_atlas_dist_to_tex_slice(&h.dist);
           (mask_slice, h.scale)
         } else {
           let scale_bounds = path_bounds.scale(cache_scale, cache_scale);
+          let alloc_bounds = Rect::new(scale_bounds.origin, scale_bounds.size + subpixel.to_size());
           let (dist, slice) =
-            self.alpha_allocate(scale_bounds.round_out().size.to_i32().cast_unit(), gpu);
+            self.alpha_allocate(alloc_bounds.round_out().size.to_i32().cast_unit(), gpu);
           let _ = self.alpha_atlas.cache(key, cache_scale, dist);
-          let offset = slice.rect.origin.to_f32().cast_unit() - scale_bounds.origin;
+          let offset = slice.rect.origin.to_f32().cast_unit() - scale_bounds.origin + subpixel;
           let transform = Transform::scale(cache_scale, cache_scale).then_translate(offset);
           self.tess_task.push(TessTask {
             slice,
@@ -142,11 +198,12 @@ where
             transform,
             clip_rect: None,
             style: style.clone(),
+            tessellation: None,
           });
           (slice, cache_scale)
         };
 
-        let path_origin = path_bounds.origin * scale;
+        let path_origin = path_bounds.origin * scale - subpixel;
         let slice_origin = slice.rect.origin.to_vector().to_f32();
         // back to slice origin
         let matrix = Transform::translation(-slice_origin.x, -slice_origin.y)
@@ -159,32 +216,76 @@ where
 
         (slice.expand_for_paste(), matrix)
       }
-      PaintPath::Own(_) => {
-        let paint_bounds = transform_to_device_rect(&path_bounds, matrix);
-        let alloc_size = size_expand_blank(paint_bounds.size);
+      PaintPath::Own(_) => self.store_frame_path(path, style, matrix, viewport, None, gpu),
+    }
+  }
 
-        let (visual_rect, clip_rect) = if self.alpha_atlas.is_good_size_to_alloc(alloc_size) {
-          (paint_bounds, None)
-        } else {
-          // We intersect the path bounds with the viewport to reduce the number of pixels
-          // drawn for large paths.
-          let visual_rect = paint_bounds.intersection(viewport).unwrap();
-          (visual_rect, Some(visual_rect))
-        };
+  /// Store a path in the texture only for the current frame.
+  fn store_frame_path(
+    &mut self, path: &PaintPath, style: &PaintingStyle, matrix: &Transform, viewport: &DeviceRect,
+    tessellation: Option<Resource<VertexBuffers<()>>>, gpu: &mut T::Host,
+  ) -> (TextureSlice, Transform) {
+    let path_bounds = path.bounds(style.line_width());
+    let paint_bounds = transform_to_device_rect(&path_bounds, matrix);
+    let alloc_size = size_expand_blank(paint_bounds.size);
+
+    let (visual_rect, clip_rect) = if self.alpha_atlas.is_good_size_to_alloc(alloc_size) {
+      (paint_bounds, None)
+    } else {
+      // We intersect the path bounds with the viewport to reduce the number of pixels
+      // drawn for large paths.
+      let visual_rect = paint_bounds.intersection(viewport).unwrap();
+      (visual_rect, Some(visual_rect))
+    };
+
+    let (_, slice) = self.alpha_allocate(visual_rect.size, gpu);
+    let offset = (slice.rect.origin - visual_rect.origin)
+      .to_f32()
+      .cast_unit();
+    let ts = matrix.then_translate(offset);
+    let task = TessTask {
+      slice,
+      transform: ts,
+      path: path.clone(),
+      style: style.clone(),
+      clip_rect,
+      tessellation,
+    };
+    self.tess_task.push(task);
+
+    let offset = (visual_rect.origin - slice.rect.origin).to_f32();
+    (slice.expand_for_paste(), Transform::translation(offset.x, offset.y))
+  }
+
+  /// Return the tessellation of the shared path that is precise enough for
+  /// the `matrix`, tessellate it if it's not cached.
+  fn path_tessellation(
+    &mut self, key: PathKey, path: &Path, style: &PaintingStyle, matrix: &Transform,
+  ) -> Resource<VertexBuffers<()>> {
+    let Transform { m11, m12, m21, m22, .. } = matrix;
+    let scale = (m11.abs() + m12.abs()).max(m21.abs() + m22.abs());
+    if let Some(t) = self
+      .path_tessellations
+      .get(&key)
+      .filter(|t| t.scale >= scale * 0.95)
+    {
+      return t.buffer.clone();
+    }
 
-        let (_, slice) = self.alpha_allocate(visual_rect.size, gpu);
-        let offset = (slice.rect.origin - visual_rect.origin)
-          .to_f32()
-          .cast_unit();
-        let ts = matrix.then_translate(offset);
-        let task =
-          TessTask { slice, transform: ts, path: path.clone(), style: style.clone(), clip_rect };
-        self.tess_task.push(task);
-
-        let offset = (visual_rect.origin - slice.rect.origin).to_f32();
-        (slice.expand_for_paste(), Transform::translation(offset.x, offset.y))
+    let mut buffer = VertexBuffers::default();
+    let tolerance = TOLERANCE / scale;
+    let vertex_ctor = |pos: Point| Vertex::new([pos.x, pos.y], ());
+    match style {
+      PaintingStyle::Fill => path.fill_tessellate(tolerance, &mut buffer, vertex_ctor),
+      PaintingStyle::Stroke(options) => {
+        path.stroke_tessellate(tolerance, options.clone(), &mut buffer, vertex_ctor)
       }
     }
+    let buffer = Resource::new(buffer);
+    self
+      .path_tessellations
+      .put(key, PathTessellation { scale, buffer: buffer.clone() });
+    buffer
   }
 
   pub(super) fn store_image(
@@ -193,14 +294,17 @@ where
     match img.color_format() {
       ColorFormat::Rgba8 => {
         let atlas = &mut self.rgba_atlas;
-        let h =
-          atlas.get_or_cache(img.clone().into_any(), 1., img.size(), gpu, |rect, texture, gpu| {
-            texture.write_data(rect, img.pixel_bytes(), gpu)
-          });
+        let h = atlas.get_or_cache(
+          img.clone().into_any_send(),
+          1.,
+          img.size(),
+          gpu,
+          |rect, texture, gpu| texture.write_data(rect, img.pixel_bytes(), gpu),
+        );
         TextureSlice { tex_id: TextureID::Rgba(h.tex_id()), rect: h.tex_rect(atlas) }
       }
       ColorFormat::Alpha8 => {
-        let key = PathKey::Fill(img.clone().into_any());
+        let key = (PathKey::Fill(img.clone().into_any_send()), SubpixelBins::default());
         let atlas = &mut self.alpha_atlas;
         let h = atlas.get_or_cache(key, 1., img.size(), gpu, |rect, texture, gpu| {
           texture.write_data(rect, img.pixel_bytes(), gpu)
@@ -211,8 +315,8 @@ where
   }
 
   pub(super) fn store_commands(
-    &mut self, size: DeviceSize, target: Resource<dyn Any>, scale: f32, gpu: &mut T::Host,
-    init: impl FnOnce(&DeviceRect, &mut T, &mut T::Host),
+    &mut self, size: DeviceSize, target: Resource<dyn Any + Send + Sync>, scale: f32,
+    gpu: &mut T::Host, init: impl FnOnce(&DeviceRect, &mut T, &mut T::Host),
   ) -> (f32, TextureSlice) {
     let dist = self
       .target_atlas
@@ -223,8 +327,79 @@ where
     })
   }
 
+  /// Update the texture of the `external` by its pending frame, and return the
+  /// slice of its current frame, or `None` if it has no frame yet.
+  pub(super) fn store_external(
+    &mut self, external: &ExternalTexture, gpu: &mut T::Host,
+  ) -> Option<TextureSlice> {
+    let id = external.id();
+    match external.take_frame() {
+      Some(ExternalFrame::Pixels(img)) => {
+        let size = img.size();
+        let reusable = self
+          .external_textures
+          .get(&id)
+          .is_some_and(|slot| !slot.imported && slot.texture.size() == size);
+        if !reusable {
+          let texture = gpu.new_texture(size, ColorFormat::Rgba8);
+          let slot = ExternalSlot { handle: external.downgrade(), texture, imported: false };
+          self.external_textures.insert(id, slot);
+        }
+        let texture = &mut self
+          .external_textures
+          .get_mut(&id)
+          .unwrap()
+          .texture;
+        texture.write_data(&DeviceRect::from_size(size), img.pixel_bytes(), gpu);
+      }
+      Some(ExternalFrame::Native { texture, .. }) => match gpu.import_texture(texture) {
+        Some(texture) => {
+          let slot = ExternalSlot { handle: external.downgrade(), texture, imported: true };
+          self.external_textures.insert(id, slot);
+        }
+        None => log::warn!("The frame of the external texture is not supported by the backend."),
+      },
+      None => {}
+    }
+
+    let texture = &self.external_textures.get(&id)?.texture;
+    let rect = DeviceRect::from_size(texture.size());
+    Some(TextureSlice { tex_id: TextureID::External(id), rect })
+  }
+
+  /// Allocate a slice in the target atlas only for the current frame.
+  pub(super) fn store_frame_target(&mut self, size: DeviceSize, gpu: &mut T::Host) -> TextureSlice {
+    let dist = self.target_atlas.allocate(size, gpu);
+    TextureSlice {
+      tex_id: TextureID::Bundle(dist.tex_id()),
+      rect: dist.tex_rect(&self.target_atlas),
+    }
+  }
+
+  /// Limit the size the alpha atlas can grow to, it's clamped by the texture
+  /// size limit of the GPU.
+  pub(super) fn set_alpha_atlas_max_size(&mut self, size: DeviceSize, gpu: &T::Host) {
+    let limit = gpu.limits().texture_size;
+    self.alpha_atlas.set_max_size(size.min(limit));
+  }
+
+  /// Release the caches rendered for a device scale, the masks of the paths,
+  /// the bundles and the tessellations, they're rendered again at the new
+  /// scale when drawn.
+  pub(super) fn clear_scaled_caches(&mut self) {
+    self.alpha_atlas.clear_cache_with(|rect| {
+      self.need_clear_areas.push(rect);
+    });
+    self.target_atlas.clear_cache_with(|_| {});
+    self.path_tessellations.clear();
+  }
+
   pub(super) fn texture(&self, tex_id: TextureID) -> &T { id_to_texture!(self, tex_id) }
 
+  pub(super) fn texture_mut(&mut self, tex_id: TextureID) -> &mut T {
+    id_to_texture_mut!(self, tex_id)
+  }
+
   fn alpha_allocate(
     &mut self, mut size: DeviceSize, gpu: &mut T::Host,
   ) -> (AtlasDist, TextureSlice) {
@@ -232,6 +407,11 @@ where
     // Allocate with a 2-pixel blank edge to ensure that neighboring slices do not
     // affect the current slice.
     let dist = self.alpha_atlas.allocate(size, gpu);
+    // The evicted areas may be reused by this allocation, clear them before
+    // drawing.
+    self
+      .need_clear_areas
+      .extend(self.alpha_atlas.take_evicted());
 
     (dist, self.alpha_atlas_dist_to_tex_slice(&dist))
   }
@@ -258,11 +438,24 @@ where
     }
   }
 
-  fn tessellate(
-    path: &Path, style: &PaintingStyle, ts: &Transform, slice_size: &DeviceSize,
-    buffer: &mut VertexBuffers<()>,
-  ) -> Range<u32> {
+  fn tessellate(task: &TessTask, buffer: &mut VertexBuffers<()>) -> Range<u32> {
+    let TessTask { slice, path, style, transform: ts, tessellation, .. } = task;
     let start = buffer.indices.len() as u32;
+    if let Some(tess) = tessellation {
+      let offset = buffer.vertices.len() as u32;
+      buffer
+        .vertices
+        .extend(tess.vertices.iter().map(|v| {
+          let pos = ts.transform_point(Point::new(v.pos[0], v.pos[1]));
+          Vertex::new([pos.x, pos.y], ())
+        }));
+      buffer
+        .indices
+        .extend(tess.indices.iter().map(|i| i + offset));
+      return start..buffer.indices.len() as u32;
+    }
+
+    let slice_size = &slice.rect.size;
     let path_size = path.bounds(style.line_width()).size;
     let slice_size = slice_size.to_f32();
     let scale = (slice_size.width / path_size.width).max(slice_size.height / path_size.height);
@@ -309,27 +502,20 @@ where
 
     let mut draw_indices = Vec::with_capacity(self.tess_task.len());
     if self.tess_task.len() < PAR_CHUNKS_SIZE {
-      for f in self.tess_task.iter() {
-        let TessTask { slice, path, clip_rect, transform, style } = f;
-        let rg =
-          Self::tessellate(path, style, transform, &slice.rect.size, &mut self.tess_task_buffer);
-        draw_indices.push((slice.tex_id, rg, clip_rect));
+      for task in self.tess_task.iter() {
+        let rg = Self::tessellate(task, &mut self.tess_task_buffer);
+        draw_indices.push((task.slice.tex_id, rg, &task.clip_rect));
       }
     } else {
-      let mut tasks = Vec::with_capacity(self.tess_task.len());
-      for f in self.tess_task.iter() {
-        let TessTask { slice, path, clip_rect, transform, style } = f;
-        tasks.push((slice, style, transform, path, clip_rect));
-      }
-
-      let par_tess_res = tasks
+      let par_tess_res = self
+        .tess_task
         .par_chunks(PAR_CHUNKS_SIZE)
         .map(|tasks| {
           let mut buffer = VertexBuffers::default();
           let mut indices = Vec::with_capacity(tasks.len());
-          for (slice, style, ts, path, clip_rect) in tasks.iter() {
-            let rg = Self::tessellate(path, style, ts, &slice.rect.size, &mut buffer);
-            indices.push((slice.tex_id, rg, *clip_rect));
+          for task in tasks.iter() {
+            let rg = Self::tessellate(task, &mut buffer);
+            indices.push((task.slice.tex_id, rg, &task.clip_rect));
           }
           (indices, buffer)
         })
@@ -400,6 +586,12 @@ where
     });
     self.rgba_atlas.end_frame();
     self.target_atlas.end_frame();
+    self
+      .path_tessellations
+      .end_frame("Path tessellation");
+    self
+      .external_textures
+      .retain(|_, slot| slot.handle.upgrade().is_some());
   }
 }
 
@@ -418,6 +610,25 @@ fn extend_buffer<V>(dist: &mut VertexBuffers<V>, from: VertexBuffers<V>) {
 
 const ALPHA_BLANK_EDGE: i32 = 2;
 
+/// The subpixel offset the mask of a path should be rendered at, so its pixels
+/// are aligned with the device pixels when it's pasted by the `matrix`. Only
+/// the mask in the same scale of an axis-aligned matrix can be aligned, others
+/// are rendered at the pixel origin.
+fn subpixel_bins(path_origin: &Point, cache_scale: f32, matrix: &Transform) -> SubpixelBins {
+  let Transform { m11, m12, m21, m22, m31, m32, .. } = *matrix;
+  let same_scale = |s: f32| (s.abs() - cache_scale).abs() < f32::EPSILON * cache_scale.max(1.);
+  if m12 != 0. || m21 != 0. || !same_scale(m11) || !same_scale(m22) {
+    return SubpixelBins::default();
+  }
+
+  let bin = |scale: f32, offset: f32, origin: f32| {
+    let device = scale * origin + offset;
+    let subpixel = (scale.signum() * device).rem_euclid(1.);
+    (subpixel * SUBPIXEL_STEPS as f32).round() as u8 % SUBPIXEL_STEPS
+  };
+  [bin(m11, m31, path_origin.x), bin(m22, m32, path_origin.y)]
+}
+
 fn size_expand_blank(mut size: DeviceSize) -> DeviceSize {
   size.width += ALPHA_BLANK_EDGE * 2;
   size.height += ALPHA_BLANK_EDGE * 2;
@@ -481,7 +692,7 @@ pub mod tests {
 
   #[test]
   fn smoke_store_image() {
-    let mut wgpu = block_on(WgpuImpl::headless());
+    let mut wgpu = block_on(WgpuImpl::headless()).unwrap();
     let mut mgr = TexturesMgr::new(&mut wgpu);
 
     let red_img = color_image(Color::RED, 32, 32);
@@ -527,10 +738,10 @@ pub mod tests {
 
   #[test]
   fn transform_path_share_cache() {
-    let mut wgpu = block_on(WgpuImpl::headless());
+    let mut wgpu = block_on(WgpuImpl::headless()).unwrap();
     let mut mgr = TexturesMgr::<WgpuTexture>::new(&mut wgpu);
 
-    let p = Resource::new(Path::rect(&rect(0., 0., 300., 300.)));
+    let p = Resource::new(Path::rect(&rect(0., 0., 50., 50.)));
     let p = PaintPath::Share(p.clone());
 
     let viewport = rect(0, 0, 1024, 1024);
@@ -555,12 +766,80 @@ pub mod tests {
     assert_eq!(ts2, Transform::new(0.5, 0., 0., 0.5, 99., 99.));
   }
 
+  #[test]
+  fn subpixel_path_cache() {
+    let mut wgpu = block_on(WgpuImpl::headless()).unwrap();
+    let mut mgr = TexturesMgr::<WgpuTexture>::new(&mut wgpu);
+
+    let p = PaintPath::Share(Resource::new(Path::rect(&rect(0., 0., 50., 50.))));
+    let viewport = rect(0, 0, 1024, 1024);
+    let mut store = |x: f32| {
+      mgr.store_alpha_path(
+        &p,
+        &PaintingStyle::Fill,
+        &Transform::translation(x, 0.),
+        &viewport,
+        &mut wgpu,
+      )
+    };
+
+    let (quarter, ts) = store(10.25);
+    // The mask pixels are aligned with the device pixels.
+    let origin = ts.transform_point(quarter.rect.origin.to_f32().cast_unit());
+    assert_eq!(origin.x.fract(), 0.);
+    assert_eq!(store(20.25).0, quarter);
+    assert_ne!(store(10.5).0, quarter);
+    assert_eq!(store(10.).0, store(20.).0);
+  }
+
+  #[test]
+  fn large_share_path_render_by_tessellation() {
+    let mut wgpu = block_on(WgpuImpl::headless()).unwrap();
+    let mut mgr = TexturesMgr::<WgpuTexture>::new(&mut wgpu);
+
+    let p = Resource::new(Path::rect(&rect(0., 0., 150., 150.)));
+    let key = (PathKey::Fill(p.clone().into_any_send()), SubpixelBins::default());
+    let p = PaintPath::Share(p);
+    let viewport = rect(0, 0, 1024, 1024);
+
+    // The path is small enough to be cached in the atlas.
+    mgr.store_alpha_path(&p, &PaintingStyle::Fill, &Transform::identity(), &viewport, &mut wgpu);
+    assert!(mgr.alpha_atlas.get(&key, 1.).is_some());
+    assert!(!mgr.path_tessellations.contains(&key.0));
+
+    // Scale it to larger than the threshold, only its tessellation is cached.
+    let scale = Transform::scale(2., 2.);
+    mgr.store_alpha_path(&p, &PaintingStyle::Fill, &scale, &viewport, &mut wgpu);
+    assert!(mgr.alpha_atlas.get(&key, 2.).is_none());
+    let tess = mgr
+      .path_tessellations
+      .get(&key.0)
+      .unwrap()
+      .buffer
+      .clone();
+
+    // The tessellation is reused by the path in a smaller or similar scale.
+    let scale = Transform::scale(1.5, 1.5);
+    mgr.store_alpha_path(&p, &PaintingStyle::Fill, &scale, &viewport, &mut wgpu);
+    let reuse = mgr
+      .path_tessellations
+      .get(&key.0)
+      .unwrap()
+      .buffer
+      .clone();
+    assert_eq!(tess, reuse);
+
+    mgr.draw_alpha_textures(&mut wgpu);
+    mgr.end_frame();
+    wgpu.end_frame();
+  }
+
   #[test]
   fn fix_resource_address_conflict() {
     // because the next resource may allocate at same address of a deallocated
     // address.
 
-    let mut wgpu = block_on(WgpuImpl::headless());
+    let mut wgpu = block_on(WgpuImpl::headless()).unwrap();
     let mut mgr = TexturesMgr::<WgpuTexture>::new(&mut wgpu);
     {
       let red_img = color_image(Color::RED, 32, 32);
@@ -569,7 +848,7 @@ pub mod tests {
 
     for _ in 0..10 {
       mgr.end_frame();
-      let red_img = color_image(Color::RED, 32, 32).into_any();
+      let red_img = color_image(Color::RED, 32, 32).into_any_send();
       assert!(mgr.rgba_atlas.get(&red_img, 1.).is_none());
     }
   }
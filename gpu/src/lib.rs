@@ -1,6 +1,6 @@
 #![allow(clippy::needless_lifetimes)]
 pub mod error;
-use std::ops::Range;
+use std::{any::Any, ops::Range};
 
 pub use gpu_backend::Texture;
 use ribir_geom::{DevicePoint, DeviceRect, DeviceSize};
@@ -8,6 +8,9 @@ use ribir_painter::{Color, GradientStop, VertexBuffers, image::ColorFormat};
 mod gpu_backend;
 use zerocopy::AsBytes;
 
+mod soft_impl;
+pub use soft_impl::*;
+
 #[cfg(feature = "wgpu")]
 pub mod wgpu_impl;
 pub use gpu_backend::*;
@@ -59,8 +62,14 @@ pub use wgpu_impl::*;
 ///   |     |  +------------------------------------+    |
 ///   |     |  | load_linear_gradient_primitives()  |    |
 ///   |     +->| load_linear_gradient_stops()       |    |
-///   |        | load_linear_gradient_vertices()    |    |
-///   |        | draw_linear_gradient_triangles()   |    |
+///   |     |  | load_linear_gradient_vertices()    |    |
+///   |     |  | draw_linear_gradient_triangles()   |    |
+///   |     |  +------------------------------------+    |
+///   |     |                                            |
+///   |     |  +------------------------------------+    |
+///   |     |  | load_round_rect_primitives()       |    |
+///   |     +->| load_round_rect_vertices()         |    |
+///   |        | draw_round_rect_triangles()        |    |
 ///   |        +------------------------------------+    |
 ///   +---<----------------------------------------------+
 ///
@@ -87,6 +96,10 @@ pub trait GPUBackendImpl {
 
   /// Create a texture.
   fn new_texture(&mut self, size: DeviceSize, format: ColorFormat) -> Self::Texture;
+  /// Import a texture created out of the backend, such as the frame of a video
+  /// decoder pushed to an `ExternalTexture`. Return `None` if the backend
+  /// doesn't support it.
+  fn import_texture(&mut self, texture: Box<dyn Any + Send>) -> Option<Self::Texture>;
   /// Load the vertices and indices buffer that `draw_alpha_triangles` &
   /// `draw_alpha_triangles_with_scissor` will use.
   fn load_alpha_vertices(&mut self, buffers: &VertexBuffers<()>);
@@ -131,6 +144,12 @@ pub trait GPUBackendImpl {
   /// Load the vertices and indices buffer that `draw_linear_gradient_triangles`
   /// will use.
   fn load_linear_gradient_vertices(&mut self, buffers: &VertexBuffers<LinearGradientPrimIndex>);
+
+  /// Load the primitives that `draw_round_rect_triangles` will use.
+  fn load_round_rect_primitives(&mut self, primitives: &[RoundRectPrimitive]);
+  /// Load the vertices and indices buffer that `draw_round_rect_triangles`
+  /// will use.
+  fn load_round_rect_vertices(&mut self, buffers: &VertexBuffers<RoundRectPrimIndex>);
   /// Draw pure color triangles in the texture. And use the clear color clear
   /// the texture first if it's a Some-Value
   fn draw_color_triangles(
@@ -153,10 +172,31 @@ pub trait GPUBackendImpl {
     &mut self, texture: &mut Self::Texture, indices: Range<u32>, clear: Option<Color>,
   );
 
+  /// Draw triangles fill with the rounded rectangles by their signed distance
+  /// fields. And use the clear color clear the texture first if it's a
+  /// Some-Value
+  fn draw_round_rect_triangles(
+    &mut self, texture: &mut Self::Texture, indices: Range<u32>, clear: Option<Color>,
+  );
+
   fn copy_texture_from_texture(
     &mut self, dist_tex: &mut Self::Texture, copy_to: DevicePoint, from_tex: &Self::Texture,
     from_rect: &DeviceRect,
   );
+
+  /// Blur the `rect` of the `texture` in place by a separable gaussian blur,
+  /// the `sigma` is the standard deviation in device pixels. The pixels out
+  /// of the `rect` are not sampled.
+  fn blur_texture(&mut self, texture: &mut Self::Texture, rect: &DeviceRect, sigma: f32);
+
+  /// Paint the `rect` of the `texture` by the custom WGSL fragment shader
+  /// `source`, the `uniforms` are the bytes of the uniform it declares. The
+  /// pipeline is cached by the source, a shader that fails to compile paints
+  /// nothing.
+  fn draw_shader(
+    &mut self, texture: &mut Self::Texture, rect: &DeviceRect, source: &str, uniforms: &[u8],
+  );
+
   /// A frame end, call once per frame
   fn end_frame(&mut self);
 }
@@ -179,6 +219,9 @@ pub struct DrawPhaseLimits {
   /// The maximum number of gradient stops that the backend can load in a single
   /// draw phase
   pub max_gradient_stop_primitives: usize,
+  /// The maximum number of rounded rectangle primitives that the backend can
+  /// load in a single draw
+  pub max_round_rect_primitives: usize,
   /// The maximum number of mask layers that the backend can load in a single
   pub max_mask_layers: usize,
 }
@@ -204,6 +247,10 @@ pub struct RadialGradientPrimIndex(u32);
 #[derive(AsBytes, PartialEq, Clone, Copy, Debug)]
 pub struct LinearGradientPrimIndex(u32);
 
+#[repr(C, packed)]
+#[derive(AsBytes, PartialEq, Clone, Copy, Debug)]
+pub struct RoundRectPrimIndex(u32);
+
 #[repr(packed)]
 #[derive(AsBytes, PartialEq, Clone, Copy, Debug)]
 pub struct GradientStopPrimitive {
@@ -263,6 +310,27 @@ pub struct LinearGradientPrimitive {
   pub mask_head_and_spread: i32,
 }
 
+/// A rounded rectangle filled or bordered by a color, the fragment shader draws
+/// it by its signed distance field instead of a mask of the tessellated path.
+#[repr(C, packed)]
+#[derive(AsBytes, PartialEq, Clone, Copy, Debug)]
+pub struct RoundRectPrimitive {
+  /// The min and the max position of the outer bounds in the texture.
+  pub rect: [f32; 4],
+  /// The radius of the outer corners, in the order of the top left, the top
+  /// right, the bottom right and the bottom left.
+  pub radius: [f32; 4],
+  /// The rgba color.
+  pub color: u32,
+  /// The width of the border inside the outer bounds, zero to fill the
+  /// rectangle.
+  pub border_width: f32,
+  /// The index of the head mask layer.
+  pub mask_head: i32,
+  /// Align the primitive to 16 bytes.
+  pub _dummy: u32,
+}
+
 #[repr(packed)]
 #[derive(AsBytes, PartialEq, Clone, Copy)]
 pub struct ImgPrimitive {
@@ -0,0 +1,766 @@
+//! A pure CPU implementation of the `GPUBackendImpl`.
+//!
+//! It rasterizes the tessellated triangles directly, and samples the masks,
+//! images and gradients with the same math as the WGSL shaders of the wgpu
+//! implementation, so the output is close to the GPU one. It's slower than a
+//! GPU, but works everywhere, such as in a virtual machine or a CI without a
+//! GPU.
+
+use std::{any::Any, error::Error, future::Future, ops::Range, sync::Arc};
+
+use ribir_geom::{DevicePoint, DeviceRect, DeviceSize};
+use ribir_painter::{Color, PixelImage, VertexBuffers, image::ColorFormat};
+
+use crate::{
+  ColorAttr, DrawPhaseLimits, GPUBackendImpl, GradientStopPrimitive, ImagePrimIndex, ImgPrimitive,
+  LinearGradientPrimIndex, LinearGradientPrimitive, MaskLayer, RadialGradientPrimIndex,
+  RadialGradientPrimitive, RoundRectPrimIndex, RoundRectPrimitive, Texture,
+};
+
+const TEX_PER_DRAW: usize = 8;
+const MAX_PRIMITIVES: usize = 4096;
+const MAX_BLUR_SIGMA: f32 = 64.;
+/// The sample pattern of the alpha triangles, keep the same as the
+/// `alpha_triangles.wgsl`.
+const ALPHA_SAMPLES: [[f32; 2]; 6] = [
+  [-8. / 18., -1. / 12.],
+  [-5. / 18., 5. / 12.],
+  [-2. / 18., -3. / 12.],
+  [2. / 18., 3. / 12.],
+  [5. / 18., 1. / 12.],
+  [8. / 18., -5. / 12.],
+];
+
+pub struct SoftImpl {
+  limits: DrawPhaseLimits,
+  alpha_vertices: VertexBuffers<()>,
+  color_vertices: VertexBuffers<ColorAttr>,
+  img_vertices: VertexBuffers<ImagePrimIndex>,
+  img_prims: Vec<ImgPrimitive>,
+  radial_gradient_vertices: VertexBuffers<RadialGradientPrimIndex>,
+  radial_gradient_prims: Vec<RadialGradientPrimitive>,
+  radial_gradient_stops: Vec<GradientStopPrimitive>,
+  linear_gradient_vertices: VertexBuffers<LinearGradientPrimIndex>,
+  linear_gradient_prims: Vec<LinearGradientPrimitive>,
+  linear_gradient_stops: Vec<GradientStopPrimitive>,
+  round_rect_vertices: VertexBuffers<RoundRectPrimIndex>,
+  round_rect_prims: Vec<RoundRectPrimitive>,
+  mask_layers: Vec<MaskLayer>,
+  /// The textures loaded for the current draw phase, they share the pixels
+  /// with the textures of the caller until one of them is written.
+  textures: Vec<SoftTexture>,
+}
+
+/// A texture of the `SoftImpl`, its pixels are stored in the memory.
+#[derive(Clone)]
+pub struct SoftTexture {
+  size: DeviceSize,
+  format: ColorFormat,
+  data: Arc<Vec<u8>>,
+}
+
+impl Default for SoftImpl {
+  fn default() -> Self { Self::new() }
+}
+
+impl SoftImpl {
+  pub fn new() -> Self {
+    SoftImpl {
+      limits: DrawPhaseLimits {
+        texture_size: DeviceSize::new(8192, 8192),
+        max_tex_load: TEX_PER_DRAW,
+        max_image_primitives: MAX_PRIMITIVES,
+        max_radial_gradient_primitives: MAX_PRIMITIVES,
+        max_linear_gradient_primitives: MAX_PRIMITIVES,
+        max_gradient_stop_primitives: MAX_PRIMITIVES,
+        max_round_rect_primitives: MAX_PRIMITIVES,
+        max_mask_layers: MAX_PRIMITIVES,
+      },
+      alpha_vertices: VertexBuffers::new(),
+      color_vertices: VertexBuffers::new(),
+      img_vertices: VertexBuffers::new(),
+      img_prims: vec![],
+      radial_gradient_vertices: VertexBuffers::new(),
+      radial_gradient_prims: vec![],
+      radial_gradient_stops: vec![],
+      linear_gradient_vertices: VertexBuffers::new(),
+      linear_gradient_prims: vec![],
+      linear_gradient_stops: vec![],
+      round_rect_vertices: VertexBuffers::new(),
+      round_rect_prims: vec![],
+      mask_layers: vec![],
+      textures: vec![],
+    }
+  }
+
+  fn mask_alpha(&self, mut mask_idx: i32, pos: [f32; 2]) -> f32 {
+    let mut alpha = 1.;
+    while mask_idx >= 0 {
+      let mask = &self.mask_layers[mask_idx as usize];
+      alpha *= self.mask_sample(mask, pos);
+      mask_idx = mask.prev_mask_idx;
+    }
+    alpha
+  }
+
+  fn mask_sample(&self, mask: &MaskLayer, pos: [f32; 2]) -> f32 {
+    let Some(tex) = self.textures.get(mask.mask_tex_idx as usize) else {
+      return 0.;
+    };
+    let [x, y] = transform(mask.transform, pos);
+    let (min, max) = (mask.min, mask.max);
+    if x < min[0] || y < min[1] || max[0] < x || max[1] < y {
+      return 0.;
+    }
+    tex.sample([x, y])[0]
+  }
+
+  fn img_color(&self, prim: &ImgPrimitive, pos: [f32; 2]) -> [f32; 4] {
+    let [x, y] = transform(prim.transform, pos);
+    let (size, start) = (prim.img_size, prim.img_start);
+    let img_pos = [x % size[0] + start[0], y % size[1] + start[1]];
+    let tex_idx = (prim.mask_head_and_tex_idx & 0x0000FFFF) as usize;
+    let mut color = match self.textures.get(tex_idx) {
+      Some(tex) => tex.sample(img_pos),
+      // should not happen, use a red color to indicate error
+      None => [1., 0., 0., 1.],
+    };
+    color[3] *= self.mask_alpha(prim.mask_head_and_tex_idx >> 16, pos) * prim.opacity;
+    color
+  }
+
+  fn radial_gradient_color(&self, prim: &RadialGradientPrimitive, pos: [f32; 2]) -> [f32; 4] {
+    let alpha = self.mask_alpha(prim.mask_head, pos);
+    let [x, y] = transform(prim.transform, pos);
+    let (start, end) = (prim.start_center, prim.end_center);
+    let (r0, r1) = (prim.start_radius, prim.end_radius);
+    let offset = match radial_offset([x, y], start, r0, end, r1) {
+      Some(offset) if r0 == r1 || offset >= r0 / (r0 - r1) => offset,
+      _ => return [1., 1., 1., alpha],
+    };
+    let offset = spread_offset(offset, prim.spread);
+    let stops = &self.radial_gradient_stops[prim.stop_start as usize..];
+    let mut color = stops_color(stops, prim.stop_cnt as usize, offset);
+    color[3] *= alpha;
+    color
+  }
+
+  fn linear_gradient_color(&self, prim: &LinearGradientPrimitive, pos: [f32; 2]) -> [f32; 4] {
+    let alpha = self.mask_alpha(prim.mask_head_and_spread >> 16, pos);
+    let (start, end) = (prim.start_position, prim.end_position);
+    if start == end {
+      return [1., 1., 1., alpha];
+    }
+    let [x, y] = transform(prim.transform, pos);
+    let (dx, dy) = (end[0] - start[0], end[1] - start[1]);
+    let offset = ((x - start[0]) * dx + (y - start[1]) * dy) / (dx * dx + dy * dy);
+    let spread = (prim.mask_head_and_spread & 0x0000FFFF) as u32;
+    let offset = spread_offset(offset, spread);
+    let stop_start = (prim.stop >> 16) as usize;
+    let stop_cnt = (prim.stop & 0x0000FFFF) as usize;
+    let mut color = stops_color(&self.linear_gradient_stops[stop_start..], stop_cnt, offset);
+    color[3] *= alpha;
+    color
+  }
+
+  fn round_rect_color(&self, prim: &RoundRectPrimitive, pos: [f32; 2]) -> [f32; 4] {
+    let RoundRectPrimitive { rect, radius, border_width, .. } = *prim;
+    let half = [(rect[2] - rect[0]) / 2., (rect[3] - rect[1]) / 2.];
+    let p = [pos[0] - rect[0] - half[0], pos[1] - rect[1] - half[1]];
+    let coverage = |d: f32| (0.5 - d).clamp(0., 1.);
+    let mut alpha = coverage(round_rect_sdf(p, half, radius));
+    if border_width > 0. {
+      let inner_half = half.map(|h| (h - border_width).max(0.));
+      let inner_radius = radius.map(|r| (r - border_width).max(0.));
+      alpha = (alpha - coverage(round_rect_sdf(p, inner_half, inner_radius))).max(0.);
+    }
+
+    let mut color = Color::from_u32(prim.color).into_f32_components();
+    color[3] *= alpha * self.mask_alpha(prim.mask_head, pos);
+    color
+  }
+}
+
+impl GPUBackendImpl for SoftImpl {
+  type Texture = SoftTexture;
+
+  fn begin_frame(&mut self) {}
+
+  fn limits(&self) -> &DrawPhaseLimits { &self.limits }
+
+  fn new_texture(&mut self, size: DeviceSize, format: ColorFormat) -> Self::Texture {
+    let len = size.area().max(0) as usize * format.pixel_per_bytes() as usize;
+    SoftTexture { size, format, data: Arc::new(vec![0; len]) }
+  }
+
+  fn import_texture(&mut self, texture: Box<dyn Any + Send>) -> Option<Self::Texture> {
+    texture.downcast::<SoftTexture>().ok().map(|t| *t)
+  }
+
+  fn load_alpha_vertices(&mut self, buffers: &VertexBuffers<()>) {
+    copy_buffers(&mut self.alpha_vertices, buffers);
+  }
+
+  fn draw_alpha_triangles(&mut self, indices: &Range<u32>, texture: &mut Self::Texture) {
+    let clip = DeviceRect::from_size(texture.size);
+    draw_alpha_triangles(&self.alpha_vertices, indices, texture, clip);
+  }
+
+  fn draw_alpha_triangles_with_scissor(
+    &mut self, indices: &Range<u32>, texture: &mut Self::Texture, scissor: DeviceRect,
+  ) {
+    draw_alpha_triangles(&self.alpha_vertices, indices, texture, scissor);
+  }
+
+  fn load_textures(&mut self, textures: &[&Self::Texture]) {
+    self.textures.clear();
+    self
+      .textures
+      .extend(textures.iter().map(|t| (*t).clone()));
+  }
+
+  fn load_mask_layers(&mut self, layers: &[MaskLayer]) {
+    self.mask_layers.clear();
+    self.mask_layers.extend_from_slice(layers);
+  }
+
+  fn load_color_vertices(&mut self, buffers: &VertexBuffers<ColorAttr>) {
+    copy_buffers(&mut self.color_vertices, buffers);
+  }
+
+  fn load_img_primitives(&mut self, primitives: &[ImgPrimitive]) {
+    self.img_prims.clear();
+    self.img_prims.extend_from_slice(primitives);
+  }
+
+  fn load_img_vertices(&mut self, buffers: &VertexBuffers<ImagePrimIndex>) {
+    copy_buffers(&mut self.img_vertices, buffers);
+  }
+
+  fn load_radial_gradient_primitives(&mut self, primitives: &[RadialGradientPrimitive]) {
+    self.radial_gradient_prims.clear();
+    self
+      .radial_gradient_prims
+      .extend_from_slice(primitives);
+  }
+
+  fn load_radial_gradient_stops(&mut self, stops: &[GradientStopPrimitive]) {
+    self.radial_gradient_stops.clear();
+    self
+      .radial_gradient_stops
+      .extend_from_slice(stops);
+  }
+
+  fn load_radial_gradient_vertices(&mut self, buffers: &VertexBuffers<RadialGradientPrimIndex>) {
+    copy_buffers(&mut self.radial_gradient_vertices, buffers);
+  }
+
+  fn load_linear_gradient_primitives(&mut self, primitives: &[LinearGradientPrimitive]) {
+    self.linear_gradient_prims.clear();
+    self
+      .linear_gradient_prims
+      .extend_from_slice(primitives);
+  }
+
+  fn load_linear_gradient_stops(&mut self, stops: &[GradientStopPrimitive]) {
+    self.linear_gradient_stops.clear();
+    self
+      .linear_gradient_stops
+      .extend_from_slice(stops);
+  }
+
+  fn load_linear_gradient_vertices(&mut self, buffers: &VertexBuffers<LinearGradientPrimIndex>) {
+    copy_buffers(&mut self.linear_gradient_vertices, buffers);
+  }
+
+  fn load_round_rect_primitives(&mut self, primitives: &[RoundRectPrimitive]) {
+    self.round_rect_prims.clear();
+    self
+      .round_rect_prims
+      .extend_from_slice(primitives);
+  }
+
+  fn load_round_rect_vertices(&mut self, buffers: &VertexBuffers<RoundRectPrimIndex>) {
+    copy_buffers(&mut self.round_rect_vertices, buffers);
+  }
+
+  fn draw_color_triangles(
+    &mut self, texture: &mut Self::Texture, indices: Range<u32>, clear: Option<Color>,
+  ) {
+    draw_triangles(&self.color_vertices, indices, texture, clear, |pos, attr| {
+      let [red, green, blue, alpha] = attr.color;
+      let mut color = Color::new(red, green, blue, alpha).into_f32_components();
+      color[3] *= self.mask_alpha(attr.mask_head, pos);
+      color
+    });
+  }
+
+  fn draw_img_triangles(
+    &mut self, texture: &mut Self::Texture, indices: Range<u32>, clear: Option<Color>,
+  ) {
+    draw_triangles(&self.img_vertices, indices, texture, clear, |pos, idx| {
+      self.img_color(&self.img_prims[idx.0 as usize], pos)
+    });
+  }
+
+  fn draw_radial_gradient_triangles(
+    &mut self, texture: &mut Self::Texture, indices: Range<u32>, clear: Option<Color>,
+  ) {
+    draw_triangles(&self.radial_gradient_vertices, indices, texture, clear, |pos, idx| {
+      self.radial_gradient_color(&self.radial_gradient_prims[idx.0 as usize], pos)
+    });
+  }
+
+  fn draw_linear_gradient_triangles(
+    &mut self, texture: &mut Self::Texture, indices: Range<u32>, clear: Option<Color>,
+  ) {
+    draw_triangles(&self.linear_gradient_vertices, indices, texture, clear, |pos, idx| {
+      self.linear_gradient_color(&self.linear_gradient_prims[idx.0 as usize], pos)
+    });
+  }
+
+  fn draw_round_rect_triangles(
+    &mut self, texture: &mut Self::Texture, indices: Range<u32>, clear: Option<Color>,
+  ) {
+    draw_triangles(&self.round_rect_vertices, indices, texture, clear, |pos, idx| {
+      self.round_rect_color(&self.round_rect_prims[idx.0 as usize], pos)
+    });
+  }
+
+  fn copy_texture_from_texture(
+    &mut self, dist_tex: &mut Self::Texture, copy_to: DevicePoint, from_tex: &Self::Texture,
+    from_rect: &DeviceRect,
+  ) {
+    let data = from_tex.read_rect(from_rect);
+    dist_tex.write_rect(&DeviceRect::new(copy_to, from_rect.size), &data);
+  }
+
+  fn blur_texture(&mut self, texture: &mut Self::Texture, rect: &DeviceRect, sigma: f32) {
+    let sigma = sigma.min(MAX_BLUR_SIGMA);
+    let rect = rect.intersection(&DeviceRect::from_size(texture.size));
+    let Some(rect) = rect.filter(|r| sigma > 0. && !r.is_empty()) else { return };
+
+    let channels = texture.format.pixel_per_bytes() as usize;
+    let (width, height) = (rect.width() as usize, rect.height() as usize);
+    let premultiplied = channels == 4;
+    let mut pixels: Vec<[f32; 4]> = texture
+      .read_rect(&rect)
+      .chunks(channels)
+      .map(|p| {
+        let mut c = [0.; 4];
+        p.iter()
+          .zip(c.iter_mut())
+          .for_each(|(v, c)| *c = *v as f32 / 255.);
+        if premultiplied {
+          (0..3).for_each(|i| c[i] *= c[3]);
+        }
+        c
+      })
+      .collect();
+
+    let radius = (sigma * 3.).ceil() as isize;
+    let weights: Vec<f32> = (0..=radius)
+      .map(|i| (-(i * i) as f32 / (2. * sigma * sigma)).exp())
+      .collect();
+    let total = weights[0] + 2. * weights[1..].iter().sum::<f32>();
+    let blur = |pixels: &[[f32; 4]], step: usize, len: usize, at: usize, offset: usize| {
+      let mut color = [0.; 4];
+      for i in -radius..=radius {
+        let idx = (offset as isize + i).clamp(0, len as isize - 1) as usize;
+        let p = pixels[at + idx * step];
+        let w = weights[i.unsigned_abs()];
+        (0..4).for_each(|c| color[c] += p[c] * w);
+      }
+      color.map(|c| c / total)
+    };
+
+    // Blur horizontally, then blur vertically, the pixels out of the rect are
+    // not sampled.
+    let mut temp = pixels.clone();
+    for y in 0..height {
+      for x in 0..width {
+        temp[y * width + x] = blur(&pixels, 1, width, y * width, x);
+      }
+    }
+    for y in 0..height {
+      for x in 0..width {
+        pixels[y * width + x] = blur(&temp, width, height, x, y);
+      }
+    }
+
+    let data: Vec<u8> = pixels
+      .iter()
+      .flat_map(|c| {
+        let mut c = *c;
+        if premultiplied && c[3] > 0. {
+          (0..3).for_each(|i| c[i] /= c[3]);
+        }
+        c.into_iter().take(channels).map(unorm)
+      })
+      .collect();
+    texture.write_rect(&rect, &data);
+  }
+
+  fn draw_shader(
+    &mut self, _texture: &mut Self::Texture, _rect: &DeviceRect, _source: &str, _uniforms: &[u8],
+  ) {
+    log::warn!("The software renderer can't run the custom shader, it paints nothing.");
+  }
+
+  fn end_frame(&mut self) {
+    // Release the shared pixels, so the textures not copied when they are
+    // written in the next frame.
+    self.textures.clear();
+  }
+}
+
+impl SoftTexture {
+  /// The pixels of the texture, row by row without any padding.
+  pub fn pixels(&self) -> &[u8] { &self.data }
+
+  fn bytes_per_pixel(&self) -> usize { self.format.pixel_per_bytes() as usize }
+
+  fn row_range(&self, x: i32, y: i32, width: i32) -> Range<usize> {
+    let bpp = self.bytes_per_pixel();
+    let start = (y as usize * self.size.width as usize + x as usize) * bpp;
+    start..start + width as usize * bpp
+  }
+
+  fn read_rect(&self, rect: &DeviceRect) -> Vec<u8> {
+    let mut data = Vec::with_capacity(rect.area() as usize * self.bytes_per_pixel());
+    for y in rect.y_range() {
+      data.extend_from_slice(&self.data[self.row_range(rect.min_x(), y, rect.width())]);
+    }
+    data
+  }
+
+  fn write_rect(&mut self, rect: &DeviceRect, data: &[u8]) {
+    let row_bytes = rect.width() as usize * self.bytes_per_pixel();
+    for (y, row) in rect.y_range().zip(data.chunks(row_bytes)) {
+      let range = self.row_range(rect.min_x(), y, rect.width());
+      Arc::make_mut(&mut self.data)[range].copy_from_slice(row);
+    }
+  }
+
+  fn texel(&self, x: i32, y: i32) -> [f32; 4] {
+    let x = x.clamp(0, self.size.width - 1);
+    let y = y.clamp(0, self.size.height - 1);
+    let range = self.row_range(x, y, 1);
+    match self.format {
+      ColorFormat::Rgba8 => {
+        let p = &self.data[range];
+        [p[0], p[1], p[2], p[3]].map(|c| c as f32 / 255.)
+      }
+      ColorFormat::Alpha8 => [self.data[range.start] as f32 / 255., 0., 0., 1.],
+    }
+  }
+
+  /// Sample the texture at the pixel position with a linear filter.
+  fn sample(&self, pos: [f32; 2]) -> [f32; 4] {
+    if self.size.is_empty() {
+      return [0.; 4];
+    }
+    let (x, y) = (pos[0] - 0.5, pos[1] - 0.5);
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let (x0, y0) = (x0 as i32, y0 as i32);
+    let lerp = |a: [f32; 4], b: [f32; 4], t: f32| [0, 1, 2, 3].map(|i| a[i] + (b[i] - a[i]) * t);
+    let top = lerp(self.texel(x0, y0), self.texel(x0 + 1, y0), fx);
+    let bottom = lerp(self.texel(x0, y0 + 1), self.texel(x0 + 1, y0 + 1), fx);
+    lerp(top, bottom, fy)
+  }
+
+  fn clear(&mut self, color: Color) {
+    let bpp = self.bytes_per_pixel();
+    let Color { red, green, blue, alpha } = color;
+    let pixel = [red, green, blue, alpha];
+    Arc::make_mut(&mut self.data)
+      .chunks_mut(bpp)
+      .for_each(|p| p.copy_from_slice(&pixel[..bpp]));
+  }
+
+  /// Blend the color to the pixel by the alpha blending.
+  fn blend(&mut self, x: i32, y: i32, color: [f32; 4]) {
+    let range = self.row_range(x, y, 1);
+    let pixel = &mut Arc::make_mut(&mut self.data)[range];
+    let alpha = color[3].clamp(0., 1.);
+    for (i, dst) in pixel.iter_mut().enumerate() {
+      let dst_v = *dst as f32 / 255.;
+      let src = if i == 3 { 1. } else { color[i] };
+      *dst = unorm(src * alpha + dst_v * (1. - alpha));
+    }
+  }
+}
+
+impl Texture for SoftTexture {
+  type Host = SoftImpl;
+
+  fn clear_areas(&mut self, areas: &[DeviceRect], _: &mut Self::Host) {
+    for rect in areas {
+      let zeros = vec![0; rect.area() as usize * self.bytes_per_pixel()];
+      self.write_rect(rect, &zeros);
+    }
+  }
+
+  fn write_data(&mut self, dist: &DeviceRect, data: &[u8], _: &mut Self::Host) {
+    self.write_rect(dist, data);
+  }
+
+  fn copy_as_image(
+    &self, rect: &DeviceRect, _: &mut Self::Host,
+  ) -> impl Future<Output = Result<PixelImage, Box<dyn Error>>> + 'static {
+    let data = self.read_rect(rect);
+    let img = PixelImage::new(data.into(), rect.width() as u32, rect.height() as u32, self.format);
+    std::future::ready(Ok(img))
+  }
+
+  fn color_format(&self) -> ColorFormat { self.format }
+
+  fn size(&self) -> DeviceSize { self.size }
+}
+
+fn copy_buffers<T: Copy>(dist: &mut VertexBuffers<T>, from: &VertexBuffers<T>) {
+  dist.vertices.clear();
+  dist.vertices.extend_from_slice(&from.vertices);
+  dist.indices.clear();
+  dist.indices.extend_from_slice(&from.indices);
+}
+
+fn draw_alpha_triangles(
+  buffers: &VertexBuffers<()>, indices: &Range<u32>, texture: &mut SoftTexture, clip: DeviceRect,
+) {
+  let Some(clip) = clip.intersection(&DeviceRect::from_size(texture.size)) else { return };
+  let data = Arc::make_mut(&mut texture.data);
+  let width = texture.size.width as usize;
+  for tri in buffers.indices[indices.start as usize..indices.end as usize].chunks_exact(3) {
+    let pts = [0, 1, 2].map(|i| buffers.vertices[tri[i] as usize].pos);
+    // Every sample covered adds `1 / 6` to the coverage.
+    for [sx, sy] in ALPHA_SAMPLES {
+      rasterize(pts, &clip, [-sx, -sy], |x, y| {
+        let v = &mut data[y as usize * width + x as usize];
+        *v = unorm(*v as f32 / 255. + 1. / 6.);
+      });
+    }
+  }
+}
+
+/// Draw the triangles by the color of the `shade` returned, the `shade` is
+/// called with the pixel center and the attribute of the first vertex of the
+/// triangle.
+fn draw_triangles<Attr: Copy>(
+  buffers: &VertexBuffers<Attr>, indices: Range<u32>, texture: &mut SoftTexture,
+  clear: Option<Color>, shade: impl Fn([f32; 2], Attr) -> [f32; 4],
+) {
+  if let Some(color) = clear {
+    texture.clear(color);
+  }
+  let size = texture.size;
+  let clip = DeviceRect::from_size(size);
+  let (w, h) = (size.width as f32, size.height as f32);
+  for tri in buffers.indices[indices.start as usize..indices.end as usize].chunks_exact(3) {
+    let vertices = [0, 1, 2].map(|i| buffers.vertices[tri[i] as usize]);
+    let pts = vertices.map(|v| {
+      let [x, y] = v.pos;
+      [x * w, y * h]
+    });
+    let attr = vertices[0].attr;
+    rasterize(pts, &clip, [0., 0.], |x, y| {
+      let color = shade([x as f32 + 0.5, y as f32 + 0.5], attr);
+      texture.blend(x, y, color);
+    });
+  }
+}
+
+/// Call `f` with every pixel in the `clip` whose center moved by `offset` is
+/// covered by the triangle. The pixels on the edge shared by two triangles
+/// only belong to one of them.
+fn rasterize(
+  mut pts: [[f32; 2]; 3], clip: &DeviceRect, offset: [f32; 2], mut f: impl FnMut(i32, i32),
+) {
+  let cross = |a: [f32; 2], b: [f32; 2], p: [f32; 2]| {
+    (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0])
+  };
+  let area = cross(pts[0], pts[1], pts[2]);
+  if area == 0. || !area.is_finite() {
+    return;
+  }
+  if area < 0. {
+    pts.swap(1, 2);
+  }
+
+  let min_x = pts.iter().map(|p| p[0]).fold(f32::MAX, f32::min);
+  let max_x = pts.iter().map(|p| p[0]).fold(f32::MIN, f32::max);
+  let min_y = pts.iter().map(|p| p[1]).fold(f32::MAX, f32::min);
+  let max_y = pts.iter().map(|p| p[1]).fold(f32::MIN, f32::max);
+  let x_start = ((min_x - 1.).floor() as i32).max(clip.min_x());
+  let x_end = ((max_x + 1.).ceil() as i32).min(clip.max_x());
+  let y_start = ((min_y - 1.).floor() as i32).max(clip.min_y());
+  let y_end = ((max_y + 1.).ceil() as i32).min(clip.max_y());
+
+  let edges = [(pts[0], pts[1]), (pts[1], pts[2]), (pts[2], pts[0])];
+  let inside = |p: [f32; 2]| {
+    edges.iter().all(|&(a, b)| {
+      let w = cross(a, b, p);
+      let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+      w > 0. || (w == 0. && (dy > 0. || (dy == 0. && dx < 0.)))
+    })
+  };
+  for y in y_start..y_end {
+    for x in x_start..x_end {
+      if inside([x as f32 + 0.5 + offset[0], y as f32 + 0.5 + offset[1]]) {
+        f(x, y);
+      }
+    }
+  }
+}
+
+/// Apply a 2x3 column-major matrix to the position.
+fn transform(m: [f32; 6], [x, y]: [f32; 2]) -> [f32; 2] {
+  [m[0] * x + m[2] * y + m[4], m[1] * x + m[3] * y + m[5]]
+}
+
+/// Return the offset of the radial gradient at the position, `None` if the
+/// position is not painted.
+fn radial_offset(pos: [f32; 2], start: [f32; 2], r0: f32, end: [f32; 2], r1: f32) -> Option<f32> {
+  // See `calc_offset` of the radial gradient shader.
+  let (dx_0, dy_0) = (pos[0] - start[0], pos[1] - start[1]);
+  let (dx_1_0, dy_1_0) = (end[0] - start[0], end[1] - start[1]);
+  let dr_1_0 = r1 - r0;
+  let a = dx_1_0 * dx_1_0 + dy_1_0 * dy_1_0 - dr_1_0 * dr_1_0;
+  let b = -2. * (dx_1_0 * dx_0 + dy_1_0 * dy_0 + dr_1_0 * r0);
+  let c = dx_0 * dx_0 + dy_0 * dy_0 - r0 * r0;
+  let delta = b * b - 4. * a * c;
+
+  if a.abs() < 0.1 {
+    if b.abs() < 0.1 { None } else { Some(-c / b) }
+  } else if delta < 0. {
+    None
+  } else {
+    let sqrt_delta = delta.sqrt();
+    Some(((-b + sqrt_delta) / (2. * a)).max((-b - sqrt_delta) / (2. * a)))
+  }
+}
+
+/// Apply the spread method to the offset, 0 for pad, 1 for reflect and 2 for
+/// repeat.
+fn spread_offset(offset: f32, spread: u32) -> f32 {
+  let fract = |v: f32| v - v.floor();
+  match spread {
+    0 => offset.clamp(0., 1.),
+    1 => 1. - (fract(offset / 2.) - 0.5).abs() * 2.,
+    _ => fract(offset),
+  }
+}
+
+fn stops_color(stops: &[GradientStopPrimitive], cnt: usize, offset: f32) -> [f32; 4] {
+  let stop = |i: usize| {
+    let stop = stops[i];
+    (Color::from_u32(stop.color).into_f32_components(), stop.offset)
+  };
+  let mut prev = stop(0);
+  let mut next = stop(1);
+  let mut i = 2;
+  while i < cnt && next.1 < offset {
+    prev = next;
+    next = stop(i);
+    i += 1;
+  }
+
+  let offset = offset.min(next.1).max(prev.1);
+  let weight1 = (next.1 - offset) / (next.1 - prev.1);
+  let weight2 = 1. - weight1;
+  [0, 1, 2, 3].map(|c| prev.0[c] * weight1 + next.0[c] * weight2)
+}
+
+/// The signed distance from the position to the rounded rectangle, the position
+/// is relative to the center of the rectangle. See `round_rect_sdf` of the
+/// round rect shader.
+fn round_rect_sdf(pos: [f32; 2], half: [f32; 2], radius: [f32; 4]) -> f32 {
+  let [top_left, top_right, bottom_right, bottom_left] = radius;
+  let r = match (pos[0] > 0., pos[1] > 0.) {
+    (false, false) => top_left,
+    (true, false) => top_right,
+    (true, true) => bottom_right,
+    (false, true) => bottom_left,
+  };
+  let q = [pos[0].abs() - half[0] + r, pos[1].abs() - half[1] + r];
+  let outside = q[0].max(0.).hypot(q[1].max(0.));
+  q[0].max(q[1]).min(0.) + outside - r
+}
+
+fn unorm(v: f32) -> u8 { (v.clamp(0., 1.) * 255.).round() as u8 }
+
+#[cfg(test)]
+mod tests {
+  use ribir_painter::Vertex;
+
+  use super::*;
+
+  #[test]
+  fn draw_color_rect() {
+    let mut soft = SoftImpl::new();
+    let mut tex = soft.new_texture(DeviceSize::new(4, 4), ColorFormat::Rgba8);
+    let attr = ColorAttr { color: Color::RED.into_components(), mask_head: -1 };
+    let vertices = [[0., 0.], [0.5, 0.], [0.5, 0.5], [0., 0.5]]
+      .map(|pos| Vertex { pos, attr })
+      .to_vec();
+    let buffers = VertexBuffers { vertices, indices: vec![0, 1, 2, 0, 2, 3] };
+    soft.load_textures(&[]);
+    soft.load_mask_layers(&[]);
+    soft.load_color_vertices(&buffers);
+    soft.draw_color_triangles(&mut tex, 0..6, Some(Color::WHITE));
+
+    let pixel = |x: usize, y: usize| &tex.pixels()[(y * 4 + x) * 4..(y * 4 + x + 1) * 4];
+    assert_eq!(pixel(0, 0), &[255, 0, 0, 255]);
+    assert_eq!(pixel(1, 1), &[255, 0, 0, 255]);
+    assert_eq!(pixel(2, 1), &[255, 255, 255, 255]);
+    assert_eq!(pixel(1, 2), &[255, 255, 255, 255]);
+  }
+
+  #[test]
+  fn draw_round_rect() {
+    let mut soft = SoftImpl::new();
+    let mut tex = soft.new_texture(DeviceSize::new(8, 8), ColorFormat::Rgba8);
+    let prim = RoundRectPrimitive {
+      rect: [0., 0., 8., 8.],
+      radius: [4., 0., 0., 0.],
+      color: Color::RED.into_u32(),
+      border_width: 2.,
+      mask_head: -1,
+      _dummy: 0,
+    };
+    let attr = RoundRectPrimIndex(0);
+    let vertices = [[0., 0.], [1., 0.], [1., 1.], [0., 1.]]
+      .map(|pos| Vertex { pos, attr })
+      .to_vec();
+    let buffers = VertexBuffers { vertices, indices: vec![0, 1, 2, 0, 2, 3] };
+    soft.load_textures(&[]);
+    soft.load_mask_layers(&[]);
+    soft.load_round_rect_primitives(&[prim]);
+    soft.load_round_rect_vertices(&buffers);
+    soft.draw_round_rect_triangles(&mut tex, 0..6, Some(Color::WHITE));
+
+    let pixel = |x: usize, y: usize| &tex.pixels()[(y * 8 + x) * 4..(y * 8 + x + 1) * 4];
+    // The border.
+    assert_eq!(pixel(7, 7), &[255, 0, 0, 255]);
+    assert_eq!(pixel(4, 1), &[255, 0, 0, 255]);
+    // The inside of the border.
+    assert_eq!(pixel(4, 4), &[255, 255, 255, 255]);
+    // The outside of the rounded corner.
+    assert_eq!(pixel(0, 0), &[255, 255, 255, 255]);
+  }
+
+  #[test]
+  fn alpha_coverage() {
+    let mut soft = SoftImpl::new();
+    let mut tex = soft.new_texture(DeviceSize::new(2, 1), ColorFormat::Alpha8);
+    let vertices = [[0., 0.], [1., 0.], [1., 1.], [0., 1.]]
+      .map(|pos| Vertex { pos, attr: () })
+      .to_vec();
+    let buffers = VertexBuffers { vertices, indices: vec![0, 1, 2, 0, 2, 3] };
+    soft.load_alpha_vertices(&buffers);
+    soft.draw_alpha_triangles(&(0..6), &mut tex);
+
+    assert_eq!(tex.pixels(), &[255, 0]);
+  }
+}
@@ -1,4 +1,5 @@
 use std::{
+  any::Any,
   error::Error,
   mem::{MaybeUninit, size_of},
   ops::Range,
@@ -9,28 +10,33 @@ use ribir_geom::{DevicePoint, DeviceRect, DeviceSize};
 use ribir_painter::{Color, PixelImage, VertexBuffers, image::ColorFormat};
 
 use self::{
+  custom_shader_pass::CustomShaderPass,
   draw_alpha_triangles_pass::DrawAlphaTrianglesPass,
   draw_color_triangles_pass::DrawColorTrianglesPass,
   draw_img_triangles_pass::DrawImgTrianglesPass,
   draw_linear_gradient_pass::DrawLinearGradientTrianglesPass,
   draw_radial_gradient_pass::DrawRadialGradientTrianglesPass,
-  texture_pass::{ClearTexturePass, CopyTexturePass},
+  draw_round_rect_pass::DrawRoundRectTrianglesPass,
+  texture_pass::{BlurTexturePass, ClearTexturePass, CopyTexturePass},
   uniform::Uniform,
 };
 use crate::{
   ColorAttr, DrawPhaseLimits, GPUBackendImpl, GradientStopPrimitive, ImagePrimIndex, ImgPrimitive,
   LinearGradientPrimIndex, LinearGradientPrimitive, MaskLayer, RadialGradientPrimIndex,
-  RadialGradientPrimitive, gpu_backend::Texture,
+  RadialGradientPrimitive, RoundRectPrimIndex, RoundRectPrimitive, error::RenderInitError,
+  gpu_backend::Texture,
 };
 mod shaders;
 mod uniform;
 mod vertex_buffer;
 
+mod custom_shader_pass;
 mod draw_alpha_triangles_pass;
 mod draw_color_triangles_pass;
 mod draw_img_triangles_pass;
 mod draw_linear_gradient_pass;
 mod draw_radial_gradient_pass;
+mod draw_round_rect_pass;
 mod texture_pass;
 
 pub const TEX_PER_DRAW: usize = 8;
@@ -46,10 +52,13 @@ pub struct WgpuImpl {
   clear_tex_pass: ClearTexturePass,
   alpha_triangles_pass: DrawAlphaTrianglesPass,
   copy_tex_pass: Option<CopyTexturePass>,
+  blur_tex_pass: Option<BlurTexturePass>,
+  custom_shader_pass: Option<CustomShaderPass>,
   color_triangles_pass: Option<DrawColorTrianglesPass>,
   img_triangles_pass: Option<DrawImgTrianglesPass>,
   radial_gradient_pass: Option<DrawRadialGradientTrianglesPass>,
   linear_gradient_pass: Option<DrawLinearGradientTrianglesPass>,
+  round_rect_pass: Option<DrawRoundRectTrianglesPass>,
   texs_layout: wgpu::BindGroupLayout,
   textures_bind: Option<wgpu::BindGroup>,
   mask_layers_uniform: Uniform<MaskLayer>,
@@ -125,12 +134,35 @@ macro_rules! linear_gradient_pass {
   };
 }
 
+macro_rules! round_rect_pass {
+  ($backend:ident) => {
+    $backend.round_rect_pass.get_or_insert_with(|| {
+      DrawRoundRectTrianglesPass::new(
+        &$backend.device,
+        $backend.mask_layers_uniform.layout(),
+        &$backend.texs_layout,
+        &$backend.limits,
+      )
+    })
+  };
+}
+
 pub(crate) use command_encoder;
 
 pub struct Surface<'a> {
   surface: wgpu::Surface<'a>,
   config: wgpu::SurfaceConfiguration,
   current_texture: Option<WgpuTexture>,
+  /// The texture keeps the content of the last frame, and whether its content
+  /// is valid.
+  back_buffer: Option<(WgpuTexture, bool)>,
+  /// Whether the back buffer can be copied to the surface directly, otherwise
+  /// it's drawn to the surface.
+  copyable: bool,
+  /// The alpha modes supported by the surface.
+  alpha_modes: Vec<wgpu::CompositeAlphaMode>,
+  /// The present modes supported by the surface.
+  present_modes: Vec<wgpu::PresentMode>,
 }
 
 impl GPUBackendImpl for WgpuImpl {
@@ -169,6 +201,18 @@ impl GPUBackendImpl for WgpuImpl {
     WgpuTexture::from_tex(tex)
   }
 
+  fn import_texture(&mut self, texture: Box<dyn Any + Send>) -> Option<Self::Texture> {
+    let texture = texture.downcast::<wgpu::Texture>().ok()?;
+    let usage = texture.usage();
+    if texture.format() != wgpu::TextureFormat::Rgba8Unorm
+      || !usage.contains(wgpu::TextureUsages::TEXTURE_BINDING)
+    {
+      log::warn!("An external texture must be `Rgba8Unorm` and can be bound, but got {texture:?}.");
+      return None;
+    }
+    Some(WgpuTexture::from_tex(*texture))
+  }
+
   fn load_textures(&mut self, textures: &[&Self::Texture]) {
     self.textures_bind =
       Some(textures_bind(&self.device, &self.sampler, &self.texs_layout, textures));
@@ -216,6 +260,14 @@ impl GPUBackendImpl for WgpuImpl {
     linear_gradient_pass!(self).load_triangles_vertices(buffers, &self.device, &self.queue);
   }
 
+  fn load_round_rect_primitives(&mut self, primitives: &[RoundRectPrimitive]) {
+    round_rect_pass!(self).load_round_rect_primitives(&self.queue, primitives);
+  }
+
+  fn load_round_rect_vertices(&mut self, buffers: &VertexBuffers<RoundRectPrimIndex>) {
+    round_rect_pass!(self).load_triangles_vertices(buffers, &self.device, &self.queue);
+  }
+
   fn load_mask_layers(&mut self, layers: &[crate::MaskLayer]) {
     self
       .mask_layers_uniform
@@ -265,6 +317,24 @@ impl GPUBackendImpl for WgpuImpl {
     self.submit()
   }
 
+  fn draw_round_rect_triangles(
+    &mut self, texture: &mut Self::Texture, indices: Range<u32>, clear: Option<Color>,
+  ) {
+    let encoder = command_encoder!(self);
+
+    round_rect_pass!(self).draw_triangles(
+      texture,
+      indices,
+      clear,
+      &self.device,
+      encoder,
+      self.textures_bind.as_ref().unwrap(),
+      &self.mask_layers_uniform,
+    );
+
+    self.submit()
+  }
+
   fn draw_alpha_triangles_with_scissor(
     &mut self, indices: &Range<u32>, texture: &mut Self::Texture, scissor: DeviceRect,
   ) {
@@ -326,6 +396,16 @@ impl GPUBackendImpl for WgpuImpl {
     }
   }
 
+  fn blur_texture(&mut self, texture: &mut Self::Texture, rect: &DeviceRect, sigma: f32) {
+    self.blur_texture_rect(texture, rect, sigma);
+  }
+
+  fn draw_shader(
+    &mut self, texture: &mut Self::Texture, rect: &DeviceRect, source: &str, uniforms: &[u8],
+  ) {
+    self.draw_custom_shader(texture, rect, source, uniforms);
+  }
+
   fn end_frame(&mut self) {
     self.submit();
     self.device.poll(wgpu::Maintain::Wait);
@@ -339,6 +419,7 @@ impl<'a> Surface<'a> {
   pub fn resize(&mut self, size: DeviceSize, backend: &WgpuImpl) {
     self.config.width = size.width as u32;
     self.config.height = size.height as u32;
+    self.back_buffer = None;
     if !size.is_empty() {
       self
         .surface
@@ -346,6 +427,42 @@ impl<'a> Surface<'a> {
     }
   }
 
+  /// Set whether the surface is composited with the content behind the window
+  /// by its alpha. It's ignored if the surface doesn't support it.
+  pub fn set_transparent(&mut self, transparent: bool, backend: &WgpuImpl) {
+    use wgpu::CompositeAlphaMode::*;
+    let mode = if transparent {
+      [PreMultiplied, PostMultiplied, Inherit]
+        .into_iter()
+        .find(|m| self.alpha_modes.contains(m))
+        .unwrap_or(Auto)
+    } else {
+      Auto
+    };
+    if self.config.alpha_mode != mode {
+      self.config.alpha_mode = mode;
+      if !self.size().is_empty() {
+        self
+          .surface
+          .configure(backend.device(), &self.config);
+      }
+    }
+  }
+
+  /// Set how the frames are presented, fallback to `Fifo` if the surface
+  /// doesn't support the `mode`.
+  pub fn set_present_mode(&mut self, mode: wgpu::PresentMode, backend: &WgpuImpl) {
+    let mode = if self.present_modes.contains(&mode) { mode } else { wgpu::PresentMode::Fifo };
+    if self.config.present_mode != mode {
+      self.config.present_mode = mode;
+      if !self.size().is_empty() {
+        self
+          .surface
+          .configure(backend.device(), &self.config);
+      }
+    }
+  }
+
   /// Get the size of the surface.
   pub fn size(&self) -> DeviceSize {
     DeviceSize::new(self.config.width as i32, self.config.height as i32)
@@ -358,6 +475,43 @@ impl<'a> Surface<'a> {
     })
   }
 
+  /// Return the back buffer to draw the frame, it keeps the content of the
+  /// last frame, so only the changed area needs to be redrawn. Call
+  /// [`Surface::copy_back_buffer`] to copy it to the surface before present.
+  ///
+  /// The returned bool is `false` if the content of the back buffer is
+  /// undefined, such as it's newly created, then the whole frame needs to be
+  /// drawn. Return `None` if the surface is empty.
+  pub fn back_buffer(&mut self, backend: &mut WgpuImpl) -> Option<(&mut WgpuTexture, bool)> {
+    let size = self.size();
+    if size.is_empty() {
+      return None;
+    }
+    let (tex, valid) = self
+      .back_buffer
+      .get_or_insert_with(|| (backend.new_texture(size, ColorFormat::Rgba8), false));
+    Some((tex, std::mem::replace(valid, true)))
+  }
+
+  /// Copy the content of the back buffer to the current texture of the
+  /// surface.
+  pub fn copy_back_buffer(&mut self, backend: &mut WgpuImpl) {
+    if let Some((back_buffer, _)) = self.back_buffer.as_ref() {
+      let rect = DeviceRect::from_size(back_buffer.size());
+      let output = self.current_texture.get_or_insert_with(|| {
+        let tex = self.surface.get_current_texture().unwrap();
+        WgpuTexture::new(InnerTexture::SurfaceTexture(tex))
+      });
+      if self.copyable {
+        let output = output.inner_tex.texture();
+        let from = back_buffer.inner_tex.texture();
+        backend.copy_same_format_texture(output, DevicePoint::zero(), from, &rect);
+      } else {
+        backend.draw_texture_to_texture(output, DevicePoint::zero(), back_buffer, &rect);
+      }
+    }
+  }
+
   /// Present the current texture to the surface.
   pub fn present(&mut self) {
     if let Some(tex) = self.current_texture.take() {
@@ -534,17 +688,25 @@ impl Texture for WgpuTexture {
 
 impl WgpuImpl {
   /// Create a new instance of `WgpuImpl` with a headless surface.
-  pub async fn headless() -> Self { Self::create(None).await.0 }
+  pub async fn headless() -> Result<Self, RenderInitError> {
+    Self::create(None)
+      .await
+      .map(|(gpu_impl, _)| gpu_impl)
+  }
 
   /// Create a new instance of `WgpuImpl` with a surface target and also return
   /// the surface.
-  pub async fn new<'a>(target: impl Into<wgpu::SurfaceTarget<'a>>) -> (Self, Surface<'a>) {
-    let (gpu_impl, surface) = Self::create(Some(target.into())).await;
-    (gpu_impl, surface.unwrap())
+  pub async fn new<'a>(
+    target: impl Into<wgpu::SurfaceTarget<'a>>,
+  ) -> Result<(Self, Surface<'a>), RenderInitError> {
+    let (gpu_impl, surface) = Self::create(Some(target.into())).await?;
+    Ok((gpu_impl, surface.unwrap()))
   }
 
   #[allow(clippy::needless_lifetimes)]
-  async fn create<'a>(target: Option<wgpu::SurfaceTarget<'a>>) -> (WgpuImpl, Option<Surface<'a>>) {
+  async fn create<'a>(
+    target: Option<wgpu::SurfaceTarget<'a>>,
+  ) -> Result<(WgpuImpl, Option<Surface<'a>>), RenderInitError> {
     let mut instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
       backends: wgpu::Backends::PRIMARY,
       ..<_>::default()
@@ -565,27 +727,26 @@ impl WgpuImpl {
       });
     }
 
-    let surface = target.map(|t| instance.create_surface(t).unwrap());
-    let adapter = instance
-      .request_adapter(&wgpu::RequestAdapterOptions {
-        power_preference: wgpu::PowerPreference::default(),
-        compatible_surface: surface.as_ref(),
-        force_fallback_adapter: false,
-      })
-      .await
-      .expect("No suitable GPU adapters found on the system!");
-
-    let (device, queue) = adapter
-      .request_device(
-        &wgpu::DeviceDescriptor {
-          required_limits: adapter.limits(),
-          required_features: wgpu::Features::CLEAR_TEXTURE,
-          ..Default::default()
-        },
-        None,
-      )
-      .await
-      .expect("Unable to find a suitable GPU adapter!");
+    let surface = target
+      .map(|t| instance.create_surface(t))
+      .transpose()
+      .map_err(|e| RenderInitError::SurfaceIncompatible(e.to_string()))?;
+    // Fallback to the software adapter if there is no hardware one.
+    let mut adapter = None;
+    for force_fallback_adapter in [false, true] {
+      adapter = instance
+        .request_adapter(&wgpu::RequestAdapterOptions {
+          power_preference: wgpu::PowerPreference::default(),
+          compatible_surface: surface.as_ref(),
+          force_fallback_adapter,
+        })
+        .await;
+      if adapter.is_some() {
+        break;
+      }
+    }
+    let adapter = adapter.ok_or(RenderInitError::NoAdapter)?;
+    let (device, queue) = Self::request_device(&adapter).await?;
 
     let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
       address_mode_u: wgpu::AddressMode::ClampToEdge,
@@ -618,6 +779,7 @@ impl WgpuImpl {
       max_radial_gradient_primitives: uniform_bytes / size_of::<RadialGradientPrimitive>(),
       max_linear_gradient_primitives: uniform_bytes / size_of::<LinearGradientPrimitive>(),
       max_gradient_stop_primitives: uniform_bytes / size_of::<GradientStopPrimitive>(),
+      max_round_rect_primitives: uniform_bytes / size_of::<RoundRectPrimitive>(),
       max_mask_layers: uniform_bytes / size_of::<MaskLayer>(),
     };
 
@@ -634,40 +796,94 @@ impl WgpuImpl {
       alpha_triangles_pass,
       clear_tex_pass,
       copy_tex_pass: None,
+      blur_tex_pass: None,
+      custom_shader_pass: None,
       color_triangles_pass: None,
       img_triangles_pass: None,
       radial_gradient_pass: None,
       linear_gradient_pass: None,
+      round_rect_pass: None,
       texs_layout,
       textures_bind: None,
       mask_layers_uniform,
       limits,
     };
 
-    let surface = surface.map(|surface| {
-      use wgpu::TextureFormat::*;
-      let format = surface
-        .get_capabilities(&adapter)
-        .formats
-        .into_iter()
-        .find(|&f| f == Rgba8Unorm || f == Bgra8Unorm)
-        .expect("No suitable format found for the surface!");
-
-      let config = wgpu::SurfaceConfiguration {
-        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-        format,
-        width: 0,
-        height: 0,
-        present_mode: wgpu::PresentMode::Fifo,
-        alpha_mode: wgpu::CompositeAlphaMode::Auto,
-        view_formats: vec![format],
-        desired_maximum_frame_latency: 2,
-      };
-
-      Surface { surface, config, current_texture: None }
-    });
+    let surface = surface
+      .map(|surface| {
+        use wgpu::TextureFormat::*;
+        let format = surface
+          .get_capabilities(&adapter)
+          .formats
+          .into_iter()
+          .find(|&f| f == Rgba8Unorm || f == Bgra8Unorm)
+          .ok_or_else(|| {
+            RenderInitError::SurfaceIncompatible(
+              "neither Rgba8Unorm nor Bgra8Unorm is supported".into(),
+            )
+          })?;
+        let capabilities = surface.get_capabilities(&adapter);
+        let copy_dst = capabilities
+          .usages
+          .contains(wgpu::TextureUsages::COPY_DST);
+        // The back buffer is `Rgba8Unorm`, it's copied to the surface only if
+        // the surface has the same format and can be copied to.
+        let copyable = copy_dst && format == Rgba8Unorm;
+        let mut usage = wgpu::TextureUsages::RENDER_ATTACHMENT;
+        if copy_dst {
+          usage |= wgpu::TextureUsages::COPY_DST;
+        }
+
+        let config = wgpu::SurfaceConfiguration {
+          usage,
+          format,
+          width: 0,
+          height: 0,
+          present_mode: wgpu::PresentMode::Fifo,
+          alpha_mode: wgpu::CompositeAlphaMode::Auto,
+          view_formats: vec![format],
+          desired_maximum_frame_latency: 2,
+        };
+
+        Ok(Surface {
+          surface,
+          config,
+          current_texture: None,
+          back_buffer: None,
+          copyable,
+          alpha_modes: capabilities.alpha_modes,
+          present_modes: capabilities.present_modes,
+        })
+      })
+      .transpose()?;
+
+    Ok((gpu_impl, surface))
+  }
 
-    (gpu_impl, surface)
+  /// Request a device with the full limits of the adapter, and fallback to the
+  /// downlevel limits if the adapter refuses it.
+  async fn request_device(
+    adapter: &wgpu::Adapter,
+  ) -> Result<(wgpu::Device, wgpu::Queue), RenderInitError> {
+    let required_features = wgpu::Features::CLEAR_TEXTURE;
+    if !adapter.features().contains(required_features) {
+      return Err(RenderInitError::DeviceLimitsTooLow(format!(
+        "the adapter doesn't support the features {required_features:?}"
+      )));
+    }
+
+    let adapter_limits = adapter.limits();
+    let downlevel = wgpu::Limits::downlevel_defaults().using_resolution(adapter_limits.clone());
+    let mut last_err = None;
+    for required_limits in [adapter_limits, downlevel] {
+      let desc =
+        wgpu::DeviceDescriptor { required_limits, required_features, ..Default::default() };
+      match adapter.request_device(&desc, None).await {
+        Ok(device) => return Ok(device),
+        Err(err) => last_err = Some(err),
+      }
+    }
+    Err(RenderInitError::DeviceLimitsTooLow(last_err.map_or_else(String::new, |e| e.to_string())))
   }
 
   pub fn start_capture(&self) { self.device.start_capture(); }
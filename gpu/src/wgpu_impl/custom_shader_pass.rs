@@ -0,0 +1,243 @@
+use std::mem::size_of;
+
+use futures::FutureExt;
+use ribir_geom::DeviceRect;
+use ribir_painter::Vertex;
+use wgpu::StoreOp;
+use zerocopy::AsBytes;
+
+use super::{
+  texture_pass::{tex_render_pipeline, vertices_corners},
+  vertex_buffer::new_vertices,
+};
+use crate::{WgpuImpl, WgpuTexture, command_encoder, gpu_backend::Texture};
+
+/// The vertex stage shared by the custom fragment shaders.
+const PRELUDE: &str = include_str!("./shaders/custom_shader_prelude.wgsl");
+
+/// The uniform buffer is at least this size, so a shader without uniforms
+/// still has a valid binding.
+const MIN_UNIFORM_SIZE: u64 = 16;
+
+/// Paints a rect by the custom fragment shaders, the pipelines are cached by
+/// the shader source.
+pub struct CustomShaderPass {
+  layout: wgpu::PipelineLayout,
+  bind_layout: wgpu::BindGroupLayout,
+  vertices_buffer: wgpu::Buffer,
+  uniform_buffer: wgpu::Buffer,
+  /// The pipeline of every shader source, `None` if the shader is invalid.
+  pipelines: ahash::HashMap<String, Option<ShaderPipeline>>,
+}
+
+struct ShaderPipeline {
+  shader: wgpu::ShaderModule,
+  format: wgpu::TextureFormat,
+  pipeline: wgpu::RenderPipeline,
+}
+
+impl CustomShaderPass {
+  pub fn new(device: &wgpu::Device) -> Self {
+    let bind_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+      entries: &[wgpu::BindGroupLayoutEntry {
+        binding: 0,
+        visibility: wgpu::ShaderStages::FRAGMENT,
+        ty: wgpu::BindingType::Buffer {
+          ty: wgpu::BufferBindingType::Uniform,
+          has_dynamic_offset: false,
+          min_binding_size: None,
+        },
+        count: None,
+      }],
+      label: Some("Custom shader"),
+    });
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+      label: Some("Custom shader"),
+      bind_group_layouts: &[&bind_layout],
+      push_constant_ranges: &[],
+    });
+    let vertices_buffer = new_vertices::<[f32; 4]>(device, 4);
+    let uniform_buffer = new_uniform_buffer(device, MIN_UNIFORM_SIZE);
+
+    Self { layout, bind_layout, vertices_buffer, uniform_buffer, pipelines: <_>::default() }
+  }
+
+  /// Prepare the pipeline of the shader `source` for the `format`, return
+  /// `false` if the shader is invalid.
+  fn update(&mut self, source: &str, format: wgpu::TextureFormat, device: &wgpu::Device) -> bool {
+    let layout = &self.layout;
+    if !self.pipelines.contains_key(source) {
+      let pipeline = create_shader(source, device).and_then(|shader| {
+        let pipeline = create_pipeline(layout, &shader, format, device)?;
+        Some(ShaderPipeline { shader, format, pipeline })
+      });
+      self.pipelines.insert(source.to_owned(), pipeline);
+    }
+    let Some(Some(pipeline)) = self.pipelines.get_mut(source) else { return false };
+    if pipeline.format != format {
+      let Some(new) = create_pipeline(layout, &pipeline.shader, format, device) else {
+        return false;
+      };
+      pipeline.pipeline = new;
+      pipeline.format = format;
+    }
+    true
+  }
+
+  fn pipeline(&self, source: &str) -> Option<&wgpu::RenderPipeline> {
+    self
+      .pipelines
+      .get(source)?
+      .as_ref()
+      .map(|p| &p.pipeline)
+  }
+}
+
+impl WgpuImpl {
+  pub(crate) fn draw_custom_shader(
+    &mut self, tex: &WgpuTexture, rect: &DeviceRect, source: &str, uniforms: &[u8],
+  ) {
+    if rect.is_empty() {
+      return;
+    }
+    self.finish_command();
+
+    let pass = self
+      .custom_shader_pass
+      .get_or_insert_with(|| CustomShaderPass::new(&self.device));
+    if !pass.update(source, tex.format(), &self.device) {
+      return;
+    }
+    let uniform_size = (uniforms.len() as u64)
+      .next_multiple_of(MIN_UNIFORM_SIZE)
+      .max(MIN_UNIFORM_SIZE);
+    if pass.uniform_buffer.size() < uniform_size {
+      pass.uniform_buffer = new_uniform_buffer(&self.device, uniform_size);
+    }
+    if !uniforms.is_empty() {
+      let mut data = uniforms.to_vec();
+      data.resize(uniform_size as usize, 0);
+      self
+        .queue
+        .write_buffer(&pass.uniform_buffer, 0, &data);
+    }
+
+    let [lt, rt, rb, lb] = vertices_corners(rect, Texture::size(tex));
+    let [w, h] = [rect.width() as f32, rect.height() as f32];
+    self.queue.write_buffer(
+      &pass.vertices_buffer,
+      0,
+      [
+        Vertex::new(lt, [0., 0., w, h]),
+        Vertex::new(lb, [0., 1., w, h]),
+        Vertex::new(rt, [1., 0., w, h]),
+        Vertex::new(rb, [1., 1., w, h]),
+      ]
+      .as_bytes(),
+    );
+
+    let bind_group = self
+      .device
+      .create_bind_group(&wgpu::BindGroupDescriptor {
+        layout: &pass.bind_layout,
+        entries: &[wgpu::BindGroupEntry {
+          binding: 0,
+          resource: pass.uniform_buffer.as_entire_binding(),
+        }],
+        label: Some("Custom shader bind group"),
+      });
+    let pipeline = pass.pipeline(source).unwrap();
+
+    let color_attachments = wgpu::RenderPassColorAttachment {
+      view: tex.view(),
+      resolve_target: None,
+      ops: wgpu::Operations { load: wgpu::LoadOp::Load, store: StoreOp::Store },
+    };
+    let encoder = command_encoder!(self);
+    {
+      let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("Custom shader"),
+        color_attachments: &[Some(color_attachments)],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+      });
+
+      rpass.set_vertex_buffer(0, pass.vertices_buffer.slice(..));
+      rpass.set_bind_group(0, &bind_group, &[]);
+      rpass.set_scissor_rect(
+        rect.min_x() as u32,
+        rect.min_y() as u32,
+        rect.width() as u32,
+        rect.height() as u32,
+      );
+      rpass.set_pipeline(pipeline);
+      rpass.draw(0..4, 0..1);
+    }
+    // Submit the pass before the buffers are rewritten by the next shader.
+    self.submit();
+  }
+}
+
+fn new_uniform_buffer(device: &wgpu::Device, size: u64) -> wgpu::Buffer {
+  device.create_buffer(&wgpu::BufferDescriptor {
+    label: Some("Custom shader uniform buffer"),
+    size,
+    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+    mapped_at_creation: false,
+  })
+}
+
+fn create_shader(source: &str, device: &wgpu::Device) -> Option<wgpu::ShaderModule> {
+  let source = format!("{PRELUDE}{source}");
+  catch_validation(device, || {
+    device.create_shader_module(wgpu::ShaderModuleDescriptor {
+      label: Some("Custom shader"),
+      source: wgpu::ShaderSource::Wgsl(source.into()),
+    })
+  })
+}
+
+fn create_pipeline(
+  layout: &wgpu::PipelineLayout, shader: &wgpu::ShaderModule, format: wgpu::TextureFormat,
+  device: &wgpu::Device,
+) -> Option<wgpu::RenderPipeline> {
+  catch_validation(device, || {
+    tex_render_pipeline::<[f32; 4]>(
+      "Custom shader",
+      device,
+      layout,
+      shader,
+      &[
+        wgpu::VertexAttribute {
+          offset: 0,
+          shader_location: 0,
+          format: wgpu::VertexFormat::Float32x2,
+        },
+        wgpu::VertexAttribute {
+          offset: size_of::<[f32; 2]>() as wgpu::BufferAddress,
+          shader_location: 1,
+          format: wgpu::VertexFormat::Float32x4,
+        },
+      ],
+      format,
+      wgpu::PrimitiveTopology::TriangleStrip,
+    )
+  })
+}
+
+/// Run `f` and return `None` if it raises a validation error, so an invalid
+/// user shader is logged instead of panicking.
+fn catch_validation<T>(device: &wgpu::Device, f: impl FnOnce() -> T) -> Option<T> {
+  device.push_error_scope(wgpu::ErrorFilter::Validation);
+  let value = f();
+  // The error is reported immediately on the native platforms, the web reports
+  // it asynchronously and it's not caught here.
+  match device.pop_error_scope().now_or_never().flatten() {
+    Some(err) => {
+      log::error!("Invalid custom shader: {err}");
+      None
+    }
+    None => Some(value),
+  }
+}
@@ -465,6 +465,99 @@ pub fn img_triangles_shader(limits: &DrawPhaseLimits) -> String {
   "#
 }
 
+pub fn round_rect_shader(limits: &DrawPhaseLimits) -> String {
+  basic_template(limits.max_mask_layers)
+    + &format!(
+      r#"
+@group(2) @binding(0)
+var<uniform> prims: array<Primitive, {}>;"#,
+      limits.max_round_rect_primitives
+    )
+    + r#"
+struct Vertex {
+  @location(0) pos: vec2<f32>,
+  @location(1) prim_idx: u32,
+};
+
+struct FragInput {
+  @builtin(position) pos: vec4<f32>,
+  @location(0) @interpolate(flat) prim_idx: u32,
+}
+
+struct Primitive {
+  // The min and the max position of the outer bounds.
+  rect: vec4<f32>,
+  // The radius of the top left, top right, bottom right and bottom left corners.
+  radius: vec4<f32>,
+  color: u32,
+  // Zero to fill the rectangle.
+  border_width: f32,
+  mask_head: i32,
+  dummy: u32,
+}
+
+@vertex
+fn vs_main(v: Vertex) -> FragInput {
+    var input: FragInput;
+    // convert from gpu-backend coords(0..1) to wgpu corrds(-1..1)
+    let pos = v.pos * vec2(2., -2.) + vec2(-1., 1.);
+    input.pos = vec4<f32>(pos, 0.0, 1.0);
+    input.prim_idx = v.prim_idx;
+    return input;
+}
+
+@fragment
+fn fs_main(input: FragInput) -> @location(0) vec4<f32> {
+    let prim = prims[input.prim_idx];
+    let half_size = (prim.rect.zw - prim.rect.xy) / 2.;
+    let pos = input.pos.xy - prim.rect.xy - half_size;
+
+    var alpha = coverage(round_rect_sdf(pos, half_size, prim.radius));
+    if prim.border_width > 0. {
+        let inner_half = max(half_size - prim.border_width, vec2(0.));
+        let inner_radius = max(prim.radius - prim.border_width, vec4(0.));
+        alpha = max(alpha - coverage(round_rect_sdf(pos, inner_half, inner_radius)), 0.);
+    }
+
+    var mask_idx = prim.mask_head;
+    loop {
+        if mask_idx < 0 { break; }
+
+        let mask = mask_layers[u32(mask_idx)];
+        alpha *= mask_sample(mask, input.pos.xy);
+        mask_idx = mask.prev_mask_idx;
+    }
+
+    var color = unpackUnorm4x8(prim.color);
+    color.a *= alpha;
+    return color;
+}
+
+// The signed distance from the position to the rounded rectangle, the position
+// is relative to the center of the rectangle.
+fn round_rect_sdf(pos: vec2<f32>, half_size: vec2<f32>, radius: vec4<f32>) -> f32 {
+    let top_bottom = select(radius.xw, radius.yz, pos.x > 0.);
+    let r = select(top_bottom.x, top_bottom.y, pos.y > 0.);
+    let q = abs(pos) - half_size + r;
+    return min(max(q.x, q.y), 0.) + length(max(q, vec2(0.))) - r;
+}
+
+// The coverage of the pixel by its distance to the edge.
+fn coverage(distance: f32) -> f32 {
+    return clamp(0.5 - distance, 0., 1.);
+}
+
+fn unpackUnorm4x8(color: u32) -> vec4<f32> {
+    return vec4<f32>(
+        f32((color & 0xff000000) >> 24) / 255.0,
+        f32((color & 0x00ff0000) >> 16) / 255.0,
+        f32((color & 0x0000ff00) >> 8) / 255.0,
+        f32((color & 0x000000ff) >> 0) / 255.0
+    );
+}
+"#
+}
+
 fn basic_template(max_mask_layers: usize) -> String {
   format!(
     r#"
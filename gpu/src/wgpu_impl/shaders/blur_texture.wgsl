@@ -0,0 +1,57 @@
+struct VertexOutput {
+  @builtin(position) pos: vec4<f32>,
+  @location(0) tex_pos: vec2<f32>,
+}
+
+struct Blur {
+  // The distance between two texels in the blur direction.
+  step: vec2<f32>,
+  // The area of the texture can be sampled.
+  min: vec2<f32>,
+  max: vec2<f32>,
+  // The standard deviation of the gaussian function, in pixels.
+  sigma: f32,
+  // The count of the texels sampled on each side.
+  radius: f32,
+}
+
+@vertex
+fn vs_main(@location(0) input_pos: vec2<f32>, @location(1) tex: vec2<f32>) -> VertexOutput {
+    var output: VertexOutput;
+    let pos = input_pos * vec2(2., -2.) + vec2(-1., 1.);
+    output.pos = vec4<f32>(pos, 0.0, 1.0);
+    output.tex_pos = tex;
+    return output;
+}
+
+@group(0) @binding(0)
+var texture: texture_2d<f32>;
+@group(0) @binding(1)
+var tex_sampler: sampler;
+@group(0) @binding(2)
+var<uniform> blur: Blur;
+
+// Sample the color premultiplied by its alpha, so the transparent pixels not
+// darken the color.
+fn premultiplied_sample(pos: vec2<f32>) -> vec4<f32> {
+    let color = textureSampleLevel(texture, tex_sampler, clamp(pos, blur.min, blur.max), 0.);
+    return vec4(color.rgb * color.a, color.a);
+}
+
+@fragment
+fn fs_main(@location(0) tex_pos: vec2<f32>) -> @location(0) vec4<f32> {
+    var color = premultiplied_sample(tex_pos);
+    var total = 1.;
+    let radius = i32(blur.radius);
+    for (var i = 1; i <= radius; i++) {
+        let offset = blur.step * f32(i);
+        let weight = exp(-f32(i * i) / (2. * blur.sigma * blur.sigma));
+        color += weight * (premultiplied_sample(tex_pos - offset) + premultiplied_sample(tex_pos + offset));
+        total += 2. * weight;
+    }
+    color /= total;
+    if color.a > 0. {
+        color = vec4(color.rgb / color.a, color.a);
+    }
+    return color;
+}
@@ -0,0 +1,21 @@
+// The vertex stage of the custom fragment shaders, the shader source is
+// appended after it.
+
+struct VertexOutput {
+  @builtin(position) pos: vec4<f32>,
+  // The position in the painted rect, from (0, 0) at the top left to (1, 1).
+  @location(0) uv: vec2<f32>,
+  // The size of the painted rect in device pixels.
+  @location(1) size: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@location(0) input_pos: vec2<f32>, @location(1) uv_size: vec4<f32>) -> VertexOutput {
+    var output: VertexOutput;
+    let pos = input_pos * vec2(2., -2.) + vec2(-1., 1.);
+    output.pos = vec4<f32>(pos, 0.0, 1.0);
+    output.uv = uv_size.xy;
+    output.size = uv_size.zw;
+    return output;
+}
+
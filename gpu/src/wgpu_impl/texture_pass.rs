@@ -132,7 +132,259 @@ impl ClearTexturePass {
   }
 }
 
+/// The max standard deviation of the blur in pixels, a larger one samples too
+/// many texels for a pixel.
+const MAX_BLUR_SIGMA: f32 = 64.;
+
+#[repr(C)]
+#[derive(AsBytes, Clone, Copy)]
+struct BlurUniform {
+  step: [f32; 2],
+  min: [f32; 2],
+  max: [f32; 2],
+  sigma: f32,
+  radius: f32,
+}
+
+pub struct BlurTexturePass {
+  pipeline: Option<wgpu::RenderPipeline>,
+  shader: wgpu::ShaderModule,
+  layout: wgpu::PipelineLayout,
+  bind_layout: wgpu::BindGroupLayout,
+  format: Option<wgpu::TextureFormat>,
+  vertices_buffer: wgpu::Buffer,
+  uniform_buffer: wgpu::Buffer,
+  /// The texture keeps the result of the horizontal blur.
+  temp_tex: Option<WgpuTexture>,
+}
+
+impl BlurTexturePass {
+  pub fn new(device: &wgpu::Device) -> Self {
+    let shader = device.create_shader_module(include_wgsl!("./shaders/blur_texture.wgsl"));
+
+    let bind_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+      entries: &[
+        wgpu::BindGroupLayoutEntry {
+          binding: 0,
+          visibility: wgpu::ShaderStages::FRAGMENT,
+          ty: wgpu::BindingType::Texture {
+            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+            view_dimension: wgpu::TextureViewDimension::D2,
+            multisampled: false,
+          },
+          count: None,
+        },
+        wgpu::BindGroupLayoutEntry {
+          binding: 1,
+          visibility: wgpu::ShaderStages::FRAGMENT,
+          ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+          count: None,
+        },
+        wgpu::BindGroupLayoutEntry {
+          binding: 2,
+          visibility: wgpu::ShaderStages::FRAGMENT,
+          ty: wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Uniform,
+            has_dynamic_offset: false,
+            min_binding_size: None,
+          },
+          count: None,
+        },
+      ],
+      label: Some("Blur texture"),
+    });
+
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+      label: Some("Blur texture"),
+      bind_group_layouts: &[&bind_layout],
+      push_constant_ranges: &[],
+    });
+    let vertices_buffer = new_vertices::<[f32; 2]>(device, 4);
+    let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+      label: Some("Blur texture uniform buffer"),
+      size: size_of::<BlurUniform>() as u64,
+      usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+      mapped_at_creation: false,
+    });
+    Self {
+      pipeline: None,
+      shader,
+      layout,
+      bind_layout,
+      format: None,
+      vertices_buffer,
+      uniform_buffer,
+      temp_tex: None,
+    }
+  }
+
+  pub fn update(&mut self, format: wgpu::TextureFormat, device: &wgpu::Device) {
+    if Some(format) != self.format {
+      self.format = Some(format);
+      self.pipeline.take();
+      self.temp_tex.take();
+    }
+
+    if self.pipeline.is_none() {
+      let pipeline = tex_render_pipeline::<[f32; 2]>(
+        "Blur texture",
+        device,
+        &self.layout,
+        &self.shader,
+        &[
+          wgpu::VertexAttribute {
+            offset: 0,
+            shader_location: 0,
+            format: wgpu::VertexFormat::Float32x2,
+          },
+          wgpu::VertexAttribute {
+            offset: (size_of::<[f32; 2]>()) as wgpu::BufferAddress,
+            shader_location: 1,
+            format: wgpu::VertexFormat::Float32x2,
+          },
+        ],
+        format,
+        wgpu::PrimitiveTopology::TriangleStrip,
+      );
+      self.pipeline = Some(pipeline);
+    }
+  }
+
+  /// Take the temporary texture that is at least `size` big.
+  fn take_temp_tex(&mut self, size: DeviceSize, device: &wgpu::Device) -> WgpuTexture {
+    match self.temp_tex.take() {
+      Some(tex) if !size.greater_than(Texture::size(&tex)).any() => tex,
+      old => {
+        let size = old.map_or(size, |t| Texture::size(&t).max(size));
+        let tex = device.create_texture(&wgpu::TextureDescriptor {
+          label: Some("Blur temporary texture"),
+          size: wgpu::Extent3d {
+            width: size.width as u32,
+            height: size.height as u32,
+            depth_or_array_layers: 1,
+          },
+          mip_level_count: 1,
+          sample_count: 1,
+          dimension: wgpu::TextureDimension::D2,
+          format: self.format.unwrap(),
+          usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::RENDER_ATTACHMENT,
+          view_formats: &[],
+        });
+        WgpuTexture::from_tex(tex)
+      }
+    }
+  }
+}
+
 impl WgpuImpl {
+  pub(crate) fn blur_texture_rect(&mut self, tex: &WgpuTexture, rect: &DeviceRect, sigma: f32) {
+    let sigma = sigma.min(MAX_BLUR_SIGMA);
+    if sigma <= 0. || rect.is_empty() {
+      return;
+    }
+
+    let pass = self
+      .blur_tex_pass
+      .get_or_insert_with(|| BlurTexturePass::new(&self.device));
+    pass.update(tex.format(), &self.device);
+    let temp = pass.take_temp_tex(rect.size, &self.device);
+
+    // Blur horizontally to the temporary texture, then blur it vertically back.
+    let temp_rect = DeviceRect::from_size(rect.size);
+    let tex_size = Texture::size(tex);
+    let step = [1. / tex_size.width as f32, 0.];
+    self.blur_pass(&temp, DevicePoint::zero(), tex, rect, step, sigma);
+    let step = [0., 1. / Texture::size(&temp).height as f32];
+    self.blur_pass(tex, rect.origin, &temp, &temp_rect, step, sigma);
+
+    if let Some(pass) = self.blur_tex_pass.as_mut() {
+      pass.temp_tex = Some(temp);
+    }
+  }
+
+  fn blur_pass(
+    &mut self, dist_tex: &WgpuTexture, dist_at: DevicePoint, from_tex: &WgpuTexture,
+    src_rect: &DeviceRect, step: [f32; 2], sigma: f32,
+  ) {
+    let pass = self.blur_tex_pass.as_mut().unwrap();
+    let from_size = Texture::size(from_tex);
+    let [d_lt, d_rt, d_rb, d_lb] =
+      vertices_corners(&DeviceRect::new(dist_at, src_rect.size), Texture::size(dist_tex));
+    let [s_lt, s_rt, s_rb, s_lb] = vertices_corners(src_rect, from_size);
+    self.queue.write_buffer(
+      &pass.vertices_buffer,
+      0,
+      [
+        Vertex::new(d_lt, s_lt),
+        Vertex::new(d_lb, s_lb),
+        Vertex::new(d_rt, s_rt),
+        Vertex::new(d_rb, s_rb),
+      ]
+      .as_bytes(),
+    );
+
+    // Only sample the texel centers in the source rect.
+    let inner = src_rect.to_f32().inflate(-0.5, -0.5);
+    let uniform = BlurUniform {
+      step,
+      min: vertices_coord(inner.min().cast_unit(), from_size),
+      max: vertices_coord(inner.max().cast_unit(), from_size),
+      sigma,
+      radius: (sigma * 3.).ceil(),
+    };
+    self
+      .queue
+      .write_buffer(&pass.uniform_buffer, 0, [uniform].as_bytes());
+
+    let bind_group = self
+      .device
+      .create_bind_group(&wgpu::BindGroupDescriptor {
+        layout: &pass.bind_layout,
+        entries: &[
+          wgpu::BindGroupEntry {
+            binding: 0,
+            resource: wgpu::BindingResource::TextureView(from_tex.view()),
+          },
+          wgpu::BindGroupEntry {
+            binding: 1,
+            resource: wgpu::BindingResource::Sampler(&self.sampler),
+          },
+          wgpu::BindGroupEntry { binding: 2, resource: pass.uniform_buffer.as_entire_binding() },
+        ],
+        label: Some("Blur texture bind group"),
+      });
+
+    let color_attachments = wgpu::RenderPassColorAttachment {
+      view: dist_tex.view(),
+      resolve_target: None,
+      ops: wgpu::Operations { load: wgpu::LoadOp::Load, store: StoreOp::Store },
+    };
+
+    let encoder = command_encoder!(self);
+    {
+      let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("Blur texture"),
+        color_attachments: &[Some(color_attachments)],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+      });
+
+      rpass.set_vertex_buffer(0, pass.vertices_buffer.slice(..));
+      rpass.set_bind_group(0, &bind_group, &[]);
+      rpass.set_scissor_rect(
+        dist_at.x as u32,
+        dist_at.y as u32,
+        src_rect.width() as u32,
+        src_rect.height() as u32,
+      );
+      rpass.set_pipeline(pass.pipeline.as_ref().unwrap());
+      rpass.draw(0..4, 0..1);
+    }
+    // Submit the pass before the buffers are rewritten by the next pass.
+    self.submit();
+  }
+
   pub(crate) fn draw_texture_to_texture(
     &mut self, dist_tex: &WgpuTexture, dist_at: DevicePoint, from_tex: &WgpuTexture,
     src_rect: &DeviceRect,
@@ -252,7 +504,7 @@ impl WgpuImpl {
   }
 }
 
-fn tex_render_pipeline<T>(
+pub(super) fn tex_render_pipeline<T>(
   label: &str, device: &wgpu::Device, layout: &wgpu::PipelineLayout, shader: &wgpu::ShaderModule,
   vertex_attrs: &[wgpu::VertexAttribute], format: wgpu::TextureFormat,
   topology: wgpu::PrimitiveTopology,
@@ -295,7 +547,7 @@ fn tex_render_pipeline<T>(
   })
 }
 
-fn vertices_corners(rect: &DeviceRect, tex_size: DeviceSize) -> [[f32; 2]; 4] {
+pub(super) fn vertices_corners(rect: &DeviceRect, tex_size: DeviceSize) -> [[f32; 2]; 4] {
   let [a, b, c, d] = rect_corners(&rect.to_f32().cast_unit());
   [
     vertices_coord(a, tex_size),
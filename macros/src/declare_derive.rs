@@ -32,6 +32,7 @@ pub(crate) fn declare_derive(input: &mut syn::DeriveInput) -> syn::Result<TokenS
 
       let field_names = declarer.fields.iter().map(DeclareField::member);
       let field_names2 = field_names.clone();
+      let field_names3 = field_names.clone();
 
       let (builder_f_names, builder_f_tys) = declarer.declare_names_tys();
       let field_values = field_values(&declarer.fields, host);
@@ -68,6 +69,15 @@ pub(crate) fn declare_derive(input: &mut syn::DeriveInput) -> syn::Result<TokenS
                fat_ಠ_ಠ = fat_ಠ_ಠ.on_disposed(move |_| u.unsubscribe());
              }
            );*
+           #(
+             if let Some(write_back) = #field_names3.2 {
+               let reader = this_ಠ_ಠ.clone_reader();
+               let u = this_ಠ_ಠ
+                 .modifies()
+                 .subscribe(move |_| write_back(&reader.read().#field_names3));
+               fat_ಠ_ಠ = fat_ಠ_ಠ.on_disposed(move |_| u.unsubscribe());
+             }
+           );*
 
            fat_ಠ_ಠ.map(move |_| this_ಠ_ಠ)
          }
@@ -163,22 +173,22 @@ fn field_values<'a>(
       if let Some(df) = f.default_value() {
         quote! {
           Option::take(&mut fat_ಠ_ಠ.#f_name).map_or_else(
-            || (#df, None),
-            |v| v.unzip()
+            || (#df, None, None),
+            |v| v.unzip_bind()
           )
         }
       } else {
         let err = format!("Required field `{stt_name}::{f_name}` not set");
-        quote! { Option::take(&mut fat_ಠ_ಠ.#f_name).expect(#err).unzip() }
+        quote! { Option::take(&mut fat_ಠ_ಠ.#f_name).expect(#err).unzip_bind() }
       }
     } else {
       // skip field must have default value.
       let df = f.default_value().unwrap();
-      quote! { (#df, None) }
+      quote! { (#df, None, None) }
     };
     quote_spanned! { f.field.span() =>
       #[allow(clippy::type_complexity)]
-      let #f_name: (#ty, Option<ValueStream<#ty>>) = #v;
+      let #f_name: (#ty, Option<ValueStream<#ty>>, Option<Box<dyn Fn(&#ty)>>) = #v;
     }
   })
 }
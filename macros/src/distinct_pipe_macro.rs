@@ -9,14 +9,32 @@ use crate::{
 pub fn gen_code(input: TokenStream, refs_ctx: Option<&mut DollarRefsCtx>) -> TokenStream {
   let span = input.span();
   let res = process_watch_body(input, refs_ctx).map(|(upstream, map_handler)| {
-    quote_spanned! {span =>
-      MapPipe::new(
-        ModifiesPipe::new(#upstream.box_it()),
-        #map_handler
-      )
-      // Since the pipe has an initial value, we skip the initial notification.
-      .value_chain(|s| s.skip(1).distinct_until_key_changed(|v: &(_, _)| v.1).box_it())
-    }
+    quote_spanned! {span => {
+      // The stream of the pipe doesn't contain the initial value, so record it
+      // as the base of the comparison when the pipe evaluates it. The values are
+      // cloned by `Clone::clone` to not trigger `clippy::clone_on_copy` in the
+      // user code.
+      let last = std::rc::Rc::new(std::cell::RefCell::new(None));
+      let c_last = last.clone();
+      #[allow(unused_mut)]
+      let mut map_handler = #map_handler;
+      MapPipe::new(ModifiesPipe::new(#upstream.box_it()), move |s| {
+        let v = map_handler(s);
+        c_last.borrow_mut().get_or_insert_with(|| Clone::clone(&v));
+        v
+      })
+      .value_chain(move |s| {
+        s.filter(move |(_, v)| {
+          let mut last = last.borrow_mut();
+          let changed = last.as_ref() != Some(v);
+          if changed {
+            *last = Some(Clone::clone(v));
+          }
+          changed
+        })
+        .box_it()
+      })
+    }}
   });
   result_to_token_stream(res)
 }
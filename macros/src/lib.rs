@@ -248,6 +248,17 @@ pub fn part_writer(input: TokenStream) -> TokenStream {
   part_state::gen_part_wrier(input.into(), &mut DollarRefsCtx::top_level()).into()
 }
 
+/// The `bind` macro binds a field of a declared widget to a part of a writer in
+/// two ways, it accepts the same expressions as `part_writer`, such as
+/// `bind!(&mut writer.xxx)`, and creates a `TwoWay`.
+///
+/// The widget field follows the modifies of the writer, and the modifies of
+/// the field made by the widget are written back to the writer.
+#[proc_macro]
+pub fn bind(input: TokenStream) -> TokenStream {
+  part_state::gen_bind(input.into(), &mut DollarRefsCtx::top_level()).into()
+}
+
 /// The `split_writer` macro creates a split writer from a reference of a
 /// writer.
 ///
@@ -1,5 +1,5 @@
 use proc_macro2::{Ident, TokenStream};
-use quote::{ToTokens, quote_spanned};
+use quote::{ToTokens, quote, quote_spanned};
 use syn::{
   AngleBracketedGenericArguments, Expr, Member, Result, Token, parenthesized,
   parse::{Parse, ParseStream},
@@ -30,6 +30,11 @@ pub fn gen_part_wrier(input: TokenStream, refs_ctx: &mut DollarRefsCtx) -> Token
   }
 }
 
+pub fn gen_bind(input: TokenStream, refs_ctx: &mut DollarRefsCtx) -> TokenStream {
+  let part_writer = gen_part_wrier(input, refs_ctx);
+  quote! { TwoWay::new(#part_writer) }
+}
+
 pub fn gen_split_wrier(input: TokenStream, refs_ctx: &mut DollarRefsCtx) -> TokenStream {
   match syn::parse2::<PartState>(input) {
     Ok(part) => {
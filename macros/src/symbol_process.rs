@@ -25,6 +25,7 @@ pub const KW_DISTINCT_PIPE: &str = "distinct_pipe";
 pub const KW_WATCH: &str = "watch";
 pub const KW_PART_WRITER: &str = "part_writer";
 pub const KW_PART_READER: &str = "part_reader";
+pub const KW_BIND: &str = "bind";
 pub const KW_FN_WIDGET: &str = "fn_widget";
 
 pub use tokens_pre_process::*;
@@ -294,6 +295,9 @@ impl Fold for DollarRefsCtx {
     } else if mac.path.is_ident(KW_PART_WRITER) {
       mac.tokens = crate::part_state::gen_part_wrier(mac.tokens, self);
       mark_macro_expanded(&mut mac);
+    } else if mac.path.is_ident(KW_BIND) {
+      mac.tokens = crate::part_state::gen_bind(mac.tokens, self);
+      mark_macro_expanded(&mut mac);
     } else if mac.path.is_ident(KW_PART_READER) {
       mac.tokens = crate::part_state::gen_part_reader(mac.tokens, self);
       mark_macro_expanded(&mut mac);
@@ -67,11 +67,34 @@ pub static BUILTIN_INFOS: phf::Map<&'static str, BuiltinMember> = phf_map! {
   "set_auto_focus" => builtin_member!{"MixFlags", Method, "mix_flags"},
   "tab_index" => builtin_member!{"MixFlags", Method, "mix_flags"},
   "set_tab_index" => builtin_member!{"MixFlags", Method, "mix_flags"},
+  "is_sensitive" => builtin_member!{"MixFlags", Method, "mix_flags"},
+  "set_sensitive" => builtin_member!{"MixFlags", Method, "mix_flags"},
+  "hover_focus" => builtin_member!{"MixFlags", Method, "mix_flags"},
+  "set_hover_focus" => builtin_member!{"MixFlags", Method, "mix_flags"},
   // MixBuiltin
   "on_event" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
   "on_mounted" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
   "on_disposed" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
   "on_performed_layout" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_window_resized" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_accessibility_action" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_drag_start" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_drag_end" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_drag_enter" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_drag_over" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_drag_leave" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_drop" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_file_hover" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_file_hover_cancel" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_file_drop" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_touch_start" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_touch_move" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_touch_end" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_touch_cancel" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_pan" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_pinch" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_long_press" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_context_menu" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
   "on_pointer_down" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
   "on_pointer_down_capture" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
   "on_pointer_up" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
@@ -131,6 +154,7 @@ pub static BUILTIN_INFOS: phf::Map<&'static str, BuiltinMember> = phf_map! {
   "letter_space" => builtin_member! { "TextStyleWidget", Method, "text_style" },
   "text_line_height" => builtin_member! { "TextStyleWidget", Method, "text_style" },
   "text_overflow" => builtin_member! { "TextStyleWidget", Method, "text_style" },
+  "text_max_lines" => builtin_member! { "TextStyleWidget", Method, "text_style" },
   // Padding
   "padding" => builtin_member!{"Padding", Field, "padding"},
   // LayoutBox
@@ -158,6 +182,11 @@ pub static BUILTIN_INFOS: phf::Map<&'static str, BuiltinMember> = phf_map! {
   "clamp" => builtin_member!{"ConstrainedBox", Field, "constrained_box"},
   // TransformWidget
   "transform" => builtin_member!{"TransformWidget", Field, "transform"},
+  "transform_origin" => builtin_member!{"TransformWidget", Field, "transform"},
+  "rotate" => builtin_member!{"TransformWidget", Field, "transform"},
+  "scale" => builtin_member!{"TransformWidget", Field, "transform"},
+  "translate_x" => builtin_member!{"TransformWidget", Field, "transform"},
+  "translate_y" => builtin_member!{"TransformWidget", Field, "transform"},
   // HAlignWidget
   "h_align" => builtin_member!{"HAlignWidget", Field, "h_align"},
   // VAlignWidget
@@ -166,12 +195,20 @@ pub static BUILTIN_INFOS: phf::Map<&'static str, BuiltinMember> = phf_map! {
   "anchor" => builtin_member!{"RelativeAnchor", Field, "relative_anchor"},
   // Visibility
   "visible" => builtin_member!{"Visibility", Field, "visibility"},
+  "maintain_size" => builtin_member!{"Visibility", Field, "visibility"},
+  "maintain_interactivity" => builtin_member!{"Visibility", Field, "visibility"},
   // Opacity
   "opacity" => builtin_member!{"Opacity", Field, "opacity"},
+  "opacity_hit_threshold" => builtin_member!{"Opacity", Field, "opacity"},
+  "opacity_group" => builtin_member!{"Opacity", Field, "opacity"},
   // KeepAlive
   "keep_alive" => builtin_member!{"KeepAlive", Field, "keep_alive"},
   // Tooltips
   "tooltips" => builtin_member!{"Tooltips", Field, "tooltips"},
+  // Semantics
+  "semantics_label" => builtin_member!{"Semantics", Field, "semantics"},
+  "semantics_role" => builtin_member!{"Semantics", Field, "semantics"},
+  "merge_semantics" => builtin_member!{"Semantics", Field, "semantics"},
   // TrackWidgetId
   "track_id" => builtin_member!{"TrackWidgetId", Method, "track_id"},
   // ClipBoundary
@@ -0,0 +1,227 @@
+use std::{
+  any::Any,
+  sync::{
+    Arc, Mutex, Weak,
+    atomic::{AtomicUsize, Ordering},
+  },
+};
+
+use ribir_geom::DeviceSize;
+use serde::{Deserialize, Serialize};
+
+use crate::{PixelImage, image::ColorFormat};
+
+/// A texture whose frames are provided from outside of the framework, such as
+/// the frames of a video decoder or a camera.
+///
+/// The producer pushes the frames from any thread, and the backend takes the
+/// latest frame when it draws the texture, a frame not drawn yet is replaced
+/// by the next one. The frames are kept in a texture of the backend for the
+/// handle, instead of being copied through the texture atlas of the images.
+///
+/// The texture of the backend is released after all the handles are dropped.
+#[derive(Clone)]
+pub struct ExternalTexture(Arc<ExternalTextureInner>);
+
+/// A weak handle of an [`ExternalTexture`], the backend holds it to know when
+/// to release the texture.
+#[derive(Clone)]
+pub struct WeakExternalTexture(Weak<ExternalTextureInner>);
+
+/// A frame pushed to an [`ExternalTexture`].
+pub enum ExternalFrame {
+  /// The pixels of the frame in the `Rgba8` format, they're written to the
+  /// texture of the backend directly.
+  Pixels(PixelImage),
+  /// A texture of the backend, for example, a `wgpu::Texture` created by the
+  /// device of the `WgpuImpl`. It's drawn without any copy, the backend ignores
+  /// the texture it doesn't know.
+  Native { texture: Box<dyn Any + Send>, size: DeviceSize },
+}
+
+struct ExternalTextureInner {
+  id: usize,
+  state: Mutex<FrameState>,
+}
+
+struct FrameState {
+  size: DeviceSize,
+  pending: Option<ExternalFrame>,
+  next_listener: usize,
+  listeners: Vec<(usize, Arc<dyn Fn() + Send + Sync>)>,
+}
+
+impl ExternalTexture {
+  pub fn new() -> Self {
+    static ID: AtomicUsize = AtomicUsize::new(0);
+    let state =
+      FrameState { size: DeviceSize::zero(), pending: None, next_listener: 0, listeners: vec![] };
+    let id = ID.fetch_add(1, Ordering::Relaxed);
+    Self(Arc::new(ExternalTextureInner { id, state: Mutex::new(state) }))
+  }
+
+  /// The unique id of the texture.
+  pub fn id(&self) -> usize { self.0.id }
+
+  /// The size of the latest frame pushed.
+  pub fn size(&self) -> DeviceSize { self.0.state.lock().unwrap().size }
+
+  /// Push the pixels of the next frame.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the format of the image is not `Rgba8`.
+  pub fn push_pixels(&self, img: PixelImage) {
+    assert_eq!(img.color_format(), ColorFormat::Rgba8, "An external frame must be `Rgba8`.");
+    self.push_frame(ExternalFrame::Pixels(img));
+  }
+
+  /// Push a texture of the backend as the next frame, the `size` is the size
+  /// of the texture in pixels.
+  pub fn push_native(&self, texture: impl Any + Send, size: DeviceSize) {
+    self.push_frame(ExternalFrame::Native { texture: Box::new(texture), size });
+  }
+
+  /// Push the next frame, it replaces the frame not taken yet.
+  pub fn push_frame(&self, frame: ExternalFrame) {
+    let listeners = {
+      let mut state = self.0.state.lock().unwrap();
+      state.size = frame.size();
+      state.pending = Some(frame);
+      state
+        .listeners
+        .iter()
+        .map(|(_, l)| l.clone())
+        .collect::<Vec<_>>()
+    };
+    // Call the listeners without the lock, so they can access the texture.
+    listeners.iter().for_each(|l| l());
+  }
+
+  /// Take the frame pushed since the last time it's taken, the backend calls it
+  /// when it draws the texture.
+  pub fn take_frame(&self) -> Option<ExternalFrame> { self.0.state.lock().unwrap().pending.take() }
+
+  /// Add a function called after a frame is pushed, it's called on the thread
+  /// pushing the frame. The widgets showing the texture use it to repaint.
+  ///
+  /// Return the id of the listener to remove it by
+  /// [`ExternalTexture::remove_frame_listener`].
+  pub fn add_frame_listener(&self, listener: impl Fn() + Send + Sync + 'static) -> usize {
+    let mut state = self.0.state.lock().unwrap();
+    let id = state.next_listener;
+    state.next_listener += 1;
+    state.listeners.push((id, Arc::new(listener)));
+    id
+  }
+
+  /// Remove the listener added by [`ExternalTexture::add_frame_listener`].
+  pub fn remove_frame_listener(&self, id: usize) {
+    let mut state = self.0.state.lock().unwrap();
+    state.listeners.retain(|(l, _)| *l != id);
+  }
+
+  pub fn downgrade(&self) -> WeakExternalTexture { WeakExternalTexture(Arc::downgrade(&self.0)) }
+}
+
+impl Default for ExternalTexture {
+  fn default() -> Self { Self::new() }
+}
+
+impl WeakExternalTexture {
+  pub fn upgrade(&self) -> Option<ExternalTexture> { self.0.upgrade().map(ExternalTexture) }
+}
+
+impl ExternalFrame {
+  pub fn size(&self) -> DeviceSize {
+    match self {
+      ExternalFrame::Pixels(img) => img.size(),
+      ExternalFrame::Native { size, .. } => *size,
+    }
+  }
+}
+
+impl PartialEq for ExternalTexture {
+  fn eq(&self, other: &Self) -> bool { Arc::ptr_eq(&self.0, &other.0) }
+}
+
+impl std::fmt::Debug for ExternalTexture {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("ExternalTexture")
+      .field("id", &self.id())
+      .field("size", &self.size())
+      .finish()
+  }
+}
+
+/// Only the size of the texture is serialized, the frames are provided at
+/// runtime, so a deserialized texture has no frame to draw.
+impl Serialize for ExternalTexture {
+  fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    self.size().serialize(serializer)
+  }
+}
+
+impl<'de> Deserialize<'de> for ExternalTexture {
+  fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    let size = DeviceSize::deserialize(deserializer)?;
+    let texture = ExternalTexture::new();
+    texture.0.state.lock().unwrap().size = size;
+    Ok(texture)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn keep_latest_frame() {
+    let texture = ExternalTexture::new();
+    let pushed = Arc::new(AtomicUsize::new(0));
+    let c_pushed = pushed.clone();
+    texture.add_frame_listener(move || {
+      c_pushed.fetch_add(1, Ordering::Relaxed);
+    });
+
+    let frame = |width: usize| {
+      PixelImage::new(vec![0; width * 4].into(), width as u32, 1, ColorFormat::Rgba8)
+    };
+    texture.push_pixels(frame(1));
+    texture.push_pixels(frame(2));
+    assert_eq!(pushed.load(Ordering::Relaxed), 2);
+    assert_eq!(texture.size(), DeviceSize::new(2, 1));
+
+    let Some(ExternalFrame::Pixels(img)) = texture.take_frame() else { panic!("expect pixels") };
+    assert_eq!(img.width(), 2);
+    assert!(texture.take_frame().is_none());
+
+    let weak = texture.downgrade();
+    assert!(weak.upgrade().is_some());
+    drop(texture);
+    assert!(weak.upgrade().is_none());
+  }
+
+  #[test]
+  fn multi_listeners() {
+    let texture = ExternalTexture::new();
+    let pushed = Arc::new(AtomicUsize::new(0));
+    let (c_pushed, c_texture) = (pushed.clone(), texture.clone());
+    // The listener can access the texture, the lock isn't held when it's called.
+    let first = texture.add_frame_listener(move || {
+      c_pushed.fetch_add(c_texture.size().width as usize, Ordering::Relaxed);
+    });
+    let c_pushed = pushed.clone();
+    texture.add_frame_listener(move || {
+      c_pushed.fetch_add(10, Ordering::Relaxed);
+    });
+
+    let frame = || PixelImage::new(vec![0; 4].into(), 1, 1, ColorFormat::Rgba8);
+    texture.push_pixels(frame());
+    assert_eq!(pushed.load(Ordering::Relaxed), 11);
+
+    texture.remove_frame_listener(first);
+    texture.push_pixels(frame());
+    assert_eq!(pushed.load(Ordering::Relaxed), 21);
+  }
+}
@@ -1,5 +1,6 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, time::Duration};
 
+use ribir_algo::Resource;
 use ribir_geom::DeviceSize;
 use serde::{Deserialize, Serialize};
 
@@ -43,6 +44,16 @@ impl PixelImage {
     PixelImage::new(img.into_raw().into(), width, height, ColorFormat::Rgba8)
   }
 
+  /// Decode an encoded image, the format is guessed from the bytes. Only the
+  /// formats enabled by the features are supported.
+  #[cfg(feature = "png")]
+  pub fn decode(bytes: &[u8]) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+    let img = ::image::load_from_memory(bytes)?.to_rgba8();
+    let width = img.width();
+    let height = img.height();
+    Ok(PixelImage::new(img.into_raw().into(), width, height, ColorFormat::Rgba8))
+  }
+
   #[cfg(feature = "png")]
   pub fn write_as_png(
     &self, w: &mut impl std::io::Write,
@@ -76,3 +87,118 @@ impl std::fmt::Debug for PixelImage {
       .finish()
   }
 }
+
+/// A frame of an animated image.
+#[derive(Debug, Clone)]
+pub struct ImageFrame {
+  pub image: Resource<PixelImage>,
+  /// How long the frame shows before the next one.
+  pub delay: Duration,
+}
+
+/// The frames of an animated image like a GIF or an APNG, every frame is a
+/// full image of the same size.
+#[derive(Debug, Clone)]
+pub struct ImageFrames(Box<[ImageFrame]>);
+
+impl ImageFrames {
+  /// The delay of the frames shorter than this is treated as the default delay,
+  /// as the browsers do.
+  #[cfg(feature = "png")]
+  const MIN_DELAY: Duration = Duration::from_millis(10);
+  #[cfg(feature = "png")]
+  const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+
+  /// # Panics
+  ///
+  /// Panics if there is no frame.
+  pub fn new(frames: Vec<ImageFrame>) -> Self {
+    assert!(!frames.is_empty(), "An animated image needs at least one frame.");
+    ImageFrames(frames.into_boxed_slice())
+  }
+
+  /// Decode the frames of a GIF or an APNG, other images are decoded as a
+  /// single frame.
+  #[cfg(feature = "png")]
+  pub fn decode(bytes: &[u8]) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+    use ::image::{
+      AnimationDecoder, ImageFormat,
+      codecs::{gif::GifDecoder, png::PngDecoder},
+    };
+
+    let frames = match ::image::guess_format(bytes)? {
+      ImageFormat::Gif => GifDecoder::new(std::io::Cursor::new(bytes))?
+        .into_frames()
+        .collect_frames()?,
+      ImageFormat::Png => {
+        let decoder = PngDecoder::new(std::io::Cursor::new(bytes))?;
+        if decoder.is_apng() { decoder.apng().into_frames().collect_frames()? } else { vec![] }
+      }
+      _ => vec![],
+    };
+    if frames.is_empty() {
+      let image = Resource::new(PixelImage::decode(bytes)?);
+      return Ok(ImageFrames::new(vec![ImageFrame { image, delay: Duration::ZERO }]));
+    }
+
+    let frames = frames
+      .into_iter()
+      .map(|frame| {
+        let (numer, denom) = frame.delay().numer_denom_ms();
+        let mut delay = Duration::from_micros(numer as u64 * 1000 / denom.max(1) as u64);
+        if delay < Self::MIN_DELAY {
+          delay = Self::DEFAULT_DELAY;
+        }
+        let img = frame.into_buffer();
+        let (width, height) = img.dimensions();
+        let image = PixelImage::new(img.into_raw().into(), width, height, ColorFormat::Rgba8);
+        ImageFrame { image: Resource::new(image), delay }
+      })
+      .collect();
+    Ok(ImageFrames::new(frames))
+  }
+
+  pub fn frames(&self) -> &[ImageFrame] { &self.0 }
+
+  pub fn len(&self) -> usize { self.0.len() }
+
+  /// Always `false`, an animated image has at least one frame.
+  pub fn is_empty(&self) -> bool { self.0.is_empty() }
+
+  /// Whether there is more than one frame to play.
+  pub fn is_animated(&self) -> bool { self.0.len() > 1 }
+
+  /// The size of the frames.
+  pub fn size(&self) -> DeviceSize { self.0[0].image.size() }
+
+  /// The time to play all the frames once.
+  pub fn duration(&self) -> Duration { self.0.iter().map(|f| f.delay).sum() }
+}
+
+#[cfg(all(test, feature = "png"))]
+mod tests {
+  use ::image::{Delay, Frame, RgbaImage, codecs::gif::GifEncoder};
+
+  use super::*;
+
+  #[test]
+  fn decode_gif_frames() {
+    let mut bytes = vec![];
+    {
+      let mut encoder = GifEncoder::new(&mut bytes);
+      let frames = [([255, 0, 0, 255], 50), ([0, 255, 0, 255], 0)].map(|(color, ms)| {
+        let img = RgbaImage::from_pixel(2, 3, ::image::Rgba(color));
+        Frame::from_parts(img, 0, 0, Delay::from_numer_denom_ms(ms, 1))
+      });
+      encoder.encode_frames(frames).unwrap();
+    }
+
+    let frames = ImageFrames::decode(&bytes).unwrap();
+    assert!(frames.is_animated());
+    assert_eq!(frames.size(), DeviceSize::new(2, 3));
+    let delays: Vec<_> = frames.frames().iter().map(|f| f.delay).collect();
+    // The frame without delay shows for the default delay.
+    assert_eq!(delays, [Duration::from_millis(50), Duration::from_millis(100)]);
+    assert_eq!(&frames.frames()[1].image.pixel_bytes()[..4], &[0, 255, 0, 255]);
+  }
+}
@@ -17,6 +17,10 @@ pub mod image;
 mod style;
 pub use style::*;
 
-pub use crate::image::PixelImage;
+pub use crate::image::{ImageFrame, ImageFrames, PixelImage};
 mod svg;
 pub use svg::Svg;
+mod record;
+pub use record::PaintRecord;
+mod external_texture;
+pub use external_texture::*;
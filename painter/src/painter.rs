@@ -5,9 +5,9 @@ use ribir_geom::{Angle, DeviceRect, Point, Rect, Size, Transform, Vector};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-  Brush, Color, Glyph, PixelImage, Svg, VisualGlyphs,
+  Brush, Color, ExternalTexture, Glyph, PixelImage, Svg, VisualGlyphs,
   color::{LinearGradient, RadialGradient},
-  font_db::FontDB,
+  font_db::{Face, FontDB},
   path::*,
   path_builder::PathBuilder,
 };
@@ -48,6 +48,11 @@ pub trait PainterBackend {
   /// Paint `commands` to the `output` Texture.  This may be called more than
   /// once during a frame.
   ///
+  /// Only the `viewport` of the `output` is painted, if it doesn't cover the
+  /// whole `output`, only it is cleared with the surface color and the rest
+  /// keeps the content of the last frame. So the backend can redraw only the
+  /// changed area of a frame.
+  ///
   /// ## Undefined Behavior
   ///
   /// You should guarantee the output be same one in the same frame, otherwise
@@ -145,8 +150,52 @@ pub enum PaintCommand {
     bounds: Rect,
     cmds: Resource<Box<[PaintCommand]>>,
   },
+  /// A bundle of paint commands that is blurred as a whole by a gaussian blur.
+  Blur {
+    transform: Transform,
+    opacity: f32,
+    /// the bounds of the bundle commands before blurred, the blurred content
+    /// spreads out of it by [`blur_spread`].
+    bounds: Rect,
+    /// The standard deviation of the gaussian blur.
+    radius: f32,
+    cmds: Resource<Box<[PaintCommand]>>,
+  },
+  /// Blur the content painted before in the bounds, it's the backdrop of the
+  /// commands painted later, such as a frosted glass panel.
+  BackdropBlur {
+    transform: Transform,
+    opacity: f32,
+    bounds: Rect,
+    /// The standard deviation of the gaussian blur.
+    radius: f32,
+  },
+  /// Paint the bounds by a custom WGSL fragment shader, see
+  /// [`Painter::draw_shader`].
+  Shader {
+    transform: Transform,
+    opacity: f32,
+    bounds: Rect,
+    /// The WGSL source of the fragment shader, the backend caches the pipeline
+    /// by it.
+    source: Resource<String>,
+    /// The bytes of the uniform bound at `@group(0) @binding(0)`.
+    uniforms: Box<[u8]>,
+  },
+  /// Paint the latest frame of an external texture scaled to the bounds, see
+  /// [`Painter::draw_external_texture`].
+  ExternalTexture {
+    transform: Transform,
+    opacity: f32,
+    bounds: Rect,
+    texture: ExternalTexture,
+  },
 }
 
+/// The distance that a gaussian blur with the standard deviation `radius`
+/// spreads the content, the pixels farther than it have an invisible weight.
+pub fn blur_spread(radius: f32) -> f32 { radius * 3. }
+
 #[derive(Clone)]
 struct PainterState {
   /// The line width use to stroke path.
@@ -217,6 +266,41 @@ impl Painter {
       .map_or_else(Rect::zero, |t| t.outer_transformed_rect(&s.bounds))
   }
 
+  /// The count of the commands painted since the last reset.
+  #[inline]
+  pub fn command_count(&self) -> usize { self.commands.len() }
+
+  /// Return the union of the paint bounds of the commands painted after the
+  /// `start`-th one, or `None` if there is no visible command.
+  pub fn commands_bounds(&self, start: usize) -> Option<Rect> {
+    self
+      .commands
+      .get(start..)?
+      .iter()
+      .filter_map(|cmd| match cmd {
+        PaintCommand::Path(PathCommand { paint_bounds, .. }) => Some(*paint_bounds),
+        PaintCommand::Bundle { transform, bounds, .. }
+        | PaintCommand::BackdropBlur { transform, bounds, .. }
+        | PaintCommand::Shader { transform, bounds, .. }
+        | PaintCommand::ExternalTexture { transform, bounds, .. } => {
+          Some(transform.outer_transformed_rect(bounds))
+        }
+        PaintCommand::Blur { transform, bounds, radius, .. } => {
+          let spread = blur_spread(*radius);
+          Some(transform.outer_transformed_rect(&bounds.inflate(spread, spread)))
+        }
+        PaintCommand::PopClip => None,
+      })
+      .reduce(|a, b| a.union(&b))
+  }
+
+  /// Take the commands painted after the `start`-th one out of the painter.
+  pub fn take_commands(&mut self, start: usize) -> Vec<PaintCommand> {
+    self
+      .commands
+      .split_off(start.min(self.commands.len()))
+  }
+
   #[inline]
   pub fn finish(&mut self) -> PainterResult {
     self.fill_all_pop_clips();
@@ -239,6 +323,21 @@ impl Painter {
     self
   }
 
+  /// Saves the state and starts to paint a layer. The commands painted after it
+  /// are in the axis of the layer, with full opacity and not clipped by the
+  /// visible boundary of the painter, until the state is restored.
+  ///
+  /// Use [`Painter::take_commands`] to take the commands of the layer out and
+  /// draw them as a bundle.
+  pub fn save_layer(&mut self) -> &mut Self {
+    self.save();
+    let s = self.current_state_mut();
+    s.transform = Transform::identity();
+    s.opacity = 1.;
+    s.bounds = Rect::new(Point::splat(f32::MIN / 2.), Size::splat(f32::MAX));
+    self
+  }
+
   /// Restores the most recently saved canvas state by popping the top entry in
   /// the drawing state stack. If there is no saved state, this method does
   /// nothing.
@@ -548,6 +647,80 @@ impl Painter {
     self
   }
 
+  /// Draws a bundle of paint commands blurred as a whole by a gaussian blur,
+  /// the `radius` is the standard deviation of the blur. The `bounds` and the
+  /// `cmds` are the same as [`Painter::draw_bundle_commands`].
+  pub fn draw_blur_commands(
+    &mut self, bounds: Rect, radius: f32, cmds: Resource<Box<[PaintCommand]>>,
+  ) -> &mut Self {
+    invisible_return!(self);
+    let transform = *self.transform();
+    let opacity = self.alpha();
+    let cmd = PaintCommand::Blur { transform, opacity, bounds, radius, cmds };
+    self.commands.push(cmd);
+    self
+  }
+
+  /// Blur the content that has been painted in the `rect` by a gaussian blur,
+  /// the `radius` is the standard deviation of the blur. The blurred area is
+  /// also limited by the current clip.
+  pub fn backdrop_blur(&mut self, rect: &Rect, radius: f32) -> &mut Self {
+    invisible_return!(self);
+    if !self.intersect_paint_bounds(rect) {
+      return self;
+    }
+    let transform = *self.transform();
+    let opacity = self.alpha();
+    let cmd = PaintCommand::BackdropBlur { transform, opacity, bounds: *rect, radius };
+    self.commands.push(cmd);
+    self
+  }
+
+  /// Paint the `rect` by a custom WGSL fragment shader, the `uniforms` are the
+  /// bytes of the uniform it declares at `@group(0) @binding(0)`.
+  ///
+  /// The backend provides the vertex stage, the `source` must define the
+  /// fragment entry `fs_main` that takes the `VertexOutput`:
+  ///
+  /// ```wgsl
+  /// struct VertexOutput {
+  ///   @builtin(position) pos: vec4<f32>,
+  ///   // The position in the rect, from (0, 0) at the top left to (1, 1).
+  ///   @location(0) uv: vec2<f32>,
+  ///   // The size of the rect in device pixels.
+  ///   @location(1) size: vec2<f32>,
+  /// }
+  /// ```
+  ///
+  /// The returned color isn't premultiplied by its alpha.
+  pub fn draw_shader(
+    &mut self, rect: &Rect, source: Resource<String>, uniforms: Box<[u8]>,
+  ) -> &mut Self {
+    invisible_return!(self);
+    if !self.intersect_paint_bounds(rect) {
+      return self;
+    }
+    let transform = *self.transform();
+    let opacity = self.alpha();
+    let cmd = PaintCommand::Shader { transform, opacity, bounds: *rect, source, uniforms };
+    self.commands.push(cmd);
+    self
+  }
+
+  /// Paint the latest frame of the external `texture` scaled to the `rect`,
+  /// nothing is painted before the first frame is pushed.
+  pub fn draw_external_texture(&mut self, rect: &Rect, texture: ExternalTexture) -> &mut Self {
+    invisible_return!(self);
+    if !self.intersect_paint_bounds(rect) {
+      return self;
+    }
+    let transform = *self.transform();
+    let opacity = self.alpha();
+    let cmd = PaintCommand::ExternalTexture { transform, opacity, bounds: *rect, texture };
+    self.commands.push(cmd);
+    self
+  }
+
   pub fn draw_svg(&mut self, svg: &Svg) -> &mut Self {
     invisible_return!(self);
     let commands = svg.commands(self.fill_brush(), self.stroke_brush());
@@ -578,6 +751,40 @@ impl Painter {
             bounds,
             cmds,
           },
+          PaintCommand::Blur { transform: b_ts, opacity, bounds, radius, cmds } => {
+            PaintCommand::Blur {
+              transform: transform.then(&b_ts),
+              opacity: alpha * opacity,
+              bounds,
+              radius,
+              cmds,
+            }
+          }
+          PaintCommand::BackdropBlur { transform: b_ts, opacity, bounds, radius } => {
+            PaintCommand::BackdropBlur {
+              transform: transform.then(&b_ts),
+              opacity: alpha * opacity,
+              bounds,
+              radius,
+            }
+          }
+          PaintCommand::Shader { transform: b_ts, opacity, bounds, source, uniforms } => {
+            PaintCommand::Shader {
+              transform: transform.then(&b_ts),
+              opacity: alpha * opacity,
+              bounds,
+              source,
+              uniforms,
+            }
+          }
+          PaintCommand::ExternalTexture { transform: b_ts, opacity, bounds, texture } => {
+            PaintCommand::ExternalTexture {
+              transform: transform.then(&b_ts),
+              opacity: alpha * opacity,
+              bounds,
+              texture,
+            }
+          }
         };
         self.commands.push(cmd);
       }
@@ -630,13 +837,34 @@ impl Painter {
     let matrix = *self.transform();
 
     let bounds = g.bounds();
-    if let Some(path) = face.outline_glyph(g.glyph_id) {
+    if face.has_color_glyphs() && self.draw_color_glyph(face, g, font_size) {
+      // The color glyph is drawn.
+    } else if let Some(path) = face.outline_glyph(g.glyph_id) {
       let scale = font_size / unit;
       self
         .translate(bounds.min_x(), bounds.min_y())
         .scale(scale, -scale)
         .translate(0., -unit)
         .draw_path(path.into());
+    }
+
+    self.set_transform(matrix);
+
+    self
+  }
+
+  /// Draw the glyph in the color formats of the face, the `COLR` layers are
+  /// drawn as paths, and the bitmaps are drawn as images, so they keep their
+  /// colors. Return false if the glyph has no color format.
+  fn draw_color_glyph(&mut self, face: &Face, g: &Glyph, font_size: f32) -> bool {
+    let unit = face.units_per_em() as f32;
+    let bounds = g.bounds();
+    if let Some(svg) = face.glyph_colr_image(g.glyph_id) {
+      let scale = font_size / unit;
+      self
+        .translate(bounds.min_x(), bounds.min_y())
+        .scale(scale, scale)
+        .draw_svg(&svg);
     } else if let Some(svg) = face.glyph_svg_image(g.glyph_id) {
       let grid_scale = face
         .vertical_height()
@@ -662,11 +890,10 @@ impl Painter {
         .translate(x_offset, y_offset)
         .scale(scale, scale)
         .draw_img(img, &Rect::from_size(Size::new(m_width, m_height)), &None);
+    } else {
+      return false;
     }
-
-    self.set_transform(matrix);
-
-    self
+    true
   }
 
   /// draw the text glyphs within the box_rect
@@ -684,7 +911,15 @@ impl Painter {
     self.translate(visual_rect.origin.x, visual_rect.origin.y);
 
     for g in glyphs {
-      self.draw_glyph(&g, visual_glyphs.font_size(), font_db);
+      let alpha = visual_glyphs.fade_alpha(&g);
+      if alpha < 1. {
+        self.save();
+        self.apply_alpha(alpha);
+        self.draw_glyph(&g, visual_glyphs.font_size(), font_db);
+        self.restore();
+      } else {
+        self.draw_glyph(&g, visual_glyphs.font_size(), font_db);
+      }
     }
 
     self
@@ -941,6 +1176,72 @@ mod test {
     assert_eq!(&Transform::new(1., 0., 0., 1., 0., 0.), painter.transform());
   }
 
+  #[test]
+  fn layer_ignore_outside_state() {
+    let mut painter = painter();
+    painter
+      .translate(600., 600.)
+      .apply_alpha(0.5)
+      .save_layer()
+      .rect(&rect(0., 0., 10., 10.))
+      .fill()
+      .restore();
+
+    // The rect is out of the painter, but is painted in the layer.
+    let cmds = painter.take_commands(0);
+    assert_eq!(cmds.len(), 1);
+    assert!(matches!(&cmds[0], PaintCommand::Path(PathCommand {
+      paint_bounds,
+      action: PaintPathAction::Paint { brush: CommandBrush::Color(c), .. },
+      ..
+    }) if paint_bounds == &rect(0., 0., 10., 10.) && c.alpha == Color::GRAY.alpha));
+    assert_eq!(painter.alpha(), 0.5);
+  }
+
+  #[test]
+  fn blur_spread_bounds() {
+    let mut painter = painter();
+    let cmds = Resource::new(Box::new([]) as Box<[PaintCommand]>);
+    painter
+      .translate(10., 10.)
+      .draw_blur_commands(rect(0., 0., 20., 20.), 2., cmds)
+      .backdrop_blur(&rect(0., 0., 20., 20.), 2.);
+
+    assert_eq!(painter.commands_bounds(0), Some(rect(4., 4., 32., 32.)));
+    assert_eq!(painter.commands_bounds(1), Some(rect(10., 10., 20., 20.)));
+  }
+
+  #[test]
+  fn keep_round_rect_shape() {
+    let radius = Radius::all(4.);
+    let mut painter = painter();
+    painter
+      .rect_round(&rect(0., 0., 10., 10.), &radius)
+      .fill()
+      .circle(Point::new(5., 5.), 5.)
+      .stroke()
+      .rect(&rect(0., 0., 10., 10.))
+      .rect(&rect(20., 0., 10., 10.))
+      .fill();
+
+    let shapes: Vec<_> = painter
+      .commands
+      .iter()
+      .map(|cmd| match cmd {
+        PaintCommand::Path(PathCommand { path, .. }) => path.round_rect().copied(),
+        _ => unreachable!(),
+      })
+      .collect();
+    assert_eq!(
+      shapes,
+      [
+        Some(RoundRect { rect: rect(0., 0., 10., 10.), radius }),
+        Some(RoundRect { rect: rect(0., 0., 10., 10.), radius: Radius::all(5.) }),
+        None,
+      ]
+    );
+  }
+
   #[test]
   fn fix_clip_pop_without_restore() {
     let mut painter = painter();
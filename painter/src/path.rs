@@ -16,6 +16,8 @@ pub struct Path {
   pub(crate) lyon_path: LyonPath,
   // the bounds of the path.
   bounds: Rect,
+  /// The rounded rectangle the path is built from, if it's the only sub-path.
+  round_rect: Option<RoundRect>,
 }
 
 /// Stroke properties.
@@ -82,7 +84,7 @@ pub enum PathSegment {
 }
 
 /// The radius of each corner of a rounded rectangle.
-#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default)]
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
 pub struct Radius {
   pub top_left: f32,
   pub top_right: f32,
@@ -90,6 +92,15 @@ pub struct Radius {
   pub bottom_right: f32,
 }
 
+/// A rounded rectangle, the rectangles and the circles are rounded rectangles
+/// too. A path only built from it knows its shape, so a backend can draw it by
+/// the signed distance field instead of tessellating it.
+#[derive(Copy, Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct RoundRect {
+  pub rect: Rect,
+  pub radius: Radius,
+}
+
 #[cfg(feature = "tessellation")]
 pub type VertexBuffers<V> = lyon_tessellation::VertexBuffers<Vertex<V>, u32>;
 
@@ -108,7 +119,14 @@ pub struct PathSampler {
 }
 
 impl Path {
-  pub(crate) fn new(lyon_path: LyonPath, bounds: Rect) -> Self { Self { lyon_path, bounds } }
+  pub(crate) fn new(lyon_path: LyonPath, bounds: Rect) -> Self {
+    Self { lyon_path, bounds, round_rect: None }
+  }
+
+  pub(crate) fn with_round_rect(mut self, round_rect: Option<RoundRect>) -> Self {
+    self.round_rect = round_rect;
+    self
+  }
 
   #[inline]
   pub fn builder() -> PathBuilder { PathBuilder::default() }
@@ -145,6 +163,11 @@ impl Path {
     builder.build()
   }
 
+  /// The rounded rectangle the path is built from, `None` if the path has any
+  /// other sub-path.
+  #[inline]
+  pub fn round_rect(&self) -> Option<&RoundRect> { self.round_rect.as_ref() }
+
   /// Returns a transformed path in place.
   ///
   /// Some points may become NaN/inf therefore this method can fail.
@@ -332,7 +355,7 @@ impl From<LyonPath> for Path {
     let bounds = lyon_algorithms::aabb::bounding_box(&lyon_path)
       .to_rect()
       .cast_unit();
-    Path { lyon_path, bounds }
+    Path::new(lyon_path, bounds)
   }
 }
 
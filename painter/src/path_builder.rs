@@ -4,13 +4,23 @@ use lyon_algorithms::path::{
   geom::{Arc, LineSegment},
   path::Builder as LyonBuilder,
 };
-use ribir_geom::{Angle, Point, Rect, Vector};
+use ribir_geom::{Angle, Point, Rect, Size, Vector};
 
-use crate::{Path, Radius};
+use crate::{Path, Radius, RoundRect};
 
 #[derive(Default)]
 pub struct PathBuilder {
   pub(crate) lyon_builder: LyonBuilder,
+  shape: BuilderShape,
+}
+
+/// The shape of the sub-paths added to the builder.
+#[derive(Default)]
+enum BuilderShape {
+  #[default]
+  Empty,
+  RoundRect(RoundRect),
+  Complex,
 }
 
 impl PathBuilder {
@@ -18,6 +28,7 @@ impl PathBuilder {
   /// Call this method when you want to create a new path.
   #[inline]
   pub fn begin_path(&mut self, at: Point) -> &mut Self {
+    self.add_shape(None);
     self.lyon_builder.begin(at.to_untyped());
     self
   }
@@ -97,6 +108,7 @@ impl PathBuilder {
 
   #[inline]
   pub fn segment(&mut self, from: Point, to: Point) -> &mut Self {
+    self.add_shape(None);
     self
       .lyon_builder
       .add_line_segment(&LineSegment { from: from.to_untyped(), to: to.to_untyped() });
@@ -109,6 +121,7 @@ impl PathBuilder {
   /// No sub-path is in progress after the method is called.
   #[inline]
   pub fn ellipse(&mut self, center: Point, radius: Vector, rotation: f32) {
+    self.add_shape(None);
     self.lyon_builder.add_ellipse(
       center.to_untyped(),
       radius.to_untyped(),
@@ -123,6 +136,7 @@ impl PathBuilder {
   /// No sub-path is in progress after the method is called.
   #[inline]
   pub fn rect(&mut self, rect: &Rect) -> &mut Self {
+    self.add_shape(Some(RoundRect { rect: *rect, radius: Radius::all(0.) }));
     self
       .lyon_builder
       .add_rectangle(&rect.to_box2d().to_untyped(), Winding::Positive);
@@ -135,6 +149,8 @@ impl PathBuilder {
   /// No sub-path is in progress after the method is called.
   #[inline]
   pub fn circle(&mut self, center: Point, radius: f32) -> &mut Self {
+    let rect = Rect::new(center - Vector::splat(radius), Size::splat(radius * 2.));
+    self.add_shape(Some(RoundRect { rect, radius: Radius::all(radius) }));
     self
       .lyon_builder
       .add_circle(center.to_untyped(), radius, Winding::Positive);
@@ -144,6 +160,7 @@ impl PathBuilder {
   /// Creates a path for a rectangle by `rect` with `radius`.
   /// #[inline]
   pub fn rect_round(&mut self, rect: &Rect, radius: &Radius) -> &mut Self {
+    self.add_shape(Some(RoundRect { rect: *rect, radius: *radius }));
     let radius: &BorderRadii = unsafe { std::mem::transmute(radius) };
     self.lyon_builder.add_rounded_rectangle(
       &rect.to_box2d().cast_unit(),
@@ -157,7 +174,8 @@ impl PathBuilder {
   #[inline]
   pub fn build(self) -> Path {
     // todo: we can store an anti-aliasing flag for the path.
-    self.lyon_builder.build().into()
+    let round_rect = self.round_rect();
+    Path::from(self.lyon_builder.build()).with_round_rect(round_rect)
   }
 
   /// Construct a path from the current state of the builder, and use the given
@@ -165,7 +183,24 @@ impl PathBuilder {
   ///
   /// Caller must ensure that the bounds are correct.
   pub fn build_with_bounds(self, bounds: Rect) -> Path {
+    let round_rect = self.round_rect();
     let path = self.lyon_builder.build();
-    Path::new(path, bounds)
+    Path::new(path, bounds).with_round_rect(round_rect)
+  }
+
+  /// Record the shape of the sub-path to add, `None` if it's not a rounded
+  /// rectangle.
+  fn add_shape(&mut self, shape: Option<RoundRect>) {
+    self.shape = match (&self.shape, shape) {
+      (BuilderShape::Empty, Some(round_rect)) => BuilderShape::RoundRect(round_rect),
+      _ => BuilderShape::Complex,
+    };
+  }
+
+  fn round_rect(&self) -> Option<RoundRect> {
+    match self.shape {
+      BuilderShape::RoundRect(round_rect) => Some(round_rect),
+      _ => None,
+    }
   }
 }
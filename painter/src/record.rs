@@ -0,0 +1,97 @@
+use std::error::Error;
+
+use ribir_algo::Resource;
+use ribir_geom::Rect;
+use serde::{Deserialize, Serialize};
+
+use crate::{PaintCommand, Painter};
+
+/// The paint commands of a frame, it can be serialized to a compact binary
+/// format and replayed later, even on another machine or by another painter
+/// backend.
+///
+/// It's useful to inspect a remote UI, attach the last frame to a crash report,
+/// or compare the frames in tests without a renderer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaintRecord {
+  /// The viewport of the recorded frame.
+  pub viewport: Rect,
+  /// The paint commands of the frame.
+  pub commands: Resource<Box<[PaintCommand]>>,
+}
+
+impl PaintRecord {
+  const MAGIC: &'static [u8; 4] = b"RPRC";
+  const VERSION: u8 = 1;
+
+  pub fn new(viewport: Rect, commands: Box<[PaintCommand]>) -> Self {
+    Self { viewport, commands: Resource::new(commands) }
+  }
+
+  /// Serialize the record to bytes, the bytes start with a magic header and a
+  /// format version, followed by the CBOR encoded record.
+  pub fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut bytes = Self::MAGIC.to_vec();
+    bytes.push(Self::VERSION);
+    ciborium::into_writer(self, &mut bytes)?;
+    Ok(bytes)
+  }
+
+  /// Deserialize a record from the bytes that are serialized by
+  /// [`PaintRecord::to_bytes`].
+  pub fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+    let body = bytes
+      .strip_prefix(Self::MAGIC.as_slice())
+      .ok_or("The bytes are not a paint record.")?;
+    let (&version, body) = body
+      .split_first()
+      .ok_or("The paint record is truncated.")?;
+    if version != Self::VERSION {
+      return Err(format!("Not support the paint record of version {version}.").into());
+    }
+    Ok(ciborium::from_reader(body)?)
+  }
+
+  /// Replay the record by the `painter`, the commands are drawn as a bundle
+  /// with the current transform and opacity of the painter.
+  pub fn replay(&self, painter: &mut Painter) {
+    painter.draw_bundle_commands(self.viewport, self.commands.clone());
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_geom::Size;
+
+  use super::*;
+  use crate::Color;
+
+  #[test]
+  fn round_trip() {
+    let viewport = Rect::from_size(Size::new(100., 100.));
+    let mut painter = Painter::new(viewport);
+    painter
+      .set_fill_brush(Color::RED)
+      .rect(&Rect::from_size(Size::new(10., 20.)))
+      .fill();
+    let record = PaintRecord::new(viewport, painter.finish().to_vec().into_boxed_slice());
+
+    let bytes = record.to_bytes().unwrap();
+    let back = PaintRecord::from_bytes(&bytes).unwrap();
+    assert_eq!(back.viewport, viewport);
+    assert_eq!(format!("{:?}", back.commands), format!("{:?}", record.commands));
+
+    back.replay(&mut painter);
+    assert!(matches!(&*painter.finish(), [PaintCommand::Bundle { cmds, .. }] if cmds.len() == 1));
+  }
+
+  #[test]
+  fn reject_invalid_bytes() {
+    assert!(PaintRecord::from_bytes(b"invalid").is_err());
+    let mut bytes = PaintRecord::new(Rect::zero(), Box::new([]))
+      .to_bytes()
+      .unwrap();
+    bytes[4] = 0;
+    assert!(PaintRecord::from_bytes(&bytes).is_err());
+  }
+}
@@ -37,7 +37,7 @@ struct StaticSvg {
 impl Svg {
   // FIXME: This is a temporary workaround. Utilize the magic color for the SVG,
   // and replace it with the actual color when rendering.
-  const DYNAMIC_COLOR: Color = Color::from_u32(0x191B1901);
+  pub(crate) const DYNAMIC_COLOR: Color = Color::from_u32(0x191B1901);
   const DYNAMIC_COLOR_STR: &'static str = "#191B1901";
 
   /// Parse SVG from bytes.
@@ -67,15 +67,20 @@ impl Svg {
     paint_group(tree.root(), &mut painter);
 
     let paint_commands = painter.finish().to_owned().into_boxed_slice();
-    let (used_fill_fallback, used_stroke_fallback) = fallback_color_check(&paint_commands);
+    Ok(Self::from_commands(Size::new(size.width(), size.height()), paint_commands))
+  }
 
-    Ok(Svg {
-      size: Size::new(size.width(), size.height()),
-      commands: Resource::new(paint_commands),
-      inherited_fill: used_fill_fallback,
-      inherited_stroke: used_stroke_fallback,
+  /// Create a SVG from the paint commands, the commands painted in the
+  /// `DYNAMIC_COLOR` inherit the brush of the environment.
+  pub(crate) fn from_commands(size: Size, commands: Box<[PaintCommand]>) -> Self {
+    let (inherited_fill, inherited_stroke) = fallback_color_check(&commands);
+    Svg {
+      size,
+      commands: Resource::new(commands),
+      inherited_fill,
+      inherited_stroke,
       last: RefCell::new(None),
-    })
+    }
   }
 
   /// Parse SVG from a file.
@@ -274,8 +279,11 @@ fn fallback_color_check(cmds: &[PaintCommand]) -> (bool, bool) {
           }
         }
       }
-      PaintCommand::PopClip => {}
-      PaintCommand::Bundle { cmds, .. } => {
+      PaintCommand::PopClip
+      | PaintCommand::BackdropBlur { .. }
+      | PaintCommand::Shader { .. }
+      | PaintCommand::ExternalTexture { .. } => {}
+      PaintCommand::Bundle { cmds, .. } | PaintCommand::Blur { cmds, .. } => {
         let (f, s) = fallback_color_check(cmds);
         fill_fallback = f;
         stroke_fallback |= s;
@@ -308,6 +316,19 @@ fn brush_replace(cmds: &[PaintCommand], fill: &Brush, stroke: &Brush) -> Box<[Pa
 
         PaintCommand::Bundle { transform: *transform, opacity: *opacity, bounds: *bounds, cmds }
       }
+      PaintCommand::Blur { transform, opacity, bounds, radius, cmds } => {
+        let cmds = Resource::new(brush_replace(cmds, fill, stroke));
+        PaintCommand::Blur {
+          transform: *transform,
+          opacity: *opacity,
+          bounds: *bounds,
+          radius: *radius,
+          cmds,
+        }
+      }
+      c @ (PaintCommand::BackdropBlur { .. }
+      | PaintCommand::Shader { .. }
+      | PaintCommand::ExternalTexture { .. }) => c.clone(),
     })
     .collect()
 }
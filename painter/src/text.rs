@@ -16,7 +16,10 @@ pub mod text_reorder;
 pub mod typography;
 pub use text_reorder::TextReorder;
 mod typography_store;
-pub use typography_store::{TypographyStore, VisualGlyphs};
+pub use typography_store::{
+  LineMetrics, RichRun, ShapeMemo, TextMetrics, TypographyStore, VisualGlyphs,
+};
+mod colr_glyph;
 mod svg_glyph_cache;
 
 // Enum value descriptions are from the CSS spec.
@@ -83,17 +86,35 @@ pub struct TextStyle {
   pub line_height: f32,
   /// How to handle the visual overflow.
   pub overflow: TextOverflow,
+  /// The max number of lines to show, the text wraps when it's set, and the
+  /// lines after it are truncated by the `overflow`.
+  pub max_lines: Option<usize>,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
 pub enum TextOverflow {
+  /// The text overflows the bounds.
   #[default]
   Overflow,
+  /// The text wraps to the next line when it reaches the bounds.
   AutoWrap,
+  /// The text of a line out of the bounds is truncated, and an ellipsis is
+  /// placed at the end of the line.
+  Ellipsis,
+  /// The text of a line out of the bounds is clipped.
+  Clip,
+  /// The text of a line out of the bounds is clipped, and the end of the line
+  /// fades out.
+  Fade,
 }
 
 impl TextOverflow {
   fn is_auto_wrap(&self) -> bool { matches!(self, TextOverflow::AutoWrap) }
+
+  /// If the glyphs out of the bounds are removed.
+  fn is_truncate(&self) -> bool {
+    matches!(self, TextOverflow::Ellipsis | TextOverflow::Clip | TextOverflow::Fade)
+  }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -253,6 +274,7 @@ impl Default for TextStyle {
       letter_space: 0.,
       line_height: 16.,
       overflow: <_>::default(),
+      max_lines: None,
     }
   }
 }
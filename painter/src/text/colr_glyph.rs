@@ -0,0 +1,193 @@
+use ribir_algo::Resource;
+use ribir_geom::{Point, Rect, Size, Transform};
+use rustybuzz::ttf_parser::{
+  GlyphId, RgbaColor,
+  colr::{self, ClipBox, CompositeMode, GradientExtend, Paint},
+};
+
+use crate::{
+  Brush, Color, GradientStop, Painter, Path, SpreadMethod, Svg,
+  color::{LinearGradient, RadialGradient},
+  font_db::Face,
+};
+
+/// Paint the color glyph in the `COLR` table of the `face` to a SVG. The glyph
+/// is painted in font units with the y-axis pointing down, and its origin is
+/// at the top of the em box. The layers painted in the foreground color inherit
+/// the fill brush when the SVG is drawn.
+pub(crate) fn colr_glyph_svg(face: &Face, glyph_id: GlyphId) -> Option<Svg> {
+  if !face.is_color_glyph(glyph_id) {
+    return None;
+  }
+
+  let unit = face.units_per_em() as f32;
+  let mut painter = Painter::new(Rect::new(Point::splat(-unit * 4.), Size::splat(unit * 8.)));
+  painter.translate(0., unit).scale(1., -1.);
+  let Color { red, green, blue, alpha } = Svg::DYNAMIC_COLOR;
+  let foreground = RgbaColor::new(red, green, blue, alpha);
+  let mut colr = ColrPainter { face, painter: &mut painter, outline: None };
+  face.paint_color_glyph(glyph_id, 0, foreground, &mut colr)?;
+
+  let commands = painter.finish().to_owned().into_boxed_slice();
+  Some(Svg::from_commands(Size::splat(unit), commands))
+}
+
+/// Replay the paint operations of a `COLR` glyph on a painter.
+struct ColrPainter<'a> {
+  face: &'a Face,
+  painter: &'a mut Painter,
+  /// The outline to paint, and the transform it's outlined in.
+  outline: Option<(Resource<Path>, Transform)>,
+}
+
+impl ColrPainter<'_> {
+  /// The outline path in the current transform of the painter.
+  fn outline_path(&self) -> Option<Path> {
+    let (path, ts) = self.outline.as_ref()?;
+    let path = (**path).clone();
+    if ts == self.painter.transform() {
+      Some(path)
+    } else {
+      let inverse = self.painter.transform().inverse()?;
+      Some(path.transform(&ts.then(&inverse)))
+    }
+  }
+}
+
+impl<'a> colr::Painter<'a> for ColrPainter<'_> {
+  fn outline_glyph(&mut self, glyph_id: GlyphId) {
+    self.outline = self
+      .face
+      .outline_glyph(glyph_id)
+      .map(|path| (path, *self.painter.transform()));
+  }
+
+  fn paint(&mut self, paint: Paint<'a>) {
+    let Some(path) = self.outline_path() else { return };
+    let brush = match paint {
+      Paint::Solid(color) => Brush::Color(to_color(color)),
+      Paint::LinearGradient(g) => Brush::LinearGradient(LinearGradient {
+        start: Point::new(g.x0, g.y0),
+        end: Point::new(g.x1, g.y1),
+        stops: g.stops(0, &[]).map(to_stop).collect(),
+        spread_method: to_spread(g.extend),
+      }),
+      Paint::RadialGradient(g) => Brush::RadialGradient(RadialGradient {
+        start_center: Point::new(g.x0, g.y0),
+        start_radius: g.r0,
+        end_center: Point::new(g.x1, g.y1),
+        end_radius: g.r1,
+        stops: g.stops(0, &[]).map(to_stop).collect(),
+        spread_method: to_spread(g.extend),
+      }),
+      Paint::SweepGradient(g) => {
+        log::warn!("[painter]: not support sweep gradient in color glyph, use its first color!");
+        let color = g
+          .stops(0, &[])
+          .next()
+          .map_or(Color::BLACK, |s| to_color(s.color));
+        Brush::Color(color)
+      }
+    };
+    self
+      .painter
+      .set_fill_brush(brush)
+      .fill_path(Resource::new(path).into());
+  }
+
+  fn push_clip(&mut self) {
+    self.painter.save();
+    if let Some(path) = self.outline_path() {
+      self.painter.clip(Resource::new(path).into());
+    }
+  }
+
+  fn push_clip_box(&mut self, clip_box: ClipBox) {
+    let ClipBox { x_min, y_min, x_max, y_max } = clip_box;
+    let rect = Rect::new(Point::new(x_min, y_min), Size::new(x_max - x_min, y_max - y_min));
+    self.painter.save();
+    self
+      .painter
+      .clip(Resource::new(Path::rect(&rect)).into());
+  }
+
+  fn pop_clip(&mut self) { self.painter.restore(); }
+
+  fn push_layer(&mut self, _: CompositeMode) { self.painter.save(); }
+
+  fn pop_layer(&mut self) { self.painter.restore(); }
+
+  fn push_transform(&mut self, ts: rustybuzz::ttf_parser::Transform) {
+    let rustybuzz::ttf_parser::Transform { a, b, c, d, e, f } = ts;
+    self.painter.save();
+    self
+      .painter
+      .apply_transform(&Transform::new(a, b, c, d, e, f));
+  }
+
+  fn pop_transform(&mut self) { self.painter.restore(); }
+}
+
+fn to_color(c: RgbaColor) -> Color { Color::new(c.red, c.green, c.blue, c.alpha) }
+
+fn to_stop(s: colr::ColorStop) -> GradientStop {
+  GradientStop::new(to_color(s.color), s.stop_offset)
+}
+
+fn to_spread(extend: GradientExtend) -> SpreadMethod {
+  match extend {
+    GradientExtend::Pad => SpreadMethod::Pad,
+    GradientExtend::Repeat => SpreadMethod::Repeat,
+    GradientExtend::Reflect => SpreadMethod::Reflect,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use colr::Painter as _;
+
+  use super::*;
+  use crate::{CommandBrush, FontFace, FontFamily, PaintCommand, PaintPathAction, font_db::FontDB};
+
+  #[test]
+  fn replay_color_layers() {
+    let mut db = FontDB::default();
+    let path = env!("CARGO_MANIFEST_DIR").to_owned() + "/../fonts/DejaVuSans.ttf";
+    db.load_font_file(path).unwrap();
+    let id = db
+      .select_best_match(&FontFace {
+        families: Box::new([FontFamily::Name("DejaVu Sans".into())]),
+        ..<_>::default()
+      })
+      .unwrap();
+    let face = db.face_data_or_insert(id).unwrap().clone();
+    let glyph = face.glyph_index('A').unwrap();
+
+    let mut painter = Painter::new(Rect::from_size(Size::splat(f32::MAX)));
+    let mut colr = ColrPainter { face: &face, painter: &mut painter, outline: None };
+    let Color { red, green, blue, alpha } = Svg::DYNAMIC_COLOR;
+    colr.outline_glyph(glyph);
+    colr.paint(Paint::Solid(RgbaColor::new(255, 0, 0, 255)));
+    colr.push_transform(rustybuzz::ttf_parser::Transform::new_translate(10., 0.));
+    colr.paint(Paint::Solid(RgbaColor::new(red, green, blue, alpha)));
+    colr.pop_transform();
+
+    let commands = painter.finish().to_owned().into_boxed_slice();
+    let svg = Svg::from_commands(Size::splat(2048.), commands);
+    let commands = svg.commands(&Color::GREEN.into(), &Color::BLACK.into());
+    let colors: Vec<_> = commands
+      .iter()
+      .filter_map(|cmd| match cmd {
+        PaintCommand::Path(p) => match &p.action {
+          PaintPathAction::Paint { brush: CommandBrush::Color(c), .. } => Some(*c),
+          _ => None,
+        },
+        _ => None,
+      })
+      .collect();
+    assert_eq!(colors, vec![Color::RED, Color::GREEN]);
+
+    // The glyph has no color layers.
+    assert!(colr_glyph_svg(&face, glyph).is_none());
+  }
+}
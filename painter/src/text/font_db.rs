@@ -10,13 +10,15 @@ use rustybuzz::ttf_parser::{GlyphId, OutlineBuilder};
 use crate::{
   Path, PixelImage, Svg,
   path_builder::PathBuilder,
-  text::{FontFace, FontFamily, svg_glyph_cache::SvgGlyphCache},
+  text::{FontFace, FontFamily, colr_glyph::colr_glyph_svg, svg_glyph_cache::SvgGlyphCache},
 };
 /// A wrapper of fontdb and cache font data.
 pub struct FontDB {
   default_fonts: Vec<ID>,
   data_base: fontdb::Database,
   cache: HashMap<ID, Option<Face>>,
+  /// The fallback face of a char for the faces look like a face.
+  fallback_cache: HashMap<(char, Option<ID>), Option<ID>>,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -40,6 +42,7 @@ pub struct Face {
   raster_image_glyphs: FontGlyphCache<GlyphId, Resource<PixelImage>>,
   outline_glyphs: FontGlyphCache<GlyphId, Resource<Path>>,
   svg_glyphs: Sc<RefCell<SvgGlyphCache>>,
+  colr_glyphs: FontGlyphCache<GlyphId, Svg>,
   x_height: u16,
   cap_height: i16,
   ascender: i16,
@@ -86,8 +89,49 @@ impl FontDB {
     }
   }
 
+  /// Selects a face that has the glyph of `c` from all the faces in the
+  /// database, the faces with the same style, and the closer weight and
+  /// stretch to the face of `like` are preferred.
+  pub fn select_fallback(&mut self, c: char, like: Option<ID>) -> Option<ID> {
+    if let Some(id) = self.fallback_cache.get(&(c, like)) {
+      return *id;
+    }
+
+    let like_info = like.and_then(|id| self.data_base.face(id));
+    let mut candidates = self
+      .data_base
+      .faces()
+      .map(|info| {
+        let score = like_info.map_or((false, 0, 0), |like| {
+          (
+            info.style != like.style,
+            info.weight.0.abs_diff(like.weight.0),
+            info
+              .stretch
+              .to_number()
+              .abs_diff(like.stretch.to_number()),
+          )
+        });
+        (score, info.id)
+      })
+      .collect::<Vec<_>>();
+    candidates.sort_by_key(|(score, _)| *score);
+
+    let id = candidates.into_iter().find_map(|(_, id)| {
+      self
+        .face_data_or_insert(id)
+        .filter(|face| face.has_char(c))
+        .map(|_| id)
+    });
+    self.fallback_cache.insert((c, like), id);
+    id
+  }
+
   #[inline]
-  pub fn load_from_bytes(&mut self, data: Vec<u8>) { self.data_base.load_font_data(data); }
+  pub fn load_from_bytes(&mut self, data: Vec<u8>) {
+    self.data_base.load_font_data(data);
+    self.fallback_cache.clear();
+  }
 
   /// Loads a font file into the `Database`.
   ///
@@ -96,6 +140,7 @@ impl FontDB {
   pub fn load_font_file<P: AsRef<std::path::Path>>(
     &mut self, path: P,
   ) -> Result<(), std::io::Error> {
+    self.fallback_cache.clear();
     self.data_base.load_font_file(path)
   }
 
@@ -111,6 +156,7 @@ impl FontDB {
   /// be added manually.
   pub fn load_system_fonts(&mut self) {
     self.data_base.load_system_fonts();
+    self.fallback_cache.clear();
     self.static_generic_families();
   }
 
@@ -267,7 +313,12 @@ impl Default for FontDB {
     let mut data_base = fontdb::Database::new();
     data_base.load_font_data(include_bytes!("./Lato-Regular.ttf").to_vec());
     let default_font = data_base.faces().next().map(|f| f.id).unwrap();
-    let mut this = FontDB { default_fonts: vec![default_font], data_base, cache: <_>::default() };
+    let mut this = FontDB {
+      default_fonts: vec![default_font],
+      data_base,
+      cache: <_>::default(),
+      fallback_cache: <_>::default(),
+    };
     this.face_data_or_insert(default_font);
     this
   }
@@ -311,6 +362,7 @@ impl Face {
       outline_glyphs: <_>::default(),
       raster_image_glyphs: <_>::default(),
       svg_glyphs: <_>::default(),
+      colr_glyphs: <_>::default(),
       x_height,
       ascender,
       descender,
@@ -331,6 +383,13 @@ impl Face {
     }
   }
 
+  /// If the face has the glyphs in colors, which are described by the `COLR`,
+  /// `CBDT`, `sbix` or `SVG` table, like an emoji font.
+  pub fn has_color_glyphs(&self) -> bool {
+    let tables = self.tables();
+    tables.colr.is_some() || tables.cbdt.is_some() || tables.sbix.is_some() || tables.svg.is_some()
+  }
+
   #[inline]
   pub fn has_char(&self, c: char) -> bool { self.rb_face.as_ref().glyph_index(c).is_some() }
 
@@ -377,6 +436,17 @@ impl Face {
       .clone()
   }
 
+  /// Return the color glyph described by the `COLR` table as a SVG in font
+  /// units.
+  pub fn glyph_colr_image(&self, glyph_id: GlyphId) -> Option<Svg> {
+    self
+      .colr_glyphs
+      .borrow_mut()
+      .entry(glyph_id)
+      .or_insert_with(|| colr_glyph_svg(self, glyph_id))
+      .clone()
+  }
+
   pub fn glyph_svg_image(&self, glyph_id: GlyphId) -> Option<Svg> {
     self
       .svg_glyphs
@@ -529,6 +599,28 @@ mod tests {
     assert_eq!(info.families[0].0, "DejaVu Sans");
   }
 
+  #[test]
+  fn select_fallback() {
+    let mut db = FontDB::default();
+    let path = env!("CARGO_MANIFEST_DIR").to_owned() + "/../fonts/";
+    db.load_font_file(path.clone() + "DejaVuSans.ttf")
+      .unwrap();
+    db.load_font_file(path + "NotoSerifSC-Bold.你好世界.otf")
+      .unwrap();
+    let dejavu = db.select_best_match(&FontFace {
+      families: Box::new([FontFamily::Name("DejaVu Sans".into())]),
+      ..<_>::default()
+    });
+    let noto = db.select_best_match(&FontFace {
+      families: Box::new([FontFamily::Name("Noto Serif SC".into())]),
+      weight: FontWeight::BOLD,
+      ..<_>::default()
+    });
+
+    assert_eq!(db.select_fallback('你', dejavu), noto);
+    assert_eq!(db.select_fallback('\u{10FFFD}', dejavu), None);
+  }
+
   #[test]
   fn load_font_from_bytes() {
     let mut db = FontDB::default();
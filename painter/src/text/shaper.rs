@@ -14,6 +14,9 @@ use crate::{
 };
 
 pub const NEWLINE_GLYPH_ID: GlyphId = GlyphId(u16::MAX);
+/// The glyph id of a placeholder that holds a box in the text but paints
+/// nothing.
+pub const PLACEHOLDER_GLYPH_ID: GlyphId = GlyphId(u16::MAX - 1);
 /// Shaper to shape the `text` using provided font faces, and will do BIDI
 /// reordering before to shape text.
 ///
@@ -292,10 +295,13 @@ struct FallBackFaceHelper<'a> {
   ids: Vec<ID>,
   font_db: &'a RefCell<FontDB>,
   face_idx: usize,
+  /// The face that the fallback faces searched from the database look like.
+  like: Option<ID>,
 }
 
 impl<'a> FallBackFaceHelper<'a> {
   fn new(ids: &'a [ID], font_db: &'a RefCell<FontDB>) -> Self {
+    let like = ids.first().copied();
     let mut ids = ids.to_vec();
     let set: ahash::HashSet<ID> = ahash::HashSet::from_iter(ids.iter().cloned());
 
@@ -310,35 +316,55 @@ impl<'a> FallBackFaceHelper<'a> {
       }
     }
 
-    Self { ids, font_db, face_idx: 0 }
+    Self { ids, font_db, face_idx: 0, like }
   }
 
   fn next_fallback_face(&mut self, text: &str) -> Option<Face> {
-    let font_db = self.font_db.borrow();
-    loop {
-      if self.face_idx >= self.ids.len() {
-        return None;
-      }
-
+    while self.face_idx < self.ids.len() {
       let face = self
-        .ids
-        .get(self.face_idx)
-        .and_then(|id| font_db.try_get_face_data(*id))
+        .font_db
+        .borrow()
+        .try_get_face_data(self.ids[self.face_idx])
         .cloned();
-
       self.face_idx += 1;
-      if self.face_idx == self.ids.len() {
+      let face = face.filter(|f| text.is_empty() || text.chars().any(|c| f.has_char(c)));
+      if face.is_some() {
         return face;
-      } else {
-        let face = face.filter(|f| match text.is_empty() {
-          true => true,
-          false => text.chars().any(|c| f.has_char(c)),
-        });
-        if face.is_some() {
-          return face;
-        }
       }
     }
+
+    // None of the selected faces has the glyphs, search the database for a face
+    // that has.
+    let fallback = text.chars().find_map(|c| {
+      self
+        .font_db
+        .borrow_mut()
+        .select_fallback(c, self.like)
+        .filter(|id| !self.ids.contains(id))
+    });
+    if let Some(id) = fallback {
+      self.ids.push(id);
+      self.face_idx = self.ids.len();
+      return self
+        .font_db
+        .borrow()
+        .try_get_face_data(id)
+        .cloned();
+    }
+
+    // No face has the glyphs, use the last face to shape them once, so the text
+    // still has its glyphs even if they are missing.
+    if self.face_idx == self.ids.len() {
+      self.face_idx += 1;
+      let font_db = self.font_db.borrow();
+      return self
+        .ids
+        .iter()
+        .rev()
+        .find_map(|id| font_db.try_get_face_data(*id))
+        .cloned();
+    }
+    None
   }
 }
 
@@ -420,25 +446,32 @@ mod tests {
       });
 
     let dir = TextDirection::LeftToRight;
+    // The chinese glyphs fall back to the face in the database that has them,
+    // even if it's not selected.
     let latin1 = shaper.shape_text(
       &"hello world! 你好，世界".into(),
       &ids_latin,
       dir,
       GlyphBaseline::Alphabetic,
     );
+    let noto = shaper
+      .font_db
+      .borrow()
+      .faces_info_iter()
+      .find(|f| {
+        f.families
+          .iter()
+          .any(|(name, _)| name == "Noto Serif SC")
+      })
+      .map(|f| f.id);
+    assert!(latin1.glyphs.iter().all(|g| g.is_not_miss()));
     assert_eq!(
       latin1
         .glyphs
         .iter()
-        .fold((0_u32, 0_u32), |(mut latin, mut chinese), g| {
-          if g.is_not_miss() {
-            latin += 1;
-          } else {
-            chinese += 1
-          }
-          (latin, chinese)
-        }),
-      (13, 5)
+        .filter(|g| Some(g.face_id) == noto)
+        .count(),
+      5
     );
 
     let fallback_chinese = shaper.shape_text(
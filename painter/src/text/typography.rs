@@ -6,7 +6,10 @@ use smallvec::{SmallVec, smallvec};
 use unicode_script::{Script, UnicodeScript};
 use unicode_segmentation::UnicodeSegmentation;
 
-use crate::{Glyph, GlyphUnit, TextAlign, TextOverflow, shaper::ShapeResult};
+use crate::{
+  Glyph, GlyphUnit, ID, TextAlign, TextOverflow,
+  shaper::{PLACEHOLDER_GLYPH_ID, ShapeResult},
+};
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PlaceLineDirection {
@@ -46,6 +49,8 @@ pub struct VisualLine {
   pub width: GlyphUnit,
   /// The glyph position is relative the line x/y
   pub glyphs: Vec<Glyph>,
+  /// If the glyphs of this line are truncated by the bounds or the max lines.
+  pub truncated: bool,
 }
 
 pub struct VisualInfos {
@@ -53,6 +58,11 @@ pub struct VisualInfos {
   pub text_align: TextAlign,
   /// if the typography result over the bounds provide by caller.
   pub over_bounds: bool,
+  /// if some lines are truncated, by the bounds or the max lines.
+  pub truncated: bool,
+  /// How the overflow is handled, the truncated lines need to fade out their
+  /// end when it's `TextOverflow::Fade`.
+  pub overflow: TextOverflow,
   pub line_dir: PlaceLineDirection,
   pub visual_width: GlyphUnit,
   pub visual_height: GlyphUnit,
@@ -65,6 +75,7 @@ pub struct TypographyMan<Paras> {
   line_height: GlyphUnit,
   bounds: Size<GlyphUnit>,
   overflow: TextOverflow,
+  max_lines: Option<usize>,
   /// Not directly use text as inputs, but accept glyphs after text shape
   /// because both simple text and rich text can custom compose its glyph runs
   /// by text reorder result and its style .
@@ -72,6 +83,12 @@ pub struct TypographyMan<Paras> {
   inline_cursor: GlyphUnit,
   visual_lines: SmallVec<[VisualLine; 1]>,
   over_bounds: bool,
+  /// The glyphs of the ellipsis, used when the overflow is `Ellipsis`.
+  ellipsis: Option<Sc<ShapeResult>>,
+  truncated: bool,
+  /// The runs of the current line, as the index of its first glyph in the line
+  /// and its em size, used to align the runs with different sizes.
+  line_runs: SmallVec<[(usize, GlyphUnit); 1]>,
 }
 
 impl<Paras> TypographyMan<Paras>
@@ -88,17 +105,52 @@ where
       line_height,
       bounds,
       overflow,
+      max_lines: None,
       inputs,
       inline_cursor: GlyphUnit::ZERO,
       visual_lines: smallvec![],
       over_bounds: false,
+      ellipsis: None,
+      truncated: false,
+      line_runs: smallvec![],
     }
   }
 
+  /// Set the shaped ellipsis that ends the truncated lines when the overflow is
+  /// `TextOverflow::Ellipsis`.
+  pub fn with_ellipsis(mut self, ellipsis: Sc<ShapeResult>) -> Self {
+    self.ellipsis = Some(ellipsis);
+    self
+  }
+
+  /// Limit the number of lines, the lines after it are dropped, and the last
+  /// line is truncated by the overflow.
+  pub fn with_max_lines(mut self, max_lines: Option<usize>) -> Self {
+    self.max_lines = max_lines;
+    self
+  }
+
   pub fn typography_all(mut self) -> VisualInfos {
+    let mut dropped = false;
     while let Some(p) = self.inputs.next() {
+      if self
+        .max_lines
+        .is_some_and(|max| max <= self.visual_lines.len())
+      {
+        dropped = true;
+        break;
+      }
       self.consume_paragraph(p);
     }
+    if let Some(max) = self.max_lines {
+      if max < self.visual_lines.len() {
+        self.visual_lines.truncate(max);
+        dropped = true;
+      }
+    }
+    if dropped {
+      self.truncate_last_line();
+    }
 
     if self.line_dir.is_reverse() {
       self.visual_lines.reverse();
@@ -113,6 +165,8 @@ where
       text_align: self.text_align,
       visual_lines: self.visual_lines,
       over_bounds: self.over_bounds,
+      truncated: self.truncated,
+      overflow: self.overflow,
       line_dir: self.line_dir,
     }
   }
@@ -211,13 +265,19 @@ where
     let text = run.text();
     let base = run.range.start as u32;
     let line_offset = (self.line_height - em) / 2.;
-    let is_auto_wrap = self.overflow.is_auto_wrap();
+    let is_auto_wrap = self.overflow.is_auto_wrap() || self.max_lines.is_some();
 
     let new_line = |this: &mut Self, cursor: &mut dyn InlineCursor| {
       this.end_line();
       this.begin_line();
+      this.line_runs.push((0, em));
       cursor.reset();
     };
+    let glyphs_len = self
+      .visual_lines
+      .last()
+      .map_or(0, |l| l.glyphs.len());
+    self.line_runs.push((glyphs_len, em));
 
     for word in run.word_glyphs() {
       let width: GlyphUnit = word
@@ -266,9 +326,14 @@ where
       line.height = self.line_height;
     }
     self.visual_lines.push(line);
+    self.line_runs.clear();
   }
 
   fn end_line(&mut self) {
+    if self.overflow.is_truncate() && self.inline_bound() < self.inline_cursor {
+      self.truncate_line(false);
+    }
+    self.align_line_runs();
     let line = self.visual_lines.last_mut().unwrap();
     // we will reorder the line after consumed all inputs.
     if self.line_dir.is_horizontal() {
@@ -281,6 +346,125 @@ where
     self.inline_cursor = GlyphUnit::ZERO;
   }
 
+  /// Grow the current line to hold its largest run, and align the bottom of
+  /// the em boxes of all its runs. Every run is placed in the middle of the
+  /// line by default, so a line only has runs in the standard size keeps as
+  /// it is.
+  fn align_line_runs(&mut self) {
+    let Some(max_em) = self.line_runs.iter().map(|(_, em)| *em).max() else {
+      return;
+    };
+    let line_height = self.line_height;
+    let block = line_height.max(line_height - GlyphUnit::STANDARD_EM + max_em);
+    let hor_line = self.line_dir.is_horizontal();
+    let line = self.visual_lines.last_mut().unwrap();
+    for (idx, (start, em)) in self.line_runs.iter().enumerate() {
+      let end = self
+        .line_runs
+        .get(idx + 1)
+        .map_or(line.glyphs.len(), |(end, _)| *end);
+      let offset = (block + max_em - line_height - *em) / 2.;
+      if offset == GlyphUnit::ZERO {
+        continue;
+      }
+      for g in &mut line.glyphs[*start..end.max(*start)] {
+        if hor_line {
+          g.x_offset += offset;
+        } else {
+          g.y_offset += offset;
+        }
+      }
+    }
+    if hor_line {
+      line.width = block;
+    } else {
+      line.height = block;
+    }
+  }
+
+  /// Truncate the last line after the lines over the max lines are dropped,
+  /// the line is ended with the ellipsis even if it's not over the bounds.
+  fn truncate_last_line(&mut self) {
+    let hor_line = self.line_dir.is_horizontal();
+    let Some(line) = self.visual_lines.last() else { return };
+    self.inline_cursor = if hor_line { line.height } else { line.width };
+    self.truncate_line(true);
+    let line = self.visual_lines.last_mut().unwrap();
+    if hor_line {
+      line.height = self.inline_cursor;
+    } else {
+      line.width = self.inline_cursor;
+    }
+    self.inline_cursor = GlyphUnit::ZERO;
+  }
+
+  /// Remove the glyphs of the current line out of the bounds, and end it with
+  /// the ellipsis if the overflow is `TextOverflow::Ellipsis`.
+  ///
+  /// If `force` is true, the line is marked as truncated even if no glyph is
+  /// out of the bounds.
+  fn truncate_line(&mut self, force: bool) {
+    let ellipsis = match self.overflow {
+      TextOverflow::Ellipsis => match self.ellipsis.clone() {
+        Some(ellipsis) => Some(ellipsis),
+        None => return,
+      },
+      _ => None,
+    };
+    let hor_line = self.line_dir.is_horizontal();
+    let inline_offset = |g: &Glyph| if hor_line { g.y_offset } else { g.x_offset };
+    let advance = |g: &Glyph| if hor_line { g.y_advance } else { g.x_advance };
+    let ellipsis_size = ellipsis.as_ref().map_or(GlyphUnit::ZERO, |e| {
+      e.glyphs
+        .iter()
+        .fold(GlyphUnit::ZERO, |acc, g| acc + advance(g))
+    });
+    let max_end = (self.inline_bound() - ellipsis_size).min(self.inline_cursor);
+
+    let line = self.visual_lines.last_mut().unwrap();
+    let mut cut = None;
+    while let Some(g) = line.glyphs.last() {
+      if inline_offset(g) + advance(g) <= max_end {
+        break;
+      }
+      cut = line.glyphs.pop();
+    }
+    let (mut pos, cluster) = match cut {
+      Some(cut) => (inline_offset(&cut), cut.cluster),
+      None if force => (max_end, line.glyphs.last().map_or(0, |g| g.cluster)),
+      None => return,
+    };
+    line.truncated = true;
+    self.truncated = true;
+
+    // Place the ellipsis at the cut position, and map it to the cut cluster.
+    let Some(ellipsis) = ellipsis else {
+      self.inline_cursor = pos;
+      return;
+    };
+    let em = GlyphUnit::from_pixel(GlyphUnit::PIXELS_PER_EM as f32);
+    let line_offset = (self.line_height - em) / 2.;
+    for g in ellipsis.glyphs.iter() {
+      let mut g = g.clone();
+      if hor_line {
+        g.x_offset += line_offset;
+        g.y_offset += pos;
+      } else {
+        g.x_offset += pos;
+        g.y_offset += line_offset;
+      }
+      g.cluster = cluster;
+      pos += advance(&g);
+      line.glyphs.push(g);
+    }
+    self.inline_cursor = pos;
+  }
+
+  /// The size of the bounds in the inline direction.
+  fn inline_bound(&self) -> GlyphUnit {
+    if self.line_dir.is_horizontal() { self.bounds.height } else { self.bounds.width }
+  }
+
   fn is_over_line_bound(&self, position: GlyphUnit) -> bool {
     if self.text_align == TextAlign::Center {
       return false;
@@ -487,6 +671,26 @@ impl InputRun {
     Self { shape_result, font_size_factor, letter_space, range, reorder_text }
   }
 
+  /// A run occupies a box of `size` in the line, it has a single glyph with
+  /// the `PLACEHOLDER_GLYPH_ID` and the bottom of the box is aligned with the
+  /// bottom of the em boxes of the other runs.
+  pub(crate) fn placeholder(size: Size<GlyphUnit>, face_id: ID, range: Range<usize>) -> Self {
+    let height = size.height.max(GlyphUnit::new(1));
+    // The glyph is scaled by the factor when placed, so its em box is the box.
+    let factor = height.into_pixel() / GlyphUnit::PIXELS_PER_EM as f32;
+    let glyph = Glyph {
+      face_id,
+      x_advance: GlyphUnit::from_pixel(size.width.into_pixel() / factor),
+      y_advance: GlyphUnit::STANDARD_EM,
+      x_offset: GlyphUnit::ZERO,
+      y_offset: GlyphUnit::ZERO,
+      glyph_id: PLACEHOLDER_GLYPH_ID,
+      cluster: 0,
+    };
+    let shape_result = ShapeResult { text: "\u{FFFC}".into(), glyphs: vec![glyph] };
+    Self::new(Sc::new(shape_result), factor, GlyphUnit::ZERO, range)
+  }
+
   #[inline]
   fn text(&self) -> &str { &self.shape_result.text }
 
@@ -1,12 +1,13 @@
-use std::{cell::RefCell, ops::Range};
+use std::{cell::RefCell, collections::HashMap, ops::Range};
 
 use font_db::GlyphBaseline;
 use ribir_algo::{FrameCache, Sc, Substr};
 use ribir_geom::{Point, Rect, Size};
+use smallvec::SmallVec;
 
 use crate::{
   font_db::FontDB,
-  shaper::{NEWLINE_GLYPH_ID, TextShaper},
+  shaper::{NEWLINE_GLYPH_ID, ShapeResult, TextShaper},
   text_reorder::ReorderResult,
   typography::*,
   *,
@@ -31,6 +32,7 @@ pub struct TypographyKey {
   baseline: GlyphBaseline,
   line_dir: PlaceLineDirection,
   overflow: TextOverflow,
+  max_lines: Option<usize>,
 }
 
 /// Do simple text typography and cache it.
@@ -50,6 +52,58 @@ pub struct VisualGlyphs {
   order_info: Sc<ReorderResult>,
 }
 
+/// The metrics of a text measured by [`TypographyStore::measure`], all values
+/// are in pixels.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextMetrics {
+  /// The size the text occupies.
+  pub size: Size,
+  /// The metrics of every line, from top to bottom.
+  pub lines: Vec<LineMetrics>,
+}
+
+/// The metrics of a line of a measured text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineMetrics {
+  /// The distance from the top of the text to the top of the line.
+  pub y: f32,
+  pub width: f32,
+  pub height: f32,
+  /// The distance from the top of the text to the alphabetic baseline of the
+  /// line.
+  pub baseline: f32,
+}
+
+impl TextMetrics {
+  /// The baseline of the first line, or `None` if the text has no line.
+  pub fn first_baseline(&self) -> Option<f32> { self.lines.first().map(|l| l.baseline) }
+
+  /// The baseline of the last line, or `None` if the text has no line.
+  pub fn last_baseline(&self) -> Option<f32> { self.lines.last().map(|l| l.baseline) }
+}
+
+/// The shape results of a text kept by its owner across frames, see
+/// [`TypographyStore::typography_with_memo`].
+///
+/// The shape cache of the store drops the results not used in the last frame,
+/// so an editor keeps its text shaped by paragraphs in it, and an edit only
+/// reshapes the paragraphs it changes.
+#[derive(Default, Clone)]
+pub struct ShapeMemo {
+  face_ids: Box<[ID]>,
+  baseline: Option<GlyphBaseline>,
+  runs: HashMap<(Substr, TextDirection), Sc<ShapeResult>>,
+}
+
+/// A styled range of a rich text, see [`TypographyStore::typography_rich`].
+#[derive(Debug, Clone, Copy)]
+pub enum RichRun<'a> {
+  /// The range of the text is typeset in the style.
+  Text(&'a Range<usize>, &'a TextStyle),
+  /// The range of the text is replaced by a box of the size in pixels.
+  Placeholder(&'a Range<usize>, Size),
+}
+
 impl VisualGlyphs {
   pub fn new(
     font_size: f32, line_dir: PlaceLineDirection, order_info: Sc<ReorderResult>,
@@ -93,7 +147,27 @@ impl TypographyStore {
     &mut self, text: Substr, style: &TextStyle, bounds: Size, text_align: TextAlign,
     baseline: GlyphBaseline, line_dir: PlaceLineDirection,
   ) -> VisualGlyphs {
-    let TextStyle { font_size, ref font_face, letter_space, line_height, overflow } = *style;
+    self.typography_impl(text, style, bounds, text_align, baseline, line_dir, None)
+  }
+
+  /// Like [`TypographyStore::typography`], but the runs of the `text` are
+  /// shaped by the results kept in `memo` if they are not changed, and the
+  /// `memo` keeps the results of this typography for the next one.
+  #[allow(clippy::too_many_arguments)]
+  pub fn typography_with_memo(
+    &mut self, text: Substr, style: &TextStyle, bounds: Size, text_align: TextAlign,
+    baseline: GlyphBaseline, line_dir: PlaceLineDirection, memo: &mut ShapeMemo,
+  ) -> VisualGlyphs {
+    self.typography_impl(text, style, bounds, text_align, baseline, line_dir, Some(memo))
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  fn typography_impl(
+    &mut self, text: Substr, style: &TextStyle, bounds: Size, text_align: TextAlign,
+    baseline: GlyphBaseline, line_dir: PlaceLineDirection, mut memo: Option<&mut ShapeMemo>,
+  ) -> VisualGlyphs {
+    let TextStyle { font_size, ref font_face, letter_space, line_height, overflow, max_lines } =
+      *style;
     // Since we cache the result of the standard font size, we must ensure that all
     // variables are cast relative to this standard font size.
     let scale = font_size / GlyphUnit::PIXELS_PER_EM as f32;
@@ -113,12 +187,26 @@ impl TypographyStore {
       .select_all_match(font_face)
       .into_boxed_slice();
     let runs = [RunKey { ids, line_height, letter_space, text }].into();
-    let key = TypographyKey::new(runs, bounds, text_align, line_dir, overflow, baseline);
+    let key = TypographyKey::new(runs, bounds, text_align, line_dir, overflow, max_lines, baseline);
     let infos = if let Some(infos) = self.cache.get(&key).cloned() {
       infos
     } else {
       let ids = &key.runs[0].ids;
       let text = &key.runs[0].text;
+      // The results of the other fonts or baseline can't be reused.
+      let keep = memo.is_some();
+      let last_runs = match memo.as_deref_mut() {
+        Some(memo) if memo.baseline == Some(baseline) && memo.face_ids == *ids => {
+          std::mem::take(&mut memo.runs)
+        }
+        _ => HashMap::default(),
+      };
+      let mut runs = HashMap::default();
+      let ellipsis = (overflow == TextOverflow::Ellipsis).then(|| {
+        self
+          .shaper
+          .shape_text(&"…".into(), ids, TextDirection::LeftToRight, baseline)
+      });
       let inputs = info.paras.iter().map(|p| {
         p.runs
           .iter()
@@ -129,16 +217,31 @@ impl TypographyStore {
               TextDirection::RightToLeft
             };
 
-            let shape_result = self
-              .shaper
-              .shape_text(&text.substr(r.clone()), ids, dir, baseline);
+            let key = (text.substr(r.clone()), dir);
+            let shape_result = match runs.get(&key).or_else(|| last_runs.get(&key)) {
+              Some(shape_result) => Sc::clone(shape_result),
+              None => self.shaper.shape_text(&key.0, ids, dir, baseline),
+            };
+            if keep {
+              runs.insert(key, shape_result.clone());
+            }
             InputRun::new(shape_result, 1., letter_space, r.clone())
           })
           .collect()
       });
 
-      let t_man = TypographyMan::new(inputs, line_dir, text_align, line_height, bounds, overflow);
+      let mut t_man =
+        TypographyMan::new(inputs, line_dir, text_align, line_height, bounds, overflow)
+          .with_max_lines(max_lines);
+      if let Some(ellipsis) = ellipsis {
+        t_man = t_man.with_ellipsis(ellipsis);
+      }
       let visual_info = t_man.typography_all();
+      if let Some(memo) = memo {
+        memo.face_ids = ids.clone();
+        memo.baseline = Some(baseline);
+        memo.runs = runs;
+      }
       let infos = Sc::new(visual_info);
       self.cache.put(key, infos.clone());
       infos
@@ -147,6 +250,112 @@ impl TypographyStore {
     VisualGlyphs::new(font_size, line_dir, info, bounds.width, bounds.height, infos.clone())
   }
 
+  /// Typography a text that its ranges are in different styles, or replaced by
+  /// boxes. The `style` provides the line height, the overflow and the font
+  /// size that the result glyphs are relative to.
+  ///
+  /// The ranges of `runs` should be sorted and not overlapped, the text not in
+  /// any run is ignored. Unlike [`TypographyStore::typography`], the result is
+  /// not cached, but the shaping of the text is.
+  #[allow(clippy::too_many_arguments)]
+  pub fn typography_rich(
+    &mut self, text: Substr, runs: &[RichRun], style: &TextStyle, bounds: Size,
+    text_align: TextAlign, baseline: GlyphBaseline, line_dir: PlaceLineDirection,
+  ) -> VisualGlyphs {
+    let scale = style.font_size / GlyphUnit::PIXELS_PER_EM as f32;
+    let to_unit = |v: f32| GlyphUnit::from_pixel(v / scale);
+    let bounds = Size::new(to_unit(bounds.width), to_unit(bounds.height));
+    let line_height = to_unit(style.line_height);
+
+    let info = self.reorder.reorder_text(&text).clone();
+    let mut paras = Vec::with_capacity(info.paras.len());
+    for p in info.paras.iter() {
+      let mut inputs = SmallVec::<[InputRun; 1]>::new();
+      for r in p.runs.iter() {
+        let dir = if r.is_empty() || p.levels[r.start].is_ltr() {
+          TextDirection::LeftToRight
+        } else {
+          TextDirection::RightToLeft
+        };
+        let start = inputs.len();
+        for run in runs {
+          let (RichRun::Text(rg, _) | RichRun::Placeholder(rg, _)) = *run;
+          let rg = rg.start.max(r.start)..rg.end.min(r.end);
+          if rg.is_empty() {
+            continue;
+          }
+          let input = match *run {
+            RichRun::Text(_, s) => {
+              let ids = self
+                .font_db
+                .borrow_mut()
+                .select_all_match(&s.font_face);
+              let shape = self
+                .shaper
+                .shape_text(&text.substr(rg.clone()), &ids, dir, baseline);
+              InputRun::new(shape, s.font_size / style.font_size, to_unit(s.letter_space), rg)
+            }
+            RichRun::Placeholder(_, size) => {
+              let size = Size::new(to_unit(size.width), to_unit(size.height));
+              InputRun::placeholder(size, ID::dummy(), rg)
+            }
+          };
+          inputs.push(input);
+        }
+        // The runs of a right-to-left text are placed from the last one.
+        if dir == TextDirection::RightToLeft {
+          inputs[start..].reverse();
+        }
+      }
+      paras.push(inputs);
+    }
+
+    let mut t_man = TypographyMan::new(
+      paras.into_iter(),
+      line_dir,
+      text_align,
+      line_height,
+      bounds,
+      style.overflow,
+    )
+    .with_max_lines(style.max_lines);
+    if style.overflow == TextOverflow::Ellipsis {
+      let ids = self
+        .font_db
+        .borrow_mut()
+        .select_all_match(&style.font_face);
+      let ellipsis =
+        self
+          .shaper
+          .shape_text(&"…".into(), &ids, TextDirection::LeftToRight, baseline);
+      t_man = t_man.with_ellipsis(ellipsis);
+    }
+    let infos = Sc::new(t_man.typography_all());
+
+    VisualGlyphs::new(style.font_size, line_dir, info, bounds.width, bounds.height, infos)
+  }
+
+  /// Measure the `text` in the `style` without painting it, the lines wrap at
+  /// `max_width` if the overflow of the style is `TextOverflow::AutoWrap` or
+  /// the `max_lines` of the style is set.
+  ///
+  /// The text is typeset in the same way as the `Text` widget does, and the
+  /// result is shared with the typography cache of the current frame, so
+  /// measuring a text in the layout and painting it later only typeset it
+  /// once.
+  pub fn measure(&mut self, text: Substr, style: &TextStyle, max_width: f32) -> TextMetrics {
+    self
+      .typography(
+        text,
+        style,
+        Size::new(max_width, f32::INFINITY),
+        TextAlign::Start,
+        GlyphBaseline::Middle,
+        PlaceLineDirection::TopToBottom,
+      )
+      .metrics()
+  }
+
   pub fn font_db(&self) -> &Sc<RefCell<FontDB>> { &self.font_db }
 }
 
@@ -161,6 +370,51 @@ impl VisualGlyphs {
     )
   }
 
+  /// Return if some lines of the text are truncated by the bounds or the max
+  /// lines.
+  pub fn is_truncated(&self) -> bool { self.visual_info.truncated }
+
+  /// Return the distance from the top of the visual rect's container to the
+  /// alphabetic baseline of the first line, or `None` if the text has no glyph
+  /// or its lines are not horizontal.
+  pub fn first_baseline(&self) -> Option<f32> {
+    if !self.is_horizontal_line() {
+      return None;
+    }
+    let line = self.visual_info.visual_lines.first()?;
+    let glyph = line.glyphs.first()?;
+    // The alphabetic baseline of a glyph is one em below its top.
+    let baseline = self.y + line.y + glyph.y_offset + GlyphUnit::STANDARD_EM;
+    Some(self.to_pixel_value(baseline))
+  }
+
+  /// Return the metrics of the text, it's only meaningful for the horizontal
+  /// lines.
+  pub fn metrics(&self) -> TextMetrics {
+    let lines = self
+      .visual_info
+      .visual_lines
+      .iter()
+      .map(|line| {
+        let y = self.y + line.y;
+        // Glyphs are placed in the middle of the line, the alphabetic baseline of
+        // a glyph is one em below its top.
+        let baseline = line.glyphs.first().map_or_else(
+          || y + (line.height + GlyphUnit::STANDARD_EM) / 2.,
+          |g| y + g.y_offset + GlyphUnit::STANDARD_EM,
+        );
+        LineMetrics {
+          y: self.to_pixel_value(y),
+          width: self.to_pixel_value(line.width),
+          height: self.to_pixel_value(line.height),
+          baseline: self.to_pixel_value(baseline),
+        }
+      })
+      .collect();
+
+    TextMetrics { size: self.visual_rect().size, lines }
+  }
+
   pub fn nearest_glyph(&self, offset_x: f32, offset_y: f32) -> (usize, usize) {
     let scale = self.font_size / GlyphUnit::PIXELS_PER_EM as f32;
     let x = GlyphUnit::from_pixel(offset_x / scale) - self.x;
@@ -434,6 +688,35 @@ impl VisualGlyphs {
     Some(iter)
   }
 
+  /// The alpha to paint the `glyph` returned by
+  /// [`VisualGlyphs::glyphs_in_bounds`], the glyphs at the end of the truncated
+  /// lines fade out in two font sizes when the overflow is
+  /// `TextOverflow::Fade`.
+  pub fn fade_alpha(&self, glyph: &Glyph) -> f32 {
+    let info = &self.visual_info;
+    if info.overflow != TextOverflow::Fade || !info.truncated {
+      return 1.;
+    }
+
+    let hor_line = self.is_horizontal_line();
+    let (block, start, advance) = if hor_line {
+      (glyph.y_offset, glyph.x_offset, glyph.x_advance)
+    } else {
+      (glyph.x_offset, glyph.y_offset, glyph.y_advance)
+    };
+    let block = block.into_pixel();
+    let line = info.visual_lines.iter().find(|l| {
+      let (pos, size) = if hor_line { (l.y, l.height) } else { (l.x, l.width) };
+      self.to_pixel_value(pos) <= block && block < self.to_pixel_value(pos + size)
+    });
+    let Some(line) = line.filter(|l| l.truncated) else { return 1. };
+
+    let end = if hor_line { line.x + line.width } else { line.y + line.height };
+    let center = start.into_pixel() + advance.into_pixel() / 2.;
+    let fade = self.font_size * 2.;
+    ((self.to_pixel_value(end) - center) / fade).clamp(0., 1.)
+  }
+
   pub fn glyph_count(&self, row: usize, ignore_new_line: bool) -> usize {
     self
       .visual_info
@@ -464,14 +747,14 @@ impl VisualGlyphs {
 impl TypographyKey {
   fn new(
     runs: Box<[RunKey]>, bounds: Size<GlyphUnit>, text_align: TextAlign,
-    line_dir: PlaceLineDirection, overflow: TextOverflow, baseline: GlyphBaseline,
+    line_dir: PlaceLineDirection, overflow: TextOverflow, max_lines: Option<usize>,
+    baseline: GlyphBaseline,
   ) -> Self {
     let line_width = match overflow {
       // line width is not so important in clip mode, the cache can be use even with difference line
       // width. The wider one can use for the narrower one. S
-      TextOverflow::Overflow => GlyphUnit::MAX,
-
-      TextOverflow::AutoWrap => {
+      TextOverflow::Overflow if max_lines.is_none() => GlyphUnit::MAX,
+      _ => {
         if line_dir.is_horizontal() {
           bounds.height
         } else {
@@ -480,7 +763,7 @@ impl TypographyKey {
       }
     };
 
-    Self { runs, line_width, text_align, line_dir, overflow, baseline }
+    Self { runs, line_width, text_align, line_dir, overflow, max_lines, baseline }
   }
 }
 
@@ -489,7 +772,7 @@ mod tests {
   use core::f32;
 
   use super::*;
-  use crate::FontFamily;
+  use crate::{FontFamily, shaper::PLACEHOLDER_GLYPH_ID};
 
   fn test_store() -> TypographyStore {
     let font_db = Sc::new(RefCell::new(FontDB::default()));
@@ -502,7 +785,14 @@ mod tests {
     FontFace { families: Box::new([FontFamily::Name("DejaVu Sans".into())]), ..<_>::default() }
   }
   fn text_style(font_size: f32, overflow: TextOverflow, letter_space: f32) -> TextStyle {
-    TextStyle { font_size, font_face: test_face(), letter_space, line_height: font_size, overflow }
+    TextStyle {
+      font_size,
+      font_face: test_face(),
+      letter_space,
+      line_height: font_size,
+      overflow,
+      max_lines: None,
+    }
   }
   fn zero_letter_space_style(font_size: f32, overflow: TextOverflow) -> TextStyle {
     text_style(font_size, overflow, 0.)
@@ -568,6 +858,92 @@ mod tests {
     assert_eq!(visual.visual_rect().size, Size::new(34.164063, 28.));
   }
 
+  #[test]
+  fn ellipsis_truncate() {
+    let typography = |text: &'static str, width: f32| {
+      let style = zero_letter_space_style(14., TextOverflow::Ellipsis);
+      let bounds = Size::new(width, f32::MAX);
+      typography_text(
+        text.into(),
+        &style,
+        bounds,
+        TextAlign::Start,
+        PlaceLineDirection::TopToBottom,
+      )
+    };
+
+    let visual = typography("Hello world!", 50.);
+    assert!(visual.is_truncated());
+    assert!(visual.visual_rect().width() <= 50.);
+    let line = &visual.visual_info.visual_lines[0];
+    assert!(line.glyphs.len() < "Hello world!".len());
+
+    let visual = typography("Hello", 50.);
+    assert!(!visual.is_truncated());
+    assert_eq!(visual.visual_info.visual_lines[0].glyphs.len(), 5);
+  }
+
+  #[test]
+  fn max_lines_truncate() {
+    let typography = |text: &'static str, overflow: TextOverflow| {
+      let style = TextStyle { max_lines: Some(2), ..zero_letter_space_style(14., overflow) };
+      typography_text(
+        text.into(),
+        &style,
+        Size::new(60., f32::MAX),
+        TextAlign::Start,
+        PlaceLineDirection::TopToBottom,
+      )
+    };
+
+    let text = "Hello world! Hello world! Hello world!";
+    let visual = typography(text, TextOverflow::Ellipsis);
+    assert!(visual.is_truncated());
+    assert_eq!(visual.glyph_row_count(), 2);
+    assert_eq!(visual.visual_rect().height(), 28.);
+    let last = &visual.visual_info.visual_lines[1];
+    assert!(last.truncated);
+    assert!(visual.visual_rect().width() <= 60.);
+
+    let visual = typography(text, TextOverflow::Clip);
+    assert!(visual.is_truncated());
+    assert_eq!(visual.glyph_row_count(), 2);
+    assert!(!visual.visual_info.visual_lines[0].truncated);
+
+    // Not truncated if all the lines are in the max lines.
+    let visual = typography("Hello world!", TextOverflow::Ellipsis);
+    assert!(!visual.is_truncated());
+    assert_eq!(visual.glyph_row_count(), 2);
+
+    let visual = typography("Hello\nworld\nribir", TextOverflow::Ellipsis);
+    assert!(visual.is_truncated());
+    assert_eq!(visual.glyph_row_count(), 2);
+  }
+
+  #[test]
+  fn fade_end_of_truncated_line() {
+    let style = zero_letter_space_style(14., TextOverflow::Fade);
+    let visual = typography_text(
+      "Hello world!".into(),
+      &style,
+      Size::new(50., f32::MAX),
+      TextAlign::Start,
+      PlaceLineDirection::TopToBottom,
+    );
+    assert!(visual.is_truncated());
+    assert!(visual.visual_rect().width() <= 50.);
+
+    let rect = visual.visual_rect();
+    let alphas: Vec<_> = visual
+      .glyphs_in_bounds(&rect)
+      .unwrap()
+      .map(|g| visual.fade_alpha(&g))
+      .collect();
+    assert_eq!(alphas[0], 1.);
+    assert!(*alphas.last().unwrap() < 0.5);
+    assert!(alphas.windows(2).all(|w| w[0] >= w[1]));
+  }
+
   #[test]
   fn simple_typography_text() {
     fn glyphs(
@@ -745,6 +1121,76 @@ mod tests {
     assert!(store.cache.is_empty());
   }
 
+  #[test]
+  fn measure_text() {
+    let mut store = test_store();
+    let style =
+      TextStyle { line_height: 20., ..zero_letter_space_style(16., TextOverflow::AutoWrap) };
+
+    let one_line = store.measure("Hello world".into(), &style, f32::INFINITY);
+    assert_eq!(one_line.lines.len(), 1);
+    assert_eq!(one_line.size.height, 20.);
+    assert_eq!(one_line.lines[0].width, one_line.size.width);
+
+    let wrapped = store.measure("Hello world".into(), &style, one_line.size.width - 1.);
+    assert_eq!(wrapped.lines.len(), 2);
+    assert_eq!(wrapped.size.height, 40.);
+    let (first, second) = (wrapped.lines[0], wrapped.lines[1]);
+    assert_eq!(second.y, first.y + first.height);
+    assert_eq!(second.baseline - first.baseline, 20.);
+    assert_eq!(wrapped.first_baseline(), one_line.first_baseline());
+    // The baseline is below the middle of the line, but inside it.
+    assert!(10. < first.baseline && first.baseline < 20.);
+
+    // Measure a same text again reuses the typography of the frame.
+    let len = store.cache.len();
+    store.measure("Hello world".into(), &style, f32::INFINITY);
+    assert_eq!(store.cache.len(), len);
+  }
+
+  #[test]
+  fn rich_runs() {
+    let mut store = test_store();
+    let style =
+      TextStyle { line_height: 20., ..zero_letter_space_style(16., TextOverflow::Overflow) };
+    let large = TextStyle { font_size: 32., ..style.clone() };
+    let text: Substr = "ab\u{FFFC}cd".into();
+    let (small_rg, box_rg, large_rg) = (0..2, 2..5, 5..7);
+    let runs = [
+      RichRun::Text(&small_rg, &style),
+      RichRun::Placeholder(&box_rg, Size::new(10., 30.)),
+      RichRun::Text(&large_rg, &large),
+    ];
+    let visual = store.typography_rich(
+      text,
+      &runs,
+      &style,
+      Size::new(f32::MAX, f32::MAX),
+      TextAlign::Start,
+      GlyphBaseline::Alphabetic,
+      PlaceLineDirection::TopToBottom,
+    );
+
+    // The line grows to hold the large run, and keeps the leading.
+    assert_eq!(visual.visual_rect().height(), 36.);
+    let glyphs: Vec<_> = visual.glyphs().collect();
+    assert_eq!(glyphs.len(), 5);
+    // The bottoms of the em boxes are aligned.
+    let top = |cluster: u32| {
+      let g = glyphs
+        .iter()
+        .find(|g| g.cluster == cluster)
+        .unwrap();
+      g.bounds().min_y()
+    };
+    assert_eq!(top(0), 18.);
+    assert_eq!(top(5), 2.);
+    let placeholder = &glyphs[2];
+    assert_eq!(placeholder.glyph_id, PLACEHOLDER_GLYPH_ID);
+    assert_eq!(top(2), 4.);
+    assert!((placeholder.bounds().width() - 10.).abs() < 0.01);
+  }
+
   #[test]
   fn cluster_position() {
     let style = zero_letter_space_style(15., TextOverflow::Overflow);
@@ -831,4 +1277,38 @@ mod tests {
     );
     assert_eq!(1, store.cache.len());
   }
+
+  #[test]
+  fn memo_keeps_unchanged_paragraphs() {
+    let mut store = test_store();
+    let mut memo = ShapeMemo::default();
+    let style = zero_letter_space_style(16., TextOverflow::Overflow);
+    let typography = |store: &mut TypographyStore, text: &'static str, memo: &mut ShapeMemo| {
+      store.typography_with_memo(
+        text.into(),
+        &style,
+        Size::new(f32::MAX, f32::MAX),
+        TextAlign::Start,
+        GlyphBaseline::Alphabetic,
+        PlaceLineDirection::TopToBottom,
+        memo,
+      );
+    };
+
+    typography(&mut store, "first\nsecond", &mut memo);
+    let first = memo.runs[&(Substr::from("first\n"), TextDirection::LeftToRight)].clone();
+    // The shape cache of the store drops the results not used in the frames.
+    store.end_frame();
+    store.end_frame();
+
+    typography(&mut store, "first\nsecond!", &mut memo);
+    assert_eq!(memo.runs.len(), 2);
+    let reused = &memo.runs[&(Substr::from("first\n"), TextDirection::LeftToRight)];
+    assert!(Sc::ptr_eq(&first, reused));
+    assert!(
+      memo
+        .runs
+        .contains_key(&(Substr::from("second!"), TextDirection::LeftToRight))
+    );
+  }
 }
@@ -1,16 +1,28 @@
 use std::{cell::RefCell, convert::Infallible, sync::LazyLock};
 
-use ribir_core::{local_sender::LocalSender, prelude::*, timer::Timer, window::WindowId};
+use ribir_core::{
+  local_sender::LocalSender,
+  prelude::*,
+  timer::Timer,
+  window::{WindowFlags, WindowId},
+};
+use ribir_gpu::error::RenderInitError;
+#[cfg(target_os = "android")]
+pub use winit::platform::android::activity::AndroidApp;
 use winit::{
-  event::{ElementState, Event, Ime, KeyEvent, StartCause, WindowEvent},
+  event::{DeviceEvent, ElementState, Event, Ime, KeyEvent, StartCause, WindowEvent},
   event_loop::{ControlFlow, EventLoop, EventLoopBuilder, EventLoopProxy, EventLoopWindowTarget},
 };
 
 use crate::{
-  register_platform_app_events_handlers,
+  prefers_reduced_motion, register_platform_app_events_handlers,
   winit_shell_wnd::{WinitShellWnd, new_id},
 };
 
+/// The Android activity the application runs in.
+#[cfg(target_os = "android")]
+pub(crate) static ANDROID_APP: std::sync::OnceLock<AndroidApp> = std::sync::OnceLock::new();
+
 pub struct App {
   event_loop_proxy: EventLoopProxy<AppEvent>,
   /// The event loop of the application, it's only available on native platform
@@ -27,12 +39,37 @@ pub struct WindowAttributes {
   pub maximized: bool,
   pub visible: bool,
   pub decorations: bool,
+  pub fullscreen: bool,
   pub title: String,
   pub size: Option<Size>,
   pub min_size: Option<Size>,
   pub max_size: Option<Size>,
   pub position: Option<Point>,
   pub icon: Option<Resource<PixelImage>>,
+  pub render_thread: bool,
+  pub backdrop: WindowBackdrop,
+  pub present_mode: PresentMode,
+  pub max_fps: Option<u32>,
+  /// The native window the window is created in as a child, see
+  /// [`WindowAttributes::with_parent_window`].
+  pub(crate) parent_window: Option<winit::raw_window_handle::RawWindowHandle>,
+}
+
+/// The platform material shows through the transparent area of a window, only
+/// the area of the `Vibrancy` widgets is transparent.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WindowBackdrop {
+  /// The window is opaque.
+  #[default]
+  None,
+  /// The blurred content behind the window, the vibrancy on macOS. It's the
+  /// acrylic on Windows.
+  Vibrancy,
+  /// The acrylic material of Windows 11, the vibrancy on the other platforms.
+  Acrylic,
+  /// The mica material of Windows 11, tinted by the desktop wallpaper. It's the
+  /// vibrancy on the other platforms.
+  Mica,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -51,6 +88,10 @@ pub enum AppEvent {
   Hotkey(HotkeyEvent),
   /// The event is sent when the application window focus changed.
   WndFocusChanged(WindowId, bool),
+  /// The event is sent when an item of the tray menu or the menu bar is
+  /// selected, see [`crate::tray`].
+  #[cfg(all(feature = "tray", not(target_family = "wasm")))]
+  Menu(crate::tray::MenuEvent),
   /// The custom event, you can send any data with this event.
   Custom(Box<dyn Any + Send>),
 }
@@ -70,7 +111,8 @@ impl App {
 
   fn process_winit_ime_event(wnd: &Window, ime: Ime) {
     match ime {
-      Ime::Enabled => {}
+      // The on-screen keyboard shows or hides with the IME.
+      Ime::Enabled => wnd.processes_viewport_insets_changed(),
       Ime::Preedit(txt, cursor) => {
         if txt.is_empty() {
           wnd.exit_pre_edit();
@@ -82,7 +124,10 @@ impl App {
         wnd.exit_pre_edit();
         wnd.processes_receive_chars(value);
       }
-      Ime::Disabled => wnd.exit_pre_edit(),
+      Ime::Disabled => {
+        wnd.exit_pre_edit();
+        wnd.processes_viewport_insets_changed();
+      }
     }
   }
 
@@ -101,11 +146,16 @@ impl App {
             }
           }
           WindowEvent::RedrawRequested => {
-            AppCtx::frame_ticks().clone().next(Instant::now());
+            AppCtx::frame_ticks()
+              .clone()
+              .next(FrameClock::now());
 
             if let Some(wnd) = AppCtx::get_window(wnd_id) {
               // if the window is not visible, don't draw it./
               if wnd.is_visible() != Some(false) {
+                // The winit doesn't notify the content rect changes of the Android activity,
+                // so the insets of the on-screen keyboard are polled before drawing.
+                wnd.processes_viewport_insets_changed();
                 // if this frame is really draw, request another redraw. To make sure the draw
                 // always end with a empty draw and emit an extra tick cycle message.
                 if wnd.draw_frame() {
@@ -114,12 +164,22 @@ impl App {
               }
             }
           }
+          WindowEvent::ScaleFactorChanged { .. } => {
+            wnd.processes_device_pixel_ratio_changed();
+            request_redraw(&wnd)
+          }
           WindowEvent::Resized(_) => {
             let size = wnd.shell_wnd().borrow().inner_size();
             wnd.shell_wnd().borrow_mut().on_resize(size);
+            wnd.processes_viewport_insets_changed();
             request_redraw(&wnd)
           }
           WindowEvent::Focused(focused) => {
+            if focused {
+              // The user may change the setting when the application is in
+              // background.
+              App::sync_reduced_motion();
+            }
             let mut event = AppEvent::WndFocusChanged(wnd_id, focused);
 
             App::shared()
@@ -152,17 +212,59 @@ impl App {
           request_redraw(&wnd)
         }
       }
+      #[cfg(not(target_family = "wasm"))]
+      Event::Suspended => {
+        // The platform may destroy the surfaces of the windows when the
+        // application goes to the background, such as Android.
+        for wnd in AppCtx::windows().borrow().values() {
+          shell_of(wnd, WinitShellWnd::suspend);
+        }
+      }
+      #[cfg(not(target_family = "wasm"))]
+      Event::Resumed => {
+        for wnd in AppCtx::windows().borrow().values() {
+          shell_of(wnd, WinitShellWnd::resume);
+          wnd.processes_viewport_insets_changed();
+          request_redraw(wnd);
+        }
+      }
+      Event::DeviceEvent { event: DeviceEvent::MouseMotion { delta: (x, y) }, .. } => {
+        // Don't hold the windows while emitting events, the handlers may open or
+        // close windows.
+        let locked: Vec<_> = AppCtx::windows()
+          .borrow()
+          .values()
+          .filter(|wnd| wnd.pointer_lock_target().is_some())
+          .cloned()
+          .collect();
+        for wnd in locked {
+          let ratio = wnd.device_pixel_ratio() as f64;
+          wnd.process_mouse_motion(Vector::new((x / ratio) as f32, (y / ratio) as f32));
+          wnd.emit_events();
+          if wnd.need_draw() {
+            request_redraw(&wnd)
+          }
+        }
+      }
       Event::AboutToWait => {
         let run_count = AppCtx::run_until_stalled();
-        if run_count > 0 {
-          for wnd in AppCtx::windows().borrow().values() {
-            request_redraw(wnd);
+        // Keep drawing frames to run the deferred works in their idle time.
+        let busy = run_count > 0 || AppCtx::has_deferred_work();
+        let mut wake_at = Timer::recently_timeout();
+        for wnd in AppCtx::windows().borrow().values() {
+          // A window may request a redraw or render continuously without any event.
+          if busy || wnd.need_draw() {
+            match wnd.next_frame_at() {
+              // The frame is held back by the max fps, wake up to draw it.
+              Some(at) => wake_at = Some(wake_at.map_or(at, |t| t.min(at))),
+              None => request_redraw(wnd),
+            }
           }
         }
-        if run_count > 0 {
+        if busy {
           loop_handle.set_control_flow(ControlFlow::Poll);
-        } else if let Some(t) = Timer::recently_timeout() {
-          let control = ControlFlow::wait_duration(t.duration_since(Instant::now()));
+        } else if let Some(t) = wake_at {
+          let control = ControlFlow::wait_duration(t.duration_since(FrameClock::now()));
           loop_handle.set_control_flow(control);
         } else {
           loop_handle.set_control_flow(ControlFlow::Wait);
@@ -196,6 +298,8 @@ pub struct AppRunGuard {
   root: Option<GenWidget>,
   wnd_attrs: Option<WindowAttributes>,
   theme_initd: bool,
+  #[cfg(feature = "theme-tokens")]
+  theme_file: Option<std::path::PathBuf>,
 }
 
 impl App {
@@ -209,6 +313,19 @@ impl App {
   /// event.
   pub fn event_sender() -> EventSender { EventSender(App::shared().event_loop_proxy.clone()) }
 
+  /// Set the Android activity the application runs in, it must be called in
+  /// the `android_main` before any use of the application.
+  ///
+  /// # Panics
+  ///
+  /// Panics if it's called more than once.
+  #[cfg(target_os = "android")]
+  pub fn set_android_app(app: AndroidApp) {
+    if ANDROID_APP.set(app).is_err() {
+      panic!("The Android activity is already set.");
+    }
+  }
+
   /// Creating a new window using the `root` widget and the specified canvas.
   /// Note: This is exclusive to the web platform.
   #[cfg(target_family = "wasm")]
@@ -219,27 +336,57 @@ impl App {
     let event_loop = event_loop.as_ref().expect(
       " Event loop consumed. You can't create window after `App::exec` called in Web platform.",
     );
-    let shell_wnd = WinitShellWnd::new_with_canvas(canvas, &event_loop, attrs).await;
+    let (present_mode, max_fps) = (attrs.present_mode, attrs.max_fps);
+    let shell_wnd = WinitShellWnd::new_with_canvas(canvas, &event_loop, attrs)
+      .await
+      .unwrap_or_else(|e| panic!("Failed to initialize the renderer of the window: {e}"));
     let wnd = AppCtx::new_window(Box::new(shell_wnd), root);
+    wnd.set_present_mode(present_mode);
+    wnd.set_max_fps(max_fps);
     wnd
   }
 
   /// create a new window with the `root` widget
-  #[allow(clippy::await_holding_refcell_ref)]
+  ///
+  /// # Panics
+  ///
+  /// Panics if the renderer of the window can't be initialized, use
+  /// [`App::try_new_window`] to handle the error.
   pub async fn new_window(root: GenWidget, attrs: WindowAttributes) -> Sc<Window> {
+    Self::try_new_window(root, attrs)
+      .await
+      .unwrap_or_else(|e| panic!("Failed to initialize the renderer of the window: {e}"))
+  }
+
+  /// create a new window with the `root` widget, return an error if the
+  /// renderer of the window can't be initialized, so the application can
+  /// degrade gracefully, such as showing a message to the user.
+  #[allow(clippy::await_holding_refcell_ref)]
+  pub async fn try_new_window(
+    root: GenWidget, attrs: WindowAttributes,
+  ) -> Result<Sc<Window>, RenderInitError> {
     let app = App::shared();
     let event_loop = app.event_loop.borrow();
     let event_loop = event_loop.as_ref().expect(
       " Event loop consumed. You can't create window after `App::exec` called in Web platform.",
     );
-    let shell_wnd = WinitShellWnd::new(event_loop, attrs).await;
+    let backdrop = attrs.backdrop;
+    let (present_mode, max_fps) = (attrs.present_mode, attrs.max_fps);
+    let shell_wnd = WinitShellWnd::new(event_loop, attrs).await?;
     let wnd = AppCtx::new_window(Box::new(shell_wnd), root);
+    wnd.set_present_mode(present_mode);
+    wnd.set_max_fps(max_fps);
+    if backdrop != WindowBackdrop::None {
+      wnd.set_flags(wnd.flags() | WindowFlags::BACKDROP);
+    }
+    #[cfg(all(feature = "tray", not(target_family = "wasm")))]
+    crate::tray::init_menu_bar_for(&wnd);
 
     #[cfg(not(target_family = "wasm"))]
     if app.active_wnd.get().is_none() {
       app.active_wnd.set(Some(wnd.id()));
     }
-    wnd
+    Ok(wnd)
   }
 
   #[cfg(not(target_family = "wasm"))]
@@ -272,7 +419,7 @@ impl App {
   /// thread until the application exit.
   #[track_caller]
   pub fn exec() {
-    #[cfg(not(target_family = "wasm"))]
+    #[cfg(not(any(target_family = "wasm", target_os = "ios")))]
     {
       use winit::platform::run_on_demand::EventLoopExtRunOnDemand;
       let mut event_loop = App::shared().event_loop.borrow_mut();
@@ -282,6 +429,14 @@ impl App {
         .run_on_demand(App::event_loop_handle);
     }
 
+    // The event loop of iOS never returns, it takes over the main thread.
+    #[cfg(target_os = "ios")]
+    {
+      let event_loop = App::shared().event_loop.borrow_mut().take();
+      let event_loop = event_loop.expect("Event loop consumed.");
+      let _ = event_loop.run(App::event_loop_handle);
+    }
+
     #[cfg(target_family = "wasm")]
     {
       use winit::platform::web::EventLoopExtWebSys;
@@ -294,19 +449,58 @@ impl App {
     }
   }
 
+  /// Process the pending events of the application and return, instead of
+  /// blocking the thread like [`App::exec`]. The host application that owns
+  /// the event loop calls it from its own loop to drive the Ribir windows.
+  ///
+  /// It waits at most `timeout` for new events, `None` means waiting until an
+  /// event arrives. Returns `false` if the application has exited.
+  #[cfg(not(any(target_family = "wasm", target_os = "ios")))]
+  pub fn pump_events(timeout: Option<std::time::Duration>) -> bool {
+    use winit::platform::pump_events::{EventLoopExtPumpEvents, PumpStatus};
+    let mut event_loop = App::shared().event_loop.borrow_mut();
+    let status = event_loop
+      .as_mut()
+      .unwrap()
+      .pump_events(timeout, App::event_loop_handle);
+    matches!(status, PumpStatus::Continue)
+  }
+
+  /// Sync the reduced motion preference of the operating system to the
+  /// animation time scale.
+  fn sync_reduced_motion() {
+    if let Some(reduced) = prefers_reduced_motion() {
+      let scale = AppCtx::animation_time_scale();
+      if scale.read().reduced_motion != reduced {
+        scale.write().reduced_motion = reduced;
+      }
+    }
+  }
+
   #[track_caller]
   fn shared() -> &'static App {
     static APP: LazyLock<LocalSender<App>> = LazyLock::new(|| {
-      let event_loop = EventLoopBuilder::with_user_event()
-        .build()
-        .unwrap();
+      #[allow(unused_mut)]
+      let mut builder = EventLoopBuilder::with_user_event();
+      #[cfg(target_os = "android")]
+      {
+        use winit::platform::android::EventLoopBuilderExtAndroid;
+        let app = ANDROID_APP
+          .get()
+          .expect("Call `App::set_android_app` in `android_main` before using the application.");
+        builder.with_android_app(app.clone());
+      }
+      let event_loop = builder.build().unwrap();
       let waker = EventWaker(event_loop.create_proxy());
 
-      #[cfg(not(target_family = "wasm"))]
+      #[cfg(not(any(target_family = "wasm", target_os = "android", target_os = "ios")))]
       AppCtx::set_clipboard(Box::new(crate::clipboard::Clipboard::new().unwrap()));
       AppCtx::set_runtime_waker(Box::new(waker));
 
       register_platform_app_events_handlers();
+      #[cfg(all(feature = "tray", not(target_family = "wasm")))]
+      crate::tray::register_menu_events_handler(event_loop.create_proxy());
+      App::sync_reduced_motion();
       let app = App {
         event_loop_proxy: event_loop.create_proxy(),
         event_loop: RefCell::new(Some(event_loop)),
@@ -326,7 +520,13 @@ impl AppRunGuard {
     assert!(!ONCE.is_completed(), "App::run can only be called once.");
     ONCE.call_once(|| {});
 
-    Self { root: Some(root), wnd_attrs: Some(Default::default()), theme_initd: false }
+    Self {
+      root: Some(root),
+      wnd_attrs: Some(Default::default()),
+      theme_initd: false,
+      #[cfg(feature = "theme-tokens")]
+      theme_file: None,
+    }
   }
 
   /// Set the application theme, this will apply to whole application.
@@ -336,6 +536,15 @@ impl AppRunGuard {
     self
   }
 
+  /// Override the application theme with the tokens of the JSON theme file
+  /// when the application starts, see [`ThemeTokens`]. In the debug build,
+  /// the file is reloaded when it's modified.
+  #[cfg(feature = "theme-tokens")]
+  pub fn with_theme_file(&mut self, path: impl Into<std::path::PathBuf>) -> &mut Self {
+    self.theme_file = Some(path.into());
+    self
+  }
+
   /// Sets the initial title of the window in the title bar.
   ///
   /// The default is `"Ribir App"`.
@@ -393,12 +602,47 @@ impl AppRunGuard {
     self
   }
 
+  /// Sets whether the window should be borderless fullscreen when it is first
+  /// shown.
+  pub fn with_fullscreen(&mut self, fullscreen: bool) -> &mut Self {
+    self.wnd_attr().fullscreen = fullscreen;
+    self
+  }
+
   /// Sets the icon of the window.
   pub fn with_icon(&mut self, icon: Resource<PixelImage>) -> &mut Self {
     self.wnd_attr().icon = Some(icon);
     self
   }
 
+  /// Sets whether the window draws its frames on a dedicated render thread,
+  /// see [`WindowAttributes::with_render_thread`].
+  pub fn with_render_thread(&mut self, render_thread: bool) -> &mut Self {
+    self.wnd_attr().render_thread = render_thread;
+    self
+  }
+
+  /// Sets the platform material shows through the window, see
+  /// [`WindowAttributes::with_backdrop`].
+  pub fn with_backdrop(&mut self, backdrop: WindowBackdrop) -> &mut Self {
+    self.wnd_attr().backdrop = backdrop;
+    self
+  }
+
+  /// Sets how the frames of the window are presented, see
+  /// [`WindowAttributes::with_present_mode`].
+  pub fn with_present_mode(&mut self, mode: PresentMode) -> &mut Self {
+    self.wnd_attr().present_mode = mode;
+    self
+  }
+
+  /// Sets the most frames the window draws per second, see
+  /// [`WindowAttributes::with_max_fps`].
+  pub fn with_max_fps(&mut self, fps: Option<u32>) -> &mut Self {
+    self.wnd_attr().max_fps = fps;
+    self
+  }
+
   fn wnd_attr(&mut self) -> &mut WindowAttributes {
     // Should be safe to unwrap because `wnd_attrs` is always `Some` before
     // drop.
@@ -413,6 +657,12 @@ impl Drop for AppRunGuard {
     if !self.theme_initd {
       AppCtx::set_app_theme(ribir_material::purple::light());
     }
+    #[cfg(feature = "theme-tokens")]
+    if let Some(path) = self.theme_file.take() {
+      if let Err(e) = AppCtx::load_theme_file(&path) {
+        log::error!("Failed to load the theme file {}: {e}", path.display());
+      }
+    }
 
     let root = self.root.take().unwrap();
     let attr = self.wnd_attrs.take().unwrap();
@@ -447,6 +697,13 @@ impl RuntimeWaker for EventWaker {
 /// EventWaker only send `RibirEvent::FuturesWake`.
 unsafe impl Send for EventWaker {}
 
+fn shell_of(wnd: &Window, f: impl FnOnce(&mut WinitShellWnd)) {
+  let mut shell = wnd.shell_wnd().borrow_mut();
+  if let Some(shell) = shell.as_any_mut().downcast_mut::<WinitShellWnd>() {
+    f(shell);
+  }
+}
+
 pub(crate) fn request_redraw(wnd: &Window) {
   let wnd = wnd.shell_wnd().borrow();
   let shell = wnd
@@ -514,11 +771,75 @@ impl WindowAttributes {
     self
   }
 
+  /// Sets whether the window should be borderless fullscreen on its monitor
+  /// when it is first shown. The default is `false`.
+  pub fn with_fullscreen(&mut self, fullscreen: bool) -> &mut Self {
+    self.fullscreen = fullscreen;
+    self
+  }
+
   /// Sets the icon of the window.
   pub fn with_icon(&mut self, icon: Resource<PixelImage>) -> &mut Self {
     self.icon = Some(icon);
     self
   }
+
+  /// Sets whether the window draws its frames on a dedicated render thread.
+  /// The UI thread only produces the paint commands of the frames, the
+  /// tessellation and the GPU submission run on the render thread, so the
+  /// events are still processed in time when the paint work is heavy.
+  ///
+  /// The default is `false`. It's not supported on the web.
+  pub fn with_render_thread(&mut self, render_thread: bool) -> &mut Self {
+    self.render_thread = render_thread;
+    self
+  }
+
+  /// Sets the platform material shows through the window. The window is
+  /// created transparent, and the `Vibrancy` widgets mark the areas where the
+  /// material shows through, the rest is filled with the surface color.
+  ///
+  /// The default is `WindowBackdrop::None`. The platform may not support it,
+  /// then the `Vibrancy` areas are just transparent.
+  pub fn with_backdrop(&mut self, backdrop: WindowBackdrop) -> &mut Self {
+    self.backdrop = backdrop;
+    self
+  }
+
+  /// Sets how the frames of the window are presented to the screen, it can be
+  /// switched by `Window::set_present_mode` after the window is created.
+  ///
+  /// The default is `PresentMode::Fifo`, which waits for the vsync.
+  pub fn with_present_mode(&mut self, mode: PresentMode) -> &mut Self {
+    self.present_mode = mode;
+    self
+  }
+
+  /// Sets the most frames the window draws per second, it can be changed by
+  /// `Window::set_max_fps` after the window is created.
+  ///
+  /// The default is `None`, the frames are only limited by the present mode.
+  pub fn with_max_fps(&mut self, fps: Option<u32>) -> &mut Self {
+    self.max_fps = fps;
+    self
+  }
+
+  /// Sets the native window that the window is created in as a child, this is
+  /// how a host application embeds the Ribir UI in its own window. The window
+  /// is confined to the client area of the parent, so usually set its
+  /// position, size and disable its decorations as well.
+  ///
+  /// It's supported on Windows, macOS and X11.
+  ///
+  /// # Safety
+  ///
+  /// The `parent` must be a valid window handle and outlive the window.
+  pub unsafe fn with_parent_window(
+    &mut self, parent: winit::raw_window_handle::RawWindowHandle,
+  ) -> &mut Self {
+    self.parent_window = Some(parent);
+    self
+  }
 }
 
 impl Default for WindowAttributes {
@@ -533,7 +854,13 @@ impl Default for WindowAttributes {
       maximized: false,
       visible: true,
       decorations: true,
+      fullscreen: false,
       icon: None,
+      render_thread: false,
+      backdrop: WindowBackdrop::None,
+      present_mode: PresentMode::Fifo,
+      max_fps: None,
+      parent_window: None,
     }
   }
 }
@@ -541,7 +868,7 @@ impl Default for WindowAttributes {
 #[cfg(test)]
 mod tests {
 
-  use ribir_core::{prelude::*, test_helper::*};
+  use ribir_core::{prelude::*, reset_test_env, test_helper::*};
   use winit::event::Ime;
 
   use super::App;
@@ -617,4 +944,30 @@ mod tests {
       "on_tap",
     ]);
   }
+
+  #[test]
+  fn ime_refresh_viewport_insets() {
+    reset_test_env!();
+
+    let wnd = TestWindow::new(fn_widget! { @MockBox { size: Size::zero() } });
+    let insets = wnd.viewport_insets();
+    let keyboard = EdgeInsets::only_bottom(300.);
+    let set_shell_insets = |insets| {
+      wnd
+        .shell_wnd()
+        .borrow_mut()
+        .as_any_mut()
+        .downcast_mut::<TestShellWindow>()
+        .unwrap()
+        .viewport_insets = insets;
+    };
+
+    set_shell_insets(keyboard);
+    App::process_winit_ime_event(&wnd, Ime::Enabled);
+    assert_eq!(*insets.read(), keyboard);
+
+    set_shell_insets(EdgeInsets::ZERO);
+    App::process_winit_ime_event(&wnd, Ime::Disabled);
+    assert_eq!(*insets.read(), EdgeInsets::ZERO);
+  }
 }
@@ -1,9 +1,127 @@
+use ribir_core::{
+  prelude::{Color, DeviceRect, DeviceSize, PaintCommand, PixelImage, Transform},
+  window::{BackendTimings, PresentMode},
+};
+
+use crate::winit_shell_wnd::WinitBackend;
+
 #[cfg(feature = "wgpu")]
 mod wgpu_backend;
-#[cfg(feature = "wgpu")]
+#[cfg(all(feature = "wgpu", not(feature = "soft")))]
 pub(crate) use wgpu_backend::WgpuBackend as Backend;
 
-#[cfg(not(any(feature = "wgpu")))]
+#[cfg(feature = "soft")]
+mod soft_backend;
+#[cfg(all(feature = "soft", not(feature = "wgpu")))]
+pub(crate) use soft_backend::SoftBackend as Backend;
+
+#[cfg(all(feature = "wgpu", feature = "soft"))]
+mod fallback_backend;
+#[cfg(all(feature = "wgpu", feature = "soft"))]
+pub(crate) use fallback_backend::FallbackBackend as Backend;
+
+#[cfg(not(any(feature = "wgpu", feature = "soft")))]
 mod mock_backend;
-#[cfg(not(any(feature = "wgpu")))]
+#[cfg(not(any(feature = "wgpu", feature = "soft")))]
 pub(crate) use mock_backend::MockBackend as Backend;
+
+#[cfg(not(target_family = "wasm"))]
+mod render_thread;
+#[cfg(not(target_family = "wasm"))]
+use render_thread::RenderThread;
+
+/// The backend of a window, it draws the frames on the UI thread or on a
+/// dedicated render thread.
+pub(crate) enum ShellBackend {
+  Local(Box<Backend<'static>>),
+  #[cfg(not(target_family = "wasm"))]
+  Thread(RenderThread),
+}
+
+impl ShellBackend {
+  /// Create the backend of the window, the `backend` is moved to a dedicated
+  /// render thread if `render_thread` is true and the platform supports it.
+  pub(crate) fn new(backend: Backend<'static>, render_thread: bool) -> Self {
+    #[cfg(not(target_family = "wasm"))]
+    if render_thread {
+      return ShellBackend::Thread(RenderThread::new(backend));
+    }
+    #[cfg(target_family = "wasm")]
+    let _ = render_thread;
+    ShellBackend::Local(Box::new(backend))
+  }
+
+  pub(crate) fn on_resize(&mut self, size: DeviceSize) {
+    match self {
+      ShellBackend::Local(backend) => backend.on_resize(size),
+      #[cfg(not(target_family = "wasm"))]
+      ShellBackend::Thread(thread) => thread.on_resize(size),
+    }
+  }
+
+  pub(crate) fn on_scale_changed(&mut self) {
+    match self {
+      ShellBackend::Local(backend) => backend.on_scale_changed(),
+      #[cfg(not(target_family = "wasm"))]
+      ShellBackend::Thread(thread) => thread.on_scale_changed(),
+    }
+  }
+
+  pub(crate) fn set_present_mode(&mut self, mode: PresentMode) {
+    match self {
+      ShellBackend::Local(backend) => backend.set_present_mode(mode),
+      #[cfg(not(target_family = "wasm"))]
+      ShellBackend::Thread(thread) => thread.set_present_mode(mode),
+    }
+  }
+
+  pub(crate) fn begin_frame(&mut self, surface: Color) {
+    match self {
+      ShellBackend::Local(backend) => backend.begin_frame(surface),
+      #[cfg(not(target_family = "wasm"))]
+      ShellBackend::Thread(thread) => thread.begin_frame(surface),
+    }
+  }
+
+  pub(crate) fn draw_commands(
+    &mut self, viewport: DeviceRect, damage: &[DeviceRect], global_matrix: &Transform,
+    commands: &[PaintCommand],
+  ) {
+    match self {
+      ShellBackend::Local(backend) => {
+        backend.draw_commands(viewport, damage, global_matrix, commands)
+      }
+      #[cfg(not(target_family = "wasm"))]
+      ShellBackend::Thread(thread) => {
+        thread.draw_commands(viewport, damage, global_matrix, commands)
+      }
+    }
+  }
+
+  pub(crate) fn end_frame(&mut self) {
+    match self {
+      ShellBackend::Local(backend) => backend.end_frame(),
+      #[cfg(not(target_family = "wasm"))]
+      ShellBackend::Thread(thread) => thread.end_frame(),
+    }
+  }
+
+  pub(crate) fn render_image(
+    &mut self, size: DeviceSize, global_matrix: &Transform, commands: &[PaintCommand],
+    surface: Color,
+  ) -> Option<PixelImage> {
+    match self {
+      ShellBackend::Local(backend) => backend.render_image(size, global_matrix, commands, surface),
+      #[cfg(not(target_family = "wasm"))]
+      ShellBackend::Thread(thread) => thread.render_image(size, global_matrix, commands, surface),
+    }
+  }
+
+  pub(crate) fn timings(&self) -> Option<BackendTimings> {
+    match self {
+      ShellBackend::Local(backend) => backend.timings(),
+      #[cfg(not(target_family = "wasm"))]
+      ShellBackend::Thread(thread) => thread.timings(),
+    }
+  }
+}
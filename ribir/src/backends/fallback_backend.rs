@@ -0,0 +1,74 @@
+use ribir_core::{
+  prelude::{Color, DeviceRect, DeviceSize, PaintCommand, PixelImage, Transform, log},
+  window::{BackendTimings, PresentMode},
+};
+use ribir_gpu::error::RenderInitError;
+
+use super::{soft_backend::SoftBackend, wgpu_backend::WgpuBackend};
+use crate::winit_shell_wnd::WinitBackend;
+
+/// The backend draws by the GPU, and falls back to draw by the CPU if the GPU
+/// renderer can't be initialized, such as no GPU adapter is found.
+// A window only has one backend, so the size of the variants doesn't matter.
+#[allow(clippy::large_enum_variant)]
+pub enum FallbackBackend<'a> {
+  Gpu(WgpuBackend<'a>),
+  Soft(SoftBackend<'a>),
+}
+
+macro_rules! dispatch {
+  ($this:ident, $b:ident => $e:expr) => {
+    match $this {
+      FallbackBackend::Gpu($b) => $e,
+      FallbackBackend::Soft($b) => $e,
+    }
+  };
+}
+
+impl<'a> WinitBackend<'a> for FallbackBackend<'a> {
+  async fn new(window: &'a winit::window::Window) -> Result<FallbackBackend<'a>, RenderInitError> {
+    match WgpuBackend::new(window).await {
+      Ok(gpu) => Ok(FallbackBackend::Gpu(gpu)),
+      Err(e) => {
+        log::warn!("Failed to initialize the GPU renderer: {e}, fall back to the software one.");
+        SoftBackend::new(window)
+          .await
+          .map(FallbackBackend::Soft)
+      }
+    }
+  }
+
+  fn on_resize(&mut self, size: DeviceSize) { dispatch!(self, b => b.on_resize(size)) }
+
+  fn on_scale_changed(&mut self) { dispatch!(self, b => b.on_scale_changed()) }
+
+  fn set_transparent(&mut self, transparent: bool) {
+    dispatch!(self, b => b.set_transparent(transparent))
+  }
+
+  fn set_present_mode(&mut self, mode: PresentMode) {
+    dispatch!(self, b => b.set_present_mode(mode))
+  }
+
+  fn begin_frame(&mut self, surface_color: Color) {
+    dispatch!(self, b => b.begin_frame(surface_color))
+  }
+
+  fn draw_commands(
+    &mut self, viewport: DeviceRect, damage: &[DeviceRect], global_matrix: &Transform,
+    commands: &[PaintCommand],
+  ) {
+    dispatch!(self, b => b.draw_commands(viewport, damage, global_matrix, commands))
+  }
+
+  fn end_frame(&mut self) { dispatch!(self, b => b.end_frame()) }
+
+  fn timings(&self) -> Option<BackendTimings> { dispatch!(self, b => b.timings()) }
+
+  fn render_image(
+    &mut self, size: DeviceSize, global_matrix: &Transform, commands: &[PaintCommand],
+    surface: Color,
+  ) -> Option<PixelImage> {
+    dispatch!(self, b => b.render_image(size, global_matrix, commands, surface))
+  }
+}
@@ -0,0 +1,253 @@
+use std::{
+  sync::{
+    Arc, Mutex,
+    mpsc::{Receiver, SyncSender, sync_channel},
+  },
+  thread::JoinHandle,
+};
+
+use ribir_core::{
+  prelude::{Color, DeviceRect, DeviceSize, PaintCommand, PixelImage, Transform, log},
+  window::{BackendTimings, PresentMode},
+};
+
+use crate::winit_shell_wnd::WinitBackend;
+
+/// Run a backend on a dedicated thread. The UI thread records every frame as a
+/// frame description and sends it to the render thread, which tessellates and
+/// submits it to the GPU. So the UI thread can process the events while the
+/// heavy paint work is in progress.
+pub(crate) struct RenderThread {
+  sender: Option<SyncSender<RenderMsg>>,
+  handle: Option<JoinHandle<()>>,
+  frame: Option<FrameDesc>,
+  /// The timings of the last frame drawn by the render thread.
+  timings: Arc<Mutex<Option<BackendTimings>>>,
+}
+
+enum RenderMsg {
+  Resize(DeviceSize),
+  ScaleChanged,
+  PresentMode(PresentMode),
+  Frame(FrameDesc),
+  Image(ImageDesc),
+}
+
+/// The retained description of a frame, all the draws of the frame in order.
+struct FrameDesc {
+  surface: Color,
+  draws: Vec<FrameDraw>,
+}
+
+/// An image to render out of the frames, the result is sent back by `reply`.
+struct ImageDesc {
+  size: DeviceSize,
+  global_matrix: Transform,
+  commands: Vec<PaintCommand>,
+  surface: Color,
+  reply: SyncSender<Option<PixelImage>>,
+}
+
+struct FrameDraw {
+  viewport: DeviceRect,
+  damage: Vec<DeviceRect>,
+  global_matrix: Transform,
+  commands: Vec<PaintCommand>,
+}
+
+impl RenderThread {
+  pub(crate) fn new<B>(backend: B) -> Self
+  where
+    B: WinitBackend<'static> + Send + 'static,
+  {
+    // Only one frame can wait for the render thread, the UI thread blocks if it
+    // produces the frames faster than the render thread draws them.
+    let (sender, receiver) = sync_channel(1);
+    let timings = Arc::new(Mutex::new(None));
+    let c_timings = timings.clone();
+    let handle = std::thread::Builder::new()
+      .name("ribir render".into())
+      .spawn(move || render_loop(backend, receiver, c_timings))
+      .expect("Failed to spawn the render thread.");
+    Self { sender: Some(sender), handle: Some(handle), frame: None, timings }
+  }
+
+  pub(crate) fn timings(&self) -> Option<BackendTimings> { *self.timings.lock().unwrap() }
+
+  pub(crate) fn on_resize(&mut self, size: DeviceSize) { self.send(RenderMsg::Resize(size)); }
+
+  pub(crate) fn on_scale_changed(&mut self) { self.send(RenderMsg::ScaleChanged); }
+
+  pub(crate) fn set_present_mode(&mut self, mode: PresentMode) {
+    self.send(RenderMsg::PresentMode(mode));
+  }
+
+  pub(crate) fn begin_frame(&mut self, surface: Color) {
+    self.frame = Some(FrameDesc { surface, draws: vec![] });
+  }
+
+  pub(crate) fn draw_commands(
+    &mut self, viewport: DeviceRect, damage: &[DeviceRect], global_matrix: &Transform,
+    commands: &[PaintCommand],
+  ) {
+    if let Some(frame) = self.frame.as_mut() {
+      frame.draws.push(FrameDraw {
+        viewport,
+        damage: damage.to_vec(),
+        global_matrix: *global_matrix,
+        commands: commands.to_vec(),
+      });
+    }
+  }
+
+  pub(crate) fn end_frame(&mut self) {
+    if let Some(frame) = self.frame.take() {
+      self.send(RenderMsg::Frame(frame));
+    }
+  }
+
+  /// Render an image on the render thread after the pending frames, and wait
+  /// for it.
+  pub(crate) fn render_image(
+    &mut self, size: DeviceSize, global_matrix: &Transform, commands: &[PaintCommand],
+    surface: Color,
+  ) -> Option<PixelImage> {
+    let (reply, receiver) = sync_channel(1);
+    let commands = commands.to_vec();
+    let desc = ImageDesc { size, global_matrix: *global_matrix, commands, surface, reply };
+    self.send(RenderMsg::Image(desc));
+    receiver.recv().ok().flatten()
+  }
+
+  fn send(&self, msg: RenderMsg) {
+    if let Some(sender) = self.sender.as_ref() {
+      if sender.send(msg).is_err() {
+        log::error!("The render thread is terminated.");
+      }
+    }
+  }
+}
+
+impl Drop for RenderThread {
+  fn drop(&mut self) {
+    // Close the channel, so the render thread exits after the pending frames.
+    self.sender.take();
+    if let Some(handle) = self.handle.take() {
+      let _ = handle.join();
+    }
+  }
+}
+
+fn render_loop<B: WinitBackend<'static>>(
+  mut backend: B, receiver: Receiver<RenderMsg>, timings: Arc<Mutex<Option<BackendTimings>>>,
+) {
+  while let Ok(msg) = receiver.recv() {
+    match msg {
+      RenderMsg::Resize(size) => backend.on_resize(size),
+      RenderMsg::ScaleChanged => backend.on_scale_changed(),
+      RenderMsg::PresentMode(mode) => backend.set_present_mode(mode),
+      RenderMsg::Frame(FrameDesc { surface, draws }) => {
+        backend.begin_frame(surface);
+        for FrameDraw { viewport, damage, global_matrix, commands } in draws {
+          backend.draw_commands(viewport, &damage, &global_matrix, &commands);
+        }
+        backend.end_frame();
+        *timings.lock().unwrap() = backend.timings();
+      }
+      RenderMsg::Image(ImageDesc { size, global_matrix, commands, surface, reply }) => {
+        let img = backend.render_image(size, &global_matrix, &commands, surface);
+        let _ = reply.send(img);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_gpu::error::RenderInitError;
+
+  use super::*;
+
+  struct RecordBackend(Arc<Mutex<Vec<String>>>);
+
+  impl WinitBackend<'static> for RecordBackend {
+    async fn new(_: &'static winit::window::Window) -> Result<Self, RenderInitError> {
+      unreachable!()
+    }
+
+    fn on_resize(&mut self, size: DeviceSize) {
+      self
+        .0
+        .lock()
+        .unwrap()
+        .push(format!("resize {}x{}", size.width, size.height));
+    }
+
+    fn begin_frame(&mut self, _: Color) { self.0.lock().unwrap().push("begin".into()); }
+
+    fn draw_commands(
+      &mut self, _: DeviceRect, damage: &[DeviceRect], _: &Transform, commands: &[PaintCommand],
+    ) {
+      self.0.lock().unwrap().push(format!(
+        "draw {} commands in {} rects",
+        commands.len(),
+        damage.len()
+      ));
+    }
+
+    fn end_frame(&mut self) { self.0.lock().unwrap().push("end".into()); }
+
+    fn render_image(
+      &mut self, size: DeviceSize, _: &Transform, commands: &[PaintCommand], _: Color,
+    ) -> Option<PixelImage> {
+      self
+        .0
+        .lock()
+        .unwrap()
+        .push(format!("image {} commands", commands.len()));
+      let data = vec![0; (size.width * size.height * 4) as usize];
+      let format = ribir_core::prelude::image::ColorFormat::Rgba8;
+      Some(PixelImage::new(data.into(), size.width as u32, size.height as u32, format))
+    }
+  }
+
+  #[test]
+  fn draw_frames_in_order() {
+    let log = Arc::new(Mutex::new(vec![]));
+    let mut thread = RenderThread::new(RecordBackend(log.clone()));
+    let rect = DeviceRect::from_size(DeviceSize::new(10, 10));
+    for _ in 0..2 {
+      thread.begin_frame(Color::WHITE);
+      thread.draw_commands(rect, &[rect], &Transform::identity(), &[PaintCommand::PopClip]);
+      thread.end_frame();
+    }
+    thread.on_resize(DeviceSize::new(20, 20));
+    // Wait for the pending messages.
+    drop(thread);
+
+    let frame = ["begin", "draw 1 commands in 1 rects", "end"];
+    let mut expected = [frame, frame].concat();
+    expected.push("resize 20x20");
+    assert_eq!(*log.lock().unwrap(), expected);
+  }
+
+  #[test]
+  fn render_image_after_frames() {
+    let log = Arc::new(Mutex::new(vec![]));
+    let mut thread = RenderThread::new(RecordBackend(log.clone()));
+    let rect = DeviceRect::from_size(DeviceSize::new(10, 10));
+    thread.begin_frame(Color::WHITE);
+    thread.draw_commands(rect, &[rect], &Transform::identity(), &[]);
+    thread.end_frame();
+
+    let size = DeviceSize::new(4, 2);
+    let img = thread
+      .render_image(size, &Transform::identity(), &[PaintCommand::PopClip], Color::WHITE)
+      .unwrap();
+    assert_eq!((img.width(), img.height()), (4, 2));
+    assert_eq!(
+      *log.lock().unwrap(),
+      ["begin", "draw 0 commands in 1 rects", "end", "image 1 commands"]
+    );
+  }
+}
@@ -0,0 +1,127 @@
+use std::num::NonZeroU32;
+
+use ribir_core::{
+  prelude::{
+    Color, DeviceRect, DeviceSize, PaintCommand, PainterBackend, PixelImage, Transform,
+    image::ColorFormat, log,
+  },
+  window::BackendTimings,
+};
+use ribir_gpu::{
+  GPUBackend, GPUBackendImpl, SoftImpl, SoftTexture, Texture, error::RenderInitError,
+};
+
+use crate::winit_shell_wnd::WinitBackend;
+
+/// The backend draws the frames by the CPU and presents them by `softbuffer`,
+/// it works on the machines without any GPU.
+pub struct SoftBackend<'a> {
+  window: &'a winit::window::Window,
+  backend: GPUBackend<SoftImpl>,
+  /// The last frame, and whether its content is valid.
+  frame: (SoftTexture, bool),
+}
+
+impl<'a> WinitBackend<'a> for SoftBackend<'a> {
+  async fn new(window: &'a winit::window::Window) -> Result<SoftBackend<'a>, RenderInitError> {
+    let size = window.inner_size();
+    let size = DeviceSize::new(size.width as i32, size.height as i32);
+    let mut soft = SoftImpl::new();
+    let frame = soft.new_texture(size, ColorFormat::Rgba8);
+    Ok(SoftBackend { window, backend: GPUBackend::new(soft), frame: (frame, false) })
+  }
+
+  fn on_resize(&mut self, size: DeviceSize) {
+    if size != self.frame.0.size() {
+      let frame = self
+        .backend
+        .get_impl_mut()
+        .new_texture(size, ColorFormat::Rgba8);
+      self.frame = (frame, false);
+    }
+  }
+
+  fn on_scale_changed(&mut self) { self.backend.clear_scaled_caches(); }
+
+  fn begin_frame(&mut self, surface_color: Color) { self.backend.begin_frame(surface_color); }
+
+  fn draw_commands(
+    &mut self, viewport: DeviceRect, damage: &[DeviceRect], global_matrix: &Transform,
+    commands: &[PaintCommand],
+  ) {
+    let (output, valid) = &mut self.frame;
+    if *valid {
+      for rect in damage
+        .iter()
+        .filter_map(|r| r.intersection(&viewport))
+      {
+        self
+          .backend
+          .draw_commands(rect, commands, global_matrix, output);
+      }
+    } else {
+      self
+        .backend
+        .draw_commands(viewport, commands, global_matrix, output);
+      *valid = true;
+    }
+  }
+
+  fn end_frame(&mut self) {
+    self.backend.end_frame();
+    if let Err(e) = self.present() {
+      log::error!("Failed to present the frame: {e}");
+    }
+  }
+
+  fn timings(&self) -> Option<BackendTimings> {
+    let ribir_gpu::DrawTimings { tessellation, submit } = self.backend.last_frame_timings();
+    Some(BackendTimings { tessellation, submit })
+  }
+
+  fn render_image(
+    &mut self, size: DeviceSize, global_matrix: &Transform, commands: &[PaintCommand],
+    surface: Color,
+  ) -> Option<PixelImage> {
+    let rect = DeviceRect::from_size(size);
+    let mut texture = self
+      .backend
+      .get_impl_mut()
+      .new_texture(size, ColorFormat::Rgba8);
+    self.backend.begin_frame(surface);
+    self
+      .backend
+      .draw_commands(rect, commands, global_matrix, &mut texture);
+    let img = texture.copy_as_image(&rect, self.backend.get_impl_mut());
+    self.backend.end_frame();
+    futures::executor::block_on(img)
+      .inspect_err(|e| log::error!("Failed to render the image: {e}"))
+      .ok()
+  }
+}
+
+impl<'a> SoftBackend<'a> {
+  /// Copy the frame to the window. The `softbuffer` surface is created for
+  /// every frame, so the backend can be moved to the render thread.
+  fn present(&self) -> Result<(), softbuffer::SoftBufferError> {
+    let frame = &self.frame.0;
+    let size = frame.size();
+    let (Some(width), Some(height)) =
+      (NonZeroU32::new(size.width as u32), NonZeroU32::new(size.height as u32))
+    else {
+      return Ok(());
+    };
+
+    let context = softbuffer::Context::new(self.window)?;
+    let mut surface = softbuffer::Surface::new(&context, self.window)?;
+    surface.resize(width, height)?;
+    let mut buffer = surface.buffer_mut()?;
+    for (dst, p) in buffer
+      .iter_mut()
+      .zip(frame.pixels().chunks_exact(4))
+    {
+      *dst = u32::from_be_bytes([0, p[0], p[1], p[2]]);
+    }
+    buffer.present()
+  }
+}
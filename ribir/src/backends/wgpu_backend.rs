@@ -1,5 +1,8 @@
-use ribir_core::prelude::{Color, DeviceRect, DeviceSize, PaintCommand, PainterBackend, Transform};
-use ribir_gpu::Surface;
+use ribir_core::{
+  prelude::{Color, DeviceRect, DeviceSize, PaintCommand, PainterBackend, PixelImage, Transform},
+  window::{BackendTimings, PresentMode},
+};
+use ribir_gpu::{Surface, error::RenderInitError};
 
 use crate::winit_shell_wnd::WinitBackend;
 
@@ -9,15 +12,15 @@ pub struct WgpuBackend<'a> {
 }
 
 impl<'a> WinitBackend<'a> for WgpuBackend<'a> {
-  async fn new(window: &'a winit::window::Window) -> WgpuBackend<'a> {
-    let (wgpu, surface) = ribir_gpu::WgpuImpl::new(window).await;
+  async fn new(window: &'a winit::window::Window) -> Result<WgpuBackend<'a>, RenderInitError> {
+    let (wgpu, surface) = ribir_gpu::WgpuImpl::new(window).await?;
     let size = window.inner_size();
     let size = DeviceSize::new(size.width as i32, size.height as i32);
 
     let mut wgpu = WgpuBackend { surface, backend: ribir_gpu::GPUBackend::new(wgpu) };
     wgpu.on_resize(size);
 
-    wgpu
+    Ok(wgpu)
   }
 
   fn on_resize(&mut self, size: DeviceSize) {
@@ -26,21 +29,94 @@ impl<'a> WinitBackend<'a> for WgpuBackend<'a> {
     }
   }
 
+  fn on_scale_changed(&mut self) { self.backend.clear_scaled_caches(); }
+
+  fn set_transparent(&mut self, transparent: bool) {
+    self
+      .surface
+      .set_transparent(transparent, self.backend.get_impl());
+  }
+
+  fn set_present_mode(&mut self, mode: PresentMode) {
+    let mode = match mode {
+      PresentMode::Fifo => wgpu::PresentMode::Fifo,
+      PresentMode::Mailbox => wgpu::PresentMode::Mailbox,
+      PresentMode::Immediate => wgpu::PresentMode::Immediate,
+    };
+    self
+      .surface
+      .set_present_mode(mode, self.backend.get_impl());
+  }
+
   fn begin_frame(&mut self, surface_color: Color) { self.backend.begin_frame(surface_color); }
 
   fn draw_commands(
-    &mut self, viewport: DeviceRect, global_matrix: &Transform, commands: &[PaintCommand],
+    &mut self, viewport: DeviceRect, damage: &[DeviceRect], global_matrix: &Transform,
+    commands: &[PaintCommand],
   ) {
-    self.backend.draw_commands(
-      viewport,
-      commands,
-      global_matrix,
-      self.surface.get_current_texture(),
-    );
+    match self
+      .surface
+      .back_buffer(self.backend.get_impl_mut())
+    {
+      Some((output, true)) => {
+        for rect in damage
+          .iter()
+          .filter_map(|r| r.intersection(&viewport))
+        {
+          self
+            .backend
+            .draw_commands(rect, commands, global_matrix, output);
+        }
+      }
+      Some((output, false)) => {
+        self
+          .backend
+          .draw_commands(viewport, commands, global_matrix, output);
+      }
+      None => self.backend.draw_commands(
+        viewport,
+        commands,
+        global_matrix,
+        self.surface.get_current_texture(),
+      ),
+    }
   }
 
   fn end_frame(&mut self) {
+    self
+      .surface
+      .copy_back_buffer(self.backend.get_impl_mut());
     self.backend.end_frame();
     self.surface.present();
   }
+
+  fn timings(&self) -> Option<BackendTimings> {
+    let ribir_gpu::DrawTimings { tessellation, submit } = self.backend.last_frame_timings();
+    Some(BackendTimings { tessellation, submit })
+  }
+
+  // The image can't be read back synchronously in the browser.
+  #[cfg(not(target_family = "wasm"))]
+  fn render_image(
+    &mut self, size: DeviceSize, global_matrix: &Transform, commands: &[PaintCommand],
+    surface: Color,
+  ) -> Option<PixelImage> {
+    use ribir_core::prelude::{image::ColorFormat, log};
+    use ribir_gpu::{GPUBackendImpl, Texture};
+
+    let rect = DeviceRect::from_size(size);
+    let mut texture = self
+      .backend
+      .get_impl_mut()
+      .new_texture(size, ColorFormat::Rgba8);
+    self.backend.begin_frame(surface);
+    self
+      .backend
+      .draw_commands(rect, commands, global_matrix, &mut texture);
+    let img = texture.copy_as_image(&rect, self.backend.get_impl_mut());
+    self.backend.end_frame();
+    futures::executor::block_on(img)
+      .inspect_err(|e| log::error!("Failed to render the image: {e}"))
+      .ok()
+  }
 }
@@ -33,22 +33,17 @@ impl ribir_core::clipboard::Clipboard for Clipboard {
   }
 
   fn read_img(&mut self) -> Result<PixelImage, Error> {
-    match self.clipboard.get_image() {
-      Ok(img) => {
-        Ok(PixelImage::new(img.bytes, img.width as u32, img.height as u32, ColorFormat::Rgba8))
-      }
-      Err(e) => Err(error_convert(e)),
-    }
+    self
+      .clipboard
+      .get_image()
+      .map(from_image_data)
+      .map_err(error_convert)
   }
 
   fn write_img(&mut self, img: &PixelImage) -> Result<(), Error> {
     self
       .clipboard
-      .set_image(ImageData {
-        width: img.width() as usize,
-        height: img.height() as usize,
-        bytes: Cow::Owned(img.pixel_bytes().to_vec()),
-      })
+      .set_image(to_image_data(img))
       .map_err(error_convert)
   }
 
@@ -65,6 +60,25 @@ impl ribir_core::clipboard::Clipboard for Clipboard {
   fn clear(&mut self) -> Result<(), Error> { self.clipboard.clear().map_err(error_convert) }
 }
 
+/// The clipboard image is always RGBA, the alpha image is copied as a gray
+/// image.
+fn to_image_data(img: &PixelImage) -> ImageData<'static> {
+  let bytes = match img.color_format() {
+    ColorFormat::Rgba8 => img.pixel_bytes().to_vec(),
+    ColorFormat::Alpha8 => img
+      .pixel_bytes()
+      .iter()
+      .flat_map(|a| [*a, *a, *a, u8::MAX])
+      .collect(),
+  };
+  ImageData { width: img.width() as usize, height: img.height() as usize, bytes: Cow::Owned(bytes) }
+}
+
+fn from_image_data(img: ImageData) -> PixelImage {
+  let bytes = Cow::Owned(img.bytes.into_owned());
+  PixelImage::new(bytes, img.width as u32, img.height as u32, ColorFormat::Rgba8)
+}
+
 fn error_convert(err: arboard::Error) -> Error {
   match err {
     arboard::Error::ContentNotAvailable => Error::new(ErrorKind::Other, "ContentNotAvailable"),
@@ -75,3 +89,22 @@ fn error_convert(err: arboard::Error) -> Error {
     e => Error::new(ErrorKind::Other, e),
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn image_data_round_trip() {
+    let rgba = PixelImage::new(Cow::Owned(vec![1, 2, 3, 4, 5, 6, 7, 8]), 2, 1, ColorFormat::Rgba8);
+    let img = from_image_data(to_image_data(&rgba));
+    assert_eq!(img.size(), rgba.size());
+    assert_eq!(img.color_format(), ColorFormat::Rgba8);
+    assert_eq!(img.pixel_bytes(), rgba.pixel_bytes());
+
+    let alpha = PixelImage::new(Cow::Owned(vec![0, 128]), 1, 2, ColorFormat::Alpha8);
+    let data = to_image_data(&alpha);
+    assert_eq!((data.width, data.height), (1, 2));
+    assert_eq!(&*data.bytes, &[0, 0, 0, 255, 128, 128, 128, 255]);
+  }
+}
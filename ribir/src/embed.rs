@@ -0,0 +1,215 @@
+//! Embed the Ribir UI in a host application.
+//!
+//! A host application creates the window inside its own native window by
+//! [`WindowAttributes::with_parent_window`](crate::app::WindowAttributes::with_parent_window)
+//! and drives it with [`App::pump_events`](crate::app::App::pump_events) from
+//! its event loop. The data flows between the host and the UI by:
+//!
+//! - [`UpdateSender`], a thread-safe queue the host pushes the data updates to.
+//!   The updates are dispatched on the UI thread to the handlers registered by
+//!   [`Embed::on_update`], which write them to the states. The updates pushed
+//!   between two frames are applied in a batch, and draw in one frame.
+//! - [`Embed::emit`], the UI emits its declared events to the host callback set
+//!   by [`Embed::set_event_callback`].
+//!
+//! With the `ffi` feature, the [`ffi`] module exports them as C functions.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use ribir::{embed::Embed, prelude::*};
+//!
+//! let count = Stateful::new(0);
+//! Embed::bind_state("count", count.clone_writer(), |data| {
+//!   Some(i32::from_le_bytes(data.try_into().ok()?))
+//! });
+//! Embed::set_event_callback(|name, _| println!("the UI emits {name}"));
+//!
+//! let sender = Embed::update_sender();
+//! std::thread::spawn(move || sender.send("count", 1i32.to_le_bytes().to_vec()));
+//!
+//! App::run(fn_widget! {
+//!   @Text {
+//!     text: pipe!($count.to_string()),
+//!     on_tap: move |_| Embed::emit("tapped", &[]),
+//!   }
+//! });
+//! ```
+
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use futures::{StreamExt, channel::mpsc};
+use ribir_core::prelude::*;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+/// A data update pushed by the host application.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostUpdate {
+  /// The key of the handler that the update is dispatched to.
+  pub key: String,
+  /// The data of the update, it's decoded by the handler.
+  pub payload: Vec<u8>,
+}
+
+/// A thread-safe sender to push the data updates to the UI thread, created by
+/// [`Embed::update_sender`].
+#[derive(Clone)]
+pub struct UpdateSender(mpsc::UnboundedSender<HostUpdate>);
+
+/// The entry of the embedding API, all its methods except
+/// [`UpdateSender::send`] must be called on the UI thread.
+pub struct Embed;
+
+type UpdateHandler = Rc<dyn Fn(&[u8])>;
+type EventCallback = Rc<dyn Fn(&str, &[u8])>;
+
+#[derive(Default)]
+struct EmbedHub {
+  sender: Option<UpdateSender>,
+  handlers: HashMap<String, UpdateHandler>,
+  event_callback: Option<EventCallback>,
+}
+
+thread_local! {
+  static HUB: RefCell<EmbedHub> = RefCell::new(EmbedHub::default());
+}
+
+impl Embed {
+  /// Return the sender to push the data updates, it can be sent to any thread.
+  /// The first call starts dispatching the updates on the UI thread.
+  pub fn update_sender() -> UpdateSender {
+    HUB.with(|hub| {
+      hub
+        .borrow_mut()
+        .sender
+        .get_or_insert_with(|| {
+          let (tx, mut rx) = mpsc::unbounded();
+          AppCtx::spawn_local(async move {
+            while let Some(update) = rx.next().await {
+              Embed::dispatch(update);
+            }
+          })
+          .unwrap();
+          UpdateSender(tx)
+        })
+        .clone()
+    })
+  }
+
+  /// Register the `handler` of the updates with `key`, it replaces the handler
+  /// registered before with the same key.
+  pub fn on_update(key: impl Into<String>, handler: impl Fn(&[u8]) + 'static) {
+    HUB.with(|hub| {
+      hub
+        .borrow_mut()
+        .handlers
+        .insert(key.into(), Rc::new(handler));
+    });
+  }
+
+  /// Write the updates with `key` to the `state`, the updates that `decode`
+  /// returns `None` are ignored.
+  pub fn bind_state<V: 'static>(
+    key: impl Into<String>, state: impl StateWriter<Value = V> + 'static,
+    decode: impl Fn(&[u8]) -> Option<V> + 'static,
+  ) {
+    Embed::on_update(key, move |data| {
+      if let Some(v) = decode(data) {
+        *state.write() = v;
+      }
+    });
+  }
+
+  /// Remove the handler of the updates with `key`.
+  pub fn remove_update_handler(key: &str) {
+    HUB.with(|hub| {
+      hub.borrow_mut().handlers.remove(key);
+    });
+  }
+
+  /// Set the callback of the host application that receives the events emitted
+  /// by [`Embed::emit`].
+  pub fn set_event_callback(callback: impl Fn(&str, &[u8]) + 'static) {
+    HUB.with(|hub| hub.borrow_mut().event_callback = Some(Rc::new(callback)));
+  }
+
+  /// Remove the callback set by [`Embed::set_event_callback`].
+  pub fn clear_event_callback() { HUB.with(|hub| hub.borrow_mut().event_callback = None); }
+
+  /// Emit an event with its `payload` to the host application, it's ignored if
+  /// no callback is set.
+  pub fn emit(name: &str, payload: &[u8]) {
+    // Release the hub before the call, so the callback can emit or replace
+    // itself.
+    let callback = HUB.with(|hub| hub.borrow().event_callback.clone());
+    if let Some(callback) = callback {
+      callback(name, payload);
+    }
+  }
+
+  fn dispatch(update: HostUpdate) {
+    let HostUpdate { key, payload } = update;
+    let handler = HUB.with(|hub| hub.borrow().handlers.get(&key).cloned());
+    if let Some(handler) = handler {
+      handler(&payload);
+    } else {
+      log::warn!("No handler for the host update `{key}`.");
+    }
+  }
+}
+
+impl UpdateSender {
+  /// Push an update to the UI thread, return `false` if the application has
+  /// exited.
+  pub fn send(&self, key: impl Into<String>, payload: Vec<u8>) -> bool {
+    self
+      .0
+      .unbounded_send(HostUpdate { key: key.into(), payload })
+      .is_ok()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::reset_test_env;
+
+  use super::*;
+
+  #[test]
+  fn dispatch_updates() {
+    reset_test_env!();
+
+    let count = Stateful::new(0);
+    Embed::bind_state("count", count.clone_writer(), |data| Some(data.len()));
+    let sender = Embed::update_sender();
+    std::thread::spawn(move || {
+      sender.send("count", vec![0; 3]);
+      sender.send("unknown", vec![]);
+      sender.send("count", vec![0; 5]);
+    })
+    .join()
+    .unwrap();
+
+    AppCtx::run_until_stalled();
+    assert_eq!(*count.read(), 5);
+  }
+
+  #[test]
+  fn emit_events() {
+    reset_test_env!();
+
+    let events = Stateful::new(vec![]);
+    let events2 = events.clone_writer();
+    Embed::set_event_callback(move |name, data| {
+      events2
+        .write()
+        .push((name.to_string(), data.len()))
+    });
+    Embed::emit("tap", &[1, 2]);
+    Embed::emit("hover", &[]);
+
+    assert_eq!(&*events.read(), &[("tap".to_string(), 2), ("hover".to_string(), 0)]);
+  }
+}
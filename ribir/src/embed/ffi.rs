@@ -0,0 +1,127 @@
+//! The C functions of the embedding API, enabled by the `ffi` feature.
+//!
+//! The UI is still declared in Rust, the library that embeds it creates the
+//! window in the native window of the host by [`parent_window_handle`], and
+//! exports these functions for the host to exchange the data with the UI.
+
+use std::{
+  ffi::{CStr, c_char, c_void},
+  time::Duration,
+};
+
+use winit::raw_window_handle::RawWindowHandle;
+
+use super::{Embed, UpdateSender};
+use crate::app::App;
+
+/// The callback of the host to receive the events emitted by the UI, it's
+/// called on the UI thread with the `user_data` passed to
+/// [`ribir_embed_set_event_callback`]. The `name` and `data` are only valid
+/// during the call.
+pub type RibirEventCallback =
+  extern "C" fn(user_data: *mut c_void, name: *const c_char, data: *const u8, len: usize);
+
+/// Convert the native window of the host to the handle of
+/// [`WindowAttributes::with_parent_window`](crate::app::WindowAttributes::with_parent_window).
+/// The `handle` is the `HWND` on Windows, the `NSView` on macOS and the X11
+/// window id on Linux. Return `None` if it's null or the platform isn't
+/// supported.
+///
+/// # Safety
+///
+/// The `handle` must be a valid native window of the platform.
+pub unsafe fn parent_window_handle(handle: *mut c_void) -> Option<RawWindowHandle> {
+  #[cfg(windows)]
+  {
+    use winit::raw_window_handle::Win32WindowHandle;
+    let hwnd = std::num::NonZeroIsize::new(handle as isize)?;
+    Some(RawWindowHandle::Win32(Win32WindowHandle::new(hwnd)))
+  }
+  #[cfg(target_os = "macos")]
+  {
+    use winit::raw_window_handle::AppKitWindowHandle;
+    let ns_view = std::ptr::NonNull::new(handle)?;
+    Some(RawWindowHandle::AppKit(AppKitWindowHandle::new(ns_view)))
+  }
+  #[cfg(all(unix, not(any(target_os = "macos", target_os = "android", target_os = "ios"))))]
+  {
+    use winit::raw_window_handle::XlibWindowHandle;
+    (!handle.is_null()).then(|| RawWindowHandle::Xlib(XlibWindowHandle::new(handle as _)))
+  }
+  #[cfg(not(any(windows, all(unix, not(any(target_os = "android", target_os = "ios"))))))]
+  {
+    let _ = handle;
+    None
+  }
+}
+
+/// Process the pending events of the application, see [`App::pump_events`].
+/// A negative `timeout_ms` waits until an event arrives. Returns `false` if the
+/// application has exited.
+#[cfg(not(any(target_family = "wasm", target_os = "ios")))]
+#[no_mangle]
+pub extern "C" fn ribir_embed_pump_events(timeout_ms: i64) -> bool {
+  let timeout = u64::try_from(timeout_ms)
+    .ok()
+    .map(Duration::from_millis);
+  App::pump_events(timeout)
+}
+
+/// Create a sender of the data updates, see [`Embed::update_sender`]. It must
+/// be called on the UI thread, the sender can be used on any thread and must
+/// be freed by [`ribir_embed_free_update_sender`].
+#[no_mangle]
+pub extern "C" fn ribir_embed_update_sender() -> *mut UpdateSender {
+  Box::into_raw(Box::new(Embed::update_sender()))
+}
+
+/// Push the update of `len` bytes `data` with the `key` to the UI thread.
+/// Returns `false` if the arguments are invalid or the application has exited.
+///
+/// # Safety
+///
+/// The `sender` must be created by [`ribir_embed_update_sender`] and not freed,
+/// the `key` must be a nul-terminated string and the `data` must be valid for
+/// `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ribir_embed_send_update(
+  sender: *const UpdateSender, key: *const c_char, data: *const u8, len: usize,
+) -> bool {
+  let (Some(sender), false) = (sender.as_ref(), key.is_null()) else {
+    return false;
+  };
+  let Ok(key) = CStr::from_ptr(key).to_str() else {
+    return false;
+  };
+  let payload =
+    if data.is_null() { vec![] } else { std::slice::from_raw_parts(data, len).to_vec() };
+  sender.send(key, payload)
+}
+
+/// Free the sender created by [`ribir_embed_update_sender`].
+///
+/// # Safety
+///
+/// The `sender` must be created by [`ribir_embed_update_sender`] and not freed.
+#[no_mangle]
+pub unsafe extern "C" fn ribir_embed_free_update_sender(sender: *mut UpdateSender) {
+  if !sender.is_null() {
+    drop(Box::from_raw(sender));
+  }
+}
+
+/// Set the `callback` to receive the events emitted by the UI, a null
+/// `callback` removes it. It must be called on the UI thread.
+#[no_mangle]
+pub extern "C" fn ribir_embed_set_event_callback(
+  callback: Option<RibirEventCallback>, user_data: *mut c_void,
+) {
+  match callback {
+    Some(callback) => Embed::set_event_callback(move |name, data| {
+      // A name with an inner nul is truncated by the host anyway.
+      let name = std::ffi::CString::new(name.replace('\0', "")).unwrap();
+      callback(user_data, name.as_ptr(), data.as_ptr(), data.len());
+    }),
+    None => Embed::clear_event_callback(),
+  }
+}
@@ -3,9 +3,12 @@ pub use ribir_core as core;
 pub use ribir_widgets as widgets;
 pub mod app;
 mod backends;
+pub mod embed;
 
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
 pub mod clipboard;
+#[cfg(all(feature = "tray", not(target_family = "wasm")))]
+pub mod tray;
 mod winit_shell_wnd;
 #[cfg(feature = "material")]
 pub use ribir_material as material;
@@ -14,6 +17,7 @@ mod platform;
 pub use platform::*;
 pub mod prelude {
   pub use ribir_core::prelude::*;
+  pub use ribir_gpu::error::RenderInitError;
 
   #[cfg(feature = "material")]
   pub use super::material;
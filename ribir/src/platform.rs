@@ -3,7 +3,155 @@
 #[cfg(target_os = "macos")]
 mod macos;
 #[cfg(target_os = "macos")]
-pub use macos::register_platform_app_events_handlers;
+pub use macos::{prefers_reduced_motion, register_platform_app_events_handlers};
 
 #[cfg(not(target_os = "macos"))]
 pub fn register_platform_app_events_handlers() {}
+
+/// Query whether the operating system prefers reduced motion, return `None` if
+/// the platform does not support it.
+#[cfg(windows)]
+pub fn prefers_reduced_motion() -> Option<bool> {
+  use winapi::{
+    shared::minwindef::{BOOL, FALSE, TRUE},
+    um::winuser::{SPI_GETCLIENTAREAANIMATION, SystemParametersInfoW},
+  };
+
+  let mut animation: BOOL = TRUE;
+  let ok = unsafe {
+    SystemParametersInfoW(SPI_GETCLIENTAREAANIMATION, 0, &mut animation as *mut BOOL as *mut _, 0)
+  };
+  (ok != FALSE).then_some(animation == FALSE)
+}
+
+/// Query whether the operating system prefers reduced motion, return `None` if
+/// the platform does not support it.
+#[cfg(target_arch = "wasm32")]
+pub fn prefers_reduced_motion() -> Option<bool> {
+  web_sys::window()?
+    .match_media("(prefers-reduced-motion: reduce)")
+    .ok()
+    .flatten()
+    .map(|m| m.matches())
+}
+
+/// Query whether the operating system prefers reduced motion, return `None` if
+/// the platform does not support it.
+#[cfg(not(any(target_os = "macos", windows, target_arch = "wasm32")))]
+pub fn prefers_reduced_motion() -> Option<bool> { None }
+
+/// Apply the platform material to the window created transparent.
+#[cfg(windows)]
+pub(crate) fn apply_window_backdrop(
+  wnd: &winit::window::Window, backdrop: crate::app::WindowBackdrop,
+) {
+  use winapi::{
+    shared::windef::HWND,
+    um::{
+      dwmapi::{DwmExtendFrameIntoClientArea, DwmSetWindowAttribute},
+      uxtheme::MARGINS,
+    },
+  };
+  use winit::raw_window_handle::{HasWindowHandle, RawWindowHandle};
+
+  use crate::app::WindowBackdrop;
+
+  // The attribute and the values are available since Windows 11 22H2.
+  const DWMWA_SYSTEMBACKDROP_TYPE: u32 = 38;
+  const DWMSBT_MAINWINDOW: i32 = 2;
+  const DWMSBT_TRANSIENTWINDOW: i32 = 3;
+
+  let Ok(handle) = wnd.window_handle() else { return };
+  let RawWindowHandle::Win32(handle) = handle.as_raw() else { return };
+  let hwnd = handle.hwnd.get() as HWND;
+  let kind = match backdrop {
+    WindowBackdrop::None => return,
+    WindowBackdrop::Mica => DWMSBT_MAINWINDOW,
+    WindowBackdrop::Vibrancy | WindowBackdrop::Acrylic => DWMSBT_TRANSIENTWINDOW,
+  };
+  let margins = MARGINS { cxLeftWidth: -1, cxRightWidth: -1, cyTopHeight: -1, cyBottomHeight: -1 };
+  let hr = unsafe {
+    DwmExtendFrameIntoClientArea(hwnd, &margins);
+    DwmSetWindowAttribute(
+      hwnd,
+      DWMWA_SYSTEMBACKDROP_TYPE,
+      &kind as *const i32 as *const _,
+      std::mem::size_of::<i32>() as u32,
+    )
+  };
+  if hr < 0 {
+    log::warn!("The window backdrop {backdrop:?} is not supported: {hr:#x}");
+  }
+}
+
+/// Apply the platform material to the window created transparent.
+#[cfg(not(windows))]
+pub(crate) fn apply_window_backdrop(
+  _wnd: &winit::window::Window, _backdrop: crate::app::WindowBackdrop,
+) {
+}
+
+/// The insets of the window area obscured by the system UI, such as the status
+/// bar, the notch and the home indicator, in logical pixels.
+#[cfg(target_os = "ios")]
+pub(crate) fn viewport_insets(wnd: &winit::window::Window) -> ribir_core::prelude::EdgeInsets {
+  // The inner area of the window is the safe area on iOS.
+  let (Ok(inner), Ok(outer)) = (wnd.inner_position(), wnd.outer_position()) else {
+    return ribir_core::prelude::EdgeInsets::ZERO;
+  };
+  let (inner_size, outer_size) = (wnd.inner_size(), wnd.outer_size());
+  let scale = wnd.scale_factor() as f32;
+  let left = (inner.x - outer.x) as f32;
+  let top = (inner.y - outer.y) as f32;
+  let right = outer_size.width as f32 - inner_size.width as f32 - left;
+  let bottom = outer_size.height as f32 - inner_size.height as f32 - top;
+  ribir_core::prelude::EdgeInsets::new(top / scale, right / scale, bottom / scale, left / scale)
+}
+
+/// The insets of the window area obscured by the system UI, such as the status
+/// bar, the notch, the navigation bar and the on-screen keyboard, in logical
+/// pixels. The content rect of the activity excludes all of them.
+#[cfg(target_os = "android")]
+pub(crate) fn viewport_insets(wnd: &winit::window::Window) -> ribir_core::prelude::EdgeInsets {
+  let Some(app) = crate::app::ANDROID_APP.get() else {
+    return ribir_core::prelude::EdgeInsets::ZERO;
+  };
+  let rect = app.content_rect();
+  if rect.right <= rect.left || rect.bottom <= rect.top {
+    return ribir_core::prelude::EdgeInsets::ZERO;
+  }
+  let size = wnd.inner_size();
+  let scale = wnd.scale_factor() as f32;
+  let right = (size.width as i32 - rect.right).max(0);
+  let bottom = (size.height as i32 - rect.bottom).max(0);
+  ribir_core::prelude::EdgeInsets::new(
+    rect.top.max(0) as f32 / scale,
+    right as f32 / scale,
+    bottom as f32 / scale,
+    rect.left.max(0) as f32 / scale,
+  )
+}
+
+/// The insets of the window area obscured by the system UI, the whole window is
+/// safe on the desktop and the web.
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+pub(crate) fn viewport_insets(_wnd: &winit::window::Window) -> ribir_core::prelude::EdgeInsets {
+  ribir_core::prelude::EdgeInsets::ZERO
+}
+
+/// Show or hide the software keyboard when a text widget gains or loses the
+/// focus. The keyboard follows the IME on iOS and the desktop.
+#[cfg(target_os = "android")]
+pub(crate) fn set_soft_keyboard_visible(visible: bool) {
+  let Some(app) = crate::app::ANDROID_APP.get() else { return };
+  if visible {
+    app.show_soft_input(true);
+  } else {
+    app.hide_soft_input(false);
+  }
+}
+
+/// Show or hide the software keyboard when a text widget gains or loses the
+/// focus. The keyboard follows the IME on iOS and the desktop.
+#[cfg(not(target_os = "android"))]
+pub(crate) fn set_soft_keyboard_visible(_visible: bool) {}
@@ -3,7 +3,7 @@ use std::{ptr::NonNull, sync::Once, time::Duration};
 use icrate::{
   AppKit::{
     NSEvent, NSEventMaskKeyDown, NSEventModifierFlagCommand, NSEventModifierFlagControl,
-    NSEventModifierFlagOption, NSEventModifierFlagShift,
+    NSEventModifierFlagOption, NSEventModifierFlagShift, NSWorkspace,
   },
   Foundation::NSObject,
   block2::ConcreteBlock,
@@ -137,6 +137,12 @@ fn add_global_monitor_for_events_matching_mask_handler() -> Option<Id<AnyObject>
   }
 }
 
+/// Query whether the user prefers reduced motion in the accessibility display
+/// settings.
+pub fn prefers_reduced_motion() -> Option<bool> {
+  Some(unsafe { NSWorkspace::sharedWorkspace().accessibilityDisplayShouldReduceMotion() })
+}
+
 #[cfg(target_os = "macos")]
 fn query_accessibility_permissions() -> bool {
   macos_accessibility_client::accessibility::application_is_trusted()
@@ -0,0 +1,292 @@
+//! The system tray icon and the native menu bar of the application, enabled by
+//! the `tray` feature.
+//!
+//! The menu items are identified by their ids, selecting an item of the tray
+//! menu or the menu bar sends an [`AppEvent::Menu`] to the
+//! [`App::events_stream`], so the widgets and the application callbacks
+//! subscribe to the stream to respond to it.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use ribir::{prelude::*, tray::*};
+//!
+//! let file = Menu::default()
+//!   .with_item(MenuItem::new("open", "Open").with_accelerator("CmdOrCtrl+O"))
+//!   .with_separator()
+//!   .with_item(MenuItem::new("quit", "Quit"));
+//! App::set_menu_bar(Menu::default().with_submenu("File", file));
+//!
+//! App::events_stream().subscribe(|e| {
+//!   if let AppEvent::Menu(MenuEvent { id }) = e {
+//!     println!("{id} selected");
+//!   }
+//! });
+//! ```
+use std::{
+  cell::RefCell,
+  io::{Error, ErrorKind},
+  sync::Mutex,
+};
+
+use ribir_core::prelude::{image::ColorFormat, log::warn, *};
+use tray_icon::menu as native;
+use winit::event_loop::EventLoopProxy;
+
+use crate::prelude::{App, AppEvent};
+
+/// The event sent when a menu item is selected.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MenuEvent {
+  /// The id of the selected [`MenuItem`].
+  pub id: String,
+}
+
+/// The description of a menu, it's converted to the native menu when it's
+/// shown.
+#[derive(Debug, Clone, Default)]
+pub struct Menu {
+  pub entries: Vec<MenuEntry>,
+}
+
+#[derive(Debug, Clone)]
+pub enum MenuEntry {
+  Item(MenuItem),
+  Separator,
+  Submenu { text: String, menu: Menu },
+}
+
+#[derive(Debug, Clone)]
+pub struct MenuItem {
+  pub id: String,
+  pub text: String,
+  pub enabled: bool,
+  /// The keyboard shortcut shown by the item, such as `"CmdOrCtrl+Q"`.
+  pub accelerator: Option<String>,
+}
+
+/// A system tray icon with an optional menu, call [`Tray::show`] to add it to
+/// the system tray.
+///
+/// It's supported on Windows and macOS. On Linux, the tray icon requires the
+/// gtk event loop to run, which the application doesn't drive.
+pub struct Tray {
+  icon: Resource<PixelImage>,
+  tooltip: Option<String>,
+  menu: Option<Menu>,
+}
+
+/// The tray icon shown in the system tray, it's removed when dropped.
+pub struct SystemTray(tray_icon::TrayIcon);
+
+impl Menu {
+  pub fn with_item(mut self, item: MenuItem) -> Self {
+    self.entries.push(MenuEntry::Item(item));
+    self
+  }
+
+  pub fn with_separator(mut self) -> Self {
+    self.entries.push(MenuEntry::Separator);
+    self
+  }
+
+  pub fn with_submenu(mut self, text: impl Into<String>, menu: Menu) -> Self {
+    self
+      .entries
+      .push(MenuEntry::Submenu { text: text.into(), menu });
+    self
+  }
+
+  fn to_native(&self) -> native::Menu {
+    let menu = native::Menu::new();
+    for item in native_items(&self.entries) {
+      if let Err(err) = menu.append(item.as_ref()) {
+        warn!("Failed to append the menu item: {err}");
+      }
+    }
+    menu
+  }
+}
+
+impl MenuItem {
+  pub fn new(id: impl Into<String>, text: impl Into<String>) -> Self {
+    Self { id: id.into(), text: text.into(), enabled: true, accelerator: None }
+  }
+
+  pub fn with_enabled(mut self, enabled: bool) -> Self {
+    self.enabled = enabled;
+    self
+  }
+
+  pub fn with_accelerator(mut self, accelerator: impl Into<String>) -> Self {
+    self.accelerator = Some(accelerator.into());
+    self
+  }
+
+  fn to_native(&self) -> native::MenuItem {
+    let accelerator = self.accelerator.as_ref().and_then(|a| {
+      a.parse::<native::accelerator::Accelerator>()
+        .map_err(|err| warn!("Invalid accelerator `{a}` of the menu item: {err}"))
+        .ok()
+    });
+    native::MenuItem::with_id(self.id.clone(), &self.text, self.enabled, accelerator)
+  }
+}
+
+fn native_items(entries: &[MenuEntry]) -> Vec<Box<dyn native::IsMenuItem>> {
+  entries
+    .iter()
+    .map(|entry| -> Box<dyn native::IsMenuItem> {
+      match entry {
+        MenuEntry::Item(item) => Box::new(item.to_native()),
+        MenuEntry::Separator => Box::new(native::PredefinedMenuItem::separator()),
+        MenuEntry::Submenu { text, menu } => {
+          let submenu = native::Submenu::new(text, true);
+          for item in native_items(&menu.entries) {
+            if let Err(err) = submenu.append(item.as_ref()) {
+              warn!("Failed to append the menu item: {err}");
+            }
+          }
+          Box::new(submenu)
+        }
+      }
+    })
+    .collect()
+}
+
+impl Tray {
+  pub fn new(icon: Resource<PixelImage>) -> Self { Self { icon, tooltip: None, menu: None } }
+
+  pub fn with_tooltip(mut self, tooltip: impl Into<String>) -> Self {
+    self.tooltip = Some(tooltip.into());
+    self
+  }
+
+  /// Sets the menu shown when the tray icon is clicked.
+  pub fn with_menu(mut self, menu: Menu) -> Self {
+    self.menu = Some(menu);
+    self
+  }
+
+  /// Add the icon to the system tray, keep the returned `SystemTray` alive
+  /// as long as the icon should be shown.
+  pub fn show(self) -> Result<SystemTray, Error> {
+    let mut builder = tray_icon::TrayIconBuilder::new().with_icon(tray_icon_of(&self.icon)?);
+    if let Some(tooltip) = self.tooltip {
+      builder = builder.with_tooltip(tooltip);
+    }
+    if let Some(menu) = self.menu {
+      builder = builder.with_menu(Box::new(menu.to_native()));
+    }
+    builder
+      .build()
+      .map(SystemTray)
+      .map_err(error_convert)
+  }
+}
+
+impl SystemTray {
+  pub fn set_icon(&self, icon: &PixelImage) -> Result<(), Error> {
+    self
+      .0
+      .set_icon(Some(tray_icon_of(icon)?))
+      .map_err(error_convert)
+  }
+
+  pub fn set_tooltip(&self, tooltip: Option<&str>) -> Result<(), Error> {
+    self.0.set_tooltip(tooltip).map_err(error_convert)
+  }
+
+  pub fn set_menu(&self, menu: Option<Menu>) {
+    let menu = menu.map(|m| Box::new(m.to_native()) as Box<dyn native::ContextMenu>);
+    self.0.set_menu(menu);
+  }
+}
+
+thread_local! {
+  static MENU_BAR: RefCell<Option<native::Menu>> = const { RefCell::new(None) };
+}
+
+impl App {
+  /// Sets the menu bar of the application, the top level entries should be
+  /// submenus.
+  ///
+  /// It's the application menu on macOS, and the menu bar of every window on
+  /// Windows, include the windows created later. The native menu bar is not
+  /// supported on the other platforms, the menu is ignored.
+  pub fn set_menu_bar(menu: Menu) {
+    let menu = menu.to_native();
+    MENU_BAR.with(|bar| {
+      let mut bar = bar.borrow_mut();
+      #[cfg(windows)]
+      if let Some(old) = bar.take() {
+        for wnd in AppCtx::windows().borrow().values() {
+          // Safety: the window handle is valid as long as the window is alive.
+          with_hwnd(wnd, |hwnd| unsafe { old.remove_for_hwnd(hwnd) });
+        }
+      }
+
+      #[cfg(target_os = "macos")]
+      menu.init_for_nsapp();
+      #[cfg(windows)]
+      for wnd in AppCtx::windows().borrow().values() {
+        // Safety: the window handle is valid as long as the window is alive.
+        with_hwnd(wnd, |hwnd| unsafe { menu.init_for_hwnd(hwnd) });
+      }
+      #[cfg(not(any(target_os = "macos", windows)))]
+      warn!("The native menu bar is not supported on this platform.");
+
+      *bar = Some(menu);
+    });
+  }
+}
+
+/// Add the menu bar of the application to the window just created.
+pub(crate) fn init_menu_bar_for(_wnd: &Window) {
+  #[cfg(windows)]
+  MENU_BAR.with(|bar| {
+    if let Some(menu) = bar.borrow().as_ref() {
+      // Safety: the window handle is valid as long as the window is alive.
+      with_hwnd(_wnd, |hwnd| unsafe { menu.init_for_hwnd(hwnd) });
+    }
+  });
+}
+
+#[cfg(windows)]
+fn with_hwnd(wnd: &Window, f: impl FnOnce(isize) -> Result<(), native::Error>) {
+  use winit::raw_window_handle::{HasWindowHandle, RawWindowHandle};
+
+  let shell = wnd.shell_wnd().borrow();
+  let Some(shell) = shell
+    .as_any()
+    .downcast_ref::<crate::winit_shell_wnd::WinitShellWnd>()
+  else {
+    return;
+  };
+  let Ok(handle) = shell.winit_wnd.window_handle() else { return };
+  let RawWindowHandle::Win32(handle) = handle.as_raw() else { return };
+  if let Err(err) = f(handle.hwnd.get()) {
+    warn!("Failed to set the menu bar of the window: {err}");
+  }
+}
+
+/// Forward the selections of the native menus to the application event loop.
+pub(crate) fn register_menu_events_handler(proxy: EventLoopProxy<AppEvent>) {
+  let proxy = Mutex::new(proxy);
+  native::MenuEvent::set_event_handler(Some(move |e: native::MenuEvent| {
+    let event = AppEvent::Menu(MenuEvent { id: e.id.0 });
+    let _ = proxy.lock().unwrap().send_event(event);
+  }));
+}
+
+fn tray_icon_of(icon: &PixelImage) -> Result<tray_icon::Icon, Error> {
+  if icon.color_format() != ColorFormat::Rgba8 {
+    return Err(Error::new(ErrorKind::InvalidInput, "The tray icon must be `Rgba8`."));
+  }
+  tray_icon::Icon::from_rgba(icon.pixel_bytes().to_vec(), icon.width(), icon.height())
+    .map_err(error_convert)
+}
+
+fn error_convert(err: impl std::fmt::Display) -> Error {
+  Error::new(ErrorKind::Other, err.to_string())
+}
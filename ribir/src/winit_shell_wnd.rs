@@ -2,35 +2,75 @@ use std::future::Future;
 
 use ribir_core::{
   prelude::{image::ColorFormat, *},
-  window::{ShellWindow, WindowId},
+  window::{BackendTimings, ImePurpose, PresentMode, ShellWindow, WindowId},
 };
+use ribir_gpu::error::RenderInitError;
 use winit::{
   dpi::{LogicalPosition, LogicalSize},
   event_loop::EventLoopWindowTarget,
+  window::Fullscreen,
 };
 
 use crate::{
   backends::*,
-  prelude::{WindowAttributes, request_redraw},
+  platform::{self, apply_window_backdrop},
+  prelude::{WindowAttributes, WindowBackdrop, request_redraw},
 };
 pub trait WinitBackend<'a>: Sized {
-  fn new(window: &'a winit::window::Window) -> impl Future<Output = Self>;
+  fn new(window: &'a winit::window::Window) -> impl Future<Output = Result<Self, RenderInitError>>;
 
   fn on_resize(&mut self, size: DeviceSize);
 
+  /// Called after the scale factor of the window changed, the backend releases
+  /// the caches rendered at the old scale.
+  fn on_scale_changed(&mut self) {}
+
+  /// Set whether the frames are composited with the backdrop of the window by
+  /// their alpha, the backend ignores it if not supported.
+  fn set_transparent(&mut self, _transparent: bool) {}
+
+  /// Set how the frames are presented, the backend ignores it if not
+  /// supported.
+  fn set_present_mode(&mut self, _mode: PresentMode) {}
+
   fn begin_frame(&mut self, surface_color: Color);
 
+  /// Draw the commands to the window, only the `damage` area needs to be
+  /// redrawn if the backend keeps the content of the last frame.
   fn draw_commands(
-    &mut self, viewport: DeviceRect, global_matrix: &Transform, commands: &[PaintCommand],
+    &mut self, viewport: DeviceRect, damage: &[DeviceRect], global_matrix: &Transform,
+    commands: &[PaintCommand],
   );
 
   fn end_frame(&mut self);
+
+  /// The time spent on drawing the last finished frame, `None` if the backend
+  /// doesn't measure it.
+  fn timings(&self) -> Option<BackendTimings> { None }
+
+  /// Render the commands to an image of `size` out of the frame, `None` if the
+  /// backend can't render to an image.
+  fn render_image(
+    &mut self, _size: DeviceSize, _global_matrix: &Transform, _commands: &[PaintCommand],
+    _surface: Color,
+  ) -> Option<PixelImage> {
+    None
+  }
 }
 
 pub struct WinitShellWnd {
+  // The backend holds a surface created from a reference to `winit_wnd`, and
+  // the render thread may still present to it until it's joined, so it must be
+  // dropped before the window. Fields are dropped in declaration order.
+  //
+  // It's `None` while the application is suspended, the platform may destroy
+  // the surface of the window, such as Android.
+  backend: Option<ShellBackend>,
   pub(crate) winit_wnd: winit::window::Window,
-  backend: Backend<'static>,
   cursor: CursorIcon,
+  render_thread: bool,
+  transparent: bool,
+  present_mode: PresentMode,
 }
 
 impl ShellWindow for WinitShellWnd {
@@ -72,7 +112,16 @@ impl ShellWindow for WinitShellWnd {
       .ceil()
       .to_i32()
       .cast_unit();
-    self.backend.on_resize(size);
+    if let Some(backend) = self.backend.as_mut() {
+      backend.on_resize(size);
+    }
+  }
+
+  fn on_device_pixel_ratio_changed(&mut self) {
+    self.on_resize(self.inner_size());
+    if let Some(backend) = self.backend.as_mut() {
+      backend.on_scale_changed();
+    }
   }
 
   fn set_min_size(&mut self, size: Size) {
@@ -81,6 +130,12 @@ impl ShellWindow for WinitShellWnd {
       .set_min_inner_size(Some(LogicalSize::new(size.width, size.height)))
   }
 
+  fn set_max_size(&mut self, size: Size) {
+    self
+      .winit_wnd
+      .set_max_inner_size(Some(LogicalSize::new(size.width, size.height)))
+  }
+
   fn set_cursor(&mut self, cursor: CursorIcon) {
     self.cursor = cursor;
     self.winit_wnd.set_cursor_icon(cursor)
@@ -137,7 +192,47 @@ impl ShellWindow for WinitShellWnd {
   fn set_decorations(&mut self, decorations: bool) { self.winit_wnd.set_decorations(decorations) }
 
   #[inline]
-  fn set_ime_allowed(&mut self, allowed: bool) { self.winit_wnd.set_ime_allowed(allowed); }
+  fn is_fullscreen(&self) -> bool { self.winit_wnd.fullscreen().is_some() }
+
+  fn set_fullscreen(&mut self, fullscreen: bool) {
+    let fullscreen = fullscreen.then_some(Fullscreen::Borderless(None));
+    self.winit_wnd.set_fullscreen(fullscreen)
+  }
+
+  fn set_pointer_lock(&mut self, lock: bool) {
+    use winit::window::CursorGrabMode;
+
+    let wnd = &self.winit_wnd;
+    let res = if lock {
+      // Not all platforms support the locked mode, fallback to confine the cursor.
+      wnd
+        .set_cursor_grab(CursorGrabMode::Locked)
+        .or_else(|_| wnd.set_cursor_grab(CursorGrabMode::Confined))
+    } else {
+      wnd.set_cursor_grab(CursorGrabMode::None)
+    };
+    if let Err(err) = res {
+      log::warn!("Failed to set the pointer lock: {err}");
+    }
+    wnd.set_cursor_visible(!lock);
+  }
+
+  fn set_present_mode(&mut self, mode: PresentMode) {
+    self.present_mode = mode;
+    if let Some(backend) = self.backend.as_mut() {
+      backend.set_present_mode(mode);
+    }
+  }
+
+  fn viewport_insets(&self) -> EdgeInsets { platform::viewport_insets(&self.winit_wnd) }
+
+  fn set_ime_allowed(&mut self, allowed: bool) {
+    self.winit_wnd.set_ime_allowed(allowed);
+    platform::set_soft_keyboard_visible(allowed);
+  }
+
+  #[inline]
+  fn set_ime_purpose(&mut self, purpose: ImePurpose) { self.winit_wnd.set_ime_purpose(purpose); }
 
   #[inline]
   fn as_any(&self) -> &dyn std::any::Any { self }
@@ -146,25 +241,58 @@ impl ShellWindow for WinitShellWnd {
   fn as_any_mut(&mut self) -> &mut dyn Any { self }
 
   #[inline]
-  fn begin_frame(&mut self, surface: Color) { self.backend.begin_frame(surface) }
+  fn begin_frame(&mut self, surface: Color) {
+    if let Some(backend) = self.backend.as_mut() {
+      backend.begin_frame(surface);
+    }
+  }
 
   #[inline]
-  fn draw_commands(&mut self, viewport: Rect, commands: &[PaintCommand]) {
+  fn draw_commands(&mut self, viewport: Rect, damage: &[Rect], commands: &[PaintCommand]) {
     let scale = self.winit_wnd.scale_factor() as f32;
-    let viewport: DeviceRect = viewport
-      .scale(scale, scale)
-      .round_out()
-      .to_i32()
-      .cast_unit();
+    let to_device = |rect: &Rect| -> DeviceRect {
+      rect
+        .scale(scale, scale)
+        .round_out()
+        .to_i32()
+        .cast_unit()
+    };
+    let viewport = to_device(&viewport);
+    let damage: Vec<_> = damage.iter().map(to_device).collect();
 
     self.winit_wnd.pre_present_notify();
-    self
-      .backend
-      .draw_commands(viewport, &Transform::scale(scale, scale), commands);
+    if let Some(backend) = self.backend.as_mut() {
+      backend.draw_commands(viewport, &damage, &Transform::scale(scale, scale), commands);
+    }
+  }
+
+  #[inline]
+  fn end_frame(&mut self) {
+    if let Some(backend) = self.backend.as_mut() {
+      backend.end_frame();
+    }
   }
 
   #[inline]
-  fn end_frame(&mut self) { self.backend.end_frame() }
+  fn backend_timings(&self) -> Option<BackendTimings> {
+    self.backend.as_ref().and_then(|b| b.timings())
+  }
+
+  fn render_image(
+    &mut self, viewport: Rect, commands: &[PaintCommand], surface: Color,
+  ) -> Option<PixelImage> {
+    let scale = self.winit_wnd.scale_factor() as f32;
+    let size = (viewport.size * scale)
+      .ceil()
+      .to_i32()
+      .cast_unit();
+    let matrix =
+      Transform::translation(-viewport.min_x(), -viewport.min_y()).then_scale(scale, scale);
+    self
+      .backend
+      .as_mut()?
+      .render_image(size, &matrix, commands, surface)
+  }
 }
 
 pub(crate) fn new_id(id: winit::window::WindowId) -> WindowId {
@@ -177,7 +305,7 @@ impl WinitShellWnd {
   pub(crate) async fn new_with_canvas<T>(
     canvas: web_sys::HtmlCanvasElement, window_target: &EventLoopWindowTarget<T>,
     attrs: WindowAttributes,
-  ) -> Self {
+  ) -> Result<Self, RenderInitError> {
     use winit::platform::web::WindowBuilderExtWebSys;
     let builder = winit::window::WindowBuilder::new().with_canvas(Some(canvas));
 
@@ -187,7 +315,7 @@ impl WinitShellWnd {
   #[cfg(target_family = "wasm")]
   pub(crate) async fn new<T>(
     window_target: &EventLoopWindowTarget<T>, attrs: WindowAttributes,
-  ) -> Self {
+  ) -> Result<Self, RenderInitError> {
     const RIBIR_CANVAS: &str = "ribir_canvas";
     const RIBIR_CANVAS_USED: &str = "ribir_canvas_used";
 
@@ -226,21 +354,26 @@ impl WinitShellWnd {
   #[cfg(not(target_family = "wasm"))]
   pub(crate) async fn new<T>(
     window_target: &EventLoopWindowTarget<T>, attrs: WindowAttributes,
-  ) -> Self {
+  ) -> Result<Self, RenderInitError> {
     Self::inner_wnd(winit::window::WindowBuilder::new(), window_target, attrs).await
   }
 
   async fn inner_wnd<T>(
     mut builder: winit::window::WindowBuilder, window_target: &EventLoopWindowTarget<T>,
     attrs: WindowAttributes,
-  ) -> Self {
+  ) -> Result<Self, RenderInitError> {
     builder = builder
       .with_title(attrs.title)
       .with_maximized(attrs.maximized)
       .with_resizable(attrs.resizable)
       // hide the window until the render backend is ready
       .with_visible(false)
-      .with_decorations(attrs.decorations);
+      .with_decorations(attrs.decorations)
+      .with_fullscreen(
+        attrs
+          .fullscreen
+          .then_some(Fullscreen::Borderless(None)),
+      );
 
     if let Some(size) = attrs.size {
       builder = builder.with_inner_size(LogicalSize::new(size.width, size.height));
@@ -257,18 +390,67 @@ impl WinitShellWnd {
     if let Some(icon) = attrs.icon {
       builder = builder.with_window_icon(Some(img_to_winit_icon(&icon)));
     }
+    if let Some(parent) = attrs.parent_window {
+      // Safety: the caller of `WindowAttributes::with_parent_window` guarantees
+      // the parent is valid.
+      builder = unsafe { builder.with_parent_window(Some(parent)) };
+    }
+    let backdrop = attrs.backdrop;
+    if backdrop != WindowBackdrop::None {
+      builder = builder
+        .with_transparent(true)
+        .with_blur(!cfg!(windows));
+    }
 
     let winit_wnd: winit::window::Window = builder.build(window_target).unwrap();
-    let ptr = &winit_wnd as *const winit::window::Window;
-    // Safety: a reference to winit_wnd is valid as long as the WinitShellWnd is
-    // alive.
-    let backend = Backend::new(unsafe { &*ptr }).await;
+    if backdrop != WindowBackdrop::None {
+      apply_window_backdrop(&winit_wnd, backdrop);
+    }
+    let mut wnd = WinitShellWnd {
+      backend: None,
+      winit_wnd,
+      cursor: CursorIcon::Default,
+      render_thread: attrs.render_thread,
+      transparent: backdrop != WindowBackdrop::None,
+      present_mode: PresentMode::default(),
+    };
+    // The native window of Android is only available after the application
+    // resumed, the backend is created then.
+    if !cfg!(target_os = "android") {
+      wnd.backend = Some(wnd.new_backend().await?);
+    }
 
     // show the window after the render backend is ready
     if attrs.visible {
-      winit_wnd.set_visible(attrs.visible);
+      wnd.winit_wnd.set_visible(attrs.visible);
     }
-    WinitShellWnd { backend, winit_wnd, cursor: CursorIcon::Default }
+    Ok(wnd)
+  }
+
+  /// Release the render backend when the application is suspended, the surface
+  /// of the window may be destroyed by the platform in the background.
+  #[cfg(not(target_family = "wasm"))]
+  pub(crate) fn suspend(&mut self) { self.backend = None; }
+
+  /// Recreate the render backend released by `suspend`.
+  #[cfg(not(target_family = "wasm"))]
+  pub(crate) fn resume(&mut self) {
+    if self.backend.is_none() {
+      match futures::executor::block_on(self.new_backend()) {
+        Ok(backend) => self.backend = Some(backend),
+        Err(e) => log::error!("Failed to recreate the renderer of the window: {e}"),
+      }
+    }
+  }
+
+  async fn new_backend(&self) -> Result<ShellBackend, RenderInitError> {
+    let ptr = &self.winit_wnd as *const winit::window::Window;
+    // Safety: a reference to winit_wnd is valid as long as the WinitShellWnd is
+    // alive, and the backend is dropped before the window.
+    let mut backend = Backend::new(unsafe { &*ptr }).await?;
+    backend.set_transparent(self.transparent);
+    backend.set_present_mode(self.present_mode);
+    Ok(ShellBackend::new(backend, self.render_thread))
   }
 }
 
@@ -2,11 +2,17 @@ use ribir_core::prelude::Classes;
 
 mod buttons_cls;
 mod checkbox_cls;
+mod dialog_cls;
 mod input_cls;
+mod menu_cls;
 mod progress_cls;
 mod radio_cls;
+mod scaffold_cls;
 mod scrollbar_cls;
 mod slider_cls;
+mod snackbar_cls;
+mod split_cls;
+mod table_cls;
 mod tooltips_cls;
 pub fn initd_classes() -> Classes {
   let mut classes = Classes::default();
@@ -19,6 +25,12 @@ pub fn initd_classes() -> Classes {
   tooltips_cls::init(&mut classes);
   slider_cls::init(&mut classes);
   input_cls::init(&mut classes);
+  menu_cls::init(&mut classes);
+  dialog_cls::init(&mut classes);
+  table_cls::init(&mut classes);
+  scaffold_cls::init(&mut classes);
+  snackbar_cls::init(&mut classes);
+  split_cls::init(&mut classes);
 
   classes
 }
@@ -190,11 +190,10 @@ fn btn_label_style(line_height: f32) -> TextStyle {
 }
 
 fn base_interactive(w: Widget, radius: Radius) -> Widget {
-  let hover_layer = HoverLayer::tracked(LayerArea::WidgetCover(radius));
-  ripple! {
-    bounded: RippleBound::Radius(radius),
+  state_layer! {
+    layer_radius: radius,
     cursor: CursorIcon::Pointer,
-    @ $hover_layer { @ { w } }
+    @ { w }
   }
   .into_widget()
 }
@@ -0,0 +1,13 @@
+use ribir_core::prelude::*;
+use ribir_widgets::prelude::*;
+
+pub(super) fn init(classes: &mut Classes) {
+  classes.insert(DIALOG, |w| {
+    FatObj::new(w)
+      .background(Palette::of(BuildCtx::get()).surface_container_high())
+      .radius(Radius::all(28.))
+      .padding(EdgeInsets::all(24.))
+      .clamp(BoxClamp::min_width(280.).with_max_width(560.))
+      .into_widget()
+  });
+}
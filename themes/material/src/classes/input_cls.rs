@@ -1,5 +1,5 @@
 use ribir_core::prelude::*;
-use ribir_widgets::input::{INPUT, TEXT_CARET, TEXT_SELECTION, TEXTAREA};
+use ribir_widgets::input::{INPUT, TEXT_CARET, TEXT_SELECTION, TEXTAREA, TEXTAREA_LINE_NUMBERS};
 
 use crate::md;
 
@@ -49,4 +49,11 @@ pub(super) fn init(classes: &mut Classes) {
   }
   classes.insert(INPUT, input_border);
   classes.insert(TEXTAREA, input_border);
+  classes.insert(TEXTAREA_LINE_NUMBERS, |w| {
+    let outline = Palette::of(BuildCtx::get()).outline();
+    FatObj::new(w)
+      .foreground(outline)
+      .padding(EdgeInsets::only_right(8.))
+      .into_widget()
+  });
 }
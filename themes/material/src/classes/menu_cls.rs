@@ -0,0 +1,63 @@
+use ribir_core::prelude::*;
+use ribir_widgets::prelude::*;
+
+use crate::*;
+
+pub(super) fn init(classes: &mut Classes) {
+  classes.insert(MENU, |w| {
+    FatObj::new(w)
+      .background(Palette::of(BuildCtx::get()).surface_container())
+      .radius(md::RADIUS_4)
+      .padding(EdgeInsets::vertical(8.))
+      .clamp(BoxClamp::min_width(112.))
+      .into_widget()
+  });
+  classes.insert(MENU_ITEM, |w| {
+    let hover_layer = HoverLayer::tracked(LayerArea::WidgetCover(Radius::all(0.)));
+    let w = FatObj::new(w).v_align(VAlign::Center);
+    ripple! {
+      cursor: CursorIcon::Pointer,
+      bounded: RippleBound::Bounded,
+      @ $hover_layer {
+        clamp: BoxClamp::min_width(112.).with_fixed_height(48.),
+        padding: md::EDGES_HOR_12,
+        @ { w }
+      }
+    }
+    .into_widget()
+  });
+  classes.insert(MENU_ITEM_CHECK, style_class! { margin: EdgeInsets::only_right(12.) });
+  classes.insert(MENU_SEPARATOR, |w| {
+    FatObj::new(w)
+      .background(Palette::of(BuildCtx::get()).outline_variant())
+      .clamp(BoxClamp::fixed_height(1.))
+      .margin(EdgeInsets::vertical(8.))
+      .into_widget()
+  });
+  classes.insert(MENU_BAR, |w| {
+    FatObj::new(w)
+      .background(Palette::of(BuildCtx::get()).surface_container())
+      .padding(md::EDGES_HOR_4)
+      .into_widget()
+  });
+  classes.insert(MENU_BAR_ITEM, |w| {
+    let hover_layer = HoverLayer::tracked(LayerArea::WidgetCover(md::RADIUS_4));
+    let label = TypographyTheme::of(BuildCtx::get())
+      .label_large
+      .text
+      .clone();
+    let w = FatObj::new(w)
+      .v_align(VAlign::Center)
+      .text_style(label);
+    ripple! {
+      cursor: CursorIcon::Pointer,
+      bounded: RippleBound::Radius(md::RADIUS_4),
+      @ $hover_layer {
+        clamp: BoxClamp::fixed_height(36.),
+        padding: md::EDGES_HOR_12,
+        @ { w }
+      }
+    }
+    .into_widget()
+  });
+}
@@ -0,0 +1,23 @@
+use ribir_core::prelude::*;
+use ribir_widgets::prelude::*;
+
+use crate::md;
+
+pub(super) fn init(classes: &mut Classes) {
+  classes.insert(APP_BAR, |w| {
+    let ctx = BuildCtx::get();
+    FatObj::new(w)
+      .background(Palette::of(ctx).surface())
+      .text_style(TypographyTheme::of(ctx).title_large.text.clone())
+      .padding(md::EDGES_HOR_4)
+      .clamp(BoxClamp::fixed_height(64.))
+      .into_widget()
+  });
+  classes.insert(DRAWER, |w| {
+    FatObj::new(w)
+      .background(Palette::of(BuildCtx::get()).surface_container_low())
+      .radius(Radius::right(16.))
+      .padding(EdgeInsets::all(12.))
+      .into_widget()
+  });
+}
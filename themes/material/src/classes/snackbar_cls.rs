@@ -0,0 +1,24 @@
+use ribir_core::prelude::*;
+use ribir_widgets::prelude::*;
+
+use crate::md;
+
+pub(super) fn init(classes: &mut Classes) {
+  classes.insert(SNACKBAR, |w| {
+    let ctx = BuildCtx::get();
+    let palette = Palette::of(ctx);
+    FatObj::new(w)
+      .background(palette.inverse_surface())
+      .foreground(palette.inverse_on_surface())
+      .text_style(TypographyTheme::of(ctx).body_medium.text.clone())
+      .radius(md::shape::EXTRA_SMALL)
+      .padding(md::EDGES_HOR_16)
+      .clamp(
+        BoxClamp::min_width(288.)
+          .with_max_width(568.)
+          .with_min_height(48.),
+      )
+      .into_widget()
+  });
+  classes.insert(SNACKBAR_ACTION, style_class! { margin: md::EDGES_LEFT_4 });
+}
@@ -0,0 +1,19 @@
+use ribir_core::prelude::*;
+use ribir_widgets::prelude::*;
+
+use crate::md;
+
+pub(super) fn init(classes: &mut Classes) {
+  classes.insert(H_SPLIT_DIVIDER, |w| {
+    FatObj::new(w)
+      .background(Palette::of(BuildCtx::get()).outline_variant())
+      .clamp(BoxClamp::fixed_width(md::THICKNESS_4))
+      .into_widget()
+  });
+  classes.insert(V_SPLIT_DIVIDER, |w| {
+    FatObj::new(w)
+      .background(Palette::of(BuildCtx::get()).outline_variant())
+      .clamp(BoxClamp::fixed_height(md::THICKNESS_4))
+      .into_widget()
+  });
+}
@@ -0,0 +1,57 @@
+use ribir_core::prelude::*;
+use ribir_widgets::prelude::*;
+
+const CELL_PADDING: EdgeInsets = EdgeInsets::symmetrical(12., 16.);
+const SORT_ICON_SIZE: f32 = 18.;
+
+pub(super) fn init(classes: &mut Classes) {
+  classes.insert(TABLE_HEADER, |w| {
+    let palette = Palette::of(BuildCtx::get());
+    FatObj::new(w)
+      .background(palette.surface_container())
+      .border(Border::only_bottom(BorderSide::new(1., palette.outline_variant().into())))
+      .into_widget()
+  });
+  classes.insert(TABLE_HEADER_CELL, |w| {
+    let text_style = TypographyTheme::of(BuildCtx::get())
+      .title_small
+      .text
+      .clone();
+    FatObj::new(w)
+      .padding(CELL_PADDING)
+      .text_style(text_style)
+      .into_widget()
+  });
+  classes.insert(TABLE_ROW, |w| {
+    let color = Palette::of(BuildCtx::get()).outline_variant();
+    FatObj::new(w)
+      .border(Border::only_bottom(BorderSide::new(1., color.into())))
+      .into_widget()
+  });
+  classes.insert(TABLE_CELL, |w| {
+    FatObj::new(w)
+      .padding(CELL_PADDING)
+      .v_align(VAlign::Center)
+      .into_widget()
+  });
+
+  // The host of the sort indicator is an empty widget, it's replaced by an
+  // arrow.
+  classes.insert(TABLE_SORT_DESCENDING, |_| sort_icon(None));
+  classes.insert(TABLE_SORT_ASCENDING, |_| {
+    let flip = Transform::scale(1., -1.).then_translate(Vector::new(0., SORT_ICON_SIZE));
+    sort_icon(Some(flip))
+  });
+}
+
+fn sort_icon(transform: Option<Transform>) -> Widget<'static> {
+  let icon = icon! {
+    text_line_height: SORT_ICON_SIZE,
+    margin: EdgeInsets::only_left(4.),
+    @ { svgs::ARROW_DROP_DOWN }
+  };
+  match transform {
+    Some(t) => FatObj::new(icon).transform(t).into_widget(),
+    None => icon.into_widget(),
+  }
+}
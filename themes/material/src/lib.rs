@@ -72,6 +72,7 @@ const LIST_ITEM_SIZE: f32 = 40.;
 const AVATAR_SIZE: f32 = 40.;
 const AVATAR_RADIUS: f32 = 20.;
 const LIST_IMAGE_ITEM_SIZE: f32 = 56.;
+const SCROLL_THUMB_MIN_SIZE: f32 = 12.;
 
 fn init_custom_style(theme: &mut Theme) {
   theme.custom_styles.set_custom_style(TabsStyle {
@@ -177,8 +178,27 @@ fn override_compose_decorator(theme: &mut Theme) {
     .into_widget()
   });
 
+  // The list items ripple in their bounds when pressed.
+  styles.override_compose_decorator::<ListItemDecorator>(|_, host, _| {
+    state_layer! { @ { host } }.into_widget()
+  });
+
   let textfield = TextFieldThemeSuit::from_theme(&theme.palette, &theme.typography_theme);
   theme.custom_styles.set_custom_style(textfield);
+  theme
+    .custom_styles
+    .set_custom_style(ScrollBarTheme {
+      thickness: md::THICKNESS_8,
+      thumb_min_size: SCROLL_THUMB_MIN_SIZE,
+      thumb_radius: 4.,
+      thumb_color: theme.palette.primary(),
+      track_color: theme.palette.secondary_container(),
+      auto_hide: Some(Duration::from_secs(3)),
+    });
+  // The focus indicator of the material design 3.
+  theme
+    .custom_styles
+    .set_custom_style(FocusRingStyle { color: theme.palette.secondary(), width: 3., offset: 2. });
 }
 
 pub mod purple {
@@ -247,6 +267,7 @@ pub fn typography_theme() -> TypographyTheme {
         letter_space,
         font_face,
         overflow: TextOverflow::Overflow,
+        max_lines: None,
       },
       decoration: TextDecorationStyle {
         decoration: TextDecoration::NONE,
@@ -58,6 +58,66 @@ pub mod easing {
   }
 }
 
+// The elevation tokens, the distance between two surfaces along the z-axis.
+// See https://m3.material.io/styles/elevation/tokens
+pub mod elevation {
+  use ribir_core::prelude::*;
+
+  pub const LEVEL0: f32 = 0.;
+  pub const LEVEL1: f32 = 1.;
+  pub const LEVEL2: f32 = 3.;
+  pub const LEVEL3: f32 = 6.;
+  pub const LEVEL4: f32 = 8.;
+  pub const LEVEL5: f32 = 12.;
+
+  /// The opacity of the primary color tinting the surface at each level.
+  const TINT_OPACITIES: [(f32, f32); 6] =
+    [(LEVEL0, 0.), (LEVEL1, 0.05), (LEVEL2, 0.08), (LEVEL3, 0.11), (LEVEL4, 0.12), (LEVEL5, 0.14)];
+
+  /// The surface color at the `elevation`, a higher surface is tinted more by
+  /// the primary color.
+  pub fn surface_tint(palette: &Palette, elevation: f32) -> Color {
+    let opacity = TINT_OPACITIES
+      .windows(2)
+      .find(|w| elevation <= w[1].0)
+      .map_or(TINT_OPACITIES[5].1, |w| {
+        let [(l0, o0), (l1, o1)] = [w[0], w[1]];
+        o0 + (o1 - o0) * ((elevation - l0) / (l1 - l0)).max(0.)
+      });
+
+    let [r0, g0, b0, a] = palette.surface().into_f32_components();
+    let [r1, g1, b1, _] = palette.primary().into_f32_components();
+    let mix = |from: f32, to: f32| from + (to - from) * opacity;
+    Color::from_f32_rgba(mix(r0, r1), mix(g0, g1), mix(b0, b1), a)
+  }
+}
+
+// The opacities of the state layers in percent, they are the overlays that
+// communicate the interaction state of a component.
+// See https://m3.material.io/foundations/interaction/states/state-layers
+pub mod state {
+  pub const HOVER_OPACITY: u8 = 8;
+  pub const FOCUS_OPACITY: u8 = 10;
+  pub const PRESSED_OPACITY: u8 = 10;
+  pub const DRAGGED_OPACITY: u8 = 16;
+}
+
+// The shape tokens, the corner radius of the components by their size.
+// See https://m3.material.io/styles/shape/corner-radius-scale
+pub mod shape {
+  use ribir_core::prelude::*;
+
+  pub const NONE: Radius = Radius::all(0.);
+  pub const EXTRA_SMALL: Radius = Radius::all(4.);
+  pub const SMALL: Radius = Radius::all(8.);
+  pub const MEDIUM: Radius = Radius::all(12.);
+  pub const LARGE: Radius = Radius::all(16.);
+  pub const EXTRA_LARGE: Radius = Radius::all(28.);
+
+  /// The fully rounded corners of a box with the `size`.
+  pub fn full(size: Size) -> Radius { Radius::all(size.width.min(size.height) / 2.) }
+}
+
 // These are the constant variables commonly used in the material theme.
 
 pub const RADIUS_5: Radius = Radius::all(5.);
@@ -1,16 +1,33 @@
 use ribir_core::prelude::*;
 use ribir_widgets::prelude::*;
 
-use crate::md;
-
-const HOVER_OPACITY: u8 = 8;
-const PRESSED_OPACITY: u8 = 10;
-
-pub type HoverLayer = StateLayer<HOVER_OPACITY>;
-pub type PressedLayer = StateLayer<PRESSED_OPACITY>;
+use crate::{Ripple, RippleBound, RippleDeclareExtend, md};
+
+pub type HoverLayer = StateOverlay<{ md::state::HOVER_OPACITY }>;
+pub type FocusLayer = StateOverlay<{ md::state::FOCUS_OPACITY }>;
+pub type PressedLayer = StateOverlay<{ md::state::PRESSED_OPACITY }>;
+
+/// The decorator composes the state layers of the material design onto an
+/// interactive widget, a tint shows when it's hovered or focused by the
+/// keyboard, and a ripple spreads when it's pressed.
+#[derive(Declare)]
+pub struct StateLayer {
+  /// The radius of the layers, it should be the same as the radius of the
+  /// widget.
+  #[declare(default)]
+  pub layer_radius: Radius,
+  /// Whether the ripple always originates from the center of the widget.
+  #[declare(default)]
+  pub center: bool,
+  /// The radius of the ripple when fully expanded, see [`Ripple`].
+  #[declare(default)]
+  pub ripple_radius: Option<f32>,
+}
 
+/// An overlay filling the widget with the opacity `M` in percent when it's
+/// visible.
 #[derive(Debug, Clone)]
-pub struct StateLayer<const M: u8> {
+pub struct StateOverlay<const M: u8> {
   pub area: LayerArea,
   pub draw_opacity: f32,
 }
@@ -28,12 +45,7 @@ impl HoverLayer {
   /// Create a hover state layer displaying only when the pointer is hovering
   /// this widget.
   pub fn tracked(path: impl Into<LayerArea>) -> FatObj<Stateful<HoverLayer>> {
-    let layer = Stateful::new(Self { area: path.into(), draw_opacity: 0. });
-    part_writer!(&mut layer.draw_opacity).transition(EasingTransition {
-      easing: md::easing::STANDARD,
-      duration: md::easing::duration::SHORT1,
-    });
-    let mut layer = FatObj::new(layer);
+    let mut layer = Self::transitional(path);
     let layer2 = layer.clone_writer();
 
     let hover = layer.get_mix_flags_widget().clone_reader();
@@ -49,13 +61,61 @@ impl HoverLayer {
   }
 }
 
-impl<const M: u8> StateLayer<M> {
+impl FocusLayer {
+  /// Create a focus state layer displaying only when the focus is on this
+  /// widget and is moved by the keyboard.
+  pub fn focus_tracked(path: impl Into<LayerArea>) -> FatObj<Stateful<FocusLayer>> {
+    let mut layer = Self::transitional(path);
+    let layer2 = layer.clone_writer();
+
+    let wnd = BuildCtx::get().window();
+    let u = watch!($layer.has_focus()).subscribe(move |focused| {
+      layer2
+        .write()
+        .set_visible_state(focused && wnd.is_focus_visible());
+    });
+    layer.on_disposed(move |_| u.unsubscribe())
+  }
+}
+
+impl<'c> ComposeChild<'c> for StateLayer {
+  type Child = Widget<'c>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    fn_widget! {
+      let radius = $this.layer_radius;
+      let hover_layer = HoverLayer::tracked(LayerArea::WidgetCover(radius));
+      let focus_layer = FocusLayer::focus_tracked(LayerArea::WidgetCover(radius));
+      @Ripple {
+        center: $this.center,
+        ripple_radius: $this.ripple_radius,
+        bounded: RippleBound::Radius(radius),
+        @ $hover_layer {
+          @ $focus_layer { @ { child } }
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+impl<const M: u8> StateOverlay<M> {
   pub fn show(&mut self) { self.set_visible_state(true); }
 
   pub fn hide(&mut self) { self.set_visible_state(false); }
 
   pub fn show_opacity() -> f32 { M as f32 / 100. }
 
+  /// Create a hidden layer that fades in and out.
+  fn transitional(path: impl Into<LayerArea>) -> FatObj<Stateful<Self>> {
+    let layer = Stateful::new(Self { area: path.into(), draw_opacity: 0. });
+    part_writer!(&mut layer.draw_opacity).transition(EasingTransition {
+      easing: md::easing::STANDARD,
+      duration: md::easing::duration::SHORT1,
+    });
+    FatObj::new(layer)
+  }
+
   fn set_visible_state(&mut self, visible: bool) {
     if visible {
       self.draw_opacity = Self::show_opacity();
@@ -65,7 +125,7 @@ impl<const M: u8> StateLayer<M> {
   }
 }
 
-impl<'c, const M: u8> ComposeChild<'c> for StateLayer<M> {
+impl<'c, const M: u8> ComposeChild<'c> for StateOverlay<M> {
   type Child = Widget<'c>;
 
   fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
@@ -83,11 +143,11 @@ impl<'c, const M: u8> ComposeChild<'c> for StateLayer<M> {
   }
 }
 
-impl<const M: u8> Render for StateLayer<M> {
+impl<const M: u8> Render for StateOverlay<M> {
   fn perform_layout(&self, clamp: BoxClamp, _: &mut LayoutCtx) -> Size { clamp.min }
 
   fn paint(&self, ctx: &mut PaintingCtx) {
-    let StateLayer { area, draw_opacity } = self;
+    let StateOverlay { area, draw_opacity } = self;
     if *draw_opacity > 0. {
       let p = ctx.parent().unwrap();
       let size = ctx.widget_box_size(p).unwrap();
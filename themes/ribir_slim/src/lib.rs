@@ -82,6 +82,7 @@ fn typography_theme() -> TypographyTheme {
         letter_space,
         font_face,
         overflow: TextOverflow::Overflow,
+        max_lines: None,
       },
       decoration: TextDecorationStyle {
         decoration: TextDecoration::NONE,
@@ -0,0 +1,294 @@
+use std::{cell::RefCell, rc::Rc};
+
+use ribir_core::prelude::*;
+
+/// Plays the frames of an animated image, like a GIF or an APNG decoded by
+/// [`ImageFrames::decode`].
+///
+/// The frames advance by the frame clock of the framework, and the widget is
+/// only repainted when the next frame is due. An image with a single frame is
+/// shown as a still image.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let frames = ImageFrames::decode(include_bytes!("../../static/counter_demo.gif"));
+/// let _w = fn_widget! {
+///   let mut img = @AnimatedImage { frames: Resource::new(frames.unwrap()) };
+///   @ $img {
+///     on_tap: move |_| {
+///       let mut img = $img.write();
+///       if img.playing { img.pause() } else { img.play() }
+///     }
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct AnimatedImage {
+  pub frames: Resource<ImageFrames>,
+  /// Whether the frames are playing, it's set to `false` when the last frame
+  /// shows if the image doesn't `repeat`.
+  #[declare(default = true)]
+  pub playing: bool,
+  /// Whether to play from the first frame again after the last frame.
+  #[declare(default = true)]
+  pub repeat: bool,
+}
+
+impl AnimatedImage {
+  /// Continue to play the frames from where they paused.
+  pub fn play(&mut self) { self.playing = true; }
+
+  /// Pause at the current frame.
+  pub fn pause(&mut self) { self.playing = false; }
+}
+
+/// Paints the current frame of the frames.
+struct FrameView {
+  frames: Resource<ImageFrames>,
+  current: usize,
+}
+
+/// The playing progress of the current frame.
+#[derive(Default)]
+struct Playback {
+  /// When the current frame began to show if the frames are playing.
+  shown_at: Option<Instant>,
+  /// How long the current frame has shown before the frames paused.
+  elapsed: Duration,
+  timer: Option<BoxSubscription<'static>>,
+}
+
+impl FrameView {
+  fn image(&self) -> &Resource<PixelImage> { &self.frames.frames()[self.current].image }
+
+  fn delay(&self) -> Duration { self.frames.frames()[self.current].delay }
+}
+
+impl Render for FrameView {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    self.image().perform_layout(clamp, ctx)
+  }
+
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    self.image().min_intrinsic_width(height, ctx)
+  }
+
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    self.image().min_intrinsic_height(width, ctx)
+  }
+
+  fn paint(&self, ctx: &mut PaintingCtx) { self.image().paint(ctx) }
+
+  /// All the frames have the same size, so a new frame only needs to repaint.
+  fn dirty_phase(&self) -> DirtyPhase { DirtyPhase::Paint }
+}
+
+impl Compose for AnimatedImage {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! {
+      // Play from the first frame when the frames change.
+      pipe!($this.frames.clone())
+        .distinct_until_changed()
+        .map(move |frames| frame_player(this.clone_writer(), frames))
+    }
+    .into_widget()
+  }
+}
+
+fn frame_player(
+  this: impl StateWriter<Value = AnimatedImage>, frames: Resource<ImageFrames>,
+) -> Widget<'static> {
+  let view = Stateful::new(FrameView { frames, current: 0 });
+  let playback = Rc::new(RefCell::new(Playback::default()));
+  if this.read().playing {
+    play(this.clone_writer(), view.clone_writer(), playback.clone());
+  }
+
+  fn_widget! {
+    let view2 = view.clone_writer();
+    let playback2 = playback.clone();
+    let u = watch!(($this.playing, $this.repeat))
+      .distinct_until_changed()
+      .subscribe(move |(playing, _)| {
+        pause(&playback2);
+        if playing {
+          play(this.clone_writer(), view2.clone_writer(), playback2.clone());
+        }
+      });
+
+    let view = FatObj::new(view);
+    @ $view {
+      on_disposed: move |_| {
+        u.unsubscribe();
+        pause(&playback);
+      }
+    }
+  }
+  .into_widget()
+}
+
+fn pause(playback: &RefCell<Playback>) {
+  let mut playback = playback.borrow_mut();
+  if let Some(timer) = playback.timer.take() {
+    timer.unsubscribe();
+  }
+  if let Some(shown_at) = playback.shown_at.take() {
+    playback.elapsed = FrameClock::now() - shown_at;
+  }
+}
+
+/// Play the frames from the current progress, and schedule a timer to show the
+/// next frame when it's due.
+fn play(
+  this: impl StateWriter<Value = AnimatedImage>, view: impl StateWriter<Value = FrameView>,
+  playback: Rc<RefCell<Playback>>,
+) {
+  let mut pb = playback.borrow_mut();
+  let remaining = {
+    let frames = view.read().frames.clone();
+    // A still image or the frames without delay have nothing to play.
+    if !frames.is_animated() || frames.duration().is_zero() {
+      return;
+    }
+    let current = view.read().current;
+    // Play from the first frame again if the last frame has shown.
+    if current + 1 == frames.len() && pb.elapsed >= frames.frames()[current].delay {
+      view.write().current = 0;
+      pb.elapsed = Duration::ZERO;
+    }
+    view.read().delay().saturating_sub(pb.elapsed)
+  };
+
+  pb.shown_at = Some(FrameClock::now() - pb.elapsed);
+  let playback2 = playback.clone();
+  let timer = observable::timer((), remaining, AppCtx::scheduler()).subscribe(move |_| {
+    if advance(&this, &view, &playback2) {
+      play(this.clone_writer(), view.clone_writer(), playback2.clone());
+    }
+  });
+  pb.timer = Some(BoxSubscription::new(timer));
+}
+
+/// Show the frame due at now, the frames too late to show are skipped. Return
+/// whether the frames are still playing.
+fn advance(
+  this: &impl StateWriter<Value = AnimatedImage>, view: &impl StateWriter<Value = FrameView>,
+  playback: &RefCell<Playback>,
+) -> bool {
+  let mut pb = playback.borrow_mut();
+  pb.timer = None;
+  let Some(mut shown_at) = pb.shown_at.take() else { return false };
+  let now = FrameClock::now();
+  let repeat = this.read().repeat;
+
+  let (frames, mut current) = {
+    let view = view.read();
+    (view.frames.clone(), view.current)
+  };
+  let frames = frames.frames();
+  let mut end = false;
+  while shown_at + frames[current].delay <= now {
+    if current + 1 < frames.len() {
+      shown_at += frames[current].delay;
+      current += 1;
+    } else if repeat {
+      shown_at += frames[current].delay;
+      current = 0;
+    } else {
+      end = true;
+      break;
+    }
+  }
+
+  if current != view.read().current {
+    view.write().current = current;
+  }
+  pb.elapsed = now - shown_at;
+  drop(pb);
+  if end {
+    this.write().playing = false;
+  }
+  !end
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+
+  const RED: [u8; 4] = [255, 0, 0, 255];
+  const GREEN: [u8; 4] = [0, 255, 0, 255];
+  const BLUE: [u8; 4] = [0, 0, 255, 255];
+
+  /// The red, green and blue frames, each shows for 100ms.
+  fn frames() -> Resource<ImageFrames> {
+    let frames = [RED, GREEN, BLUE]
+      .into_iter()
+      .map(|color| {
+        let img = PixelImage::new(color.to_vec().into(), 1, 1, image::ColorFormat::Rgba8);
+        ImageFrame { image: Resource::new(img), delay: Duration::from_millis(100) }
+      })
+      .collect();
+    Resource::new(ImageFrames::new(frames))
+  }
+
+  /// The color of the frame painted by the window.
+  fn painted(wnd: &mut TestWindow) -> [u8; 4] {
+    let frame = wnd.draw_and_take_frame();
+    frame
+      .commands
+      .iter()
+      .find_map(|cmd| match cmd {
+        PaintCommand::Path(PathCommand {
+          action: PaintPathAction::Paint { brush: CommandBrush::Image { img, .. }, .. },
+          ..
+        }) => Some(img.pixel_bytes()[..4].try_into().unwrap()),
+        _ => None,
+      })
+      .unwrap()
+  }
+
+  #[test]
+  fn play_frames() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! { @AnimatedImage { frames: frames() } });
+    assert_eq!(painted(&mut wnd), RED);
+    wnd.tick(Duration::from_millis(50));
+    assert_eq!(painted(&mut wnd), RED);
+    wnd.tick(Duration::from_millis(50));
+    assert_eq!(painted(&mut wnd), GREEN);
+    // The frames too late to show are skipped.
+    wnd.tick(Duration::from_millis(200));
+    assert_eq!(painted(&mut wnd), RED);
+  }
+
+  #[test]
+  fn pause_and_no_repeat() {
+    reset_test_env!();
+
+    let (img, w_img) =
+      split_value(AnimatedImage { frames: frames(), playing: true, repeat: false });
+    let c_img = w_img.clone_writer();
+    let mut wnd = TestWindow::new(fn_widget! { @ { c_img.clone_writer() } });
+    wnd.tick(Duration::from_millis(150));
+    assert_eq!(painted(&mut wnd), GREEN);
+
+    w_img.write().pause();
+    wnd.tick(Duration::from_millis(500));
+    assert_eq!(painted(&mut wnd), GREEN);
+
+    // Continue the left 50ms of the green frame.
+    w_img.write().play();
+    wnd.tick(Duration::from_millis(50));
+    assert_eq!(painted(&mut wnd), BLUE);
+
+    wnd.tick(Duration::from_millis(500));
+    assert_eq!(painted(&mut wnd), BLUE);
+    assert!(!img.read().playing);
+  }
+}
@@ -0,0 +1,338 @@
+use std::{cell::RefCell, collections::VecDeque, future::Future, io, rc::Rc};
+
+use futures::future::LocalBoxFuture;
+use ribir_core::prelude::*;
+
+/// Shows an image loaded from the `src` without blocking the UI thread.
+///
+/// The `src` is a path of the image file or a `file://` URL, which is read and
+/// decoded on a background thread. Other URLs are loaded by the fetcher set by
+/// [`ImageCache::set_fetcher`]. The decoded images are kept in the
+/// [`ImageCache`], so the same `src` shows immediately the next time.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @AsyncImage {
+///     src: "assets/photo.png",
+///     placeholder: GenWidget::new(|| fn_widget! {
+///       @SpinnerProgress { value: None }
+///     }.into_widget()),
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct AsyncImage {
+  /// The path or the URL of the image.
+  pub src: CowArc<str>,
+  /// Built while the image is loading, nothing is shown if it's `None`.
+  #[declare(default)]
+  pub placeholder: Option<GenWidget>,
+  /// Built if the image fails to load, the placeholder is kept if it's `None`.
+  #[declare(default)]
+  pub error: Option<GenWidget>,
+}
+
+#[derive(Clone)]
+enum ImageStatus {
+  Loading,
+  Ready(Resource<PixelImage>),
+  Failed,
+}
+
+type Fetcher = Rc<dyn Fn(&str) -> LocalBoxFuture<'static, io::Result<Vec<u8>>>>;
+
+/// The cache of the images decoded by the [`AsyncImage`], keyed by their `src`.
+///
+/// The cache is bounded by the total bytes of the decoded pixels, 64 MiB by
+/// default, the least recently used images are evicted when it's exceeded.
+pub struct ImageCache {
+  capacity: usize,
+  used: usize,
+  /// The images from the least recently used to the most recently used.
+  images: VecDeque<(CowArc<str>, Resource<PixelImage>)>,
+  fetcher: Option<Fetcher>,
+}
+
+thread_local! {
+  static IMAGE_CACHE: RefCell<ImageCache> = RefCell::new(ImageCache {
+    capacity: 64 * 1024 * 1024,
+    used: 0,
+    images: VecDeque::new(),
+    fetcher: None,
+  });
+}
+
+impl ImageCache {
+  /// Get the image of the `src`, and mark it as the most recently used.
+  pub fn get(src: &str) -> Option<Resource<PixelImage>> {
+    IMAGE_CACHE.with_borrow_mut(|cache| {
+      let idx = cache
+        .images
+        .iter()
+        .position(|(key, _)| &**key == src)?;
+      let item = cache.images.remove(idx)?;
+      let img = item.1.clone();
+      cache.images.push_back(item);
+      Some(img)
+    })
+  }
+
+  /// Cache the image of the `src`, it's not cached if it's larger than the
+  /// capacity.
+  pub fn insert(src: CowArc<str>, img: Resource<PixelImage>) {
+    IMAGE_CACHE.with_borrow_mut(|cache| {
+      cache.remove(&src);
+      let bytes = img.pixel_bytes().len();
+      if bytes <= cache.capacity {
+        cache.used += bytes;
+        cache.images.push_back((src, img));
+        cache.evict();
+      }
+    })
+  }
+
+  /// Set the max total bytes of the cached images.
+  pub fn set_capacity(bytes: usize) {
+    IMAGE_CACHE.with_borrow_mut(|cache| {
+      cache.capacity = bytes;
+      cache.evict();
+    })
+  }
+
+  /// The total bytes of the cached images.
+  pub fn used_bytes() -> usize { IMAGE_CACHE.with_borrow(|cache| cache.used) }
+
+  /// Remove all the cached images.
+  pub fn clear() {
+    IMAGE_CACHE.with_borrow_mut(|cache| {
+      cache.images.clear();
+      cache.used = 0;
+    })
+  }
+
+  /// Set the function to load the bytes of the images whose `src` is a URL
+  /// other than `file://`, for example, to download them by an HTTP client.
+  pub fn set_fetcher<F>(fetcher: impl Fn(&str) -> F + 'static)
+  where
+    F: Future<Output = io::Result<Vec<u8>>> + 'static,
+  {
+    let fetcher: Fetcher = Rc::new(move |src| Box::pin(fetcher(src)));
+    IMAGE_CACHE.with_borrow_mut(|cache| cache.fetcher = Some(fetcher));
+  }
+
+  fn remove(&mut self, src: &str) {
+    if let Some(idx) = self
+      .images
+      .iter()
+      .position(|(key, _)| &**key == src)
+    {
+      let (_, img) = self.images.remove(idx).unwrap();
+      self.used -= img.pixel_bytes().len();
+    }
+  }
+
+  fn evict(&mut self) {
+    while self.used > self.capacity {
+      let Some((_, img)) = self.images.pop_front() else { break };
+      self.used -= img.pixel_bytes().len();
+    }
+  }
+}
+
+impl Compose for AsyncImage {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    let this = this.clone_watcher();
+    fn_widget! {
+      // Only reload the image when the `src` changes.
+      pipe!($this.src.clone())
+        .value_chain(|s| {
+          s.distinct_until_key_changed(|(_, src): &(_, CowArc<str>)| src.clone())
+            .box_it()
+        })
+        .map(move |src| image_of(this.clone_watcher(), src))
+    }
+    .into_widget()
+  }
+}
+
+/// The image of the `src`, it starts to load when it's built.
+fn image_of(this: impl StateWatcher<Value = AsyncImage>, src: CowArc<str>) -> Widget<'static> {
+  let status = if let Some(img) = ImageCache::get(&src) {
+    Stateful::new(ImageStatus::Ready(img))
+  } else {
+    let status = Stateful::new(ImageStatus::Loading);
+    let writer = status.clone_writer();
+    let _ = AppCtx::spawn_local(async move {
+      let new_status = match load_image(&src).await {
+        Ok(img) => {
+          let img = Resource::new(img);
+          ImageCache::insert(src, img.clone());
+          ImageStatus::Ready(img)
+        }
+        Err(err) => {
+          log::warn!("Failed to load the image `{}`: {err}", &*src);
+          ImageStatus::Failed
+        }
+      };
+      *writer.write() = new_status;
+    });
+    status
+  };
+
+  fn_widget! {
+    pipe!($status.clone()).map(move |status| {
+      let this = $this;
+      match status {
+        ImageStatus::Loading => this.placeholder.as_ref().map(GenWidget::gen_widget),
+        ImageStatus::Ready(img) => Some(img.into_widget()),
+        ImageStatus::Failed => this
+          .error
+          .as_ref()
+          .or(this.placeholder.as_ref())
+          .map(GenWidget::gen_widget),
+      }
+    })
+  }
+  .into_widget()
+}
+
+async fn load_image(src: &str) -> io::Result<PixelImage> {
+  let bytes = match src.split_once("://") {
+    None => read_file(src.to_owned()).await?,
+    Some(("file", path)) => read_file(path.to_owned()).await?,
+    Some(_) => {
+      let fetcher = IMAGE_CACHE.with_borrow(|cache| cache.fetcher.clone());
+      let fetcher = fetcher.ok_or_else(|| io::Error::other("no fetcher to load the URL"))?;
+      fetcher(src).await?
+    }
+  };
+  in_background(move || decode(&bytes)).await
+}
+
+async fn read_file(path: String) -> io::Result<Vec<u8>> {
+  in_background(move || std::fs::read(path)).await
+}
+
+#[cfg(feature = "png")]
+fn decode(bytes: &[u8]) -> io::Result<PixelImage> {
+  PixelImage::decode(bytes).map_err(io::Error::other)
+}
+
+#[cfg(not(feature = "png"))]
+fn decode(_: &[u8]) -> io::Result<PixelImage> {
+  Err(io::Error::other("no image decoder, enable the `png` feature"))
+}
+
+/// Run the `f` on a new thread and wait for its result. The platforms without
+/// threads run it in place.
+async fn in_background<R: Send + 'static>(f: impl FnOnce() -> R + Send + 'static) -> R {
+  #[cfg(not(target_family = "wasm"))]
+  {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    std::thread::spawn(move || {
+      let _ = sender.send(f());
+    });
+    receiver
+      .await
+      .expect("The background task panicked.")
+  }
+  #[cfg(target_family = "wasm")]
+  f()
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+  use crate::prelude::*;
+
+  fn image(width: u32, height: u32) -> PixelImage {
+    let data = vec![255; (width * height * 4) as usize];
+    PixelImage::new(data.into(), width, height, image::ColorFormat::Rgba8)
+  }
+
+  #[test]
+  fn load_and_cache() {
+    reset_test_env!();
+
+    // The bytes are fetched only after the placeholder is checked.
+    let (sender, receiver) = futures::channel::oneshot::channel::<Vec<u8>>();
+    let receiver = std::cell::RefCell::new(Some(receiver));
+    ImageCache::set_fetcher(move |_| {
+      let receiver = receiver.borrow_mut().take();
+      async move {
+        let receiver = receiver.ok_or_else(|| io::Error::other("fetched twice"))?;
+        receiver.await.map_err(io::Error::other)
+      }
+    });
+    let src: CowArc<str> = "test://load_and_cache.png".into();
+
+    let src2 = src.clone();
+    let mut wnd = TestWindow::new(fn_widget! {
+      @AsyncImage {
+        src: src2.clone(),
+        placeholder: GenWidget::new(|| @SizedBox { size: Size::new(1., 1.) }.into_widget()),
+      }
+    });
+    wnd.draw_frame();
+    wnd.assert_root_size(Size::new(1., 1.));
+
+    let mut bytes = vec![];
+    image(4, 3).write_as_png(&mut bytes).unwrap();
+    sender.send(bytes).unwrap();
+    // Wait for the background thread to decode it.
+    let mut retry = 0;
+    while ImageCache::get(&src).is_none() && retry < 100 {
+      std::thread::sleep(std::time::Duration::from_millis(10));
+      wnd.draw_frame();
+      retry += 1;
+    }
+    wnd.draw_frame();
+    wnd.assert_root_size(Size::new(4., 3.));
+
+    // The image shows immediately from the cache.
+    let mut wnd = TestWindow::new(fn_widget! { @AsyncImage { src: src.clone() } });
+    wnd.draw_frame();
+    wnd.assert_root_size(Size::new(4., 3.));
+  }
+
+  #[test]
+  fn show_error() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! {
+      @AsyncImage {
+        src: "unknown://image.png",
+        error: GenWidget::new(|| @SizedBox { size: Size::new(2., 2.) }.into_widget()),
+      }
+    });
+    wnd.draw_frame();
+    wnd.draw_frame();
+    wnd.assert_root_size(Size::new(2., 2.));
+  }
+
+  #[test]
+  fn evict_least_recently_used() {
+    ImageCache::clear();
+    ImageCache::set_capacity(100);
+    // 48 bytes for each.
+    ImageCache::insert("a".into(), Resource::new(image(4, 3)));
+    ImageCache::insert("b".into(), Resource::new(image(4, 3)));
+    assert!(ImageCache::get("a").is_some());
+    ImageCache::insert("c".into(), Resource::new(image(4, 3)));
+
+    assert!(ImageCache::get("b").is_none());
+    assert!(ImageCache::get("a").is_some());
+    assert!(ImageCache::get("c").is_some());
+    assert_eq!(ImageCache::used_bytes(), 96);
+
+    // Too large to cache.
+    ImageCache::insert("d".into(), Resource::new(image(10, 10)));
+    assert!(ImageCache::get("d").is_none());
+  }
+}
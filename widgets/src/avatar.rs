@@ -19,7 +19,7 @@ use crate::prelude::*;
 /// # #[cfg(feature="png")]
 /// fn_widget! {
 ///   @ Avatar {
-///     @ { ShallowImage::from_png(include_bytes!("../../gpu/examples/leaves.png")) }
+///     @ { Resource::new(PixelImage::from_png(include_bytes!("../../gpu/imgs/leaves.png"))) }
 ///   }
 /// };
 /// ```
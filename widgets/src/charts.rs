@@ -0,0 +1,297 @@
+//! The charts drawing the data through the `Painter`, enabled by the `charts`
+//! feature.
+//!
+//! The [`LineChart`] and [`BarChart`] lay out a value axis with nice ticks and
+//! a category axis with the labels of the data, the [`PieChart`] divides a
+//! circle by the values of the first series. The charts show a tooltip when
+//! the pointer hovers over a point, a bar or a slice, and animate to the new
+//! data when the bound data changes.
+use std::cell::RefCell;
+
+use ribir_core::prelude::*;
+
+mod axis;
+mod bar_chart;
+mod line_chart;
+mod pie_chart;
+
+pub use axis::Ticks;
+use axis::*;
+pub use bar_chart::*;
+pub use line_chart::*;
+pub use pie_chart::*;
+
+/// The data of a chart, the values of every series are matched to the labels
+/// by their index.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ChartData {
+  pub labels: Vec<CowArc<str>>,
+  pub series: Vec<Series>,
+}
+
+/// A named list of values of a chart.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Series {
+  pub name: CowArc<str>,
+  pub values: Vec<f32>,
+}
+
+/// An item of the chart data, a value of a series or a slice of the pie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChartItem {
+  pub series: usize,
+  pub index: usize,
+}
+
+/// The style of the charts.
+#[derive(Clone)]
+pub struct ChartStyle {
+  /// The colors of the series, or the slices of the pie, used in turn.
+  pub colors: Vec<Color>,
+  pub axis: Color,
+  pub grid: Color,
+  pub label: Color,
+  /// The value axis has about this count of steps.
+  pub tick_count: usize,
+  pub line_width: f32,
+  /// The radius of the points of the line chart.
+  pub point_radius: f32,
+  /// The transition of the chart to its new data.
+  pub transition: TransitionIdent,
+}
+
+impl CustomStyle for ChartStyle {
+  fn default_style(ctx: &impl AsRef<ProviderCtx>) -> Self {
+    let palette = Palette::of(ctx);
+    ChartStyle {
+      colors: vec![
+        palette.primary(),
+        palette.secondary(),
+        palette.tertiary(),
+        palette.success(),
+        palette.warning(),
+        palette.error(),
+      ],
+      axis: palette.outline(),
+      grid: palette.outline_variant(),
+      label: palette.on_surface_variant(),
+      tick_count: 5,
+      line_width: 2.,
+      point_radius: 4.,
+      transition: transitions::EASE_OUT,
+    }
+  }
+}
+
+impl ChartData {
+  /// The count of the categories, the most values of the series.
+  pub fn count(&self) -> usize {
+    self
+      .series
+      .iter()
+      .map(|s| s.values.len())
+      .max()
+      .unwrap_or(0)
+      .max(self.labels.len())
+  }
+
+  /// The minimum and maximum of the values, both include zero.
+  pub fn range(&self) -> (f32, f32) {
+    self
+      .series
+      .iter()
+      .flat_map(|s| s.values.iter())
+      .fold((0., 0.), |(min, max), v| (v.min(min), v.max(max)))
+  }
+
+  fn value(&self, item: ChartItem) -> Option<f32> {
+    self
+      .series
+      .get(item.series)?
+      .values
+      .get(item.index)
+      .copied()
+  }
+
+  /// The text describing the item, shown in the tooltip.
+  fn describe(&self, item: ChartItem) -> String {
+    let Some(value) = self.value(item) else { return String::new() };
+    let label = self.labels.get(item.index).map_or("", |l| &**l);
+    match self.series.get(item.series) {
+      Some(s) if !s.name.is_empty() => format!("{} {label}: {value}", &*s.name),
+      _ => format!("{label}: {value}"),
+    }
+  }
+}
+
+impl Lerp for Series {
+  /// The missing values of the shorter series are lerped from zero.
+  fn lerp(&self, to: &Self, factor: f32) -> Self {
+    let len = self.values.len().max(to.values.len());
+    let value = |values: &[f32], i: usize| values.get(i).copied().unwrap_or(0.);
+    let values = (0..len)
+      .map(|i| value(&self.values, i).lerp(&value(&to.values, i), factor))
+      .collect();
+    Series { name: to.name.clone(), values }
+  }
+}
+
+impl Lerp for ChartData {
+  fn lerp(&self, to: &Self, factor: f32) -> Self {
+    let empty = Series::default();
+    let series = to
+      .series
+      .iter()
+      .enumerate()
+      .map(|(i, s)| {
+        self
+          .series
+          .get(i)
+          .unwrap_or(&empty)
+          .lerp(s, factor)
+      })
+      .collect();
+    ChartData { labels: to.labels.clone(), series }
+  }
+}
+
+impl ChartStyle {
+  fn color(&self, idx: usize) -> Color {
+    if self.colors.is_empty() { self.axis } else { self.colors[idx % self.colors.len()] }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChartKind {
+  Line,
+  Bar,
+  Pie,
+}
+
+/// The render widget painting the data of the charts.
+struct ChartPlot {
+  kind: ChartKind,
+  data: ChartData,
+  style: ChartStyle,
+  layout: RefCell<Option<PlotLayout>>,
+}
+
+/// The size of a chart if its parent doesn't limit it.
+const DEFAULT_SIZE: Size = Size::new(320., 200.);
+
+impl ChartPlot {
+  fn item_at(&self, pos: Point) -> Option<ChartItem> {
+    let layout = self.layout.borrow();
+    let layout = layout.as_ref()?;
+    match self.kind {
+      ChartKind::Line => line_item_at(&self.data, &self.style, layout, pos),
+      ChartKind::Bar => bar_item_at(&self.data, layout, pos),
+      ChartKind::Pie => pie_item_at(&self.data, layout, pos),
+    }
+  }
+}
+
+impl Render for ChartPlot {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let default = clamp.clamp(DEFAULT_SIZE);
+    let width = if clamp.max.width.is_finite() { clamp.max.width } else { default.width };
+    let height = if clamp.max.height.is_finite() { clamp.max.height } else { default.height };
+    let size = Size::new(width, height);
+
+    let layout = if self.kind == ChartKind::Pie {
+      PlotLayout::without_axes(size)
+    } else {
+      let text_style = Provider::of::<TextStyle>(ctx).unwrap();
+      PlotLayout::new(&self.data, &text_style, self.style.tick_count, size)
+    };
+    *self.layout.borrow_mut() = Some(layout);
+    size
+  }
+
+  fn only_sized_by_parent(&self) -> bool { true }
+
+  fn paint(&self, ctx: &mut PaintingCtx) {
+    let layout = self.layout.borrow();
+    let Some(layout) = layout.as_ref() else { return };
+    let painter = ctx.painter();
+    match self.kind {
+      ChartKind::Line => {
+        layout.paint_axes(&self.style, painter);
+        paint_lines(&self.data, &self.style, layout, painter);
+      }
+      ChartKind::Bar => {
+        layout.paint_axes(&self.style, painter);
+        paint_bars(&self.data, &self.style, layout, painter);
+      }
+      ChartKind::Pie => paint_pie(&self.data, &self.style, layout, painter),
+    }
+  }
+
+  /// Only the items of the data are hit, so the tooltip hides when the pointer
+  /// leaves them.
+  fn hit_test(&self, _: &mut HitTestCtx, pos: Point) -> HitTest {
+    HitTest { hit: self.item_at(pos).is_some(), can_hit_child: false }
+  }
+}
+
+/// Build the widget of the chart of `kind` painting the `data`.
+fn chart(kind: ChartKind, data: impl StateWatcher<Value = ChartData>) -> Widget<'static> {
+  fn_widget! {
+    let style = ChartStyle::of(BuildCtx::get());
+    let transition = style.transition.clone().of(BuildCtx::get());
+    let plot = Stateful::new(ChartPlot {
+      kind,
+      data: $data.clone(),
+      style,
+      layout: RefCell::new(None),
+    });
+    let plot_data = part_writer!(&mut plot.data);
+    plot_data.clone_writer().transition(transition);
+    let u = watch!($data.clone()).subscribe(move |data| *plot_data.write() = data);
+
+    let hovered = Stateful::new(None);
+    let plot = FatObj::new(plot);
+    @ $plot {
+      tooltips: pipe!($hovered.map(|item| $plot.data.describe(item)).unwrap_or_default()),
+      on_pointer_move: move |e| {
+        let item = $plot.item_at(e.position());
+        if *$hovered != item {
+          *$hovered.write() = item;
+        }
+      },
+      on_disposed: move |_| u.unsubscribe(),
+    }
+  }
+  .into_widget()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sales() -> ChartData {
+    ChartData {
+      labels: vec!["Jan".into(), "Feb".into(), "Mar".into()],
+      series: vec![Series { name: "Sales".into(), values: vec![3., -1., 8.] }],
+    }
+  }
+
+  #[test]
+  fn data_range_and_describe() {
+    let data = sales();
+    assert_eq!(data.count(), 3);
+    assert_eq!(data.range(), (-1., 8.));
+    assert_eq!(data.describe(ChartItem { series: 0, index: 2 }), "Sales Mar: 8");
+    assert_eq!(data.describe(ChartItem { series: 1, index: 0 }), "");
+  }
+
+  #[test]
+  fn lerp_data() {
+    let from = ChartData::default();
+    let to = sales();
+    let half = from.lerp(&to, 0.5);
+    assert_eq!(half.labels, to.labels);
+    assert_eq!(half.series[0].values, [1.5, -0.5, 4.]);
+    assert_eq!(from.lerp(&to, 1.), to);
+  }
+}
@@ -0,0 +1,166 @@
+use ribir_core::prelude::*;
+
+use super::{ChartData, ChartStyle};
+
+/// The ticks of a value axis, from `min` to `max` by `step`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ticks {
+  pub min: f32,
+  pub max: f32,
+  pub step: f32,
+}
+
+/// The gap between the axes and their labels.
+const LABEL_GAP: f32 = 4.;
+
+impl Ticks {
+  /// The nice ticks covering `min` to `max` in about `count` steps, the step
+  /// is 1, 2 or 5 multiplied by a power of 10.
+  pub fn nice(min: f32, max: f32, count: usize) -> Self {
+    let max = if max - min > f32::EPSILON { max } else { min + 1. };
+    let raw = (max - min) / count.max(1) as f32;
+    let base = 10_f32.powf(raw.log10().floor());
+    let step = match raw / base {
+      f if f <= 1. => base,
+      f if f <= 2. => 2. * base,
+      f if f <= 5. => 5. * base,
+      _ => 10. * base,
+    };
+    // Tolerate the error of the division, so a bound on a tick is not extended.
+    let min = (min / step + 1e-4).floor() * step;
+    let max = (max / step - 1e-4).ceil() * step;
+    Ticks { min, max, step }
+  }
+
+  /// The values of the ticks from the minimum to the maximum.
+  pub fn values(&self) -> impl Iterator<Item = f32> + '_ {
+    let count = ((self.max - self.min) / self.step).round() as usize;
+    (0..=count).map(move |i| self.min + self.step * i as f32)
+  }
+
+  /// The text of the tick `value`, with the decimals the step needs.
+  pub fn format(&self, value: f32) -> String {
+    let decimals = (-self.step.log10().floor()).max(0.) as usize;
+    format!("{value:.decimals$}")
+  }
+}
+
+/// Where the plot area and the labels of a chart are.
+pub(super) struct PlotLayout {
+  /// The area the data is painted in.
+  pub(super) plot: Rect,
+  pub(super) ticks: Ticks,
+  /// The labels of the ticks and the categories, with their origin.
+  labels: Vec<(Point, VisualGlyphs)>,
+}
+
+impl PlotLayout {
+  pub(super) fn new(
+    data: &ChartData, text_style: &TextStyle, tick_count: usize, size: Size,
+  ) -> Self {
+    let (min, max) = data.range();
+    let ticks = Ticks::nice(min, max, tick_count);
+    let glyphs = |text: String| {
+      text_glyph(text.into(), text_style, TextAlign::Start, Size::splat(f32::INFINITY))
+    };
+
+    let tick_labels: Vec<_> = ticks
+      .values()
+      .map(|v| glyphs(ticks.format(v)))
+      .collect();
+    let left = tick_labels
+      .iter()
+      .map(|g| g.visual_rect().width())
+      .fold(0., f32::max)
+      + LABEL_GAP;
+    let bottom = text_style.line_height + LABEL_GAP;
+    // Keep the half of the top label in the chart.
+    let top = text_style.line_height / 2.;
+    let plot = Rect::new(
+      Point::new(left, top),
+      Size::new((size.width - left).max(0.), (size.height - top - bottom).max(0.)),
+    );
+
+    let mut layout = PlotLayout { plot, ticks, labels: vec![] };
+    for (v, g) in ticks.values().zip(tick_labels) {
+      let rc = g.visual_rect();
+      let origin = Point::new(left - LABEL_GAP - rc.width(), layout.value_y(v) - rc.height() / 2.);
+      layout.labels.push((origin, g));
+    }
+    let count = data.count();
+    for (idx, label) in data.labels.iter().enumerate().take(count) {
+      let g = glyphs(label.to_string());
+      let x = layout.category_x(idx, count) - g.visual_rect().width() / 2.;
+      layout
+        .labels
+        .push((Point::new(x, plot.max_y() + LABEL_GAP), g));
+    }
+    layout
+  }
+
+  /// The layout of a chart that has no axis, the whole size is the plot area.
+  pub(super) fn without_axes(size: Size) -> Self {
+    PlotLayout { plot: Rect::from_size(size), ticks: Ticks::nice(0., 1., 1), labels: vec![] }
+  }
+
+  /// The y of the `value` in the plot area.
+  pub(super) fn value_y(&self, value: f32) -> f32 {
+    let Ticks { min, max, .. } = self.ticks;
+    self.plot.max_y() - (value - min) / (max - min) * self.plot.height()
+  }
+
+  /// The width of the band of a category in the plot area.
+  pub(super) fn band_width(&self, count: usize) -> f32 { self.plot.width() / count.max(1) as f32 }
+
+  /// The x of the center of the category `idx` in the plot area.
+  pub(super) fn category_x(&self, idx: usize, count: usize) -> f32 {
+    self.plot.min_x() + self.band_width(count) * (idx as f32 + 0.5)
+  }
+
+  pub(super) fn paint_axes(&self, style: &ChartStyle, painter: &mut Painter) {
+    let plot = self.plot;
+    painter.set_line_width(1.);
+    painter.set_stroke_brush(style.grid);
+    for v in self.ticks.values() {
+      let y = self.value_y(v);
+      painter
+        .begin_path(Point::new(plot.min_x(), y))
+        .line_to(Point::new(plot.max_x(), y))
+        .end_path(false)
+        .stroke();
+    }
+
+    painter
+      .set_stroke_brush(style.axis)
+      .begin_path(plot.origin)
+      .line_to(Point::new(plot.min_x(), plot.max_y()))
+      .line_to(Point::new(plot.max_x(), plot.max_y()))
+      .end_path(false)
+      .stroke();
+
+    painter.set_fill_brush(style.label);
+    for (origin, glyphs) in &self.labels {
+      let mut painter = painter.save_guard();
+      painter.translate(origin.x, origin.y);
+      let rect = Rect::from_size(glyphs.visual_rect().size);
+      paint_text(&mut painter, glyphs, PaintingStyle::Fill, rect);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn nice_ticks() {
+    assert_eq!(Ticks::nice(0., 97., 5), Ticks { min: 0., max: 100., step: 20. });
+    assert_eq!(Ticks::nice(-3., 8., 5), Ticks { min: -5., max: 10., step: 5. });
+    assert_eq!(Ticks::nice(5., 5., 1), Ticks { min: 5., max: 6., step: 1. });
+
+    let ticks = Ticks::nice(0., 97., 5);
+    assert_eq!(ticks.values().collect::<Vec<_>>(), [0., 20., 40., 60., 80., 100.]);
+    assert_eq!(ticks.format(40.), "40");
+    assert_eq!(Ticks { min: 0., max: 1., step: 0.5 }.format(0.5), "0.5");
+  }
+}
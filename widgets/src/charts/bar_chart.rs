@@ -0,0 +1,82 @@
+use ribir_core::prelude::*;
+
+use super::*;
+
+/// A chart draws the values of the series as bars, the bars of a category
+/// stand side by side from the zero of the value axis.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _chart = fn_widget! {
+///   @BarChart {
+///     data: ChartData {
+///       labels: vec!["Q1".into(), "Q2".into()],
+///       series: vec![
+///         Series { name: "2024".into(), values: vec![12., 18.] },
+///         Series { name: "2025".into(), values: vec![15., 21.] },
+///       ],
+///     }
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct BarChart {
+  pub data: ChartData,
+}
+
+/// The rate of the band of a category the bars take.
+const BARS_RATE: f32 = 0.7;
+
+impl Compose for BarChart {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    chart(ChartKind::Bar, part_writer!(&mut this.data))
+  }
+}
+
+fn bar_rect(data: &ChartData, layout: &PlotLayout, item: ChartItem, value: f32) -> Rect {
+  let count = data.count();
+  let band = layout.band_width(count);
+  let width = band * BARS_RATE / data.series.len().max(1) as f32;
+  let x = layout.plot.min_x()
+    + band * item.index as f32
+    + band * (1. - BARS_RATE) / 2.
+    + width * item.series as f32;
+  let zero = layout.value_y(0.);
+  let y = layout.value_y(value);
+  Rect::new(Point::new(x, y.min(zero)), Size::new(width, (y - zero).abs()))
+}
+
+fn bars(data: &ChartData) -> impl Iterator<Item = (ChartItem, f32)> + '_ {
+  data
+    .series
+    .iter()
+    .enumerate()
+    .flat_map(|(s, series)| {
+      series
+        .values
+        .iter()
+        .enumerate()
+        .map(move |(index, v)| (ChartItem { series: s, index }, *v))
+    })
+}
+
+pub(super) fn paint_bars(
+  data: &ChartData, style: &ChartStyle, layout: &PlotLayout, painter: &mut Painter,
+) {
+  for (item, v) in bars(data) {
+    painter
+      .set_fill_brush(style.color(item.series))
+      .rect(&bar_rect(data, layout, item, v))
+      .fill();
+  }
+}
+
+pub(super) fn bar_item_at(data: &ChartData, layout: &PlotLayout, pos: Point) -> Option<ChartItem> {
+  bars(data)
+    .find(|(item, v)| bar_rect(data, layout, *item, *v).contains(pos))
+    .map(|(item, _)| item)
+}
@@ -0,0 +1,86 @@
+use ribir_core::prelude::*;
+
+use super::*;
+
+/// A chart connects the values of every series by a line, the points of the
+/// values are placed at the center of their categories.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let data = Stateful::new(ChartData {
+///   labels: vec!["Jan".into(), "Feb".into(), "Mar".into()],
+///   series: vec![Series { name: "Sales".into(), values: vec![3., 5., 4.] }],
+/// });
+/// let _chart = fn_widget! {
+///   @LineChart { data: pipe!($data.clone()) }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct LineChart {
+  pub data: ChartData,
+}
+
+impl Compose for LineChart {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    chart(ChartKind::Line, part_writer!(&mut this.data))
+  }
+}
+
+fn point_of(layout: &PlotLayout, count: usize, idx: usize, value: f32) -> Point {
+  Point::new(layout.category_x(idx, count), layout.value_y(value))
+}
+
+pub(super) fn paint_lines(
+  data: &ChartData, style: &ChartStyle, layout: &PlotLayout, painter: &mut Painter,
+) {
+  let count = data.count();
+  painter.set_line_width(style.line_width);
+  for (s, series) in data.series.iter().enumerate() {
+    let color = style.color(s);
+    let mut points = series
+      .values
+      .iter()
+      .enumerate()
+      .map(|(idx, v)| point_of(layout, count, idx, *v));
+    let Some(first) = points.next() else { continue };
+    painter.set_stroke_brush(color).begin_path(first);
+    for p in points {
+      painter.line_to(p);
+    }
+    painter.end_path(false).stroke();
+
+    painter.set_fill_brush(color);
+    for (idx, v) in series.values.iter().enumerate() {
+      painter
+        .circle(point_of(layout, count, idx, *v), style.point_radius)
+        .fill();
+    }
+  }
+}
+
+/// The point nearest to `pos` within the twice radius of the points.
+pub(super) fn line_item_at(
+  data: &ChartData, style: &ChartStyle, layout: &PlotLayout, pos: Point,
+) -> Option<ChartItem> {
+  let count = data.count();
+  let max_distance = style.point_radius * 2.;
+  data
+    .series
+    .iter()
+    .enumerate()
+    .flat_map(|(s, series)| {
+      series
+        .values
+        .iter()
+        .enumerate()
+        .map(move |(index, v)| (ChartItem { series: s, index }, *v))
+    })
+    .map(|(item, v)| (item, (point_of(layout, count, item.index, v) - pos).length()))
+    .filter(|(_, distance)| *distance <= max_distance)
+    .min_by(|a, b| a.1.total_cmp(&b.1))
+    .map(|(item, _)| item)
+}
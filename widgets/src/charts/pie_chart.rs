@@ -0,0 +1,110 @@
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+use ribir_core::prelude::*;
+
+use super::*;
+
+/// A chart divides a circle into slices by the values of the first series of
+/// its data, the negative values take no slice.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _chart = fn_widget! {
+///   @PieChart {
+///     data: ChartData {
+///       labels: vec!["Rust".into(), "Dart".into(), "Swift".into()],
+///       series: vec![Series { name: "".into(), values: vec![6., 3., 1.] }],
+///     }
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct PieChart {
+  pub data: ChartData,
+}
+
+impl Compose for PieChart {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    chart(ChartKind::Pie, part_writer!(&mut this.data))
+  }
+}
+
+/// The start and end angles of the slices from the top clockwise, in radians.
+fn slices(data: &ChartData) -> Vec<(f32, f32)> {
+  let values = data.series.first().map_or(&[][..], |s| &s.values);
+  let total: f32 = values.iter().map(|v| v.max(0.)).sum();
+  let mut start = 0.;
+  values
+    .iter()
+    .map(|v| {
+      let sweep = if total > 0. { v.max(0.) / total * TAU } else { 0. };
+      let slice = (start, start + sweep);
+      start += sweep;
+      slice
+    })
+    .collect()
+}
+
+fn circle(layout: &PlotLayout) -> (Point, f32) {
+  let plot = layout.plot;
+  (plot.center(), plot.width().min(plot.height()) / 2.)
+}
+
+pub(super) fn paint_pie(
+  data: &ChartData, style: &ChartStyle, layout: &PlotLayout, painter: &mut Painter,
+) {
+  let (center, radius) = circle(layout);
+  for (idx, (start, end)) in slices(data).into_iter().enumerate() {
+    if end - start <= 0. {
+      continue;
+    }
+    // The angles of the painter start from the right.
+    let start = Angle::radians(start - FRAC_PI_2);
+    let end = Angle::radians(end - FRAC_PI_2);
+    let from = center + Vector::new(start.radians.cos(), start.radians.sin()) * radius;
+    painter
+      .set_fill_brush(style.color(idx))
+      .begin_path(center)
+      .line_to(from)
+      .arc_to(center, radius, start, end)
+      .line_to(center)
+      .end_path(true)
+      .fill();
+  }
+}
+
+pub(super) fn pie_item_at(data: &ChartData, layout: &PlotLayout, pos: Point) -> Option<ChartItem> {
+  let (center, radius) = circle(layout);
+  let v = pos - center;
+  if v.length() > radius {
+    return None;
+  }
+  let angle = (v.y.atan2(v.x) + FRAC_PI_2).rem_euclid(TAU);
+  slices(data)
+    .into_iter()
+    .position(|(start, end)| start <= angle && angle < end)
+    .map(|index| ChartItem { series: 0, index })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn hit_slices() {
+    let data = ChartData {
+      labels: vec!["A".into(), "B".into()],
+      series: vec![Series { name: "".into(), values: vec![1., 3.] }],
+    };
+    let layout = PlotLayout::without_axes(Size::new(100., 100.));
+    let hit = |x, y| pie_item_at(&data, &layout, Point::new(x, y)).map(|item| item.index);
+    // The first slice is the quarter from the top to the right.
+    assert_eq!(hit(70., 30.), Some(0));
+    assert_eq!(hit(30., 70.), Some(1));
+    assert_eq!(hit(2., 2.), None);
+  }
+}
@@ -0,0 +1,224 @@
+use std::{cell::RefCell, future::Future, rc::Rc};
+
+use futures::channel::oneshot;
+use ribir_core::prelude::*;
+
+class_names! {
+  #[doc = "Class name for the surface of the dialog"]
+  DIALOG,
+}
+
+/// A modal dialog shows above all the content of the window with a scrim.
+///
+/// The scrim blocks the pointer events to the content below, the tab
+/// navigation is trapped in the dialog, and pressing `Escape` cancels it. The
+/// dialog fades in and out by the `EASE_OUT` transition of the theme.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @FilledButton {
+///     on_tap: move |e| {
+///       let result = Dialog::show(e.window(), |ctrl: DialogCtrl<bool>| {
+///         let (yes, no) = (ctrl.clone(), ctrl);
+///         row! {
+///           @TextButton { on_tap: move |_| yes.close(true), @{ "Yes" } }
+///           @TextButton { on_tap: move |_| no.close(false), @{ "No" } }
+///         }
+///         .into_widget()
+///       });
+///       let _ = AppCtx::spawn_local(async move {
+///         println!("The answer is {:?}", result.await);
+///       });
+///     },
+///     @{ "Ask" }
+///   }
+/// };
+/// ```
+pub struct Dialog;
+
+/// The handle of a dialog shown by [`Dialog::show`], to close it with a
+/// result.
+pub struct DialogCtrl<R>(Rc<RefCell<DialogInner<R>>>);
+
+struct DialogInner<R> {
+  overlay: Option<Overlay>,
+  sender: Option<oneshot::Sender<R>>,
+}
+
+impl Dialog {
+  /// Show the content built by `builder` in a modal dialog, the `builder` is
+  /// given the handle to close the dialog.
+  ///
+  /// Return a future that resolves to the result the dialog closed with, or
+  /// `None` if the dialog is canceled.
+  pub fn show<R: 'static>(
+    wnd: Sc<Window>, mut builder: impl FnMut(DialogCtrl<R>) -> Widget<'static> + 'static,
+  ) -> impl Future<Output = Option<R>> {
+    let (sender, receiver) = oneshot::channel();
+    let ctrl =
+      DialogCtrl(Rc::new(RefCell::new(DialogInner { overlay: None, sender: Some(sender) })));
+    let ctrl2 = ctrl.clone();
+    let overlay = Overlay::new(
+      move || dialog(ctrl2.clone(), builder(ctrl2.clone())),
+      OverlayStyle { auto_close_policy: AutoClosePolicy::NOT_AUTO_CLOSE, mask: None },
+    );
+    overlay.show(wnd);
+    ctrl.0.borrow_mut().overlay = Some(overlay);
+
+    async move { receiver.await.ok() }
+  }
+}
+
+impl<R> DialogCtrl<R> {
+  /// Close the dialog, and resolve its future with the `result`.
+  pub fn close(&self, result: R) {
+    let sender = self.0.borrow_mut().sender.take();
+    if let Some(sender) = sender {
+      let _ = sender.send(result);
+    }
+    self.cancel();
+  }
+
+  /// Close the dialog without a result.
+  pub fn cancel(&self) {
+    let mut inner = self.0.borrow_mut();
+    inner.sender.take();
+    // Release the overlay to break the reference cycle from its content.
+    let overlay = inner.overlay.take();
+    drop(inner);
+    if let Some(overlay) = overlay {
+      overlay.close();
+    }
+  }
+
+  /// Whether the dialog is showing.
+  pub fn is_showing(&self) -> bool { self.0.borrow().overlay.is_some() }
+}
+
+impl<R> Clone for DialogCtrl<R> {
+  fn clone(&self) -> Self { Self(self.0.clone()) }
+}
+
+fn dialog<R: 'static>(ctrl: DialogCtrl<R>, content: Widget<'static>) -> Widget<'static> {
+  fn_widget! {
+    let content = FatObj::new(content);
+    let mut scrim = @Container {
+      size: Size::splat(f32::INFINITY),
+      background: Palette::of(BuildCtx::get()).scrim().with_alpha(0.32),
+      opacity: 0.,
+      tab_index: -1_i16,
+      auto_focus: true,
+    };
+    let animate = part_writer!(&mut scrim.opacity)
+      .transition(transitions::EASE_OUT.of(BuildCtx::get()));
+    let ctrl2 = ctrl.clone();
+
+    @ $scrim {
+      keep_alive: pipe!($animate.is_running() || $scrim.opacity != 0.),
+      on_mounted: move |_| $scrim.write().opacity = 1.,
+      on_disposed: move |_| {
+        $scrim.write().opacity = 0.;
+        ctrl.cancel();
+      },
+      on_key_down: move |e| {
+        if *e.key() == VirtualKey::Named(NamedKey::Escape) {
+          ctrl2.cancel();
+        }
+      },
+      @FocusScope {
+        trap: true,
+        @ $content {
+          class: DIALOG,
+          h_align: HAlign::Center,
+          v_align: VAlign::Center,
+        }
+      }
+    }
+  }
+  .into_widget()
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+  use winit::{
+    event::{DeviceId, ElementState, MouseButton, WindowEvent},
+    keyboard::{KeyCode, KeyLocation, PhysicalKey},
+  };
+
+  use super::*;
+
+  fn tap_at(wnd: &mut TestWindow, x: f64, y: f64) {
+    let device_id = unsafe { DeviceId::dummy() };
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::CursorMoved { device_id, position: (x, y).into() });
+    wnd.process_mouse_input(device_id, ElementState::Pressed, MouseButton::Left);
+    wnd.process_mouse_input(device_id, ElementState::Released, MouseButton::Left);
+    wnd.draw_frame();
+  }
+
+  #[test]
+  fn dialog_result() {
+    reset_test_env!();
+
+    let (taps, w_taps) = split_value(0);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(200., 200.),
+          on_tap: move |_| *$w_taps.write() += 1,
+        }
+      },
+      Size::new(200., 200.),
+    );
+    wnd.draw_frame();
+
+    let (result, w_result) = split_value(None);
+    let show = |wnd: &mut TestWindow| {
+      let ctrl = Rc::new(RefCell::new(None));
+      let ctrl2 = ctrl.clone();
+      let future = Dialog::show(wnd.0.clone(), move |c: DialogCtrl<i32>| {
+        *ctrl2.borrow_mut() = Some(c.clone());
+        fn_widget! {
+          @MockBox {
+            size: Size::new(20., 20.),
+            on_tap: move |_| c.close(7),
+          }
+        }
+        .into_widget()
+      });
+      let w_result = w_result.clone_writer();
+      let _ = AppCtx::spawn_local(async move { *w_result.write() = Some(future.await) });
+      wnd.draw_frame();
+      let ctrl = ctrl.borrow().clone().unwrap();
+      ctrl
+    };
+
+    let ctrl = show(&mut wnd);
+    assert!(ctrl.is_showing());
+    // The scrim blocks the tap to the content below.
+    tap_at(&mut wnd, 10., 10.);
+    assert_eq!(*taps.read(), 0);
+    assert!(ctrl.is_showing());
+    // The content is at the center.
+    tap_at(&mut wnd, 100., 100.);
+    assert!(!ctrl.is_showing());
+    assert_eq!(*result.read(), Some(Some(7)));
+
+    let ctrl = show(&mut wnd);
+    wnd.processes_keyboard_event(
+      PhysicalKey::Code(KeyCode::Escape),
+      VirtualKey::Named(NamedKey::Escape),
+      false,
+      KeyLocation::Standard,
+      ElementState::Pressed,
+    );
+    wnd.draw_frame();
+    assert!(!ctrl.is_showing());
+    assert_eq!(*result.read(), Some(None));
+  }
+}
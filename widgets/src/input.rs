@@ -20,6 +20,8 @@ class_names!(
   INPUT,
   ///Class name for the text area widget
   TEXTAREA,
+  ///Class name for the line numbers of the text area
+  TEXTAREA_LINE_NUMBERS,
 );
 
 /// The `Input` struct is a widget that represents a text input field
@@ -82,11 +84,28 @@ impl Input {
 
 /// The `TextArea` struct is a widget that represents a text input field
 /// that displays multiple lines of text. for single line text, use `[Input]`
+///
+/// It scrolls vertically when the text is taller than it, and also
+/// horizontally if the text doesn't wrap. Editing a long text only reshapes
+/// the paragraphs changed.
+///
+/// ## Example
+///
+/// ```rust no_run
+/// use ribir::prelude::*;
+/// let w = fn_widget! {
+///   @TextArea { line_numbers: true, auto_wrap: false }
+/// };
+/// App::run(w);
+/// ```
 #[derive(Declare)]
 pub struct TextArea {
   /// if true, the text will be auto wrap when the text is too long
   #[declare(default = true)]
   auto_wrap: bool,
+  /// if true, the number of every line is shown before it
+  #[declare(default)]
+  line_numbers: bool,
   #[declare(skip)]
   basic: BasicEditor<CowArc<str>>,
 }
@@ -112,6 +131,30 @@ impl TextArea {
 
   /// return the selection range of the text
   pub fn selection(&self) -> Range<usize> { self.basic.cluster_rg() }
+
+  /// The numbers of the lines, one for every visual row of the text, the rows
+  /// wrapped from the previous row are left blank.
+  fn line_number_text(&self) -> String {
+    let Some(glyphs) = self.basic.glyphs() else { return String::new() };
+    let text = self.text();
+    let mut line = 0;
+    let mut numbers = String::new();
+    for row in 0..glyphs.glyph_row_count() {
+      if row > 0 {
+        numbers.push('\n');
+      }
+      let cluster = glyphs.position_to_cluster(row, 0);
+      if row == 0
+        || text
+          .get(..cluster)
+          .is_some_and(|t| t.ends_with('\n'))
+      {
+        line += 1;
+        numbers.push_str(&line.to_string());
+      }
+    }
+    numbers
+  }
 }
 
 #[derive(Clone, Eq, PartialEq, Default)]
@@ -135,6 +178,12 @@ impl VisualText for InputText {
     self.0.layout_glyphs(clamp, ctx)
   }
 
+  fn layout_glyphs_with_memo(
+    &self, clamp: BoxClamp, ctx: &LayoutCtx, memo: &mut ShapeMemo,
+  ) -> VisualGlyphs {
+    self.0.layout_glyphs_with_memo(clamp, ctx, memo)
+  }
+
   fn paint(&self, painter: &mut Painter, style: PaintingStyle, glyphs: &VisualGlyphs, rect: Rect) {
     self.0.paint(painter, style, glyphs, rect);
   }
@@ -180,14 +229,39 @@ impl Compose for Input {
 
 impl Compose for TextArea {
   fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
-    focus_scope! {
-      @TextClamp {
-        rows: Some(2.),
-        cols: Some(20.),
-        class: TEXTAREA,
-        @Scrollbar {
-          text_overflow: TextOverflow::AutoWrap,
-          @part_writer!(&mut this.basic)
+    let line_numbers = this.read().line_numbers;
+    fn_widget! {
+      let editor = part_writer!(&mut this.basic);
+      let content = if line_numbers {
+        let numbers = Stateful::new(String::new());
+        @Row {
+          @Text { class: TEXTAREA_LINE_NUMBERS, text: pipe!($numbers.clone()) }
+          @FatObj {
+            on_performed_layout: move |_| {
+              let text = $this.line_number_text();
+              if *$numbers != text {
+                *$numbers.write() = text;
+              }
+            },
+            @ { editor }
+          }
+        }
+        .into_widget()
+      } else {
+        editor.into_widget()
+      };
+      @FocusScope {
+        @TextClamp {
+          rows: Some(2.),
+          cols: Some(20.),
+          class: TEXTAREA,
+          @Scrollbar {
+            scrollable: pipe!(if $this.auto_wrap { Scrollable::Y } else { Scrollable::Both }),
+            text_overflow: pipe! {
+              if $this.auto_wrap { TextOverflow::AutoWrap } else { TextOverflow::Overflow }
+            },
+            @ { content }
+          }
         }
       }
     }
@@ -197,6 +271,8 @@ impl Compose for TextArea {
 
 #[cfg(test)]
 mod tests {
+  use std::{cell::RefCell, rc::Rc};
+
   use ribir_core::{prelude::*, reset_test_env, test_helper::*};
   use winit::event::{DeviceId, ElementState, MouseButton, WindowEvent};
 
@@ -253,4 +329,111 @@ mod tests {
     wnd.draw_frame();
     assert_eq!(*value.read(), "hello");
   }
+
+  #[test]
+  fn text_area_line_numbers() {
+    reset_test_env!();
+    let (area, w_area) = split_value(None);
+    let w = fn_widget! {
+      let area = @TextArea { auto_focus: true, line_numbers: true };
+      *$w_area.write() = Some(area.clone_writer());
+      area
+    };
+
+    let mut wnd = TestWindow::new_with_size(w, Size::new(200., 200.));
+    wnd.draw_frame();
+    let line_numbers = || {
+      area
+        .read()
+        .as_ref()
+        .unwrap()
+        .read()
+        .line_number_text()
+    };
+    assert_eq!(line_numbers(), "1");
+
+    wnd.processes_receive_chars("one\ntwo\n".into());
+    wnd.draw_frame();
+    assert_eq!(line_numbers(), "1\n2\n3");
+  }
+
+  struct MemClipboard(Rc<RefCell<String>>);
+
+  impl ribir_core::clipboard::Clipboard for MemClipboard {
+    fn read_text(&mut self) -> Result<String, std::io::Error> { Ok(self.0.borrow().clone()) }
+
+    fn write_text(&mut self, text: &str) -> Result<(), std::io::Error> {
+      *self.0.borrow_mut() = text.to_string();
+      Ok(())
+    }
+
+    fn read_img(&mut self) -> Result<PixelImage, std::io::Error> { Err(unsupported()) }
+
+    fn write_img(&mut self, _: &PixelImage) -> Result<(), std::io::Error> { Err(unsupported()) }
+
+    fn read(&mut self, _: &str) -> Result<std::borrow::Cow<'_, [u8]>, std::io::Error> {
+      Err(unsupported())
+    }
+
+    fn write(&mut self, _: &str, _: &[u8]) -> Result<(), std::io::Error> { Err(unsupported()) }
+
+    fn clear(&mut self) -> Result<(), std::io::Error> {
+      self.0.borrow_mut().clear();
+      Ok(())
+    }
+  }
+
+  fn unsupported() -> std::io::Error { std::io::ErrorKind::Unsupported.into() }
+
+  fn command_key(wnd: &mut TestWindow, code: KeyCode) {
+    #[cfg(target_os = "macos")]
+    let command = ModifiersState::SUPER;
+    #[cfg(not(target_os = "macos"))]
+    let command = ModifiersState::CONTROL;
+
+    let physical_key = PhysicalKey::Code(code);
+    let key = VirtualKey::Character(
+      format!("{code:?}")
+        .trim_start_matches("Key")
+        .to_lowercase()
+        .into(),
+    );
+    wnd.process_input(TestInput::Modifiers(command));
+    wnd.process_input(TestInput::KeyPress { key: key.clone(), physical_key });
+    wnd.process_input(TestInput::KeyRelease { key, physical_key });
+    wnd.draw_frame();
+    wnd.process_input(TestInput::Modifiers(ModifiersState::empty()));
+  }
+
+  #[test]
+  fn copy_cut_paste_by_command_key() {
+    reset_test_env!();
+    let clipboard = Rc::new(RefCell::new(String::new()));
+    AppCtx::set_clipboard(Box::new(MemClipboard(clipboard.clone())));
+
+    let (value, w_value) = split_value(String::default());
+    let w = fn_widget! {
+      let input = @Input { auto_focus: true };
+      watch!($input.text().clone())
+        .subscribe(move |text| *$w_value.write() = text.to_string());
+      input
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(200., 200.));
+    wnd.draw_frame();
+    wnd.processes_receive_chars("hello".into());
+    wnd.draw_frame();
+
+    command_key(&mut wnd, KeyCode::KeyA);
+    command_key(&mut wnd, KeyCode::KeyC);
+    assert_eq!(&*clipboard.borrow(), "hello");
+    assert_eq!(*value.read(), "hello");
+
+    command_key(&mut wnd, KeyCode::KeyX);
+    assert_eq!(&*clipboard.borrow(), "hello");
+    assert_eq!(*value.read(), "");
+
+    command_key(&mut wnd, KeyCode::KeyV);
+    command_key(&mut wnd, KeyCode::KeyV);
+    assert_eq!(*value.read(), "hellohello");
+  }
 }
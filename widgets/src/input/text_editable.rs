@@ -1,6 +1,6 @@
 use std::ops::Range;
 
-use ribir_core::prelude::*;
+use ribir_core::{prelude::*, window::ImePurpose};
 
 use super::{
   CaretPosition,
@@ -31,23 +31,32 @@ impl<T: Default + VisualText + EditText + Clone + 'static> Compose for BasicEdit
 
       @Stack {
         fit: StackFit::Passthrough,
-        @ $text {
+        // The margin wraps the text, so the listeners below are mixed into the
+        // selectable text and it receives the keys of the focused editor too.
+        @FatObj {
           margin: pipe!($caret.layout_size()).map(|v|EdgeInsets::only_right(v.width)),
-          on_focus_in: move |e| { e.window().set_ime_allowed(true); },
-          on_focus_out: move|e| { e.window().set_ime_allowed(false); },
-          on_chars: move |e| {
-            let mut this = $this.write();
-            if !this.chars_handle(e) {
-              this.forget_modifies();
-            }
-          },
-          on_key_down: move |k| {
-            let mut this = $this.write();
-            if !this.keys_handle(k) {
-              this.forget_modifies();
-            }
-          },
-          on_ime_pre_edit: move|e| { $this.write().process_pre_edit(e);},
+          @ $text {
+            on_focus_in: move |e| {
+              let purpose = if e.is_sensitive() { ImePurpose::Password } else { ImePurpose::Normal };
+              e.window().set_ime_purpose(purpose).set_ime_allowed(true);
+            },
+            on_focus_out: move|e| {
+              e.window().set_ime_allowed(false).set_ime_purpose(ImePurpose::Normal);
+            },
+            on_chars: move |e| {
+              let mut this = $this.write();
+              if !this.chars_handle(e) {
+                this.forget_modifies();
+              }
+            },
+            on_key_down: move |k| {
+              let mut this = $this.write();
+              if !this.keys_handle(k) {
+                this.forget_modifies();
+              }
+            },
+            on_ime_pre_edit: move|e| { $this.write().process_pre_edit(e);},
+          }
         }
         @IgnorePointer {
           @UnconstrainedBox {
@@ -141,7 +150,8 @@ impl<T: EditText + 'static> BasicEditor<T> {
           return true;
         }
       }
-      PhysicalKey::Code(KeyCode::KeyX) => {
+      // The sensitive content is not allowed to be cut to the clipboard.
+      PhysicalKey::Code(KeyCode::KeyX) if !event.is_sensitive() => {
         let rg = self.cluster_rg();
         if !rg.is_empty() {
           let txt = self.substr(rg).to_string();
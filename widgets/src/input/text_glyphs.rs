@@ -1,6 +1,6 @@
 use std::cell::{Ref, RefCell};
 
-use ribir_core::prelude::*;
+use ribir_core::prelude::{font_db::GlyphBaseline, typography::PlaceLineDirection, *};
 
 use super::{CaretPosition, edit_text::BaseText};
 
@@ -17,10 +17,16 @@ where
   text: T,
   #[declare(skip)]
   glyphs: RefCell<Option<VisualGlyphs>>,
+  /// The shape results of the last layout, so an edit only reshapes the
+  /// paragraphs it changes.
+  #[declare(skip)]
+  memo: RefCell<ShapeMemo>,
 }
 
 impl<T: 'static> TextGlyphs<T> {
-  pub fn new(text: T) -> Self { Self { text, glyphs: Default::default() } }
+  pub fn new(text: T) -> Self {
+    Self { text, glyphs: Default::default(), memo: Default::default() }
+  }
 
   pub fn text(&self) -> &T { &self.text }
 
@@ -38,6 +44,15 @@ pub trait VisualText: BaseText {
   /// return self's glyphs layout info.
   fn layout_glyphs(&self, clamp: BoxClamp, ctx: &LayoutCtx) -> VisualGlyphs;
 
+  /// Like `layout_glyphs`, but the unchanged paragraphs are shaped by the
+  /// results kept in `memo` by the last layout.
+  fn layout_glyphs_with_memo(
+    &self, clamp: BoxClamp, ctx: &LayoutCtx, memo: &mut ShapeMemo,
+  ) -> VisualGlyphs {
+    let _ = memo;
+    self.layout_glyphs(clamp, ctx)
+  }
+
   /// paint the glyphs in the rect.
   fn paint(&self, painter: &mut Painter, style: PaintingStyle, glyphs: &VisualGlyphs, rect: Rect);
 }
@@ -48,6 +63,23 @@ impl VisualText for CowArc<str> {
     text_glyph(self.substr(..), &style, TextAlign::Start, clamp.max)
   }
 
+  fn layout_glyphs_with_memo(
+    &self, clamp: BoxClamp, ctx: &LayoutCtx, memo: &mut ShapeMemo,
+  ) -> VisualGlyphs {
+    let style = Provider::of::<TextStyle>(ctx).unwrap();
+    AppCtx::typography_store()
+      .borrow_mut()
+      .typography_with_memo(
+        self.substr(..),
+        &style,
+        clamp.max,
+        TextAlign::Start,
+        GlyphBaseline::Middle,
+        PlaceLineDirection::TopToBottom,
+        memo,
+      )
+  }
+
   fn paint(&self, painter: &mut Painter, style: PaintingStyle, glyphs: &VisualGlyphs, rect: Rect) {
     paint_text(painter, glyphs, style, rect);
   }
@@ -61,13 +93,18 @@ impl<T: VisualText> TextGlyphs<T> {
   }
 
   pub fn layout_glyphs(&mut self, clamp: BoxClamp, ctx: &LayoutCtx) {
-    *self.glyphs.borrow_mut() = Some(self.text.layout_glyphs(clamp, ctx));
+    let glyphs = self
+      .text
+      .layout_glyphs_with_memo(clamp, ctx, self.memo.get_mut());
+    *self.glyphs.borrow_mut() = Some(glyphs);
   }
 }
 
 impl<T: VisualText + 'static> Render for TextGlyphs<T> {
   fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
-    let glyphs = self.text.layout_glyphs(clamp, ctx);
+    let glyphs = self
+      .text
+      .layout_glyphs_with_memo(clamp, ctx, &mut self.memo.borrow_mut());
     let size = glyphs.visual_rect().size;
     *self.glyphs.borrow_mut() = Some(glyphs);
     clamp.clamp(size)
@@ -87,6 +124,10 @@ impl<T: VisualText + 'static> Render for TextGlyphs<T> {
     let visual_glyphs = self.glyphs().unwrap();
     paint_text(ctx.painter(), &visual_glyphs, style.unwrap_or(PaintingStyle::Fill), box_rect);
   }
+
+  fn baseline_offset(&self, _: &mut LayoutCtx) -> Option<f32> {
+    self.glyphs().and_then(|g| g.first_baseline())
+  }
 }
 
 pub trait VisualGlyphsHelper {
@@ -244,6 +285,7 @@ mod tests {
       letter_space: 0.,
       line_height: 16.,
       overflow: TextOverflow::AutoWrap,
+      max_lines: None,
     };
     let text: CowArc<str> = "1 23 456 7890\n12345".into();
     let glyphs = store.typography(
@@ -209,7 +209,8 @@ impl<T: BaseText> TextSelectable<T> {
       PhysicalKey::Code(KeyCode::KeyC) => {
         let rg = self.cluster_rg();
         let text = text.substr(rg);
-        if !text.is_empty() {
+        // The sensitive content is not allowed to be copied to the clipboard.
+        if !text.is_empty() && !event.is_sensitive() {
           let clipboard = AppCtx::clipboard();
           let _ = clipboard.borrow_mut().clear();
           let _ = clipboard.borrow_mut().write_text(&text);
@@ -33,7 +33,15 @@ mod stack;
 pub use stack::*;
 pub mod only_sized_by_parent;
 pub use only_sized_by_parent::*;
+mod relayout_boundary;
+pub use relayout_boundary::*;
 mod fractionally;
 pub use fractionally::*;
+mod aspect_ratio;
+pub use aspect_ratio::*;
+mod safe_area;
+pub use safe_area::*;
 mod line;
 pub use line::*;
+mod intrinsic_width;
+pub use intrinsic_width::*;
@@ -0,0 +1,116 @@
+use ribir_core::prelude::*;
+
+/// A widget sizes its child to the given ratio of the width to the height,
+/// within the clamp of its parent.
+///
+/// It tries the largest width the clamp allows first, then the largest height
+/// if the width is unlimited. If the size overflows the clamp, it's shrunk
+/// along the overflowing axis, the ratio is kept as long as the clamp allows.
+///
+/// Alternatively, it can function as an empty box of the ratio.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// // A 16:9 video area as wide as its parent.
+/// let _w = fn_widget! {
+///   @AspectRatio {
+///     ratio: 16. / 9.,
+///     @Container { size: Size::splat(f32::INFINITY), background: Color::BLACK }
+///   }
+/// };
+/// ```
+#[derive(SingleChild, Declare, Clone)]
+pub struct AspectRatio {
+  /// The ratio of the width to the height, must be greater than zero.
+  pub ratio: f32,
+}
+
+impl AspectRatio {
+  fn size_in(&self, clamp: BoxClamp, unlimited_width: impl FnOnce() -> f32) -> Size {
+    let BoxClamp { min, max } = clamp;
+    if min == max {
+      return min;
+    }
+
+    let ratio = self.ratio;
+    let mut size = if max.width.is_finite() {
+      Size::new(max.width, max.width / ratio)
+    } else if max.height.is_finite() {
+      Size::new(max.height * ratio, max.height)
+    } else {
+      let width = unlimited_width().max(min.width);
+      Size::new(width, width / ratio)
+    };
+
+    if size.width > max.width {
+      size = Size::new(max.width, max.width / ratio);
+    }
+    if size.height > max.height {
+      size = Size::new(max.height * ratio, max.height);
+    }
+    if size.width < min.width {
+      size = Size::new(min.width, min.width / ratio);
+    }
+    if size.height < min.height {
+      size = Size::new(min.height * ratio, min.height);
+    }
+    clamp.clamp(size)
+  }
+}
+
+impl Render for AspectRatio {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let child = ctx.single_child();
+    let size =
+      self.size_in(clamp, || child.map_or(0., |c| ctx.min_intrinsic_width_of(c, f32::INFINITY)));
+    if let Some(child) = child {
+      ctx.perform_child_layout(child, BoxClamp::fixed_size(size));
+    }
+    size
+  }
+
+  fn min_intrinsic_width(&self, height: f32, _: &mut LayoutCtx) -> f32 {
+    if height.is_finite() { height * self.ratio } else { 0. }
+  }
+
+  fn min_intrinsic_height(&self, width: f32, _: &mut LayoutCtx) -> f32 {
+    if width.is_finite() { width / self.ratio } else { 0. }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::test_helper::*;
+  use ribir_dev_helper::*;
+
+  use super::*;
+
+  widget_layout_test! {
+    fill_width,
+    WidgetTester::new(fn_widget! {
+      @AspectRatio { ratio: 2., @ { Void } }
+    })
+    .with_wnd_size(Size::new(100., 100.)),
+    LayoutCase::default().with_size(Size::new(100., 50.)),
+    LayoutCase::new(&[0, 0]).with_size(Size::new(100., 50.))
+  }
+
+  widget_layout_test! {
+    shrink_to_height,
+    WidgetTester::new(fn_widget! {
+      @AspectRatio { ratio: 0.5 }
+    })
+    .with_wnd_size(Size::new(100., 100.)),
+    LayoutCase::default().with_size(Size::new(50., 100.))
+  }
+
+  #[test]
+  fn unlimited_width() {
+    let ratio = AspectRatio { ratio: 2. };
+    assert_eq!(ratio.size_in(BoxClamp::fixed_height(40.), || 0.), Size::new(80., 40.));
+    assert_eq!(ratio.size_in(BoxClamp::UNLIMITED, || 30.), Size::new(30., 15.));
+  }
+}
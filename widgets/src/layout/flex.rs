@@ -45,7 +45,9 @@ pub enum JustifyContent {
 ///
 /// The `align_items` property specifies how flex items are positioned in the
 /// flex container along the cross axis, while `justify_content` determines
-/// their placement along the main axis.
+/// their placement along the main axis. A horizontal flex container can align
+/// the items of every line by their first text baselines with
+/// [`Align::Baseline`].
 ///
 /// Adjust the `item_gap` property to set the gap between items in the main
 /// axis, and the `line_gap` property for the gap between lines in the cross
@@ -137,10 +139,79 @@ impl Render for Flex {
     layouter.layout(clamp, ctx)
   }
 
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut LayoutCtx) -> f32 {
+    match self.direction {
+      Direction::Horizontal => self.min_intrinsic_main(height, ctx),
+      Direction::Vertical => self.min_intrinsic_cross(height, ctx),
+    }
+  }
+
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut LayoutCtx) -> f32 {
+    match self.direction {
+      Direction::Horizontal => self.min_intrinsic_cross(width, ctx),
+      Direction::Vertical => self.min_intrinsic_main(width, ctx),
+    }
+  }
+
   #[inline]
   fn paint(&self, _: &mut PaintingCtx) {}
 }
 
+impl Flex {
+  /// The smallest main size in the `cross` size, the items are in a line
+  /// unless the flex wraps.
+  fn min_intrinsic_main(&self, cross: f32, ctx: &mut LayoutCtx) -> f32 {
+    let dir = self.direction;
+    let (ctx, children) = ctx.split_children();
+    let mains = children.map(|c| match dir {
+      Direction::Horizontal => ctx.min_intrinsic_width_of(c, cross),
+      Direction::Vertical => ctx.min_intrinsic_height_of(c, cross),
+    });
+    if self.wrap {
+      mains.fold(0., f32::max)
+    } else {
+      mains
+        .fold(-self.item_gap, |sum, m| sum + m + self.item_gap)
+        .max(0.)
+    }
+  }
+
+  /// The smallest cross size in the `main` size. Like the layout, the items
+  /// are measured without limiting their main size, and wrapped into lines if
+  /// the flex wraps.
+  fn min_intrinsic_cross(&self, main: f32, ctx: &mut LayoutCtx) -> f32 {
+    let dir = self.direction;
+    let (ctx, children) = ctx.split_children();
+    let items = children.map(|c| {
+      let width = ctx.min_intrinsic_width_of(c, f32::INFINITY);
+      let height = ctx.min_intrinsic_height_of(c, f32::INFINITY);
+      match dir {
+        Direction::Horizontal => (width, height),
+        Direction::Vertical => (height, width),
+      }
+    });
+    if !self.wrap {
+      return items.fold(0., |max, (_, cross)| max.max(cross));
+    }
+
+    let (mut line_main, mut line_cross, mut cross_sum) = (None::<f32>, 0f32, 0.);
+    for (item_main, item_cross) in items {
+      match line_main {
+        Some(m) if m + self.item_gap + item_main <= main => {
+          line_main = Some(m + self.item_gap + item_main);
+          line_cross = line_cross.max(item_cross);
+        }
+        Some(_) => {
+          cross_sum += line_cross + self.line_gap;
+          (line_main, line_cross) = (Some(item_main), item_cross);
+        }
+        None => (line_main, line_cross) = (Some(item_main), item_cross),
+      }
+    }
+    cross_sum + line_cross
+  }
+}
+
 #[derive(Debug, Clone, Copy, Default)]
 struct FlexSize {
   main: f32,
@@ -210,6 +281,10 @@ impl FlexLayouter {
       self.flex_children_layout(flex_main, child_clamp, ctx);
     }
 
+    if self.align_items == Align::Baseline && dir == Direction::Horizontal {
+      self.align_baselines(ctx);
+    }
+
     // cross direction need calculate cross_axis_gap but last line don't need.
     let cross = self
       .lines
@@ -265,7 +340,7 @@ impl FlexLayouter {
           self.current_line.has_flex = true;
           self.has_flex = true;
         });
-      let info = FlexLayoutInfo { flex, pos: <_>::default(), size };
+      let info = FlexLayoutInfo { flex, pos: <_>::default(), size, baseline: 0. };
       self.current_line.items_info.push(info);
     }
 
@@ -296,6 +371,28 @@ impl FlexLayouter {
     });
   }
 
+  /// Align the items of every line by their first baselines, and grow the
+  /// lines to contain the aligned items.
+  fn align_baselines(&mut self, ctx: &mut LayoutCtx) {
+    let (ctx, mut children) = ctx.split_children();
+
+    self.lines.iter_mut().for_each(|line| {
+      line.items_info.iter_mut().for_each(|info| {
+        let child = children.next().unwrap();
+        info.baseline = ctx.baseline_of(child).unwrap_or(info.size.cross);
+      });
+      let baseline = line
+        .items_info
+        .iter()
+        .fold(0f32, |max, info| max.max(info.baseline));
+      line.baseline = Some(baseline);
+      line.cross_line_height = line
+        .items_info
+        .iter()
+        .fold(0f32, |max, info| max.max(baseline - info.baseline + info.size.cross));
+    });
+  }
+
   fn update_children_position(&mut self, bound: FlexSize, ctx: &mut LayoutCtx) {
     let Self { reverse, dir, align_items, justify_content, lines, .. } = self;
 
@@ -311,8 +408,10 @@ impl FlexLayouter {
         lines.iter_mut()$(.$rev())?.for_each(|line| {
           let (mut main, step) = line.place_args(bound.main, *justify_content, self.main_axis_gap);
           line.items_info.iter_mut()$(.$rev())?.for_each(|item| {
-            let item_cross_offset =
-              align_items.align_value(item.size.cross, line.cross_line_height);
+            let item_cross_offset = match line.baseline {
+              Some(baseline) => baseline - item.baseline,
+              None => align_items.align_value(item.size.cross, line.cross_line_height),
+            };
 
             item.pos.cross = cross + item_cross_offset + self.cross_axis_gap;
             item.pos.main = main;
@@ -353,12 +452,16 @@ struct MainLineInfo {
   items_info: Vec<FlexLayoutInfo>,
   cross_line_height: f32,
   has_flex: bool,
+  /// The baseline of the line if its items are aligned by baselines.
+  baseline: Option<f32>,
 }
 
 struct FlexLayoutInfo {
   pos: FlexSize,
   size: FlexSize,
   flex: Option<f32>,
+  /// The first baseline of the item, only used to align by baselines.
+  baseline: f32,
 }
 
 impl MainLineInfo {
@@ -435,7 +538,7 @@ impl JustifyContent {
 
 #[cfg(test)]
 mod tests {
-  use ribir_core::test_helper::*;
+  use ribir_core::{reset_test_env, test_helper::*};
   use ribir_dev_helper::*;
 
   use super::*;
@@ -745,4 +848,67 @@ mod tests {
       .with_y(150.)
       .with_height(500.)
   );
+
+  #[test]
+  fn baseline_align() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! {
+      @Row {
+        align_items: Align::Baseline,
+        @Text { text: "A", padding: EdgeInsets::only_top(10.) }
+        @Text { text: "A" }
+        @SizedBox { size: Size::new(10., 100.) }
+      }
+    });
+    wnd.draw_frame();
+
+    let rect = |path: &[usize]| {
+      let info = wnd.layout_info_by_path(path).unwrap();
+      Rect::new(info.pos, info.size.unwrap())
+    };
+    let (padded, text, bx) = (rect(&[0, 0]), rect(&[0, 1]), rect(&[0, 2]));
+    // The box without baseline is aligned by its bottom edge, so the texts are
+    // moved down to it.
+    assert_eq!(bx.min_y(), 0.);
+    assert!(text.min_y() > 0.);
+    // The texts are aligned by their baselines, the padding is above the
+    // baseline.
+    assert_eq!(text.min_y() - padded.min_y(), 10.);
+    // The line grows to contain the descent of the texts.
+    assert_eq!(rect(&[0]).height(), text.max_y());
+    assert!(text.max_y() > bx.max_y());
+  }
+
+  #[test]
+  fn min_intrinsic_size() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! {
+      @Column {
+        @IntrinsicWidth {
+          @Row {
+            item_gap: 5.,
+            @SizedBox { size: Size::new(10., 10.) }
+            @SizedBox { size: Size::new(20., 30.) }
+          }
+        }
+        @IntrinsicWidth {
+          @Column {
+            item_gap: 5.,
+            @SizedBox { size: Size::new(10., 10.) }
+            @SizedBox { size: Size::new(20., 30.) }
+          }
+        }
+      }
+    });
+    wnd.draw_frame();
+
+    // The items of a row are side by side with the gap.
+    let row = wnd.layout_info_by_path(&[0, 0]).unwrap();
+    assert_eq!(row.size.unwrap(), Size::new(35., 30.));
+    // A column is as wide as the widest item.
+    let column = wnd.layout_info_by_path(&[0, 1]).unwrap();
+    assert_eq!(column.size.unwrap(), Size::new(20., 45.));
+  }
 }
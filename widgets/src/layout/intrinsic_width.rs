@@ -0,0 +1,103 @@
+use ribir_core::prelude::*;
+
+/// A widget sizes its child to the child's min intrinsic width, so the child
+/// is as narrow as its content allows rather than as wide as the parent allows.
+///
+/// It's useful to make a group of children as wide as the widest one, for
+/// example, wrap a `Column` with `align_items: Align::Stretch` in it.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @IntrinsicWidth {
+///     @Column {
+///       align_items: Align::Stretch,
+///       @FilledButton { @{ "Ok" } }
+///       @FilledButton { @{ "Cancel" } }
+///     }
+///   }
+/// };
+/// ```
+#[derive(SingleChild, Declare)]
+pub struct IntrinsicWidth;
+
+impl Render for IntrinsicWidth {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let Some(child) = ctx.single_child() else { return clamp.min };
+    let width = ctx
+      .min_intrinsic_width_of(child, clamp.max.height)
+      .clamp(clamp.min.width, clamp.max.width);
+    ctx.perform_child_layout(child, clamp.with_fixed_width(width))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+  use crate::prelude::*;
+
+  #[test]
+  fn stretch_to_widest_child() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @IntrinsicWidth {
+          @Column {
+            align_items: Align::Stretch,
+            @SizedBox { size: Size::new(30., 10.) }
+            @Container {
+              size: Size::new(50., 10.),
+              @SizedBox { size: Size::new(10., 10.), padding: EdgeInsets::all(5.) }
+            }
+            @SizedBox { size: Size::new(10., 10.), padding: EdgeInsets::horizontal(30.) }
+          }
+        }
+      },
+      Size::new(200., 200.),
+    );
+    wnd.draw_frame();
+
+    wnd.assert_root_size(Size::new(70., 30.));
+    for i in 0..3 {
+      let child = wnd.layout_info_by_path(&[0, 0, i]).unwrap();
+      assert_eq!(child.size.unwrap().width, 70.);
+    }
+  }
+
+  #[test]
+  fn wrap_text_at_widest_word() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @Column {
+          text_style: TextStyle { overflow: TextOverflow::AutoWrap, ..<_>::default() },
+          @IntrinsicWidth { @Text { text: "ab abcd" } }
+          @Text { text: "abcd" }
+        }
+      },
+      Size::new(200., 200.),
+    );
+    wnd.draw_frame();
+
+    let wrapped = wnd
+      .layout_info_by_path(&[0, 0])
+      .unwrap()
+      .size
+      .unwrap();
+    let word = wnd
+      .layout_info_by_path(&[0, 1])
+      .unwrap()
+      .size
+      .unwrap();
+    assert_eq!(wrapped.width, word.width);
+    // Wrapped into two lines.
+    assert!((wrapped.height - word.height * 2.).abs() < 0.01);
+  }
+}
@@ -0,0 +1,93 @@
+use ribir_core::prelude::*;
+
+/// A widget that stops the layout changes of its subtree from propagating to
+/// its ancestors.
+///
+/// It fills the max size of the clamp its parent passed, or the min size in
+/// the unbounded directions, so its size never depends on its child. When the
+/// subtree needs to relayout, only the subtree of the `RelayoutBoundary` is
+/// laid out again.
+///
+/// Unlike [`OnlySizedByParent`](super::OnlySizedByParent), which keeps the
+/// size of its child, the child is laid out with a loose clamp and placed at
+/// the top-left of the boundary.
+#[derive(SingleChild, Declare)]
+pub struct RelayoutBoundary {}
+
+impl Render for RelayoutBoundary {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let width = if clamp.max.width.is_finite() { clamp.max.width } else { clamp.min.width };
+    let height = if clamp.max.height.is_finite() { clamp.max.height } else { clamp.min.height };
+    let size = Size::new(width, height);
+    ctx.perform_single_child_layout(BoxClamp { min: ZERO_SIZE, max: size });
+
+    size
+  }
+
+  #[inline]
+  fn only_sized_by_parent(&self) -> bool { true }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{prelude::*, reset_test_env, test_helper::*};
+
+  use super::*;
+  use crate::layout::{SizedBox, SizedBoxDeclareExtend};
+
+  #[test]
+  fn stop_relayout_at_boundary() {
+    reset_test_env!();
+    let (size, w_size) = split_value(Size::new(10., 10.));
+    let w = fn_widget! {
+      @MockMulti {
+        @MockBox { size: Size::new(10., 10.) }
+        @MockBox {
+          size: Size::new(50., 50.),
+          @RelayoutBoundary {
+            @MockStack {
+              @MockBox { size: pipe!(*$size) }
+            }
+          }
+        }
+      }
+    };
+
+    let mut wnd = TestWindow::new_with_size(w, Size::new(200., 200.));
+    wnd.draw_frame();
+    wnd.assert_root_size(Size::new(60., 50.));
+
+    let count = wnd.performed_layout_count();
+    *w_size.write() = Size::new(20., 20.);
+    wnd.draw_frame();
+    // Only the boundary and its descendants are laid out again.
+    assert_eq!(wnd.performed_layout_count() - count, 3);
+    wnd.assert_root_size(Size::new(60., 50.));
+  }
+
+  #[test]
+  fn tight_clamp_as_boundary() {
+    reset_test_env!();
+    let (size, w_size) = split_value(Size::new(10., 10.));
+    let w = fn_widget! {
+      @MockMulti {
+        @MockBox { size: Size::new(10., 10.) }
+        @SizedBox {
+          size: Size::new(50., 50.),
+          @MockStack {
+            @MockBox { size: pipe!(*$size) }
+          }
+        }
+      }
+    };
+
+    let mut wnd = TestWindow::new_with_size(w, Size::new(200., 200.));
+    wnd.draw_frame();
+
+    let count = wnd.performed_layout_count();
+    *w_size.write() = Size::new(20., 20.);
+    wnd.draw_frame();
+    // The stack was laid out with a tight clamp, so its parent needn't relayout.
+    assert_eq!(wnd.performed_layout_count() - count, 2);
+  }
+}
@@ -0,0 +1,73 @@
+use ribir_core::prelude::*;
+
+/// A widget that insets its child by the safe area of the window, so the child
+/// is not obscured by the system bars, the display cutouts or the on-screen
+/// keyboard.
+///
+/// The insets come from [`Window::viewport_insets`], they are zero on the
+/// desktop. The `Scaffold` already keeps its content out of them by its
+/// `resize_policy`, so it needn't be wrapped in a `SafeArea`.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @SafeArea {
+///     @Text { text: "Never under the status bar" }
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct SafeArea {}
+
+impl<'c> ComposeChild<'c> for SafeArea {
+  type Child = Widget<'c>;
+  fn compose_child(_: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    fn_widget! {
+      let insets = BuildCtx::get().window().viewport_insets();
+      let child = FatObj::new(child);
+      @ $child { padding: pipe!(*$insets) }
+    }
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+
+  #[test]
+  fn inset_by_safe_area() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @SafeArea { @MockMulti { @MockBox { size: INFINITY_SIZE } } }
+      },
+      Size::new(100., 200.),
+    );
+    wnd.draw_frame();
+    let child = wnd.widget_id_by_path(&[0, 0]);
+    assert_eq!(wnd.map_to_global(Point::zero(), child), Point::zero());
+    assert_eq!(wnd.widget_size(child), Some(Size::new(100., 200.)));
+
+    wnd
+      .shell_wnd()
+      .borrow_mut()
+      .as_any_mut()
+      .downcast_mut::<TestShellWindow>()
+      .unwrap()
+      .viewport_insets = EdgeInsets::new(20., 0., 10., 0.);
+    wnd.processes_viewport_insets_changed();
+    wnd.draw_frame();
+
+    wnd.assert_root_size(Size::new(100., 200.));
+    let child = wnd.widget_id_by_path(&[0, 0]);
+    assert_eq!(wnd.map_to_global(Point::zero(), child), Point::new(0., 20.));
+    assert_eq!(wnd.widget_size(child), Some(Size::new(100., 170.)));
+  }
+}
@@ -1,7 +1,12 @@
+pub mod animated_image;
+pub mod async_image;
 pub mod avatar;
 pub mod buttons;
+#[cfg(feature = "charts")]
+pub mod charts;
 pub mod checkbox;
 pub mod common_widget;
+pub mod dialog;
 pub mod divider;
 pub mod grid_view;
 pub mod icon;
@@ -10,20 +15,33 @@ pub mod label;
 pub mod layout;
 pub mod link;
 pub mod lists;
+pub mod menu;
+pub mod navigator;
 pub mod path;
 pub mod progress;
 pub mod radio;
+pub mod scaffold;
 pub mod scrollbar;
+pub mod select;
 pub mod select_region;
 pub mod slider;
+pub mod snackbar;
+pub mod split;
+pub mod switch;
+pub mod table;
 pub mod tabs;
 pub mod text_field;
 
 pub mod transform_box;
+pub mod video_surface;
 pub mod prelude {
+  #[cfg(feature = "charts")]
+  pub use super::charts::*;
   pub use super::{
-    avatar::*, buttons::*, checkbox::*, common_widget::*, divider::*, grid_view::*, icon::*,
-    input::*, label::*, layout::*, link::*, lists::*, path::*, progress::*, radio::*, scrollbar::*,
-    select_region::*, slider::*, tabs::*, text_field::*, transform_box::*,
+    animated_image::*, async_image::*, avatar::*, buttons::*, checkbox::*, common_widget::*,
+    dialog::*, divider::*, grid_view::*, icon::*, input::*, label::*, layout::*, link::*, lists::*,
+    menu::*, navigator::*, path::*, progress::*, radio::*, scaffold::*, scrollbar::*, select::*,
+    select_region::*, slider::*, snackbar::*, split::*, switch::*, table::*, tabs::*,
+    text_field::*, transform_box::*, video_surface::*,
   };
 }
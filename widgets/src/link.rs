@@ -15,6 +15,7 @@ impl<'c> ComposeChild<'c> for UrlLink {
   type Child = Widget<'c>;
   fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
     FatObj::new(child)
+      .cursor(CursorIcon::Pointer)
       .on_tap(move |_| {
         let this = this.read();
         if open(this.browser, &this.url).is_err() {
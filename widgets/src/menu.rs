@@ -0,0 +1,830 @@
+use std::{
+  cell::{Cell, RefCell},
+  rc::Rc,
+};
+
+use ribir_core::prelude::*;
+use smallvec::smallvec;
+
+use crate::prelude::*;
+
+class_names! {
+  #[doc = "Class name for the menu"]
+  MENU,
+  #[doc = "Class name for the item of the menu"]
+  MENU_ITEM,
+  #[doc = "Class name for the check mark of the checkable menu item"]
+  MENU_ITEM_CHECK,
+  #[doc = "Class name for the separator of the menu"]
+  MENU_SEPARATOR,
+  #[doc = "Class name for the menu bar"]
+  MENU_BAR,
+  #[doc = "Class name for the title of a menu in the menu bar"]
+  MENU_BAR_ITEM,
+}
+
+/// Shows the `menu` at the pointer when the context menu is requested on its
+/// child, by pressing the right mouse button or the long press of a finger.
+///
+/// The menu closes when tapping outside it, pressing `Escape`, or tapping an
+/// item without a submenu.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @ContextMenu {
+///     menu: GenWidget::new(|| fn_widget! {
+///       @Menu {
+///         @MenuItem {
+///           on_tap: |_| println!("copy"),
+///           @Text { text: "Copy" }
+///         }
+///         @MenuItem {
+///           submenu: GenWidget::new(|| fn_widget! {
+///             @Menu {
+///               @MenuItem { @Text { text: "Plain text" } }
+///               @MenuItem { @Text { text: "Rich text" } }
+///             }
+///           }.into_widget()),
+///           @Text { text: "Paste as" }
+///         }
+///       }
+///     }.into_widget()),
+///     @Text { text: "Right click or long press me!" }
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct ContextMenu {
+  /// The generator of the menu, it's built every time the menu shows.
+  pub menu: GenWidget,
+}
+
+/// The list of the menu items, it's focused when it shows to receive the keys.
+///
+/// The `ArrowDown` and `ArrowUp` keys move the focus through the items,
+/// `ArrowRight` shows the submenu of the focused item and `ArrowLeft` closes
+/// the submenu. In a menu of the [`MenuBar`], they switch to the adjacent menu
+/// of the bar instead if there is no submenu to show or close. `Escape` closes
+/// all the menus.
+#[derive(Declare)]
+pub struct Menu;
+
+/// An item of the menu. Selecting it by tapping or pressing `Enter` shows its
+/// submenu beside it if it has one, otherwise bubbles a [`MenuSelectedEvent`]
+/// and closes all the menus.
+#[derive(Declare)]
+pub struct MenuItem {
+  #[declare(default)]
+  pub submenu: Option<GenWidget>,
+  /// Whether the item is checked, or `None` if it's not checkable. Selecting a
+  /// checkable item toggles it, bind it to a state to keep the change.
+  #[declare(default)]
+  pub checked: Option<bool>,
+}
+
+/// A line separates the groups of the items in the menu.
+#[derive(Declare)]
+pub struct MenuSeparator;
+
+/// The menu item selected by the user, see [`MenuSelectedEvent`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MenuSelected {
+  /// The checked state of the item after it's toggled, `None` if the item is
+  /// not checkable.
+  pub checked: Option<bool>,
+}
+
+/// The event bubbled from the [`MenuItem`] when the user selects it.
+pub type MenuSelectedEvent = CustomEvent<MenuSelected>;
+
+/// The data of a menu entry, to build the menus from data by
+/// [`MenuModel::menu`] or the [`MenuBar`].
+///
+/// A `&` in the label marks the next character as the mnemonic, `&&` is a
+/// literal `&`.
+#[derive(Clone)]
+#[allow(clippy::type_complexity)]
+pub enum MenuModel {
+  Item {
+    label: CowArc<str>,
+    /// Whether the item is checked, or `None` if it's not checkable.
+    checked: Option<bool>,
+    /// Called when the item without a submenu is selected.
+    on_select: Option<Rc<dyn Fn(&MenuSelected)>>,
+    /// The entries of the submenu, empty if the item has no submenu.
+    submenu: Vec<MenuModel>,
+  },
+  Separator,
+}
+
+/// A bar of the menus above its child, the menus are built from the
+/// [`MenuModel`]s.
+///
+/// Tapping a title in the bar shows its menu below it, so does pressing `Alt`
+/// with the mnemonic of the title while the focus is in the child. An entry
+/// without a submenu calls its `on_select` directly.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @MenuBar {
+///     menus: vec![
+///       MenuModel::submenu("&File", vec![
+///         MenuModel::item("&Open", |_| println!("open")),
+///         MenuModel::Separator,
+///         MenuModel::check_item("&Autosave", true, |e| println!("autosave {:?}", e.checked)),
+///       ]),
+///       MenuModel::submenu("&Edit", vec![MenuModel::item("&Undo", |_| println!("undo"))]),
+///     ],
+///     @Text { text: "Press Alt+F to open the file menu." }
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct MenuBar {
+  /// The entries of the bar, usually the items with submenus.
+  pub menus: Vec<MenuModel>,
+}
+
+/// The menus showing with the items they show from, the first is the root
+/// menu and the others are the submenus in order.
+#[derive(Clone, Default)]
+struct MenuChain(Rc<RefCell<Vec<ShowingMenu>>>);
+
+/// A showing menu and the opener of it.
+type ShowingMenu = (Overlay, Option<Opener>);
+
+/// Focus the widget that shows a menu.
+type Opener = Rc<dyn Fn()>;
+
+/// The position of a menu in the `MenuChain`, it's provided to the content of
+/// the menu.
+#[derive(Clone)]
+struct MenuLevel {
+  chain: MenuChain,
+  depth: usize,
+  /// The menu bar the root menu shows from.
+  bar: Option<MenuBarCtrl>,
+  /// Focus the widget that shows the menu, when the menu is closed by the
+  /// keyboard or by selecting an item.
+  opener: Option<Opener>,
+}
+
+/// The menus of the [`MenuBar`] and the titles they show from.
+#[derive(Clone, Default)]
+struct MenuBarCtrl(Rc<MenuBarInner>);
+
+#[derive(Default)]
+struct MenuBarInner {
+  chain: MenuChain,
+  menus: RefCell<Vec<BarEntry>>,
+  /// The index of the menu that showed last.
+  active: Cell<Option<usize>>,
+}
+
+/// A menu of the [`MenuBar`] and the title it shows from.
+#[derive(Clone)]
+struct BarEntry {
+  model: MenuModel,
+  title: Option<TrackId>,
+  focus: Option<Opener>,
+}
+
+impl MenuChain {
+  /// Close the menus from the `depth`, and their submenus.
+  fn close_from(&self, depth: usize) {
+    let mut menus = self.0.borrow_mut();
+    let depth = depth.min(menus.len());
+    let closing = menus.split_off(depth);
+    drop(menus);
+    closing
+      .iter()
+      .rev()
+      .for_each(|(overlay, _)| overlay.close());
+  }
+
+  /// Close all the menus, and focus back the widget the root menu shows from.
+  fn dismiss(&self) {
+    let opener = self
+      .0
+      .borrow()
+      .first()
+      .and_then(|(_, opener)| opener.clone());
+    self.close_from(0);
+    if let Some(opener) = opener {
+      opener();
+    }
+  }
+}
+
+impl MenuLevel {
+  fn root(chain: MenuChain, bar: Option<MenuBarCtrl>, opener: Option<Opener>) -> Self {
+    Self { chain, depth: 0, bar, opener }
+  }
+
+  /// The level of the submenu shown by the `opener` item in this menu.
+  fn submenu(&self, opener: Opener) -> Self {
+    Self {
+      chain: self.chain.clone(),
+      depth: self.depth + 1,
+      bar: self.bar.clone(),
+      opener: Some(opener),
+    }
+  }
+
+  /// Show the `menu` at this level, the menus from this level are closed
+  /// first. The root menu closes when tapping outside it.
+  fn show(
+    self, menu: GenWidget, place: impl FnMut(Widget<'static>) -> Widget<'static> + 'static,
+    wnd: Sc<Window>,
+  ) {
+    let (depth, opener) = (self.depth, self.opener.clone());
+    let chain = self.chain.clone();
+    chain.close_from(depth);
+    let auto_close_policy =
+      if depth == 0 { AutoClosePolicy::TAP_OUTSIDE } else { AutoClosePolicy::NOT_AUTO_CLOSE };
+    let overlay = Overlay::new(
+      move || {
+        let level = self.clone();
+        let chain = level.chain.clone();
+        let menu = FatObj::new(menu.gen_widget());
+        fn_widget! {
+          @Providers {
+            providers: smallvec![Provider::new(level)],
+            @ $menu {
+              // The submenus close with their parent.
+              on_disposed: move |_| chain.close_from(depth + 1),
+            }
+          }
+        }
+        .into_widget()
+      },
+      OverlayStyle { auto_close_policy, mask: None },
+    );
+    overlay.show_map(place, wnd);
+    chain.0.borrow_mut().push((overlay, opener));
+  }
+
+  /// Move the focus or switch the menus by the arrow keys, and close all the
+  /// menus by `Escape`.
+  fn navigate(&self, e: &mut KeyboardEvent) {
+    let wnd = e.window();
+    match e.key() {
+      VirtualKey::Named(NamedKey::Escape) => self.chain.dismiss(),
+      VirtualKey::Named(NamedKey::ArrowDown) => wnd.request_next_focus(),
+      VirtualKey::Named(NamedKey::ArrowUp) => wnd.request_prev_focus(),
+      VirtualKey::Named(NamedKey::ArrowLeft) if self.depth > 0 => {
+        self.chain.close_from(self.depth);
+        if let Some(opener) = &self.opener {
+          opener();
+        }
+      }
+      VirtualKey::Named(NamedKey::ArrowLeft) => match &self.bar {
+        Some(bar) => bar.step(-1, wnd),
+        None => return,
+      },
+      VirtualKey::Named(NamedKey::ArrowRight) => match &self.bar {
+        Some(bar) => bar.step(1, wnd),
+        None => return,
+      },
+      _ => return,
+    }
+    e.stop_propagation();
+  }
+}
+
+impl MenuModel {
+  /// An item calls `on_select` when it's selected.
+  pub fn item(label: impl Into<CowArc<str>>, on_select: impl Fn(&MenuSelected) + 'static) -> Self {
+    MenuModel::Item {
+      label: label.into(),
+      checked: None,
+      on_select: Some(Rc::new(on_select)),
+      submenu: vec![],
+    }
+  }
+
+  /// A checkable item, it calls `on_select` with the toggled state when it's
+  /// selected.
+  pub fn check_item(
+    label: impl Into<CowArc<str>>, checked: bool, on_select: impl Fn(&MenuSelected) + 'static,
+  ) -> Self {
+    MenuModel::Item {
+      label: label.into(),
+      checked: Some(checked),
+      on_select: Some(Rc::new(on_select)),
+      submenu: vec![],
+    }
+  }
+
+  /// An item shows the `items` as its submenu.
+  pub fn submenu(label: impl Into<CowArc<str>>, items: Vec<MenuModel>) -> Self {
+    MenuModel::Item { label: label.into(), checked: None, on_select: None, submenu: items }
+  }
+
+  /// The generator of the menu of the `items`, for the [`ContextMenu`] or the
+  /// submenu of a [`MenuItem`].
+  pub fn menu(items: Vec<MenuModel>) -> GenWidget {
+    let items: Rc<[MenuModel]> = items.into();
+    GenWidget::new(move || menu_of(&items))
+  }
+
+  fn has_submenu(&self) -> bool {
+    matches!(self, MenuModel::Item { submenu, .. } if !submenu.is_empty())
+  }
+
+  fn mnemonic(&self) -> Option<char> {
+    match self {
+      MenuModel::Item { label, .. } => split_mnemonic(label).1,
+      MenuModel::Separator => None,
+    }
+  }
+}
+
+fn menu_of(items: &[MenuModel]) -> Widget<'static> {
+  let items = items
+    .iter()
+    .map(|model| match model {
+      MenuModel::Separator => fn_widget! { @MenuSeparator {} }.into_widget(),
+      MenuModel::Item { label, checked, on_select, submenu } => {
+        let (text, _) = split_mnemonic(label);
+        let submenu = (!submenu.is_empty()).then(|| MenuModel::menu(submenu.clone()));
+        let (checked, on_select) = (*checked, on_select.clone());
+        fn_widget! {
+          @MenuItem {
+            checked,
+            submenu,
+            on_custom_concrete_event: move |e: &mut MenuSelectedEvent| {
+              if let Some(on_select) = &on_select {
+                on_select(e.data());
+              }
+            },
+            @Text { text }
+          }
+        }
+        .into_widget()
+      }
+    })
+    .collect::<Vec<_>>();
+  fn_widget! { @Menu { @ { items } } }.into_widget()
+}
+
+/// Strip the `&` marks from the `label`, and return the character after the
+/// first mark in lowercase as the mnemonic.
+fn split_mnemonic(label: &str) -> (String, Option<char>) {
+  let mut text = String::with_capacity(label.len());
+  let mut mnemonic = None;
+  let mut chars = label.chars();
+  while let Some(c) = chars.next() {
+    if c != '&' {
+      text.push(c);
+      continue;
+    }
+    match chars.next() {
+      Some('&') => text.push('&'),
+      Some(c) => {
+        if mnemonic.is_none() {
+          mnemonic = c.to_lowercase().next();
+        }
+        text.push(c);
+      }
+      None => {}
+    }
+  }
+  (text, mnemonic)
+}
+
+impl MenuBarCtrl {
+  /// Show the menu at `idx` below its title, or select it if it has no
+  /// submenu.
+  fn open(&self, idx: usize, wnd: Sc<Window>) {
+    let Some(BarEntry { model, title, focus }) = self.0.menus.borrow().get(idx).cloned() else {
+      return;
+    };
+    let MenuModel::Item { checked, on_select, submenu, .. } = model else { return };
+    if submenu.is_empty() {
+      self.0.chain.close_from(0);
+      if let Some(on_select) = on_select {
+        on_select(&MenuSelected { checked: checked.map(|c| !c) });
+      }
+      return;
+    }
+
+    let Some(title) = title else { return };
+    let height = title
+      .get()
+      .and_then(|id| wnd.widget_size(id))
+      .map_or(0., |size| size.height);
+    let place = move |w: Widget<'static>| {
+      FatObj::new(w)
+        .global_anchor_x(GlobalAnchorX::left_align_to(title.clone(), 0.))
+        .global_anchor_y(GlobalAnchorY::top_align_to(title.clone(), height))
+        .into_widget()
+    };
+    self.0.active.set(Some(idx));
+    MenuLevel::root(self.0.chain.clone(), Some(self.clone()), focus).show(
+      MenuModel::menu(submenu),
+      place,
+      wnd,
+    );
+  }
+
+  /// Show the menu `delta` steps away from the active one, the entries
+  /// without a submenu are skipped.
+  fn step(&self, delta: isize, wnd: Sc<Window>) {
+    let next = {
+      let menus = self.0.menus.borrow();
+      let len = menus.len() as isize;
+      let cur = self.0.active.get().unwrap_or(0) as isize;
+      (1..=len)
+        .map(|i| (cur + delta * i).rem_euclid(len) as usize)
+        .find(|i| menus[*i].model.has_submenu())
+    };
+    if let Some(idx) = next {
+      self.open(idx, wnd);
+    }
+  }
+
+  /// Show the menu whose title has the mnemonic `c`, return if it's found.
+  fn open_by_mnemonic(&self, c: char, wnd: Sc<Window>) -> bool {
+    let Some(c) = c.to_lowercase().next() else { return false };
+    let idx = self
+      .0
+      .menus
+      .borrow()
+      .iter()
+      .position(|entry| entry.model.mnemonic() == Some(c));
+    if let Some(idx) = idx {
+      self.open(idx, wnd);
+    }
+    idx.is_some()
+  }
+}
+
+impl<'c> ComposeChild<'c> for ContextMenu {
+  type Child = Widget<'c>;
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    let chain = MenuChain::default();
+    let chain2 = chain.clone();
+    FatObj::new(child)
+      .on_context_menu(move |e| {
+        let pos = e.global_pos();
+        let place = move |w: Widget<'static>| {
+          FatObj::new(w)
+            .anchor(Anchor::from_point(pos))
+            .into_widget()
+        };
+        MenuLevel::root(chain.clone(), None, None).show(
+          this.read().menu.clone(),
+          place,
+          e.window(),
+        );
+      })
+      .on_disposed(move |_| chain2.close_from(0))
+      .into_widget()
+  }
+}
+
+impl<'c> ComposeChild<'c> for Menu {
+  type Child = Vec<Widget<'c>>;
+  fn compose_child(_: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    let level = Provider::of::<MenuLevel>(BuildCtx::get()).map(|l| l.clone());
+    fn_widget! {
+      // The menu itself is focused when it shows, the arrow keys move the
+      // focus into the items trapped in the scope.
+      @IntrinsicWidth {
+        class: MENU,
+        tab_index: -1_i16,
+        auto_focus: true,
+        on_key_down: move |e| {
+          if let Some(level) = &level {
+            level.navigate(e);
+          }
+        },
+        @FocusScope {
+          trap: true,
+          @Column {
+            align_items: Align::Stretch,
+            @ { child }
+          }
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+impl<'c> ComposeChild<'c> for MenuItem {
+  type Child = Widget<'c>;
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    let level = Provider::of::<MenuLevel>(BuildCtx::get()).map(|l| l.clone());
+    let level2 = level.clone();
+    let checkable = this.read().checked.is_some();
+    let (by_tap, by_key) = (this.clone_boxed_writer(), this.clone_boxed_writer());
+    fn_widget! {
+      let mut item = @FatObj { class: MENU_ITEM, tab_index: 0_i16 };
+      let focus = item.get_request_focus_widget().clone_watcher();
+      let opener: Opener = Rc::new(move || focus.read().request_focus());
+      let opener2 = opener.clone();
+      @ $item {
+        on_tap: move |e| {
+          let target = $item.track_id();
+          select_item(&*by_tap, level.as_ref(), target, opener.clone(), e);
+        },
+        on_key_down: move |e| {
+          let select = match e.key() {
+            VirtualKey::Named(NamedKey::Enter | NamedKey::Space) => true,
+            VirtualKey::Named(NamedKey::ArrowRight) => by_key.read().submenu.is_some(),
+            _ => false,
+          };
+          if select {
+            let target = $item.track_id();
+            select_item(&*by_key, level2.as_ref(), target, opener2.clone(), e);
+            e.stop_propagation();
+          }
+        },
+        @Row {
+          align_items: Align::Center,
+          @ {
+            checkable.then(|| @Icon {
+              class: MENU_ITEM_CHECK,
+              opacity: pipe!(if $this.checked == Some(true) { 1. } else { 0. }),
+              @ { svgs::DONE }
+            })
+          }
+          @ { child }
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+/// Show the submenu of the item beside it, or toggle the item and bubble the
+/// [`MenuSelectedEvent`] then close all the menus.
+fn select_item(
+  item: &dyn StateWriter<Value = MenuItem>, level: Option<&MenuLevel>, target: TrackId,
+  opener: Opener, e: &CommonEvent,
+) {
+  let wnd = e.window();
+  let submenu = item.read().submenu.clone();
+  if let Some(submenu) = submenu {
+    let Some(level) = level else { return };
+    let width = wnd
+      .widget_size(e.current_target())
+      .map_or(0., |size| size.width);
+    let place = move |w: Widget<'static>| {
+      FatObj::new(w)
+        .global_anchor_x(GlobalAnchorX::left_align_to(target.clone(), width))
+        .global_anchor_y(GlobalAnchorY::top_align_to(target.clone(), 0.))
+        .into_widget()
+    };
+    level.submenu(opener).show(submenu, place, wnd);
+  } else {
+    let checked = item.read().checked.map(|c| !c);
+    if checked.is_some() {
+      item.write().checked = checked;
+    }
+    wnd.bubble_custom_event(e.current_target(), MenuSelected { checked });
+    if let Some(level) = level {
+      level.chain.dismiss();
+    }
+  }
+}
+
+impl Compose for MenuSeparator {
+  fn compose(_: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! { @Void { class: MENU_SEPARATOR } }.into_widget()
+  }
+}
+
+impl<'c> ComposeChild<'c> for MenuBar {
+  type Child = Widget<'c>;
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    let ctrl = MenuBarCtrl::default();
+    let (by_key, on_disposed) = (ctrl.clone(), ctrl.clone());
+    fn_widget! {
+      @Column {
+        align_items: Align::Stretch,
+        on_key_down: move |e| {
+          if !e.with_alt_key() {
+            return;
+          }
+          let c = match e.key() {
+            VirtualKey::Character(c) => c.chars().next(),
+            _ => None,
+          };
+          if c.is_some_and(|c| by_key.open_by_mnemonic(c, e.window())) {
+            e.stop_propagation();
+          }
+        },
+        on_disposed: move |_| on_disposed.0.chain.close_from(0),
+        @Row {
+          class: MENU_BAR,
+          @ {
+            pipe!($this.menus.clone()).map(move |menus| {
+              let titles = menus
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, model)| menu_bar_title(&ctrl, idx, model))
+                .collect::<Vec<_>>();
+              *ctrl.0.menus.borrow_mut() = menus
+                .into_iter()
+                .map(|model| BarEntry { model, title: None, focus: None })
+                .collect();
+              titles
+            })
+          }
+        }
+        @Expanded { @ { child } }
+      }
+    }
+    .into_widget()
+  }
+}
+
+/// The title of the menu at `idx` in the menu bar, it's tracked by the `ctrl`
+/// to place the menu below it and to focus back when the menu is closed.
+fn menu_bar_title(ctrl: &MenuBarCtrl, idx: usize, model: &MenuModel) -> Option<Widget<'static>> {
+  let MenuModel::Item { label, .. } = model else { return None };
+  let (text, _) = split_mnemonic(label);
+  let (by_tap, by_key) = (ctrl.clone(), ctrl.clone());
+  let ctrl = ctrl.clone();
+  let title = fn_widget! {
+    let mut title = @Text { class: MENU_BAR_ITEM, tab_index: 0_i16, text };
+    let track_id = title.get_track_id_widget().read().track_id();
+    let focus = title.get_request_focus_widget().clone_watcher();
+    if let Some(entry) = ctrl.0.menus.borrow_mut().get_mut(idx) {
+      entry.title = Some(track_id);
+      entry.focus = Some(Rc::new(move || focus.read().request_focus()));
+    }
+    @ $title {
+      on_tap: move |e| by_tap.open(idx, e.window()),
+      on_key_down: move |e| {
+        let open = matches!(
+          e.key(),
+          VirtualKey::Named(NamedKey::Enter | NamedKey::Space | NamedKey::ArrowDown)
+        );
+        if open {
+          by_key.open(idx, e.window());
+          e.stop_propagation();
+        }
+      },
+    }
+  };
+  Some(title.into_widget())
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+  use winit::event::{DeviceId, ElementState, MouseButton, WindowEvent};
+
+  use super::*;
+
+  fn click(wnd: &mut TestWindow, x: f64, y: f64, button: MouseButton) {
+    let device_id = unsafe { DeviceId::dummy() };
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::CursorMoved { device_id, position: (x, y).into() });
+    wnd.process_mouse_input(device_id, ElementState::Pressed, button);
+    wnd.process_mouse_input(device_id, ElementState::Released, button);
+    wnd.draw_frame();
+  }
+
+  #[test]
+  fn context_menu_with_submenu() {
+    reset_test_env!();
+
+    let (log, w_log) = split_value(vec![]);
+    let menu = move |name: &'static str, submenu: Option<GenWidget>| {
+      let w_log = w_log.clone_writer();
+      GenWidget::new(move || {
+        let (w_log, w_log2) = (w_log.clone_writer(), w_log.clone_writer());
+        let submenu = submenu.clone();
+        fn_widget! {
+          @Menu {
+            on_mounted: move |_| $w_log.write().push(format!("show {name}")),
+            on_disposed: move |_| $w_log2.write().push(format!("close {name}")),
+            @MenuItem {
+              submenu: submenu.clone(),
+              @SizedBox { size: Size::new(50., 20.) }
+            }
+          }
+        }
+        .into_widget()
+      })
+    };
+    let root = menu("root", Some(menu("sub", None)));
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @ContextMenu {
+          menu: root.clone(),
+          @SizedBox { size: Size::new(100., 100.) }
+        }
+      },
+      Size::new(200., 200.),
+    );
+    wnd.draw_frame();
+
+    click(&mut wnd, 10., 10., MouseButton::Right);
+    // The submenu shows beside the item.
+    click(&mut wnd, 20., 15., MouseButton::Left);
+    // Tapping the item without submenu closes all.
+    click(&mut wnd, 70., 15., MouseButton::Left);
+    assert_eq!(*log.read(), ["show root", "show sub", "close sub", "close root"]);
+
+    // Tapping outside closes all.
+    click(&mut wnd, 10., 10., MouseButton::Right);
+    click(&mut wnd, 20., 15., MouseButton::Left);
+    click(&mut wnd, 150., 150., MouseButton::Left);
+    // The submenu closes with its parent in the next frame.
+    wnd.draw_frame();
+    assert_eq!(log.read().len(), 8);
+    assert_eq!(
+      log.read()[6..]
+        .iter()
+        .filter(|s| s.starts_with("close"))
+        .count(),
+      2
+    );
+  }
+
+  fn press(wnd: &mut TestWindow, key: VirtualKey, code: KeyCode) {
+    let physical_key = PhysicalKey::Code(code);
+    wnd.process_input(TestInput::KeyPress { key: key.clone(), physical_key });
+    wnd.process_input(TestInput::KeyRelease { key, physical_key });
+    wnd.draw_frame();
+  }
+
+  #[test]
+  fn menu_bar_by_keyboard() {
+    reset_test_env!();
+
+    let (log, w_log) = split_value(vec![]);
+    let (w_log1, w_log2, w_log3) =
+      (w_log.clone_writer(), w_log.clone_writer(), w_log.clone_writer());
+    let menus = vec![
+      MenuModel::submenu(
+        "&File",
+        vec![
+          MenuModel::item("&Open", move |_| w_log1.write().push("open".to_string())),
+          MenuModel::Separator,
+          MenuModel::check_item("&Autosave", false, move |e| {
+            w_log2
+              .write()
+              .push(format!("autosave {:?}", e.checked));
+          }),
+        ],
+      ),
+      MenuModel::submenu(
+        "&Edit",
+        vec![MenuModel::item("&Undo", move |_| {
+          w_log3.write().push("undo".to_string());
+        })],
+      ),
+    ];
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MenuBar {
+          menus: menus.clone(),
+          @MockBox { size: Size::new(100., 100.), tab_index: 0_i16, auto_focus: true }
+        }
+      },
+      Size::new(200., 200.),
+    );
+    wnd.draw_frame();
+
+    wnd.process_input(TestInput::Modifiers(ModifiersState::ALT));
+    press(&mut wnd, VirtualKey::Character("f".into()), KeyCode::KeyF);
+    wnd.process_input(TestInput::Modifiers(ModifiersState::empty()));
+    // The separator is skipped.
+    press(&mut wnd, VirtualKey::Named(NamedKey::ArrowDown), KeyCode::ArrowDown);
+    press(&mut wnd, VirtualKey::Named(NamedKey::ArrowDown), KeyCode::ArrowDown);
+    press(&mut wnd, VirtualKey::Named(NamedKey::Enter), KeyCode::Enter);
+
+    wnd.process_input(TestInput::Modifiers(ModifiersState::ALT));
+    press(&mut wnd, VirtualKey::Character("f".into()), KeyCode::KeyF);
+    wnd.process_input(TestInput::Modifiers(ModifiersState::empty()));
+    // Switch to the edit menu.
+    press(&mut wnd, VirtualKey::Named(NamedKey::ArrowRight), KeyCode::ArrowRight);
+    press(&mut wnd, VirtualKey::Named(NamedKey::ArrowDown), KeyCode::ArrowDown);
+    press(&mut wnd, VirtualKey::Named(NamedKey::Enter), KeyCode::Enter);
+
+    assert_eq!(*log.read(), ["autosave Some(true)", "undo"]);
+  }
+
+  #[test]
+  fn strip_mnemonic() {
+    assert_eq!(split_mnemonic("&File"), ("File".to_string(), Some('f')));
+    assert_eq!(split_mnemonic("Save &As"), ("Save As".to_string(), Some('a')));
+    assert_eq!(split_mnemonic("Tom && &Jerry"), ("Tom & Jerry".to_string(), Some('j')));
+    assert_eq!(split_mnemonic("Plain"), ("Plain".to_string(), None));
+  }
+}
@@ -0,0 +1,400 @@
+use std::cell::RefCell;
+
+use ribir_core::prelude::*;
+
+use crate::prelude::*;
+
+/// A stack of pages, only the top page is visible and interactive.
+///
+/// The pages above the root page fade in when pushed and fade out when popped.
+/// The pages below the top retain their widget subtree and state according to
+/// the [`KeepAlivePolicy`], so going back to them doesn't rebuild them.
+///
+/// The navigator is provided to its pages, use [`Navigator::write_of`] to
+/// change it, or [`Navigator::writer_of`] to capture it in a callback. Pressing
+/// the back key goes back by [`Navigator::back`].
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// fn detail() -> GenWidget {
+///   GenWidget::new(|| {
+///     fn_widget! {
+///       Navigator::of(BuildCtx::get()).on_back(|_| {
+///         println!("leave the detail page");
+///         false
+///       });
+///       @TextButton {
+///         on_tap: move |e| { Navigator::write_of(&e).pop(); },
+///         @{ "Back" }
+///       }
+///     }
+///     .into_widget()
+///   })
+/// }
+///
+/// let _w = fn_widget! {
+///   @Navigator {
+///     @ {
+///       GenWidget::new(|| fn_widget! {
+///         @FilledButton {
+///           on_tap: move |e| Navigator::write_of(&e).push(detail()),
+///           @{ "Show detail" }
+///         }
+///       }.into_widget())
+///     }
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct Navigator {
+  /// Decide whether the pages below the top keep their widget subtree and
+  /// state.
+  #[declare(default = KeepAlivePolicy::Always)]
+  pub keep_alive_policy: KeepAlivePolicy,
+  #[declare(skip)]
+  pages: Vec<PageEntry>,
+  #[declare(skip)]
+  next_key: usize,
+}
+
+type BackHandler = Box<dyn FnMut(&mut Navigator) -> bool>;
+
+struct PageEntry {
+  /// The unique key of the page in the navigator, the page is built again if
+  /// the page at its position is replaced.
+  key: usize,
+  page: GenWidget,
+  on_back: RefCell<Option<BackHandler>>,
+}
+
+impl Navigator {
+  /// Return the navigator of the pages in the context.
+  ///
+  /// # Panic
+  ///
+  /// Panic if the context is not in a navigator.
+  pub fn of(ctx: &impl AsRef<ProviderCtx>) -> QueryRef<'_, Self> { Provider::of(ctx).unwrap() }
+
+  /// Return the write reference of the navigator in the context.
+  ///
+  /// # Panic
+  ///
+  /// Panic if the context is not in a navigator.
+  pub fn write_of(ctx: &impl AsRef<ProviderCtx>) -> WriteRef<'_, Self> {
+    Provider::write_of(ctx).unwrap()
+  }
+
+  /// Return the writer of the navigator in the context, to change the
+  /// navigator later.
+  ///
+  /// # Panic
+  ///
+  /// Panic if the context is not in a navigator.
+  pub fn writer_of(ctx: &impl AsRef<ProviderCtx>) -> Box<dyn StateWriter<Value = Self>> {
+    Provider::state_of::<Box<dyn StateWriter<Value = Self>>>(ctx)
+      .unwrap()
+      .clone_boxed_writer()
+  }
+
+  /// Push the `page` to the top of the stack.
+  pub fn push(&mut self, page: GenWidget) {
+    let entry = self.new_entry(page);
+    self.pages.push(entry);
+  }
+
+  /// Pop the top page, the root page is never popped. Return if a page is
+  /// popped.
+  pub fn pop(&mut self) -> bool {
+    if self.pages.len() > 1 {
+      self.pages.pop();
+      true
+    } else {
+      false
+    }
+  }
+
+  /// Replace the top page with the `page`.
+  pub fn replace(&mut self, page: GenWidget) {
+    let entry = self.new_entry(page);
+    if let Some(top) = self.pages.last_mut() {
+      *top = entry;
+    } else {
+      self.pages.push(entry);
+    }
+  }
+
+  /// The count of the pages in the stack.
+  pub fn depth(&self) -> usize { self.pages.len() }
+
+  /// The count of the pages that keep their widget subtree and state by the
+  /// [`KeepAlivePolicy`], include the top page.
+  pub fn alive_count(&self) -> usize {
+    self
+      .depth()
+      .min(self.keep_alive_policy.capacity())
+  }
+
+  /// Set the back handler of the top page, it's usually called when the page
+  /// is built.
+  ///
+  /// When going back, the handler is called with the navigator, and return
+  /// `true` to prevent the top page from being popped.
+  pub fn on_back(&self, handler: impl FnMut(&mut Navigator) -> bool + 'static) {
+    if let Some(top) = self.pages.last() {
+      *top.on_back.borrow_mut() = Some(Box::new(handler));
+    }
+  }
+
+  /// Go back from the top page, the back handler of the top page decides
+  /// whether to pop it. Return if the back is handled.
+  pub fn back(&mut self) -> bool {
+    let Some(top) = self.pages.last() else { return false };
+    let key = top.key;
+    let handler = top.on_back.borrow_mut().take();
+    if let Some(mut handler) = handler {
+      let prevented = handler(self);
+      // Give the handler back if its page is still in the stack.
+      if let Some(entry) = self.pages.iter().find(|p| p.key == key) {
+        entry.on_back.borrow_mut().get_or_insert(handler);
+      }
+      if prevented {
+        return true;
+      }
+    }
+    self.pop()
+  }
+
+  fn new_entry(&mut self, page: GenWidget) -> PageEntry {
+    self.next_key += 1;
+    PageEntry { key: self.next_key, page, on_back: RefCell::new(None) }
+  }
+
+  fn key_of(&self, idx: usize) -> Option<usize> { self.pages.get(idx).map(|p| p.key) }
+
+  fn is_alive(&self, idx: usize) -> bool {
+    let depth = self.depth();
+    if idx >= depth {
+      return false;
+    }
+    depth - 1 - idx < self.keep_alive_policy.capacity()
+  }
+}
+
+impl ComposeChild<'static> for Navigator {
+  /// The root page.
+  type Child = GenWidget;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'static> {
+    if this.read().pages.is_empty() {
+      let mut nav = this.silent();
+      let root = nav.new_entry(child);
+      nav.pages.push(root);
+    }
+    let nav = this.clone_boxed_writer();
+    let key = this.read().key_of(0);
+
+    fn_widget! {
+      @Providers {
+        providers: [Provider::value_of_writer(this.clone_boxed_writer(), None)],
+        @Stack {
+          fit: StackFit::Passthrough,
+          on_key_down: move |e| {
+            let back = matches!(
+              e.key(),
+              VirtualKey::Named(NamedKey::BrowserBack | NamedKey::GoBack)
+            );
+            if back && $this.write().back() {
+              e.stop_propagation();
+            }
+          },
+          @ { page(nav.clone_boxed_writer(), 0, key) }
+          @ { above(nav, 0) }
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+/// The page at `idx` with the `key`, it's built only when it's alive.
+fn page(
+  nav: Box<dyn StateWriter<Value = Navigator>>, idx: usize, key: Option<usize>,
+) -> Widget<'static> {
+  fn_widget! {
+    distinct_pipe!($nav.is_alive(idx)).map(move |alive| {
+      let page = $nav
+        .pages
+        .get(idx)
+        .filter(|p| alive && Some(p.key) == key)
+        .map(|p| p.page.clone());
+      page.map(|page| {
+        let page = FatObj::new(page.gen_widget());
+        @ $page { visible: pipe!($nav.depth() == idx + 1) }
+      })
+    })
+  }
+  .into_widget()
+}
+
+/// The pages above the page at `idx`, they are nested to keep the pages below
+/// when the top page changes.
+fn above(nav: Box<dyn StateWriter<Value = Navigator>>, idx: usize) -> Widget<'static> {
+  let nav2 = nav.clone_boxed_writer();
+  fn_widget! {
+    distinct_pipe!($nav.key_of(idx + 1)).map(move |key| {
+      key.map(|key| transition_page(nav2.clone_boxed_writer(), idx + 1, key))
+    })
+  }
+  .into_widget()
+}
+
+/// The page at `idx` and the pages above it, fading in when it's pushed and
+/// fading out when it's popped.
+fn transition_page(
+  nav: Box<dyn StateWriter<Value = Navigator>>, idx: usize, key: usize,
+) -> Widget<'static> {
+  fn_widget! {
+    let mut stack = @Stack { fit: StackFit::Passthrough, opacity: 0. };
+    let animate = part_writer!(&mut stack.opacity)
+      .transition(transitions::EASE_OUT.of(BuildCtx::get()));
+
+    @ $stack {
+      keep_alive: pipe!($animate.is_running() || $stack.opacity != 0.),
+      on_mounted: move |_| $stack.write().opacity = 1.,
+      on_disposed: move |_| $stack.write().opacity = 0.,
+      @ { page(nav.clone_boxed_writer(), idx, Some(key)) }
+      @ { above(nav, idx) }
+    }
+  }
+  .into_widget()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{cell::Cell, rc::Rc};
+
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+
+  fn counted_page(builds: Rc<Cell<usize>>) -> GenWidget {
+    GenWidget::new(move || {
+      builds.set(builds.get() + 1);
+      MockBox { size: Size::new(100., 100.) }.into_widget()
+    })
+  }
+
+  fn navigator(policy: KeepAlivePolicy) -> (TestWindow, Box<dyn StateWriter<Value = Navigator>>) {
+    let nav = Rc::new(RefCell::new(None));
+    let nav2 = nav.clone();
+    let mut wnd = TestWindow::new(fn_widget! {
+      let nav2 = nav2.clone();
+      @Navigator {
+        keep_alive_policy: policy,
+        @ {
+          GenWidget::new(move || {
+            *nav2.borrow_mut() = Some(Navigator::writer_of(BuildCtx::get()));
+            MockBox { size: Size::new(100., 100.) }.into_widget()
+          })
+        }
+      }
+    });
+    wnd.draw_frame();
+    let nav = nav.borrow_mut().take().unwrap();
+    (wnd, nav)
+  }
+
+  #[test]
+  fn push_pop_replace() {
+    reset_test_env!();
+
+    let (mut wnd, nav) = navigator(KeepAlivePolicy::Always);
+    let (first, second) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+    nav.write().push(counted_page(first.clone()));
+    wnd.draw_frame();
+    assert_eq!((nav.read().depth(), first.get()), (2, 1));
+
+    nav.write().push(counted_page(second.clone()));
+    wnd.draw_frame();
+    assert!(nav.write().pop());
+    wnd.draw_frame();
+    // The page below the top is kept.
+    assert_eq!((nav.read().depth(), first.get(), second.get()), (2, 1, 1));
+
+    nav.write().replace(counted_page(second.clone()));
+    wnd.draw_frame();
+    assert_eq!((nav.read().depth(), second.get()), (2, 2));
+
+    assert!(nav.write().pop());
+    assert!(!nav.write().pop());
+    assert_eq!(nav.read().depth(), 1);
+  }
+
+  #[test]
+  fn rebuild_page_never_keep_alive() {
+    reset_test_env!();
+
+    let (mut wnd, nav) = navigator(KeepAlivePolicy::Never);
+    let builds = Rc::new(Cell::new(0));
+    nav.write().push(counted_page(builds.clone()));
+    wnd.draw_frame();
+    nav
+      .write()
+      .push(counted_page(Rc::new(Cell::new(0))));
+    wnd.draw_frame();
+    assert_eq!(nav.read().alive_count(), 1);
+    nav.write().pop();
+    wnd.draw_frame();
+    assert_eq!(builds.get(), 2);
+  }
+
+  #[test]
+  fn keep_recent_pages_alive() {
+    reset_test_env!();
+
+    let (mut wnd, nav) = navigator(KeepAlivePolicy::Lru(2));
+    let (first, second) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+    nav.write().push(counted_page(first.clone()));
+    wnd.draw_frame();
+    nav.write().push(counted_page(second.clone()));
+    wnd.draw_frame();
+    nav
+      .write()
+      .push(counted_page(Rc::new(Cell::new(0))));
+    wnd.draw_frame();
+    assert_eq!((nav.read().depth(), nav.read().alive_count()), (4, 2));
+
+    // The page right below the top is alive, the one below it is rebuilt.
+    nav.write().pop();
+    wnd.draw_frame();
+    assert_eq!(second.get(), 1);
+    nav.write().pop();
+    wnd.draw_frame();
+    assert_eq!(first.get(), 2);
+  }
+
+  #[test]
+  fn back_handler() {
+    reset_test_env!();
+
+    let (mut wnd, nav) = navigator(KeepAlivePolicy::Always);
+    nav
+      .write()
+      .push(counted_page(Rc::new(Cell::new(0))));
+    wnd.draw_frame();
+
+    let prevent = Rc::new(Cell::new(true));
+    let prevent2 = prevent.clone();
+    nav.read().on_back(move |_| prevent2.get());
+    assert!(nav.write().back());
+    assert_eq!(nav.read().depth(), 2);
+
+    prevent.set(false);
+    assert!(nav.write().back());
+    assert_eq!(nav.read().depth(), 1);
+    assert!(!nav.write().back());
+  }
+}
@@ -1,4 +1,6 @@
-use ribir_core::prelude::*;
+use std::{convert::Infallible, rc::Rc};
+
+use ribir_core::{prelude::*, rxrust::ops::box_it::BoxOp};
 
 use crate::prelude::{PositionChild, icon_with_label};
 
@@ -43,17 +45,40 @@ class_names! {
 ///   @Trailing::new("Trailing label placed after the radio button!")
 /// };
 /// ```
+///
+/// The radio buttons of a [`RadioGroup`] share one selection.
+///
+/// ```
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let size = Stateful::new("M");
+/// let group = RadioGroup::new(size.clone_writer());
+/// let _sizes = fn_widget! {
+///   @Column {
+///     @Radio { value: "S", group: group.clone(), @ { "Small" } }
+///     @Radio { value: "M", group: group.clone(), @ { "Medium" } }
+///     @Radio { value: "L", group: group.clone(), @ { "Large" } }
+///   }
+/// };
+/// ```
 #[derive(Declare)]
 pub struct Radio {
   #[declare(default)]
   pub selected: bool,
   #[declare(custom, default = Box::new(()) as Box<dyn Any>)]
   pub value: Box<dyn Any>,
+  #[declare(custom, default)]
+  pub group: Option<RadioGroup>,
 }
 
 pub trait RadioDeclarerCustomExtend {
   /// Initialize the radio value without supporting the pipe value format.
   fn value<V: 'static>(self, value: V) -> Self;
+
+  /// Add the radio to a group, it's selected when its value equals the
+  /// selection of the group.
+  fn group(self, group: RadioGroup) -> Self;
 }
 
 impl RadioDeclarerCustomExtend for FatObj<RadioDeclarer> {
@@ -61,9 +86,76 @@ impl RadioDeclarerCustomExtend for FatObj<RadioDeclarer> {
     self.value = Some(DeclareInit::Value(Box::new(value)));
     self
   }
+
+  fn group(mut self, group: RadioGroup) -> Self {
+    self.group = Some(DeclareInit::Value(Some(group)));
+    self
+  }
+}
+
+/// The shared selection of a group of radio buttons.
+///
+/// A radio of the group is selected when its value equals the selection, and
+/// selecting a radio writes its value to the selection, so the other radios of
+/// the group are unselected. The selection is a state, it can be read and
+/// written outside the group.
+#[derive(Clone)]
+pub struct RadioGroup(Rc<dyn RadioSelection>);
+
+trait RadioSelection {
+  fn is_selected(&self, value: &dyn Any) -> bool;
+  fn select(&self, value: &dyn Any);
+  fn modifies(&self) -> BoxOp<'static, ModifyScope, Infallible>;
+}
+
+struct StateSelection<S>(S);
+
+impl<S, V> RadioSelection for StateSelection<S>
+where
+  S: StateWriter<Value = V>,
+  V: PartialEq + Clone + 'static,
+{
+  fn is_selected(&self, value: &dyn Any) -> bool {
+    value
+      .downcast_ref::<V>()
+      .is_some_and(|v| *self.0.read() == *v)
+  }
+
+  fn select(&self, value: &dyn Any) {
+    if let Some(v) = value.downcast_ref::<V>() {
+      if *self.0.read() != *v {
+        *self.0.write() = v.clone();
+      }
+    }
+  }
+
+  fn modifies(&self) -> BoxOp<'static, ModifyScope, Infallible> { self.0.modifies() }
+}
+
+impl RadioGroup {
+  /// Create a group that shares the selection of the state, the values of the
+  /// radios in the group should be the type of the selection.
+  pub fn new<V: PartialEq + Clone + 'static>(selection: impl StateWriter<Value = V>) -> Self {
+    Self(Rc::new(StateSelection(selection)))
+  }
+
+  /// Return if the value is the selection of the group.
+  pub fn is_selected(&self, value: &dyn Any) -> bool { self.0.is_selected(value) }
+
+  /// Make the value the selection of the group, it's ignored if the value is
+  /// not the type of the selection.
+  pub fn select(&self, value: &dyn Any) { self.0.select(value) }
 }
 
 impl Radio {
+  /// Select this radio, and unselect the other radios of its group.
+  pub fn select(&mut self) {
+    self.selected = true;
+    if let Some(group) = &self.group {
+      group.select(&*self.value);
+    }
+  }
+
   fn radio_class_name(&self) -> ClassName {
     if self.selected { RADIO_SELECTED } else { RADIO_UNSELECTED }
   }
@@ -73,15 +165,33 @@ impl ComposeChild<'static> for Radio {
   type Child = Option<PositionChild<TextInit>>;
 
   fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'static> {
+    // Keep the radio following the selection of its group.
+    let group = this.read().group.clone();
+    let mut follow_group = group.map(|group| {
+      let modifies = group.0.modifies();
+      let this = this.clone_writer();
+      let sync = move |_| {
+        let selected = group.is_selected(&*this.read().value);
+        if this.read().selected != selected {
+          this.write().selected = selected;
+        }
+      };
+      sync(ModifyScope::DATA);
+      modifies.subscribe(sync)
+    });
+
     rdl! {
       let icon = @Class {
         class: distinct_pipe!($this.radio_class_name()),
         @Void { class: RADIO }
       };
       @FatObj {
-        on_tap: move |_| $this.write().selected = true,
+        on_tap: move |_| $this.write().select(),
         on_key_up: move |k| if *k.key() == VirtualKey::Named(NamedKey::Space) {
-          $this.write().selected = true
+          $this.write().select()
+        },
+        on_disposed: move |_| if let Some(u) = follow_group.take() {
+          u.unsubscribe();
         },
         @icon_with_label(icon.into_widget(), child)
       }
@@ -92,7 +202,7 @@ impl ComposeChild<'static> for Radio {
 
 #[cfg(test)]
 mod tests {
-  use ribir_core::{prelude::*, test_helper::*};
+  use ribir_core::{prelude::*, reset_test_env, test_helper::*};
   use ribir_dev_helper::*;
 
   use crate::prelude::*;
@@ -117,4 +227,46 @@ mod tests {
     .with_wnd_size(Size::new(200., 256.))
     .with_comparison(0.002)
   );
+
+  #[test]
+  fn radio_group_share_selection() {
+    reset_test_env!();
+
+    struct Form {
+      first: bool,
+      second: bool,
+    }
+
+    let selection = Stateful::new(1);
+    let group = RadioGroup::new(selection.clone_writer());
+    let form = Stateful::new(Form { first: false, second: false });
+    let c_form = form.clone_writer();
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockMulti {
+        @Radio { value: 1, group: group.clone(), selected: bind!(&mut c_form.first) }
+        @Radio {
+          value: 2,
+          group: group.clone(),
+          auto_focus: true,
+          selected: bind!(&mut c_form.second),
+        }
+      }
+    });
+    wnd.draw_frame();
+    assert!(form.read().first);
+    assert!(!form.read().second);
+
+    UiEvent::key_press(VirtualKey::Named(NamedKey::Space), PhysicalKey::Code(KeyCode::Space))
+      .into_iter()
+      .for_each(|e| wnd.inject_event(e));
+    wnd.draw_frame();
+    assert_eq!(*selection.read(), 2);
+    assert!(!form.read().first);
+    assert!(form.read().second);
+
+    *selection.write() = 1;
+    wnd.draw_frame();
+    assert!(form.read().first);
+    assert!(!form.read().second);
+  }
 }
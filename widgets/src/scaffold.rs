@@ -0,0 +1,565 @@
+use std::cell::Cell;
+
+use ribir_core::{prelude::*, ticker::FrameMsg};
+
+use crate::prelude::*;
+
+/// How the [`Scaffold`] reacts to the viewport insets of the window, such as
+/// the on-screen keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScaffoldResizePolicy {
+  /// Shrink the content to the area not covered by the insets. So the bars
+  /// anchored to the bottom move up, and the scroll views shrink and scroll the
+  /// focused widget into view.
+  #[default]
+  Resize,
+  /// Keep the size of the content, but move it up if the focused widget is
+  /// covered by the bottom inset.
+  Pan,
+  /// Ignore the insets, the content may be covered by them.
+  Overlay,
+}
+
+class_names! {
+  #[doc = "Class name for the app bar"]
+  APP_BAR,
+  #[doc = "Class name for the panel of the navigation drawer"]
+  DRAWER,
+}
+
+/// The width of the navigation drawer.
+const DRAWER_WIDTH: f32 = 360.;
+
+/// The conventional skeleton of a page, it lays out an optional [`TopBar`],
+/// the body, an optional [`BottomBar`], and a [`FloatingAction`] at the bottom
+/// right of the body.
+///
+/// The [`Drawer`] is shown in an overlay with a scrim when `drawer_open` is
+/// set, it slides in from the left and is closed by tapping the scrim or
+/// pressing `Escape`. The scaffold is provided to its content, use
+/// [`Scaffold::write_of`] to open the drawer.
+///
+/// The content is kept out of the area covered by the viewport insets of the
+/// window according to the `resize_policy`.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let _page = fn_widget! {
+///   @Scaffold {
+///     @TopBar::new(@AppBar {
+///       @Leading::new(@Icon {
+///         on_tap: move |e| Scaffold::write_of(&e).drawer_open = true,
+///         @{ svgs::MENU }
+///       })
+///       @{ "Inbox" }
+///     })
+///     @Drawer::new(fn_widget! { @Text { text: "Navigation" } })
+///     @FloatingAction::new(@Button { @Icon { @{ svgs::ADD } } })
+///     @Column {
+///       @Expanded { @Input {} }
+///       @Text { text: "The bottom bar moves up with the keyboard." }
+///     }
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct Scaffold {
+  #[declare(default)]
+  pub resize_policy: ScaffoldResizePolicy,
+  /// Whether the navigation drawer is open.
+  #[declare(default)]
+  pub drawer_open: bool,
+}
+
+/// The bar at the top of the [`Scaffold`], usually an [`AppBar`].
+#[derive(ChildOfCompose)]
+pub struct TopBar<'w>(Widget<'w>);
+
+/// The bar at the bottom of the [`Scaffold`], such as a navigation bar.
+#[derive(ChildOfCompose)]
+pub struct BottomBar<'w>(Widget<'w>);
+
+/// The floating action button of the [`Scaffold`], it floats at the bottom
+/// right of the body.
+#[derive(ChildOfCompose)]
+pub struct FloatingAction<'w>(Widget<'w>);
+
+/// The content of the navigation drawer of the [`Scaffold`], it's built every
+/// time the drawer opens.
+#[derive(ChildOfCompose)]
+pub struct Drawer(GenWidget);
+
+#[derive(Template)]
+pub struct ScaffoldChild<'w> {
+  body: Widget<'w>,
+  top_bar: Option<TopBar<'w>>,
+  bottom_bar: Option<BottomBar<'w>>,
+  floating_action: Option<FloatingAction<'w>>,
+  drawer: Option<Drawer>,
+}
+
+impl<'w> TopBar<'w> {
+  pub fn new<const M: usize>(bar: impl IntoChildCompose<Widget<'w>, M>) -> Self {
+    TopBar(bar.into_child_compose())
+  }
+}
+
+impl<'w> BottomBar<'w> {
+  pub fn new<const M: usize>(bar: impl IntoChildCompose<Widget<'w>, M>) -> Self {
+    BottomBar(bar.into_child_compose())
+  }
+}
+
+impl<'w> FloatingAction<'w> {
+  pub fn new<const M: usize>(action: impl IntoChildCompose<Widget<'w>, M>) -> Self {
+    FloatingAction(action.into_child_compose())
+  }
+}
+
+impl Drawer {
+  pub fn new(content: impl Into<GenWidget>) -> Self { Drawer(content.into()) }
+}
+
+impl Scaffold {
+  /// Return the scaffold of the page in the context.
+  ///
+  /// # Panic
+  ///
+  /// Panic if the context is not in a scaffold.
+  pub fn of(ctx: &impl AsRef<ProviderCtx>) -> QueryRef<'_, Self> { Provider::of(ctx).unwrap() }
+
+  /// Return the write reference of the scaffold in the context.
+  ///
+  /// # Panic
+  ///
+  /// Panic if the context is not in a scaffold.
+  pub fn write_of(ctx: &impl AsRef<ProviderCtx>) -> WriteRef<'_, Self> {
+    Provider::write_of(ctx).unwrap()
+  }
+
+  /// Return the writer of the scaffold in the context, to change the scaffold
+  /// later.
+  ///
+  /// # Panic
+  ///
+  /// Panic if the context is not in a scaffold.
+  pub fn writer_of(ctx: &impl AsRef<ProviderCtx>) -> Box<dyn StateWriter<Value = Self>> {
+    Provider::state_of::<Box<dyn StateWriter<Value = Self>>>(ctx)
+      .unwrap()
+      .clone_boxed_writer()
+  }
+}
+
+impl<'c> ComposeChild<'c> for Scaffold {
+  type Child = ScaffoldChild<'c>;
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    let ScaffoldChild { body, top_bar, bottom_bar, floating_action, drawer } = child;
+    let drawer = drawer.map(|Drawer(content)| {
+      let this = this.clone_boxed_writer();
+      Overlay::new(
+        move || drawer_overlay(this.clone_boxed_writer(), content.gen_widget()),
+        OverlayStyle { auto_close_policy: AutoClosePolicy::NOT_AUTO_CLOSE, mask: None },
+      )
+    });
+
+    fn_widget! {
+      let wnd = BuildCtx::get().window();
+      let insets = wnd.viewport_insets();
+      let pan = Stateful::new(0f32);
+
+      let drawer_wnd = wnd.clone();
+      let showing_drawer = drawer.clone();
+      let u_drawer = drawer.map(|drawer| {
+        watch!($this.drawer_open)
+          .distinct_until_changed()
+          .subscribe(move |open| {
+            if open {
+              drawer.show(drawer_wnd.clone());
+            } else {
+              drawer.close();
+            }
+          })
+      });
+
+      // The focused widget may be covered when the focus moves while the insets
+      // keep the same, so check the focus again at the end of every frame.
+      let last_focus = Cell::new(None);
+      let c_wnd = wnd.clone();
+      let focus_changed = wnd
+        .frame_tick_stream()
+        .filter(|msg| matches!(msg, FrameMsg::Finish(_)))
+        .filter(move |_| {
+          let focus = c_wnd.focusing();
+          last_focus.replace(focus) != focus
+        })
+        .map(move |_| (*$insets, $this.resize_policy));
+      let u = watch!((*$insets, $this.resize_policy))
+        .merge(focus_changed)
+        .subscribe(move |(insets, policy)| {
+          let mut offset = 0.;
+          if policy == ScaffoldResizePolicy::Pan {
+            if let Some(focus) = wnd.focusing() {
+              let height = wnd.widget_size(focus).map_or(0., |s| s.height);
+              let bottom = wnd.map_to_global(Point::new(0., height), focus).y;
+              // The position already contains the current pan offset.
+              let visible_bottom = wnd.size().height - insets.bottom;
+              offset = (bottom + *$pan - visible_bottom).max(0.);
+            }
+          }
+          if *$pan != offset {
+            *$pan.write() = offset;
+          }
+        });
+
+      let floating_action = floating_action.map(|FloatingAction(action)| {
+        let action = FatObj::new(action);
+        @ $action {
+          h_align: HAlign::Right,
+          v_align: VAlign::Bottom,
+          margin: EdgeInsets::all(16.),
+        }
+      });
+
+      @Providers {
+        providers: [Provider::value_of_writer(this.clone_boxed_writer(), None)],
+        @Column {
+          align_items: Align::Stretch,
+          padding: pipe! {
+            if $this.resize_policy == ScaffoldResizePolicy::Resize {
+              *$insets
+            } else {
+              EdgeInsets::default()
+            }
+          },
+          transform: pipe!(Transform::translation(0., -*$pan)),
+          on_disposed: move |_| {
+            u.unsubscribe();
+            if let Some(u) = u_drawer {
+              u.unsubscribe();
+            }
+            if let Some(drawer) = &showing_drawer {
+              drawer.close();
+            }
+          },
+          @ { top_bar.map(|TopBar(bar)| bar) }
+          @Expanded {
+            flex: 1.,
+            @Stack {
+              fit: StackFit::Passthrough,
+              @ { body }
+              @ { floating_action }
+            }
+          }
+          @ { bottom_bar.map(|BottomBar(bar)| bar) }
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+/// The navigation drawer sliding in from the left above a scrim.
+fn drawer_overlay(
+  scaffold: Box<dyn StateWriter<Value = Scaffold>>, content: Widget<'static>,
+) -> Widget<'static> {
+  let close = scaffold.clone_boxed_writer();
+  let esc_close = scaffold.clone_boxed_writer();
+  fn_widget! {
+    let mut scrim = @Container {
+      size: Size::splat(f32::INFINITY),
+      background: Palette::of(BuildCtx::get()).scrim().with_alpha(0.32),
+      opacity: 0.,
+      tab_index: -1_i16,
+      auto_focus: true,
+    };
+    let animate = part_writer!(&mut scrim.opacity)
+      .transition(transitions::EASE_OUT.of(BuildCtx::get()));
+    let content = FatObj::new(content);
+
+    @Providers {
+      providers: [Provider::value_of_writer(scaffold.clone_boxed_writer(), None)],
+      @ $scrim {
+        keep_alive: pipe!($animate.is_running() || $scrim.opacity != 0.),
+        on_mounted: move |_| $scrim.write().opacity = 1.,
+        on_disposed: move |_| $scrim.write().opacity = 0.,
+        on_tap: move |e| {
+          if e.target() == e.current_target() {
+            close.write().drawer_open = false;
+          }
+        },
+        on_key_down: move |e| {
+          if *e.key() == VirtualKey::Named(NamedKey::Escape) {
+            esc_close.write().drawer_open = false;
+          }
+        },
+        @FocusScope {
+          trap: true,
+          @ $content {
+            class: DRAWER,
+            v_align: VAlign::Stretch,
+            clamp: BoxClamp::fixed_width(DRAWER_WIDTH),
+            transform: pipe!(Transform::translation(($scrim.opacity - 1.) * DRAWER_WIDTH, 0.)),
+          }
+        }
+      }
+    }
+  }
+  .into_widget()
+}
+
+/// The bar at the top of a page, it shows the title of the page between an
+/// optional leading widget, such as a navigation button, and an optional
+/// trailing widget, such as the actions of the page.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let _bar = app_bar! {
+///   @Leading::new(@Icon { @{ svgs::MENU } })
+///   @{ "Inbox" }
+///   @Trailing::new(@Icon { @{ svgs::SEARCH } })
+/// };
+/// ```
+#[derive(Declare)]
+pub struct AppBar;
+
+#[derive(Template)]
+pub struct AppBarChild<'w> {
+  title: TextInit,
+  leading: Option<Leading<Widget<'w>>>,
+  trailing: Option<Trailing<Widget<'w>>>,
+}
+
+impl<'c> ComposeChild<'c> for AppBar {
+  type Child = AppBarChild<'c>;
+
+  fn compose_child(_: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    let AppBarChild { title, leading, trailing } = child;
+    row! {
+      class: APP_BAR,
+      align_items: Align::Center,
+      @ { leading.map(Leading::unwrap) }
+      @Expanded {
+        flex: 1.,
+        @Text { text: title }
+      }
+      @ { trailing.map(Trailing::unwrap) }
+    }
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{cell::RefCell, rc::Rc};
+
+  use ribir_core::{reset_test_env, test_helper::*};
+  use winit::{
+    event::{DeviceId, ElementState, MouseButton, WindowEvent},
+    keyboard::{KeyCode, KeyLocation, PhysicalKey},
+  };
+
+  use super::*;
+
+  fn page(
+    policy: ScaffoldResizePolicy, bar: impl StateWriter<Value = Option<WidgetId>> + 'static,
+  ) -> GenWidget {
+    fn_widget! {
+      let bar = bar.clone_writer();
+      @Scaffold {
+        resize_policy: policy,
+        @Column {
+          @Expanded { @SizedBox { size: Size::new(100., 10.) } }
+          @SizedBox {
+            size: Size::new(100., 20.),
+            on_mounted: move |e| *$bar.write() = Some(e.current_target()),
+          }
+        }
+      }
+    }
+    .into()
+  }
+
+  #[test]
+  fn resize_moves_bottom_bar_up() {
+    reset_test_env!();
+
+    let bar = Stateful::new(None);
+    let mut wnd = TestWindow::new_with_size(
+      page(ScaffoldResizePolicy::Resize, bar.clone_writer()),
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+    let bar = bar.read().unwrap();
+    assert_eq!(wnd.map_to_global(Point::zero(), bar), Point::new(0., 80.));
+
+    wnd.set_viewport_insets(EdgeInsets::only_bottom(30.));
+    wnd.draw_frame();
+    assert_eq!(wnd.map_to_global(Point::zero(), bar), Point::new(0., 50.));
+
+    wnd.set_viewport_insets(EdgeInsets::default());
+    wnd.draw_frame();
+    assert_eq!(wnd.map_to_global(Point::zero(), bar), Point::new(0., 80.));
+  }
+
+  #[test]
+  fn pan_focused_widget_above_insets() {
+    reset_test_env!();
+
+    let focus = Stateful::new(None);
+    let c_focus = focus.clone_writer();
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let focus = c_focus.clone_writer();
+        @Scaffold {
+          resize_policy: ScaffoldResizePolicy::Pan,
+          @Column {
+            @Expanded { @SizedBox { size: Size::new(100., 10.) } }
+            @SizedBox {
+              size: Size::new(100., 20.),
+              tab_index: 0_i16,
+              auto_focus: true,
+              on_mounted: move |e| *$focus.write() = Some(e.current_target()),
+            }
+          }
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+    let input = focus.read().unwrap();
+    assert_eq!(wnd.focusing(), Some(input));
+
+    wnd.set_viewport_insets(EdgeInsets::only_bottom(30.));
+    wnd.draw_frame();
+    assert_eq!(wnd.map_to_global(Point::zero(), input), Point::new(0., 50.));
+    // The size of the content is not changed.
+    assert_eq!(wnd.widget_size(input), Some(Size::new(100., 20.)));
+
+    wnd.set_viewport_insets(EdgeInsets::default());
+    wnd.draw_frame();
+    assert_eq!(wnd.map_to_global(Point::zero(), input), Point::new(0., 80.));
+  }
+
+  #[test]
+  fn pan_when_focus_moves() {
+    reset_test_env!();
+
+    let bottom = Stateful::new(None);
+    let c_bottom = bottom.clone_writer();
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let bottom = c_bottom.clone_writer();
+        @Scaffold {
+          resize_policy: ScaffoldResizePolicy::Pan,
+          @Column {
+            @SizedBox { size: Size::new(100., 20.), tab_index: 0_i16, auto_focus: true }
+            @Expanded { @SizedBox { size: Size::new(100., 10.) } }
+            @SizedBox {
+              size: Size::new(100., 20.),
+              tab_index: 0_i16,
+              on_mounted: move |e| *$bottom.write() = Some(e.current_target()),
+            }
+          }
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+    let bottom = bottom.read().unwrap();
+
+    // The focused widget is not covered, so the content keeps still.
+    wnd.set_viewport_insets(EdgeInsets::only_bottom(30.));
+    wnd.draw_frame();
+    assert_eq!(wnd.map_to_global(Point::zero(), bottom), Point::new(0., 80.));
+
+    wnd.request_next_focus();
+    wnd.draw_frame();
+    assert_eq!(wnd.focusing(), Some(bottom));
+    // The new focus is checked at the end of the frame, and panned in the next.
+    wnd.draw_frame();
+    assert_eq!(wnd.map_to_global(Point::zero(), bottom), Point::new(0., 50.));
+  }
+
+  #[test]
+  fn overlay_ignore_insets() {
+    reset_test_env!();
+
+    let bar = Stateful::new(None);
+    let mut wnd = TestWindow::new_with_size(
+      page(ScaffoldResizePolicy::Overlay, bar.clone_writer()),
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+    let bar = bar.read().unwrap();
+    wnd.set_viewport_insets(EdgeInsets::only_bottom(30.));
+    wnd.draw_frame();
+    assert_eq!(wnd.map_to_global(Point::zero(), bar), Point::new(0., 80.));
+  }
+
+  #[test]
+  fn drawer_open_and_close() {
+    reset_test_env!();
+
+    let scaffold = Rc::new(RefCell::new(None));
+    let c_scaffold = scaffold.clone();
+    let (mounted, w_mounted) = split_value(0);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let c_scaffold = c_scaffold.clone();
+        @Scaffold {
+          @Drawer::new(fn_widget! {
+            @MockBox {
+              size: Size::new(100., 100.),
+              on_mounted: move |_| *$w_mounted.write() += 1,
+            }
+          })
+          @ {
+            fn_widget! {
+              *c_scaffold.borrow_mut() = Some(Scaffold::writer_of(BuildCtx::get()));
+              @MockBox { size: Size::new(100., 100.) }
+            }
+          }
+        }
+      },
+      Size::new(400., 200.),
+    );
+    wnd.draw_frame();
+    let scaffold = scaffold.borrow_mut().take().unwrap();
+    assert_eq!(*mounted.read(), 0);
+
+    scaffold.write().drawer_open = true;
+    wnd.draw_frame();
+    assert_eq!(*mounted.read(), 1);
+
+    // Tapping the scrim out of the drawer closes it.
+    let device_id = unsafe { DeviceId::dummy() };
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::CursorMoved {
+      device_id,
+      position: (390., 100.).into(),
+    });
+    wnd.process_mouse_input(device_id, ElementState::Pressed, MouseButton::Left);
+    wnd.process_mouse_input(device_id, ElementState::Released, MouseButton::Left);
+    wnd.draw_frame();
+    assert!(!scaffold.read().drawer_open);
+
+    scaffold.write().drawer_open = true;
+    wnd.draw_frame();
+    assert_eq!(*mounted.read(), 2);
+    wnd.processes_keyboard_event(
+      PhysicalKey::Code(KeyCode::Escape),
+      VirtualKey::Named(NamedKey::Escape),
+      false,
+      KeyLocation::Standard,
+      ElementState::Pressed,
+    );
+    wnd.draw_frame();
+    assert!(!scaffold.read().drawer_open);
+  }
+}
@@ -1,6 +1,6 @@
 use ribir_core::prelude::*;
 
-use crate::layout::{Stack, StackDeclareExtend, StackFit};
+use crate::layout::{Direction, Stack, StackDeclareExtend, StackFit};
 
 /// This widget wraps its child in a `ScrollableWidget` and adds two scrollbar
 /// for interactivity and visual scroll position indication.
@@ -188,6 +188,183 @@ impl<'c> ComposeChild<'c> for Scrollbar {
   }
 }
 
+/// The style of `HScrollBar` and `VScrollBar`, a theme can customize it by
+/// `CustomStyles`.
+#[derive(Clone, Debug)]
+pub struct ScrollBarTheme {
+  /// The thickness of the scrollbar.
+  pub thickness: f32,
+  /// The minimum length of the thumb.
+  pub thumb_min_size: f32,
+  /// The radius of the thumb.
+  pub thumb_radius: f32,
+  /// The color of the thumb.
+  pub thumb_color: Color,
+  /// The color of the track, it's only displayed when the pointer hovers on
+  /// the scrollbar.
+  pub track_color: Color,
+  /// The scrollbar hides if the scroll position is not changed for this
+  /// duration, `None` means the scrollbar is always shown.
+  pub auto_hide: Option<Duration>,
+}
+
+impl CustomStyle for ScrollBarTheme {
+  fn default_style(ctx: &impl AsRef<ProviderCtx>) -> Self {
+    let palette = Palette::of(ctx);
+    ScrollBarTheme {
+      thickness: 8.,
+      thumb_min_size: 12.,
+      thumb_radius: 4.,
+      thumb_color: palette.on_surface_variant(),
+      track_color: palette.surface_container(),
+      auto_hide: Some(Duration::from_secs(3)),
+    }
+  }
+}
+
+/// A horizontal scrollbar bound to the scroll state of a scrollable widget.
+///
+/// Unlike `Scrollbar`, it doesn't host the scroll content, so you can place it
+/// anywhere. It keeps in sync with the scroll position, and you can drag its
+/// thumb or tap its track to scroll. The appearance is read from
+/// `ScrollBarTheme`.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _ = fn_widget! {
+///   let scroll = Stateful::new(ScrollableWidget::default());
+///   scroll.write().scrollable = Scrollable::X;
+///   let client = FatObj::new(scroll.clone_writer());
+///   @Stack {
+///     @ $client { @Container { size: Size::new(1000., 100.) } }
+///     @HScrollBar { scroll, v_align: VAlign::Bottom }
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct HScrollBar {
+  pub scroll: Stateful<ScrollableWidget>,
+}
+
+/// A vertical scrollbar bound to the scroll state of a scrollable widget. See
+/// [`HScrollBar`] for more details.
+#[derive(Declare)]
+pub struct VScrollBar {
+  pub scroll: Stateful<ScrollableWidget>,
+}
+
+impl Compose for HScrollBar {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    let scroll = this.read().scroll.clone_writer();
+    scroll_bar(scroll, Direction::Horizontal)
+  }
+}
+
+impl Compose for VScrollBar {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    let scroll = this.read().scroll.clone_writer();
+    scroll_bar(scroll, Direction::Vertical)
+  }
+}
+
+fn scroll_bar(scroll: Stateful<ScrollableWidget>, dir: Direction) -> Widget<'static> {
+  let hor = dir.is_horizontal();
+  // Pick the value of the main axis.
+  let main = move |p: Point| if hor { p.x } else { p.y };
+  let main_size = move |s: Size| if hor { s.width } else { s.height };
+  let with_main = move |p: Point, v: f32| if hor { Point::new(v, p.y) } else { Point::new(p.x, v) };
+
+  fn_widget! {
+    let ScrollBarTheme {
+      thickness, thumb_min_size, thumb_radius, thumb_color, track_color, auto_hide
+    } = ScrollBarTheme::of(BuildCtx::get());
+
+    let drag: Stateful<Option<(GrabPointer, f32, f32)>> = Stateful::new(None);
+    let active = Stateful::new(auto_hide.is_none());
+
+    let mut track = @Stack {
+      visible: pipe!(if hor { $scroll.is_x_scrollable() } else { $scroll.is_y_scrollable() }),
+      opacity: pipe!(if *$active { 1. } else { 0. }),
+      // The hidden scrollbar only reveals itself by hovering, the other input
+      // is ignored until it shows.
+      on_wheel: move |e| if *$active {
+        $scroll.write().scroll(-e.delta_x, -e.delta_y)
+      },
+    };
+    track = if hor { track.h_align(HAlign::Stretch) } else { track.v_align(VAlign::Stretch) };
+
+    let mut thumb = @Container {
+      size: distinct_pipe! {
+        let scroll = $scroll;
+        let rate = main_size(scroll.scroll_view_size()) / main_size(scroll.scroll_content_size());
+        let len = (rate.min(1.) * main_size($track.layout_size())).max(thumb_min_size);
+        if hor { Size::new(len, thickness) } else { Size::new(thickness, len) }
+      },
+      radius: Radius::all(thumb_radius),
+      background: thumb_color,
+      on_tap: move |e| e.stop_propagation(),
+      on_pointer_down: move |e| if e.is_primary && *$active {
+        if let Some(handle) = GrabPointer::grab(e.current_target(), &e.window()) {
+          let pos = main(($scroll).get_scroll_pos());
+          *$drag.write() = Some((handle, main(e.global_pos()), pos));
+        }
+      },
+      on_pointer_up: move |_| { $drag.write().take(); },
+    };
+
+    // Show the scrollbar when it is scrolling, hovering or dragging, and hide it
+    // after inactivity.
+    let auto_hide = auto_hide.map(|delay| {
+      let mut hide: Option<TaskHandle<_>> = None;
+      watch!((($scroll).get_scroll_pos(), $track.is_hover(), $drag.is_some()))
+        .distinct_until_changed()
+        .subscribe(move |_| {
+          *$active.write() = true;
+          if let Some(h) = hide.take() {
+            h.unsubscribe();
+          }
+          let h = observable::timer((), delay, AppCtx::scheduler())
+            .filter(move |_| !$track.is_hover() && $drag.is_none())
+            .subscribe(move |_| *$active.write() = false);
+          hide = Some(h);
+        })
+    });
+
+    @ $track {
+      background: pipe!(if $track.is_hover() { track_color } else { track_color.with_alpha(0.) }),
+      on_disposed: move |_| if let Some(u) = auto_hide { u.unsubscribe() },
+      on_tap: move |e| if e.is_primary && *$active {
+        let rate = main(e.position()) / main_size($track.layout_size());
+        let mut scroll = $scroll.write();
+        let to = with_main(scroll.get_scroll_pos(), rate * main(scroll.max_scrollable()));
+        scroll.jump_to(to);
+      },
+      @ $thumb {
+        on_pointer_move: move |e| if let Some((_, from, pos)) = $drag.as_ref() {
+          let distance = main_size($track.layout_size()) - main_size($thumb.layout_size());
+          if distance > 0. {
+            let mut scroll = $scroll.write();
+            let offset = (main(e.global_pos()) - from) / distance * main(scroll.max_scrollable());
+            let to = with_main(scroll.get_scroll_pos(), pos + offset);
+            scroll.jump_to(to);
+          }
+        },
+        anchor: distinct_pipe! {
+          let scroll = $scroll;
+          let rate = if hor { scroll.get_x_scroll_rate() } else { scroll.get_y_scroll_rate() };
+          let distance = main_size($track.layout_size()) - main_size($thumb.layout_size());
+          if hor { Anchor::left(rate * distance) } else { Anchor::top(rate * distance) }
+        }
+      }
+    }
+  }
+  .into_widget()
+}
+
 fn h_thumb_rate(s: &ScrollableWidget) -> f32 {
   s.scroll_view_size().width / s.scroll_content_size().width
 }
@@ -197,8 +374,9 @@ fn v_thumb_rate(s: &ScrollableWidget) -> f32 {
 
 #[cfg(test)]
 mod test {
-  use ribir_core::test_helper::*;
+  use ribir_core::{reset_test_env, test_helper::*};
   use ribir_dev_helper::*;
+  use winit::event::{DeviceId, ElementState, MouseButton, Touch, TouchPhase, WindowEvent};
 
   use super::*;
 
@@ -238,4 +416,93 @@ mod test {
     },
     LayoutCase::default().with_size(Size::new(100., 100.))
   );
+
+  #[test]
+  fn drag_scroll_bar_thumb() {
+    reset_test_env!();
+
+    let scroll = Stateful::new(ScrollableWidget::default());
+    let c_scroll = scroll.clone_writer();
+    let w = fn_widget! {
+      let client = FatObj::new(scroll.clone_writer());
+      @Stack {
+        @ $client { @Container { size: Size::new(100., 400.) } }
+        @VScrollBar { scroll: scroll.clone_writer() }
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+
+    // The thumb is 25 pixels long at the top of the track.
+    let device_id = unsafe { DeviceId::dummy() };
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::CursorMoved { device_id, position: (4., 10.).into() });
+    wnd.process_mouse_input(device_id, ElementState::Pressed, MouseButton::Left);
+    wnd.run_frame_tasks();
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::CursorMoved { device_id, position: (4., 35.).into() });
+    wnd.run_frame_tasks();
+    wnd.process_mouse_input(device_id, ElementState::Released, MouseButton::Left);
+    wnd.draw_frame();
+
+    assert_eq!(c_scroll.read().get_scroll_pos(), Point::new(0., 100.));
+  }
+
+  #[test]
+  fn hidden_scroll_bar_ignore_input() {
+    reset_test_env!();
+
+    AppCtx::app_theme()
+      .write()
+      .custom_styles
+      .set_custom_style(ScrollBarTheme {
+        thickness: 8.,
+        thumb_min_size: 12.,
+        thumb_radius: 4.,
+        thumb_color: Color::BLACK,
+        track_color: Color::GRAY,
+        auto_hide: Some(Duration::ZERO),
+      });
+    let scroll = Stateful::new(ScrollableWidget::default());
+    let c_scroll = scroll.clone_writer();
+    let w = fn_widget! {
+      let client = FatObj::new(scroll.clone_writer());
+      @Stack {
+        @ $client { @Container { size: Size::new(100., 400.) } }
+        @VScrollBar { scroll: scroll.clone_writer() }
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+    AppCtx::run_until_stalled();
+    wnd.draw_frame();
+
+    let tap = |wnd: &mut TestWindow| {
+      for phase in [TouchPhase::Started, TouchPhase::Ended] {
+        #[allow(deprecated)]
+        wnd.processes_native_event(WindowEvent::Touch(Touch {
+          device_id: unsafe { DeviceId::dummy() },
+          phase,
+          location: (4., 90.).into(),
+          force: None,
+          id: 0,
+        }));
+      }
+      wnd.draw_frame();
+    };
+
+    // A touch doesn't hover, so the hidden scrollbar ignores the tap.
+    tap(&mut wnd);
+    assert_eq!(c_scroll.read().get_scroll_pos(), Point::zero());
+
+    // Hovering shows the scrollbar, then it handles the tap.
+    let device_id = unsafe { DeviceId::dummy() };
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::CursorMoved { device_id, position: (4., 90.).into() });
+    wnd.draw_frame();
+    wnd.process_mouse_input(device_id, ElementState::Pressed, MouseButton::Left);
+    wnd.process_mouse_input(device_id, ElementState::Released, MouseButton::Left);
+    wnd.draw_frame();
+    assert_eq!(c_scroll.read().get_scroll_pos(), Point::new(0., 270.));
+  }
 }
@@ -0,0 +1,325 @@
+use ribir_core::prelude::*;
+
+use crate::prelude::*;
+
+class_names! {
+  #[doc = "Class name for the field of the select showing the selected option"]
+  SELECT,
+  #[doc = "Class name for the list of the options"]
+  SELECT_OPTIONS,
+  #[doc = "Class name for an option of the list"]
+  SELECT_OPTION,
+  #[doc = "Class name for the option navigated by the keyboard"]
+  SELECT_OPTION_ACTIVE,
+}
+
+/// The characters typed within it are searched together.
+const TYPEAHEAD_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A field that shows the selected option, tapping it or pressing `Enter`
+/// opens a list of the options below it to choose one.
+///
+/// In the list, the arrow keys navigate the options and `Enter` chooses the
+/// navigated one. Typing the characters jumps to the option whose label starts
+/// with them, it also works on the field without opening the list.
+///
+/// Choosing an option different from the selected one bubbles a
+/// [`SelectChangedEvent`] from the select.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _select = fn_widget! {
+///   @Select {
+///     options: vec!["Apple".into(), "Banana".into(), "Cherry".into()],
+///     placeholder: "Pick a fruit",
+///     on_custom_concrete_event: move |e: &mut SelectChangedEvent| {
+///       println!("{} is selected", &*e.data().label);
+///     },
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct Select {
+  /// The labels of the options.
+  pub options: Vec<CowArc<str>>,
+  /// The index of the selected option, it can be bound to a state by `bind!`.
+  #[declare(default)]
+  pub selected: Option<usize>,
+  /// The text shown when no option is selected.
+  #[declare(default)]
+  pub placeholder: CowArc<str>,
+}
+
+/// The option chosen by the user, see [`SelectChangedEvent`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectChanged {
+  pub index: usize,
+  pub label: CowArc<str>,
+}
+
+/// The event bubbled from the [`Select`] when the user chooses another option.
+pub type SelectChangedEvent = CustomEvent<SelectChanged>;
+
+impl Select {
+  /// Search the option whose label starts with the `query`, ignoring the case.
+  ///
+  /// A query of one character is searched from the option after `from`, so
+  /// typing the same character cycles through the options starting with it.
+  /// A longer query is searched from `from` itself.
+  pub fn search(&self, query: &str, from: Option<usize>) -> Option<usize> {
+    let len = self.options.len();
+    if len == 0 || query.is_empty() {
+      return None;
+    }
+    let query = query.to_lowercase();
+    let start = match from {
+      Some(from) if query.chars().count() == 1 => from + 1,
+      Some(from) => from,
+      None => 0,
+    };
+    (0..len)
+      .map(|i| (start + i) % len)
+      .find(|&i| self.options[i].to_lowercase().starts_with(&query))
+  }
+
+  fn label(&self) -> CowArc<str> {
+    self
+      .selected
+      .and_then(|idx| self.options.get(idx))
+      .cloned()
+      .unwrap_or_else(|| self.placeholder.clone())
+  }
+}
+
+/// The characters typed to search the options.
+#[derive(Default)]
+struct Typeahead {
+  query: String,
+  last: Option<Instant>,
+}
+
+impl Typeahead {
+  /// Append the characters to the query, the query restarts if nothing is
+  /// typed for a while.
+  fn push(&mut self, chars: &str) -> &str {
+    let now = FrameClock::now();
+    if self
+      .last
+      .is_none_or(|last| now - last > TYPEAHEAD_TIMEOUT)
+    {
+      self.query.clear();
+    }
+    self.last = Some(now);
+    self.query.push_str(chars);
+    &self.query
+  }
+}
+
+/// Select the option at `idx`, and bubble the change from the select `wid`.
+fn select_option(
+  select: &dyn StateWriter<Value = Select>, idx: usize, wid: WidgetId, wnd: &Window,
+) {
+  let Some(label) = select.read().options.get(idx).cloned() else { return };
+  if select.read().selected != Some(idx) {
+    select.write().selected = Some(idx);
+    wnd.bubble_custom_event(wid, SelectChanged { index: idx, label });
+  }
+}
+
+fn show_options(
+  select: Box<dyn StateWriter<Value = Select>>, wid: WidgetId, target: TrackId, wnd: Sc<Window>,
+) {
+  let height = wnd
+    .widget_size(wid)
+    .map_or(0., |size| size.height);
+  let overlay = Overlay::new(
+    move || options_list(select.clone_boxed_writer(), wid),
+    OverlayStyle {
+      auto_close_policy: AutoClosePolicy::TAP_OUTSIDE | AutoClosePolicy::ESC,
+      mask: None,
+    },
+  );
+  let place = move |w: Widget<'static>| {
+    FatObj::new(w)
+      .global_anchor_x(GlobalAnchorX::left_align_to(target.clone(), 0.))
+      .global_anchor_y(GlobalAnchorY::top_align_to(target.clone(), height))
+      .into_widget()
+  };
+  overlay.show_map(place, wnd);
+}
+
+/// Choose the option at `idx` in the list, and close the list.
+fn choose_option(
+  select: &dyn StateWriter<Value = Select>, idx: usize, wid: WidgetId, e: &CommonEvent,
+) {
+  select_option(select, idx, wid, &e.window());
+  if let Some(overlay) = Overlay::of(e) {
+    overlay.close();
+  }
+}
+
+fn options_list(select: Box<dyn StateWriter<Value = Select>>, wid: WidgetId) -> Widget<'static> {
+  let labels = select.read().options.clone();
+  let count = labels.len();
+  let active = Stateful::new(select.read().selected);
+  let option_active = active.clone_watcher();
+  let by_key = select.clone_boxed_writer();
+  let by_chars = select.clone_boxed_writer();
+
+  fn_widget! {
+    let mut typeahead = Typeahead::default();
+    @Column {
+      class: SELECT_OPTIONS,
+      auto_focus: true,
+      on_key_down: move |e| {
+        let cur = *$active;
+        match e.key() {
+          VirtualKey::Named(NamedKey::ArrowDown) if count > 0 => {
+            *$active.write() = Some(cur.map_or(0, |idx| (idx + 1).min(count - 1)));
+          }
+          VirtualKey::Named(NamedKey::ArrowUp) if count > 0 => {
+            *$active.write() = Some(cur.map_or(0, |idx| idx.saturating_sub(1)));
+          }
+          VirtualKey::Named(NamedKey::Enter) => {
+            if let Some(idx) = cur {
+              choose_option(&*by_key, idx, wid, e);
+            }
+          }
+          _ => {}
+        }
+      },
+      on_chars: move |e| {
+        let query = typeahead.push(&e.chars);
+        let found = by_chars.read().search(query, *$active);
+        if found.is_some() {
+          *$active.write() = found;
+        }
+      },
+      @ {
+        labels.into_iter().enumerate().map(move |(idx, label)| {
+          let active = option_active.clone_watcher();
+          let select = select.clone_boxed_writer();
+          fn_widget! {
+            @Text {
+              class: pipe! {
+                if *$active == Some(idx) { SELECT_OPTION_ACTIVE } else { SELECT_OPTION }
+              },
+              text: label,
+              on_tap: move |e| choose_option(&*select, idx, wid, e),
+            }
+          }
+        })
+      }
+    }
+  }
+  .into_widget()
+}
+
+impl Compose for Select {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    let by_tap = this.clone_boxed_writer();
+    let by_key = this.clone_boxed_writer();
+    let by_chars = this.clone_boxed_writer();
+    fn_widget! {
+      let mut typeahead = Typeahead::default();
+      let mut field = @FatObj { class: SELECT, tab_index: 0_i16 };
+      @ $field {
+        on_tap: move |e| {
+          let wid = e.current_target();
+          show_options(by_tap.clone_boxed_writer(), wid, $field.track_id(), e.window());
+        },
+        on_key_down: move |e| {
+          let open = matches!(
+            e.key(),
+            VirtualKey::Named(NamedKey::Enter | NamedKey::Space | NamedKey::ArrowDown)
+          );
+          if open {
+            let wid = e.current_target();
+            show_options(by_key.clone_boxed_writer(), wid, $field.track_id(), e.window());
+          }
+        },
+        on_chars: move |e| {
+          let query = typeahead.push(&e.chars);
+          let selected = by_chars.read().selected;
+          let found = by_chars.read().search(query, selected);
+          if let Some(idx) = found {
+            select_option(&*by_chars, idx, e.current_target(), &e.window());
+          }
+        },
+        @Text { text: pipe!($this.label()) }
+      }
+    }
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+
+  fn fruits() -> Select {
+    Select {
+      options: vec!["Apple".into(), "Banana".into(), "Blueberry".into(), "Cherry".into()],
+      selected: None,
+      placeholder: "".into(),
+    }
+  }
+
+  #[test]
+  fn search_options() {
+    let select = fruits();
+    assert_eq!(select.search("b", None), Some(1));
+    // The same character cycles the options.
+    assert_eq!(select.search("b", Some(1)), Some(2));
+    assert_eq!(select.search("b", Some(2)), Some(1));
+    assert_eq!(select.search("BL", Some(1)), Some(2));
+    assert_eq!(select.search("ch", Some(3)), Some(3));
+    assert_eq!(select.search("x", None), None);
+  }
+
+  #[test]
+  fn choose_option_by_keyboard() {
+    reset_test_env!();
+
+    let (changes, w_changes) = split_value(vec![]);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let mut select = fruits();
+        select.selected = Some(0);
+        let select = FatObj::new(Stateful::new(select)).auto_focus(true);
+        @ $select {
+          on_custom_concrete_event: move |e: &mut SelectChangedEvent| {
+            $w_changes.write().push(e.data().index);
+          },
+        }
+      },
+      Size::new(200., 200.),
+    );
+    wnd.draw_frame();
+    let key = |wnd: &mut TestWindow, key: NamedKey, code: KeyCode| {
+      UiEvent::key_press(VirtualKey::Named(key), PhysicalKey::Code(code))
+        .into_iter()
+        .for_each(|e| wnd.inject_event(e));
+      wnd.draw_frame();
+    };
+
+    // Type to select without opening the list.
+    wnd.inject_event(UiEvent::Chars("c".into()));
+    wnd.draw_frame();
+    assert_eq!(*changes.read(), [3]);
+
+    // Open the list, move up two options and choose it.
+    key(&mut wnd, NamedKey::Enter, KeyCode::Enter);
+    key(&mut wnd, NamedKey::ArrowUp, KeyCode::ArrowUp);
+    key(&mut wnd, NamedKey::ArrowUp, KeyCode::ArrowUp);
+    key(&mut wnd, NamedKey::Enter, KeyCode::Enter);
+    wnd.draw_frame();
+    assert_eq!(*changes.read(), [3, 1]);
+  }
+}
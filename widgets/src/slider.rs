@@ -60,6 +60,17 @@ impl Slider {
     self.value = (self.min + v * (self.max - self.min)).clamp(self.min, self.max);
   }
 
+  /// Move the value by a step, the step is a division if the slider is
+  /// divided, otherwise 5% of the range.
+  fn step(&mut self, forward: bool) {
+    let step = match self.divisions {
+      Some(divisions) if divisions > 0 => 1. / divisions as f32,
+      _ => 0.05,
+    };
+    let ratio = self.ratio();
+    self.set_to(if forward { ratio + step } else { ratio - step });
+  }
+
   fn ratio(&self) -> f32 {
     if self.max == self.min {
       return 1.;
@@ -122,6 +133,17 @@ impl Compose for Slider {
             $this.write().set_to(e.position().x / width);
           },
           on_disposed: move |_| u.unsubscribe(),
+          on_accessibility_action: move |e| match e.action {
+            AccessibilityAction::Increment => {
+              $this.write().step(true);
+              e.stop_propagation();
+            }
+            AccessibilityAction::Decrement => {
+              $this.write().step(false);
+              e.stop_propagation();
+            }
+            _ => {}
+          },
           @Expanded {
             flex: pipe!($this.ratio()),
             @Void { class: SLIDER_ACTIVE_TRACK }
@@ -0,0 +1,294 @@
+use std::{
+  cell::RefCell,
+  collections::{HashMap, hash_map::Entry},
+  rc::Rc,
+};
+
+use ribir_core::{prelude::*, window::WindowId};
+
+use crate::prelude::*;
+
+class_names! {
+  #[doc = "Class name for the surface of the snackbar"]
+  SNACKBAR,
+  #[doc = "Class name for the action button of the snackbar"]
+  SNACKBAR_ACTION,
+}
+
+/// The horizontal distance a touch drags a snackbar to dismiss it.
+const SWIPE_DISMISS_DISTANCE: f32 = 80.;
+
+/// The service shows brief messages about the processes of the application at
+/// the bottom of the window.
+///
+/// The messages of a window are queued in the overlay, the first
+/// `max_visible` of them show and the others wait. A message is dismissed
+/// after the `duration`, when its action is tapped, or when it's swiped away by
+/// a touch. Both are configured by the [`SnackbarStyle`] of the theme.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @FilledButton {
+///     on_tap: move |e| {
+///       Snackbar::show(
+///         e.window(),
+///         "The conversation is archived.",
+///         Some(SnackbarAction::new("Undo", || println!("undo"))),
+///       );
+///     },
+///     @{ "Archive" }
+///   }
+/// };
+/// ```
+pub struct Snackbar;
+
+/// The action button of a snackbar message.
+pub struct SnackbarAction {
+  label: CowArc<str>,
+  on_action: Box<dyn FnMut()>,
+}
+
+/// How the snackbars of a window show.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SnackbarStyle {
+  /// How long a message shows before it's dismissed.
+  pub duration: Duration,
+  /// The max count of the messages showing at the same time, the others wait
+  /// in the queue.
+  pub max_visible: usize,
+}
+
+/// The handle of a message queued by [`Snackbar::show`], to dismiss it
+/// earlier.
+#[derive(Clone, Debug)]
+pub struct SnackbarHandle {
+  wnd_id: WindowId,
+  id: usize,
+}
+
+struct SnackbarItem {
+  id: usize,
+  message: CowArc<str>,
+  action: RefCell<Option<SnackbarAction>>,
+}
+
+/// The messages of a window and the overlay showing them.
+struct SnackbarQueue {
+  overlay: Overlay,
+  items: Stateful<Vec<Rc<SnackbarItem>>>,
+  next_id: usize,
+}
+
+thread_local! {
+  static QUEUES: RefCell<HashMap<WindowId, SnackbarQueue>> = RefCell::default();
+}
+
+impl Snackbar {
+  /// Queue the `message` with an optional `action` to show in the window.
+  pub fn show(
+    wnd: Sc<Window>, message: impl Into<CowArc<str>>, action: Option<SnackbarAction>,
+  ) -> SnackbarHandle {
+    let wnd_id = wnd.id();
+    let (id, items, overlay) = QUEUES.with_borrow_mut(|queues| {
+      let queue = match queues.entry(wnd_id) {
+        Entry::Occupied(e) => e.into_mut(),
+        Entry::Vacant(e) => e.insert(SnackbarQueue::new(wnd_id)),
+      };
+      queue.next_id += 1;
+      (queue.next_id, queue.items.clone_writer(), queue.overlay.clone())
+    });
+
+    let item = SnackbarItem { id, message: message.into(), action: RefCell::new(action) };
+    items.write().push(Rc::new(item));
+    overlay.show(wnd);
+    SnackbarHandle { wnd_id, id }
+  }
+
+  /// The count of the messages of the window, including the waiting ones.
+  pub fn count(wnd: &Window) -> usize {
+    QUEUES.with_borrow(|queues| {
+      queues
+        .get(&wnd.id())
+        .map_or(0, |q| q.items.read().len())
+    })
+  }
+}
+
+impl SnackbarAction {
+  /// Create an action button with the `label`, the `on_action` is called when
+  /// it's tapped, then the message is dismissed.
+  pub fn new(label: impl Into<CowArc<str>>, on_action: impl FnMut() + 'static) -> Self {
+    Self { label: label.into(), on_action: Box::new(on_action) }
+  }
+}
+
+impl SnackbarHandle {
+  /// Dismiss the message, whether it's showing or waiting.
+  pub fn dismiss(&self) { dismiss(self.wnd_id, self.id) }
+
+  /// Whether the message is showing or waiting.
+  pub fn is_queued(&self) -> bool {
+    QUEUES.with_borrow(|queues| {
+      queues
+        .get(&self.wnd_id)
+        .is_some_and(|q| q.items.read().iter().any(|i| i.id == self.id))
+    })
+  }
+}
+
+impl CustomStyle for SnackbarStyle {
+  fn default_style(_: &impl AsRef<ProviderCtx>) -> Self {
+    SnackbarStyle { duration: Duration::from_secs(4), max_visible: 1 }
+  }
+}
+
+impl SnackbarQueue {
+  fn new(wnd_id: WindowId) -> Self {
+    let items = Stateful::new(vec![]);
+    let watcher = items.clone_watcher();
+    let overlay = Overlay::new(
+      move || snackbars(watcher.clone_watcher(), wnd_id),
+      OverlayStyle { auto_close_policy: AutoClosePolicy::NOT_AUTO_CLOSE, mask: None },
+    );
+    Self { overlay, items, next_id: 0 }
+  }
+}
+
+fn dismiss(wnd_id: WindowId, id: usize) {
+  let items = QUEUES.with_borrow(|queues| {
+    queues
+      .get(&wnd_id)
+      .map(|q| q.items.clone_writer())
+  });
+  let Some(items) = items else { return };
+  {
+    let mut items = items.write();
+    match items.iter().position(|i| i.id == id) {
+      Some(idx) => {
+        items.remove(idx);
+      }
+      None => {
+        items.forget_modifies();
+      }
+    }
+  }
+
+  if items.read().is_empty() {
+    // The queue is created again by the next message.
+    let queue = QUEUES.with_borrow_mut(|queues| queues.remove(&wnd_id));
+    if let Some(queue) = queue {
+      queue.overlay.close();
+    }
+  }
+}
+
+fn snackbars(
+  items: impl StateWatcher<Value = Vec<Rc<SnackbarItem>>>, wnd_id: WindowId,
+) -> Widget<'static> {
+  fn_widget! {
+    let SnackbarStyle { duration, max_visible } = SnackbarStyle::of(BuildCtx::get());
+    @Column {
+      h_align: HAlign::Center,
+      v_align: VAlign::Bottom,
+      align_items: Align::Center,
+      item_gap: 8.,
+      margin: EdgeInsets::all(16.),
+      @ {
+        pipe!($items.iter().take(max_visible).cloned().collect::<Vec<_>>())
+          .map(move |items| {
+            items
+              .into_iter()
+              .map(move |item| Keyed::new(item.id, snackbar(item, wnd_id, duration)))
+          })
+      }
+    }
+  }
+  .into_widget()
+}
+
+fn snackbar(item: Rc<SnackbarItem>, wnd_id: WindowId, duration: Duration) -> Widget<'static> {
+  let id = item.id;
+  let message = item.message.clone();
+  let label = item
+    .action
+    .borrow()
+    .as_ref()
+    .map(|a| a.label.clone());
+  // The timer starts when the message shows, the keyed item is not built again
+  // when the messages before it are dismissed.
+  let timer =
+    observable::timer((), duration, AppCtx::scheduler()).subscribe(move |_| dismiss(wnd_id, id));
+
+  fn_widget! {
+    let offset = Stateful::new(0f32);
+    @Row {
+      class: SNACKBAR,
+      align_items: Align::Center,
+      transform: pipe!(Transform::translation(*$offset, 0.)),
+      on_disposed: move |_| timer.unsubscribe(),
+      on_pan: move |e| {
+        if e.pointer.is_mouse() {
+          return;
+        }
+        if e.phase != GesturePhase::End {
+          *$offset.write() = e.translation.x;
+        } else if e.translation.x.abs() > SWIPE_DISMISS_DISTANCE {
+          dismiss(wnd_id, id);
+        } else {
+          *$offset.write() = 0.;
+        }
+      },
+      @Text { text: message }
+      @ {
+        label.map(|label| @TextButton {
+          class: SNACKBAR_ACTION,
+          on_tap: move |_| {
+            if let Some(action) = item.action.borrow_mut().as_mut() {
+              (action.on_action)();
+            }
+            dismiss(wnd_id, id);
+          },
+          @ { label }
+        })
+      }
+    }
+  }
+  .into_widget()
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+
+  #[test]
+  fn queue_and_dismiss() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(fn_widget! { @Void {} }, Size::new(400., 200.));
+    wnd.draw_frame();
+
+    let first = Snackbar::show(wnd.0.clone(), "first", None);
+    let second = Snackbar::show(wnd.0.clone(), "second", None);
+    wnd.draw_frame();
+    assert_eq!(Snackbar::count(&wnd), 2);
+    assert!(first.is_queued());
+
+    first.dismiss();
+    wnd.draw_frame();
+    assert!(!first.is_queued());
+    assert!(second.is_queued());
+    assert_eq!(Snackbar::count(&wnd), 1);
+
+    second.dismiss();
+    wnd.draw_frame();
+    assert_eq!(Snackbar::count(&wnd), 0);
+    // Dismissing a dismissed message does nothing.
+    second.dismiss();
+  }
+}
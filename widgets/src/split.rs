@@ -0,0 +1,230 @@
+use ribir_core::prelude::*;
+
+use crate::prelude::*;
+
+class_names! {
+  #[doc = "Class name for the divider of the horizontal split"]
+  H_SPLIT_DIVIDER,
+  #[doc = "Class name for the divider of the vertical split"]
+  V_SPLIT_DIVIDER,
+}
+
+/// A widget splits its space into two panes by a divider, dragging the divider
+/// resizes the panes and double-clicking it resets the `ratio` to the
+/// `default_ratio`.
+///
+/// It requires exactly two children as the panes. Use [`HSplit`] to place them
+/// side by side, or [`VSplit`] to stack them.
+///
+/// The `ratio` is the share of the start pane in the space of the panes, watch
+/// it to keep the split, for example, write it to a `PersistentState` to
+/// restore it on the next launch.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let ratio = Stateful::new(0.3);
+/// let _w = fn_widget! {
+///   let split = @HSplit { ratio: *$ratio, min_start: 120., min_end: 240. };
+///   watch!($split.ratio).subscribe(move |v| *$ratio.write() = v);
+///   @ $split {
+///     @Text { text: "Sidebar" }
+///     @Text { text: "Content" }
+///   }
+/// };
+/// ```
+#[derive(Declare, Clone, PartialEq)]
+pub struct Split {
+  /// Place the panes side by side or stack them.
+  #[declare(default)]
+  pub direction: Direction,
+  /// The share of the start pane in the space of the panes, from 0 to 1.
+  #[declare(default = 0.5)]
+  pub ratio: f32,
+  /// The ratio the split resets to when the divider is double-clicked.
+  #[declare(default = 0.5)]
+  pub default_ratio: f32,
+  /// The minimum size of the start pane along the main axis.
+  #[declare(default)]
+  pub min_start: f32,
+  /// The maximum size of the start pane along the main axis.
+  #[declare(default = f32::INFINITY)]
+  pub max_start: f32,
+  /// The minimum size of the end pane along the main axis.
+  #[declare(default)]
+  pub min_end: f32,
+  /// The maximum size of the end pane along the main axis.
+  #[declare(default = f32::INFINITY)]
+  pub max_end: f32,
+}
+
+/// A type help to declare the split that places the panes side by side.
+pub struct HSplit;
+
+/// A type help to declare the split that stacks the panes.
+pub struct VSplit;
+
+impl Declare for HSplit {
+  type Builder = FatObj<SplitDeclarer>;
+  fn declarer() -> Self::Builder { Split::declarer().direction(Direction::Horizontal) }
+}
+
+impl Declare for VSplit {
+  type Builder = FatObj<SplitDeclarer>;
+  fn declarer() -> Self::Builder { Split::declarer().direction(Direction::Vertical) }
+}
+
+impl Split {
+  /// Resize the start pane to the `size` in the `space` of the panes, the size
+  /// is constrained by the minimum and the maximum sizes of both panes.
+  pub fn resize_start(&mut self, size: f32, space: f32) {
+    if space <= 0. {
+      return;
+    }
+    let min = self.min_start.max(space - self.max_end);
+    let max = self.max_start.min(space - self.min_end);
+    // The minimum sizes win if the constraints conflict.
+    let size = size.min(max).max(min);
+    self.ratio = (size / space).clamp(0., 1.);
+  }
+
+  /// The space of the panes, the `total` size of the split minus the size of
+  /// the divider.
+  fn space(&self, total: Size, divider: Size) -> f32 {
+    self.main_axis(total.to_vector()) - self.main_axis(divider.to_vector())
+  }
+
+  fn main_axis(&self, v: Vector) -> f32 {
+    match self.direction {
+      Direction::Horizontal => v.x,
+      Direction::Vertical => v.y,
+    }
+  }
+}
+
+impl<'c> ComposeChild<'c> for Split {
+  type Child = Vec<Widget<'c>>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    assert_eq!(child.len(), 2, "The split requires exactly two panes.");
+    let mut panes = child.into_iter();
+    let (start, end) = (panes.next().unwrap(), panes.next().unwrap());
+    let direction = this.read().direction;
+    let (class, cursor) = match direction {
+      Direction::Horizontal => (H_SPLIT_DIVIDER, CursorIcon::ColResize),
+      Direction::Vertical => (V_SPLIT_DIVIDER, CursorIcon::RowResize),
+    };
+
+    fn_widget! {
+      let flex = @Flex { direction, align_items: Align::Stretch };
+      let mut divider = @Void { class, cursor };
+      // The grab of the pointer, the position it pressed and the size of the
+      // start pane at that time.
+      let drag = Stateful::new(None);
+
+      @ $flex {
+        @Expanded {
+          flex: pipe!($this.ratio),
+          @ { start }
+        }
+        @ $divider {
+          on_pointer_down: move |e| {
+            if let Some(handle) = GrabPointer::grab(e.current_target(), &e.window()) {
+              let split = $this;
+              let space = split.space($flex.layout_size(), $divider.layout_size());
+              let pos = split.main_axis(e.global_pos().to_vector());
+              *$drag.write() = Some((handle, pos, split.ratio * space));
+            }
+          },
+          on_pointer_move: move |e| if let Some((_, from, size)) = $drag.as_ref() {
+            let mut split = $this.write();
+            let space = split.space($flex.layout_size(), $divider.layout_size());
+            let pos = split.main_axis(e.global_pos().to_vector());
+            split.resize_start(size + pos - from, space);
+          },
+          on_pointer_up: move |_| {
+            $drag.write().take();
+          },
+          on_double_tap: move |_| {
+            let mut split = $this.write();
+            let space = split.space($flex.layout_size(), $divider.layout_size());
+            let size = split.default_ratio * space;
+            split.resize_start(size, space);
+          },
+        }
+        @Expanded {
+          flex: pipe!(1. - $this.ratio),
+          @ { end }
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+  use smallvec::smallvec;
+
+  use super::*;
+
+  #[test]
+  fn drag_and_reset() {
+    reset_test_env!();
+
+    let (start, w_start) = split_value(0.);
+    let (end, w_end) = split_value(0.);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let mut classes = Classes::default();
+        classes.insert(H_SPLIT_DIVIDER, style_class! { clamp: BoxClamp::fixed_width(10.) });
+        @Providers {
+          providers: smallvec![Provider::new(classes)],
+          @HSplit {
+            ratio: 0.25,
+            min_end: 50.,
+            @MockBox {
+              size: Size::zero(),
+              on_performed_layout: move |e| {
+                *$w_start.write() = e.box_size().unwrap().width;
+              },
+            }
+            @MockBox {
+              size: Size::zero(),
+              on_performed_layout: move |e| {
+                *$w_end.write() = e.box_size().unwrap().width;
+              },
+            }
+          }
+        }
+      },
+      Size::new(210., 100.),
+    );
+    wnd.draw_frame();
+    assert_eq!((*start.read(), *end.read()), (50., 150.));
+
+    wnd.process_input(TestInput::CursorMove(Point::new(55., 50.)));
+    wnd.process_input(TestInput::MousePress(MouseButton::Left));
+    wnd.draw_frame();
+    wnd.process_input(TestInput::CursorMove(Point::new(95., 50.)));
+    wnd.draw_frame();
+    assert_eq!((*start.read(), *end.read()), (90., 110.));
+
+    // The end pane keeps its minimum size.
+    wnd.process_input(TestInput::CursorMove(Point::new(205., 50.)));
+    wnd.process_input(TestInput::MouseRelease(MouseButton::Left));
+    wnd.draw_frame();
+    assert_eq!((*start.read(), *end.read()), (150., 50.));
+
+    wnd.process_input(TestInput::CursorMove(Point::new(155., 50.)));
+    wnd.process_input(TestInput::MousePress(MouseButton::Left));
+    wnd.process_input(TestInput::MouseRelease(MouseButton::Left));
+    wnd.process_input(TestInput::MousePress(MouseButton::Left));
+    wnd.process_input(TestInput::MouseRelease(MouseButton::Left));
+    wnd.draw_frame();
+    assert_eq!((*start.read(), *end.read()), (100., 100.));
+  }
+}
@@ -0,0 +1,149 @@
+use ribir_core::prelude::*;
+
+use crate::prelude::{PositionChild, Stack, icon_with_label};
+
+/// The `Switch` toggles the state of a single setting on or off, the thumb of
+/// it slides to the other side of the track when the state changes.
+///
+/// Like the `Checkbox`, it supports a label before or after it, and it's
+/// toggled by tapping it or pressing the `Space` key when it's focused.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// struct Settings {
+///   dark_mode: bool,
+/// }
+///
+/// let settings = Stateful::new(Settings { dark_mode: false });
+/// let _switch = fn_widget! {
+///   @Switch {
+///     checked: bind!(&mut settings.dark_mode),
+///     @Leading::new("Dark mode")
+///   }
+/// };
+/// ```
+#[derive(Clone, Copy, Declare, PartialEq, Eq)]
+pub struct Switch {
+  #[declare(default)]
+  pub checked: bool,
+}
+
+/// The style of the [`Switch`].
+#[derive(Clone)]
+pub struct SwitchStyle {
+  pub track_size: Size,
+  /// The diameter of the thumb.
+  pub thumb_size: f32,
+  pub checked_track: Color,
+  pub unchecked_track: Color,
+  pub checked_thumb: Color,
+  pub unchecked_thumb: Color,
+  /// The transition of the thumb sliding to the other side.
+  pub transition: TransitionIdent,
+}
+
+impl CustomStyle for SwitchStyle {
+  fn default_style(ctx: &impl AsRef<ProviderCtx>) -> Self {
+    let palette = Palette::of(ctx);
+    SwitchStyle {
+      track_size: Size::new(52., 32.),
+      thumb_size: 24.,
+      checked_track: palette.primary(),
+      unchecked_track: palette.surface_container_highest(),
+      checked_thumb: palette.on_primary(),
+      unchecked_thumb: palette.outline(),
+      transition: transitions::EASE_OUT,
+    }
+  }
+}
+
+impl Switch {
+  pub fn toggle(&mut self) { self.checked = !self.checked; }
+}
+
+impl SwitchStyle {
+  fn thumb_offset(&self, checked: bool) -> f32 {
+    let gap = (self.track_size.height - self.thumb_size) / 2.;
+    if checked { self.track_size.width - self.thumb_size - gap } else { gap }
+  }
+}
+
+impl ComposeChild<'static> for Switch {
+  type Child = Option<PositionChild<TextInit>>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'static> {
+    rdl! {
+      let style = SwitchStyle::of(BuildCtx::get());
+      let SwitchStyle {
+        track_size, thumb_size, checked_track, unchecked_track,
+        checked_thumb, unchecked_thumb, transition,
+      } = style.clone();
+      let mut thumb = @Container {
+        size: Size::splat(thumb_size),
+        radius: Radius::all(thumb_size / 2.),
+        v_align: VAlign::Center,
+        background: pipe!(if $this.checked { checked_thumb } else { unchecked_thumb }),
+        anchor: pipe!(Anchor::left(style.thumb_offset($this.checked))),
+      };
+      part_writer!(&mut thumb.anchor).transition(transition.of(BuildCtx::get()));
+
+      let track = @Stack {
+        clamp: BoxClamp::fixed_size(track_size),
+        radius: Radius::all(track_size.height / 2.),
+        background: pipe!(if $this.checked { checked_track } else { unchecked_track }),
+        @ { thumb }
+      };
+      @FatObj {
+        cursor: CursorIcon::Pointer,
+        on_tap: move |_| $this.write().toggle(),
+        on_key_up: move |k| if *k.key() == VirtualKey::Named(NamedKey::Space) {
+          $this.write().toggle()
+        },
+        @ icon_with_label(track.into_widget(), child)
+      }
+    }
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+
+  #[test]
+  fn toggle_by_tap_and_key() {
+    reset_test_env!();
+
+    struct Form {
+      checked: bool,
+    }
+
+    let form = Stateful::new(Form { checked: false });
+    let c_form = form.clone_writer();
+    let mut wnd = TestWindow::new(fn_widget! {
+      @Switch {
+        auto_focus: true,
+        checked: bind!(&mut c_form.checked),
+      }
+    });
+    wnd.draw_frame();
+
+    UiEvent::tap_at(Point::new(10., 10.))
+      .into_iter()
+      .for_each(|e| wnd.inject_event(e));
+    wnd.draw_frame();
+    assert!(form.read().checked);
+
+    UiEvent::key_press(VirtualKey::Named(NamedKey::Space), PhysicalKey::Code(KeyCode::Space))
+      .into_iter()
+      .for_each(|e| wnd.inject_event(e));
+    wnd.draw_frame();
+    assert!(!form.read().checked);
+  }
+}
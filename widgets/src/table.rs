@@ -0,0 +1,489 @@
+use std::{cmp::Ordering, rc::Rc};
+
+use ribir_core::prelude::*;
+
+use crate::prelude::*;
+
+class_names! {
+  #[doc = "Class name for the table"]
+  TABLE,
+  #[doc = "Class name for the header row of the table"]
+  TABLE_HEADER,
+  #[doc = "Class name for the cell of the header row"]
+  TABLE_HEADER_CELL,
+  #[doc = "Class name for the row of the table body"]
+  TABLE_ROW,
+  #[doc = "Class name for the cell of the table body"]
+  TABLE_CELL,
+  #[doc = "Class name for the sort indicator of the column sorted in ascending order"]
+  TABLE_SORT_ASCENDING,
+  #[doc = "Class name for the sort indicator of the column sorted in descending order"]
+  TABLE_SORT_DESCENDING,
+}
+
+/// A table shows the `rows` in the `columns`, a row of the body is built by
+/// the cell builders of the columns.
+///
+/// The header row stays at the top while the body scrolls. Tapping the header
+/// of a sortable column sorts the rows by it, and tapping it again reverses the
+/// order.
+///
+/// All the rows share the widths of the columns, the space of the table is
+/// distributed by the [`ColumnWidth`] of the columns. The classes of the header
+/// and the rows should not add horizontal space around their cells to keep the
+/// columns aligned.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// struct Fruit {
+///   name: &'static str,
+///   price: f32,
+/// }
+///
+/// fn name(f: &Fruit) -> Widget<'static> {
+///   let name = f.name;
+///   text! { text: name }.into_widget()
+/// }
+///
+/// fn price(f: &Fruit) -> Widget<'static> {
+///   let price = f.price.to_string();
+///   text! { text: price }.into_widget()
+/// }
+///
+/// let _w = fn_widget! {
+///   @Table {
+///     columns: vec![
+///       TableColumn::new("Name", name).sort_by(|a, b| a.name.cmp(b.name)),
+///       TableColumn::new("Price", price)
+///         .with_width(ColumnWidth::fixed(80.))
+///         .sort_by(|a, b| a.price.total_cmp(&b.price)),
+///     ],
+///     rows: vec![
+///       Fruit { name: "Apple", price: 1.2 },
+///       Fruit { name: "Banana", price: 0.5 },
+///     ],
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct Table<R: 'static> {
+  pub columns: Vec<TableColumn<R>>,
+  pub rows: Vec<R>,
+  /// The column the rows are sorted by, the rows are in their original order
+  /// if it's `None`.
+  #[declare(default)]
+  pub sort: Option<TableSort>,
+}
+
+/// A column of the [`Table`].
+pub struct TableColumn<R> {
+  pub title: CowArc<str>,
+  pub width: ColumnWidth,
+  cell: Rc<dyn Fn(&R) -> Widget<'static>>,
+  compare: Option<RowCompare<R>>,
+}
+
+type RowCompare<R> = Rc<dyn Fn(&R, &R) -> Ordering>;
+
+/// How a column of the [`Table`] is sized.
+///
+/// A column is at least `min` width, and the remaining space of the table is
+/// distributed to the columns by their `flex`, but a column never exceeds its
+/// `max` width.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColumnWidth {
+  pub min: f32,
+  pub max: f32,
+  pub flex: f32,
+}
+
+/// The sorting of the [`Table`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableSort {
+  /// The index of the column the rows are sorted by.
+  pub column: usize,
+  pub order: SortOrder,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+  Ascending,
+  Descending,
+}
+
+impl<R> TableColumn<R> {
+  /// Create a column with the `title`, and the `cell` builds its cell of a
+  /// row. The column shares the remaining space of the table by default.
+  pub fn new(
+    title: impl Into<CowArc<str>>, cell: impl Fn(&R) -> Widget<'static> + 'static,
+  ) -> Self {
+    Self { title: title.into(), width: ColumnWidth::flex(1.), cell: Rc::new(cell), compare: None }
+  }
+
+  pub fn with_width(mut self, width: ColumnWidth) -> Self {
+    self.width = width;
+    self
+  }
+
+  /// Make the column sortable, the rows are compared by `compare` when the
+  /// table is sorted by the column.
+  pub fn sort_by(mut self, compare: impl Fn(&R, &R) -> Ordering + 'static) -> Self {
+    self.compare = Some(Rc::new(compare));
+    self
+  }
+
+  pub fn is_sortable(&self) -> bool { self.compare.is_some() }
+}
+
+impl<R> Clone for TableColumn<R> {
+  fn clone(&self) -> Self {
+    Self {
+      title: self.title.clone(),
+      width: self.width,
+      cell: self.cell.clone(),
+      compare: self.compare.clone(),
+    }
+  }
+}
+
+impl ColumnWidth {
+  /// A column always in the `width`.
+  pub const fn fixed(width: f32) -> Self { Self { min: width, max: width, flex: 0. } }
+
+  /// A column shares the remaining space of the table by the `flex`.
+  pub const fn flex(flex: f32) -> Self { Self { min: 0., max: f32::INFINITY, flex } }
+
+  pub const fn with_min(mut self, min: f32) -> Self {
+    self.min = min;
+    self
+  }
+
+  pub const fn with_max(mut self, max: f32) -> Self {
+    self.max = max;
+    self
+  }
+}
+
+impl<R> Table<R> {
+  /// Sort the rows by the `column`, reverse the order if the table is already
+  /// sorted by it. Do nothing if the column is not sortable.
+  pub fn toggle_sort(&mut self, column: usize) {
+    if !self
+      .columns
+      .get(column)
+      .is_some_and(TableColumn::is_sortable)
+    {
+      return;
+    }
+    let order = match self.sort {
+      Some(TableSort { column: c, order: SortOrder::Ascending }) if c == column => {
+        SortOrder::Descending
+      }
+      _ => SortOrder::Ascending,
+    };
+    self.sort = Some(TableSort { column, order });
+  }
+
+  /// The indices of the rows in the display order.
+  pub fn sorted_rows(&self) -> Vec<usize> {
+    let mut indices: Vec<_> = (0..self.rows.len()).collect();
+    let Some(TableSort { column, order }) = self.sort else { return indices };
+    if let Some(compare) = self
+      .columns
+      .get(column)
+      .and_then(|c| c.compare.as_ref())
+    {
+      indices.sort_by(|a, b| {
+        let ord = compare(&self.rows[*a], &self.rows[*b]);
+        if order == SortOrder::Ascending { ord } else { ord.reverse() }
+      });
+    }
+    indices
+  }
+
+  fn widths(&self) -> Rc<[ColumnWidth]> { self.columns.iter().map(|c| c.width).collect() }
+}
+
+impl<R: 'static> Compose for Table<R> {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    let this = this.clone_boxed_writer();
+    fn_widget! {
+      let this2 = this.clone_boxed_writer();
+      let body = @ScrollableWidget {
+        scrollable: Scrollable::Y,
+        @Column { @ { pipe!(body(&$this)) } }
+      };
+      let header = pipe!(header(&$this, &*this2)).into_widget();
+      let table = TableLayout.with_child([header, body.into_widget()]);
+      @FatObj { class: TABLE, @ { table } }
+    }
+    .into_widget()
+  }
+}
+
+fn header<R: 'static>(
+  table: &Table<R>, this: &dyn StateWriter<Value = Table<R>>,
+) -> Widget<'static> {
+  let cells = table
+    .columns
+    .iter()
+    .enumerate()
+    .map(|(idx, col)| {
+      let this = this.clone_boxed_writer();
+      let title = col.title.clone();
+      let indicator = table
+        .sort
+        .filter(|s| s.column == idx)
+        .map(|s| match s.order {
+          SortOrder::Ascending => TABLE_SORT_ASCENDING,
+          SortOrder::Descending => TABLE_SORT_DESCENDING,
+        });
+      row! {
+        class: TABLE_HEADER_CELL,
+        align_items: Align::Center,
+        on_tap: move |_| $this.write().toggle_sort(idx),
+        @Text { text: title }
+        @ { indicator.map(|cls| @Void { class: cls }) }
+      }
+      .into_widget()
+    })
+    .collect::<Vec<_>>();
+
+  FatObj::new(TableRow { widths: table.widths() }.with_child(cells))
+    .class(TABLE_HEADER)
+    .into_widget()
+}
+
+fn body<R>(table: &Table<R>) -> Vec<Widget<'static>> {
+  let widths = table.widths();
+  table
+    .sorted_rows()
+    .into_iter()
+    .map(|idx| {
+      let row = &table.rows[idx];
+      let cells = table
+        .columns
+        .iter()
+        .map(|col| {
+          FatObj::new((col.cell)(row))
+            .class(TABLE_CELL)
+            .into_widget()
+        })
+        .collect::<Vec<_>>();
+      FatObj::new(TableRow { widths: widths.clone() }.with_child(cells))
+        .class(TABLE_ROW)
+        .into_widget()
+    })
+    .collect()
+}
+
+/// Calculate the widths of the columns in the `available` width.
+///
+/// Every column starts from its `min`, and the remaining space is distributed
+/// by the `flex` to the columns that don't reach their `max`. The columns are
+/// in their `min` if the `available` width is unbounded.
+fn column_widths(columns: &[ColumnWidth], available: f32) -> Vec<f32> {
+  let mut widths: Vec<_> = columns.iter().map(|c| c.min).collect();
+  if !available.is_finite() {
+    return widths;
+  }
+
+  let mut remaining = available - widths.iter().sum::<f32>();
+  let mut growing: Vec<_> = (0..columns.len())
+    .filter(|i| columns[*i].flex > 0. && columns[*i].max > columns[*i].min)
+    .collect();
+  while remaining > 0. && !growing.is_empty() {
+    let flex: f32 = growing.iter().map(|i| columns[*i].flex).sum();
+    let unit = remaining / flex;
+    // The columns reaching their `max` give back the space beyond it, and it's
+    // distributed to the others in the next round.
+    let (mut used, count) = (0., growing.len());
+    growing.retain(|i| {
+      let c = &columns[*i];
+      let width = (widths[*i] + unit * c.flex).min(c.max);
+      used += width - widths[*i];
+      widths[*i] = width;
+      width < c.max
+    });
+    if growing.len() == count {
+      break;
+    }
+    remaining -= used;
+  }
+  widths
+}
+
+/// The layout of the table, the first child is the header and the second is
+/// the body. The body takes the rest height after the header.
+#[derive(MultiChild)]
+struct TableLayout;
+
+impl Render for TableLayout {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let (ctx, mut children) = ctx.split_children();
+    let (Some(header), Some(body)) = (children.next(), children.next()) else {
+      return clamp.min;
+    };
+    let header_clamp = BoxClamp { min: Size::new(clamp.min.width, 0.), max: clamp.max };
+    let header_size = ctx.perform_child_layout(header, header_clamp);
+    let body_clamp = BoxClamp {
+      min: Size::new(clamp.min.width, (clamp.min.height - header_size.height).max(0.)),
+      max: Size::new(clamp.max.width, (clamp.max.height - header_size.height).max(0.)),
+    };
+    let body_size = ctx.perform_child_layout(body, body_clamp);
+    ctx.update_position(body, Point::new(0., header_size.height));
+
+    let width = header_size.width.max(body_size.width);
+    clamp.clamp(Size::new(width, header_size.height + body_size.height))
+  }
+
+  #[inline]
+  fn only_sized_by_parent(&self) -> bool { false }
+
+  #[inline]
+  fn paint(&self, _: &mut PaintingCtx) {}
+}
+
+/// The row of the table, the cells are laid out in the widths of the columns.
+#[derive(MultiChild)]
+struct TableRow {
+  widths: Rc<[ColumnWidth]>,
+}
+
+impl Render for TableRow {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let widths = column_widths(&self.widths, clamp.max.width);
+    let (ctx, children) = ctx.split_children();
+    let mut x = 0.;
+    let mut height: f32 = 0.;
+    for (c, width) in children.zip(widths) {
+      let cell_clamp = BoxClamp::fixed_width(width).with_max_height(clamp.max.height);
+      let size = ctx.perform_child_layout(c, cell_clamp);
+      ctx.update_position(c, Point::new(x, 0.));
+      x += width;
+      height = height.max(size.height);
+    }
+    clamp.clamp(Size::new(x, height))
+  }
+
+  #[inline]
+  fn only_sized_by_parent(&self) -> bool { false }
+
+  #[inline]
+  fn paint(&self, _: &mut PaintingCtx) {}
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+  use winit::event::{
+    DeviceId, ElementState, MouseButton, MouseScrollDelta, TouchPhase, WindowEvent,
+  };
+
+  use super::*;
+
+  #[test]
+  fn distribute_column_widths() {
+    let columns = [
+      ColumnWidth::fixed(50.),
+      ColumnWidth::flex(1.).with_max(30.),
+      ColumnWidth::flex(1.),
+      ColumnWidth::flex(1.),
+    ];
+    // The space beyond the max of the second column goes to the others.
+    assert_eq!(column_widths(&columns, 200.), [50., 30., 60., 60.]);
+
+    let columns = [ColumnWidth::fixed(50.), ColumnWidth::flex(1.).with_min(20.)];
+    assert_eq!(column_widths(&columns, 100.), [50., 50.]);
+    // Not enough space for the min.
+    assert_eq!(column_widths(&columns, 60.), [50., 20.]);
+    assert_eq!(column_widths(&columns, f32::INFINITY), [50., 20.]);
+  }
+
+  fn table(rows: Vec<i32>) -> impl FnMut() -> Widget<'static> {
+    fn_widget! {
+      let cell = |v: &i32| MockBox { size: Size::new(10., *v as f32) }.into_widget();
+      @Table {
+        columns: vec![
+          TableColumn::new("A", cell).with_width(ColumnWidth::fixed(40.)),
+          TableColumn::new("B", cell).sort_by(|a: &i32, b: &i32| a.cmp(b)),
+        ],
+        rows: rows.clone(),
+      }
+    }
+  }
+
+  #[test]
+  fn sticky_header() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(table(vec![30, 10, 20]), Size::new(100., 50.));
+    wnd.draw_frame();
+
+    let header = wnd.layout_info_by_path(&[0, 0]).unwrap();
+    assert_eq!(header.pos, Point::zero());
+    let header_height = header.size.unwrap().height;
+    // The body takes the rest height of the table.
+    let body = wnd.layout_info_by_path(&[0, 1]).unwrap();
+    assert_eq!(body.pos.y, header_height);
+    assert_eq!(body.size.unwrap().height, 50. - header_height);
+    // The cells of the rows share the widths of the columns.
+    let cell = wnd.layout_info_by_path(&[0, 1, 0, 0, 1]).unwrap();
+    assert_eq!((cell.pos.x, cell.size.unwrap()), (40., Size::new(60., 30.)));
+
+    let device_id = unsafe { DeviceId::dummy() };
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::CursorMoved { device_id, position: (50., 40.).into() });
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::MouseWheel {
+      device_id,
+      delta: MouseScrollDelta::PixelDelta((0., -10.).into()),
+      phase: TouchPhase::Started,
+    });
+    wnd.draw_frame();
+    // Only the body scrolls.
+    assert_eq!(wnd.layout_info_by_path(&[0, 1, 0]).unwrap().pos.y, -10.);
+    assert_eq!(wnd.layout_info_by_path(&[0, 0]).unwrap().pos, Point::zero());
+  }
+
+  #[test]
+  fn sort_by_tapping_header() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(table(vec![30, 10, 20]), Size::new(100., 200.));
+    wnd.draw_frame();
+    let heights = |wnd: &TestWindow| {
+      (0..3)
+        .map(|i| {
+          wnd
+            .layout_info_by_path(&[0, 1, 0, i])
+            .unwrap()
+            .size
+            .unwrap()
+            .height
+        })
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(heights(&wnd), [30., 10., 20.]);
+
+    let tap = |wnd: &mut TestWindow, x: f64| {
+      let device_id = unsafe { DeviceId::dummy() };
+      #[allow(deprecated)]
+      wnd.processes_native_event(WindowEvent::CursorMoved { device_id, position: (x, 1.).into() });
+      wnd.process_mouse_input(device_id, ElementState::Pressed, MouseButton::Left);
+      wnd.process_mouse_input(device_id, ElementState::Released, MouseButton::Left);
+      wnd.draw_frame();
+    };
+    // The first column is not sortable.
+    tap(&mut wnd, 10.);
+    assert_eq!(heights(&wnd), [30., 10., 20.]);
+
+    tap(&mut wnd, 50.);
+    assert_eq!(heights(&wnd), [10., 20., 30.]);
+    tap(&mut wnd, 50.);
+    assert_eq!(heights(&wnd), [30., 20., 10.]);
+  }
+}
@@ -53,8 +53,73 @@ use crate::prelude::*;
 pub struct Tabs {
   #[declare(default = Position::Top)]
   pub pos: Position,
+  /// The index of the current tab, it can be bound to a state by `bind!` to
+  /// switch the tabs programmatically and follow the tabs the user switches.
   #[declare(default)]
   pub cur_idx: usize,
+  /// Decide whether the hidden panes keep their widget subtree and state, see
+  /// [`KeepAlivePolicy`].
+  #[declare(default)]
+  pub keep_alive_policy: KeepAlivePolicy,
+  /// Whether swiping the panes by a finger switches to the adjacent tab.
+  #[declare(default = true)]
+  pub swipeable: bool,
+}
+
+/// The ratio of the pane extent that a swipe moves over to switch the tab.
+const SWIPE_DISTANCE_RATIO: f32 = 0.25;
+/// The velocity in logical pixels per second that a swipe switches the tab by
+/// a fling, no matter how far it moves.
+const SWIPE_FLING_VELOCITY: f32 = 500.;
+
+/// The policy of a container that shows one pane at a time, decides whether
+/// the hidden panes retain their widget subtree and state or are rebuilt when
+/// they are shown again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeepAlivePolicy {
+  /// Every pane is kept alive once it has been shown.
+  Always,
+  /// Keep at most the given count of the most recently shown panes alive, the
+  /// current pane is always alive.
+  Lru(usize),
+  /// Only the current pane is alive, the hidden panes are disposed and rebuilt
+  /// when they are shown again.
+  #[default]
+  Never,
+}
+
+impl KeepAlivePolicy {
+  /// The max count of the alive panes, include the current one.
+  pub fn capacity(self) -> usize {
+    match self {
+      KeepAlivePolicy::Always => usize::MAX,
+      KeepAlivePolicy::Lru(count) => count.max(1),
+      KeepAlivePolicy::Never => 1,
+    }
+  }
+}
+
+/// Track the panes that are alive by a [`KeepAlivePolicy`], the most recently
+/// shown pane first.
+#[derive(Debug, Default, Clone)]
+pub struct AlivePanes {
+  recent: Vec<usize>,
+}
+
+impl AlivePanes {
+  /// Mark the pane at `idx` as shown and release the panes that the `policy`
+  /// not retain anymore.
+  pub fn visit(&mut self, idx: usize, policy: KeepAlivePolicy) {
+    self.recent.retain(|i| *i != idx);
+    self.recent.insert(0, idx);
+    self.recent.truncate(policy.capacity());
+  }
+
+  /// Return if the pane at `idx` is alive.
+  pub fn is_alive(&self, idx: usize) -> bool { self.recent.contains(&idx) }
+
+  /// The count of the alive panes, include the current one.
+  pub fn alive_count(&self) -> usize { self.recent.len() }
 }
 
 #[derive(Clone)]
@@ -156,6 +221,25 @@ impl ComposeDecorator for IndicatorDecorator {
 }
 
 impl Tabs {
+  /// Return the tab that a swipe over the panes switches to, by the
+  /// `translation` and the `velocity` of the swipe along the main axis of the
+  /// panes, which `extent` long. Swiping toward the start switches to the
+  /// next tab.
+  pub fn swipe_target(
+    &self, tab_count: usize, translation: f32, velocity: f32, extent: f32,
+  ) -> Option<usize> {
+    let far = translation.abs() > extent * SWIPE_DISTANCE_RATIO;
+    let fling = velocity.abs() > SWIPE_FLING_VELOCITY && velocity.signum() == translation.signum();
+    if !far && !fling {
+      return None;
+    }
+    if translation < 0. {
+      (self.cur_idx + 1 < tab_count).then_some(self.cur_idx + 1)
+    } else {
+      self.cur_idx.checked_sub(1)
+    }
+  }
+
   fn tab_header(
     headers: Vec<(Option<NamedSvg>, Option<Label>)>, tabs_style: TabsStyle,
     tabs: impl StateWriter<Value = Tabs> + 'static,
@@ -227,6 +311,7 @@ impl ComposeChild<'static> for Tabs {
       headers.push((header.icon, header.text));
       panes.push(pane.0)
     }
+    let tab_count = panes.len();
 
     fn_widget! {
       let tabs_style = TabsStyle::of(BuildCtx::get());
@@ -308,7 +393,14 @@ impl ComposeChild<'static> for Tabs {
           }
         };
 
+      let mut alive = AlivePanes::default();
+      alive.visit($this.cur_idx, $this.keep_alive_policy);
+      let alive = Stateful::new(alive);
+      let u = watch!(($this.cur_idx, $this.keep_alive_policy))
+        .subscribe(move |(idx, policy)| $alive.write().visit(idx, policy));
+
       @TabsDecorator {
+        on_disposed: move |_| u.unsubscribe(),
         @Flex {
           direction: pipe!(match  $this.pos {
             Position::Left | Position::Right => Direction::Horizontal,
@@ -320,7 +412,38 @@ impl ComposeChild<'static> for Tabs {
           },
           @ { header }
           @Expanded {
-            @ { pipe!($this.cur_idx).map(move |idx| panes[idx].gen_widget()) }
+            @Stack {
+              fit: StackFit::Passthrough,
+              on_pan: move |e| {
+                if e.phase != GesturePhase::End || e.pointer.is_mouse() || !$this.swipeable {
+                  return;
+                }
+                let size = e.box_size().unwrap_or_default();
+                let (translation, cross, velocity, extent) = match $this.pos {
+                  Position::Top | Position::Bottom => {
+                    (e.translation.x, e.translation.y, e.velocity.x, size.width)
+                  }
+                  Position::Left | Position::Right => {
+                    (e.translation.y, e.translation.x, e.velocity.y, size.height)
+                  }
+                };
+                if translation.abs() <= cross.abs() {
+                  return;
+                }
+                let target = $this.swipe_target(tab_count, translation, velocity, extent);
+                if let Some(idx) = target {
+                  $this.write().cur_idx = idx;
+                }
+              },
+              @ {
+                panes.into_iter().enumerate().map(move |(idx, pane)| {
+                  distinct_pipe!($alive.is_alive(idx)).map(move |alive| alive.then(|| {
+                    let pane = FatObj::new(pane.gen_widget());
+                    @ $pane { visible: pipe!($this.cur_idx == idx) }
+                  }))
+                })
+              }
+            }
           }
         }
       }
@@ -328,3 +451,94 @@ impl ComposeChild<'static> for Tabs {
     .into_widget()
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+
+  fn count_pane_builds(policy: KeepAlivePolicy, visits: &[usize]) -> usize {
+    reset_test_env!();
+
+    let builds = Stateful::new(0);
+    let tabs = Stateful::new(Tabs {
+      pos: Position::Top,
+      cur_idx: 0,
+      keep_alive_policy: policy,
+      swipeable: true,
+    });
+    let c_builds = builds.clone_writer();
+    let c_tabs = tabs.clone_writer();
+    let mut wnd = TestWindow::new(fn_widget! {
+      let pane = |builds: Stateful<usize>| {
+        TabPane(GenWidget::new(move || {
+          *builds.write() += 1;
+          Void.into_widget()
+        }))
+      };
+      let tabs = c_tabs.clone_writer();
+      @ $tabs {
+        @Tab {
+          @TabItem { @ { Label::new("A") } }
+          @ { pane(c_builds.clone_writer()) }
+        }
+        @Tab {
+          @TabItem { @ { Label::new("B") } }
+          @ { pane(c_builds.clone_writer()) }
+        }
+        @Tab {
+          @TabItem { @ { Label::new("C") } }
+          @ { pane(c_builds.clone_writer()) }
+        }
+      }
+    });
+    wnd.draw_frame();
+    for idx in visits {
+      tabs.write().cur_idx = *idx;
+      wnd.draw_frame();
+    }
+    let count = *builds.read();
+    count
+  }
+
+  #[test]
+  fn keep_alive_policy() {
+    let visits = [1, 2, 0, 1];
+    assert_eq!(count_pane_builds(KeepAlivePolicy::Never, &visits), 5);
+    assert_eq!(count_pane_builds(KeepAlivePolicy::Always, &visits), 3);
+    assert_eq!(count_pane_builds(KeepAlivePolicy::Lru(2), &visits), 5);
+    assert_eq!(count_pane_builds(KeepAlivePolicy::Lru(3), &visits), 3);
+  }
+
+  #[test]
+  fn swipe_target() {
+    let tabs = Tabs {
+      pos: Position::Top,
+      cur_idx: 1,
+      keep_alive_policy: KeepAlivePolicy::Never,
+      swipeable: true,
+    };
+    // Swipe far enough or fling.
+    assert_eq!(tabs.swipe_target(3, -60., 0., 200.), Some(2));
+    assert_eq!(tabs.swipe_target(3, 60., 0., 200.), Some(0));
+    assert_eq!(tabs.swipe_target(3, -20., -800., 200.), Some(2));
+    // Too short and slow.
+    assert_eq!(tabs.swipe_target(3, -20., -100., 200.), None);
+    // No tab beyond the last one.
+    assert_eq!(tabs.swipe_target(2, -60., 0., 200.), None);
+  }
+
+  #[test]
+  fn lru_alive_panes() {
+    let mut alive = AlivePanes::default();
+    alive.visit(0, KeepAlivePolicy::Lru(2));
+    alive.visit(1, KeepAlivePolicy::Lru(2));
+    alive.visit(0, KeepAlivePolicy::Lru(2));
+    alive.visit(2, KeepAlivePolicy::Lru(2));
+    assert!(alive.is_alive(0));
+    assert!(!alive.is_alive(1));
+    assert!(alive.is_alive(2));
+    assert_eq!(alive.alive_count(), 2);
+  }
+}
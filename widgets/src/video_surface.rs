@@ -0,0 +1,143 @@
+use futures::{StreamExt, channel::mpsc, future::abortable};
+use ribir_core::prelude::*;
+
+/// Shows the frames of an [`ExternalTexture`], such as the frames that a video
+/// decoder or a camera pushes from another thread.
+///
+/// The frames are drawn from a texture of the GPU backend kept for the
+/// `texture`, they aren't copied through the image atlas. The widget is sized
+/// by the frame and scales the frame to fill its box, it repaints when a new
+/// frame is pushed, and only lays out again when the size of the frame changes.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let texture = ExternalTexture::new();
+/// let producer = texture.clone();
+/// std::thread::spawn(move || {
+///   // Decode the frames and push them, for example, the pixels of a frame.
+///   let pixels = vec![0; 320 * 240 * 4];
+///   let frame = PixelImage::new(pixels.into(), 320, 240, image::ColorFormat::Rgba8);
+///   producer.push_pixels(frame);
+/// });
+///
+/// let _w = fn_widget! {
+///   @VideoSurface { texture: texture.clone() }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct VideoSurface {
+  pub texture: ExternalTexture,
+}
+
+/// Paints the latest frame of the texture.
+struct SurfaceView(ExternalTexture);
+
+impl Render for SurfaceView {
+  fn perform_layout(&self, clamp: BoxClamp, _: &mut LayoutCtx) -> Size {
+    let size = self.0.size();
+    clamp.clamp(Size::new(size.width as f32, size.height as f32))
+  }
+
+  fn paint(&self, ctx: &mut PaintingCtx) {
+    let size = ctx.box_size().unwrap();
+    ctx
+      .painter()
+      .draw_external_texture(&Rect::from_size(size), self.0.clone());
+  }
+}
+
+impl Compose for VideoSurface {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! {
+      pipe!($this.texture.clone())
+        .distinct_until_changed()
+        .map(surface_of)
+    }
+    .into_widget()
+  }
+}
+
+fn surface_of(texture: ExternalTexture) -> Widget<'static> {
+  // Count the frames to repaint, and track the size to lay out again.
+  let frames = Stateful::new(0usize);
+  let size = Stateful::new(texture.size());
+
+  let (sender, mut receiver) = mpsc::unbounded();
+  let listener = texture.add_frame_listener(move || {
+    let _ = sender.unbounded_send(());
+  });
+  let (frames2, size2, texture2) = (frames.clone_writer(), size.clone_writer(), texture.clone());
+  let (task, handle) = abortable(async move {
+    while receiver.next().await.is_some() {
+      let new_size = texture2.size();
+      if *size2.read() != new_size {
+        *size2.write() = new_size;
+      }
+      *frames2.write() += 1;
+    }
+  });
+  let _ = AppCtx::spawn_local(async move {
+    let _ = task.await;
+  });
+
+  fn_widget! {
+    let view = SurfaceView(texture.clone())
+      .into_widget()
+      .dirty_on(frames.raw_modifies(), DirtyPhase::Paint)
+      .dirty_on(size.raw_modifies(), DirtyPhase::Layout);
+    let view = FatObj::new(view);
+    @ $view {
+      on_disposed: move |_| {
+        texture.remove_frame_listener(listener);
+        handle.abort();
+      }
+    }
+  }
+  .into_widget()
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+
+  fn frame(width: u32, height: u32) -> PixelImage {
+    let data = vec![255; (width * height * 4) as usize];
+    PixelImage::new(data.into(), width, height, image::ColorFormat::Rgba8)
+  }
+
+  fn painted_texture(wnd: &mut TestWindow) -> Option<Rect> {
+    let frame = wnd.draw_and_take_frame();
+    frame.commands.iter().find_map(|cmd| match cmd {
+      PaintCommand::ExternalTexture { bounds, .. } => Some(*bounds),
+      _ => None,
+    })
+  }
+
+  #[test]
+  fn follow_pushed_frames() {
+    reset_test_env!();
+
+    let texture = ExternalTexture::new();
+    let t2 = texture.clone();
+    let mut wnd = TestWindow::new(fn_widget! { @VideoSurface { texture: t2.clone() } });
+    wnd.draw_frame();
+    wnd.assert_root_size(Size::zero());
+
+    std::thread::spawn({
+      let texture = texture.clone();
+      move || texture.push_pixels(frame(4, 3))
+    })
+    .join()
+    .unwrap();
+    wnd.draw_frame();
+    wnd.assert_root_size(Size::new(4., 3.));
+
+    texture.push_pixels(frame(8, 6));
+    assert_eq!(painted_texture(&mut wnd), Some(Rect::from_size(Size::new(8., 6.))));
+  }
+}